@@ -0,0 +1,401 @@
+//! Typed cell values carried alongside the `DecoratedText` of a `TableView`
+//!
+//! `DecoratedText` is what gets displayed; `CellValue` is what the cell *is*.
+//! Data formatters (JSON, YAML, TOML, SQL) use the value to emit real literals —
+//! numbers, booleans, nulls, timestamps, nested arrays/objects — instead of
+//! quoting every cell as a string. Visual formatters keep rendering the text.
+//!
+//! A view built from text only has no explicit values; `TableView::cell_value()`
+//! then derives one from the cell text and the column's `DataType`, so declaring
+//! `column_types` is enough to get typed output.
+
+use crate::DataType;
+
+/// Typed value of one table cell
+///
+/// # Examples
+///
+/// ```
+/// use data_fmt::CellValue;
+///
+/// assert_eq!( CellValue::from( 30 ).to_string(), "30" );
+/// assert_eq!( CellValue::from( true ).to_string(), "true" );
+/// assert_eq!( CellValue::Timestamp( 0 ).to_string(), "1970-01-01T00:00:00Z" );
+/// assert_eq!( CellValue::from( vec![ CellValue::from( 1 ), CellValue::from( "a" ) ] ).to_string(), r#"[1,"a"]"# );
+/// ```
+#[ derive( Debug, Clone, PartialEq, Default ) ]
+#[ cfg_attr( feature = "serde_support", derive( serde::Serialize, serde::Deserialize ) ) ]
+pub enum CellValue
+{
+  /// Absent value — `null` in JSON/YAML, `NULL` in SQL, omitted in TOML
+  #[ default ]
+  Null,
+  /// Boolean
+  Bool( bool ),
+  /// Signed integer
+  Int( i64 ),
+  /// Floating point number; non-finite values are emitted as null
+  Float( f64 ),
+  /// Plain text
+  Text( String ),
+  /// Point in time as whole seconds since the Unix epoch, UTC
+  Timestamp( i64 ),
+  /// Raw bytes — base64 in text formats, a hex blob literal in SQL
+  Bytes( Vec< u8 > ),
+  /// Ordered list of nested values
+  List( Vec< CellValue > ),
+  /// Ordered map of nested values (key order is preserved on output)
+  Map( Vec< ( String, CellValue ) > ),
+}
+
+impl CellValue
+{
+  /// Column type this value belongs to, or `None` for `Null`
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use data_fmt::{ CellValue, DataType };
+  ///
+  /// assert_eq!( CellValue::from( 1.5 ).data_type(), Some( DataType::Float ) );
+  /// assert_eq!( CellValue::Null.data_type(), None );
+  /// ```
+  pub fn data_type( &self ) -> Option< DataType >
+  {
+    match self
+    {
+      Self::Null => None,
+      Self::Bool( _ ) => Some( DataType::Boolean ),
+      Self::Int( _ ) => Some( DataType::Integer ),
+      Self::Float( _ ) => Some( DataType::Float ),
+      Self::Text( _ ) => Some( DataType::String ),
+      Self::Timestamp( _ ) => Some( DataType::Timestamp ),
+      Self::Bytes( _ ) => Some( DataType::Bytes ),
+      Self::List( _ ) => Some( DataType::List ),
+      Self::Map( _ ) => Some( DataType::Map ),
+    }
+  }
+
+  /// Derive a value from cell text interpreted as `data_type`
+  ///
+  /// Text that does not parse as the requested type, or whose number would
+  /// be written back differently (`007`, `1.10`, `1E5`), stays `Text`, so a
+  /// mistyped column degrades to strings instead of losing data. Empty text in
+  /// a non-string column is `Null`. `Bytes`, `List` and `Map` have no textual
+  /// inverse and always yield `Text`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use data_fmt::{ CellValue, DataType };
+  ///
+  /// assert_eq!( CellValue::from_text( "42", &DataType::Integer ), CellValue::Int( 42 ) );
+  /// assert_eq!( CellValue::from_text( "n/a", &DataType::Integer ), CellValue::Text( "n/a".into() ) );
+  /// assert_eq!( CellValue::from_text( "007", &DataType::Integer ), CellValue::Text( "007".into() ) );
+  /// assert_eq!( CellValue::from_text( "", &DataType::Float ), CellValue::Null );
+  /// assert_eq!( CellValue::from_text( "42", &DataType::String ), CellValue::Text( "42".into() ) );
+  /// ```
+  pub fn from_text( text : &str, data_type : &DataType ) -> Self
+  {
+    let parsed = match data_type
+    {
+      DataType::String | DataType::Path => return Self::Text( text.to_string() ),
+      _ if text.is_empty() => return Self::Null,
+      DataType::Integer => parse_int_exact( text ).map( Self::Int ),
+      DataType::Float => parse_float_exact( text ).map( Self::Float ),
+      DataType::Boolean => match text
+      {
+        "true" => Some( Self::Bool( true ) ),
+        "false" => Some( Self::Bool( false ) ),
+        _ => None,
+      },
+      DataType::Timestamp => parse_timestamp( text ).map( Self::Timestamp ),
+      DataType::Bytes | DataType::List | DataType::Map => None,
+    };
+    parsed.unwrap_or_else( || Self::Text( text.to_string() ) )
+  }
+
+  /// Whether the value is a number (`Int` or `Float`)
+  pub fn is_numeric( &self ) -> bool
+  {
+    matches!( self, Self::Int( _ ) | Self::Float( _ ) )
+  }
+
+  /// Render as a JSON value (nested values inside `Display`, `TableDiff::to_json_patch`)
+  pub( crate ) fn write_json( &self, out : &mut String )
+  {
+    match self
+    {
+      Self::Text( s ) => write_json_string( out, s ),
+      Self::Timestamp( _ ) | Self::Bytes( _ ) => write_json_string( out, &self.to_string() ),
+      Self::Null => out.push_str( "null" ),
+      Self::Float( f ) if !f.is_finite() => out.push_str( "null" ),
+      Self::List( items ) =>
+      {
+        out.push( '[' );
+        for ( idx, item ) in items.iter().enumerate()
+        {
+          if idx > 0
+          {
+            out.push( ',' );
+          }
+          item.write_json( out );
+        }
+        out.push( ']' );
+      }
+      Self::Map( entries ) =>
+      {
+        out.push( '{' );
+        for ( idx, ( key, value ) ) in entries.iter().enumerate()
+        {
+          if idx > 0
+          {
+            out.push( ',' );
+          }
+          write_json_string( out, key );
+          out.push( ':' );
+          value.write_json( out );
+        }
+        out.push( '}' );
+      }
+      Self::Bool( _ ) | Self::Int( _ ) | Self::Float( _ ) => out.push_str( &self.to_string() ),
+    }
+  }
+}
+
+/// Cell text for a value: scalars in their canonical form, `Null` as empty,
+/// timestamps as RFC 3339 UTC, bytes as base64, lists/maps as compact JSON.
+impl core::fmt::Display for CellValue
+{
+  fn fmt( &self, f : &mut core::fmt::Formatter< '_ > ) -> core::fmt::Result
+  {
+    match self
+    {
+      Self::Null => Ok( () ),
+      Self::Bool( b ) => write!( f, "{b}" ),
+      Self::Int( i ) => write!( f, "{i}" ),
+      Self::Float( x ) => write!( f, "{x}" ),
+      Self::Text( s ) => f.write_str( s ),
+      Self::Timestamp( secs ) => f.write_str( &format_timestamp( *secs ) ),
+      Self::Bytes( bytes ) => f.write_str( &base64_encode( bytes ) ),
+      Self::List( _ ) | Self::Map( _ ) =>
+      {
+        let mut out = String::new();
+        self.write_json( &mut out );
+        f.write_str( &out )
+      }
+    }
+  }
+}
+
+impl From< bool > for CellValue
+{
+  fn from( v : bool ) -> Self { Self::Bool( v ) }
+}
+
+impl From< i64 > for CellValue
+{
+  fn from( v : i64 ) -> Self { Self::Int( v ) }
+}
+
+impl From< i32 > for CellValue
+{
+  fn from( v : i32 ) -> Self { Self::Int( i64::from( v ) ) }
+}
+
+impl From< u32 > for CellValue
+{
+  fn from( v : u32 ) -> Self { Self::Int( i64::from( v ) ) }
+}
+
+impl From< f64 > for CellValue
+{
+  fn from( v : f64 ) -> Self { Self::Float( v ) }
+}
+
+impl From< &str > for CellValue
+{
+  fn from( v : &str ) -> Self { Self::Text( v.to_string() ) }
+}
+
+impl From< String > for CellValue
+{
+  fn from( v : String ) -> Self { Self::Text( v ) }
+}
+
+impl From< Vec< u8 > > for CellValue
+{
+  fn from( v : Vec< u8 > ) -> Self { Self::Bytes( v ) }
+}
+
+impl From< Vec< CellValue > > for CellValue
+{
+  fn from( v : Vec< CellValue > ) -> Self { Self::List( v ) }
+}
+
+impl< T : Into< CellValue > > From< Option< T > > for CellValue
+{
+  fn from( v : Option< T > ) -> Self { v.map_or( Self::Null, Into::into ) }
+}
+
+/// Append `s` as a quoted, escaped JSON string.
+pub( crate ) fn write_json_string( out : &mut String, s : &str )
+{
+  out.push( '"' );
+  for ch in s.chars()
+  {
+    match ch
+    {
+      '"' => out.push_str( "\\\"" ),
+      '\\' => out.push_str( "\\\\" ),
+      '\n' => out.push_str( "\\n" ),
+      '\r' => out.push_str( "\\r" ),
+      '\t' => out.push_str( "\\t" ),
+      c if ( c as u32 ) < 0x20 => out.push_str( &format!( "\\u{:04x}", c as u32 ) ),
+      c => out.push( c ),
+    }
+  }
+  out.push( '"' );
+}
+
+/// Standard base64 (RFC 4648, padded).
+pub( crate ) fn base64_encode( bytes : &[ u8 ] ) -> String
+{
+  const ALPHABET : &[ u8; 64 ] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+  let mut out = String::with_capacity( bytes.len().div_ceil( 3 ) * 4 );
+  for chunk in bytes.chunks( 3 )
+  {
+    let b = [ chunk[ 0 ], chunk.get( 1 ).copied().unwrap_or( 0 ), chunk.get( 2 ).copied().unwrap_or( 0 ) ];
+    let n = ( u32::from( b[ 0 ] ) << 16 ) | ( u32::from( b[ 1 ] ) << 8 ) | u32::from( b[ 2 ] );
+    for ( idx, shift ) in [ 18, 12, 6, 0 ].into_iter().enumerate()
+    {
+      if idx <= chunk.len()
+      {
+        out.push( char::from( ALPHABET[ ( ( n >> shift ) & 0x3f ) as usize ] ) );
+      }
+      else
+      {
+        out.push( '=' );
+      }
+    }
+  }
+  out
+}
+
+/// Split Unix seconds into `( year, month, day, seconds-of-day )` (proleptic Gregorian, UTC).
+pub( crate ) fn timestamp_parts( secs : i64 ) -> ( i64, u32, u32, i64 )
+{
+  let days = secs.div_euclid( 86_400 );
+  let day_secs = secs.rem_euclid( 86_400 );
+  // Days-to-civil conversion (H. Hinnant's algorithm), era = 400 years.
+  let z = days + 719_468;
+  let era = z.div_euclid( 146_097 );
+  let doe = z.rem_euclid( 146_097 );
+  let yoe = ( doe - doe / 1460 + doe / 36_524 - doe / 146_096 ) / 365;
+  let doy = doe - ( 365 * yoe + yoe / 4 - yoe / 100 );
+  let mp = ( 5 * doy + 2 ) / 153;
+  let day = doy - ( 153 * mp + 2 ) / 5 + 1;
+  let month = if mp < 10 { mp + 3 } else { mp - 9 };
+  let year = yoe + era * 400 + i64::from( month <= 2 );
+  // Both are bounded (1..=31, 1..=12), so the narrowing is lossless.
+  #[ allow( clippy::cast_possible_truncation, clippy::cast_sign_loss ) ]
+  ( year, month as u32, day as u32, day_secs )
+}
+
+/// Render Unix seconds as RFC 3339 UTC, e.g. `2024-03-01T12:00:00Z`.
+pub( crate ) fn format_timestamp( secs : i64 ) -> String
+{
+  let ( year, month, day, day_secs ) = timestamp_parts( secs );
+  format!(
+    "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+    day_secs / 3600, day_secs % 3600 / 60, day_secs % 60
+  )
+}
+
+/// Parse an `i64` written exactly as it would be re-emitted (no sign, padding or leading zeros).
+pub( crate ) fn parse_int_exact( text : &str ) -> Option< i64 >
+{
+  text.parse::< i64 >().ok().filter( | n | n.to_string() == text )
+}
+
+/// Parse a finite decimal `f64` written exactly as `Display` or `Debug` re-emits it
+/// (`2.5`, `1.0`, `-0.25`); `1.10`, `1E5` and `inf` are rejected.
+pub( crate ) fn parse_float_exact( text : &str ) -> Option< f64 >
+{
+  if !text.bytes().any( | b | b.is_ascii_digit() )
+    || !text.bytes().all( | b | b.is_ascii_digit() || matches!( b, b'.' | b'e' | b'E' | b'+' | b'-' ) )
+  {
+    return None;
+  }
+  text.parse::< f64 >().ok()
+    .filter( | f | f.is_finite() && ( f.to_string() == text || format!( "{f:?}" ) == text ) )
+}
+
+/// Parse `YYYY-MM-DD[T| ]HH:MM:SS[.frac][Z|±HH:MM]` into Unix seconds.
+///
+/// A missing offset is read as UTC; fractional seconds are dropped.
+pub( crate ) fn parse_timestamp( text : &str ) -> Option< i64 >
+{
+  let bytes = text.as_bytes();
+  if bytes.len() < 19 || !matches!( bytes[ 10 ], b'T' | b't' | b' ' )
+  {
+    return None;
+  }
+  let num = | range : core::ops::Range< usize > | -> Option< i64 >
+  {
+    let s = text.get( range )?;
+    if s.bytes().all( | b | b.is_ascii_digit() ) { s.parse().ok() } else { None }
+  };
+  let sep_ok = bytes[ 4 ] == b'-' && bytes[ 7 ] == b'-' && bytes[ 13 ] == b':' && bytes[ 16 ] == b':';
+  if !sep_ok
+  {
+    return None;
+  }
+  let ( year, month, day ) = ( num( 0..4 )?, num( 5..7 )?, num( 8..10 )? );
+  let ( hour, minute, second ) = ( num( 11..13 )?, num( 14..16 )?, num( 17..19 )? );
+  if !( 1..=12 ).contains( &month ) || !( 1..=31 ).contains( &day ) || hour > 23 || minute > 59 || second > 60
+  {
+    return None;
+  }
+
+  let mut rest = &text[ 19.. ];
+  if let Some( frac ) = rest.strip_prefix( '.' )
+  {
+    let digits = frac.bytes().take_while( u8::is_ascii_digit ).count();
+    if digits == 0
+    {
+      return None;
+    }
+    rest = &frac[ digits.. ];
+  }
+  let offset = match rest
+  {
+    "" | "Z" | "z" => 0,
+    _ =>
+    {
+      let sign = match rest.as_bytes()[ 0 ]
+      {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+      };
+      let tz = &rest[ 1.. ];
+      if tz.len() != 5 || tz.as_bytes()[ 2 ] != b':'
+      {
+        return None;
+      }
+      let ( h, m ) : ( i64, i64 ) = ( tz[ ..2 ].parse().ok()?, tz[ 3.. ].parse().ok()? );
+      sign * ( h * 3600 + m * 60 )
+    }
+  };
+
+  // Civil-to-days conversion (inverse of `timestamp_parts`).
+  let y = if month <= 2 { year - 1 } else { year };
+  let era = y.div_euclid( 400 );
+  let yoe = y.rem_euclid( 400 );
+  let mp = if month > 2 { month - 3 } else { month + 9 };
+  let doy = ( 153 * mp + 2 ) / 5 + day - 1;
+  let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+  let days = era * 146_097 + doe - 719_468;
+  Some( days * 86_400 + hour * 3600 + minute * 60 + second - offset )
+}
@@ -28,6 +28,9 @@ pub struct ExpandedConfig
   pub heading : Option< Heading >,
   /// Optional titled rule rendered below the formatted output (`None` = no footer)
   pub footer : Option< Heading >,
+  /// Records the streaming writer buffers to size the heading/footer rule
+  /// (only consulted when a heading or footer is set)
+  pub stream_window : usize,
 }
 
 impl Default for ExpandedConfig
@@ -45,6 +48,7 @@ impl Default for ExpandedConfig
       indent_prefix : String::new(),
       heading : None,
       footer : None,
+      stream_window : crate::formatters::DEFAULT_STREAM_WINDOW,
     }
   }
 }
@@ -78,6 +82,7 @@ impl ExpandedConfig
       indent_prefix : String::new(),
       heading : None,
       footer : None,
+      stream_window : crate::formatters::DEFAULT_STREAM_WINDOW,
     }
   }
 
@@ -152,4 +157,16 @@ impl ExpandedConfig
     self.footer = Some( f );
     self
   }
+
+  /// Set how many records the streaming writer buffers before writing
+  ///
+  /// Key widths come from the headers, so records never need look-ahead; only
+  /// the heading/footer rule (sized to the widest body line) does. Without a
+  /// heading or footer every record is written as soon as it arrives.
+  #[ must_use ]
+  pub fn with_stream_window( mut self, records : usize ) -> Self
+  {
+    self.stream_window = records;
+    self
+  }
 }
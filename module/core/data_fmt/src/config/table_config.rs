@@ -51,6 +51,8 @@ pub struct TableConfig
   column_widths : Vec< usize >,
   /// Align columns right (false = left align)
   align_right : Vec< bool >,
+  /// Right-align `Integer`/`Float` columns not covered by `align_right`
+  numeric_align_right : bool,
   /// Border rendering variant
   border_variant : BorderVariant,
   /// Header separator line variant
@@ -99,6 +101,10 @@ pub struct TableConfig
   heading : Option< Heading >,
   /// Optional titled rule rendered below the table (None = no footer)
   footer : Option< Heading >,
+  /// Rows buffered by the streaming writer before column widths are locked
+  stream_window : usize,
+  /// Escape `\` as `\\` in CSV/TSV cells so `\n` escapes stay reversible
+  escape_backslashes : bool,
 }
 
 impl Default for TableConfig
@@ -109,6 +115,7 @@ impl Default for TableConfig
     {
       column_widths : Vec::new(),
       align_right : Vec::new(),
+      numeric_align_right : true,
       border_variant : BorderVariant::None,
       header_separator_variant : HeaderSeparatorVariant::Dash,
       column_separator : ColumnSeparator::Spaces( 2 ),
@@ -133,6 +140,8 @@ impl Default for TableConfig
       border_color : None,
       heading : None,
       footer : None,
+      stream_window : crate::formatters::DEFAULT_STREAM_WINDOW,
+      escape_backslashes : false,
     }
   }
 }
@@ -161,6 +170,17 @@ impl TableConfig
     self
   }
 
+  /// Right-align columns whose `DataType` is numeric (default: enabled)
+  ///
+  /// Applies to columns without an explicit `with_align_right()` entry; the
+  /// types come from the formatted view's `TableMetadata::column_types`.
+  #[ must_use ]
+  pub fn with_numeric_align_right( mut self, enabled : bool ) -> Self
+  {
+    self.numeric_align_right = enabled;
+    self
+  }
+
   // Variant preset constructors
 
   /// Plain variant: space-separated with dash separator (default)
@@ -444,6 +464,33 @@ impl TableConfig
     self.footer = Some( f );
     self
   }
+
+  /// Set how many rows the streaming writer (`StreamFormat::row_sink`) buffers
+  /// before it locks column widths and starts writing
+  ///
+  /// Later rows wider than the locked widths are truncated with the truncation
+  /// marker. A window of `0` sizes columns from the headers alone. Ignored when
+  /// explicit widths are set via `with_column_widths()` — that is the fixed-width
+  /// mode, which writes the header immediately.
+  #[ must_use ]
+  pub fn with_stream_window( mut self, rows : usize ) -> Self
+  {
+    self.stream_window = rows;
+    self
+  }
+
+  /// Enable/disable backslash escaping in CSV/TSV cells
+  ///
+  /// CSV and TSV always write an embedded newline as `\n`. By default a
+  /// backslash is written as is, so a cell holding the text `C:\new` reads back
+  /// with a newline. When enabled, `\` is written as `\\` and the parser undoes
+  /// both escapes exactly.
+  #[ must_use ]
+  pub fn with_escape_backslashes( mut self, enabled : bool ) -> Self
+  {
+    self.escape_backslashes = enabled;
+    self
+  }
 }
 
 /// Internal accessors for formatters (pub(crate) methods, not fields — satisfies AF1).
@@ -471,6 +518,12 @@ impl TableConfig
     &self.align_right
   }
 
+  /// Whether numeric columns right-align by default (accessor; distinct from `with_numeric_align_right` setter)
+  pub( crate ) fn numeric_align_right_enabled( &self ) -> bool
+  {
+    self.numeric_align_right
+  }
+
   /// Whether outer padding is enabled (accessor; distinct from `outer_padding` setter)
   pub( crate ) fn has_outer_padding( &self ) -> bool
   {
@@ -603,12 +656,33 @@ impl TableConfig
     self.heading.as_ref()
   }
 
+  /// Streaming look-ahead window in rows (accessor; distinct from `with_stream_window` setter)
+  pub( crate ) fn stream_window_size( &self ) -> usize
+  {
+    self.stream_window
+  }
+
+  /// Whether CSV/TSV cells escape backslashes (accessor; distinct from `with_escape_backslashes` setter)
+  pub( crate ) fn escapes_backslashes( &self ) -> bool
+  {
+    self.escape_backslashes
+  }
+
   /// Footer reference (accessor)
   pub( crate ) fn footer_ref( &self ) -> Option< &Heading >
   {
     self.footer.as_ref()
   }
 
+  /// Copy without heading and footer rules (the pager renders windows of a table)
+  #[ cfg( feature = "pager" ) ]
+  pub( crate ) fn without_rules( mut self ) -> Self
+  {
+    self.heading = None;
+    self.footer = None;
+    self
+  }
+
   /// Whether column separator is comma or tab (CSV/TSV mode disables padding and ANSI)
   pub( crate ) fn is_csv_or_tsv( &self ) -> bool
   {
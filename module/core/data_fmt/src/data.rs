@@ -1,6 +1,7 @@
 //! Core data structures for `data_fmt`
 
 use color_tools::DecoratedText;
+use crate::CellValue;
 
 /// Generic tree node that can hold any data type
 ///
@@ -293,6 +294,89 @@ pub enum DataType
   Boolean,
   /// File system path
   Path,
+  /// Floating point number
+  Float,
+  /// Point in time (RFC 3339 text, `CellValue::Timestamp`)
+  Timestamp,
+  /// Raw binary data (`CellValue::Bytes`)
+  Bytes,
+  /// Nested list (`CellValue::List`)
+  List,
+  /// Nested key/value map (`CellValue::Map`)
+  Map,
+}
+
+impl DataType
+{
+  /// Classify a single textual cell value
+  ///
+  /// Used by `TableView::infer_column_types()` when a table is reconstructed
+  /// from text (see `Parse`). Empty text carries no type evidence and yields
+  /// `String`.
+  ///
+  /// Paths are recognized by prefix only (`/`, `./`, `../`, `~/`, or a drive
+  /// letter such as `C:\`) — a bare `a/b` is too ambiguous to call a path.
+  /// Numbers are recognized only when re-emitting them reproduces the text, so
+  /// `007`, `1.10`, `1E5`, `+5` and integers beyond `i64` stay strings, as do
+  /// `inf`/`NaN`; timestamps must be RFC 3339 (`2024-03-01T12:00:00Z`).
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use data_fmt::DataType;
+  ///
+  /// assert_eq!( DataType::infer( "-42" ), DataType::Integer );
+  /// assert_eq!( DataType::infer( "2.5" ), DataType::Float );
+  /// assert_eq!( DataType::infer( "007" ), DataType::String );
+  /// assert_eq!( DataType::infer( "true" ), DataType::Boolean );
+  /// assert_eq!( DataType::infer( "2024-03-01T12:00:00Z" ), DataType::Timestamp );
+  /// assert_eq!( DataType::infer( "./src/lib.rs" ), DataType::Path );
+  /// assert_eq!( DataType::infer( "Alice" ), DataType::String );
+  /// ```
+  pub fn infer( text : &str ) -> Self
+  {
+    if crate::cell_value::parse_int_exact( text ).is_some()
+    {
+      Self::Integer
+    }
+    else if crate::cell_value::parse_float_exact( text ).is_some()
+    {
+      Self::Float
+    }
+    else if crate::cell_value::parse_timestamp( text ).is_some()
+    {
+      Self::Timestamp
+    }
+    else if text == "true" || text == "false"
+    {
+      Self::Boolean
+    }
+    else if Self::looks_like_path( text )
+    {
+      Self::Path
+    }
+    else
+    {
+      Self::String
+    }
+  }
+
+  /// Whether values of this type are numbers (right-aligned by `TableFormatter`)
+  pub fn is_numeric( &self ) -> bool
+  {
+    matches!( self, Self::Integer | Self::Float )
+  }
+
+  /// Prefix-based path detection used by `infer`
+  fn looks_like_path( text : &str ) -> bool
+  {
+    let bytes = text.as_bytes();
+    let has_drive = bytes.len() >= 3
+      && bytes[ 0 ].is_ascii_alphabetic()
+      && bytes[ 1 ] == b':'
+      && ( bytes[ 2 ] == b'\\' || bytes[ 2 ] == b'/' );
+    has_drive || [ "/", "./", "../", "~/" ].iter().any( | prefix | text.starts_with( prefix ) )
+  }
 }
 
 /// Metadata describing table structure and column types
@@ -346,6 +430,7 @@ impl TableMetadata
 ///     vec![ "Bob".into(), "25".into() ],
 ///   ],
 ///   row_details : vec![],
+///   values : vec![],
 /// };
 ///
 /// assert_eq!( view.metadata.column_names.len(), 2 );
@@ -361,6 +446,13 @@ pub struct TableView
   pub rows : Vec< Vec< DecoratedText > >,
   /// Optional detail line per row (parallel to `rows`)
   pub row_details : Vec< Option< DecoratedText > >,
+  /// Optional typed value per cell (parallel to `rows`; empty for text-only views)
+  ///
+  /// Set by `from_values()`, read through `cell_value()`, which falls back to
+  /// deriving a value from the cell text and `metadata.column_types` when no
+  /// explicit value is stored.
+  #[ cfg_attr( feature = "serde_support", serde( default ) ) ]
+  pub values : Vec< Vec< CellValue > >,
 }
 
 impl TableView
@@ -368,7 +460,35 @@ impl TableView
   /// Create new table view
   pub fn new( metadata : TableMetadata, rows : Vec< Vec< DecoratedText > > ) -> Self
   {
-    Self { metadata, rows, row_details : vec![] }
+    Self { metadata, rows, row_details : vec![], values : vec![] }
+  }
+
+  /// Create table view from typed values
+  ///
+  /// Cell text is each value's `Display` form and `column_types` is inferred
+  /// from the values (see `infer_column_types`).
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use data_fmt::{ TableView, CellValue, DataType };
+  ///
+  /// let view = TableView::from_values(
+  ///   vec![ "Name".into(), "Score".into() ],
+  ///   vec![ vec![ "Alice".into(), CellValue::from( 9.5 ) ] ],
+  /// );
+  ///
+  /// assert_eq!( view.rows[ 0 ][ 1 ].text, "9.5" );
+  /// assert_eq!( view.metadata.column_types, vec![ DataType::String, DataType::Float ] );
+  /// ```
+  pub fn from_values( column_names : Vec< String >, values : Vec< Vec< CellValue > > ) -> Self
+  {
+    let rows = values.iter()
+      .map( | row | row.iter().map( | value | DecoratedText::from( value.to_string() ) ).collect() )
+      .collect();
+    let mut view = Self { metadata : TableMetadata::new( column_names ), rows, row_details : vec![], values };
+    view.infer_column_types();
+    view
   }
 
   /// Create table view with per-row detail lines
@@ -380,7 +500,83 @@ impl TableView
   )
   -> Self
   {
-    Self { metadata, rows, row_details }
+    Self { metadata, rows, row_details, values : vec![] }
+  }
+
+  /// Typed value of the cell at `row`, `col`
+  ///
+  /// Returns the stored value when `values` has one, otherwise derives it with
+  /// `CellValue::from_text()` from the cell text and the column's `DataType`.
+  /// Out-of-range positions yield `CellValue::Null`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use data_fmt::{ TableView, TableMetadata, DataType, CellValue };
+  ///
+  /// let view = TableView::new(
+  ///   TableMetadata::with_types( vec![ "Age".into() ], vec![ DataType::Integer ] ),
+  ///   vec![ vec![ "30".into() ] ],
+  /// );
+  ///
+  /// assert_eq!( view.cell_value( 0, 0 ), CellValue::Int( 30 ) );
+  /// ```
+  pub fn cell_value( &self, row : usize, col : usize ) -> CellValue
+  {
+    if let Some( value ) = self.values.get( row ).and_then( | values | values.get( col ) )
+    {
+      return value.clone();
+    }
+    let data_type = self.metadata.column_types.get( col ).cloned().unwrap_or_default();
+    self.rows.get( row )
+      .and_then( | cells | cells.get( col ) )
+      .map_or( CellValue::Null, | cell | CellValue::from_text( &cell.text, &data_type ) )
   }
 
+  /// Infer `metadata.column_types` from the cells of every row
+  ///
+  /// Each cell is classified by its stored `CellValue` when there is one, else
+  /// by its text (per `DataType::infer`). A column gets a non-`String` type only
+  /// when every non-empty cell agrees on it — except that mixing `Integer` and
+  /// `Float` widens to `Float`. Columns with no non-empty cells stay `String`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use data_fmt::{ RowBuilder, DataType };
+  ///
+  /// let mut view = RowBuilder::new( vec![ "Name".into(), "Age".into() ] )
+  ///   .add_row( vec![ "Alice".into(), "30".into() ] )
+  ///   .add_row( vec![ "Bob".into(), "".into() ] )
+  ///   .build_view();
+  /// view.infer_column_types();
+  ///
+  /// assert_eq!( view.metadata.column_types, vec![ DataType::String, DataType::Integer ] );
+  /// ```
+  pub fn infer_column_types( &mut self )
+  {
+    let count = self.metadata.column_names.len();
+    self.metadata.column_types = ( 0..count ).map( | col |
+    {
+      let mut inferred : Option< DataType > = None;
+      for ( row_idx, row ) in self.rows.iter().enumerate()
+      {
+        let stored = self.values.get( row_idx ).and_then( | values | values.get( col ) );
+        let ty = match stored
+        {
+          Some( value ) => value.data_type(),
+          None => row.get( col ).filter( | cell | !cell.text.is_empty() ).map( | cell | DataType::infer( &cell.text ) ),
+        };
+        let Some( ty ) = ty else { continue };
+        inferred = match inferred
+        {
+          None => Some( ty ),
+          Some( seen ) if seen == ty => Some( seen ),
+          Some( seen ) if seen.is_numeric() && ty.is_numeric() => Some( DataType::Float ),
+          Some( _ ) => return DataType::String,
+        };
+      }
+      inferred.unwrap_or_default()
+    }).collect();
+  }
 }
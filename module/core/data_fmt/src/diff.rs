@@ -0,0 +1,629 @@
+//! Keyed row diff between two `TableView` snapshots
+//!
+//! `old.diff( &new, &[ "Host" ] )` matches rows of the two views by the
+//! values of the key columns, then classifies each row as added, removed,
+//! changed (some non-key cell differs) or unchanged. Cells compare as typed
+//! values read through `TableView::cell_value()`, so `"10"` in a `String`
+//! column differs from `10` in an `Integer` one — infer or declare column
+//! types on both views the same way before diffing.
+//!
+//! The result renders through the existing formatters: `unified()` and
+//! `side_by_side()` build coloured `TableView`s, `to_json_patch()` produces an
+//! RFC 6902 patch against `JsonFormatter` output of the old view.
+//!
+//! Columns are the union of both views — the new view's columns in order,
+//! then columns only the old view has. A cell of a column missing on one side
+//! is `Null` there.
+
+use std::collections::HashMap;
+use color_tools::{ Color, DecoratedText };
+use crate::{ TableView, TableMetadata, DataType, CellValue, Heading };
+
+/// Colour of removed rows and old values of changed cells
+const REMOVED_COLOR : Color = Color::Red;
+/// Colour of added rows and new values of changed cells
+const ADDED_COLOR : Color = Color::Green;
+
+/// Error type for `TableView::diff`
+#[ derive( thiserror::Error, Debug, Clone, PartialEq, Eq ) ]
+pub enum DiffError
+{
+  /// No key columns were given
+  #[ error( "Diff needs at least one key column" ) ]
+  EmptyKey,
+  /// A key column is missing from one of the views
+  #[ error( "Key column {column} is missing from the {side} view" ) ]
+  UnknownColumn
+  {
+    /// Key column name
+    column : String,
+    /// View that lacks the column
+    side : DiffSide,
+  },
+  /// Two rows of one view have the same key
+  #[ error( "Duplicate key ({key}) in the {side} view" ) ]
+  DuplicateKey
+  {
+    /// Key cell texts joined with `, `
+    key : String,
+    /// View with the duplicate
+    side : DiffSide,
+  },
+}
+
+/// Which of the two diffed views something refers to
+#[ derive( Debug, Clone, Copy, PartialEq, Eq ) ]
+pub enum DiffSide
+{
+  /// The view `diff` is called on (before)
+  Old,
+  /// The view passed to `diff` (after)
+  New,
+}
+
+impl core::fmt::Display for DiffSide
+{
+  fn fmt( &self, f : &mut core::fmt::Formatter< '_ > ) -> core::fmt::Result
+  {
+    f.write_str( match self
+    {
+      Self::Old => "old",
+      Self::New => "new",
+    })
+  }
+}
+
+/// Classification of one keyed row
+#[ derive( Debug, Clone, Copy, PartialEq, Eq ) ]
+pub enum RowChange
+{
+  /// Key present in both views with equal cells
+  Unchanged,
+  /// Key only in the new view
+  Added,
+  /// Key only in the old view
+  Removed,
+  /// Key present in both views, at least one cell differs
+  Changed,
+}
+
+/// One keyed row of a `TableDiff`
+///
+/// `old_values()` and `new_values()` are aligned with `TableDiff::columns()`; the side a row
+/// does not exist on is empty.
+#[ derive( Debug, Clone, PartialEq ) ]
+pub struct RowDiff
+{
+  change : RowChange,
+  key : Vec< String >,
+  old_index : Option< usize >,
+  new_index : Option< usize >,
+  old : Vec< CellValue >,
+  new : Vec< CellValue >,
+  changed : Vec< usize >,
+}
+
+impl RowDiff
+{
+  /// How the row changed
+  pub fn change( &self ) -> RowChange
+  {
+    self.change
+  }
+
+  /// Key cell texts, in key column order
+  pub fn key( &self ) -> &[ String ]
+  {
+    &self.key
+  }
+
+  /// Row index in the old view, `None` for added rows
+  pub fn old_index( &self ) -> Option< usize >
+  {
+    self.old_index
+  }
+
+  /// Row index in the new view, `None` for removed rows
+  pub fn new_index( &self ) -> Option< usize >
+  {
+    self.new_index
+  }
+
+  /// Old cell values, empty for added rows
+  pub fn old_values( &self ) -> &[ CellValue ]
+  {
+    &self.old
+  }
+
+  /// New cell values, empty for removed rows
+  pub fn new_values( &self ) -> &[ CellValue ]
+  {
+    &self.new
+  }
+
+  /// Indices (into `TableDiff::columns()`) of cells that differ; empty unless `Changed`
+  pub fn changed_columns( &self ) -> &[ usize ]
+  {
+    &self.changed
+  }
+}
+
+/// Row counts per `RowChange`
+#[ derive( Debug, Clone, Copy, PartialEq, Eq, Default ) ]
+pub struct DiffSummary
+{
+  /// Rows only in the new view
+  pub added : usize,
+  /// Rows only in the old view
+  pub removed : usize,
+  /// Rows whose cells differ
+  pub changed : usize,
+  /// Rows equal in both views
+  pub unchanged : usize,
+}
+
+/// Keyed row diff produced by `TableView::diff`
+///
+/// Rows come in new-view order; each removed row follows the nearest row
+/// that preceded it in the old view and still exists.
+///
+/// # Examples
+///
+/// ```
+/// use data_fmt::{ TableView, RowChange };
+///
+/// let old = TableView::from_values(
+///   vec![ "Host".into(), "Version".into() ],
+///   vec![
+///     vec![ "web1".into(), "1.2".into() ],
+///     vec![ "web2".into(), "1.2".into() ],
+///   ],
+/// );
+/// let new = TableView::from_values(
+///   vec![ "Host".into(), "Version".into() ],
+///   vec![
+///     vec![ "web1".into(), "1.3".into() ],
+///     vec![ "web3".into(), "1.3".into() ],
+///   ],
+/// );
+///
+/// let diff = old.diff( &new, &[ "Host" ] ).unwrap();
+/// let changes : Vec< _ > = diff.rows().iter().map( | row | ( row.key()[ 0 ].as_str(), row.change() ) ).collect();
+/// assert_eq!( changes, vec![
+///   ( "web1", RowChange::Changed ),
+///   ( "web2", RowChange::Removed ),
+///   ( "web3", RowChange::Added ),
+/// ] );
+/// assert_eq!( diff.rows()[ 0 ].changed_columns(), &[ 1 ] );
+/// ```
+#[ derive( Debug, Clone, PartialEq ) ]
+pub struct TableDiff
+{
+  columns : Vec< String >,
+  column_types : Vec< DataType >,
+  in_old : Vec< bool >,
+  in_new : Vec< bool >,
+  key : Vec< String >,
+  rows : Vec< RowDiff >,
+}
+
+impl TableDiff
+{
+  /// Union of both views' column names
+  pub fn columns( &self ) -> &[ String ]
+  {
+    &self.columns
+  }
+
+  /// Key column names, as passed to `diff`
+  pub fn key_columns( &self ) -> &[ String ]
+  {
+    &self.key
+  }
+
+  /// Keyed rows, including unchanged ones unless `changes_only` was applied
+  pub fn rows( &self ) -> &[ RowDiff ]
+  {
+    &self.rows
+  }
+
+  /// Whether no row was added, removed or changed
+  pub fn is_empty( &self ) -> bool
+  {
+    self.rows.iter().all( | row | row.change == RowChange::Unchanged )
+  }
+
+  /// Row counts per kind of change
+  pub fn summary( &self ) -> DiffSummary
+  {
+    self.rows.iter().fold( DiffSummary::default(), | mut summary, row |
+    {
+      match row.change
+      {
+        RowChange::Added => summary.added += 1,
+        RowChange::Removed => summary.removed += 1,
+        RowChange::Changed => summary.changed += 1,
+        RowChange::Unchanged => summary.unchanged += 1,
+      }
+      summary
+    })
+  }
+
+  /// Drop unchanged rows
+  #[ must_use ]
+  pub fn changes_only( mut self ) -> Self
+  {
+    self.rows.retain( | row | row.change != RowChange::Unchanged );
+    self
+  }
+
+  /// Titled rule with the counts of added, removed and changed rows
+  ///
+  /// Pass the result to a formatter's `with_heading` or `with_footer`.
+  pub fn to_heading( &self, title : impl Into< String > ) -> Heading
+  {
+    let summary = self.summary();
+    Heading::new( title )
+      .with_field( format!( "+{} added", summary.added ) )
+      .with_field( format!( "-{} removed", summary.removed ) )
+      .with_field( format!( "~{} changed", summary.changed ) )
+  }
+
+  /// Unified table: a marker column, then every column
+  ///
+  /// Added rows are marked `+` and green, removed rows `-` and red. A changed
+  /// row becomes a `-` row with its old values followed by a `+` row with its
+  /// new values; only the differing cells are coloured. Unchanged rows are
+  /// marked with a space. The marker column's header is empty.
+  pub fn unified( &self ) -> TableView
+  {
+    let mut names = vec![ String::new() ];
+    names.extend( self.columns.iter().cloned() );
+    let mut types = vec![ DataType::String ];
+    types.extend( self.column_types.iter().cloned() );
+    let mut out = DiffViewBuilder::new( TableMetadata::with_types( names, types ) );
+
+    for row in &self.rows
+    {
+      match row.change
+      {
+        RowChange::Unchanged => out.push( " ", None, &row.new, &[], None ),
+        RowChange::Added => out.push( "+", Some( ADDED_COLOR ), &row.new, &[], Some( ADDED_COLOR ) ),
+        RowChange::Removed => out.push( "-", Some( REMOVED_COLOR ), &row.old, &[], Some( REMOVED_COLOR ) ),
+        RowChange::Changed =>
+        {
+          out.push( "-", Some( REMOVED_COLOR ), &row.old, &row.changed, Some( REMOVED_COLOR ) );
+          out.push( "+", Some( ADDED_COLOR ), &row.new, &row.changed, Some( ADDED_COLOR ) );
+        }
+      }
+    }
+    out.build()
+  }
+
+  /// Side-by-side table: old columns, a marker column, new columns
+  ///
+  /// Each side has the columns that view had. Markers follow `diff -y`:
+  /// `<` removed, `>` added, `|` changed, a space for unchanged. Removed rows
+  /// are red, added rows green, and changed cells red on the old side and
+  /// green on the new side. The side a row is missing from is left blank.
+  pub fn side_by_side( &self ) -> TableView
+  {
+    let old_cols : Vec< usize > = ( 0..self.columns.len() ).filter( | &c | self.in_old[ c ] ).collect();
+    let new_cols : Vec< usize > = ( 0..self.columns.len() ).filter( | &c | self.in_new[ c ] ).collect();
+    let names = old_cols.iter().map( | &c | self.columns[ c ].clone() )
+      .chain( core::iter::once( String::new() ) )
+      .chain( new_cols.iter().map( | &c | self.columns[ c ].clone() ) )
+      .collect();
+    let types = old_cols.iter().map( | &c | self.column_types[ c ].clone() )
+      .chain( core::iter::once( DataType::String ) )
+      .chain( new_cols.iter().map( | &c | self.column_types[ c ].clone() ) )
+      .collect();
+    let mut out = DiffViewBuilder::new( TableMetadata::with_types( names, types ) );
+
+    for row in &self.rows
+    {
+      let ( marker, color, highlight ) = match row.change
+      {
+        RowChange::Unchanged => ( " ", None, &[][ .. ] ),
+        RowChange::Added => ( ">", Some( ADDED_COLOR ), &[][ .. ] ),
+        RowChange::Removed => ( "<", Some( REMOVED_COLOR ), &[][ .. ] ),
+        RowChange::Changed => ( "|", None, &row.changed[ .. ] ),
+      };
+      let side_color = | side : Color | ( row.change != RowChange::Unchanged ).then_some( side );
+      out.begin_row();
+      out.extend( &old_cols, &row.old, highlight, side_color( REMOVED_COLOR ) );
+      out.cell( CellValue::Text( marker.to_string() ), color );
+      out.extend( &new_cols, &row.new, highlight, side_color( ADDED_COLOR ) );
+    }
+    out.build()
+  }
+
+  /// RFC 6902 JSON Patch turning the old view into the new one
+  ///
+  /// The patch applies to `JsonFormatter` output of the old view (an array of
+  /// row objects keyed by column name). Operations come in this order:
+  /// cell `replace`/`add`/`remove` for changed rows, row `remove`s from the
+  /// highest index down, then row `add`s appended with `/-`. Applying it
+  /// yields the new rows, with added rows at the end. Values are typed as
+  /// `JsonFormatter` types them. One operation per line; no changes give `[]`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use data_fmt::TableView;
+  ///
+  /// let old = TableView::from_values( vec![ "Id".into(), "Qty".into() ], vec![ vec![ 1.into(), 5.into() ] ] );
+  /// let new = TableView::from_values( vec![ "Id".into(), "Qty".into() ], vec![ vec![ 1.into(), 7.into() ] ] );
+  ///
+  /// let patch = old.diff( &new, &[ "Id" ] ).unwrap().to_json_patch();
+  /// assert_eq!( patch, "[\n  {\"op\":\"replace\",\"path\":\"/0/Qty\",\"value\":7}\n]" );
+  /// ```
+  pub fn to_json_patch( &self ) -> String
+  {
+    let mut ops = Vec::new();
+
+    for row in self.rows.iter().filter( | row | row.change == RowChange::Changed )
+    {
+      let index = row.old_index.unwrap_or_default();
+      for &col in &row.changed
+      {
+        let path = format!( "/{index}/{}", pointer_escape( &self.columns[ col ] ) );
+        ops.push( if self.in_new[ col ]
+        {
+          patch_op( if self.in_old[ col ] { "replace" } else { "add" }, &path, Some( &row.new[ col ] ) )
+        }
+        else
+        {
+          patch_op( "remove", &path, None )
+        });
+      }
+    }
+
+    let mut removed : Vec< usize > = self.rows.iter()
+      .filter( | row | row.change == RowChange::Removed )
+      .filter_map( | row | row.old_index )
+      .collect();
+    removed.sort_unstable_by( | a, b | b.cmp( a ) );
+    ops.extend( removed.into_iter().map( | index | patch_op( "remove", &format!( "/{index}" ), None ) ) );
+
+    for row in self.rows.iter().filter( | row | row.change == RowChange::Added )
+    {
+      let entries = ( 0..self.columns.len() )
+        .filter( | &c | self.in_new[ c ] )
+        .map( | c | ( self.columns[ c ].clone(), row.new[ c ].clone() ) )
+        .collect();
+      ops.push( patch_op( "add", "/-", Some( &CellValue::Map( entries ) ) ) );
+    }
+
+    if ops.is_empty()
+    {
+      return "[]".to_string();
+    }
+    format!( "[\n  {}\n]", ops.join( ",\n  " ) )
+  }
+}
+
+impl TableView
+{
+  /// Keyed row diff from `self` (old) to `new`
+  ///
+  /// Rows are matched by the text of the `key` columns, which both views must
+  /// have and which must be unique within each view. See `TableDiff` for the
+  /// row order and rendering.
+  ///
+  /// # Errors
+  ///
+  /// - `DiffError::EmptyKey` if `key` is empty
+  /// - `DiffError::UnknownColumn` if a view lacks a key column
+  /// - `DiffError::DuplicateKey` if two rows of one view share a key
+  pub fn diff( &self, new : &TableView, key : &[ &str ] ) -> Result< TableDiff, DiffError >
+  {
+    if key.is_empty()
+    {
+      return Err( DiffError::EmptyKey );
+    }
+    let key_cols = | view : &TableView, side : DiffSide |
+    {
+      key.iter()
+        .map( | name | view.column_index( name ).ok_or_else( || DiffError::UnknownColumn { column : ( *name ).to_string(), side } ) )
+        .collect::< Result< Vec< _ >, _ > >()
+    };
+    let old_key = key_cols( self, DiffSide::Old )?;
+    let new_key = key_cols( new, DiffSide::New )?;
+
+    // Union of columns: new order first, then old-only columns.
+    let mut columns = new.metadata.column_names.clone();
+    let mut column_types = ( 0..columns.len() )
+      .map( | c | new.metadata.column_types.get( c ).cloned().unwrap_or_default() )
+      .collect::< Vec< _ > >();
+    for ( c, name ) in self.metadata.column_names.iter().enumerate()
+    {
+      if !columns.contains( name )
+      {
+        columns.push( name.clone() );
+        column_types.push( self.metadata.column_types.get( c ).cloned().unwrap_or_default() );
+      }
+    }
+    let old_map : Vec< Option< usize > > = columns.iter().map( | name | self.column_index( name ) ).collect();
+    let new_map : Vec< Option< usize > > = columns.iter().map( | name | new.column_index( name ) ).collect();
+
+    let old_rows = index_keys( self, &old_key, DiffSide::Old )?;
+    let new_rows = index_keys( new, &new_key, DiffSide::New )?;
+    let old_by_key : HashMap< &[ String ], usize > = old_rows.iter().enumerate().map( | ( idx, k ) | ( k.as_slice(), idx ) ).collect();
+    let new_keys : std::collections::HashSet< &[ String ] > = new_rows.iter().map( Vec::as_slice ).collect();
+
+    // Removed rows are anchored after the nearest preceding old row that survives.
+    let mut anchored : HashMap< Option< usize >, Vec< usize > > = HashMap::new();
+    let mut anchor = None;
+    for ( idx, k ) in old_rows.iter().enumerate()
+    {
+      if new_keys.contains( k.as_slice() )
+      {
+        anchor = Some( idx );
+      }
+      else
+      {
+        anchored.entry( anchor ).or_default().push( idx );
+      }
+    }
+
+    let values = | view : &TableView, map : &[ Option< usize > ], row : usize |
+    {
+      map.iter().map( | col | col.map_or( CellValue::Null, | col | view.cell_value( row, col ) ) ).collect::< Vec< _ > >()
+    };
+    let removed = | idx : usize | RowDiff
+    {
+      change : RowChange::Removed,
+      key : old_rows[ idx ].clone(),
+      old_index : Some( idx ),
+      new_index : None,
+      old : values( self, &old_map, idx ),
+      new : vec![],
+      changed : vec![],
+    };
+
+    let mut rows : Vec< RowDiff > = anchored.remove( &None ).unwrap_or_default().into_iter().map( removed ).collect();
+    for ( new_idx, k ) in new_rows.iter().enumerate()
+    {
+      let new_values = values( new, &new_map, new_idx );
+      let Some( &old_idx ) = old_by_key.get( k.as_slice() ) else
+      {
+        rows.push( RowDiff
+        {
+          change : RowChange::Added,
+          key : k.clone(),
+          old_index : None,
+          new_index : Some( new_idx ),
+          old : vec![],
+          new : new_values,
+          changed : vec![],
+        });
+        continue;
+      };
+      let old_values = values( self, &old_map, old_idx );
+      let changed : Vec< usize > = ( 0..columns.len() ).filter( | &c | old_values[ c ] != new_values[ c ] ).collect();
+      rows.push( RowDiff
+      {
+        change : if changed.is_empty() { RowChange::Unchanged } else { RowChange::Changed },
+        key : k.clone(),
+        old_index : Some( old_idx ),
+        new_index : Some( new_idx ),
+        old : old_values,
+        new : new_values,
+        changed,
+      });
+      rows.extend( anchored.remove( &Some( old_idx ) ).unwrap_or_default().into_iter().map( removed ) );
+    }
+
+    Ok( TableDiff
+    {
+      in_old : old_map.iter().map( Option::is_some ).collect(),
+      in_new : new_map.iter().map( Option::is_some ).collect(),
+      columns,
+      column_types,
+      key : key.iter().map( | name | ( *name ).to_string() ).collect(),
+      rows,
+    })
+  }
+}
+
+/// Key texts of every row, rejecting duplicates.
+fn index_keys( view : &TableView, key_cols : &[ usize ], side : DiffSide ) -> Result< Vec< Vec< String > >, DiffError >
+{
+  let keys : Vec< Vec< String > > = ( 0..view.rows.len() )
+    .map( | row | key_cols.iter().map( | &col | view.cell_value( row, col ).to_string() ).collect() )
+    .collect();
+  let mut seen = std::collections::HashSet::new();
+  if let Some( dup ) = keys.iter().find( | k | !seen.insert( k.as_slice() ) )
+  {
+    return Err( DiffError::DuplicateKey { key : dup.join( ", " ), side } );
+  }
+  Ok( keys )
+}
+
+/// Accumulates the coloured rows of a rendered diff, keeping `values` typed.
+struct DiffViewBuilder
+{
+  metadata : TableMetadata,
+  rows : Vec< Vec< DecoratedText > >,
+  values : Vec< Vec< CellValue > >,
+}
+
+impl DiffViewBuilder
+{
+  fn new( metadata : TableMetadata ) -> Self
+  {
+    Self { metadata, rows : vec![], values : vec![] }
+  }
+
+  fn begin_row( &mut self )
+  {
+    self.rows.push( vec![] );
+    self.values.push( vec![] );
+  }
+
+  fn cell( &mut self, value : CellValue, color : Option< Color > )
+  {
+    let text = DecoratedText::from( value.to_string() );
+    let text = match color
+    {
+      Some( color ) if !text.is_empty() => text.with_color_named( color ),
+      _ => text,
+    };
+    if let ( Some( row ), Some( values ) ) = ( self.rows.last_mut(), self.values.last_mut() )
+    {
+      row.push( text );
+      values.push( value );
+    }
+  }
+
+  /// Append the cells of `cols`; with `highlight` non-empty only those
+  /// columns get `color`, otherwise every cell does. Missing values are blank.
+  fn extend( &mut self, cols : &[ usize ], values : &[ CellValue ], highlight : &[ usize ], color : Option< Color > )
+  {
+    for &col in cols
+    {
+      let value = values.get( col ).cloned().unwrap_or( CellValue::Null );
+      let color = color.filter( | _ | highlight.is_empty() || highlight.contains( &col ) );
+      self.cell( value, color );
+    }
+  }
+
+  /// One unified-diff row: marker, then every column.
+  fn push( &mut self, marker : &str, marker_color : Option< Color >, values : &[ CellValue ], highlight : &[ usize ], color : Option< Color > )
+  {
+    self.begin_row();
+    self.cell( CellValue::Text( marker.to_string() ), marker_color );
+    let cols : Vec< usize > = ( 0..values.len() ).collect();
+    self.extend( &cols, values, highlight, color );
+  }
+
+  fn build( self ) -> TableView
+  {
+    let mut view = TableView::new( self.metadata, self.rows );
+    view.values = self.values;
+    view
+  }
+}
+
+/// Escape a column name as a JSON Pointer reference token (RFC 6901).
+fn pointer_escape( name : &str ) -> String
+{
+  name.replace( '~', "~0" ).replace( '/', "~1" )
+}
+
+/// One compact JSON Patch operation object.
+fn patch_op( op : &str, path : &str, value : Option< &CellValue > ) -> String
+{
+  let mut out = String::from( "{\"op\":" );
+  crate::cell_value::write_json_string( &mut out, op );
+  out.push_str( ",\"path\":" );
+  crate::cell_value::write_json_string( &mut out, path );
+  if let Some( value ) = value
+  {
+    out.push_str( ",\"value\":" );
+    value.write_json( &mut out );
+  }
+  out.push( '}' );
+  out
+}
@@ -35,7 +35,9 @@
 //! // Age:  25
 //! ```
 
-use crate::{ TreeNode, ExpandedConfig, TableView };
+use crate::{ TreeNode, ExpandedConfig, TableView, TableMetadata };
+use crate::formatters::{ FormatError, StreamFormat, RowSink };
+use crate::formatters::stream_trait::{ check_row_len, check_not_finished, write_str, flush };
 use crate::ansi_str::visual_len;
 use color_tools::DecoratedText;
 
@@ -156,87 +158,101 @@ impl ExpandedFormatter
       return self.wrap_with_heading_footer( String::new() );
     }
 
-    let max_key_width = headers.iter()
-      .map( | h | visual_len( h ) )
-      .max()
-      .unwrap_or( 0 );
+    let max_key_width = Self::max_key_width( headers );
 
     let mut output = String::with_capacity( INITIAL_CAPACITY );
 
     for ( idx, row ) in data.rows.iter().enumerate()
     {
-      let record_name = ( idx + 1 ).to_string();
+      self.format_record( &mut output, idx, headers, row, max_key_width );
+    }
 
-      if !self.config.record_separator.is_empty()
-      {
-        let record_label = if self.config.show_record_numbers { record_name.as_str() } else { "" };
-        output.push_str( &self.config.record_separator.replace( "{}", record_label ) );
-        output.push( '\n' );
-      }
-      else if idx > 0
-      {
-        output.push( '\n' );
-      }
+    self.wrap_with_heading_footer( output )
+  }
 
-      for ( key, cell ) in headers.iter().zip( row.iter() )
+  /// Widest header key — every record pads its keys to this width.
+  fn max_key_width( headers : &[ String ] ) -> usize
+  {
+    headers.iter()
+      .map( | h | visual_len( h ) )
+      .max()
+      .unwrap_or( 0 )
+  }
+
+  /// Render one record block: separator line (or blank line) then one key-value line per column.
+  ///
+  /// Shared by `format_view()` and the streaming `ExpandedRowSink`.
+  fn format_record( &self, output : &mut String, idx : usize, headers : &[ String ], row : &[ DecoratedText ], max_key_width : usize )
+  {
+    let record_name = ( idx + 1 ).to_string();
+
+    if !self.config.record_separator.is_empty()
+    {
+      let record_label = if self.config.show_record_numbers { record_name.as_str() } else { "" };
+      output.push_str( &self.config.record_separator.replace( "{}", record_label ) );
+      output.push( '\n' );
+    }
+    else if idx > 0
+    {
+      output.push( '\n' );
+    }
+
+    for ( key, cell ) in headers.iter().zip( row.iter() )
+    {
+      output.push_str( &self.config.indent_prefix );
+      // Fix(BUG-012): call .render() to get color+text+RESET, not raw .data access.
+      // Root cause: .data.as_ref().map_or("", ..) returned bare text, bypassing ANSI
+      //   color wrapping and emitting plain strings for colored DecoratedText cells.
+      // Pitfall: never access .data directly for rendering — .render() is the only
+      //   correct output path as it handles both plain and colored cell values.
+      let value = cell.render();
+      let key_width = visual_len( key );
+
+      match self.config.padding_side
       {
-        output.push_str( &self.config.indent_prefix );
-        // Fix(BUG-012): call .render() to get color+text+RESET, not raw .data access.
-        // Root cause: .data.as_ref().map_or("", ..) returned bare text, bypassing ANSI
-        //   color wrapping and emitting plain strings for colored DecoratedText cells.
-        // Pitfall: never access .data directly for rendering — .render() is the only
-        //   correct output path as it handles both plain and colored cell values.
-        let value = cell.render();
-        let key_width = visual_len( key );
-
-        match self.config.padding_side
+        crate::PaddingSide::BeforeSeparator =>
         {
-          crate::PaddingSide::BeforeSeparator =>
+          let padding_needed = max_key_width - key_width;
+
+          if self.config.colorize_keys && !self.config.key_color.is_empty()
           {
-            let padding_needed = max_key_width - key_width;
-
-            if self.config.colorize_keys && !self.config.key_color.is_empty()
-            {
-              let key_padded = format!( "{}{}", key, " ".repeat( padding_needed ) );
-              output.push_str( &DecoratedText::from( key_padded ).with_color( self.config.key_color.clone() ).render() );
-            }
-            else
-            {
-              output.push_str( key );
-              output.push_str( &" ".repeat( padding_needed ) );
-            }
-
-            output.push_str( &self.config.key_value_separator );
-            output.push_str( &value );
+            let key_padded = format!( "{}{}", key, " ".repeat( padding_needed ) );
+            output.push_str( &DecoratedText::from( key_padded ).with_color( self.config.key_color.clone() ).render() );
           }
-
-          crate::PaddingSide::AfterSeparator =>
+          else
           {
-            let padding_needed = max_key_width - key_width;
-            let sep_trimmed = self.config.key_value_separator.trim_end();
-            let sep_trailing_spaces = self.config.key_value_separator.len() - sep_trimmed.len();
-
-            if self.config.colorize_keys && !self.config.key_color.is_empty()
-            {
-              let key_with_sep = format!( "{key}{sep_trimmed}" );
-              output.push_str( &DecoratedText::from( key_with_sep ).with_color( self.config.key_color.clone() ).render() );
-            }
-            else
-            {
-              output.push_str( key );
-              output.push_str( sep_trimmed );
-            }
-
-            output.push_str( &" ".repeat( sep_trailing_spaces + padding_needed ) );
-            output.push_str( &value );
+            output.push_str( key );
+            output.push_str( &" ".repeat( padding_needed ) );
           }
+
+          output.push_str( &self.config.key_value_separator );
+          output.push_str( &value );
         }
 
-        output.push( '\n' );
+        crate::PaddingSide::AfterSeparator =>
+        {
+          let padding_needed = max_key_width - key_width;
+          let sep_trimmed = self.config.key_value_separator.trim_end();
+          let sep_trailing_spaces = self.config.key_value_separator.len() - sep_trimmed.len();
+
+          if self.config.colorize_keys && !self.config.key_color.is_empty()
+          {
+            let key_with_sep = format!( "{key}{sep_trimmed}" );
+            output.push_str( &DecoratedText::from( key_with_sep ).with_color( self.config.key_color.clone() ).render() );
+          }
+          else
+          {
+            output.push_str( key );
+            output.push_str( sep_trimmed );
+          }
+
+          output.push_str( &" ".repeat( sep_trailing_spaces + padding_needed ) );
+          output.push_str( &value );
+        }
       }
-    }
 
-    self.wrap_with_heading_footer( output )
+      output.push( '\n' );
+    }
   }
 
   /// Prepend heading and/or append footer around an already-rendered expanded body.
@@ -276,3 +292,96 @@ impl super::Format for ExpandedFormatter
   }
 }
 
+impl StreamFormat for ExpandedFormatter
+{
+  fn row_sink< 'a, W : std::io::Write + 'a >( &'a self, metadata : &TableMetadata, out : W ) -> Result< Box< dyn RowSink + 'a >, FormatError >
+  {
+    let needs_rule = self.config.heading.is_some() || self.config.footer.is_some();
+    Ok( Box::new( ExpandedRowSink
+    {
+      formatter : self,
+      headers : metadata.column_names.clone(),
+      max_key_width : Self::max_key_width( &metadata.column_names ),
+      pending : String::new(),
+      pending_records : 0,
+      rule_width : if needs_rule { None } else { Some( 0 ) },
+      out,
+      records_written : 0,
+      finished : false,
+    }))
+  }
+}
+
+/// `RowSink` behind `ExpandedFormatter::row_sink`
+struct ExpandedRowSink< 'a, W : std::io::Write >
+{
+  formatter : &'a ExpandedFormatter,
+  headers : Vec< String >,
+  max_key_width : usize,
+  /// Rendered records held back until the heading rule width is known
+  pending : String,
+  pending_records : usize,
+  /// `None` while the heading rule width is still being measured
+  rule_width : Option< usize >,
+  out : W,
+  records_written : usize,
+  finished : bool,
+}
+
+impl< W : std::io::Write > ExpandedRowSink< '_, W >
+{
+  /// Size the rule from the buffered records, write the heading and the buffer.
+  fn lock_rule_width( &mut self ) -> Result< (), FormatError >
+  {
+    let width = self.pending.lines().map( crate::ansi_str::unicode_visual_len ).max().unwrap_or( 0 );
+    let mut head = String::new();
+    crate::config::render_rule_if_present( &mut head, self.formatter.config.heading.as_ref(), width );
+    head.push_str( &core::mem::take( &mut self.pending ) );
+    self.pending_records = 0;
+    self.rule_width = Some( width );
+    write_str( &mut self.out, &head )
+  }
+}
+
+impl< W : std::io::Write > RowSink for ExpandedRowSink< '_, W >
+{
+  fn write_row_with_detail( &mut self, row : &[ DecoratedText ], _detail : Option< &DecoratedText > ) -> Result< (), FormatError >
+  {
+    check_not_finished( self.finished )?;
+    check_row_len( self.headers.len(), row.len() )?;
+    if self.headers.is_empty()
+    {
+      return Ok( () );
+    }
+
+    let mut record = String::new();
+    self.formatter.format_record( &mut record, self.records_written, &self.headers, row, self.max_key_width );
+    self.records_written += 1;
+
+    if self.rule_width.is_some()
+    {
+      return write_str( &mut self.out, &record );
+    }
+    self.pending.push_str( &record );
+    self.pending_records += 1;
+    if self.pending_records >= self.formatter.config.stream_window
+    {
+      self.lock_rule_width()?;
+    }
+    Ok( () )
+  }
+
+  fn finish( &mut self ) -> Result< (), FormatError >
+  {
+    check_not_finished( self.finished )?;
+    self.finished = true;
+    if self.rule_width.is_none()
+    {
+      self.lock_rule_width()?;
+    }
+    let mut tail = String::new();
+    crate::config::render_rule_if_present( &mut tail, self.formatter.config.footer.as_ref(), self.rule_width.unwrap_or( 0 ) );
+    write_str( &mut self.out, &tail )?;
+    flush( &mut self.out )
+  }
+}
@@ -16,6 +16,9 @@ pub enum FormatError
   /// Unsupported operation for this formatter
   #[ error( "Unsupported operation: {0}" ) ]
   UnsupportedOperation( String ),
+  /// Writing to the output sink failed (streaming via `StreamFormat`)
+  #[ error( "I/O error: {0}" ) ]
+  Io( String ),
 }
 
 /// Unified formatting interface for all output formats
@@ -49,4 +52,29 @@ pub trait Format
   /// Returns `FormatError` if formatting fails due to invalid data,
   /// serialization errors, or unsupported operations.
   fn format( &self, data : &TableView ) -> Result< String, FormatError >;
+
+  /// Format the table view to bytes
+  ///
+  /// Text formatters return the UTF-8 bytes of `format`; binary formats
+  /// (`XlsxFormatter`, `OdsFormatter`) override this and reject `format`.
+  ///
+  /// # Errors
+  ///
+  /// Same as `format`.
+  fn format_bytes( &self, data : &TableView ) -> Result< Vec< u8 >, FormatError >
+  {
+    self.format( data ).map( String::into_bytes )
+  }
+
+  /// Format a slice of `TableRow` records, one row each (see `TableView::from_rows`)
+  ///
+  /// # Errors
+  ///
+  /// Same as `format`.
+  fn format_rows< R : crate::TableRow >( &self, records : &[ R ] ) -> Result< String, FormatError >
+  where
+    Self : Sized,
+  {
+    self.format( &TableView::from_rows( records ) )
+  }
 }
@@ -28,7 +28,9 @@
 //! # }
 //! ```
 
-use crate::{ TableView, formatters::{ Format, FormatError } };
+use crate::{ TableView, TableMetadata, DataType, CellValue, formatters::{ Format, FormatError, StreamFormat, RowSink, Parse, ParseError } };
+use crate::formatters::stream_trait::{ check_row_len, check_not_finished, row_values, write_str, flush };
+use color_tools::DecoratedText;
 
 /// JSON output formatter
 ///
@@ -133,6 +135,13 @@ impl Format for JsonFormatter
   /// 3. **Easier Processing**: Downstream tools can directly access fields by
   ///    name without maintaining separate column metadata
   ///
+  /// ## Typed Values
+  ///
+  /// Keys follow column order. Each value comes from `TableView::cell_value()`:
+  /// `String` columns stay JSON strings, while typed columns (or explicit
+  /// `TableView::values`) become native numbers, booleans and `null`, nested
+  /// arrays/objects, RFC 3339 timestamp strings and base64 byte strings.
+  ///
   /// ## Not Preserving `TableView` Structure
   ///
   /// If you need the complete `TableView` structure (with metadata), serialize
@@ -147,7 +156,7 @@ impl Format for JsonFormatter
   /// and why the flat format was chosen over preserving internal structure.
   fn format( &self, data : &TableView ) -> Result< String, FormatError >
   {
-    let rows = super::table_view_to_row_maps( data );
+    let rows = super::table_view_to_typed_rows( data );
 
     if self.pretty
     {
@@ -160,3 +169,139 @@ impl Format for JsonFormatter
     .map_err( | e | FormatError::Serialization( e.to_string() ) )
   }
 }
+
+impl Parse for JsonFormatter
+{
+  /// Parse a JSON array of row objects (pretty or compact) back into a `TableView`.
+  ///
+  /// Keys keep their document order. Numbers, booleans, `null` and nested
+  /// arrays/objects are kept as typed `TableView::values` (cell text is their
+  /// `Display` form) and `column_types` is inferred from them, so JSON produced
+  /// by other tools parses too.
+  fn parse( &self, input : &str ) -> Result< TableView, ParseError >
+  {
+    let rows : Vec< super::OrderedRow > = serde_json::from_str( input )
+      .map_err( | e | ParseError::Deserialization( e.to_string() ) )?;
+    Ok( super::row_maps_to_table_view( rows ) )
+  }
+}
+
+impl StreamFormat for JsonFormatter
+{
+  /// Open a sink writing one JSON object per row as soon as it arrives.
+  ///
+  /// Output is byte-identical to `Format::format()`: keys in column order,
+  /// values typed the same way, and pretty mode using the same two-space
+  /// layout as `serde_json::to_string_pretty`.
+  fn row_sink< 'a, W : std::io::Write + 'a >( &'a self, metadata : &TableMetadata, out : W ) -> Result< Box< dyn RowSink + 'a >, FormatError >
+  {
+    // Keys are escaped once up front; every row reuses them.
+    let keys = metadata.column_names
+      .iter()
+      .map( | name | serde_json::to_string( name ).map_err( | e | FormatError::Serialization( e.to_string() ) ) )
+      .collect::< Result< Vec< _ >, _ > >()?;
+    let column_types = metadata.column_types.clone();
+    Ok( Box::new( JsonRowSink { pretty : self.pretty, keys, column_types, out, rows_written : 0, finished : false } ) )
+  }
+}
+
+/// `RowSink` behind `JsonFormatter::row_sink`
+struct JsonRowSink< W : std::io::Write >
+{
+  pretty : bool,
+  keys : Vec< String >,
+  column_types : Vec< DataType >,
+  out : W,
+  rows_written : usize,
+  finished : bool,
+}
+
+impl< W : std::io::Write > JsonRowSink< W >
+{
+  /// Write one row object, typing cells from `values` or the column types.
+  fn write_typed( &mut self, row : &[ DecoratedText ], values : Option< &[ CellValue ] > ) -> Result< (), FormatError >
+  {
+    check_not_finished( self.finished )?;
+    check_row_len( self.keys.len(), row.len() )?;
+    if let Some( values ) = values
+    {
+      check_row_len( self.keys.len(), values.len() )?;
+    }
+    let values = row_values( row, values, &self.column_types );
+    let mut chunk = String::new();
+    chunk.push_str( if self.rows_written == 0 { "[" } else { "," } );
+    if self.pretty
+    {
+      chunk.push_str( "\n  {" );
+    }
+    else
+    {
+      chunk.push( '{' );
+    }
+    for ( idx, ( key, cell ) ) in self.keys.iter().zip( &values ).enumerate()
+    {
+      if idx > 0
+      {
+        chunk.push( ',' );
+      }
+      let value = if self.pretty
+      {
+        // Nested arrays/objects span lines; indent them to the object's depth.
+        serde_json::to_string_pretty( &super::PlainValue( cell ) ).map( | v | v.replace( '\n', "\n    " ) )
+      }
+      else
+      {
+        serde_json::to_string( &super::PlainValue( cell ) )
+      }
+      .map_err( | e | FormatError::Serialization( e.to_string() ) )?;
+      if self.pretty
+      {
+        chunk.push_str( "\n    " );
+        chunk.push_str( key );
+        chunk.push_str( ": " );
+      }
+      else
+      {
+        chunk.push_str( key );
+        chunk.push( ':' );
+      }
+      chunk.push_str( &value );
+    }
+    if self.pretty && !self.keys.is_empty()
+    {
+      chunk.push_str( "\n  " );
+    }
+    chunk.push( '}' );
+
+    write_str( &mut self.out, &chunk )?;
+    self.rows_written += 1;
+    Ok( () )
+  }
+}
+
+impl< W : std::io::Write > RowSink for JsonRowSink< W >
+{
+  fn write_row_with_detail( &mut self, row : &[ DecoratedText ], _detail : Option< &DecoratedText > ) -> Result< (), FormatError >
+  {
+    self.write_typed( row, None )
+  }
+
+  fn write_row_with_values( &mut self, row : &[ DecoratedText ], _detail : Option< &DecoratedText >, values : &[ CellValue ] ) -> Result< (), FormatError >
+  {
+    self.write_typed( row, Some( values ) )
+  }
+
+  fn finish( &mut self ) -> Result< (), FormatError >
+  {
+    check_not_finished( self.finished )?;
+    self.finished = true;
+    let closing = match ( self.rows_written, self.pretty )
+    {
+      ( 0, _ ) => "[]",
+      ( _, true ) => "\n]",
+      ( _, false ) => "]",
+    };
+    write_str( &mut self.out, closing )?;
+    flush( &mut self.out )
+  }
+}
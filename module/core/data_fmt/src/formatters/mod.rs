@@ -4,6 +4,14 @@
 mod format_trait;
 pub use format_trait::{ Format, FormatError };
 
+// Streaming counterpart of `Format` (always available)
+mod stream_trait;
+pub use stream_trait::{ StreamFormat, RowSink, DEFAULT_STREAM_WINDOW };
+
+// Inverse of `Format` (always available; implemented by readable formats only)
+mod parse_trait;
+pub use parse_trait::{ Parse, ParseError };
+
 // Visual formatters (feature-gated)
 #[ cfg( any(
   feature = "table_plain",
@@ -87,29 +95,223 @@ mod sql;
   feature = "sql_mysql",
   feature = "sql_sqlite"
 ) ) ]
-pub use sql::{ SqlFormatter, SqlVariant };
+pub use sql::{ SqlFormatter, SqlVariant, DEFAULT_SQL_BATCH_SIZE };
 
 // Data serialization formatters (feature-gated)
 
-/// Build a `Vec<HashMap<String, String>>` from `TableView` by zipping column names
-/// with row cell text — shared by json, yaml, and toml formatters.
-#[ cfg( any( feature = "format_json", feature = "format_yaml", feature = "format_toml" ) ) ]
-fn table_view_to_row_maps( data : &crate::TableView ) -> Vec< std::collections::HashMap< String, String > >
+/// One output row: `( column name, typed value )` pairs in column order —
+/// shared by the json and yaml formatters.
+///
+/// Serialized as a map whose keys keep column order, with each value emitted
+/// through `PlainValue` so numbers, booleans, nulls and nested values become
+/// native literals rather than strings.
+#[ cfg( any( feature = "format_json", feature = "format_yaml" ) ) ]
+struct TypedRow< 'a >( Vec< ( &'a str, crate::CellValue ) > );
+
+/// Serialize a `CellValue` as the plain data it holds (no enum tagging).
+///
+/// Non-finite floats become null, timestamps RFC 3339 strings, bytes base64 strings.
+#[ cfg( any( feature = "format_json", feature = "format_yaml" ) ) ]
+struct PlainValue< 'a >( &'a crate::CellValue );
+
+#[ cfg( any( feature = "format_json", feature = "format_yaml" ) ) ]
+impl serde::Serialize for TypedRow< '_ >
+{
+  fn serialize< S : serde::Serializer >( &self, serializer : S ) -> Result< S::Ok, S::Error >
+  {
+    use serde::ser::SerializeMap;
+    let mut map = serializer.serialize_map( Some( self.0.len() ) )?;
+    for ( key, value ) in &self.0
+    {
+      map.serialize_entry( key, &PlainValue( value ) )?;
+    }
+    map.end()
+  }
+}
+
+#[ cfg( any( feature = "format_json", feature = "format_yaml" ) ) ]
+impl serde::Serialize for PlainValue< '_ >
+{
+  fn serialize< S : serde::Serializer >( &self, serializer : S ) -> Result< S::Ok, S::Error >
+  {
+    use serde::ser::{ SerializeMap, SerializeSeq };
+    use crate::CellValue;
+    match self.0
+    {
+      CellValue::Bool( b ) => serializer.serialize_bool( *b ),
+      CellValue::Int( i ) => serializer.serialize_i64( *i ),
+      CellValue::Float( f ) if f.is_finite() => serializer.serialize_f64( *f ),
+      CellValue::Null | CellValue::Float( _ ) => serializer.serialize_none(),
+      CellValue::Text( s ) => serializer.serialize_str( s ),
+      CellValue::Timestamp( _ ) | CellValue::Bytes( _ ) => serializer.serialize_str( &self.0.to_string() ),
+      CellValue::List( items ) =>
+      {
+        let mut seq = serializer.serialize_seq( Some( items.len() ) )?;
+        for item in items
+        {
+          seq.serialize_element( &PlainValue( item ) )?;
+        }
+        seq.end()
+      }
+      CellValue::Map( entries ) =>
+      {
+        let mut map = serializer.serialize_map( Some( entries.len() ) )?;
+        for ( key, value ) in entries
+        {
+          map.serialize_entry( key, &PlainValue( value ) )?;
+        }
+        map.end()
+      }
+    }
+  }
+}
+
+/// Build one `TypedRow` per `TableView` row via `TableView::cell_value()` —
+/// shared by json and yaml formatters.
+#[ cfg( any( feature = "format_json", feature = "format_yaml" ) ) ]
+fn table_view_to_typed_rows( data : &crate::TableView ) -> Vec< TypedRow< '_ > >
 {
   let column_names = &data.metadata.column_names;
-  data.rows
-    .iter()
+  ( 0..data.rows.len() )
     .map( | row |
     {
-      column_names
+      TypedRow( column_names
         .iter()
-        .zip( row.iter() )
-        .map( | ( name, value ) | ( name.clone(), value.text.clone() ) )
-        .collect()
+        .enumerate()
+        .map( | ( col, name ) | ( name.as_str(), data.cell_value( row, col ) ) )
+        .collect() )
     })
     .collect()
 }
 
+/// One parsed row object with keys kept in document order.
+///
+/// `serde_json::Value`/`serde_yaml_ng::Value` maps don't preserve insertion order
+/// without extra features, and column order is part of a `TableView`, so JSON and
+/// YAML parsing deserialize rows through this instead.
+#[ cfg( any( feature = "format_json", feature = "format_yaml" ) ) ]
+struct OrderedRow( Vec< ( String, ParsedValue ) > );
+
+/// A cell value read back from a JSON/YAML document: the inverse of `PlainValue`.
+///
+/// Strings stay `Text` (no timestamp/bytes guessing), integers outside `i64`
+/// range become `Float`, nested sequences and maps become `List`/`Map`.
+#[ cfg( any( feature = "format_json", feature = "format_yaml" ) ) ]
+struct ParsedValue( crate::CellValue );
+
+#[ cfg( any( feature = "format_json", feature = "format_yaml" ) ) ]
+impl< 'de > serde::Deserialize< 'de > for OrderedRow
+{
+  fn deserialize< D : serde::Deserializer< 'de > >( deserializer : D ) -> Result< Self, D::Error >
+  {
+    struct RowVisitor;
+    impl< 'de > serde::de::Visitor< 'de > for RowVisitor
+    {
+      type Value = OrderedRow;
+      fn expecting( &self, f : &mut std::fmt::Formatter< '_ > ) -> std::fmt::Result
+      {
+        f.write_str( "a row object mapping column names to values" )
+      }
+      fn visit_map< A : serde::de::MapAccess< 'de > >( self, mut map : A ) -> Result< OrderedRow, A::Error >
+      {
+        let mut entries = Vec::new();
+        while let Some( entry ) = map.next_entry::< String, ParsedValue >()?
+        {
+          entries.push( entry );
+        }
+        Ok( OrderedRow( entries ) )
+      }
+    }
+    deserializer.deserialize_map( RowVisitor )
+  }
+}
+
+#[ cfg( any( feature = "format_json", feature = "format_yaml" ) ) ]
+impl< 'de > serde::Deserialize< 'de > for ParsedValue
+{
+  fn deserialize< D : serde::Deserializer< 'de > >( deserializer : D ) -> Result< Self, D::Error >
+  {
+    use crate::CellValue;
+    struct ValueVisitor;
+    impl< 'de > serde::de::Visitor< 'de > for ValueVisitor
+    {
+      type Value = ParsedValue;
+      fn expecting( &self, f : &mut std::fmt::Formatter< '_ > ) -> std::fmt::Result
+      {
+        f.write_str( "a string, number, boolean, null, sequence or map" )
+      }
+      fn visit_str< E : serde::de::Error >( self, v : &str ) -> Result< ParsedValue, E > { Ok( ParsedValue( CellValue::Text( v.to_string() ) ) ) }
+      fn visit_string< E : serde::de::Error >( self, v : String ) -> Result< ParsedValue, E > { Ok( ParsedValue( CellValue::Text( v ) ) ) }
+      fn visit_bool< E : serde::de::Error >( self, v : bool ) -> Result< ParsedValue, E > { Ok( ParsedValue( CellValue::Bool( v ) ) ) }
+      fn visit_i64< E : serde::de::Error >( self, v : i64 ) -> Result< ParsedValue, E > { Ok( ParsedValue( CellValue::Int( v ) ) ) }
+      // Precision loss above 2^53 is accepted: the value doesn't fit `Int` anyway.
+      #[ allow( clippy::cast_precision_loss ) ]
+      fn visit_u64< E : serde::de::Error >( self, v : u64 ) -> Result< ParsedValue, E >
+      {
+        Ok( ParsedValue( i64::try_from( v ).map_or( CellValue::Float( v as f64 ), CellValue::Int ) ) )
+      }
+      fn visit_f64< E : serde::de::Error >( self, v : f64 ) -> Result< ParsedValue, E > { Ok( ParsedValue( CellValue::Float( v ) ) ) }
+      fn visit_unit< E : serde::de::Error >( self ) -> Result< ParsedValue, E > { Ok( ParsedValue( CellValue::Null ) ) }
+      fn visit_none< E : serde::de::Error >( self ) -> Result< ParsedValue, E > { Ok( ParsedValue( CellValue::Null ) ) }
+      fn visit_seq< A : serde::de::SeqAccess< 'de > >( self, mut seq : A ) -> Result< ParsedValue, A::Error >
+      {
+        let mut items = Vec::new();
+        while let Some( ParsedValue( item ) ) = seq.next_element()?
+        {
+          items.push( item );
+        }
+        Ok( ParsedValue( CellValue::List( items ) ) )
+      }
+      fn visit_map< A : serde::de::MapAccess< 'de > >( self, mut map : A ) -> Result< ParsedValue, A::Error >
+      {
+        let mut entries = Vec::new();
+        while let Some( ( key, ParsedValue( value ) ) ) = map.next_entry::< String, ParsedValue >()?
+        {
+          entries.push( ( key, value ) );
+        }
+        Ok( ParsedValue( CellValue::Map( entries ) ) )
+      }
+    }
+    deserializer.deserialize_any( ValueVisitor )
+  }
+}
+
+/// Inverse of `table_view_to_typed_rows` — shared by json and yaml parsers.
+///
+/// Columns are the union of all row keys in first-seen order; a row missing a
+/// key gets a null cell there. The parsed values are kept in `TableView::values`
+/// and `column_types` is inferred from them.
+#[ cfg( any( feature = "format_json", feature = "format_yaml" ) ) ]
+fn row_maps_to_table_view( rows : Vec< OrderedRow > ) -> crate::TableView
+{
+  let mut column_names : Vec< String > = Vec::new();
+  for OrderedRow( entries ) in &rows
+  {
+    for ( key, _ ) in entries
+    {
+      if !column_names.contains( key )
+      {
+        column_names.push( key.clone() );
+      }
+    }
+  }
+
+  let values = rows.into_iter().map( | OrderedRow( entries ) |
+  {
+    let mut row = vec![ crate::CellValue::Null; column_names.len() ];
+    for ( key, ParsedValue( value ) ) in entries
+    {
+      if let Some( idx ) = column_names.iter().position( | name | *name == key )
+      {
+        row[ idx ] = value;
+      }
+    }
+    row
+  }).collect();
+
+  crate::TableView::from_values( column_names, values )
+}
+
 #[ cfg( feature = "format_json" ) ]
 mod json;
 #[ cfg( feature = "format_json" ) ]
@@ -130,3 +332,10 @@ mod text;
 #[ cfg( feature = "format_text" ) ]
 pub use text::{ TextFormatter, TextVariant };
 
+// Binary spreadsheet formatters (feature-gated)
+#[ cfg( any( feature = "format_xlsx", feature = "format_ods" ) ) ]
+mod spreadsheet;
+#[ cfg( feature = "format_xlsx" ) ]
+pub use spreadsheet::XlsxFormatter;
+#[ cfg( feature = "format_ods" ) ]
+pub use spreadsheet::OdsFormatter;
@@ -0,0 +1,68 @@
+//! Inverse of the `Format` trait — reconstruct `TableView` from formatter output
+
+use crate::TableView;
+
+/// Error type for parsing operations
+#[ derive( thiserror::Error, Debug ) ]
+pub enum ParseError
+{
+  /// Malformed input at a specific (1-based) line
+  #[ error( "Syntax error at line {line}: {message}" ) ]
+  Syntax
+  {
+    /// 1-based line number where the problem was detected
+    line : usize,
+    /// Human-readable description of the problem
+    message : String,
+  },
+  /// Deserialization error from a data format (requires `serde_support` feature)
+  #[ cfg( feature = "serde_support" ) ]
+  #[ error( "Deserialization error: {0}" ) ]
+  Deserialization( String ),
+  /// The formatter's configuration produces output that cannot be read back
+  #[ error( "Unsupported operation: {0}" ) ]
+  UnsupportedOperation( String ),
+}
+
+/// Unified parsing interface — the inverse of `Format`
+///
+/// Implemented by the formatters whose output is unambiguous enough to be read
+/// back: `TableFormatter` in CSV, TSV and Markdown mode, `JsonFormatter` and
+/// `YamlFormatter`. Parsing uses the same formatter configuration that produced
+/// the text, so a round trip is `formatter.parse( &formatter.format( &view )? )`.
+///
+/// Parsed views carry `TableMetadata::column_types` inferred from the cell text
+/// (see `TableView::infer_column_types`); JSON and YAML keep the document's own
+/// typed scalars in `TableView::values` and infer from those. Cell colors,
+/// heading/footer rules and sub-row detail lines are presentation and are not
+/// reconstructed.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "table_csv")]
+/// # {
+/// use data_fmt::{ RowBuilder, TableFormatter, TableConfig, Format, Parse, DataType };
+///
+/// let view = RowBuilder::new( vec![ "Name".into(), "Age".into() ] )
+///   .add_row( vec![ "Alice".into(), "30".into() ] )
+///   .build_view();
+///
+/// let formatter = TableFormatter::with_config( TableConfig::csv() );
+/// let parsed = formatter.parse( &formatter.format( &view ).unwrap() ).unwrap();
+///
+/// assert_eq!( parsed.metadata.column_names, vec![ "Name", "Age" ] );
+/// assert_eq!( parsed.metadata.column_types, vec![ DataType::String, DataType::Integer ] );
+/// assert_eq!( parsed.rows[ 0 ][ 0 ].text, "Alice" );
+/// # }
+/// ```
+pub trait Parse
+{
+  /// Parse formatted text back into a table view
+  ///
+  /// # Errors
+  ///
+  /// Returns `ParseError` if the input is malformed for this format, or the
+  /// formatter's configuration has no readable inverse.
+  fn parse( &self, input : &str ) -> Result< TableView, ParseError >;
+}
@@ -0,0 +1,410 @@
+//! Spreadsheet formatters — XLSX (Office Open XML) and ODS (`OpenDocument`)
+//!
+//! Both formatters lay a `TableView` out as the same one-sheet grid (see
+//! `Sheet`) and differ only in how they serialize it. The grid is:
+//!
+//! 1. an optional title row from `heading`, bold and merged across all columns
+//! 2. the header row, bold (plus the theme header colour), frozen on scroll
+//! 3. one row per table row, typed from `TableView::cell_value()`
+//! 4. an optional footer row from `footer`, italic and merged
+//!
+//! Cell colours come from ANSI SGR sequences — `DecoratedText::color`, the
+//! theme header colour and the alternating row colours — translated to RGB
+//! font and fill colours. The resulting archive is a stored (uncompressed)
+//! ZIP produced by `zip::ZipWriter`, so no compression dependency is needed.
+
+mod zip;
+pub( crate ) use zip::ZipWriter;
+
+#[ cfg( feature = "format_xlsx" ) ]
+mod xlsx;
+#[ cfg( feature = "format_xlsx" ) ]
+pub use xlsx::XlsxFormatter;
+
+#[ cfg( feature = "format_ods" ) ]
+mod ods;
+#[ cfg( feature = "format_ods" ) ]
+pub use ods::OdsFormatter;
+
+use crate::{ TableView, Heading, CellValue };
+use color_tools::DecoratedText;
+
+/// Sheet name used when none is configured or the configured one sanitizes to nothing.
+pub( crate ) const DEFAULT_SHEET_NAME : &str = "Sheet1";
+
+/// Column widths are clamped to this range (in characters).
+const MIN_COLUMN_WIDTH : usize = 8;
+const MAX_COLUMN_WIDTH : usize = 60;
+
+/// Dim (SGR 2) has no spreadsheet equivalent; it becomes this gray font colour.
+const DIM_COLOR : [ u8; 3 ] = [ 0x80, 0x80, 0x80 ];
+
+/// Visual style of one cell, deduplicated in `Sheet::styles`
+#[ derive( Debug, Clone, Default, PartialEq, Eq ) ]
+pub( crate ) struct CellStyle
+{
+  pub( crate ) bold : bool,
+  pub( crate ) italic : bool,
+  /// Font colour as RGB
+  pub( crate ) color : Option< [ u8; 3 ] >,
+  /// Background fill as RGB
+  pub( crate ) fill : Option< [ u8; 3 ] >,
+  /// Cell holds a timestamp and needs a date-time number format
+  pub( crate ) date : bool,
+}
+
+impl CellStyle
+{
+  /// Apply every SGR sequence (`ESC [ params m`) found in `sgr`.
+  ///
+  /// Understands reset, bold, dim, italic, their resets, the 8/16 basic
+  /// colours, 256-colour (`38;5;n`) and true-colour (`38;2;r;g;b`) forms for
+  /// both foreground and background. Other parameters are ignored.
+  pub( crate ) fn apply_sgr( &mut self, sgr : &str )
+  {
+    for sequence in sgr.split( '\x1b' ).filter_map( | part | part.strip_prefix( '[' )?.strip_suffix( 'm' ) )
+    {
+      let params : Vec< u16 > = if sequence.is_empty()
+      {
+        vec![ 0 ]
+      }
+      else
+      {
+        sequence.split( ';' ).map( | p | p.parse().unwrap_or( u16::MAX ) ).collect()
+      };
+      let mut i = 0;
+      while i < params.len()
+      {
+        match params[ i ]
+        {
+          0 =>
+          {
+            let date = self.date;
+            *self = Self { date, ..Self::default() };
+          }
+          1 => self.bold = true,
+          2 => self.color = self.color.or( Some( DIM_COLOR ) ),
+          3 => self.italic = true,
+          22 => self.bold = false,
+          23 => self.italic = false,
+          code @ 30..=37 => self.color = Some( PALETTE[ usize::from( code - 30 ) ] ),
+          code @ 90..=97 => self.color = Some( PALETTE[ usize::from( code - 90 + 8 ) ] ),
+          code @ 40..=47 => self.fill = Some( PALETTE[ usize::from( code - 40 ) ] ),
+          code @ 100..=107 => self.fill = Some( PALETTE[ usize::from( code - 100 + 8 ) ] ),
+          39 => self.color = None,
+          49 => self.fill = None,
+          code @ ( 38 | 48 ) =>
+          {
+            let ( rgb, used ) = extended_color( &params[ i + 1.. ] );
+            if let Some( rgb ) = rgb
+            {
+              if code == 38 { self.color = Some( rgb ); } else { self.fill = Some( rgb ); }
+            }
+            i += used;
+          }
+          _ => {}
+        }
+        i += 1;
+      }
+    }
+  }
+}
+
+/// Parse the tail of a `38;…`/`48;…` parameter list: `5;n` or `2;r;g;b`.
+///
+/// Returns the colour (if well-formed) and how many parameters were consumed.
+fn extended_color( params : &[ u16 ] ) -> ( Option< [ u8; 3 ] >, usize )
+{
+  let byte = | idx : usize | params.get( idx ).and_then( | &p | u8::try_from( p ).ok() );
+  match params.first()
+  {
+    Some( 5 ) => ( byte( 1 ).map( xterm_color ), 2 ),
+    Some( 2 ) =>
+    {
+      let rgb = byte( 1 ).zip( byte( 2 ) ).zip( byte( 3 ) ).map( | ( ( r, g ), b ) | [ r, g, b ] );
+      ( rgb, 4 )
+    }
+    _ => ( None, 0 ),
+  }
+}
+
+/// The 16 basic terminal colours, as xterm renders them.
+const PALETTE : [ [ u8; 3 ]; 16 ] =
+[
+  [ 0x00, 0x00, 0x00 ], [ 0xcd, 0x00, 0x00 ], [ 0x00, 0xcd, 0x00 ], [ 0xcd, 0xcd, 0x00 ],
+  [ 0x00, 0x00, 0xee ], [ 0xcd, 0x00, 0xcd ], [ 0x00, 0xcd, 0xcd ], [ 0xe5, 0xe5, 0xe5 ],
+  [ 0x7f, 0x7f, 0x7f ], [ 0xff, 0x00, 0x00 ], [ 0x00, 0xff, 0x00 ], [ 0xff, 0xff, 0x00 ],
+  [ 0x5c, 0x5c, 0xff ], [ 0xff, 0x00, 0xff ], [ 0x00, 0xff, 0xff ], [ 0xff, 0xff, 0xff ],
+];
+
+/// RGB of an xterm 256-colour index: 16 basic colours, a 6×6×6 cube, 24 grays.
+fn xterm_color( n : u8 ) -> [ u8; 3 ]
+{
+  const LEVELS : [ u8; 6 ] = [ 0, 95, 135, 175, 215, 255 ];
+  match n
+  {
+    0..=15 => PALETTE[ usize::from( n ) ],
+    16..=231 =>
+    {
+      let n = usize::from( n - 16 );
+      [ LEVELS[ n / 36 ], LEVELS[ n / 6 % 6 ], LEVELS[ n % 6 ] ]
+    }
+    _ =>
+    {
+      let gray = 8 + 10 * ( n - 232 );
+      [ gray, gray, gray ]
+    }
+  }
+}
+
+/// Typed content of one sheet cell
+#[ derive( Debug, Clone, PartialEq ) ]
+pub( crate ) enum SheetValue
+{
+  /// No value; the cell is only written when it carries a style
+  Empty,
+  /// Number literal, already rendered in a form both XML formats accept
+  Number( String ),
+  Bool( bool ),
+  /// Unix seconds, UTC
+  Date( i64 ),
+  /// Plain text with ANSI sequences removed
+  Text( String ),
+}
+
+impl SheetValue
+{
+  fn from_cell( value : CellValue ) -> Self
+  {
+    match value
+    {
+      CellValue::Bool( b ) => Self::Bool( b ),
+      CellValue::Int( i ) => Self::Number( i.to_string() ),
+      // `{:?}` never uses locale separators and keeps `1.0` distinguishable from `1`.
+      CellValue::Float( f ) if f.is_finite() => Self::Number( format!( "{f:?}" ) ),
+      CellValue::Null | CellValue::Float( _ ) => Self::Empty,
+      CellValue::Timestamp( secs ) => Self::Date( secs ),
+      CellValue::Text( text ) => Self::Text( clean_text( &text ) ),
+      other => Self::Text( clean_text( &other.to_string() ) ),
+    }
+  }
+
+  /// Text shown for this value, used to size columns
+  fn display_width( &self ) -> usize
+  {
+    match self
+    {
+      Self::Empty => 0,
+      Self::Number( n ) => n.len(),
+      Self::Bool( b ) => if *b { 4 } else { 5 },
+      Self::Date( _ ) => 19,
+      Self::Text( t ) => t.lines().map( crate::ansi_str::unicode_visual_len ).max().unwrap_or( 0 ),
+    }
+  }
+}
+
+/// One cell of the laid-out sheet
+#[ derive( Debug, Clone ) ]
+pub( crate ) struct SheetCell
+{
+  pub( crate ) value : SheetValue,
+  /// Index into `Sheet::styles`; `0` is the default style
+  pub( crate ) style : usize,
+}
+
+/// Spreadsheet-independent layout shared by the XLSX and ODS writers
+#[ derive( Debug ) ]
+pub( crate ) struct Sheet
+{
+  pub( crate ) name : String,
+  pub( crate ) rows : Vec< Vec< SheetCell > >,
+  /// Distinct cell styles; `styles[ 0 ]` is always the default style
+  pub( crate ) styles : Vec< CellStyle >,
+  /// Rows whose single cell spans all `columns` (title and footer)
+  pub( crate ) spanned : Vec< usize >,
+  /// Index of the column header row
+  pub( crate ) header_row : usize,
+  pub( crate ) columns : usize,
+  /// Column widths in characters
+  pub( crate ) widths : Vec< usize >,
+}
+
+/// Formatter settings that shape a `Sheet`
+pub( crate ) struct Layout< 'a >
+{
+  pub( crate ) sheet_name : &'a str,
+  pub( crate ) heading : Option< &'a Heading >,
+  pub( crate ) footer : Option< &'a Heading >,
+  pub( crate ) header_color : Option< &'a str >,
+  pub( crate ) row_colors : Option< ( &'a str, &'a str ) >,
+}
+
+impl Sheet
+{
+  pub( crate ) fn build( data : &TableView, layout : &Layout< '_ > ) -> Self
+  {
+    let columns = data.metadata.column_names.len();
+    let mut sheet = Self
+    {
+      name : sanitize_sheet_name( layout.sheet_name ),
+      rows : Vec::with_capacity( data.rows.len() + 3 ),
+      styles : vec![ CellStyle::default() ],
+      spanned : Vec::new(),
+      header_row : 0,
+      columns,
+      widths : vec![ 0; columns ],
+    };
+
+    if let Some( heading ) = layout.heading
+    {
+      let style = CellStyle { bold : true, ..CellStyle::default() };
+      sheet.push_spanned( heading, style );
+    }
+
+    let mut header_style = CellStyle { bold : true, ..CellStyle::default() };
+    if let Some( color ) = layout.header_color
+    {
+      header_style.apply_sgr( color );
+    }
+    let header_style = sheet.intern( header_style );
+    sheet.header_row = sheet.rows.len();
+    let header = data.metadata.column_names.iter()
+      .map( | name | SheetCell { value : SheetValue::Text( clean_text( name ) ), style : header_style } )
+      .collect();
+    sheet.push_row( header );
+
+    for ( row_idx, cells ) in data.rows.iter().enumerate()
+    {
+      let mut row_style = CellStyle::default();
+      if let Some( ( even, odd ) ) = layout.row_colors
+      {
+        row_style.apply_sgr( if row_idx % 2 == 0 { even } else { odd } );
+      }
+      let row = ( 0..columns ).map( | col |
+      {
+        let value = SheetValue::from_cell( data.cell_value( row_idx, col ) );
+        let mut style = row_style.clone();
+        if let Some( cell ) = cells.get( col )
+        {
+          apply_decoration( &mut style, cell );
+        }
+        style.date = matches!( value, SheetValue::Date( _ ) );
+        SheetCell { value, style : sheet.intern( style ) }
+      }).collect();
+      sheet.push_row( row );
+    }
+
+    if let Some( footer ) = layout.footer
+    {
+      let style = CellStyle { italic : true, ..CellStyle::default() };
+      sheet.push_spanned( footer, style );
+    }
+
+    for width in &mut sheet.widths
+    {
+      *width = ( *width + 2 ).clamp( MIN_COLUMN_WIDTH, MAX_COLUMN_WIDTH );
+    }
+    sheet
+  }
+
+  /// Index of `style` in `styles`, adding it when new.
+  fn intern( &mut self, style : CellStyle ) -> usize
+  {
+    if let Some( idx ) = self.styles.iter().position( | s | *s == style )
+    {
+      return idx;
+    }
+    self.styles.push( style );
+    self.styles.len() - 1
+  }
+
+  fn push_row( &mut self, row : Vec< SheetCell > )
+  {
+    for ( width, cell ) in self.widths.iter_mut().zip( &row )
+    {
+      *width = ( *width ).max( cell.value.display_width() );
+    }
+    self.rows.push( row );
+  }
+
+  /// Title/footer row: one cell spanning every column, excluded from column sizing.
+  fn push_spanned( &mut self, rule : &Heading, style : CellStyle )
+  {
+    let style = self.intern( style );
+    self.spanned.push( self.rows.len() );
+    self.rows.push( vec![ SheetCell { value : SheetValue::Text( clean_text( &rule.content_str() ) ), style } ] );
+  }
+
+  /// Number of columns a spanned row covers (at least one).
+  pub( crate ) fn span( &self ) -> usize
+  {
+    self.columns.max( 1 )
+  }
+}
+
+/// Overlay a cell's own colour, bold and dim onto the row style.
+fn apply_decoration( style : &mut CellStyle, cell : &DecoratedText )
+{
+  if let Some( color ) = &cell.color
+  {
+    style.apply_sgr( color );
+  }
+  if cell.bold
+  {
+    style.bold = true;
+  }
+  if cell.dim
+  {
+    style.color = style.color.or( Some( DIM_COLOR ) );
+  }
+}
+
+/// Remove ANSI sequences and characters XML 1.0 cannot carry.
+fn clean_text( text : &str ) -> String
+{
+  crate::ansi_str::strip_ansi( text )
+    .chars()
+    .filter( | &c | !c.is_control() || matches!( c, '\t' | '\n' ) )
+    .collect()
+}
+
+/// Sheet names: at most 31 characters, none of `[]:*?/\`, no leading or
+/// trailing apostrophe — the rules Excel enforces, which also satisfy ODS.
+fn sanitize_sheet_name( name : &str ) -> String
+{
+  let cleaned : String = clean_text( name )
+    .chars()
+    .filter( | c | !matches!( c, '[' | ']' | ':' | '*' | '?' | '/' | '\\' | '\t' | '\n' ) )
+    .take( 31 )
+    .collect();
+  let cleaned = cleaned.trim_matches( '\'' ).trim();
+  if cleaned.is_empty() { DEFAULT_SHEET_NAME.to_string() } else { cleaned.to_string() }
+}
+
+/// Escape text for XML element content and attribute values.
+pub( crate ) fn xml_escape( text : &str ) -> String
+{
+  let mut out = String::with_capacity( text.len() );
+  for c in text.chars()
+  {
+    match c
+    {
+      '&' => out.push_str( "&amp;" ),
+      '<' => out.push_str( "&lt;" ),
+      '>' => out.push_str( "&gt;" ),
+      '"' => out.push_str( "&quot;" ),
+      '\'' => out.push_str( "&apos;" ),
+      '\n' => out.push_str( "&#10;" ),
+      '\t' => out.push_str( "&#9;" ),
+      c => out.push( c ),
+    }
+  }
+  out
+}
+
+/// `RRGGBB` hex form of a colour.
+pub( crate ) fn hex_color( rgb : [ u8; 3 ] ) -> String
+{
+  format!( "{:02X}{:02X}{:02X}", rgb[ 0 ], rgb[ 1 ], rgb[ 2 ] )
+}
+
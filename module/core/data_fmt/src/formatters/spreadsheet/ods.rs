@@ -0,0 +1,364 @@
+//! ODS (`OpenDocument` spreadsheet) formatter for `TableView` data
+//!
+//! ## Output Example
+//! ```
+//! # #[cfg(feature = "format_ods")]
+//! # {
+//! # use data_fmt::{ RowBuilder, OdsFormatter, Format };
+//! # let view = RowBuilder::new(vec!["Name".into(), "Age".into()])
+//! #   .add_row(vec!["Alice".into(), "30".into()])
+//! #   .build_view();
+//! let bytes = OdsFormatter::new().format_bytes( &view ).unwrap();
+//! // PK\x03\x04 mimetype application/vnd.oasis.opendocument.spreadsheet …
+//! # }
+//! ```
+
+use crate::{ TableView, Heading, formatters::{ Format, FormatError } };
+use super::{ Sheet, SheetValue, Layout, ZipWriter, DEFAULT_SHEET_NAME, xml_escape, hex_color };
+
+const MIMETYPE : &str = "application/vnd.oasis.opendocument.spreadsheet";
+/// Approximate width of one character of the default font.
+const CHAR_WIDTH_CM : f64 = 0.21;
+
+/// ODS output formatter
+///
+/// Writes a single-table `OpenDocument` spreadsheet, readable by `LibreOffice`,
+/// Google Sheets and Excel. Cell types and layout match `XlsxFormatter`:
+/// numbers, booleans and dates stay typed, the header row repeats on every
+/// printed page, and `heading`/`footer` become spanned rows.
+///
+/// The output is binary, so use `format_bytes`; `format` returns
+/// `FormatError::UnsupportedOperation`.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "format_ods")]
+/// # {
+/// use data_fmt::{ RowBuilder, OdsFormatter, Format };
+///
+/// let view = RowBuilder::new( vec![ "Name".into(), "Age".into() ] )
+///   .add_row( vec![ "Alice".into(), "30".into() ] )
+///   .build_view();
+///
+/// let bytes = OdsFormatter::new().with_sheet_name( "People" ).format_bytes( &view ).unwrap();
+/// assert_eq!( &bytes[ 30..38 ], b"mimetype" );
+/// # }
+/// ```
+#[ derive( Debug, Clone ) ]
+pub struct OdsFormatter
+{
+  /// Table (sheet) name; sanitized like `XlsxFormatter::sheet_name`
+  pub sheet_name : String,
+  /// Optional title row above the header, bold and spanning all columns
+  pub heading : Option< Heading >,
+  /// Optional footer row below the data, italic and spanning all columns
+  pub footer : Option< Heading >,
+  /// ANSI SGR sequence styling the header row on top of bold (`None` = bold only)
+  pub header_color : Option< String >,
+  /// ANSI SGR sequences for even and odd data rows (`None` = unstyled rows)
+  pub row_colors : Option< ( String, String ) >,
+}
+
+impl OdsFormatter
+{
+  /// Create new ODS formatter writing one table named `Sheet1`
+  pub fn new() -> Self
+  {
+    Self
+    {
+      sheet_name : DEFAULT_SHEET_NAME.to_string(),
+      heading : None,
+      footer : None,
+      header_color : None,
+      row_colors : None,
+    }
+  }
+
+  /// Set the table name
+  #[ must_use ]
+  pub fn with_sheet_name( mut self, name : impl Into< String > ) -> Self
+  {
+    self.sheet_name = name.into();
+    self
+  }
+
+  /// Attach a title row above the header
+  #[ must_use ]
+  pub fn with_heading( mut self, h : Heading ) -> Self
+  {
+    self.heading = Some( h );
+    self
+  }
+
+  /// Attach a footer row below the data
+  #[ must_use ]
+  pub fn with_footer( mut self, f : Heading ) -> Self
+  {
+    self.footer = Some( f );
+    self
+  }
+
+  /// Style the header row with an ANSI SGR sequence, e.g. `"\x1b[36m"`
+  #[ must_use ]
+  pub fn with_header_color( mut self, color : impl Into< String > ) -> Self
+  {
+    self.header_color = Some( color.into() );
+    self
+  }
+
+  /// Alternate data row styles between two ANSI SGR sequences
+  #[ must_use ]
+  pub fn with_row_colors( mut self, even : impl Into< String >, odd : impl Into< String > ) -> Self
+  {
+    self.row_colors = Some( ( even.into(), odd.into() ) );
+    self
+  }
+
+  /// Take header and alternating row colours from a `ColorTheme`
+  #[ cfg( feature = "themes" ) ]
+  #[ must_use ]
+  pub fn with_theme( self, theme : &crate::ColorTheme ) -> Self
+  {
+    self
+      .with_header_color( theme.header_color.clone() )
+      .with_row_colors( theme.row_color1.clone(), theme.row_color2.clone() )
+  }
+
+  fn layout( &self ) -> Layout< '_ >
+  {
+    Layout
+    {
+      sheet_name : &self.sheet_name,
+      heading : self.heading.as_ref(),
+      footer : self.footer.as_ref(),
+      header_color : self.header_color.as_deref(),
+      row_colors : self.row_colors.as_ref().map( | ( even, odd ) | ( even.as_str(), odd.as_str() ) ),
+    }
+  }
+}
+
+impl Default for OdsFormatter
+{
+  fn default() -> Self
+  {
+    Self::new()
+  }
+}
+
+impl Format for OdsFormatter
+{
+  /// ODS is binary; always fails — use `format_bytes`.
+  fn format( &self, _data : &TableView ) -> Result< String, FormatError >
+  {
+    Err( FormatError::UnsupportedOperation( "ODS output is binary; use format_bytes()".to_string() ) )
+  }
+
+  /// Format `TableView` as an ODS document.
+  ///
+  /// The `mimetype` entry comes first and uncompressed, as the `OpenDocument`
+  /// packaging rules require.
+  fn format_bytes( &self, data : &TableView ) -> Result< Vec< u8 >, FormatError >
+  {
+    let sheet = Sheet::build( data, &self.layout() );
+    let mut zip = ZipWriter::new();
+    zip.add( "mimetype", MIMETYPE.as_bytes() )?;
+    zip.add( "META-INF/manifest.xml", MANIFEST.as_bytes() )?;
+    zip.add( "content.xml", content_xml( &sheet ).as_bytes() )?;
+    zip.finish()
+  }
+}
+
+const MANIFEST : &str = concat!(
+  "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+  "<manifest:manifest xmlns:manifest=\"urn:oasis:names:tc:opendocument:xmlns:manifest:1.0\" manifest:version=\"1.2\">",
+  "<manifest:file-entry manifest:full-path=\"/\" manifest:version=\"1.2\" manifest:media-type=\"application/vnd.oasis.opendocument.spreadsheet\"/>",
+  "<manifest:file-entry manifest:full-path=\"content.xml\" manifest:media-type=\"text/xml\"/>",
+  "</manifest:manifest>",
+);
+
+const CONTENT_OPEN : &str = concat!(
+  "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+  "<office:document-content",
+  " xmlns:office=\"urn:oasis:names:tc:opendocument:xmlns:office:1.0\"",
+  " xmlns:style=\"urn:oasis:names:tc:opendocument:xmlns:style:1.0\"",
+  " xmlns:text=\"urn:oasis:names:tc:opendocument:xmlns:text:1.0\"",
+  " xmlns:table=\"urn:oasis:names:tc:opendocument:xmlns:table:1.0\"",
+  " xmlns:fo=\"urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0\"",
+  " xmlns:number=\"urn:oasis:names:tc:opendocument:xmlns:datastyle:1.0\"",
+  " office:version=\"1.2\">",
+);
+
+const DATE_STYLE : &str = concat!(
+  "<number:date-style style:name=\"N1\">",
+  "<number:year number:style=\"long\"/><number:text>-</number:text>",
+  "<number:month number:style=\"long\"/><number:text>-</number:text>",
+  "<number:day number:style=\"long\"/><number:text> </number:text>",
+  "<number:hours number:style=\"long\"/><number:text>:</number:text>",
+  "<number:minutes number:style=\"long\"/><number:text>:</number:text>",
+  "<number:seconds number:style=\"long\"/>",
+  "</number:date-style>",
+);
+
+fn content_xml( sheet : &Sheet ) -> String
+{
+  let mut xml = String::from( CONTENT_OPEN );
+
+  xml.push_str( "<office:automatic-styles>" );
+  xml.push_str( DATE_STYLE );
+  for ( idx, width ) in sheet.widths.iter().enumerate()
+  {
+    xml.push_str( &format!(
+      "<style:style style:name=\"co{}\" style:family=\"table-column\"><style:table-column-properties style:column-width=\"{:.2}cm\"/></style:style>",
+      idx + 1, *width as f64 * CHAR_WIDTH_CM
+    ));
+  }
+  // `styles[ 0 ]` is the default style and needs no entry.
+  for ( idx, style ) in sheet.styles.iter().enumerate().skip( 1 )
+  {
+    let data_style = if style.date { " style:data-style-name=\"N1\"" } else { "" };
+    xml.push_str( &format!( "<style:style style:name=\"ce{idx}\" style:family=\"table-cell\"{data_style}>" ) );
+    if let Some( fill ) = style.fill
+    {
+      xml.push_str( &format!( "<style:table-cell-properties fo:background-color=\"#{}\"/>", hex_color( fill ) ) );
+    }
+    let mut text = String::new();
+    if style.bold { text.push_str( " fo:font-weight=\"bold\"" ); }
+    if style.italic { text.push_str( " fo:font-style=\"italic\"" ); }
+    if let Some( color ) = style.color
+    {
+      text.push_str( &format!( " fo:color=\"#{}\"", hex_color( color ) ) );
+    }
+    if !text.is_empty()
+    {
+      xml.push_str( &format!( "<style:text-properties{text}/>" ) );
+    }
+    xml.push_str( "</style:style>" );
+  }
+  xml.push_str( "</office:automatic-styles>" );
+
+  xml.push_str( &format!( "<office:body><office:spreadsheet><table:table table:name=\"{}\">", xml_escape( &sheet.name ) ) );
+  for idx in 0..sheet.widths.len()
+  {
+    xml.push_str( &format!( "<table:table-column table:style-name=\"co{}\"/>", idx + 1 ) );
+  }
+  for ( row_idx, row ) in sheet.rows.iter().enumerate()
+  {
+    let is_header = row_idx == sheet.header_row;
+    if is_header
+    {
+      xml.push_str( "<table:table-header-rows>" );
+    }
+    xml.push_str( "<table:table-row>" );
+    let spanned = sheet.spanned.contains( &row_idx );
+    for cell in row
+    {
+      let mut attrs = String::new();
+      if cell.style != 0
+      {
+        attrs.push_str( &format!( " table:style-name=\"ce{}\"", cell.style ) );
+      }
+      if spanned && sheet.span() > 1
+      {
+        attrs.push_str( &format!( " table:number-columns-spanned=\"{}\"", sheet.span() ) );
+      }
+      push_cell( &mut xml, &attrs, &cell.value );
+    }
+    if spanned
+    {
+      for _ in 1..sheet.span()
+      {
+        xml.push_str( "<table:covered-table-cell/>" );
+      }
+    }
+    xml.push_str( "</table:table-row>" );
+    if is_header
+    {
+      xml.push_str( "</table:table-header-rows>" );
+    }
+  }
+  xml.push_str( "</table:table></office:spreadsheet></office:body></office:document-content>" );
+  xml
+}
+
+fn push_cell( xml : &mut String, attrs : &str, value : &SheetValue )
+{
+  match value
+  {
+    SheetValue::Empty => xml.push_str( &format!( "<table:table-cell{attrs}/>" ) ),
+    SheetValue::Number( n ) => xml.push_str( &format!(
+      "<table:table-cell{attrs} office:value-type=\"float\" office:value=\"{n}\"><text:p>{n}</text:p></table:table-cell>"
+    )),
+    SheetValue::Bool( b ) => xml.push_str( &format!(
+      "<table:table-cell{attrs} office:value-type=\"boolean\" office:boolean-value=\"{b}\"><text:p>{}</text:p></table:table-cell>",
+      if *b { "TRUE" } else { "FALSE" }
+    )),
+    SheetValue::Date( secs ) =>
+    {
+      let ( year, month, day, day_secs ) = crate::cell_value::timestamp_parts( *secs );
+      let ( hours, minutes, seconds ) = ( day_secs / 3600, day_secs % 3600 / 60, day_secs % 60 );
+      xml.push_str( &format!(
+        "<table:table-cell{attrs} office:value-type=\"date\" office:date-value=\"{year:04}-{month:02}-{day:02}T{hours:02}:{minutes:02}:{seconds:02}\">\
+<text:p>{year:04}-{month:02}-{day:02} {hours:02}:{minutes:02}:{seconds:02}</text:p></table:table-cell>"
+      ));
+    }
+    SheetValue::Text( text ) => xml.push_str( &format!(
+      "<table:table-cell{attrs} office:value-type=\"string\">{}</table:table-cell>",
+      text_paragraphs( text )
+    )),
+  }
+}
+
+/// Text as `<text:p>` paragraphs, one per line.
+///
+/// ODF collapses whitespace, so space runs become `<text:s/>` and tabs `<text:tab/>`.
+fn text_paragraphs( text : &str ) -> String
+{
+  let mut xml = String::new();
+  for line in text.split( '\n' )
+  {
+    xml.push_str( "<text:p>" );
+    let mut spaces = 0usize;
+    let mut at_start = true;
+    for c in line.chars()
+    {
+      if c == ' '
+      {
+        spaces += 1;
+        continue;
+      }
+      push_spaces( &mut xml, spaces, at_start );
+      spaces = 0;
+      at_start = false;
+      if c == '\t'
+      {
+        xml.push_str( "<text:tab/>" );
+      }
+      else
+      {
+        xml.push_str( &xml_escape( c.encode_utf8( &mut [ 0; 4 ] ) ) );
+      }
+    }
+    // Trailing spaces are dropped by readers too, so keep them all explicit.
+    push_spaces( &mut xml, spaces, true );
+    xml.push_str( "</text:p>" );
+  }
+  xml
+}
+
+/// A single interior space is literal; leading, trailing and repeated spaces need `<text:s/>`.
+fn push_spaces( xml : &mut String, count : usize, explicit : bool )
+{
+  match ( count, explicit )
+  {
+    ( 0, _ ) => {}
+    ( 1, false ) => xml.push( ' ' ),
+    ( n, false ) =>
+    {
+      xml.push( ' ' );
+      xml.push_str( &format!( "<text:s text:c=\"{}\"/>", n - 1 ) );
+    }
+    ( 1, true ) => xml.push_str( "<text:s/>" ),
+    ( n, true ) => xml.push_str( &format!( "<text:s text:c=\"{n}\"/>" ) ),
+  }
+}
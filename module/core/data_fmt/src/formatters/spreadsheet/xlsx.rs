@@ -0,0 +1,349 @@
+//! XLSX (Office Open XML spreadsheet) formatter for `TableView` data
+//!
+//! ## Output Example
+//! ```
+//! # #[cfg(feature = "format_xlsx")]
+//! # {
+//! # use data_fmt::{ RowBuilder, XlsxFormatter, Format };
+//! # let view = RowBuilder::new(vec!["Name".into(), "Age".into()])
+//! #   .add_row(vec!["Alice".into(), "30".into()])
+//! #   .build_view();
+//! let bytes = XlsxFormatter::new().format_bytes( &view ).unwrap();
+//! // PK\x03\x04 … a workbook with one sheet:
+//! // | Name  | Age |
+//! // | Alice | 30  |
+//! # }
+//! ```
+
+use crate::{ TableView, Heading, formatters::{ Format, FormatError } };
+use super::{ Sheet, SheetValue, Layout, ZipWriter, DEFAULT_SHEET_NAME, xml_escape, hex_color };
+
+/// Days between the spreadsheet epoch (1899-12-30) and the Unix epoch.
+const UNIX_EPOCH_SERIAL : f64 = 25_569.0;
+/// First id available for custom number formats.
+const DATE_NUM_FMT_ID : u32 = 164;
+
+/// XLSX output formatter
+///
+/// Writes a single-sheet Excel workbook. Cell types follow
+/// `TableView::cell_value()`: integers and floats become numbers, booleans
+/// booleans, timestamps date-times, nulls empty cells, everything else text.
+/// The header row is bold and frozen; `heading`/`footer` become merged title
+/// and footer rows.
+///
+/// The output is binary, so use `format_bytes`; `format` returns
+/// `FormatError::UnsupportedOperation`.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "format_xlsx")]
+/// # {
+/// use data_fmt::{ RowBuilder, XlsxFormatter, Heading, Format };
+///
+/// let view = RowBuilder::new( vec![ "Name".into(), "Age".into() ] )
+///   .add_row( vec![ "Alice".into(), "30".into() ] )
+///   .build_view();
+///
+/// let formatter = XlsxFormatter::new()
+///   .with_sheet_name( "People" )
+///   .with_heading( Heading::new( "Staff" ) );
+/// let bytes = formatter.format_bytes( &view ).unwrap();
+/// assert!( bytes.starts_with( b"PK\x03\x04" ) );
+/// # }
+/// ```
+#[ derive( Debug, Clone ) ]
+pub struct XlsxFormatter
+{
+  /// Worksheet name; sanitized to Excel's rules (31 characters, no `[]:*?/\`)
+  pub sheet_name : String,
+  /// Optional title row above the header, bold and merged across all columns
+  pub heading : Option< Heading >,
+  /// Optional footer row below the data, italic and merged across all columns
+  pub footer : Option< Heading >,
+  /// ANSI SGR sequence styling the header row on top of bold (`None` = bold only)
+  pub header_color : Option< String >,
+  /// ANSI SGR sequences for even and odd data rows (`None` = unstyled rows)
+  pub row_colors : Option< ( String, String ) >,
+}
+
+impl XlsxFormatter
+{
+  /// Create new XLSX formatter writing one sheet named `Sheet1`
+  pub fn new() -> Self
+  {
+    Self
+    {
+      sheet_name : DEFAULT_SHEET_NAME.to_string(),
+      heading : None,
+      footer : None,
+      header_color : None,
+      row_colors : None,
+    }
+  }
+
+  /// Set the worksheet name
+  #[ must_use ]
+  pub fn with_sheet_name( mut self, name : impl Into< String > ) -> Self
+  {
+    self.sheet_name = name.into();
+    self
+  }
+
+  /// Attach a title row above the header
+  #[ must_use ]
+  pub fn with_heading( mut self, h : Heading ) -> Self
+  {
+    self.heading = Some( h );
+    self
+  }
+
+  /// Attach a footer row below the data
+  #[ must_use ]
+  pub fn with_footer( mut self, f : Heading ) -> Self
+  {
+    self.footer = Some( f );
+    self
+  }
+
+  /// Style the header row with an ANSI SGR sequence, e.g. `"\x1b[36m"`
+  #[ must_use ]
+  pub fn with_header_color( mut self, color : impl Into< String > ) -> Self
+  {
+    self.header_color = Some( color.into() );
+    self
+  }
+
+  /// Alternate data row styles between two ANSI SGR sequences
+  #[ must_use ]
+  pub fn with_row_colors( mut self, even : impl Into< String >, odd : impl Into< String > ) -> Self
+  {
+    self.row_colors = Some( ( even.into(), odd.into() ) );
+    self
+  }
+
+  /// Take header and alternating row colours from a `ColorTheme`
+  #[ cfg( feature = "themes" ) ]
+  #[ must_use ]
+  pub fn with_theme( self, theme : &crate::ColorTheme ) -> Self
+  {
+    self
+      .with_header_color( theme.header_color.clone() )
+      .with_row_colors( theme.row_color1.clone(), theme.row_color2.clone() )
+  }
+
+  fn layout( &self ) -> Layout< '_ >
+  {
+    Layout
+    {
+      sheet_name : &self.sheet_name,
+      heading : self.heading.as_ref(),
+      footer : self.footer.as_ref(),
+      header_color : self.header_color.as_deref(),
+      row_colors : self.row_colors.as_ref().map( | ( even, odd ) | ( even.as_str(), odd.as_str() ) ),
+    }
+  }
+}
+
+impl Default for XlsxFormatter
+{
+  fn default() -> Self
+  {
+    Self::new()
+  }
+}
+
+impl Format for XlsxFormatter
+{
+  /// XLSX is binary; always fails — use `format_bytes`.
+  fn format( &self, _data : &TableView ) -> Result< String, FormatError >
+  {
+    Err( FormatError::UnsupportedOperation( "XLSX output is binary; use format_bytes()".to_string() ) )
+  }
+
+  /// Format `TableView` as an XLSX workbook.
+  fn format_bytes( &self, data : &TableView ) -> Result< Vec< u8 >, FormatError >
+  {
+    let sheet = Sheet::build( data, &self.layout() );
+    let mut zip = ZipWriter::new();
+    zip.add( "[Content_Types].xml", CONTENT_TYPES.as_bytes() )?;
+    zip.add( "_rels/.rels", ROOT_RELS.as_bytes() )?;
+    zip.add( "xl/workbook.xml", workbook_xml( &sheet.name ).as_bytes() )?;
+    zip.add( "xl/_rels/workbook.xml.rels", WORKBOOK_RELS.as_bytes() )?;
+    zip.add( "xl/styles.xml", styles_xml( &sheet ).as_bytes() )?;
+    zip.add( "xl/worksheets/sheet1.xml", sheet_xml( &sheet ).as_bytes() )?;
+    zip.finish()
+  }
+}
+
+const XML_DECL : &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n";
+const MAIN_NS : &str = "http://schemas.openxmlformats.org/spreadsheetml/2006/main";
+const REL_NS : &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+
+const CONTENT_TYPES : &str = concat!(
+  "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n",
+  "<Types xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\">",
+  "<Default Extension=\"rels\" ContentType=\"application/vnd.openxmlformats-package.relationships+xml\"/>",
+  "<Default Extension=\"xml\" ContentType=\"application/xml\"/>",
+  "<Override PartName=\"/xl/workbook.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml\"/>",
+  "<Override PartName=\"/xl/worksheets/sheet1.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml\"/>",
+  "<Override PartName=\"/xl/styles.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.spreadsheetml.styles+xml\"/>",
+  "</Types>",
+);
+
+const ROOT_RELS : &str = concat!(
+  "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n",
+  "<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">",
+  "<Relationship Id=\"rId1\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument\" Target=\"xl/workbook.xml\"/>",
+  "</Relationships>",
+);
+
+const WORKBOOK_RELS : &str = concat!(
+  "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n",
+  "<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">",
+  "<Relationship Id=\"rId1\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet\" Target=\"worksheets/sheet1.xml\"/>",
+  "<Relationship Id=\"rId2\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles\" Target=\"styles.xml\"/>",
+  "</Relationships>",
+);
+
+fn workbook_xml( sheet_name : &str ) -> String
+{
+  format!(
+    "{XML_DECL}<workbook xmlns=\"{MAIN_NS}\" xmlns:r=\"{REL_NS}\"><sheets><sheet name=\"{}\" sheetId=\"1\" r:id=\"rId1\"/></sheets></workbook>",
+    xml_escape( sheet_name )
+  )
+}
+
+/// One font per style (font 0 is the default), fills only where set.
+fn styles_xml( sheet : &Sheet ) -> String
+{
+  let mut fonts = String::new();
+  let mut fills = String::from( "<fill><patternFill patternType=\"none\"/></fill><fill><patternFill patternType=\"gray125\"/></fill>" );
+  let mut fill_count = 2;
+  let mut xfs = String::new();
+  for ( font_id, style ) in sheet.styles.iter().enumerate()
+  {
+    fonts.push_str( "<font>" );
+    if style.bold { fonts.push_str( "<b/>" ); }
+    if style.italic { fonts.push_str( "<i/>" ); }
+    if let Some( color ) = style.color
+    {
+      fonts.push_str( &format!( "<color rgb=\"FF{}\"/>", hex_color( color ) ) );
+    }
+    fonts.push_str( "<sz val=\"11\"/><name val=\"Calibri\"/></font>" );
+
+    let fill_id = match style.fill
+    {
+      Some( fill ) =>
+      {
+        fills.push_str( &format!( "<fill><patternFill patternType=\"solid\"><fgColor rgb=\"FF{}\"/><bgColor indexed=\"64\"/></patternFill></fill>", hex_color( fill ) ) );
+        fill_count += 1;
+        fill_count - 1
+      }
+      None => 0,
+    };
+    let num_fmt_id = if style.date { DATE_NUM_FMT_ID } else { 0 };
+    xfs.push_str( &format!(
+      "<xf numFmtId=\"{num_fmt_id}\" fontId=\"{font_id}\" fillId=\"{fill_id}\" borderId=\"0\" xfId=\"0\" applyNumberFormat=\"{}\" applyFont=\"1\" applyFill=\"{}\"/>",
+      u8::from( style.date ), u8::from( style.fill.is_some() )
+    ));
+  }
+
+  let count = sheet.styles.len();
+  format!(
+    "{XML_DECL}<styleSheet xmlns=\"{MAIN_NS}\">\
+<numFmts count=\"1\"><numFmt numFmtId=\"{DATE_NUM_FMT_ID}\" formatCode=\"yyyy-mm-dd hh:mm:ss\"/></numFmts>\
+<fonts count=\"{count}\">{fonts}</fonts>\
+<fills count=\"{fill_count}\">{fills}</fills>\
+<borders count=\"1\"><border><left/><right/><top/><bottom/><diagonal/></border></borders>\
+<cellStyleXfs count=\"1\"><xf numFmtId=\"0\" fontId=\"0\" fillId=\"0\" borderId=\"0\"/></cellStyleXfs>\
+<cellXfs count=\"{count}\">{xfs}</cellXfs>\
+<cellStyles count=\"1\"><cellStyle name=\"Normal\" xfId=\"0\" builtinId=\"0\"/></cellStyles>\
+</styleSheet>"
+  )
+}
+
+fn sheet_xml( sheet : &Sheet ) -> String
+{
+  let mut xml = format!( "{XML_DECL}<worksheet xmlns=\"{MAIN_NS}\" xmlns:r=\"{REL_NS}\">" );
+
+  // Freeze everything down to and including the header row.
+  let split = sheet.header_row + 1;
+  xml.push_str( &format!(
+    "<sheetViews><sheetView workbookViewId=\"0\"><pane ySplit=\"{split}\" topLeftCell=\"A{}\" activePane=\"bottomLeft\" state=\"frozen\"/></sheetView></sheetViews>",
+    split + 1
+  ));
+
+  if !sheet.widths.is_empty()
+  {
+    xml.push_str( "<cols>" );
+    for ( idx, width ) in sheet.widths.iter().enumerate()
+    {
+      xml.push_str( &format!( "<col min=\"{n}\" max=\"{n}\" width=\"{width}\" customWidth=\"1\"/>", n = idx + 1 ) );
+    }
+    xml.push_str( "</cols>" );
+  }
+
+  xml.push_str( "<sheetData>" );
+  for ( row_idx, row ) in sheet.rows.iter().enumerate()
+  {
+    let r = row_idx + 1;
+    xml.push_str( &format!( "<row r=\"{r}\">" ) );
+    for ( col_idx, cell ) in row.iter().enumerate()
+    {
+      let reference = format!( "{}{r}", column_name( col_idx ) );
+      let style = if cell.style == 0 { String::new() } else { format!( " s=\"{}\"", cell.style ) };
+      match &cell.value
+      {
+        SheetValue::Empty if cell.style != 0 => xml.push_str( &format!( "<c r=\"{reference}\"{style}/>" ) ),
+        SheetValue::Empty => {}
+        SheetValue::Number( n ) => xml.push_str( &format!( "<c r=\"{reference}\"{style}><v>{n}</v></c>" ) ),
+        SheetValue::Bool( b ) => xml.push_str( &format!( "<c r=\"{reference}\"{style} t=\"b\"><v>{}</v></c>", u8::from( *b ) ) ),
+        SheetValue::Date( secs ) =>
+        {
+          let serial = *secs as f64 / 86_400.0 + UNIX_EPOCH_SERIAL;
+          xml.push_str( &format!( "<c r=\"{reference}\"{style}><v>{serial:?}</v></c>" ) );
+        }
+        SheetValue::Text( text ) => xml.push_str( &format!(
+          "<c r=\"{reference}\"{style} t=\"inlineStr\"><is><t xml:space=\"preserve\">{}</t></is></c>",
+          xml_escape( text )
+        )),
+      }
+    }
+    xml.push_str( "</row>" );
+  }
+  xml.push_str( "</sheetData>" );
+
+  if sheet.span() > 1 && !sheet.spanned.is_empty()
+  {
+    let last = column_name( sheet.span() - 1 );
+    xml.push_str( &format!( "<mergeCells count=\"{}\">", sheet.spanned.len() ) );
+    for row_idx in &sheet.spanned
+    {
+      xml.push_str( &format!( "<mergeCell ref=\"A{r}:{last}{r}\"/>", r = row_idx + 1 ) );
+    }
+    xml.push_str( "</mergeCells>" );
+  }
+
+  xml.push_str( "</worksheet>" );
+  xml
+}
+
+/// Spreadsheet column letters for a zero-based index: `A`, …, `Z`, `AA`, …
+fn column_name( mut idx : usize ) -> String
+{
+  let mut name = Vec::new();
+  loop
+  {
+    // `idx % 26` is below 26, so the narrowing is lossless.
+    #[ allow( clippy::cast_possible_truncation ) ]
+    name.push( b'A' + ( idx % 26 ) as u8 );
+    if idx < 26
+    {
+      break;
+    }
+    idx = idx / 26 - 1;
+  }
+  name.reverse();
+  String::from_utf8( name ).unwrap_or_default()
+}
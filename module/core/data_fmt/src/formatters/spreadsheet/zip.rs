@@ -0,0 +1,154 @@
+//! Minimal ZIP writer for spreadsheet containers
+//!
+//! Writes stored (uncompressed) entries only: XLSX and ODS readers accept
+//! them, ODS requires its `mimetype` entry stored anyway, and it keeps the
+//! spreadsheet formats free of a compression dependency. Timestamps are fixed
+//! at the DOS epoch so identical input produces identical bytes.
+
+use crate::formatters::FormatError;
+
+const LOCAL_HEADER_SIG : u32 = 0x0403_4b50;
+const CENTRAL_HEADER_SIG : u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIR_SIG : u32 = 0x0605_4b50;
+/// ZIP 2.0 — the minimum version that understands stored entries in folders.
+const VERSION : u16 = 20;
+/// General purpose flag bit 11: entry names are UTF-8.
+const FLAG_UTF8 : u16 = 0x0800;
+/// DOS date for 1980-01-01.
+const DOS_DATE : u16 = ( 1 << 5 ) | 1;
+
+struct Entry
+{
+  name : String,
+  crc : u32,
+  size : u32,
+  offset : u32,
+}
+
+/// In-memory ZIP archive built entry by entry
+pub( crate ) struct ZipWriter
+{
+  buf : Vec< u8 >,
+  entries : Vec< Entry >,
+}
+
+impl ZipWriter
+{
+  pub( crate ) fn new() -> Self
+  {
+    Self { buf : Vec::new(), entries : Vec::new() }
+  }
+
+  /// Append a stored entry.
+  pub( crate ) fn add( &mut self, name : &str, data : &[ u8 ] ) -> Result< (), FormatError >
+  {
+    let size = to_u32( data.len() )?;
+    let offset = to_u32( self.buf.len() )?;
+    let name_len = u16::try_from( name.len() ).map_err( | _ | too_large() )?;
+    let crc = crc32( data );
+
+    put_u32( &mut self.buf, LOCAL_HEADER_SIG );
+    put_u16( &mut self.buf, VERSION );
+    put_u16( &mut self.buf, FLAG_UTF8 );
+    put_u16( &mut self.buf, 0 ); // method: stored
+    put_u16( &mut self.buf, 0 ); // time
+    put_u16( &mut self.buf, DOS_DATE );
+    put_u32( &mut self.buf, crc );
+    put_u32( &mut self.buf, size ); // compressed size
+    put_u32( &mut self.buf, size );
+    put_u16( &mut self.buf, name_len );
+    put_u16( &mut self.buf, 0 ); // extra field length
+    self.buf.extend_from_slice( name.as_bytes() );
+    self.buf.extend_from_slice( data );
+
+    self.entries.push( Entry { name : name.to_string(), crc, size, offset } );
+    Ok( () )
+  }
+
+  /// Write the central directory and return the archive bytes.
+  pub( crate ) fn finish( mut self ) -> Result< Vec< u8 >, FormatError >
+  {
+    let directory_offset = to_u32( self.buf.len() )?;
+    for entry in &self.entries
+    {
+      put_u32( &mut self.buf, CENTRAL_HEADER_SIG );
+      put_u16( &mut self.buf, VERSION ); // made by
+      put_u16( &mut self.buf, VERSION ); // needed to extract
+      put_u16( &mut self.buf, FLAG_UTF8 );
+      put_u16( &mut self.buf, 0 ); // method: stored
+      put_u16( &mut self.buf, 0 ); // time
+      put_u16( &mut self.buf, DOS_DATE );
+      put_u32( &mut self.buf, entry.crc );
+      put_u32( &mut self.buf, entry.size );
+      put_u32( &mut self.buf, entry.size );
+      // Checked in `add`.
+      #[ allow( clippy::cast_possible_truncation ) ]
+      put_u16( &mut self.buf, entry.name.len() as u16 );
+      put_u16( &mut self.buf, 0 ); // extra field length
+      put_u16( &mut self.buf, 0 ); // comment length
+      put_u16( &mut self.buf, 0 ); // disk number
+      put_u16( &mut self.buf, 0 ); // internal attributes
+      put_u32( &mut self.buf, 0 ); // external attributes
+      put_u32( &mut self.buf, entry.offset );
+      self.buf.extend_from_slice( entry.name.as_bytes() );
+    }
+    let directory_size = to_u32( self.buf.len() )? - directory_offset;
+    let count = u16::try_from( self.entries.len() ).map_err( | _ | too_large() )?;
+
+    put_u32( &mut self.buf, END_OF_CENTRAL_DIR_SIG );
+    put_u16( &mut self.buf, 0 ); // this disk
+    put_u16( &mut self.buf, 0 ); // disk with the directory
+    put_u16( &mut self.buf, count );
+    put_u16( &mut self.buf, count );
+    put_u32( &mut self.buf, directory_size );
+    put_u32( &mut self.buf, directory_offset );
+    put_u16( &mut self.buf, 0 ); // comment length
+    Ok( self.buf )
+  }
+}
+
+fn too_large() -> FormatError
+{
+  FormatError::InvalidData( "spreadsheet exceeds the 4 GiB ZIP limit".to_string() )
+}
+
+fn to_u32( n : usize ) -> Result< u32, FormatError >
+{
+  u32::try_from( n ).map_err( | _ | too_large() )
+}
+
+fn put_u16( buf : &mut Vec< u8 >, v : u16 )
+{
+  buf.extend_from_slice( &v.to_le_bytes() );
+}
+
+fn put_u32( buf : &mut Vec< u8 >, v : u32 )
+{
+  buf.extend_from_slice( &v.to_le_bytes() );
+}
+
+/// CRC-32 (IEEE 802.3, reflected polynomial `0xEDB88320`) lookup table.
+const CRC_TABLE : [ u32; 256 ] =
+{
+  let mut table = [ 0u32; 256 ];
+  let mut i = 0;
+  while i < 256
+  {
+    #[ allow( clippy::cast_possible_truncation ) ]
+    let mut c = i as u32;
+    let mut k = 0;
+    while k < 8
+    {
+      c = if c & 1 == 1 { 0xEDB8_8320 ^ ( c >> 1 ) } else { c >> 1 };
+      k += 1;
+    }
+    table[ i ] = c;
+    i += 1;
+  }
+  table
+};
+
+pub( crate ) fn crc32( data : &[ u8 ] ) -> u32
+{
+  !data.iter().fold( u32::MAX, | crc, &byte | CRC_TABLE[ ( ( crc ^ u32::from( byte ) ) & 0xFF ) as usize ] ^ ( crc >> 8 ) )
+}
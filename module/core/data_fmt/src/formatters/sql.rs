@@ -17,6 +17,10 @@
 //!   ('value4', 'value5', 'value6');
 //! ```
 //!
+//! `with_batch_size( n )` splits the rows into several such statements of `n`
+//! rows each. The streaming sink always splits, every `DEFAULT_SQL_BATCH_SIZE`
+//! rows unless a batch size is set.
+//!
 //! ## SQL Dialects
 //!
 //! **`ANSI`** (default) - Standard SQL compliant
@@ -31,6 +35,13 @@
 //! - Backslashes: `\` → `\\` (`MySQL` only)
 //! - NULL handling: Empty strings can become NULL
 //!
+//! ## Typed Values
+//!
+//! Cells are read through `TableView::cell_value()`. Text cells keep the
+//! heuristics above; typed cells become native literals — `NULL`, `TRUE`/`FALSE`
+//! (`1`/`0` in `SQLite`), numbers, `TIMESTAMP '…'` and hex blobs (`X'…'`, or
+//! `'\x…'` for `PostgreSQL` `bytea`). Nested lists/maps are stored as JSON text.
+//!
 //! ## Examples
 //!
 //! ```
@@ -45,7 +56,12 @@
 //! // INSERT INTO users (name, age) VALUES ('Alice', 30), ('Bob', 25);
 //! ```
 
-use crate::{ TableView, Heading, formatters::{ Format, FormatError } };
+use crate::{ TableView, TableMetadata, Heading, DataType, CellValue, formatters::{ Format, FormatError, StreamFormat, RowSink } };
+use crate::formatters::stream_trait::{ check_row_len, check_not_finished, row_values, write_str, flush };
+use color_tools::DecoratedText;
+
+/// Rows per `INSERT` statement written by the streaming sink when no batch size is set
+pub const DEFAULT_SQL_BATCH_SIZE : usize = 1000;
 
 /// SQL dialect for identifier quoting and syntax
 #[ derive( Debug, Clone, Copy, PartialEq, Eq ) ]
@@ -63,7 +79,8 @@ pub enum SqlVariant
 
 /// SQL INSERT statement formatter
 ///
-/// Generates multi-row INSERT statements from tabular data.
+/// Generates multi-row INSERT statements from tabular data — a single statement
+/// unless `with_batch_size()` is set.
 ///
 /// # Examples
 ///
@@ -89,6 +106,9 @@ pub struct SqlFormatter
   pub variant : SqlVariant,
   /// Treat empty strings as NULL
   pub empty_as_null : bool,
+  /// Rows per `INSERT` statement (`0` = all rows in one statement; the streaming
+  /// sink then uses `DEFAULT_SQL_BATCH_SIZE`)
+  pub batch_size : usize,
   /// Optional titled rule rendered above the formatted output, as a `--` comment (`None` = no heading)
   pub heading : Option< Heading >,
   /// Optional titled rule rendered below the formatted output, as a `--` comment (`None` = no footer)
@@ -105,6 +125,7 @@ impl SqlFormatter
       table_name : table_name.into(),
       variant : SqlVariant::Ansi,
       empty_as_null : false,
+      batch_size : 0,
       heading : None,
       footer : None,
     }
@@ -118,6 +139,7 @@ impl SqlFormatter
       table_name : table_name.into(),
       variant,
       empty_as_null : false,
+      batch_size : 0,
       heading : None,
       footer : None,
     }
@@ -131,6 +153,18 @@ impl SqlFormatter
     self
   }
 
+  /// Set the number of rows per `INSERT` statement (`0` = all rows in one statement)
+  ///
+  /// Applies to both `Format::format()` and the streaming sink. The sink never
+  /// writes a single unbounded statement: with `0` it splits every
+  /// `DEFAULT_SQL_BATCH_SIZE` rows.
+  #[ must_use ]
+  pub fn with_batch_size( mut self, rows : usize ) -> Self
+  {
+    self.batch_size = rows;
+    self
+  }
+
   /// Attach a titled heading rule rendered above the formatted output, as a `--` comment
   #[ must_use ]
   pub fn with_heading( mut self, h : Heading ) -> Self
@@ -179,6 +213,118 @@ impl SqlFormatter
     output
   }
 
+  /// Separator written before the tuple of row `row_idx`: the `INSERT` preamble
+  /// when the row opens a statement, closing the previous one first, else `,`.
+  /// A `batch` of `0` keeps every row in one statement.
+  fn tuple_separator( row_idx : usize, batch : usize, prefix : &str ) -> String
+  {
+    if row_idx == 0
+    {
+      format!( "{prefix}\n  " )
+    }
+    else if batch > 0 && row_idx.is_multiple_of( batch )
+    {
+      format!( ";\n{prefix}\n  " )
+    }
+    else
+    {
+      ",\n  ".to_string()
+    }
+  }
+
+  /// Render `INSERT INTO "table" ("col", ...) VALUES` — everything before the first tuple
+  fn insert_prefix( &self, column_names : &[ String ] ) -> String
+  {
+    let mut output = String::new();
+
+    // INSERT INTO table_name
+    output.push_str( "INSERT INTO " );
+    output.push_str( &self.quote_identifier( &self.table_name ) );
+    output.push( ' ' );
+
+    // Column names
+    if !column_names.is_empty()
+    {
+      output.push( '(' );
+
+      for ( idx, col_name ) in column_names.iter().enumerate()
+      {
+        if idx > 0
+        {
+          output.push_str( ", " );
+        }
+        output.push_str( &self.quote_identifier( col_name ) );
+      }
+
+      output.push_str( ") " );
+    }
+
+    // VALUES clause
+    output.push_str( "VALUES" );
+    output
+  }
+
+  /// Render one row as a parenthesized, comma-separated tuple of SQL literals
+  fn values_tuple( &self, row : &[ CellValue ] ) -> String
+  {
+    let mut output = String::from( "(" );
+    for ( cell_idx, value ) in row.iter().enumerate()
+    {
+      if cell_idx > 0
+      {
+        output.push_str( ", " );
+      }
+      output.push_str( &self.literal( value ) );
+    }
+    output.push( ')' );
+    output
+  }
+
+  /// Render a typed value as a literal of this dialect
+  ///
+  /// `Text` keeps the text heuristics of `escape_value` (numeric text unquoted,
+  /// optional empty-as-NULL); typed values map to native literals. Nested lists
+  /// and maps are stored as their JSON text.
+  fn literal( &self, value : &CellValue ) -> String
+  {
+    match value
+    {
+      CellValue::Bool( b ) => match ( self.variant, b )
+      {
+        ( SqlVariant::SQLite, true ) => "1".to_string(),
+        ( SqlVariant::SQLite, false ) => "0".to_string(),
+        ( _, true ) => "TRUE".to_string(),
+        ( _, false ) => "FALSE".to_string(),
+      },
+      CellValue::Int( i ) => i.to_string(),
+      CellValue::Float( f ) if f.is_finite() => format!( "{f:?}" ),
+      CellValue::Null | CellValue::Float( _ ) => "NULL".to_string(),
+      CellValue::Text( text ) => self.escape_value( text ),
+      CellValue::Timestamp( secs ) =>
+      {
+        let ( year, month, day, day_secs ) = crate::cell_value::timestamp_parts( *secs );
+        let stamp = format!(
+          "'{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}'",
+          day_secs / 3600, day_secs % 3600 / 60, day_secs % 60
+        );
+        // SQLite has no timestamp type; its date functions read this text form.
+        if matches!( self.variant, SqlVariant::SQLite ) { stamp } else { format!( "TIMESTAMP {stamp}" ) }
+      }
+      CellValue::Bytes( bytes ) =>
+      {
+        let hex = bytes.iter().fold( String::with_capacity( bytes.len() * 2 ), | mut hex, b |
+        {
+          use core::fmt::Write;
+          let _ = write!( hex, "{b:02X}" );
+          hex
+        });
+        // PostgreSQL's X'..' is a bit string; bytea takes the '\x..' escape form.
+        if matches!( self.variant, SqlVariant::PostgreSQL ) { format!( "'\\x{hex}'" ) } else { format!( "X'{hex}'" ) }
+      }
+      CellValue::List( _ ) | CellValue::Map( _ ) => self.quote_text( &value.to_string() ),
+    }
+  }
+
   /// Quote identifier (table/column name) according to variant
   fn quote_identifier( &self, name : &str ) -> String
   {
@@ -210,6 +356,12 @@ impl SqlFormatter
       return "NULL".to_string();
     }
 
+    self.quote_text( value )
+  }
+
+  /// Quote a string literal, escaping per dialect
+  fn quote_text( &self, value : &str ) -> String
+  {
     // Escape single quotes by doubling
     let escaped = value.replace( '\'', "''" );
 
@@ -240,61 +392,122 @@ impl Format for SqlFormatter
       return Ok( self.wrap_with_heading_footer( String::new() ) );
     }
 
+    let prefix = self.insert_prefix( &data.metadata.column_names );
     let mut output = String::new();
 
-    // INSERT INTO table_name
-    output.push_str( "INSERT INTO " );
-    output.push_str( &self.quote_identifier( &self.table_name ) );
-    output.push( ' ' );
+    // Data rows, a new statement every `batch_size` rows when one is set
+    for row_idx in 0..data.rows.len()
+    {
+      let row : Vec< CellValue > = ( 0..data.metadata.column_names.len() )
+        .map( | col | data.cell_value( row_idx, col ) )
+        .collect();
+      output.push_str( &Self::tuple_separator( row_idx, self.batch_size, &prefix ) );
+      output.push_str( &self.values_tuple( &row ) );
+    }
 
-    // Column names
-    if !data.metadata.column_names.is_empty()
+    output.push( ';' );
+
+    Ok( self.wrap_with_heading_footer( output ) )
+  }
+}
+
+impl StreamFormat for SqlFormatter
+{
+  /// Open a sink emitting the `INSERT ... VALUES` preamble with the first row
+  /// and one tuple per subsequent row, closing the statement and opening a new
+  /// one every `batch_size` rows — `DEFAULT_SQL_BATCH_SIZE` when none is set —
+  /// so no single statement grows without bound.
+  ///
+  /// Zero rows produce no statement, matching the BUG-020 behavior of
+  /// `Format::format()`. The heading/footer rule can't be sized from the widest
+  /// body line before the body exists, so both are sized from the `INSERT`
+  /// preamble line instead.
+  fn row_sink< 'a, W : std::io::Write + 'a >( &'a self, metadata : &TableMetadata, out : W ) -> Result< Box< dyn RowSink + 'a >, FormatError >
+  {
+    let prefix = self.insert_prefix( &metadata.column_names );
+    let rule_width = crate::ansi_str::unicode_visual_len( &prefix );
+    let mut sink = SqlRowSink
     {
-      output.push( '(' );
+      formatter : self,
+      columns : metadata.column_names.len(),
+      column_types : metadata.column_types.clone(),
+      prefix,
+      batch : if self.batch_size > 0 { self.batch_size } else { DEFAULT_SQL_BATCH_SIZE },
+      rule_width,
+      out,
+      rows_written : 0,
+      finished : false,
+    };
+    let mut heading = String::new();
+    crate::config::render_commented_rule_if_present( &mut heading, self.heading.as_ref(), rule_width, "-- " );
+    write_str( &mut sink.out, &heading )?;
+    Ok( Box::new( sink ) )
+  }
+}
 
-      for ( idx, col_name ) in data.metadata.column_names.iter().enumerate()
-      {
-        if idx > 0
-        {
-          output.push_str( ", " );
-        }
-        output.push_str( &self.quote_identifier( col_name ) );
-      }
+/// `RowSink` behind `SqlFormatter::row_sink`
+struct SqlRowSink< 'a, W : std::io::Write >
+{
+  formatter : &'a SqlFormatter,
+  columns : usize,
+  column_types : Vec< DataType >,
+  prefix : String,
+  batch : usize,
+  rule_width : usize,
+  out : W,
+  rows_written : usize,
+  finished : bool,
+}
 
-      output.push_str( ") " );
+impl< W : std::io::Write > SqlRowSink< '_, W >
+{
+  /// Write one tuple, typing cells from `values` or the column types.
+  fn write_typed( &mut self, row : &[ DecoratedText ], values : Option< &[ CellValue ] > ) -> Result< (), FormatError >
+  {
+    check_not_finished( self.finished )?;
+    check_row_len( self.columns, row.len() )?;
+    if let Some( values ) = values
+    {
+      check_row_len( self.columns, values.len() )?;
     }
+    let values = row_values( row, values, &self.column_types );
 
-    // VALUES clause
-    output.push_str( "VALUES" );
+    let mut chunk = SqlFormatter::tuple_separator( self.rows_written, self.batch, &self.prefix );
+    chunk.push_str( &self.formatter.values_tuple( &values ) );
 
-    // Data rows
-    for ( row_idx, row ) in data.rows.iter().enumerate()
-    {
-      if row_idx == 0
-      {
-        output.push_str( "\n  " );
-      }
-      else
-      {
-        output.push_str( ",\n  " );
-      }
+    write_str( &mut self.out, &chunk )?;
+    self.rows_written += 1;
+    Ok( () )
+  }
+}
 
-      output.push( '(' );
+impl< W : std::io::Write > RowSink for SqlRowSink< '_, W >
+{
+  fn write_row_with_detail( &mut self, row : &[ DecoratedText ], _detail : Option< &DecoratedText > ) -> Result< (), FormatError >
+  {
+    self.write_typed( row, None )
+  }
+
+  fn write_row_with_values( &mut self, row : &[ DecoratedText ], _detail : Option< &DecoratedText >, values : &[ CellValue ] ) -> Result< (), FormatError >
+  {
+    self.write_typed( row, Some( values ) )
+  }
 
-      for ( cell_idx, cell ) in row.iter().enumerate()
+  fn finish( &mut self ) -> Result< (), FormatError >
+  {
+    check_not_finished( self.finished )?;
+    self.finished = true;
+    let mut closing = String::new();
+    if self.rows_written > 0
+    {
+      closing.push( ';' );
+      if self.formatter.footer.is_some()
       {
-        if cell_idx > 0
-        {
-          output.push_str( ", " );
-        }
-        output.push_str( &self.escape_value( &cell.text ) );
+        closing.push( '\n' );
       }
-
-      output.push( ')' );
     }
-
-    output.push( ';' );
-
-    Ok( self.wrap_with_heading_footer( output ) )
+    crate::config::render_commented_rule_if_present( &mut closing, self.formatter.footer.as_ref(), self.rule_width, "-- " );
+    write_str( &mut self.out, &closing )?;
+    flush( &mut self.out )
   }
 }
@@ -0,0 +1,183 @@
+//! Incremental (row-by-row) counterpart of the `Format` trait
+
+use crate::{ TableView, TableMetadata, CellValue, formatters::FormatError };
+use color_tools::DecoratedText;
+
+/// Default number of rows a width-dependent streaming writer buffers before it
+/// locks column widths and starts emitting output.
+///
+/// Used by `TableConfig::with_stream_window()` and `ExpandedConfig::stream_window`
+/// when the caller does not override it.
+pub const DEFAULT_STREAM_WINDOW : usize = 100;
+
+/// Row-by-row consumer produced by a `StreamFormat` implementor
+///
+/// Rows are pushed one at a time and written straight into the underlying
+/// `std::io::Write`; only formatters that must measure column widths keep a
+/// bounded look-ahead window of rows in memory.
+///
+/// `finish()` must be called once after the last row — it writes the closing
+/// part of the output (bottom border, closing bracket, `;`, footer) and flushes
+/// the writer. Dropping a sink without calling `finish()` leaves the output
+/// truncated.
+pub trait RowSink
+{
+  /// Push one data row
+  ///
+  /// # Errors
+  ///
+  /// Returns `FormatError::InvalidData` when the row length doesn't match the
+  /// column count or the sink is already finished, and `FormatError::Io` when
+  /// writing to the underlying writer fails.
+  fn write_row( &mut self, row : &[ DecoratedText ] ) -> Result< (), FormatError >
+  {
+    self.write_row_with_detail( row, None )
+  }
+
+  /// Push one data row with an optional sub-row detail line
+  ///
+  /// Formatters without a detail-line concept ignore `detail`.
+  ///
+  /// # Errors
+  ///
+  /// Same as `write_row`.
+  fn write_row_with_detail( &mut self, row : &[ DecoratedText ], detail : Option< &DecoratedText > ) -> Result< (), FormatError >;
+
+  /// Push one data row together with its typed cell values
+  ///
+  /// Data formatters (JSON, SQL) emit `values` as native literals; the others
+  /// render the text and ignore them (the default). Rows pushed without values
+  /// are typed from the cell text and the metadata's `column_types`, exactly
+  /// like `TableView::cell_value()`.
+  ///
+  /// # Errors
+  ///
+  /// Same as `write_row`; `values` must also match the column count.
+  fn write_row_with_values( &mut self, row : &[ DecoratedText ], detail : Option< &DecoratedText >, values : &[ CellValue ] ) -> Result< (), FormatError >
+  {
+    let _ = values;
+    self.write_row_with_detail( row, detail )
+  }
+
+  /// Write the closing part of the output and flush the writer
+  ///
+  /// # Errors
+  ///
+  /// Returns `FormatError::InvalidData` when called twice and `FormatError::Io`
+  /// when writing or flushing fails.
+  fn finish( &mut self ) -> Result< (), FormatError >;
+}
+
+/// Streaming formatting interface writing directly into `std::io::Write`
+///
+/// Mirrors `Format`, but instead of taking a fully materialized `TableView`
+/// it opens a `RowSink` from the table metadata alone, so callers can feed
+/// rows as they are produced (e.g. from a database cursor) without holding
+/// the whole result set in memory.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "format_json")]
+/// # {
+/// use data_fmt::{ TableMetadata, JsonFormatter, StreamFormat };
+///
+/// let metadata = TableMetadata::new( vec![ "Name".into() ] );
+/// let formatter = JsonFormatter::compact();
+/// let mut out = Vec::new();
+/// {
+///   let mut sink = formatter.row_sink( &metadata, &mut out ).unwrap();
+///   sink.write_row( &[ "Alice".into() ] ).unwrap();
+///   sink.write_row( &[ "Bob".into() ] ).unwrap();
+///   sink.finish().unwrap();
+/// }
+/// assert_eq!( String::from_utf8( out ).unwrap(), r#"[{"Name":"Alice"},{"Name":"Bob"}]"# );
+/// # }
+/// ```
+pub trait StreamFormat
+{
+  /// Open a row sink writing formatted output for `metadata` into `out`
+  ///
+  /// # Errors
+  ///
+  /// Returns `FormatError::Io` if the formatter writes a preamble eagerly and
+  /// the write fails.
+  fn row_sink< 'a, W : std::io::Write + 'a >( &'a self, metadata : &TableMetadata, out : W ) -> Result< Box< dyn RowSink + 'a >, FormatError >;
+
+  /// Stream an already-built `TableView` into `out`
+  ///
+  /// Produces the same bytes as writing `Format::format()` output for
+  /// formatters whose rendering does not depend on the whole table.
+  ///
+  /// # Errors
+  ///
+  /// Propagates any error from `row_sink`, `write_row_with_detail` or `finish`.
+  fn write_view< W : std::io::Write >( &self, data : &TableView, out : W ) -> Result< (), FormatError >
+  {
+    let mut sink = self.row_sink( &data.metadata, out )?;
+    for ( idx, row ) in data.rows.iter().enumerate()
+    {
+      let detail = data.row_details.get( idx ).and_then( Option::as_ref );
+      match data.values.get( idx )
+      {
+        Some( values ) => sink.write_row_with_values( row, detail, values )?,
+        None => sink.write_row_with_detail( row, detail )?,
+      }
+    }
+    sink.finish()
+  }
+}
+
+/// Reject rows whose length differs from the column count — the streaming
+/// equivalent of `RowBuilder`'s length assertion.
+pub( crate ) fn check_row_len( metadata_len : usize, row_len : usize ) -> Result< (), FormatError >
+{
+  if metadata_len == row_len
+  {
+    Ok( () )
+  }
+  else
+  {
+    Err( FormatError::InvalidData( format!( "row length {row_len} doesn't match headers length {metadata_len}" ) ) )
+  }
+}
+
+/// Typed values for a streamed row: the explicit `values` when given, else
+/// derived from the cell text and column types (see `TableView::cell_value()`).
+#[ cfg( any( feature = "format_json", feature = "sql_ansi", feature = "sql_postgres", feature = "sql_mysql", feature = "sql_sqlite" ) ) ]
+pub( crate ) fn row_values( row : &[ DecoratedText ], values : Option< &[ CellValue ] >, column_types : &[ crate::DataType ] ) -> Vec< CellValue >
+{
+  match values
+  {
+    Some( values ) => values.to_vec(),
+    None => row.iter().enumerate().map( | ( col, cell ) |
+    {
+      CellValue::from_text( &cell.text, column_types.get( col ).unwrap_or( &crate::DataType::String ) )
+    }).collect(),
+  }
+}
+
+/// Reject writes after `finish()`.
+pub( crate ) fn check_not_finished( finished : bool ) -> Result< (), FormatError >
+{
+  if finished
+  {
+    Err( FormatError::InvalidData( "row sink already finished".to_string() ) )
+  }
+  else
+  {
+    Ok( () )
+  }
+}
+
+/// Write a string into `out`, mapping I/O failures into `FormatError::Io`.
+pub( crate ) fn write_str< W : std::io::Write >( out : &mut W, s : &str ) -> Result< (), FormatError >
+{
+  out.write_all( s.as_bytes() ).map_err( | e | FormatError::Io( e.to_string() ) )
+}
+
+/// Flush `out`, mapping I/O failures into `FormatError::Io`.
+pub( crate ) fn flush< W : std::io::Write >( out : &mut W ) -> Result< (), FormatError >
+{
+  out.flush().map_err( | e | FormatError::Io( e.to_string() ) )
+}
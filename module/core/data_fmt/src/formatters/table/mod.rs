@@ -94,7 +94,8 @@
 //! // Alice  30
 //! ```
 
-use crate::{ TreeNode, TableConfig };
+use crate::{ TreeNode, TableConfig, DataType };
+use std::borrow::Cow;
 use crate::ansi_str::unicode_visual_len;
 use color_tools::DecoratedText;
 
@@ -105,7 +106,8 @@ const INITIAL_CAPACITY : usize = 512;
 ///
 /// Provides table rendering with configurable borders, column widths,
 /// and alignment. Automatically handles ANSI color codes for proper alignment.
-#[ derive( Debug ) ]
+/// Columns typed `Integer`/`Float` in the view's metadata are right-aligned.
+#[ derive( Debug, Clone ) ]
 pub struct TableFormatter
 {
   pub( super ) config : TableConfig,
@@ -172,6 +174,26 @@ impl TableFormatter
     super::Format::format( self, &flattened ).unwrap_or_default()
   }
 
+  /// This formatter with numeric columns right-aligned per `column_types`
+  ///
+  /// Explicit `with_align_right()` entries win; remaining `Integer`/`Float`
+  /// columns right-align unless `with_numeric_align_right( false )` is set.
+  /// Borrows `self` unchanged when no column needs it.
+  pub( super ) fn aligned_for( &self, column_types : &[ DataType ] ) -> Cow< '_, Self >
+  {
+    let explicit = self.config.col_align_right();
+    let needs_numeric = self.config.numeric_align_right_enabled()
+      && column_types.iter().skip( explicit.len() ).any( DataType::is_numeric );
+    if !needs_numeric
+    {
+      return Cow::Borrowed( self );
+    }
+    let align = ( 0..column_types.len().max( explicit.len() ) )
+      .map( | idx | explicit.get( idx ).copied().unwrap_or_else( || column_types.get( idx ).is_some_and( DataType::is_numeric ) ) )
+      .collect();
+    Cow::Owned( Self::with_config( self.config.clone().with_align_right( align ) ) )
+  }
+
   /// Internal implementation of table formatting
   fn format_internal
   (
@@ -234,95 +256,8 @@ impl TableFormatter
     // Data rows — optionally alternating color, with inter-row separators
     for ( idx, row ) in rows.iter().enumerate()
     {
-      let color = if self.config.alternating_rows_enabled()
-      {
-        if idx % 2 == 0 { self.config.row_color1_str() } else { self.config.row_color2_str() }
-      }
-      else
-      {
-        ""
-      };
-
-      let primary_row = &row[ ..fold_point ];
-
-      if color.is_empty()
-      {
-        self.format_row_colored( &mut output, primary_row, primary_widths, false );
-      }
-      else
-      {
-        // Row-level color: strip cell colors (cell RESET would clear row background)
-        let plain_cells : Vec< DecoratedText > = primary_row.iter()
-          .map( | ct | DecoratedText::from( ct.text.as_str() ) )
-          .collect();
-        let mut row_buf = String::new();
-        self.format_row( &mut row_buf, &plain_cells, primary_widths, false );
-        // Fix(BUG-009): wrap each output line with row color individually.
-        // Root cause: single color/RESET wrap around the whole row buffer left `\n` chars
-        //   inside the color sequence, causing background-color bleed across sub-lines.
-        // Pitfall: never wrap row_buf as a whole — always iterate .lines() and emit
-        //   color + line + RESET per output line.
-        let reset = self.config.color_reset_str();
-        for line in row_buf.lines()
-        {
-          output.push_str( color );
-          output.push_str( line );
-          output.push_str( reset );
-          output.push( '\n' );
-        }
-      }
-
-      // Fold continuation lines — overflow columns rendered below primary row.
-      // Emitted before sub-row detail lines per algorithm/005 rendering order.
-      if fold_point < column_widths.len()
-      {
-        let overflow_hdrs : Vec< &str > = headers[ fold_point.. ]
-          .iter()
-          .map( String::as_str )
-          .collect();
-        let overflow_vals : Vec< &str > = row[ fold_point.. ]
-          .iter()
-          .map( | ct | ct.text.as_str() )
-          .collect();
-        let continuation = self.render_fold_continuation( &overflow_hdrs, &overflow_vals );
-        output.push_str( &continuation );
-      }
-
-      // Sub-row detail line(s) — indent every line; apply per-line ANSI color when set.
-      //
-      // Fix(BUG-010): iterate ct.text.lines() and wrap each line
-      //   individually with color + line + RESET.
-      // Root cause: calling ct.render() then .lines() would place the ANSI RESET
-      //   at the very end of the whole block; any intermediate \n would cause terminal
-      //   background-color bleed across line boundaries.
-      // Pitfall: never call .render() and then .lines() on the result — always iterate
-      //   .text.lines() and emit color/RESET per output line.
-      //
-      // Fix(BUG-013): emit `indent` inside the loop, not once before push_str( full_detail ).
-      // Root cause: a single `push_str( indent ) + push_str( full_detail )` applied the
-      //   prefix only before the first visual line; continuation lines started at column 0.
-      // Pitfall: same pattern as color rendering — a single push_str cannot apply per-line
-      //   prefixes to strings that may contain embedded newlines.
-      if let Some( Some( ct ) ) = row_details.get( idx )
-      {
-        if !ct.text.is_empty()
-        {
-          let indent = self.config.detail_indent();
-          for line in ct.text.lines()
-          {
-            output.push_str( indent );
-            if let Some( ref color ) = ct.color
-            {
-              output.push_str( &DecoratedText::from( line.to_string() ).with_color( color.clone() ).render() );
-            }
-            else
-            {
-              output.push_str( line );
-            }
-            output.push( '\n' );
-          }
-        }
-      }
+      let detail = row_details.get( idx ).and_then( Option::as_ref );
+      self.format_body_row( &mut output, idx, row, detail, headers, &column_widths, fold_point );
 
       if idx < rows.len() - 1
       {
@@ -339,11 +274,125 @@ impl TableFormatter
     output
   }
 
+
+  /// Render one data row: primary cells (with optional alternating row color),
+  /// fold continuation lines, then the sub-row detail line(s).
+  ///
+  /// Shared by `format_internal()` and the streaming `TableRowSink` so both paths
+  /// produce identical bytes per row. Inter-row separators are the caller's job.
+  // Each argument is a distinct piece of per-row render state already computed by the
+  // caller; bundling them into a struct would only move the list one level down.
+  #[ allow( clippy::too_many_arguments ) ]
+  pub( super ) fn format_body_row
+  (
+    &self,
+    output : &mut String,
+    idx : usize,
+    row : &[ DecoratedText ],
+    detail : Option< &DecoratedText >,
+    headers : &[ String ],
+    column_widths : &[ usize ],
+    fold_point : usize,
+  )
+  {
+    let primary_widths : &[ usize ] = &column_widths[ ..fold_point ];
+    let color = if self.config.alternating_rows_enabled()
+    {
+      if idx.is_multiple_of( 2 ) { self.config.row_color1_str() } else { self.config.row_color2_str() }
+    }
+    else
+    {
+      ""
+    };
+
+    let primary_row = &row[ ..fold_point ];
+
+    if color.is_empty()
+    {
+      self.format_row_colored( output, primary_row, primary_widths, false );
+    }
+    else
+    {
+      // Row-level color: strip cell colors (cell RESET would clear row background)
+      let plain_cells : Vec< DecoratedText > = primary_row.iter()
+        .map( | ct | DecoratedText::from( ct.text.as_str() ) )
+        .collect();
+      let mut row_buf = String::new();
+      self.format_row( &mut row_buf, &plain_cells, primary_widths, false );
+      // Fix(BUG-009): wrap each output line with row color individually.
+      // Root cause: single color/RESET wrap around the whole row buffer left `\n` chars
+      //   inside the color sequence, causing background-color bleed across sub-lines.
+      // Pitfall: never wrap row_buf as a whole — always iterate .lines() and emit
+      //   color + line + RESET per output line.
+      let reset = self.config.color_reset_str();
+      for line in row_buf.lines()
+      {
+        output.push_str( color );
+        output.push_str( line );
+        output.push_str( reset );
+        output.push( '\n' );
+      }
+    }
+
+    // Fold continuation lines — overflow columns rendered below primary row.
+    // Emitted before sub-row detail lines per algorithm/005 rendering order.
+    if fold_point < column_widths.len()
+    {
+      let overflow_hdrs : Vec< &str > = headers[ fold_point.. ]
+        .iter()
+        .map( String::as_str )
+        .collect();
+      let overflow_vals : Vec< &str > = row[ fold_point.. ]
+        .iter()
+        .map( | ct | ct.text.as_str() )
+        .collect();
+      let continuation = self.render_fold_continuation( &overflow_hdrs, &overflow_vals );
+      output.push_str( &continuation );
+    }
+
+    // Sub-row detail line(s) — indent every line; apply per-line ANSI color when set.
+    //
+    // Fix(BUG-010): iterate ct.text.lines() and wrap each line
+    //   individually with color + line + RESET.
+    // Root cause: calling ct.render() then .lines() would place the ANSI RESET
+    //   at the very end of the whole block; any intermediate \n would cause terminal
+    //   background-color bleed across line boundaries.
+    // Pitfall: never call .render() and then .lines() on the result — always iterate
+    //   .text.lines() and emit color/RESET per output line.
+    //
+    // Fix(BUG-013): emit `indent` inside the loop, not once before push_str( full_detail ).
+    // Root cause: a single `push_str( indent ) + push_str( full_detail )` applied the
+    //   prefix only before the first visual line; continuation lines started at column 0.
+    // Pitfall: same pattern as color rendering — a single push_str cannot apply per-line
+    //   prefixes to strings that may contain embedded newlines.
+    if let Some( ct ) = detail
+    {
+      if !ct.text.is_empty()
+      {
+        let indent = self.config.detail_indent();
+        for line in ct.text.lines()
+        {
+          output.push_str( indent );
+          if let Some( ref color ) = ct.color
+          {
+            output.push_str( &DecoratedText::from( line.to_string() ).with_color( color.clone() ).render() );
+          }
+          else
+          {
+            output.push_str( line );
+          }
+          output.push( '\n' );
+        }
+      }
+    }
+  }
 }
 
 mod auto_fit;
 mod rendering;
 mod row_rendering;
+mod parse;
+mod stream;
 
 impl TableFormatter
 {
@@ -466,6 +515,7 @@ impl super::Format for TableFormatter
 {
   fn format( &self, data : &crate::TableView ) -> Result< String, super::FormatError >
   {
-    Ok( self.format_internal( &data.metadata.column_names, &data.rows, &data.row_details ) )
+    let formatter = self.aligned_for( &data.metadata.column_types );
+    Ok( formatter.format_internal( &data.metadata.column_names, &data.rows, &data.row_details ) )
   }
 }
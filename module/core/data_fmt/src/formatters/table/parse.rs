@@ -0,0 +1,250 @@
+//! `Parse` support for `TableFormatter` — CSV, TSV and Markdown styles
+//!
+//! Only the delimiter-based styles have an unambiguous inverse. Aligned
+//! plain-text styles (plain, bordered, grid, unicode, compact, minimal) pad
+//! cells with spaces and may wrap or fold them, so they are rejected with
+//! `ParseError::UnsupportedOperation`.
+//!
+//! Every style undoes exactly the escaping its formatter applies: RFC 4180
+//! quoting for CSV (Fix BUG-021), `\|` for Markdown pipes (Fix BUG-022), and the
+//! `\n` escape CSV/TSV use for embedded newlines — plus `\\` for backslashes
+//! when `TableConfig::with_escape_backslashes( true )` is set.
+//! ANSI sequences (colored headers or borders) are stripped, and heading/footer
+//! rules are skipped.
+
+use super::TableFormatter;
+use crate::{ TableView, TableMetadata, Heading, HEADING_LEAD_WIDTH, HEADING_RULE_CHAR };
+use crate::ansi_str::strip_ansi;
+use crate::formatters::{ Parse, ParseError };
+use color_tools::DecoratedText;
+
+/// One logical record with the 1-based line it started on
+type Record = ( usize, Vec< String > );
+
+impl Parse for TableFormatter
+{
+  fn parse( &self, input : &str ) -> Result< TableView, ParseError >
+  {
+    let titles : Vec< String > = [ self.config.heading_ref(), self.config.footer_ref() ]
+      .into_iter()
+      .flatten()
+      .map( Heading::content_str )
+      .collect();
+    let records = if self.config.is_csv()
+    {
+      parse_csv( input, &titles, self.config.escapes_backslashes() )?
+    }
+    else if self.config.is_csv_or_tsv()
+    {
+      parse_tsv( input, &titles, self.config.escapes_backslashes() )
+    }
+    else if self.config.is_markdown()
+    {
+      parse_markdown( input )
+    }
+    else
+    {
+      return Err( ParseError::UnsupportedOperation(
+        "only CSV, TSV and Markdown table styles can be parsed; aligned styles are ambiguous".to_string()
+      ) );
+    };
+
+    let mut records = records.into_iter();
+    let Some( ( _, headers ) ) = records.next() else
+    {
+      return Ok( TableView::new( TableMetadata::new( Vec::new() ), Vec::new() ) );
+    };
+
+    let mut rows = Vec::new();
+    for ( line, record ) in records
+    {
+      if record.len() != headers.len()
+      {
+        return Err( ParseError::Syntax
+        {
+          line,
+          message : format!( "row has {} cells but the header has {}", record.len(), headers.len() ),
+        } );
+      }
+      rows.push( record.into_iter().map( DecoratedText::from ).collect() );
+    }
+
+    let mut view = TableView::new( TableMetadata::new( headers ), rows );
+    view.infer_column_types();
+    Ok( view )
+  }
+}
+
+/// Blank lines and heading/footer rules carry no table data.
+///
+/// A rule is a line of rule characters only, or a titled rule
+/// `─── title ───` with rule characters on both sides. A titled rule without
+/// trailing rule characters, which `Heading::render_line` emits when the
+/// title is wider than the table, is recognised only for one of the
+/// configured `titles`. A data row that merely starts with a rule character
+/// is kept.
+fn is_decoration( line : &str, titles : &[ String ] ) -> bool
+{
+  let trimmed = line.trim();
+  if trimmed.chars().all( | c | c == HEADING_RULE_CHAR )
+  {
+    return true;
+  }
+  let lead : String = core::iter::repeat_n( HEADING_RULE_CHAR, HEADING_LEAD_WIDTH ).collect();
+  let Some( rest ) = trimmed.strip_prefix( lead.as_str() ).and_then( | rest | rest.strip_prefix( ' ' ) ) else
+  {
+    return false;
+  };
+  let title = rest.trim_end_matches( HEADING_RULE_CHAR );
+  if title.len() < rest.len()
+  {
+    title.ends_with( ' ' ) && !title.trim().is_empty()
+  }
+  else
+  {
+    titles.iter().any( | configured | configured.trim() == title )
+  }
+}
+
+/// Undo the backslash escaping CSV/TSV apply to cells.
+///
+/// `\n` always becomes a newline. With `backslashes` set, `\\` becomes `\` and
+/// is consumed first, so `\\n` reads back as the text `\n`. Any other backslash
+/// is kept as written.
+fn unescape_cell( text : &str, backslashes : bool ) -> String
+{
+  let mut result = String::with_capacity( text.len() );
+  let mut chars = text.chars().peekable();
+  while let Some( ch ) = chars.next()
+  {
+    match ( ch, chars.peek() )
+    {
+      ( '\\', Some( '\\' ) ) if backslashes => { chars.next(); result.push( '\\' ); }
+      ( '\\', Some( 'n' ) ) => { chars.next(); result.push( '\n' ); }
+      _ => result.push( ch ),
+    }
+  }
+  result
+}
+
+/// RFC 4180 reader; a quoted field may span physical lines.
+fn parse_csv( input : &str, titles : &[ String ], backslashes : bool ) -> Result< Vec< Record >, ParseError >
+{
+  let mut records = Vec::new();
+  let mut fields : Vec< String > = Vec::new();
+  let mut field = String::new();
+  let mut in_quotes = false;
+  let mut record_line = 0;
+
+  for ( idx, raw ) in input.lines().enumerate()
+  {
+    let line = strip_ansi( raw );
+    if in_quotes
+    {
+      field.push( '\n' );
+    }
+    else
+    {
+      if is_decoration( &line, titles )
+      {
+        continue;
+      }
+      record_line = idx + 1;
+    }
+
+    let mut chars = line.chars().peekable();
+    while let Some( ch ) = chars.next()
+    {
+      match ( ch, in_quotes )
+      {
+        ( '"', true ) if chars.peek() == Some( &'"' ) =>
+        {
+          chars.next();
+          field.push( '"' );
+        }
+        ( '"', true ) => in_quotes = false,
+        ( '"', false ) if field.is_empty() => in_quotes = true,
+        ( ',', false ) => fields.push( unescape_cell( &core::mem::take( &mut field ), backslashes ) ),
+        _ => field.push( ch ),
+      }
+    }
+
+    if !in_quotes
+    {
+      fields.push( unescape_cell( &core::mem::take( &mut field ), backslashes ) );
+      records.push( ( record_line, core::mem::take( &mut fields ) ) );
+    }
+  }
+
+  if in_quotes
+  {
+    return Err( ParseError::Syntax { line : record_line, message : "unterminated quoted field".to_string() } );
+  }
+  Ok( records )
+}
+
+/// Tab-separated reader; TSV output never quotes.
+fn parse_tsv( input : &str, titles : &[ String ], backslashes : bool ) -> Vec< Record >
+{
+  input.lines()
+    .enumerate()
+    .map( | ( idx, raw ) | ( idx + 1, strip_ansi( raw ) ) )
+    .filter( | ( _, line ) | !is_decoration( line, titles ) )
+    .map( | ( line_no, line ) | ( line_no, line.split( '\t' ).map( | cell | unescape_cell( cell, backslashes ) ).collect() ) )
+    .collect()
+}
+
+/// GitHub-flavored Markdown table reader; the `|---|` separator row is skipped.
+fn parse_markdown( input : &str ) -> Vec< Record >
+{
+  let mut records = Vec::new();
+  for ( idx, raw ) in input.lines().enumerate()
+  {
+    let line = strip_ansi( raw );
+    let trimmed = line.trim();
+    if !trimmed.starts_with( '|' )
+    {
+      continue;
+    }
+
+    let cells = split_markdown_row( trimmed );
+    let is_separator = cells.iter().all( | cell |
+    {
+      let core = cell.trim_matches( ':' );
+      !core.is_empty() && core.chars().all( | c | c == '-' )
+    });
+    if !is_separator
+    {
+      records.push( ( idx + 1, cells ) );
+    }
+  }
+  records
+}
+
+/// Split `| a | b\|c |` on unescaped pipes into trimmed, unescaped cells.
+fn split_markdown_row( line : &str ) -> Vec< String >
+{
+  let mut cells = Vec::new();
+  let mut cell = String::new();
+  let mut chars = line.chars().peekable();
+  while let Some( ch ) = chars.next()
+  {
+    match ch
+    {
+      '\\' if chars.peek() == Some( &'|' ) =>
+      {
+        chars.next();
+        cell.push( '|' );
+      }
+      '|' => cells.push( core::mem::take( &mut cell ).trim().to_string() ),
+      _ => cell.push( ch ),
+    }
+  }
+  // A row starts and ends with `|`: drop the empty piece before the first pipe,
+  // and anything after the last one is trailing whitespace.
+  if !cells.is_empty()
+  {
+    cells.remove( 0 );
+  }
+  cells
+}
@@ -115,7 +115,7 @@ impl TableFormatter
       {
         if is_csv_or_tsv
         {
-          let escaped = ct.text.replace( '\n', "\\n" );
+          let escaped = self.escape_cell( &ct.text );
           if is_csv { Self::csv_quote( &escaped ) } else { escaped }
         }
         else if is_markdown
@@ -173,7 +173,7 @@ impl TableFormatter
         .iter()
         .map( | ct |
         {
-          let escaped = ct.text.replace( '\n', "\\n" );
+          let escaped = self.escape_cell( &ct.text );
           if is_csv { Self::csv_quote( &escaped ) } else { escaped }
         } )
         .collect();
@@ -230,6 +230,22 @@ impl TableFormatter
     }
   }
 
+  /// Escape a CSV/TSV cell onto one line: a newline becomes `\n`.
+  ///
+  /// With `TableConfig::with_escape_backslashes( true )` a backslash becomes
+  /// `\\` first, so text such as `C:\new` stays apart from an escaped newline.
+  fn escape_cell( &self, text : &str ) -> String
+  {
+    if self.config.escapes_backslashes()
+    {
+      text.replace( '\\', "\\\\" ).replace( '\n', "\\n" )
+    }
+    else
+    {
+      text.replace( '\n', "\\n" )
+    }
+  }
+
   /// Apply RFC 4180 quoting: wrap cell in double-quotes and double internal `"`
   /// when text contains comma or double-quote.
   ///
@@ -0,0 +1,180 @@
+//! Streaming (`StreamFormat`) support for `TableFormatter`
+//!
+//! Column widths are the only global property of a table, so the sink buffers
+//! at most `TableConfig::with_stream_window()` rows, measures them together with
+//! the headers, locks the widths, and from then on writes every row as soon as
+//! it arrives. Explicit `with_column_widths()` skips the window entirely
+//! (fixed-width mode). Rows arriving after the lock that are wider than their
+//! column are truncated with the configured truncation marker, so alignment is
+//! never broken mid-stream.
+//!
+//! Auto-wrap is not applied while streaming — it redistributes width budgets
+//! across the whole table. Column folding still applies, since the fold point
+//! only depends on the locked widths.
+
+use super::TableFormatter;
+use crate::TableMetadata;
+use crate::ansi_str::{ unicode_visual_len, truncate_cell };
+use crate::formatters::{ FormatError, StreamFormat, RowSink };
+use crate::formatters::stream_trait::{ check_row_len, check_not_finished, write_str, flush };
+use color_tools::DecoratedText;
+use std::borrow::Cow;
+
+impl StreamFormat for TableFormatter
+{
+  fn row_sink< 'a, W : std::io::Write + 'a >( &'a self, metadata : &TableMetadata, out : W ) -> Result< Box< dyn RowSink + 'a >, FormatError >
+  {
+    let mut sink = TableRowSink
+    {
+      formatter : self.aligned_for( &metadata.column_types ),
+      headers : metadata.column_names.clone(),
+      pending_rows : Vec::new(),
+      pending_details : Vec::new(),
+      layout : None,
+      out,
+      rows_written : 0,
+      finished : false,
+    };
+    if !self.config.col_widths_override().is_empty() || self.config.stream_window_size() == 0
+    {
+      sink.lock_widths()?;
+    }
+    Ok( Box::new( sink ) )
+  }
+}
+
+/// Widths and fold point fixed once the look-ahead window is consumed
+struct LockedLayout
+{
+  widths : Vec< usize >,
+  fold_point : usize,
+}
+
+/// `RowSink` behind `TableFormatter::row_sink`
+struct TableRowSink< 'a, W : std::io::Write >
+{
+  formatter : Cow< 'a, TableFormatter >,
+  headers : Vec< String >,
+  pending_rows : Vec< Vec< DecoratedText > >,
+  pending_details : Vec< Option< DecoratedText > >,
+  layout : Option< LockedLayout >,
+  out : W,
+  rows_written : usize,
+  finished : bool,
+}
+
+impl< W : std::io::Write > TableRowSink< '_, W >
+{
+  /// Measure headers plus buffered rows, write the table head, then drain the buffer.
+  fn lock_widths( &mut self ) -> Result< (), FormatError >
+  {
+    let fmt = &*self.formatter;
+    let widths = fmt.calculate_column_widths_for_rows( &self.headers, &self.pending_rows );
+    let fold_point = if fmt.should_auto_fold() { fmt.determine_fold_point( &widths ) } else { widths.len() };
+
+    // IC-3: no columns → empty output, same as `Format::format()`.
+    if !self.headers.is_empty()
+    {
+      let primary_widths = &widths[ ..fold_point ];
+      let mut head = String::new();
+      crate::config::render_rule_if_present( &mut head, fmt.config.heading_ref(), fmt.compute_total_row_width( primary_widths ) );
+      fmt.format_top_border_if_needed( &mut head, primary_widths );
+      fmt.format_header_with_color( &mut head, &self.headers[ ..fold_point ], primary_widths );
+      fmt.format_header_separator( &mut head, primary_widths );
+      write_str( &mut self.out, &head )?;
+    }
+
+    self.layout = Some( LockedLayout { widths, fold_point } );
+    let rows = core::mem::take( &mut self.pending_rows );
+    let details = core::mem::take( &mut self.pending_details );
+    for ( row, detail ) in rows.iter().zip( details )
+    {
+      self.emit_row( row, detail.as_ref() )?;
+    }
+    Ok( () )
+  }
+
+  /// Render one row against the locked layout and write it out.
+  fn emit_row( &mut self, row : &[ DecoratedText ], detail : Option< &DecoratedText > ) -> Result< (), FormatError >
+  {
+    let Some( layout ) = self.layout.as_ref() else { return Ok( () ) };
+    if self.headers.is_empty()
+    {
+      return Ok( () );
+    }
+    let fmt = &*self.formatter;
+    let marker = fmt.config.trunc_marker();
+
+    // Cells wider than their locked column are cut down so the row stays aligned.
+    let fitted : Vec< DecoratedText > = row.iter().zip( &layout.widths ).map( | ( cell, &width ) |
+    {
+      let cell_width = cell.text.lines().map( unicode_visual_len ).max().unwrap_or( 0 );
+      if cell_width <= width
+      {
+        cell.clone()
+      }
+      else
+      {
+        let mut fitted = cell.clone();
+        fitted.text = truncate_cell( &cell.text, width, marker );
+        fitted
+      }
+    }).collect();
+
+    let mut chunk = String::new();
+    if self.rows_written > 0
+    {
+      fmt.format_inter_row_sep_if_needed( &mut chunk, &layout.widths[ ..layout.fold_point ] );
+    }
+    fmt.format_body_row( &mut chunk, self.rows_written, &fitted, detail, &self.headers, &layout.widths, layout.fold_point );
+    write_str( &mut self.out, &chunk )?;
+    self.rows_written += 1;
+    Ok( () )
+  }
+}
+
+impl< W : std::io::Write > RowSink for TableRowSink< '_, W >
+{
+  fn write_row_with_detail( &mut self, row : &[ DecoratedText ], detail : Option< &DecoratedText > ) -> Result< (), FormatError >
+  {
+    check_not_finished( self.finished )?;
+    check_row_len( self.headers.len(), row.len() )?;
+
+    if self.layout.is_some()
+    {
+      return self.emit_row( row, detail );
+    }
+
+    self.pending_rows.push( row.to_vec() );
+    self.pending_details.push( detail.cloned() );
+    if self.pending_rows.len() >= self.formatter.config.stream_window_size()
+    {
+      self.lock_widths()?;
+    }
+    Ok( () )
+  }
+
+  fn finish( &mut self ) -> Result< (), FormatError >
+  {
+    check_not_finished( self.finished )?;
+    self.finished = true;
+    if self.layout.is_none()
+    {
+      self.lock_widths()?;
+    }
+
+    if let Some( layout ) = self.layout.as_ref()
+    {
+      if !self.headers.is_empty()
+      {
+        let fmt = &*self.formatter;
+        let primary_widths = &layout.widths[ ..layout.fold_point ];
+        let mut tail = String::new();
+        fmt.format_bottom_border_if_needed( &mut tail, primary_widths );
+        crate::config::render_rule_if_present( &mut tail, fmt.config.footer_ref(), fmt.compute_total_row_width( primary_widths ) );
+        write_str( &mut self.out, &tail )?;
+      }
+    }
+    flush( &mut self.out )
+  }
+}
@@ -15,7 +15,7 @@
 //! # }
 //! ```
 
-use crate::{ TableView, Heading, formatters::{ Format, FormatError } };
+use crate::{ TableView, Heading, CellValue, formatters::{ Format, FormatError } };
 
 /// TOML output formatter
 ///
@@ -104,20 +104,51 @@ impl Default for TomlFormatter
 
 impl Format for TomlFormatter
 {
+  /// Format `TableView` as a TOML array of tables, one `[[row]]` per row.
+  ///
+  /// Values come from `TableView::cell_value()` and map onto native TOML types:
+  /// integers, floats, booleans, offset datetimes, arrays and inline tables.
+  /// TOML has no null, so `Null` cells (and null list elements) are omitted;
+  /// bytes are base64 strings.
   fn format( &self, data : &TableView ) -> Result< String, FormatError >
   {
-    let rows = super::table_view_to_row_maps( data );
-
-    // TOML requires wrapping in a structure for array of tables
-    #[ derive( serde::Serialize ) ]
-    struct TomlWrapper
+    let rows = ( 0..data.rows.len() ).map( | row |
     {
-      row : Vec< std::collections::HashMap< String, String > >,
-    }
+      let table : toml::Table = data.metadata.column_names
+        .iter()
+        .enumerate()
+        .filter_map( | ( col, name ) | toml_value( &data.cell_value( row, col ) ).map( | value | ( name.clone(), value ) ) )
+        .collect();
+      toml::Value::Table( table )
+    }).collect();
 
-    let wrapper = TomlWrapper { row : rows };
+    // TOML requires wrapping in a structure for array of tables
+    let mut wrapper = toml::Table::new();
+    wrapper.insert( "row".to_string(), toml::Value::Array( rows ) );
     toml::to_string( &wrapper )
       .map( | body | self.wrap_with_heading_footer( body ) )
       .map_err( | e | FormatError::Serialization( e.to_string() ) )
   }
 }
+
+/// Native TOML value for a cell, or `None` for values TOML cannot represent (null).
+fn toml_value( value : &CellValue ) -> Option< toml::Value >
+{
+  Some( match value
+  {
+    CellValue::Null => return None,
+    CellValue::Bool( b ) => toml::Value::Boolean( *b ),
+    CellValue::Int( i ) => toml::Value::Integer( *i ),
+    CellValue::Float( f ) => toml::Value::Float( *f ),
+    CellValue::Text( s ) => toml::Value::String( s.clone() ),
+    CellValue::Timestamp( _ ) => value.to_string().parse().map_or_else(
+      | _ | toml::Value::String( value.to_string() ),
+      toml::Value::Datetime,
+    ),
+    CellValue::Bytes( _ ) => toml::Value::String( value.to_string() ),
+    CellValue::List( items ) => toml::Value::Array( items.iter().filter_map( toml_value ).collect() ),
+    CellValue::Map( entries ) => toml::Value::Table(
+      entries.iter().filter_map( | ( k, v ) | toml_value( v ).map( | v | ( k.clone(), v ) ) ).collect()
+    ),
+  })
+}
@@ -14,7 +14,7 @@
 //! # }
 //! ```
 
-use crate::{ TableView, Heading, formatters::{ Format, FormatError } };
+use crate::{ TableView, Heading, formatters::{ Format, FormatError, Parse, ParseError } };
 
 /// YAML output formatter
 ///
@@ -106,10 +106,24 @@ impl Format for YamlFormatter
 {
   fn format( &self, data : &TableView ) -> Result< String, FormatError >
   {
-    let rows = super::table_view_to_row_maps( data );
+    let rows = super::table_view_to_typed_rows( data );
 
     serde_yaml_ng::to_string( &rows )
       .map( | body | self.wrap_with_heading_footer( body ) )
       .map_err( | e | FormatError::Serialization( e.to_string() ) )
   }
 }
+
+impl Parse for YamlFormatter
+{
+  /// Parse a YAML sequence of row mappings back into a `TableView`.
+  ///
+  /// Heading/footer rules are `#` comments and are skipped by the YAML parser.
+  /// Scalars and nested values are kept as typed `TableView::values`, as for JSON.
+  fn parse( &self, input : &str ) -> Result< TableView, ParseError >
+  {
+    let rows : Vec< super::OrderedRow > = serde_yaml_ng::from_str( input )
+      .map_err( | e | ParseError::Deserialization( e.to_string() ) )?;
+    Ok( super::row_maps_to_table_view( rows ) )
+  }
+}
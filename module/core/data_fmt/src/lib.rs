@@ -11,6 +11,14 @@
 //! - **10 Formatters**: Table (9 styles), Tree (3), Expanded (2), JSON, HTML (4),
 //!   SQL (4), YAML, TOML, Logfmt, Text (6)
 //! - **String Output**: All formatters return `String`, no direct console output
+//! - **Streaming Output**: `StreamFormat` writes rows incrementally into any `std::io::Write`
+//! - **Parsing**: `Parse` reads CSV/TSV/Markdown/JSON/YAML output back into `TableView`
+//! - **Typed Cells**: `CellValue` gives JSON/YAML/TOML/SQL real numbers, nulls, timestamps and nested values
+//! - **Queries**: sort, filter, project, group-by aggregates and totals directly on `TableView`
+//! - **Derive**: `#[derive(TableRow)]` turns structs into rows, so `Vec< MyStruct >` formats directly
+//! - **Pager**: `Pager` browses wide tables and trees interactively — frozen header/column, search, column hiding
+//! - **Diff**: `old.diff( &new, &[ "Key" ] )` compares two snapshots by key — unified or side-by-side coloured tables, RFC 6902 JSON Patch
+//! - **Spreadsheets**: `XlsxFormatter`/`OdsFormatter` write typed, styled workbooks via `Format::format_bytes`
 
 // Inlining every public item in a 30+ type / 10-formatter library adds noise
 // without measurable gain — the lint is too aggressive for a library of this size.
@@ -159,11 +167,15 @@
 
 // Module declarations
 #[ cfg( feature = "enabled" ) ] mod data;
+#[ cfg( feature = "enabled" ) ] mod cell_value;
 #[ cfg( feature = "enabled" ) ] mod config;
 #[ cfg( feature = "enabled" ) ] mod ansi_str;
 #[ cfg( feature = "enabled" ) ] mod builder;
 #[ cfg( feature = "enabled" ) ] mod table_tree;
 #[ cfg( feature = "enabled" ) ] mod wrap;
+#[ cfg( feature = "enabled" ) ] mod query;
+#[ cfg( feature = "enabled" ) ] mod diff;
+#[ cfg( feature = "enabled" ) ] mod table_row;
 #[ cfg( feature = "enabled" ) ] pub mod conversions;
 #[ cfg( feature = "enabled" ) ] pub mod formatters;
 
@@ -173,6 +185,9 @@ pub mod themes;
 #[ cfg( feature = "quantity" ) ]
 pub mod quantity;
 
+#[ cfg( feature = "pager" ) ]
+mod pager;
+
 // Public re-exports - Core data types
 #[ cfg( feature = "enabled" ) ]
 pub use data::{
@@ -180,6 +195,8 @@ pub use data::{
   TableView, TableMetadata, DataType, TableShapedView
 };
 #[ cfg( feature = "enabled" ) ]
+pub use cell_value::CellValue;
+#[ cfg( feature = "enabled" ) ]
 pub use color_tools::DecoratedText;
 #[ cfg( feature = "enabled" ) ]
 pub use config::{
@@ -193,6 +210,14 @@ pub use ansi_str::{ visual_len, pad_to_width, truncate_cell, strip_ansi };
 #[ cfg( feature = "enabled" ) ]
 pub use wrap::{ WrapConfig, WrapFormatter, BreakStrategy, Overflow };
 #[ cfg( feature = "enabled" ) ]
+pub use query::{ SortKey, SortOrder, RowRef, Aggregate, AggregateFn, Totals, QueryError };
+#[ cfg( feature = "enabled" ) ]
+pub use diff::{ TableDiff, RowDiff, RowChange, DiffSummary, DiffSide, DiffError };
+#[ cfg( feature = "enabled" ) ]
+pub use table_row::{ TableRow, ToCellValue };
+#[ cfg( all( feature = "enabled", feature = "derive" ) ) ]
+pub use data_fmt_meta::TableRow;
+#[ cfg( feature = "enabled" ) ]
 pub use builder::TreeBuilder;
 #[ cfg( feature = "enabled" ) ]
 pub use table_tree::RowBuilder;
@@ -201,6 +226,14 @@ pub use table_tree::RowBuilder;
 #[ cfg( feature = "enabled" ) ]
 pub use formatters::{ Format, FormatError };
 
+// Streaming format trait
+#[ cfg( feature = "enabled" ) ]
+pub use formatters::{ StreamFormat, RowSink, DEFAULT_STREAM_WINDOW };
+
+// Parse trait (inverse of Format)
+#[ cfg( feature = "enabled" ) ]
+pub use formatters::{ Parse, ParseError };
+
 // Conditional formatter exports — all require `enabled` because `mod formatters`
 // is gated on `enabled`. Without it the module doesn't exist and the re-exports
 // would fail to compile even though the format sub-feature is active.
@@ -247,7 +280,7 @@ pub use formatters::{ HtmlFormatter, HtmlVariant };
   feature = "sql_mysql",
   feature = "sql_sqlite"
 ) ) ) ]
-pub use formatters::{ SqlFormatter, SqlVariant };
+pub use formatters::{ SqlFormatter, SqlVariant, DEFAULT_SQL_BATCH_SIZE };
 
 #[ cfg( all( feature = "enabled", feature = "format_json" ) ) ]
 pub use formatters::JsonFormatter;
@@ -261,10 +294,20 @@ pub use formatters::TomlFormatter;
 #[ cfg( all( feature = "enabled", feature = "format_text" ) ) ]
 pub use formatters::{ TextFormatter, TextVariant };
 
+#[ cfg( all( feature = "enabled", feature = "format_xlsx" ) ) ]
+pub use formatters::XlsxFormatter;
+
+#[ cfg( all( feature = "enabled", feature = "format_ods" ) ) ]
+pub use formatters::OdsFormatter;
+
 // Color themes (feature-gated)
 #[ cfg( feature = "themes" ) ]
 pub use themes::{ ColorTheme, ColorThemeBuilder };
 
+// Interactive viewer (feature-gated)
+#[ cfg( feature = "pager" ) ]
+pub use pager::{ Pager, PagerKey };
+
 // Quantity formatters (feature-gated)
 #[ cfg( feature = "quantity" ) ]
 pub use quantity::{ QuantityStyle, duration_6ch, duration_human, duration_human_hours, duration_ms, number_compact, bytes_iec, bytes_compact_si, bytes_human, bytes_si };
@@ -0,0 +1,705 @@
+//! Interactive terminal viewer for tables and trees (feature `pager`)
+//!
+//! `TableFormatter::with_config( auto_fit )` has to squeeze a wide table into
+//! the terminal by truncating or wrapping. `Pager` instead shows a window of
+//! it and lets the user move that window:
+//!
+//! - the header stays on screen while rows scroll vertically
+//! - the first column(s) stay on screen while the others scroll horizontally
+//! - `/` searches cell text, `n`/`N` jump between matches
+//! - `x` hides a column, `X` shows all hidden columns again
+//!
+//! Each frame is an ordinary `TableFormatter` rendering of the visible rows and
+//! columns, so borders, separators, colours and alignment follow the
+//! `TableConfig` given to `with_config`. Column widths are measured once over
+//! the whole table with `visual_len` semantics, so they don't jitter while
+//! scrolling. Trees and plain text are shown as scrollable lines, optionally
+//! wrapped with `WrapFormatter`.
+//!
+//! `Pager::run` drives the terminal (via `crossterm`); `handle_key` and
+//! `render` are the terminal-independent core it is built from.
+//!
+//! # Keys
+//!
+//! | Key | Action |
+//! |-----|--------|
+//! | `↑`/`k`, `↓`/`j` | Scroll one row |
+//! | `PgUp`/`b`, `PgDn`/`Space` | Scroll one page |
+//! | `Home`/`g`, `End`/`G` | First / last page |
+//! | `←`/`h`, `→`/`l` | Scroll one column (text: 8 characters) |
+//! | `/` … `Enter` | Search (case-insensitive); empty query clears it |
+//! | `n`, `N` | Next / previous match |
+//! | `x`, `X` | Hide the first scrolled column / show all columns |
+//! | `f` | Toggle frozen columns |
+//! | `w` | Toggle wrapping (text only) |
+//! | `q`, `Esc` | Quit |
+
+use std::io::{ self, Write, IsTerminal };
+use crate::ansi_str::{ unicode_visual_len, unicode_visual_byte_offset, truncate_cell, strip_ansi };
+use crate::{ TableView, TableMetadata, TableConfig, TableFormatter, TreeNode, TreeFormatter, WrapConfig, WrapFormatter, Format };
+use color_tools::DecoratedText;
+
+/// Horizontal scroll step for text content, in display columns.
+const TEXT_SCROLL_STEP : usize = 8;
+/// Highlight for search matches.
+const MATCH_COLOR : &str = "\x1b[7m";
+/// Highlight for the match the last jump landed on.
+const CURRENT_MATCH_COLOR : &str = "\x1b[1;7m";
+/// Style of the status line.
+const STATUS_COLOR : &str = "\x1b[7m";
+const RESET : &str = "\x1b[0m";
+
+/// Key input understood by `Pager::handle_key`
+///
+/// Terminal-independent so the viewer can be driven by tests or another
+/// event source; `Pager::run` maps `crossterm` key events onto it.
+#[ derive( Debug, Clone, Copy, PartialEq, Eq ) ]
+pub enum PagerKey
+{
+  /// Arrow up
+  Up,
+  /// Arrow down
+  Down,
+  /// Arrow left
+  Left,
+  /// Arrow right
+  Right,
+  /// Page up
+  PageUp,
+  /// Page down
+  PageDown,
+  /// Home
+  Home,
+  /// End
+  End,
+  /// Enter / Return
+  Enter,
+  /// Backspace
+  Backspace,
+  /// Escape
+  Escape,
+  /// Printable character
+  Char( char ),
+}
+
+/// What the pager shows
+#[ derive( Debug, Clone ) ]
+enum Content
+{
+  /// A table and the natural display width of each column
+  Table { view : TableView, widths : Vec< usize > },
+  /// Pre-rendered lines (trees, plain text), ANSI removed
+  Lines( Vec< String > ),
+}
+
+/// Interactive viewer over a `TableView`, a `TreeNode` or plain text
+///
+/// # Examples
+///
+/// ```
+/// # #[ cfg( feature = "pager" ) ]
+/// # {
+/// use data_fmt::{ RowBuilder, Pager, PagerKey, TableConfig };
+///
+/// let view = RowBuilder::new( vec![ "Name".into(), "Role".into(), "Team".into() ] )
+///   .add_row( vec![ "Alice".into(), "admin".into(), "core".into() ] )
+///   .add_row( vec![ "Bob".into(), "dev".into(), "web".into() ] )
+///   .build_view();
+///
+/// let mut pager = Pager::new( view ).with_config( TableConfig::plain() );
+/// pager.handle_key( PagerKey::Right );
+/// let frame = pager.render( 20, 5 );
+/// assert!( frame[ 0 ].contains( "Name" ) );  // frozen first column
+/// assert!( !frame[ 0 ].contains( "Role" ) ); // scrolled out
+/// assert!( frame[ 0 ].contains( "Team" ) );
+///
+/// // Interactive use: `pager.run()?;`
+/// # }
+/// ```
+#[ derive( Debug, Clone ) ]
+pub struct Pager
+{
+  content : Content,
+  config : TableConfig,
+  frozen_columns : usize,
+  frozen : bool,
+  wrap : bool,
+  /// First visible row (table) or line (text)
+  row : usize,
+  /// First visible scrollable column (table) or display column (text)
+  column : usize,
+  hidden : Vec< bool >,
+  /// Active search query, lowercased
+  query : Option< String >,
+  /// Matching `( row, column )` cells; `( line, 0 )` for text
+  matches : Vec< ( usize, usize ) >,
+  current_match : Option< usize >,
+  /// Search text being typed after `/`
+  input : Option< String >,
+  /// Rows per page and scrolled columns shown in the last frame
+  page_rows : usize,
+  visible_columns : usize,
+}
+
+impl Pager
+{
+  /// Create a pager over a table
+  ///
+  /// The first column is frozen; the default `TableConfig` is used for rendering.
+  pub fn new( view : TableView ) -> Self
+  {
+    let config = TableConfig::default();
+    let widths = natural_widths( &view, &config );
+    let columns = widths.len();
+    Self::with_content( Content::Table { view, widths }, config, columns )
+  }
+
+  /// Create a pager over a tree, rendered by `TreeFormatter` with each item's `Display` form
+  pub fn from_tree< T : std::fmt::Display >( tree : &TreeNode< T > ) -> Self
+  {
+    Self::from_text( &TreeFormatter::new().format( tree, ToString::to_string ) )
+  }
+
+  /// Create a pager over lines of text
+  pub fn from_text( text : &str ) -> Self
+  {
+    let lines = text.lines().map( strip_ansi ).collect();
+    Self::with_content( Content::Lines( lines ), TableConfig::default(), 0 )
+  }
+
+  fn with_content( content : Content, config : TableConfig, columns : usize ) -> Self
+  {
+    Self
+    {
+      content,
+      config,
+      frozen_columns : 1,
+      frozen : true,
+      wrap : false,
+      row : 0,
+      column : 0,
+      hidden : vec![ false; columns ],
+      query : None,
+      matches : Vec::new(),
+      current_match : None,
+      input : None,
+      page_rows : 1,
+      visible_columns : 1,
+    }
+  }
+
+  /// Render tables with this configuration (borders, separators, colours, alignment)
+  ///
+  /// Heading and footer rules are not shown; the status line takes their place.
+  /// `max_column_width` and `min_column_width` bound the column widths.
+  #[ must_use ]
+  pub fn with_config( mut self, config : TableConfig ) -> Self
+  {
+    if let Content::Table { view, widths } = &mut self.content
+    {
+      *widths = natural_widths( view, &config );
+    }
+    self.config = config;
+    self
+  }
+
+  /// Number of leading columns that stay visible while scrolling horizontally (default 1)
+  #[ must_use ]
+  pub fn with_frozen_columns( mut self, count : usize ) -> Self
+  {
+    self.frozen_columns = count;
+    self
+  }
+
+  /// Wrap long text lines instead of scrolling horizontally (text content only)
+  #[ must_use ]
+  pub fn with_wrap( mut self, wrap : bool ) -> Self
+  {
+    self.wrap = wrap;
+    self
+  }
+
+  /// Apply one key press
+  ///
+  /// Returns `false` when the key asks the viewer to quit.
+  pub fn handle_key( &mut self, key : PagerKey ) -> bool
+  {
+    if let Some( input ) = &mut self.input
+    {
+      match key
+      {
+        PagerKey::Char( c ) => input.push( c ),
+        PagerKey::Backspace => { input.pop(); }
+        PagerKey::Escape => self.input = None,
+        PagerKey::Enter =>
+        {
+          let query = self.input.take().unwrap_or_default();
+          self.search( &query );
+        }
+        _ => {}
+      }
+      return true;
+    }
+
+    let page = self.page_rows.max( 1 );
+    let step = if matches!( self.content, Content::Lines( _ ) ) { TEXT_SCROLL_STEP } else { 1 };
+    match key
+    {
+      PagerKey::Char( 'q' ) | PagerKey::Escape => return false,
+      PagerKey::Up | PagerKey::Char( 'k' ) => self.row = self.row.saturating_sub( 1 ),
+      PagerKey::Down | PagerKey::Char( 'j' ) => self.row += 1,
+      PagerKey::PageUp | PagerKey::Char( 'b' ) => self.row = self.row.saturating_sub( page ),
+      PagerKey::PageDown | PagerKey::Char( ' ' ) => self.row += page,
+      PagerKey::Home | PagerKey::Char( 'g' ) => self.row = 0,
+      PagerKey::End | PagerKey::Char( 'G' ) => self.row = usize::MAX,
+      PagerKey::Left | PagerKey::Char( 'h' ) => self.column = self.column.saturating_sub( step ),
+      PagerKey::Right | PagerKey::Char( 'l' ) => self.column += step,
+      PagerKey::Char( '/' ) => self.input = Some( String::new() ),
+      PagerKey::Char( 'n' ) => self.jump( true ),
+      PagerKey::Char( 'N' ) => self.jump( false ),
+      PagerKey::Char( 'x' ) => self.hide_first_scrolled(),
+      PagerKey::Char( 'X' ) =>
+      {
+        self.hidden.iter_mut().for_each( | h | *h = false );
+        self.refresh_matches();
+      }
+      PagerKey::Char( 'f' ) => self.frozen = !self.frozen,
+      PagerKey::Char( 'w' ) => self.wrap = !self.wrap,
+      _ => {}
+    }
+    self.clamp();
+    true
+  }
+
+  /// Render one frame of `height` lines, each at most `width` display columns
+  ///
+  /// The last line is the status line (position, hidden columns, search state).
+  pub fn render( &mut self, width : usize, height : usize ) -> Vec< String >
+  {
+    if width == 0 || height == 0
+    {
+      return Vec::new();
+    }
+    let body_height = height - 1;
+    let mut frame = match &self.content
+    {
+      Content::Table { .. } => self.render_table( width, body_height ),
+      Content::Lines( _ ) => self.render_lines( width, body_height ),
+    };
+    frame.truncate( body_height );
+    frame.resize( body_height, String::new() );
+    frame.push( self.status_line( width ) );
+    frame
+  }
+
+  /// Run the viewer on the terminal until the user quits
+  ///
+  /// When stdin or stdout is not a terminal, writes the whole content once
+  /// (the table through `TableFormatter`) and returns — like `less -F`.
+  ///
+  /// # Errors
+  ///
+  /// Returns any I/O error from the terminal.
+  pub fn run( mut self ) -> io::Result< () >
+  {
+    use crossterm::{ cursor, event, execute, queue, style, terminal };
+
+    let mut stdout = io::stdout();
+    if !stdout.is_terminal() || !io::stdin().is_terminal()
+    {
+      return self.write_all( &mut stdout );
+    }
+
+    terminal::enable_raw_mode()?;
+    let _restore = TerminalGuard;
+    execute!( stdout, terminal::EnterAlternateScreen, cursor::Hide )?;
+    loop
+    {
+      let ( width, height ) = terminal::size()?;
+      let frame = self.render( usize::from( width ), usize::from( height ) );
+      for ( idx, line ) in frame.iter().enumerate()
+      {
+        let y = u16::try_from( idx ).unwrap_or( u16::MAX );
+        queue!( stdout, cursor::MoveTo( 0, y ), style::Print( line ), style::Print( RESET ), terminal::Clear( terminal::ClearType::UntilNewLine ) )?;
+      }
+      stdout.flush()?;
+
+      let event::Event::Key( key ) = event::read()? else { continue };
+      if key.kind == event::KeyEventKind::Release
+      {
+        continue;
+      }
+      if key.modifiers.contains( event::KeyModifiers::CONTROL ) && key.code == event::KeyCode::Char( 'c' )
+      {
+        break;
+      }
+      let key = match key.code
+      {
+        event::KeyCode::Up => PagerKey::Up,
+        event::KeyCode::Down => PagerKey::Down,
+        event::KeyCode::Left => PagerKey::Left,
+        event::KeyCode::Right => PagerKey::Right,
+        event::KeyCode::PageUp => PagerKey::PageUp,
+        event::KeyCode::PageDown => PagerKey::PageDown,
+        event::KeyCode::Home => PagerKey::Home,
+        event::KeyCode::End => PagerKey::End,
+        event::KeyCode::Enter => PagerKey::Enter,
+        event::KeyCode::Backspace => PagerKey::Backspace,
+        event::KeyCode::Esc => PagerKey::Escape,
+        event::KeyCode::Char( c ) => PagerKey::Char( c ),
+        _ => continue,
+      };
+      if !self.handle_key( key )
+      {
+        break;
+      }
+    }
+    Ok( () )
+  }
+
+  /// Non-interactive fallback: the whole content, unpaged.
+  fn write_all( &self, out : &mut impl Write ) -> io::Result< () >
+  {
+    match &self.content
+    {
+      Content::Table { view, .. } =>
+      {
+        let text = TableFormatter::with_config( self.config.clone() ).format( view ).map_err( io::Error::other )?;
+        out.write_all( text.as_bytes() )?;
+      }
+      Content::Lines( lines ) =>
+      {
+        for line in lines
+        {
+          writeln!( out, "{line}" )?;
+        }
+      }
+    }
+    out.flush()
+  }
+
+  // --- Table content ---
+
+  /// Frozen column indices, then scrollable column indices (hidden ones excluded).
+  fn column_split( &self ) -> ( Vec< usize >, Vec< usize > )
+  {
+    let frozen_count = if self.frozen { self.frozen_columns } else { 0 };
+    let visible = ( 0..self.hidden.len() ).filter( | &c | !self.hidden[ c ] );
+    let ( frozen, scrollable ) : ( Vec< usize >, Vec< usize > ) = visible.partition( | &c | c < frozen_count );
+    ( frozen, scrollable )
+  }
+
+  fn render_table( &mut self, width : usize, body_height : usize ) -> Vec< String >
+  {
+    let Content::Table { view, widths } = &self.content else { return Vec::new() };
+    let ( frozen, scrollable ) = self.column_split();
+    // A single column wider than the screen is cut to fit.
+    let widths : Vec< usize > = widths.iter().map( | &w | w.min( width ) ).collect();
+
+    let mut columns = frozen;
+    let mut shown = 0;
+    for &col in scrollable.iter().skip( self.column )
+    {
+      columns.push( col );
+      if shown > 0 && self.table_width( view, &columns, &widths ) > width
+      {
+        columns.pop();
+        break;
+      }
+      shown += 1;
+    }
+    // Header lines (plus any closing border) take space from the rows.
+    let chrome = self.render_window( view, &columns, &widths, 0..0 ).len();
+
+    self.visible_columns = shown.max( 1 );
+    self.page_rows = body_height.saturating_sub( chrome ).max( 1 );
+    self.clamp();
+
+    let Content::Table { view, .. } = &self.content else { return Vec::new() };
+    let rows = self.row..( self.row + self.page_rows ).min( view.rows.len() );
+    self.render_window( view, &columns, &widths, rows )
+      .into_iter()
+      .map( | line | clip( &line, width ) )
+      .collect()
+  }
+
+  /// Rendered width of a table with just these columns.
+  fn table_width( &self, view : &TableView, columns : &[ usize ], widths : &[ usize ] ) -> usize
+  {
+    self.render_window( view, columns, widths, 0..0 ).iter().map( | line | unicode_visual_len( line ) ).max().unwrap_or( 0 )
+  }
+
+  /// Render the given columns and rows through `TableFormatter` at fixed widths.
+  fn render_window( &self, view : &TableView, columns : &[ usize ], widths : &[ usize ], rows : std::ops::Range< usize > ) -> Vec< String >
+  {
+    let marker = self.config.trunc_marker();
+    let fit = | text : &str, col : usize | truncate_cell( text, widths[ col ], marker );
+    let names = columns.iter().map( | &c | fit( &view.metadata.column_names[ c ], c ) ).collect();
+    let types = columns.iter().map( | &c | view.metadata.column_types.get( c ).cloned().unwrap_or_default() ).collect();
+    let cells = rows.clone().map( | r |
+    {
+      columns.iter().map( | &c |
+      {
+        let mut cell = view.rows[ r ].get( c ).cloned().unwrap_or_default();
+        cell.text = fit( &cell.text, c );
+        match self.matches.iter().position( | &m | m == ( r, c ) )
+        {
+          Some( idx ) if Some( idx ) == self.current_match => cell.with_color( CURRENT_MATCH_COLOR ),
+          Some( _ ) => cell.with_color( MATCH_COLOR ),
+          None => cell,
+        }
+      }).collect()
+    }).collect();
+    let details = rows.map( | r | view.row_details.get( r ).cloned().flatten() ).collect();
+
+    let window = TableView
+    {
+      metadata : TableMetadata::with_types( names, types ),
+      rows : cells,
+      row_details : details,
+      values : Vec::new(),
+    };
+    let config = self.config.clone()
+      .without_rules()
+      .with_column_widths( columns.iter().map( | &c | widths[ c ] ).collect() );
+    TableFormatter::with_config( config )
+      .format( &window )
+      .unwrap_or_default()
+      .lines()
+      .map( str::to_string )
+      .collect()
+  }
+
+  fn hide_first_scrolled( &mut self )
+  {
+    let ( frozen, scrollable ) = self.column_split();
+    // Keep at least one column on screen.
+    if frozen.len() + scrollable.len() <= 1
+    {
+      return;
+    }
+    if let Some( &col ) = scrollable.get( self.column )
+    {
+      self.hidden[ col ] = true;
+      self.refresh_matches();
+    }
+  }
+
+  // --- Text content ---
+
+  fn render_lines( &mut self, width : usize, body_height : usize ) -> Vec< String >
+  {
+    self.page_rows = body_height.max( 1 );
+    self.clamp();
+    let Content::Lines( lines ) = &self.content else { return Vec::new() };
+    let current = self.current_match.and_then( | idx | self.matches.get( idx ) ).map( | m | m.0 );
+    let wrapper = WrapFormatter::with_config( WrapConfig::new().width( width ) );
+
+    let mut frame = Vec::with_capacity( body_height );
+    for ( idx, line ) in lines.iter().enumerate().skip( self.row )
+    {
+      if frame.len() >= body_height
+      {
+        break;
+      }
+      let pieces = if self.wrap && !line.is_empty()
+      {
+        wrapper.wrap( line )
+      }
+      else
+      {
+        let start = unicode_visual_byte_offset( line, self.column );
+        vec![ clip( &line[ start.. ], width ) ]
+      };
+      for piece in pieces
+      {
+        frame.push( if Some( idx ) == current { format!( "{CURRENT_MATCH_COLOR}{piece}{RESET}" ) } else { piece } );
+      }
+    }
+    frame
+  }
+
+  // --- Search ---
+
+  fn search( &mut self, query : &str )
+  {
+    self.query = if query.is_empty() { None } else { Some( query.to_lowercase() ) };
+    self.current_match = None;
+    self.refresh_matches();
+    // Start from the first match at or below the top of the screen.
+    let row = self.row;
+    let first = self.matches.iter().position( | m | m.0 >= row ).or_else( || ( !self.matches.is_empty() ).then_some( 0 ) );
+    if let Some( idx ) = first
+    {
+      self.go_to_match( idx );
+    }
+  }
+
+  fn refresh_matches( &mut self )
+  {
+    self.matches.clear();
+    let Some( query ) = &self.query else { return };
+    let hit = | text : &str | strip_ansi( text ).to_lowercase().contains( query.as_str() );
+    match &self.content
+    {
+      Content::Table { view, .. } =>
+      {
+        for ( r, row ) in view.rows.iter().enumerate()
+        {
+          for ( c, cell ) in row.iter().enumerate()
+          {
+            if !self.hidden.get( c ).copied().unwrap_or( true ) && hit( &cell.text )
+            {
+              self.matches.push( ( r, c ) );
+            }
+          }
+        }
+      }
+      Content::Lines( lines ) =>
+      {
+        self.matches.extend( lines.iter().enumerate().filter( | ( _, line ) | hit( line ) ).map( | ( idx, _ ) | ( idx, 0 ) ) );
+      }
+    }
+    if self.current_match.is_some_and( | idx | idx >= self.matches.len() )
+    {
+      self.current_match = None;
+    }
+  }
+
+  fn jump( &mut self, forward : bool )
+  {
+    if self.matches.is_empty()
+    {
+      return;
+    }
+    let count = self.matches.len();
+    let idx = match self.current_match
+    {
+      None => 0,
+      Some( idx ) if forward => ( idx + 1 ) % count,
+      Some( idx ) => ( idx + count - 1 ) % count,
+    };
+    self.go_to_match( idx );
+  }
+
+  /// Make match `idx` current and scroll it into view.
+  fn go_to_match( &mut self, idx : usize )
+  {
+    self.current_match = Some( idx );
+    let ( row, col ) = self.matches[ idx ];
+    if row < self.row || row >= self.row + self.page_rows
+    {
+      self.row = row;
+    }
+    if matches!( self.content, Content::Table { .. } )
+    {
+      let ( _, scrollable ) = self.column_split();
+      if let Some( pos ) = scrollable.iter().position( | &c | c == col )
+      {
+        if pos < self.column || pos >= self.column + self.visible_columns
+        {
+          self.column = pos;
+        }
+      }
+    }
+    self.clamp();
+  }
+
+  // --- Shared ---
+
+  /// Keep the scroll position within the content.
+  fn clamp( &mut self )
+  {
+    let ( rows, columns ) = match &self.content
+    {
+      Content::Table { view, .. } => ( view.rows.len(), self.column_split().1.len() ),
+      Content::Lines( lines ) => ( lines.len(), lines.iter().map( | l | unicode_visual_len( l ) ).max().unwrap_or( 0 ) ),
+    };
+    self.row = self.row.min( rows.saturating_sub( self.page_rows ) );
+    self.column = self.column.min( columns.saturating_sub( 1 ) );
+  }
+
+  fn status_line( &self, width : usize ) -> String
+  {
+    let text = if let Some( input ) = &self.input
+    {
+      format!( "/{input}" )
+    }
+    else
+    {
+      let mut parts = Vec::new();
+      match &self.content
+      {
+        Content::Table { view, .. } =>
+        {
+          let total = view.rows.len();
+          let last = ( self.row + self.page_rows ).min( total );
+          parts.push( format!( "rows {}-{last} of {total}", ( self.row + 1 ).min( last ) ) );
+          let ( _, scrollable ) = self.column_split();
+          if !scrollable.is_empty()
+          {
+            let last = ( self.column + self.visible_columns ).min( scrollable.len() );
+            parts.push( format!( "cols {}-{last} of {}", self.column + 1, scrollable.len() ) );
+          }
+          let hidden = self.hidden.iter().filter( | &&h | h ).count();
+          if hidden > 0
+          {
+            parts.push( format!( "{hidden} hidden" ) );
+          }
+        }
+        Content::Lines( lines ) =>
+        {
+          let total = lines.len();
+          let last = ( self.row + self.page_rows ).min( total );
+          parts.push( format!( "lines {}-{last} of {total}", ( self.row + 1 ).min( last ) ) );
+        }
+      }
+      if let Some( query ) = &self.query
+      {
+        match self.current_match
+        {
+          Some( idx ) => parts.push( format!( "/{query} {}/{}", idx + 1, self.matches.len() ) ),
+          None => parts.push( format!( "/{query} {} matches", self.matches.len() ) ),
+        }
+      }
+      parts.push( "q quit  / search  x hide".to_string() );
+      parts.join( " │ " )
+    };
+    let text = clip( &format!( " {text}" ), width );
+    let pad = width.saturating_sub( unicode_visual_len( &text ) );
+    format!( "{STATUS_COLOR}{text}{}{RESET}", " ".repeat( pad ) )
+  }
+}
+
+/// Restores the terminal when the viewer exits, including on error or panic.
+struct TerminalGuard;
+
+impl Drop for TerminalGuard
+{
+  fn drop( &mut self )
+  {
+    let _ = crossterm::execute!( io::stdout(), crossterm::terminal::LeaveAlternateScreen, crossterm::cursor::Show );
+    let _ = crossterm::terminal::disable_raw_mode();
+  }
+}
+
+/// Widest line of each column over header and cells, bounded by the config's min/max widths.
+fn natural_widths( view : &TableView, config : &TableConfig ) -> Vec< usize >
+{
+  let line_width = | text : &str | text.lines().map( unicode_visual_len ).max().unwrap_or( 0 );
+  view.metadata.column_names.iter().enumerate().map( | ( col, name ) |
+  {
+    let widest = view.rows.iter()
+      .filter_map( | row | row.get( col ) )
+      .map( | cell : &DecoratedText | line_width( &cell.text ) )
+      .fold( line_width( name ), usize::max );
+    let capped = config.max_col_width().map_or( widest, | max | widest.min( max ) );
+    capped.max( config.min_col_width() ).max( 1 )
+  }).collect()
+}
+
+/// Cut a rendered line to `width` display columns, keeping ANSI sequences.
+fn clip( line : &str, width : usize ) -> String
+{
+  truncate_cell( line, width, "" )
+}
@@ -0,0 +1,768 @@
+//! Query layer over `TableView` — sort, filter, project, group and total
+//!
+//! Every operation works on typed cell values read through
+//! `TableView::cell_value()`, so comparison and arithmetic follow
+//! `metadata.column_types` (or explicit `values`): an `Integer` column sorts
+//! `9` before `10`, a `String` column sorts them as text. Views built from
+//! text alone type every column `String`; call `infer_column_types()` (or
+//! declare types with `TableMetadata::with_types()`) first to get numeric
+//! ordering.
+//!
+//! Transformations (`sort_by`, `filter`, `select`) consume the view and keep
+//! `row_details` and `values` aligned with the reordered rows. Derivations
+//! (`group_by`, `totals`) build new data; `Totals::to_heading()` turns a
+//! totals line into a `Heading` for any formatter's footer.
+
+use core::cmp::Ordering;
+use std::collections::HashMap;
+use color_tools::DecoratedText;
+use crate::{ TableView, TableMetadata, CellValue, Heading };
+
+/// Error type for query operations
+#[ derive( thiserror::Error, Debug, Clone, PartialEq, Eq ) ]
+pub enum QueryError
+{
+  /// A sort key, projection, group key or aggregate names a column the view does not have
+  #[ error( "Unknown column: {0}" ) ]
+  UnknownColumn( String ),
+  /// `sum`/`avg` met a cell that is neither a number nor numeric text
+  #[ error( "Column {column} row {row}: value is not numeric" ) ]
+  NotNumeric
+  {
+    /// Aggregated column name
+    column : String,
+    /// 0-based row index of the offending cell
+    row : usize,
+  },
+}
+
+/// Direction of a sort key
+#[ derive( Debug, Clone, Copy, PartialEq, Eq, Default ) ]
+pub enum SortOrder
+{
+  /// Smallest first
+  #[ default ]
+  Ascending,
+  /// Largest first
+  Descending,
+}
+
+/// One column of a multi-key sort
+///
+/// # Examples
+///
+/// ```
+/// use data_fmt::{ SortKey, SortOrder };
+///
+/// let key = SortKey::desc( "Size" );
+/// assert_eq!( key.column(), "Size" );
+/// assert_eq!( key.order(), SortOrder::Descending );
+/// ```
+#[ derive( Debug, Clone, PartialEq, Eq ) ]
+pub struct SortKey
+{
+  column : String,
+  order : SortOrder,
+}
+
+impl SortKey
+{
+  /// Sort key on `column` in the given direction
+  pub fn new( column : impl Into< String >, order : SortOrder ) -> Self
+  {
+    Self { column : column.into(), order }
+  }
+
+  /// Ascending sort key on `column`
+  pub fn asc( column : impl Into< String > ) -> Self
+  {
+    Self::new( column, SortOrder::Ascending )
+  }
+
+  /// Descending sort key on `column`
+  pub fn desc( column : impl Into< String > ) -> Self
+  {
+    Self::new( column, SortOrder::Descending )
+  }
+
+  /// Column this key sorts on
+  pub fn column( &self ) -> &str
+  {
+    &self.column
+  }
+
+  /// Sort direction
+  pub fn order( &self ) -> SortOrder
+  {
+    self.order
+  }
+}
+
+/// Read-only view of one row, handed to `TableView::filter` predicates
+#[ derive( Debug, Clone, Copy ) ]
+pub struct RowRef< 'a >
+{
+  view : &'a TableView,
+  index : usize,
+}
+
+impl< 'a > RowRef< 'a >
+{
+  /// 0-based index of the row in the view being filtered
+  pub fn index( &self ) -> usize
+  {
+    self.index
+  }
+
+  /// Cell text of `column`, or `None` if the view has no such column
+  pub fn text( &self, column : &str ) -> Option< &'a str >
+  {
+    let col = self.view.column_index( column )?;
+    self.view.rows.get( self.index )?.get( col ).map( | cell | cell.text.as_str() )
+  }
+
+  /// Typed value of `column` (see `TableView::cell_value`), `Null` for unknown columns
+  pub fn value( &self, column : &str ) -> CellValue
+  {
+    self.view.column_index( column )
+      .map_or( CellValue::Null, | col | self.view.cell_value( self.index, col ) )
+  }
+}
+
+/// Aggregate function applied by `group_by` and `totals`
+#[ derive( Debug, Clone, Copy, PartialEq, Eq ) ]
+pub enum AggregateFn
+{
+  /// Number of rows, or of non-null cells when a column is given
+  Count,
+  /// Sum of numeric cells — `Int` while every input is an integer, else `Float`
+  Sum,
+  /// Smallest non-null cell
+  Min,
+  /// Largest non-null cell
+  Max,
+  /// Arithmetic mean of numeric cells, as `Float`
+  Avg,
+}
+
+/// One aggregate column: a function, its input column and an output label
+///
+/// The default label is `count` for row counts and `fn(Column)` otherwise,
+/// e.g. `sum(Size)`. Null and empty cells are skipped by every function; an aggregate
+/// with no input values yields `Null` (except counts, which yield `0`).
+///
+/// # Examples
+///
+/// ```
+/// use data_fmt::Aggregate;
+///
+/// assert_eq!( Aggregate::count().label(), "count" );
+/// assert_eq!( Aggregate::sum( "Size" ).label(), "sum(Size)" );
+/// assert_eq!( Aggregate::avg( "Size" ).with_label( "Mean" ).label(), "Mean" );
+/// ```
+#[ derive( Debug, Clone, PartialEq, Eq ) ]
+pub struct Aggregate
+{
+  function : AggregateFn,
+  column : Option< String >,
+  label : Option< String >,
+}
+
+impl Aggregate
+{
+  fn on( function : AggregateFn, column : impl Into< String > ) -> Self
+  {
+    Self { function, column : Some( column.into() ), label : None }
+  }
+
+  /// Number of rows
+  pub fn count() -> Self
+  {
+    Self { function : AggregateFn::Count, column : None, label : None }
+  }
+
+  /// Number of non-null cells in `column`
+  pub fn count_of( column : impl Into< String > ) -> Self
+  {
+    Self::on( AggregateFn::Count, column )
+  }
+
+  /// Sum of `column`
+  pub fn sum( column : impl Into< String > ) -> Self
+  {
+    Self::on( AggregateFn::Sum, column )
+  }
+
+  /// Minimum of `column`
+  pub fn min( column : impl Into< String > ) -> Self
+  {
+    Self::on( AggregateFn::Min, column )
+  }
+
+  /// Maximum of `column`
+  pub fn max( column : impl Into< String > ) -> Self
+  {
+    Self::on( AggregateFn::Max, column )
+  }
+
+  /// Mean of `column`
+  pub fn avg( column : impl Into< String > ) -> Self
+  {
+    Self::on( AggregateFn::Avg, column )
+  }
+
+  /// Replace the default output label
+  #[ must_use ]
+  pub fn with_label( mut self, label : impl Into< String > ) -> Self
+  {
+    self.label = Some( label.into() );
+    self
+  }
+
+  /// Aggregate function
+  pub fn function( &self ) -> AggregateFn
+  {
+    self.function
+  }
+
+  /// Input column, `None` for a plain row count
+  pub fn column( &self ) -> Option< &str >
+  {
+    self.column.as_deref()
+  }
+
+  /// Output label — explicit, or derived from function and column
+  pub fn label( &self ) -> String
+  {
+    if let Some( label ) = &self.label
+    {
+      return label.clone();
+    }
+    let name = match self.function
+    {
+      AggregateFn::Count => "count",
+      AggregateFn::Sum => "sum",
+      AggregateFn::Min => "min",
+      AggregateFn::Max => "max",
+      AggregateFn::Avg => "avg",
+    };
+    match &self.column
+    {
+      Some( column ) => format!( "{name}({column})" ),
+      None => name.to_string(),
+    }
+  }
+}
+
+/// Aggregates computed over a whole view by `TableView::totals`
+///
+/// # Examples
+///
+/// ```
+/// use data_fmt::{ TableView, CellValue, Aggregate };
+///
+/// let view = TableView::from_values(
+///   vec![ "File".into(), "Lines".into() ],
+///   vec![
+///     vec![ "main.rs".into(), 100.into() ],
+///     vec![ "lib.rs".into(), 200.into() ],
+///   ],
+/// );
+/// let totals = view.totals( &[ Aggregate::count(), Aggregate::sum( "Lines" ) ] ).unwrap();
+///
+/// assert_eq!( totals.get( "sum(Lines)" ), Some( &CellValue::Int( 300 ) ) );
+/// assert_eq!( totals.values(), &[ CellValue::Int( 2 ), CellValue::Int( 300 ) ] );
+/// ```
+#[ derive( Debug, Clone, PartialEq ) ]
+pub struct Totals
+{
+  labels : Vec< String >,
+  values : Vec< CellValue >,
+}
+
+impl Totals
+{
+  /// Aggregate labels, in request order
+  pub fn labels( &self ) -> &[ String ]
+  {
+    &self.labels
+  }
+
+  /// Aggregate results, parallel to `labels()`
+  pub fn values( &self ) -> &[ CellValue ]
+  {
+    &self.values
+  }
+
+  /// Result of the aggregate labelled `label`
+  pub fn get( &self, label : &str ) -> Option< &CellValue >
+  {
+    self.labels.iter().position( | l | l == label ).and_then( | idx | self.values.get( idx ) )
+  }
+
+  /// Titled rule listing every aggregate as a `label: value` field
+  ///
+  /// Pass the result to a formatter's `with_footer` to print totals under the output.
+  pub fn to_heading( &self, title : impl Into< String > ) -> Heading
+  {
+    self.labels.iter().zip( &self.values ).fold( Heading::new( title ), | heading, ( label, value ) |
+    {
+      heading.with_field( format!( "{label}: {value}" ) )
+    })
+  }
+}
+
+impl TableView
+{
+  /// Index of the first column named `name`
+  pub fn column_index( &self, name : &str ) -> Option< usize >
+  {
+    self.metadata.column_names.iter().position( | n | n == name )
+  }
+
+  fn resolve( &self, name : &str ) -> Result< usize, QueryError >
+  {
+    self.column_index( name ).ok_or_else( || QueryError::UnknownColumn( name.to_string() ) )
+  }
+
+  /// Sort rows by one or more keys, earlier keys taking precedence
+  ///
+  /// The sort is stable. Values compare by type: numbers numerically (`Int`
+  /// and `Float` together), text lexicographically, timestamps chronologically,
+  /// `false` before `true`. Null cells sort last in either direction.
+  ///
+  /// # Errors
+  ///
+  /// Returns `QueryError::UnknownColumn` if a key names a missing column.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use data_fmt::{ RowBuilder, SortKey };
+  ///
+  /// let mut view = RowBuilder::new( vec![ "File".into(), "Lines".into() ] )
+  ///   .add_row( vec![ "a.rs".into(), "9".into() ] )
+  ///   .add_row( vec![ "b.rs".into(), "10".into() ] )
+  ///   .build_view();
+  /// view.infer_column_types();
+  ///
+  /// let sorted = view.sort_by( &[ SortKey::desc( "Lines" ) ] ).unwrap();
+  /// assert_eq!( sorted.rows[ 0 ][ 0 ].text, "b.rs" );
+  /// ```
+  pub fn sort_by( self, keys : &[ SortKey ] ) -> Result< Self, QueryError >
+  {
+    let columns = keys.iter()
+      .map( | key | self.resolve( &key.column ).map( | col | ( col, key.order ) ) )
+      .collect::< Result< Vec< _ >, _ > >()?;
+
+    let sort_values : Vec< Vec< CellValue > > = ( 0..self.rows.len() )
+      .map( | row | columns.iter().map( | &( col, _ ) | self.cell_value( row, col ) ).collect() )
+      .collect();
+
+    let mut order : Vec< usize > = ( 0..self.rows.len() ).collect();
+    order.sort_by( | &a, &b |
+    {
+      columns.iter().enumerate()
+        .map( | ( key, &( _, direction ) ) | compare_keys( &sort_values[ a ][ key ], &sort_values[ b ][ key ], direction ) )
+        .find( | ord | ord.is_ne() )
+        .unwrap_or( Ordering::Equal )
+    });
+    Ok( self.reorder( &order ) )
+  }
+
+  /// Keep only the rows for which `predicate` returns `true`
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use data_fmt::{ RowBuilder, CellValue };
+  ///
+  /// let view = RowBuilder::new( vec![ "File".into(), "Lines".into() ] )
+  ///   .add_typed_row( vec![ "a.rs".into(), 9.into() ] )
+  ///   .add_typed_row( vec![ "b.rs".into(), 10.into() ] )
+  ///   .build_view();
+  ///
+  /// let big = view.filter( | row | matches!( row.value( "Lines" ), CellValue::Int( n ) if n >= 10 ) );
+  /// assert_eq!( big.rows.len(), 1 );
+  /// assert_eq!( big.rows[ 0 ][ 0 ].text, "b.rs" );
+  /// ```
+  #[ must_use ]
+  pub fn filter( self, predicate : impl Fn( &RowRef< '_ > ) -> bool ) -> Self
+  {
+    let keep : Vec< usize > = ( 0..self.rows.len() )
+      .filter( | &index | predicate( &RowRef { view : &self, index } ) )
+      .collect();
+    self.reorder( &keep )
+  }
+
+  /// Keep only `columns`, in the given order (a column may appear twice)
+  ///
+  /// # Errors
+  ///
+  /// Returns `QueryError::UnknownColumn` if a name is not a column of the view.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use data_fmt::RowBuilder;
+  ///
+  /// let view = RowBuilder::new( vec![ "File".into(), "Lines".into(), "Owner".into() ] )
+  ///   .add_row( vec![ "a.rs".into(), "9".into(), "ann".into() ] )
+  ///   .build_view();
+  ///
+  /// let projected = view.select( &[ "Owner", "File" ] ).unwrap();
+  /// assert_eq!( projected.metadata.column_names, vec![ "Owner", "File" ] );
+  /// assert_eq!( projected.rows[ 0 ][ 0 ].text, "ann" );
+  /// ```
+  pub fn select( self, columns : &[ &str ] ) -> Result< Self, QueryError >
+  {
+    let picks = columns.iter().map( | name | self.resolve( name ) ).collect::< Result< Vec< _ >, _ > >()?;
+
+    let metadata = TableMetadata::with_types(
+      picks.iter().map( | &col | self.metadata.column_names[ col ].clone() ).collect(),
+      picks.iter().map( | &col | self.metadata.column_types.get( col ).cloned().unwrap_or_default() ).collect(),
+    );
+    let rows = self.rows.iter()
+      .map( | row | picks.iter().map( | &col | row.get( col ).cloned().unwrap_or_default() ).collect() )
+      .collect();
+    let values = self.values.iter().enumerate()
+      .map( | ( row, stored ) |
+      {
+        if stored.is_empty() { Vec::new() } else { picks.iter().map( | &col | self.cell_value( row, col ) ).collect() }
+      })
+      .collect();
+
+    Ok( Self { metadata, rows, row_details : self.row_details, values } )
+  }
+
+  /// Group rows by the values of `keys` and compute `aggregates` per group
+  ///
+  /// The result has the key columns followed by one column per aggregate
+  /// (named by `Aggregate::label`). Groups appear in order of first occurrence;
+  /// sort the result with `sort_by` if another order is wanted.
+  ///
+  /// # Errors
+  ///
+  /// Returns `QueryError::UnknownColumn` for a missing key or aggregate column,
+  /// and `QueryError::NotNumeric` when `sum`/`avg` meet non-numeric cells.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use data_fmt::{ TableView, CellValue, Aggregate };
+  ///
+  /// let view = TableView::from_values(
+  ///   vec![ "Lang".into(), "Lines".into() ],
+  ///   vec![
+  ///     vec![ "rust".into(), 100.into() ],
+  ///     vec![ "toml".into(), 10.into() ],
+  ///     vec![ "rust".into(), 50.into() ],
+  ///   ],
+  /// );
+  ///
+  /// let grouped = view.group_by( &[ "Lang" ], &[ Aggregate::count(), Aggregate::sum( "Lines" ) ] ).unwrap();
+  /// assert_eq!( grouped.metadata.column_names, vec![ "Lang", "count", "sum(Lines)" ] );
+  /// assert_eq!( grouped.values[ 0 ], vec![ CellValue::from( "rust" ), CellValue::Int( 2 ), CellValue::Int( 150 ) ] );
+  /// ```
+  pub fn group_by( &self, keys : &[ &str ], aggregates : &[ Aggregate ] ) -> Result< TableView, QueryError >
+  {
+    let key_columns = keys.iter().map( | name | self.resolve( name ) ).collect::< Result< Vec< _ >, _ > >()?;
+    let inputs = self.resolve_aggregates( aggregates )?;
+
+    // `CellValue` is not `Hash` (it holds `f64`), so groups are keyed by the
+    // Debug form of the key values. `Display` would not do: it renders
+    // `Int( 1 )` and `Text( "1" )` alike, and `Null` like an empty string.
+    let mut index : HashMap< String, usize > = HashMap::new();
+    let mut groups : Vec< ( Vec< CellValue >, Vec< usize > ) > = Vec::new();
+    for row in 0..self.rows.len()
+    {
+      let key : Vec< CellValue > = key_columns.iter().map( | &col | self.cell_value( row, col ) ).collect();
+      let slot = *index.entry( format!( "{key:?}" ) ).or_insert_with( ||
+      {
+        groups.push( ( key, Vec::new() ) );
+        groups.len() - 1
+      });
+      groups[ slot ].1.push( row );
+    }
+
+    let mut column_names : Vec< String > = keys.iter().map( | &name | name.to_string() ).collect();
+    column_names.extend( aggregates.iter().map( Aggregate::label ) );
+
+    let values = groups.into_iter()
+      .map( | ( mut key, rows ) |
+      {
+        for ( aggregate, &col ) in aggregates.iter().zip( &inputs )
+        {
+          key.push( self.aggregate( aggregate.function, col, &rows )? );
+        }
+        Ok( key )
+      })
+      .collect::< Result< Vec< _ >, QueryError > >()?;
+
+    Ok( TableView::from_values( column_names, values ) )
+  }
+
+  /// Compute `aggregates` over every row of the view
+  ///
+  /// # Errors
+  ///
+  /// Same as `group_by`.
+  pub fn totals( &self, aggregates : &[ Aggregate ] ) -> Result< Totals, QueryError >
+  {
+    let inputs = self.resolve_aggregates( aggregates )?;
+    let rows : Vec< usize > = ( 0..self.rows.len() ).collect();
+    let values = aggregates.iter().zip( &inputs )
+      .map( | ( aggregate, &col ) | self.aggregate( aggregate.function, col, &rows ) )
+      .collect::< Result< Vec< _ >, _ > >()?;
+    Ok( Totals { labels : aggregates.iter().map( Aggregate::label ).collect(), values } )
+  }
+
+  /// Append a totals row: each aggregate's result under its input column, `label` in the first column
+  ///
+  /// Aggregates without an input column (`Aggregate::count()`) have no cell to
+  /// land in and are skipped; use `totals()` and a footer `Heading` for those.
+  /// The first column carries `label` unless an aggregate already targets it.
+  ///
+  /// # Errors
+  ///
+  /// Same as `group_by`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use data_fmt::{ TableView, Aggregate };
+  ///
+  /// let view = TableView::from_values(
+  ///   vec![ "File".into(), "Lines".into() ],
+  ///   vec![ vec![ "main.rs".into(), 100.into() ], vec![ "lib.rs".into(), 200.into() ] ],
+  /// );
+  ///
+  /// let view = view.with_totals_row( "Total", &[ Aggregate::sum( "Lines" ) ] ).unwrap();
+  /// assert_eq!( view.rows[ 2 ][ 0 ].text, "Total" );
+  /// assert_eq!( view.rows[ 2 ][ 1 ].text, "300" );
+  /// ```
+  pub fn with_totals_row( mut self, label : &str, aggregates : &[ Aggregate ] ) -> Result< Self, QueryError >
+  {
+    let inputs = self.resolve_aggregates( aggregates )?;
+    let rows : Vec< usize > = ( 0..self.rows.len() ).collect();
+
+    let mut cells = vec![ CellValue::Null; self.metadata.column_names.len() ];
+    if let Some( first ) = cells.first_mut()
+    {
+      *first = CellValue::Text( label.to_string() );
+    }
+    for ( aggregate, &col ) in aggregates.iter().zip( &inputs )
+    {
+      if let Some( col ) = col
+      {
+        cells[ col ] = self.aggregate( aggregate.function, Some( col ), &rows )?;
+      }
+    }
+
+    self.rows.push( cells.iter().map( | value | DecoratedText::from( value.to_string() ) ).collect() );
+    if !self.row_details.is_empty()
+    {
+      self.row_details.resize( self.rows.len(), None );
+    }
+    if !self.values.is_empty()
+    {
+      self.values.resize( self.rows.len() - 1, Vec::new() );
+      self.values.push( cells );
+    }
+    Ok( self )
+  }
+
+  fn resolve_aggregates( &self, aggregates : &[ Aggregate ] ) -> Result< Vec< Option< usize > >, QueryError >
+  {
+    aggregates.iter()
+      .map( | aggregate | aggregate.column.as_deref().map( | name | self.resolve( name ) ).transpose() )
+      .collect()
+  }
+
+  fn aggregate( &self, function : AggregateFn, col : Option< usize >, rows : &[ usize ] ) -> Result< CellValue, QueryError >
+  {
+    let Some( col ) = col else
+    {
+      return Ok( CellValue::Int( i64::try_from( rows.len() ).unwrap_or( i64::MAX ) ) );
+    };
+    let cells = rows.iter()
+      .map( | &row | ( row, self.cell_value( row, col ) ) )
+      .filter( | ( _, value ) | !is_blank( value ) );
+
+    match function
+    {
+      AggregateFn::Count => Ok( CellValue::Int( i64::try_from( cells.count() ).unwrap_or( i64::MAX ) ) ),
+      AggregateFn::Min => Ok( cells.map( | ( _, value ) | value ).min_by( compare_values ).unwrap_or_default() ),
+      AggregateFn::Max => Ok( cells.map( | ( _, value ) | value ).max_by( compare_values ).unwrap_or_default() ),
+      AggregateFn::Sum | AggregateFn::Avg =>
+      {
+        let mut total = Number::Int( 0 );
+        let mut count = 0usize;
+        for ( row, value ) in cells
+        {
+          let number = Number::of( &value ).ok_or_else( || QueryError::NotNumeric
+          {
+            column : self.metadata.column_names[ col ].clone(),
+            row,
+          })?;
+          total = total.add( number );
+          count += 1;
+        }
+        Ok( match ( count, function ) {
+          ( 0, _ ) => CellValue::Null,
+          ( _, AggregateFn::Avg ) => CellValue::Float( total.as_f64() / count as f64 ),
+          _ => total.into(),
+        })
+      }
+    }
+  }
+
+  /// Rebuild the view from rows `order` (each index at most once), keeping
+  /// `row_details` and `values` aligned.
+  fn reorder( self, order : &[ usize ] ) -> Self
+  {
+    Self
+    {
+      metadata : self.metadata,
+      rows : pick( self.rows, order ),
+      row_details : pick( self.row_details, order ),
+      values : pick( self.values, order ),
+    }
+  }
+}
+
+/// Move the items at `order` out of `items`; an empty `items` stays empty.
+///
+/// Indices past the end of a shorter parallel vector yield `T::default()`
+/// (`None` detail, no stored values), matching how formatters read them.
+fn pick< T : Default >( items : Vec< T >, order : &[ usize ] ) -> Vec< T >
+{
+  if items.is_empty()
+  {
+    return items;
+  }
+  let mut slots : Vec< Option< T > > = items.into_iter().map( Some ).collect();
+  order.iter()
+    .map( | &idx | slots.get_mut( idx ).and_then( Option::take ).unwrap_or_default() )
+    .collect()
+}
+
+/// Running sum that stays integral until a float or an overflow appears
+#[ derive( Clone, Copy ) ]
+enum Number
+{
+  Int( i64 ),
+  Float( f64 ),
+}
+
+impl Number
+{
+  /// Numeric reading of a value; text counts when it parses as a number
+  fn of( value : &CellValue ) -> Option< Self >
+  {
+    match value
+    {
+      CellValue::Int( n ) => Some( Self::Int( *n ) ),
+      CellValue::Float( f ) => Some( Self::Float( *f ) ),
+      CellValue::Text( text ) =>
+      {
+        let text = text.trim();
+        text.parse::< i64 >().map( Self::Int ).ok()
+          .or_else( || text.parse::< f64 >().ok().map( Self::Float ) )
+      }
+      _ => None,
+    }
+  }
+
+  fn as_f64( self ) -> f64
+  {
+    match self
+    {
+      Self::Int( n ) => n as f64,
+      Self::Float( f ) => f,
+    }
+  }
+
+  fn add( self, other : Self ) -> Self
+  {
+    match ( self, other )
+    {
+      ( Self::Int( a ), Self::Int( b ) ) => a.checked_add( b ).map_or( Self::Float( a as f64 + b as f64 ), Self::Int ),
+      _ => Self::Float( self.as_f64() + other.as_f64() ),
+    }
+  }
+}
+
+impl From< Number > for CellValue
+{
+  fn from( number : Number ) -> Self
+  {
+    match number
+    {
+      Number::Int( n ) => Self::Int( n ),
+      Number::Float( f ) => Self::Float( f ),
+    }
+  }
+}
+
+/// Aggregates skip nulls and the empty text of blank cells in `String` columns.
+fn is_blank( value : &CellValue ) -> bool
+{
+  match value
+  {
+    CellValue::Null => true,
+    CellValue::Text( text ) => text.is_empty(),
+    _ => false,
+  }
+}
+
+/// Apply a sort direction, keeping nulls last either way.
+fn compare_keys( a : &CellValue, b : &CellValue, order : SortOrder ) -> Ordering
+{
+  match ( a, b )
+  {
+    ( CellValue::Null, CellValue::Null ) => Ordering::Equal,
+    ( CellValue::Null, _ ) => Ordering::Greater,
+    ( _, CellValue::Null ) => Ordering::Less,
+    _ if order == SortOrder::Descending => compare_values( b, a ),
+    _ => compare_values( a, b ),
+  }
+}
+
+/// Total order over cell values: same-kind values compare naturally, numbers
+/// across `Int`/`Float`; different kinds order by kind so mixed columns still sort.
+fn compare_values( a : &CellValue, b : &CellValue ) -> Ordering
+{
+  match ( a, b )
+  {
+    ( CellValue::Int( x ), CellValue::Int( y ) )
+    | ( CellValue::Timestamp( x ), CellValue::Timestamp( y ) ) => x.cmp( y ),
+    ( CellValue::Int( _ ) | CellValue::Float( _ ), CellValue::Int( _ ) | CellValue::Float( _ ) ) =>
+    {
+      let x = Number::of( a ).map_or( 0.0, Number::as_f64 );
+      let y = Number::of( b ).map_or( 0.0, Number::as_f64 );
+      x.total_cmp( &y )
+    }
+    ( CellValue::Bool( x ), CellValue::Bool( y ) ) => x.cmp( y ),
+    ( CellValue::Text( x ), CellValue::Text( y ) ) => x.cmp( y ),
+    ( CellValue::Bytes( x ), CellValue::Bytes( y ) ) => x.cmp( y ),
+    ( CellValue::List( _ ), CellValue::List( _ ) ) | ( CellValue::Map( _ ), CellValue::Map( _ ) ) =>
+      a.to_string().cmp( &b.to_string() ),
+    _ => kind_rank( a ).cmp( &kind_rank( b ) ),
+  }
+}
+
+fn kind_rank( value : &CellValue ) -> u8
+{
+  match value
+  {
+    CellValue::Null => 0,
+    CellValue::Bool( _ ) => 1,
+    CellValue::Int( _ ) | CellValue::Float( _ ) => 2,
+    CellValue::Timestamp( _ ) => 3,
+    CellValue::Text( _ ) => 4,
+    CellValue::Bytes( _ ) => 5,
+    CellValue::List( _ ) => 6,
+    CellValue::Map( _ ) => 7,
+  }
+}
@@ -0,0 +1,156 @@
+//! Struct-to-row mapping — the `TableRow` trait behind `#[derive(TableRow)]`
+//!
+//! A `TableRow` type describes its columns statically (`headers`,
+//! `column_types`) and yields one typed `CellValue` per column. That is enough
+//! to build a `TableView` from `&[T]` (`TableView::from_rows`), format a slice
+//! directly (`Format::format_rows`), or render each record as a `TreeNode`.
+//!
+//! Field types map to cells through `ToCellValue`, implemented for the
+//! primitive numbers, `bool`, `char`, strings, paths, `Option< T >`, `Vec< T >`
+//! and `CellValue` itself.
+
+use std::path::{ Path, PathBuf };
+use color_tools::DecoratedText;
+use crate::{ CellValue, DataType, TableMetadata, TableView, TreeNode };
+
+/// Field type that maps onto one typed table cell
+///
+/// # Examples
+///
+/// ```
+/// use data_fmt::{ ToCellValue, CellValue, DataType };
+///
+/// assert_eq!( < u16 as ToCellValue >::data_type(), DataType::Integer );
+/// assert_eq!( Some( 2.5 ).to_cell_value(), CellValue::Float( 2.5 ) );
+/// assert_eq!( None::< bool >.to_cell_value(), CellValue::Null );
+/// ```
+pub trait ToCellValue
+{
+  /// Column type of every value of this type
+  fn data_type() -> DataType;
+
+  /// Typed cell value of `self`
+  fn to_cell_value( &self ) -> CellValue;
+}
+
+macro_rules! impl_to_cell_value
+{
+  ( $data_type : ident, | $v : ident | $body : expr, $( $ty : ty ),+ ) =>
+  {
+    $(
+      impl ToCellValue for $ty
+      {
+        fn data_type() -> DataType { DataType::$data_type }
+        fn to_cell_value( &self ) -> CellValue { let $v = self; $body }
+      }
+    )+
+  };
+}
+
+impl_to_cell_value!( Integer, | v | CellValue::Int( *v ), i64 );
+impl_to_cell_value!( Integer, | v | CellValue::Int( i64::from( *v ) ), i8, i16, i32, u8, u16, u32 );
+// Wider than `i64` on some inputs: out-of-range values fall back to a float.
+impl_to_cell_value!( Integer, | v | i64::try_from( *v ).map_or( CellValue::Float( *v as f64 ), CellValue::Int ), isize, u64, usize, i128, u128 );
+impl_to_cell_value!( Float, | v | CellValue::Float( *v ), f64 );
+impl_to_cell_value!( Float, | v | CellValue::Float( f64::from( *v ) ), f32 );
+impl_to_cell_value!( Boolean, | v | CellValue::Bool( *v ), bool );
+impl_to_cell_value!( String, | v | CellValue::Text( v.clone() ), String );
+impl_to_cell_value!( String, | v | CellValue::Text( v.to_string() ), char, str );
+impl_to_cell_value!( Path, | v | CellValue::Text( v.display().to_string() ), Path, PathBuf );
+
+impl ToCellValue for CellValue
+{
+  /// A bare `CellValue` field can hold anything; `String` is the neutral column type.
+  fn data_type() -> DataType { DataType::String }
+  fn to_cell_value( &self ) -> CellValue { self.clone() }
+}
+
+impl< T : ToCellValue + ?Sized > ToCellValue for &T
+{
+  fn data_type() -> DataType { T::data_type() }
+  fn to_cell_value( &self ) -> CellValue { ( **self ).to_cell_value() }
+}
+
+impl< T : ToCellValue > ToCellValue for Option< T >
+{
+  fn data_type() -> DataType { T::data_type() }
+  fn to_cell_value( &self ) -> CellValue { self.as_ref().map_or( CellValue::Null, ToCellValue::to_cell_value ) }
+}
+
+impl< T : ToCellValue > ToCellValue for Vec< T >
+{
+  fn data_type() -> DataType { DataType::List }
+  fn to_cell_value( &self ) -> CellValue { CellValue::List( self.iter().map( ToCellValue::to_cell_value ).collect() ) }
+}
+
+/// Record type that renders as one table row
+///
+/// Usually derived with `#[derive(TableRow)]` (feature `derive`); implement
+/// it by hand when columns need logic the attributes cannot express.
+/// `headers()`, `column_types()` and `values()` must have equal lengths.
+///
+/// # Examples
+///
+/// ```
+/// use data_fmt::{ TableRow, TableView, DataType, CellValue };
+///
+/// struct Crate { name : &'static str, deps : u32 }
+///
+/// impl TableRow for Crate
+/// {
+///   fn headers() -> Vec< String > { vec![ "Name".into(), "Deps".into() ] }
+///   fn column_types() -> Vec< DataType > { vec![ DataType::String, DataType::Integer ] }
+///   fn values( &self ) -> Vec< CellValue > { vec![ self.name.into(), self.deps.into() ] }
+/// }
+///
+/// let view = TableView::from_rows( &[ Crate { name : "serde", deps : 0 } ] );
+/// assert_eq!( view.metadata.column_names, vec![ "Name", "Deps" ] );
+/// assert_eq!( view.rows[ 0 ][ 1 ].text, "0" );
+/// ```
+pub trait TableRow
+{
+  /// Column headers, in column order
+  fn headers() -> Vec< String >;
+
+  /// Column types, parallel to `headers()`
+  fn column_types() -> Vec< DataType >;
+
+  /// Typed cell values of this record, parallel to `headers()`
+  fn values( &self ) -> Vec< CellValue >;
+
+  /// Child nodes of this record's tree: one `header → value` leaf per column
+  ///
+  /// The derive overrides this so flattened fields become subtrees instead of
+  /// `header.column` leaves.
+  fn tree_children( &self ) -> Vec< TreeNode< String > >
+  {
+    Self::headers().into_iter().zip( self.values() )
+      .map( | ( header, value ) | TreeNode::new( header, Some( value.to_string() ) ) )
+      .collect()
+  }
+
+  /// This record as a tree node named `name`
+  fn to_tree_node( &self, name : &str ) -> TreeNode< String >
+  {
+    let mut node = TreeNode::new( name.to_string(), None );
+    node.children = self.tree_children();
+    node
+  }
+}
+
+impl TableView
+{
+  /// Build a table view with one row per record
+  ///
+  /// Column names and types come from `R`; each cell keeps its typed value
+  /// and shows the value's `Display` form.
+  pub fn from_rows< R : TableRow >( records : &[ R ] ) -> Self
+  {
+    let values : Vec< Vec< CellValue > > = records.iter().map( TableRow::values ).collect();
+    let rows = values.iter()
+      .map( | row | row.iter().map( | value | DecoratedText::from( value.to_string() ) ).collect() )
+      .collect();
+    let metadata = TableMetadata::with_types( R::headers(), R::column_types() );
+    Self { metadata, rows, row_details : vec![], values }
+  }
+}
@@ -27,6 +27,7 @@ pub struct RowBuilder
   row_count : usize,
   rows : Vec< Vec< DecoratedText > >,
   row_details : Vec< Option< DecoratedText > >,
+  values : Vec< Option< Vec< crate::CellValue > > >,
 }
 
 impl RowBuilder
@@ -40,6 +41,7 @@ impl RowBuilder
       row_count : 0,
       rows : Vec::new(),
       row_details : Vec::new(),
+      values : Vec::new(),
     }
   }
 
@@ -226,11 +228,57 @@ impl RowBuilder
     self.add_row_internal( row_name, row, detail );
   }
 
+  /// Add a row of typed values
+  ///
+  /// Cell text is each value's `Display` form. When any typed row is added,
+  /// `build_view()` keeps the values and infers `column_types` from them;
+  /// text rows mixed in alongside contribute `CellValue::Text` cells.
+  ///
+  /// # Panics
+  ///
+  /// Panics if row length doesn't match headers length
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use data_fmt::{ RowBuilder, CellValue, DataType };
+  ///
+  /// let view = RowBuilder::new( vec![ "Name".into(), "Age".into() ] )
+  ///   .add_typed_row( vec![ "Alice".into(), 30.into() ] )
+  ///   .add_typed_row( vec![ "Bob".into(), CellValue::Null ] )
+  ///   .build_view();
+  ///
+  /// assert_eq!( view.metadata.column_types[ 1 ], DataType::Integer );
+  /// assert_eq!( view.cell_value( 1, 1 ), CellValue::Null );
+  /// ```
+  #[ must_use ]
+  pub fn add_typed_row( mut self, values : Vec< crate::CellValue > ) -> Self
+  {
+    self.add_typed_row_mut( values );
+    self
+  }
+
+  /// Add a row of typed values (non-consuming, for programmatic use)
+  ///
+  /// # Panics
+  ///
+  /// Panics if row length doesn't match headers length
+  pub fn add_typed_row_mut( &mut self, values : Vec< crate::CellValue > )
+  {
+    self.validate_row_length( values.len() );
+    self.row_count += 1;
+    let row = values.iter().map( | value | DecoratedText::from( value.to_string() ) ).collect();
+    self.row_details.push( None );
+    self.rows.push( row );
+    self.values.push( Some( values ) );
+  }
+
   /// Internal row addition (no validation)
   fn add_row_internal( &mut self, _row_name : String, row : Vec< DecoratedText >, detail : Option< DecoratedText > )
   {
     self.row_details.push( detail );
     self.rows.push( row );
+    self.values.push( None );
   }
 
   /// Build as canonical `TableView` for use with Format trait
@@ -254,11 +302,21 @@ impl RowBuilder
   /// ```
   pub fn build_view( self ) -> crate::TableView
   {
-    crate::TableView::with_details(
+    let typed = self.values.iter().any( Option::is_some );
+    let mut view = crate::TableView::with_details(
       crate::TableMetadata::new( self.headers ),
       self.rows,
       self.row_details,
-    )
+    );
+    if typed
+    {
+      view.values = self.values.into_iter().zip( &view.rows ).map( | ( values, row ) |
+      {
+        values.unwrap_or_else( || row.iter().map( | cell | crate::CellValue::Text( cell.text.clone() ) ).collect() )
+      }).collect();
+      view.infer_column_types();
+    }
+    view
   }
 
 }
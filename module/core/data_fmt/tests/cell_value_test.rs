@@ -0,0 +1,191 @@
+//! Tests for typed cell values (`feature/011_typed_cells`)
+//!
+//! ## What This Tests
+//!
+//! Verifies that `CellValue`s — explicit via `RowBuilder::add_typed_row` or derived
+//! from `TableMetadata::column_types` — reach JSON/YAML/TOML/SQL as native
+//! literals, that text-only views keep their string output, that streamed and
+//! buffered output agree for typed rows, and that `TableFormatter` right-aligns
+//! numeric columns unless told otherwise.
+
+#![ cfg( feature = "enabled" ) ]
+
+use data_fmt::{ RowBuilder, TableFormatter, TableConfig, CellValue, DataType, Format };
+#[ cfg( any( feature = "format_json", all( feature = "format_yaml", feature = "format_toml" ), all( feature = "sql_ansi", feature = "sql_postgres", feature = "sql_sqlite" ) ) ) ]
+use data_fmt::TableView;
+#[ cfg( feature = "format_json" ) ]
+use data_fmt::TableMetadata;
+
+// --- Test helpers ---
+
+#[ cfg( any( feature = "format_json", all( feature = "format_yaml", feature = "format_toml" ), all( feature = "sql_ansi", feature = "sql_postgres", feature = "sql_sqlite" ) ) ) ]
+fn typed_view() -> TableView
+{
+  RowBuilder::new( vec![ "id".into(), "score".into(), "ok".into(), "seen".into(), "raw".into(), "tags".into() ] )
+    .add_typed_row( vec![
+      1.into(),
+      2.5.into(),
+      true.into(),
+      CellValue::Timestamp( 1_709_294_400 ),
+      CellValue::Bytes( b"hi!".to_vec() ),
+      vec![ CellValue::from( "a" ), CellValue::from( 7 ) ].into(),
+    ] )
+    .add_typed_row( vec![ 2.into(), 3.into(), false.into(), CellValue::Null, CellValue::Null, CellValue::List( vec![] ) ] )
+    .build_view()
+}
+
+/// FT-1 — `feature/011`: explicit values become native JSON literals, keys in column order.
+// test_kind: standard
+#[ cfg( feature = "format_json" ) ]
+#[ test ]
+fn json_emits_native_literals_ft1()
+{
+  let output = data_fmt::JsonFormatter::compact().format( &typed_view() ).unwrap();
+  assert_eq!(
+    output,
+    r#"[{"id":1,"score":2.5,"ok":true,"seen":"2024-03-01T12:00:00Z","raw":"aGkh","tags":["a",7]},{"id":2,"score":3,"ok":false,"seen":null,"raw":null,"tags":[]}]"#
+  );
+}
+
+/// FT-2 — `feature/011`: declared column types type text cells; `String` columns stay strings.
+// test_kind: standard
+#[ cfg( feature = "format_json" ) ]
+#[ test ]
+fn column_types_drive_derived_values_ft2()
+{
+  let view = TableView::new(
+    TableMetadata::with_types(
+      vec![ "zip".into(), "qty".into(), "note".into() ],
+      vec![ DataType::String, DataType::Integer, DataType::Integer ],
+    ),
+    vec![ vec![ "007".into(), "".into(), "n/a".into() ] ],
+  );
+  let output = data_fmt::JsonFormatter::compact().format( &view ).unwrap();
+  assert_eq!( output, r#"[{"zip":"007","qty":null,"note":"n/a"}]"# );
+
+  let plain = RowBuilder::new( vec![ "n".into() ] ).add_row( vec![ "30".into() ] ).build_view();
+  assert_eq!( data_fmt::JsonFormatter::compact().format( &plain ).unwrap(), r#"[{"n":"30"}]"# );
+}
+
+/// FT-3 — `feature/011`: YAML and TOML emit native scalars; TOML omits nulls and uses datetimes.
+// test_kind: standard
+#[ cfg( all( feature = "format_yaml", feature = "format_toml" ) ) ]
+#[ test ]
+fn yaml_and_toml_emit_native_scalars_ft3()
+{
+  let view = typed_view();
+
+  let yaml = data_fmt::YamlFormatter::new().format( &view ).unwrap();
+  assert!( yaml.contains( "id: 1\n" ), "{yaml}" );
+  assert!( yaml.contains( "ok: true\n" ), "{yaml}" );
+  assert!( yaml.contains( "seen: null\n" ), "{yaml}" );
+
+  let toml_out = data_fmt::TomlFormatter::new().format( &view ).unwrap();
+  let parsed : toml::Table = toml_out.parse().unwrap();
+  let rows = parsed[ "row" ].as_array().unwrap();
+  assert_eq!( rows[ 0 ][ "id" ].as_integer(), Some( 1 ) );
+  assert_eq!( rows[ 0 ][ "score" ].as_float(), Some( 2.5 ) );
+  assert!( rows[ 0 ][ "seen" ].is_datetime(), "{toml_out}" );
+  assert!( !rows[ 1 ].as_table().unwrap().contains_key( "seen" ), "null omitted: {toml_out}" );
+}
+
+/// FT-4 — `feature/011`: SQL literals follow the dialect.
+// test_kind: standard
+#[ cfg( all( feature = "sql_ansi", feature = "sql_postgres", feature = "sql_sqlite" ) ) ]
+#[ test ]
+fn sql_emits_dialect_literals_ft4()
+{
+  use data_fmt::{ SqlFormatter, SqlVariant };
+  let view = typed_view();
+
+  let ansi = SqlFormatter::new( "t" ).format( &view ).unwrap();
+  assert!( ansi.contains( "(1, 2.5, TRUE, TIMESTAMP '2024-03-01 12:00:00', X'686921', '[\"a\",7]')" ), "{ansi}" );
+  assert!( ansi.contains( "(2, 3, FALSE, NULL, NULL, '[]')" ), "{ansi}" );
+
+  let pg = SqlFormatter::with_variant( "t", SqlVariant::PostgreSQL ).format( &view ).unwrap();
+  assert!( pg.contains( "'\\x686921'" ), "{pg}" );
+
+  let lite = SqlFormatter::with_variant( "t", SqlVariant::SQLite ).format( &view ).unwrap();
+  assert!( lite.contains( "(1, 2.5, 1, '2024-03-01 12:00:00', X'686921'" ), "{lite}" );
+}
+
+/// FT-5 — `feature/011`: numeric columns right-align; explicit alignment and the opt-out win.
+// test_kind: standard
+#[ test ]
+fn table_right_aligns_numeric_columns_ft5()
+{
+  let view = RowBuilder::new( vec![ "name".into(), "qty".into() ] )
+    .add_typed_row( vec![ "apple".into(), 5.into() ] )
+    .add_typed_row( vec![ "kiwi".into(), 120.into() ] )
+    .build_view();
+
+  let output = TableFormatter::new().format( &view ).unwrap();
+  let lines : Vec< &str > = output.lines().collect();
+  assert!( lines[ 2 ].ends_with( "    5" ), "right-aligned; got:\n{output}" );
+  assert!( lines[ 3 ].ends_with( "  120" ), "right-aligned; got:\n{output}" );
+
+  let off = TableFormatter::with_config( TableConfig::plain().with_numeric_align_right( false ) ).format( &view ).unwrap();
+  assert!( off.lines().nth( 2 ).unwrap().starts_with( "apple  5 " ), "left-aligned; got:\n{off}" );
+
+  let explicit = TableFormatter::with_config( TableConfig::plain().with_align_right( vec![ false, false ] ) ).format( &view ).unwrap();
+  assert_eq!( explicit, off );
+}
+
+/// FT-6 — `feature/011`: streamed JSON and SQL match buffered output for typed rows.
+// test_kind: standard
+#[ cfg( all( feature = "format_json", feature = "sql_ansi" ) ) ]
+#[ test ]
+fn streamed_typed_rows_match_buffered_ft6()
+{
+  use data_fmt::StreamFormat;
+  fn streamed< F : StreamFormat >( formatter : &F, view : &TableView ) -> String
+  {
+    let mut out = Vec::new();
+    formatter.write_view( view, &mut out ).unwrap();
+    String::from_utf8( out ).unwrap()
+  }
+
+  let view = typed_view();
+  for json in [ data_fmt::JsonFormatter::new(), data_fmt::JsonFormatter::compact() ]
+  {
+    assert_eq!( streamed( &json, &view ), json.format( &view ).unwrap() );
+  }
+  let sql = data_fmt::SqlFormatter::new( "t" );
+  assert_eq!( streamed( &sql, &view ), sql.format( &view ).unwrap() );
+}
+
+/// FT-7 — `feature/011`: parsed JSON keeps typed values and infers column types from them.
+// test_kind: standard
+#[ cfg( feature = "format_json" ) ]
+#[ test ]
+fn json_parse_keeps_values_ft7()
+{
+  use data_fmt::Parse;
+  let parsed = data_fmt::JsonFormatter::new()
+    .parse( r#"[ { "n" : 1, "x" : 1.5, "m" : { "k" : [ 1, null ] } }, { "n" : 2, "x" : 2 } ]"# )
+    .unwrap();
+  assert_eq!( parsed.metadata.column_types, vec![ DataType::Integer, DataType::Float, DataType::Map ] );
+  assert_eq!( parsed.cell_value( 0, 2 ), CellValue::Map( vec![ ( "k".into(), CellValue::List( vec![ 1.into(), CellValue::Null ] ) ) ] ) );
+  assert_eq!( parsed.cell_value( 1, 2 ), CellValue::Null );
+  assert_eq!( parsed.rows[ 0 ][ 2 ].text, r#"{"k":[1,null]}"# );
+}
+
+/// FT-8 — `feature/011`: text inference recognizes floats and timestamps; Integer+Float widens.
+// test_kind: standard
+#[ test ]
+fn inference_and_text_round_trip_ft8()
+{
+  let mut view = RowBuilder::new( vec![ "a".into(), "b".into(), "c".into() ] )
+    .add_row( vec![ "1".into(), "2024-03-01T12:00:00Z".into(), "NaN".into() ] )
+    .add_row( vec![ "2.5".into(), "2024-03-01 13:00:00+01:00".into(), "inf".into() ] )
+    .build_view();
+  view.infer_column_types();
+  assert_eq!( view.metadata.column_types, vec![ DataType::Float, DataType::Timestamp, DataType::String ] );
+  assert_eq!( view.cell_value( 1, 1 ), CellValue::Timestamp( 1_709_294_400 ) );
+
+  for secs in [ -86_401, 0, 951_782_400, 4_102_444_799 ]
+  {
+    let text = CellValue::Timestamp( secs ).to_string();
+    assert_eq!( CellValue::from_text( &text, &DataType::Timestamp ), CellValue::Timestamp( secs ), "{text}" );
+  }
+}
@@ -0,0 +1,236 @@
+//! Tests for keyed `TableView` diffs (`feature/016_table_diff`)
+//!
+//! ## What This Tests
+//!
+//! Verifies that rows are matched by key columns and classified as added,
+//! removed, changed or unchanged in a stable order, that key errors are
+//! reported, that columns present on one side only are handled, and that the
+//! unified, side-by-side and JSON Patch renderings carry the expected markers,
+//! colours and operations.
+
+#![ cfg( feature = "enabled" ) ]
+
+use data_fmt::{
+  RowBuilder, TableView, TableFormatter, TableConfig, CellValue, Format,
+  RowChange, DiffError, DiffSide, DiffSummary, DecoratedText,
+};
+
+// --- Test helpers ---
+
+fn before() -> TableView
+{
+  TableView::from_values(
+    vec![ "Region".into(), "Host".into(), "Version".into(), "Cpu".into() ],
+    vec![
+      vec![ "eu".into(), "web1".into(), "1.2".into(), 40.into() ],
+      vec![ "eu".into(), "web2".into(), "1.2".into(), 55.into() ],
+      vec![ "us".into(), "web1".into(), "1.2".into(), 10.into() ],
+      vec![ "us".into(), "db1".into(), "9.6".into(), 70.into() ],
+    ],
+  )
+}
+
+fn after() -> TableView
+{
+  TableView::from_values(
+    vec![ "Region".into(), "Host".into(), "Version".into(), "Cpu".into() ],
+    vec![
+      vec![ "eu".into(), "web1".into(), "1.3".into(), 42.into() ],
+      vec![ "us".into(), "web1".into(), "1.2".into(), 10.into() ],
+      vec![ "us".into(), "db1".into(), "9.6".into(), 75.into() ],
+      vec![ "us".into(), "web9".into(), "1.3".into(), 5.into() ],
+    ],
+  )
+}
+
+fn markers( view : &TableView ) -> Vec< &str >
+{
+  view.rows.iter().map( | row | row[ 0 ].text.as_str() ).collect()
+}
+
+/// FT-1 — `feature/016`: rows match on a composite key and come in new-view order.
+// test_kind: standard
+#[ test ]
+fn rows_classified_by_composite_key_ft1()
+{
+  let diff = before().diff( &after(), &[ "Region", "Host" ] ).unwrap();
+  let rows : Vec< _ > = diff.rows().iter().map( | row | ( row.key().join( "/" ), row.change() ) ).collect();
+  assert_eq!( rows, vec![
+    ( "eu/web1".to_string(), RowChange::Changed ),
+    ( "eu/web2".to_string(), RowChange::Removed ),
+    ( "us/web1".to_string(), RowChange::Unchanged ),
+    ( "us/db1".to_string(), RowChange::Changed ),
+    ( "us/web9".to_string(), RowChange::Added ),
+  ] );
+  assert_eq!( diff.summary(), DiffSummary { added : 1, removed : 1, changed : 2, unchanged : 1 } );
+  assert_eq!( diff.key_columns(), &[ "Region", "Host" ] );
+
+  let web1 = &diff.rows()[ 0 ];
+  assert_eq!( web1.changed_columns(), &[ 2, 3 ] );
+  assert_eq!( web1.old_values()[ 3 ], CellValue::Int( 40 ) );
+  assert_eq!( web1.new_values()[ 3 ], CellValue::Int( 42 ) );
+  assert_eq!( ( web1.old_index(), web1.new_index() ), ( Some( 0 ), Some( 0 ) ) );
+  assert!( diff.rows()[ 1 ].new_values().is_empty() );
+}
+
+/// FT-2 — `feature/016`: `changes_only` drops unchanged rows; identical views diff empty.
+// test_kind: standard
+#[ test ]
+fn changes_only_and_empty_diff_ft2()
+{
+  let diff = before().diff( &after(), &[ "Region", "Host" ] ).unwrap().changes_only();
+  assert_eq!( diff.rows().len(), 4 );
+  assert!( diff.rows().iter().all( | row | row.change() != RowChange::Unchanged ) );
+  assert!( !diff.is_empty() );
+
+  let same = before().diff( &before(), &[ "Region", "Host" ] ).unwrap();
+  assert!( same.is_empty() );
+  assert_eq!( same.rows().len(), 4 );
+  assert!( same.changes_only().rows().is_empty() );
+}
+
+/// FT-3 — `feature/016`: missing key columns, empty keys and duplicate keys are errors.
+// test_kind: standard
+#[ test ]
+fn key_errors_ft3()
+{
+  assert_eq!( before().diff( &after(), &[] ).unwrap_err(), DiffError::EmptyKey );
+
+  let renamed = after().select( &[ "Host", "Version" ] ).unwrap();
+  assert_eq!(
+    before().diff( &renamed, &[ "Region", "Host" ] ).unwrap_err(),
+    DiffError::UnknownColumn { column : "Region".into(), side : DiffSide::New },
+  );
+
+  let err = before().diff( &after(), &[ "Host" ] ).unwrap_err();
+  assert_eq!( err, DiffError::DuplicateKey { key : "web1".into(), side : DiffSide::Old } );
+  assert_eq!( err.to_string(), "Duplicate key (web1) in the old view" );
+}
+
+/// FT-4 — `feature/016`: values compare typed, keys compare by text.
+// test_kind: standard
+#[ test ]
+fn typed_comparison_ft4()
+{
+  let text = RowBuilder::new( vec![ "Id".into(), "Qty".into() ] )
+    .add_row( vec![ "1".into(), "10".into() ] )
+    .build_view();
+  let typed = TableView::from_values( vec![ "Id".into(), "Qty".into() ], vec![ vec![ 1.into(), 10.into() ] ] );
+
+  // `Text("10")` differs from `Int(10)`, but the keys still pair up.
+  let diff = text.diff( &typed, &[ "Id" ] ).unwrap();
+  assert_eq!( diff.rows()[ 0 ].change(), RowChange::Changed );
+
+  let mut inferred = text.clone();
+  inferred.infer_column_types();
+  assert!( inferred.diff( &typed, &[ "Id" ] ).unwrap().is_empty() );
+}
+
+/// FT-5 — `feature/016`: columns present on one side only compare against `Null`.
+// test_kind: standard
+#[ test ]
+fn column_added_and_dropped_ft5()
+{
+  let old = TableView::from_values(
+    vec![ "Host".into(), "Legacy".into() ],
+    vec![ vec![ "a".into(), "x".into() ], vec![ "b".into(), CellValue::Null ] ],
+  );
+  let new = TableView::from_values(
+    vec![ "Host".into(), "Owner".into() ],
+    vec![ vec![ "a".into(), CellValue::Null ], vec![ "b".into(), "ann".into() ] ],
+  );
+  let diff = old.diff( &new, &[ "Host" ] ).unwrap();
+  assert_eq!( diff.columns(), &[ "Host", "Owner", "Legacy" ] );
+  assert_eq!( diff.rows()[ 0 ].changed_columns(), &[ 2 ] );
+  assert_eq!( diff.rows()[ 1 ].changed_columns(), &[ 1 ] );
+
+  let side = diff.side_by_side();
+  assert_eq!( side.metadata.column_names, vec![ "Host", "Legacy", "", "Host", "Owner" ] );
+  assert_eq!( diff.unified().metadata.column_names, vec![ "", "Host", "Owner", "Legacy" ] );
+
+  assert_eq!( diff.to_json_patch(), concat!(
+    "[\n",
+    "  {\"op\":\"remove\",\"path\":\"/0/Legacy\"},\n",
+    "  {\"op\":\"add\",\"path\":\"/1/Owner\",\"value\":\"ann\"}\n",
+    "]",
+  ) );
+}
+
+/// FT-6 — `feature/016`: unified output marks rows and colours only changed cells.
+// test_kind: standard
+#[ test ]
+fn unified_view_ft6()
+{
+  let diff = before().diff( &after(), &[ "Region", "Host" ] ).unwrap();
+  let view = diff.unified();
+  assert_eq!( markers( &view ), vec![ "-", "+", "-", " ", "-", "+", "+" ] );
+
+  // Changed row: only Version and Cpu are coloured, red for old and green for new.
+  assert_eq!( view.rows[ 0 ][ 3 ].render(), "\x1b[31m1.2\x1b[0m" );
+  assert_eq!( view.rows[ 1 ][ 4 ].render(), "\x1b[32m42\x1b[0m" );
+  assert!( !view.rows[ 1 ][ 2 ].is_colored() );
+  // Removed and added rows are coloured whole; unchanged rows not at all.
+  assert!( view.rows[ 2 ].iter().all( DecoratedText::is_colored ) );
+  assert!( view.rows[ 3 ].iter().all( | cell | !cell.is_colored() ) );
+  assert!( view.rows[ 6 ].iter().all( DecoratedText::is_colored ) );
+  // Typed values survive for typed formatters.
+  assert_eq!( view.cell_value( 1, 4 ), CellValue::Int( 42 ) );
+
+  let output = TableFormatter::with_config( TableConfig::plain().with_footer( diff.to_heading( "deploy" ) ) )
+    .format( &view )
+    .unwrap();
+  let plain = data_fmt::strip_ansi( &output );
+  assert!( plain.contains( "-  eu      web2  1.2       55" ), "{plain}" );
+  assert!( plain.contains( "+1 added" ) && plain.contains( "-1 removed" ) && plain.contains( "~2 changed" ) );
+}
+
+/// FT-7 — `feature/016`: side-by-side output uses `diff -y` markers and blank missing sides.
+// test_kind: standard
+#[ test ]
+fn side_by_side_view_ft7()
+{
+  let view = before().diff( &after(), &[ "Region", "Host" ] ).unwrap().changes_only().side_by_side();
+  assert_eq!( view.metadata.column_names.len(), 9 );
+  let marker : Vec< &str > = view.rows.iter().map( | row | row[ 4 ].text.as_str() ).collect();
+  assert_eq!( marker, vec![ "|", "<", "|", ">" ] );
+
+  // Changed: old side red and new side green on differing cells only.
+  assert_eq!( view.rows[ 0 ][ 2 ].render(), "\x1b[31m1.2\x1b[0m" );
+  assert_eq!( view.rows[ 0 ][ 7 ].render(), "\x1b[32m1.3\x1b[0m" );
+  assert!( !view.rows[ 0 ][ 0 ].is_colored() && !view.rows[ 0 ][ 5 ].is_colored() );
+
+  // Removed: new side blank; added: old side blank.
+  assert!( view.rows[ 1 ][ 5..].iter().all( | cell | cell.text.is_empty() ) );
+  assert!( view.rows[ 3 ][ ..4 ].iter().all( | cell | cell.text.is_empty() ) );
+  assert_eq!( view.rows[ 3 ][ 6 ].render(), "\x1b[32mweb9\x1b[0m" );
+}
+
+/// FT-8 — `feature/016`: the JSON Patch replaces cells, removes rows high-to-low, appends additions.
+// test_kind: standard
+#[ test ]
+fn json_patch_ft8()
+{
+  let old = TableView::from_values(
+    vec![ "Id".into(), "a/b~c".into() ],
+    vec![
+      vec![ 1.into(), "x".into() ],
+      vec![ 2.into(), "y".into() ],
+      vec![ 3.into(), "z".into() ],
+    ],
+  );
+  let new = TableView::from_values(
+    vec![ "Id".into(), "a/b~c".into() ],
+    vec![ vec![ 2.into(), true.into() ], vec![ 4.into(), CellValue::Null ] ],
+  );
+  let patch = old.diff( &new, &[ "Id" ] ).unwrap().to_json_patch();
+  assert_eq!( patch, concat!(
+    "[\n",
+    "  {\"op\":\"replace\",\"path\":\"/1/a~1b~0c\",\"value\":true},\n",
+    "  {\"op\":\"remove\",\"path\":\"/2\"},\n",
+    "  {\"op\":\"remove\",\"path\":\"/0\"},\n",
+    "  {\"op\":\"add\",\"path\":\"/-\",\"value\":{\"Id\":4,\"a/b~c\":null}}\n",
+    "]",
+  ) );
+
+  assert_eq!( old.diff( &old, &[ "Id" ] ).unwrap().to_json_patch(), "[]" );
+}
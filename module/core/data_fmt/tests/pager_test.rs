@@ -0,0 +1,223 @@
+//! Tests for the interactive `Pager` viewer (`feature/015_pager`)
+//!
+//! ## What This Tests
+//!
+//! Drives `Pager` through `handle_key` and inspects `render` frames — no
+//! terminal involved. Verifies that the header and first column stay frozen
+//! while scrolling, that frames never exceed the requested size, that search
+//! highlights and jumps to matches, that columns can be hidden and restored,
+//! and that trees and text scroll and wrap as lines.
+
+#![ cfg( feature = "pager" ) ]
+
+use data_fmt::{ RowBuilder, TreeBuilder, TableView, TableConfig, Pager, PagerKey, strip_ansi, visual_len };
+
+// --- Test helpers ---
+
+fn wide_view() -> TableView
+{
+  let mut builder = RowBuilder::new( vec![ "Name".into(), "Alpha".into(), "Beta".into(), "Gamma".into(), "Delta".into() ] );
+  for i in 0..30
+  {
+    builder = builder.add_row( vec![
+      format!( "row{i}" ).into(),
+      format!( "a{i}" ).into(),
+      "bbbbbbbbbb".into(),
+      ( i * 7 ).to_string().into(),
+      "dd".into(),
+    ] );
+  }
+  builder.build_view()
+}
+
+fn pager() -> Pager
+{
+  Pager::new( wide_view() ).with_config( TableConfig::bordered() )
+}
+
+fn press( pager : &mut Pager, keys : &[ PagerKey ] )
+{
+  for &key in keys
+  {
+    assert!( pager.handle_key( key ) );
+  }
+}
+
+fn search( pager : &mut Pager, query : &str )
+{
+  pager.handle_key( PagerKey::Char( '/' ) );
+  for c in query.chars()
+  {
+    pager.handle_key( PagerKey::Char( c ) );
+  }
+  pager.handle_key( PagerKey::Enter );
+}
+
+fn plain( frame : &[ String ] ) -> Vec< String >
+{
+  frame.iter().map( | line | strip_ansi( line ) ).collect()
+}
+
+/// FT-1 — `feature/015`: the header stays on top while rows scroll by line and page.
+// test_kind: standard
+#[ test ]
+fn header_frozen_while_scrolling_ft1()
+{
+  let mut pager = pager();
+  let frame = plain( &pager.render( 40, 8 ) );
+  assert_eq!( frame.len(), 8 );
+  assert!( frame[ 0 ].contains( "Name" ) );
+  assert!( frame[ 2 ].contains( "row0" ) );
+
+  press( &mut pager, &[ PagerKey::PageDown, PagerKey::Down ] );
+  let frame = plain( &pager.render( 40, 8 ) );
+  assert!( frame[ 0 ].contains( "Name" ) );
+  assert!( frame[ 2 ].contains( "row6 " ), "{frame:?}" );
+  assert!( frame[ 7 ].contains( "rows 7-11 of 30" ), "{}", frame[ 7 ] );
+}
+
+/// FT-2 — `feature/015`: the first column stays while the others scroll horizontally.
+// test_kind: standard
+#[ test ]
+fn first_column_frozen_while_scrolling_ft2()
+{
+  let mut pager = pager();
+  let header = &plain( &pager.render( 30, 6 ) )[ 0 ];
+  assert_eq!( header, "| Name  | Alpha | Beta       |" );
+
+  press( &mut pager, &[ PagerKey::Right ] );
+  let frame = plain( &pager.render( 30, 6 ) );
+  assert_eq!( frame[ 0 ], "| Name  | Beta       | Gamma |" );
+  assert!( frame[ 2 ].starts_with( "| row0  | bbbbbbbbbb |" ) );
+
+  // Scrolling never runs past the last column.
+  press( &mut pager, &[ PagerKey::Right, PagerKey::Right, PagerKey::Right, PagerKey::Right ] );
+  assert_eq!( plain( &pager.render( 30, 6 ) )[ 0 ], "| Name  | Delta |" );
+}
+
+/// FT-3 — `feature/015`: frames are exactly `height` lines, none wider than `width`.
+// test_kind: standard
+#[ test ]
+fn frame_fits_requested_size_ft3()
+{
+  let mut pager = pager();
+  for ( width, height ) in [ ( 12, 3 ), ( 30, 6 ), ( 80, 40 ), ( 5, 2 ) ]
+  {
+    let frame = pager.render( width, height );
+    assert_eq!( frame.len(), height );
+    assert!( frame.iter().all( | line | visual_len( line ) <= width ), "{width}x{height}: {frame:?}" );
+  }
+  assert!( pager.render( 0, 10 ).is_empty() );
+
+  // A column wider than the screen is cut rather than overflowing.
+  let view = RowBuilder::new( vec![ "Text".into() ] ).add_row( vec![ "x".repeat( 100 ).into() ] ).build_view();
+  let frame = Pager::new( view ).render( 20, 4 );
+  assert!( frame.iter().all( | line | visual_len( line ) <= 20 ) );
+}
+
+/// FT-4 — `feature/015`: search highlights matches and `n`/`N` scroll each into view.
+// test_kind: standard
+#[ test ]
+fn search_jumps_to_matches_ft4()
+{
+  let mut pager = pager();
+  search( &mut pager, "21" );
+  // Matches run in row order: Gamma of row3 (3 * 7), then Name and Alpha of row21.
+  let frame = pager.render( 30, 8 );
+  assert!( strip_ansi( &frame[ 0 ] ).contains( "Gamma" ) );
+  assert!( frame[ 2 ].contains( "\x1b[1;7m21" ), "{frame:?}" );
+  assert!( strip_ansi( &pager.render( 60, 8 )[ 7 ] ).contains( "/21 1/3" ) );
+
+  press( &mut pager, &[ PagerKey::Char( 'n' ) ] );
+  assert!( pager.render( 30, 8 )[ 2 ].contains( "\x1b[1;7mrow21" ) );
+
+  // The third match is in the Alpha column, which the pager scrolls back to.
+  press( &mut pager, &[ PagerKey::Char( 'n' ) ] );
+  let frame = pager.render( 30, 8 );
+  assert!( strip_ansi( &frame[ 0 ] ).contains( "Alpha" ) );
+  assert!( frame[ 2 ].contains( "\x1b[1;7ma21" ), "{frame:?}" );
+
+  // `n` wraps around to the first match, `N` back to the last; an empty query clears the search.
+  press( &mut pager, &[ PagerKey::Char( 'n' ), PagerKey::Char( 'N' ) ] );
+  assert!( pager.render( 30, 8 )[ 2 ].contains( "\x1b[1;7ma21" ) );
+  search( &mut pager, "" );
+  assert!( !pager.render( 30, 8 ).iter().any( | line | line.contains( "\x1b[1;7m" ) ) );
+}
+
+/// FT-5 — `feature/015`: `x` hides the first scrolled column, `X` restores all columns.
+// test_kind: standard
+#[ test ]
+fn hide_and_restore_columns_ft5()
+{
+  let mut pager = pager();
+  press( &mut pager, &[ PagerKey::Char( 'x' ), PagerKey::Char( 'x' ) ] );
+  let frame = plain( &pager.render( 60, 6 ) );
+  assert_eq!( frame[ 0 ], "| Name  | Gamma | Delta |" );
+  assert!( frame[ 5 ].contains( "2 hidden" ), "{}", frame[ 5 ] );
+
+  // Matches in hidden columns are skipped.
+  search( &mut pager, "bbb" );
+  assert!( plain( &pager.render( 60, 6 ) )[ 5 ].contains( "/bbb 0 matches" ) );
+
+  press( &mut pager, &[ PagerKey::Char( 'X' ) ] );
+  assert!( plain( &pager.render( 60, 6 ) )[ 0 ].contains( "Alpha" ) );
+}
+
+/// FT-6 — `feature/015`: `f` unfreezes the first column; `with_frozen_columns` freezes more.
+// test_kind: standard
+#[ test ]
+fn frozen_columns_configurable_ft6()
+{
+  let mut pager = pager();
+  press( &mut pager, &[ PagerKey::Char( 'f' ), PagerKey::Right ] );
+  assert_eq!( plain( &pager.render( 30, 6 ) )[ 0 ], "| Alpha | Beta       | Gamma |" );
+
+  let mut pager = Pager::new( wide_view() ).with_config( TableConfig::bordered() ).with_frozen_columns( 2 );
+  press( &mut pager, &[ PagerKey::Right ] );
+  assert_eq!( plain( &pager.render( 40, 6 ) )[ 0 ], "| Name  | Alpha | Gamma | Delta |" );
+}
+
+/// FT-7 — `feature/015`: trees and text page as lines; text scrolls horizontally or wraps.
+// test_kind: standard
+#[ test ]
+fn trees_and_text_as_lines_ft7()
+{
+  let tree = TreeBuilder::new( "root" )
+    .insert( &[ "src", "main.rs" ], 10 )
+    .insert( &[ "src", "lib.rs" ], 20 )
+    .build();
+  let frame = plain( &Pager::from_tree( &tree ).render( 40, 4 ) );
+  assert!( frame[ 1 ].contains( "main.rs 10" ), "{frame:?}" );
+  assert!( frame[ 3 ].contains( "lines 1-3 of 3" ) );
+
+  let text = "0123456789abcdefghij\nshort";
+  let mut pager = Pager::from_text( text );
+  press( &mut pager, &[ PagerKey::Right ] );
+  let frame = pager.render( 6, 3 );
+  assert_eq!( frame[ 0 ], "89abcd" );
+  assert_eq!( frame[ 1 ], "" );
+
+  let frame = Pager::from_text( "alpha beta gamma delta" ).with_wrap( true ).render( 11, 4 );
+  assert_eq!( &frame[ ..3 ], &[ "alpha beta", "gamma delta", "" ] );
+}
+
+/// FT-8 — `feature/015`: `End`/`Home` jump to the ends; `q` quits, `Esc` in search only cancels it.
+// test_kind: standard
+#[ test ]
+fn navigation_ends_and_quit_ft8()
+{
+  let mut pager = pager();
+  pager.render( 40, 8 );
+  press( &mut pager, &[ PagerKey::End ] );
+  let frame = plain( &pager.render( 40, 8 ) );
+  assert!( frame[ 6 ].contains( "row29" ) );
+  assert!( frame[ 7 ].contains( "rows 26-30 of 30" ) );
+
+  press( &mut pager, &[ PagerKey::Home ] );
+  assert!( plain( &pager.render( 40, 8 ) )[ 2 ].contains( "row0" ) );
+
+  press( &mut pager, &[ PagerKey::Char( '/' ), PagerKey::Char( 'q' ) ] );
+  assert!( strip_ansi( &pager.render( 40, 8 )[ 7 ] ).starts_with( " /q" ) );
+  assert!( pager.handle_key( PagerKey::Escape ) );
+  assert!( !pager.handle_key( PagerKey::Char( 'q' ) ) );
+}
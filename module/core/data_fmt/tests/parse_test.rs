@@ -0,0 +1,216 @@
+//! Tests for reading formatter output back via `Parse` (`feature/010_parsing`)
+//!
+//! ## What This Tests
+//!
+//! Verifies that `formatter.parse( &formatter.format( &view )? )` restores the
+//! headers and cell text for every readable format (CSV, TSV, Markdown, JSON,
+//! YAML), that the escaping each formatter applies is undone, that
+//! `column_types` is inferred from the cell text, and that malformed input or
+//! ambiguous table styles surface as `ParseError` rather than a panic.
+
+#![ cfg( feature = "enabled" ) ]
+
+use data_fmt::{ RowBuilder, TableFormatter, TableConfig, TableView, Format, Parse, ParseError };
+#[ cfg( feature = "table_csv" ) ]
+use data_fmt::Heading;
+#[ cfg( any( feature = "table_csv", feature = "format_json" ) ) ]
+use data_fmt::DataType;
+
+// --- Test helpers ---
+
+fn sample_view() -> TableView
+{
+  RowBuilder::new( vec![ "Name".into(), "Age".into(), "Active".into(), "Home".into() ] )
+    .add_row( vec![ "Alice".into(), "30".into(), "true".into(), "/home/alice".into() ] )
+    .add_row( vec![ "Bob, Jr.".into(), "-4".into(), "false".into(), "./bob".into() ] )
+    .add_row( vec![ "Say \"hi\" | bye".into(), "7".into(), "true".into(), "~/carol".into() ] )
+    .build_view()
+}
+
+#[ cfg( any( feature = "table_csv", feature = "table_tsv", feature = "table_markdown", feature = "format_json" ) ) ]
+fn texts( view : &TableView ) -> Vec< Vec< String > >
+{
+  view.rows.iter().map( | row | row.iter().map( | cell | cell.text.clone() ).collect() ).collect()
+}
+
+#[ cfg( any( feature = "table_csv", feature = "table_tsv", feature = "table_markdown", all( feature = "format_json", feature = "format_yaml" ) ) ) ]
+fn assert_round_trip< F : Format + Parse >( formatter : &F, view : &TableView )
+{
+  let output = formatter.format( view ).unwrap();
+  let parsed = formatter.parse( &output ).unwrap();
+  assert_eq!( parsed.metadata.column_names, view.metadata.column_names, "output was:\n{output}" );
+  assert_eq!( texts( &parsed ), texts( view ), "output was:\n{output}" );
+}
+
+/// FT-1 — `feature/010`: CSV round trip undoes RFC 4180 quoting and skips heading/footer rules.
+// test_kind: standard
+#[ cfg( feature = "table_csv" ) ]
+#[ test ]
+fn csv_round_trip_ft1()
+{
+  let plain = TableFormatter::with_config( TableConfig::csv() );
+  assert_round_trip( &plain, &sample_view() );
+
+  let titled = TableFormatter::with_config(
+    TableConfig::csv().with_heading( Heading::new( "Users" ) ).with_footer( Heading::new( "3 rows" ) )
+  );
+  assert_round_trip( &titled, &sample_view() );
+}
+
+/// FT-2 — `feature/010`: TSV round trip restores escaped newlines.
+// test_kind: standard
+#[ cfg( feature = "table_tsv" ) ]
+#[ test ]
+fn tsv_round_trip_ft2()
+{
+  let view = RowBuilder::new( vec![ "Key".into(), "Note".into() ] )
+    .add_row( vec![ "a".into(), "line one\nline two".into() ] )
+    .build_view();
+  assert_round_trip( &TableFormatter::with_config( TableConfig::tsv() ), &view );
+}
+
+/// FT-3 — `feature/010`: Markdown round trip skips the separator row and unescapes pipes.
+// test_kind: standard
+#[ cfg( feature = "table_markdown" ) ]
+#[ test ]
+fn markdown_round_trip_ft3()
+{
+  assert_round_trip( &TableFormatter::with_config( TableConfig::markdown() ), &sample_view() );
+}
+
+/// FT-4 — `feature/010`: JSON and YAML round trips keep column order.
+// test_kind: standard
+#[ cfg( all( feature = "format_json", feature = "format_yaml" ) ) ]
+#[ test ]
+fn json_and_yaml_round_trip_ft4()
+{
+  assert_round_trip( &data_fmt::JsonFormatter::new(), &sample_view() );
+  assert_round_trip( &data_fmt::YamlFormatter::new(), &sample_view() );
+}
+
+/// FT-5 — `feature/010`: JSON scalars become cell text; keys missing from a row become empty cells.
+// test_kind: standard
+#[ cfg( feature = "format_json" ) ]
+#[ test ]
+fn json_scalars_and_ragged_objects_ft5()
+{
+  let input = r#"[ { "id" : 1, "ok" : true }, { "id" : 2, "note" : null, "extra" : "x" } ]"#;
+  let parsed = data_fmt::JsonFormatter::new().parse( input ).unwrap();
+  assert_eq!( parsed.metadata.column_names, vec![ "id", "ok", "note", "extra" ] );
+  assert_eq!( texts( &parsed ), vec![ vec![ "1", "true", "", "" ], vec![ "2", "", "", "x" ] ] );
+  assert_eq!( parsed.metadata.column_types[ 0 ], DataType::Integer );
+  assert_eq!( parsed.metadata.column_types[ 1 ], DataType::Boolean );
+}
+
+/// FT-6 — `feature/010`: column types are inferred when every non-empty cell agrees.
+// test_kind: standard
+#[ cfg( feature = "table_csv" ) ]
+#[ test ]
+fn column_types_are_inferred_ft6()
+{
+  let parsed = TableFormatter::with_config( TableConfig::csv() )
+    .parse( "Name,Age,Active,Home,Mixed\nAlice,30,true,/home/a,1\nBob,,false,./b,x\n" )
+    .unwrap();
+  assert_eq!(
+    parsed.metadata.column_types,
+    vec![ DataType::String, DataType::Integer, DataType::Boolean, DataType::Path, DataType::String ]
+  );
+}
+
+/// FT-7 — `feature/010`: malformed input is a `Syntax` error naming the line.
+// test_kind: standard
+#[ cfg( feature = "table_csv" ) ]
+#[ test ]
+fn malformed_input_reports_line_ft7()
+{
+  let csv = TableFormatter::with_config( TableConfig::csv() );
+
+  let err = csv.parse( "a,b\n1,2\n3\n" ).unwrap_err();
+  assert!( matches!( err, ParseError::Syntax { line : 3, .. } ), "got: {err:?}" );
+
+  let err = csv.parse( "a,b\n\"open,2\n" ).unwrap_err();
+  assert!( matches!( err, ParseError::Syntax { line : 2, .. } ), "got: {err:?}" );
+
+  assert!( csv.parse( "" ).unwrap().metadata.column_names.is_empty() );
+}
+
+/// FT-8 — `feature/010`: aligned table styles are rejected as unsupported.
+// test_kind: standard
+#[ test ]
+fn aligned_styles_are_unsupported_ft8()
+{
+  let formatter = TableFormatter::with_config( TableConfig::bordered() );
+  let output = formatter.format( &sample_view() ).unwrap();
+  let err = formatter.parse( &output ).unwrap_err();
+  assert!( matches!( err, ParseError::UnsupportedOperation( _ ) ), "got: {err:?}" );
+}
+
+/// FT-9 — `feature/010`: CSV parsed and re-emitted as JSON keeps numeric-looking text verbatim.
+// test_kind: standard
+#[ cfg( all( feature = "table_csv", feature = "format_json" ) ) ]
+#[ test ]
+fn csv_to_json_keeps_number_like_text_ft9()
+{
+  let parsed = TableFormatter::with_config( TableConfig::csv() )
+    .parse( "Zip,Id,Version,Code,Count,Ratio\n007,12345678901234567890,1.10,1E5,42,0.5\n0042,98765432109876543210,2.0,+5,-7,1.0\n" )
+    .unwrap();
+  assert_eq!(
+    parsed.metadata.column_types,
+    vec![ DataType::String, DataType::String, DataType::String, DataType::String, DataType::Integer, DataType::Float ]
+  );
+
+  let json = data_fmt::JsonFormatter::new().format( &parsed ).unwrap();
+  for text in [ r#""007""#, r#""0042""#, r#""12345678901234567890""#, r#""98765432109876543210""#, r#""1.10""#, r#""1E5""#, r#""+5""# ]
+  {
+    assert!( json.contains( text ), "{text} missing from:\n{json}" );
+  }
+  assert!( json.contains( "42" ) && !json.contains( r#""42""# ), "{json}" );
+  assert!( json.contains( "-7" ) && json.contains( "0.5" ), "{json}" );
+}
+
+/// FT-10 — `feature/010`: backslashes are written as is by default; with
+/// `with_escape_backslashes( true )` CSV/TSV round trips keep them apart from escaped newlines.
+// test_kind: standard
+#[ cfg( all( feature = "table_csv", feature = "table_tsv" ) ) ]
+#[ test ]
+fn backslashes_round_trip_ft10()
+{
+  let view = RowBuilder::new( vec![ "Path".into(), "Note".into() ] )
+    .add_row( vec![ r"C:\new\table".into(), r"literal \n, not a newline".into() ] )
+    .add_row( vec![ r"\\server\share\".into(), "two\nlines \\ here".into() ] )
+    .build_view();
+  let output = TableFormatter::with_config( TableConfig::csv() ).format( &view ).unwrap();
+  assert!( output.contains( r"C:\new\table" ) && output.contains( r"\\server\share\" ), "output was:\n{output}" );
+
+  for config in [ TableConfig::csv(), TableConfig::tsv() ]
+  {
+    assert_round_trip( &TableFormatter::with_config( config.with_escape_backslashes( true ) ), &view );
+  }
+
+  let escaping = TableFormatter::with_config( TableConfig::csv().with_escape_backslashes( true ) );
+  let parsed = escaping.parse( "Path\nC:\\data\\x\\\n" ).unwrap();
+  assert_eq!( texts( &parsed ), vec![ vec![ "C:\\data\\x\\" ] ], "a lone backslash is kept as written" );
+}
+
+/// FT-11 — `feature/010`: only whole rule lines are skipped, not data rows starting with a rule character.
+// test_kind: standard
+#[ cfg( feature = "table_csv" ) ]
+#[ test ]
+fn data_row_starting_with_rule_char_is_kept_ft11()
+{
+  let view = RowBuilder::new( vec![ "Mark".into(), "Count".into() ] )
+    .add_row( vec![ "─".into(), "1".into() ] )
+    .add_row( vec![ "─── not a rule".into(), "2".into() ] )
+    .add_row( vec![ "──x".into(), "3".into() ] )
+    .build_view();
+  let titled = TableFormatter::with_config(
+    TableConfig::csv().with_heading( Heading::new( "Marks" ) ).with_footer( Heading::new( "3 rows" ) )
+  );
+  assert_round_trip( &titled, &view );
+  assert_round_trip( &TableFormatter::with_config( TableConfig::csv() ), &view );
+
+  let parsed = TableFormatter::with_config( TableConfig::csv() )
+    .parse( "─────────\nMark,Count\n─,1\n─── Footer ───\n" )
+    .unwrap();
+  assert_eq!( texts( &parsed ), vec![ vec![ "─", "1" ] ] );
+}
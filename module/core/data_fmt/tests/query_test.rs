@@ -0,0 +1,178 @@
+//! Tests for the `TableView` query layer (`feature/012_query`)
+//!
+//! ## What This Tests
+//!
+//! Verifies that sorting compares by column type with nulls last, that filter,
+//! sort and projection keep `row_details` and `values` aligned with their rows,
+//! that group-by aggregates follow first-occurrence order and typed arithmetic,
+//! and that totals reach formatter output as a row or a footer `Heading`.
+
+#![ cfg( feature = "enabled" ) ]
+
+use data_fmt::{
+  RowBuilder, TableView, TableMetadata, TableFormatter, TableConfig, DataType, CellValue, Format,
+  SortKey, Aggregate, QueryError,
+};
+
+// --- Test helpers ---
+
+fn files() -> TableView
+{
+  TableView::from_values(
+    vec![ "File".into(), "Lang".into(), "Lines".into() ],
+    vec![
+      vec![ "main.rs".into(), "rust".into(), 120.into() ],
+      vec![ "Cargo.toml".into(), "toml".into(), 30.into() ],
+      vec![ "lib.rs".into(), "rust".into(), 9.into() ],
+      vec![ "build.rs".into(), "rust".into(), CellValue::Null ],
+      vec![ "deny.toml".into(), "toml".into(), 30.into() ],
+    ],
+  )
+}
+
+fn column( view : &TableView, col : usize ) -> Vec< &str >
+{
+  view.rows.iter().map( | row | row[ col ].text.as_str() ).collect()
+}
+
+/// FT-1 — `feature/012`: integer columns sort numerically, string columns lexically.
+// test_kind: standard
+#[ test ]
+fn sort_is_type_aware_ft1()
+{
+  let view = TableView::new(
+    TableMetadata::with_types( vec![ "n".into(), "s".into() ], vec![ DataType::Integer, DataType::String ] ),
+    vec![
+      vec![ "10".into(), "10".into() ],
+      vec![ "9".into(), "9".into() ],
+      vec![ "100".into(), "100".into() ],
+    ],
+  );
+
+  let by_number = view.clone().sort_by( &[ SortKey::asc( "n" ) ] ).unwrap();
+  assert_eq!( column( &by_number, 0 ), vec![ "9", "10", "100" ] );
+
+  let by_text = view.sort_by( &[ SortKey::asc( "s" ) ] ).unwrap();
+  assert_eq!( column( &by_text, 1 ), vec![ "10", "100", "9" ] );
+}
+
+/// FT-2 — `feature/012`: later keys break ties, the sort is stable and nulls sort last both ways.
+// test_kind: standard
+#[ test ]
+fn multi_key_sort_with_nulls_last_ft2()
+{
+  let sorted = files().sort_by( &[ SortKey::desc( "Lines" ), SortKey::asc( "File" ) ] ).unwrap();
+  assert_eq!( column( &sorted, 0 ), vec![ "main.rs", "Cargo.toml", "deny.toml", "lib.rs", "build.rs" ] );
+
+  let ascending = files().sort_by( &[ SortKey::asc( "Lines" ) ] ).unwrap();
+  assert_eq!( column( &ascending, 0 ), vec![ "lib.rs", "Cargo.toml", "deny.toml", "main.rs", "build.rs" ] );
+  assert_eq!( ascending.values[ 0 ][ 2 ], CellValue::Int( 9 ) );
+}
+
+/// FT-3 — `feature/012`: filter keeps detail lines aligned with surviving rows.
+// test_kind: standard
+#[ test ]
+fn filter_keeps_details_aligned_ft3()
+{
+  let view = RowBuilder::new( vec![ "Name".into(), "State".into() ] )
+    .add_row_with_detail( vec![ "a".into(), "ok".into() ], Some( "detail a".into() ) )
+    .add_row_with_detail( vec![ "b".into(), "failed".into() ], Some( "detail b".into() ) )
+    .add_row_with_detail( vec![ "c".into(), "failed".into() ], Some( "detail c".into() ) )
+    .build_view();
+
+  let failed = view.filter( | row | row.text( "State" ) == Some( "failed" ) );
+  assert_eq!( column( &failed, 0 ), vec![ "b", "c" ] );
+  let details : Vec< _ > = failed.row_details.iter().map( | d | d.as_ref().map( | d | d.text.as_str() ) ).collect();
+  assert_eq!( details, vec![ Some( "detail b" ), Some( "detail c" ) ] );
+}
+
+/// FT-4 — `feature/012`: select projects and reorders columns together with their types and values.
+// test_kind: standard
+#[ test ]
+fn select_projects_and_reorders_ft4()
+{
+  let projected = files().select( &[ "Lines", "File" ] ).unwrap();
+  assert_eq!( projected.metadata.column_names, vec![ "Lines", "File" ] );
+  assert_eq!( projected.metadata.column_types, vec![ DataType::Integer, DataType::String ] );
+  assert_eq!( projected.values[ 0 ], vec![ CellValue::Int( 120 ), CellValue::from( "main.rs" ) ] );
+  assert_eq!( projected.rows[ 1 ][ 1 ].text, "Cargo.toml" );
+}
+
+/// FT-5 — `feature/012`: unknown columns and non-numeric sums are reported, not panicked on.
+// test_kind: standard
+#[ test ]
+fn errors_are_reported_ft5()
+{
+  assert_eq!( files().sort_by( &[ SortKey::asc( "Size" ) ] ).unwrap_err(), QueryError::UnknownColumn( "Size".into() ) );
+  assert_eq!( files().select( &[ "File", "Size" ] ).unwrap_err(), QueryError::UnknownColumn( "Size".into() ) );
+  assert_eq!( files().group_by( &[ "Lang" ], &[ Aggregate::max( "Size" ) ] ).unwrap_err(), QueryError::UnknownColumn( "Size".into() ) );
+  assert_eq!(
+    files().totals( &[ Aggregate::sum( "File" ) ] ).unwrap_err(),
+    QueryError::NotNumeric { column : "File".into(), row : 0 },
+  );
+}
+
+/// FT-6 — `feature/012`: group-by keeps first-occurrence order and aggregates skip nulls.
+// test_kind: standard
+#[ test ]
+fn group_by_aggregates_ft6()
+{
+  let grouped = files().group_by( &[ "Lang" ], &[
+    Aggregate::count(),
+    Aggregate::count_of( "Lines" ),
+    Aggregate::sum( "Lines" ),
+    Aggregate::min( "Lines" ),
+    Aggregate::max( "File" ),
+    Aggregate::avg( "Lines" ).with_label( "mean" ),
+  ] ).unwrap();
+
+  assert_eq!(
+    grouped.metadata.column_names,
+    vec![ "Lang", "count", "count(Lines)", "sum(Lines)", "min(Lines)", "max(File)", "mean" ],
+  );
+  assert_eq!( grouped.values[ 0 ], vec![
+    "rust".into(), 3.into(), 2.into(), 129.into(), 9.into(), "main.rs".into(), 64.5.into(),
+  ] );
+  assert_eq!( grouped.values[ 1 ], vec![
+    "toml".into(), 2.into(), 2.into(), 60.into(), 30.into(), "deny.toml".into(), 30.0.into(),
+  ] );
+  assert_eq!( grouped.metadata.column_types[ 6 ], DataType::Float );
+}
+
+/// FT-7 — `feature/012`: text-only views aggregate numeric text and skip empty cells; integer sums widen to float on overflow.
+// test_kind: standard
+#[ test ]
+fn text_cells_and_overflow_ft7()
+{
+  let view = RowBuilder::new( vec![ "n".into() ] )
+    .add_row( vec![ "1.5".into() ] )
+    .add_row( vec![ "2".into() ] )
+    .add_row( vec![ "".into() ] )
+    .build_view();
+  let totals = view.totals( &[ Aggregate::sum( "n" ), Aggregate::count_of( "n" ) ] ).unwrap();
+  assert_eq!( totals.values(), &[ CellValue::Float( 3.5 ), CellValue::Int( 2 ) ] );
+
+  let big = TableView::from_values( vec![ "n".into() ], vec![ vec![ i64::MAX.into() ], vec![ 1.into() ] ] );
+  let totals = big.totals( &[ Aggregate::sum( "n" ) ] ).unwrap();
+  assert_eq!( totals.get( "sum(n)" ), Some( &CellValue::Float( i64::MAX as f64 + 1.0 ) ) );
+}
+
+/// FT-8 — `feature/012`: totals render as an appended row and as a table footer heading.
+// test_kind: standard
+#[ test ]
+fn totals_render_as_row_and_footer_ft8()
+{
+  let view = files().select( &[ "File", "Lines" ] ).unwrap();
+  let aggregates = [ Aggregate::count(), Aggregate::sum( "Lines" ) ];
+
+  let footer = view.totals( &aggregates ).unwrap().to_heading( "Total" );
+  let output = TableFormatter::with_config( TableConfig::plain().with_footer( footer ) ).format( &view ).unwrap();
+  let last = output.lines().last().unwrap();
+  assert!( last.starts_with( "─── Total · count: 5 · sum(Lines): 189" ), "got:\n{output}" );
+
+  let with_row = view.with_totals_row( "Total", &aggregates ).unwrap();
+  assert_eq!( with_row.rows.len(), 6 );
+  assert_eq!( with_row.values[ 5 ], vec![ CellValue::from( "Total" ), CellValue::Int( 189 ) ] );
+  let output = TableFormatter::new().format( &with_row ).unwrap();
+  assert_eq!( output.lines().last().unwrap(), "Total         189", "got:\n{output}" );
+}
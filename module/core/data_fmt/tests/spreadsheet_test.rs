@@ -0,0 +1,217 @@
+//! Tests for XLSX/ODS spreadsheet output (`feature/014_spreadsheet_output`)
+//!
+//! ## What This Tests
+//!
+//! Verifies that `XlsxFormatter` and `OdsFormatter` produce well-formed ZIP
+//! containers with the parts each format requires, that typed `CellValue`s map
+//! to native spreadsheet cell types, that heading/footer rows are merged and the
+//! header row frozen, that ANSI colours become font/fill styles, and that the
+//! text-only `format` entry point is rejected while `format_bytes` works for
+//! every formatter.
+
+#![ cfg( any( feature = "format_xlsx", feature = "format_ods" ) ) ]
+
+use data_fmt::{ TableView, CellValue, Heading, Format };
+
+// --- Test helpers ---
+
+/// Entries of a stored (uncompressed) ZIP archive, in file order, CRC-checked.
+fn unzip( bytes : &[ u8 ] ) -> Vec< ( String, Vec< u8 > ) >
+{
+  let u16_at = | pos : usize | usize::from( u16::from_le_bytes( [ bytes[ pos ], bytes[ pos + 1 ] ] ) );
+  let u32_at = | pos : usize | u32::from_le_bytes( [ bytes[ pos ], bytes[ pos + 1 ], bytes[ pos + 2 ], bytes[ pos + 3 ] ] );
+  let mut entries = Vec::new();
+  let mut pos = 0;
+  while u32_at( pos ) == 0x0403_4b50
+  {
+    assert_eq!( u16_at( pos + 8 ), 0, "entries are stored" );
+    let crc = u32_at( pos + 14 );
+    let size = u32_at( pos + 18 ) as usize;
+    let name_len = u16_at( pos + 26 );
+    let extra_len = u16_at( pos + 28 );
+    let name = String::from_utf8( bytes[ pos + 30..pos + 30 + name_len ].to_vec() ).unwrap();
+    let start = pos + 30 + name_len + extra_len;
+    let data = bytes[ start..start + size ].to_vec();
+    assert_eq!( crc32( &data ), crc, "CRC of {name}" );
+    entries.push( ( name, data ) );
+    pos = start + size;
+  }
+  let end = bytes.len() - 22;
+  assert_eq!( u32_at( end ), 0x0605_4b50, "end of central directory" );
+  assert_eq!( u16_at( end + 10 ), entries.len() );
+  entries
+}
+
+fn crc32( data : &[ u8 ] ) -> u32
+{
+  let mut crc = u32::MAX;
+  for &byte in data
+  {
+    crc ^= u32::from( byte );
+    for _ in 0..8
+    {
+      crc = if crc & 1 == 1 { 0xEDB8_8320 ^ ( crc >> 1 ) } else { crc >> 1 };
+    }
+  }
+  !crc
+}
+
+fn part( entries : &[ ( String, Vec< u8 > ) ], name : &str ) -> String
+{
+  let ( _, data ) = entries.iter().find( | ( n, _ ) | n == name ).unwrap_or_else( || panic!( "missing part {name}" ) );
+  String::from_utf8( data.clone() ).unwrap()
+}
+
+fn typed_view() -> TableView
+{
+  TableView::from_values(
+    vec![ "Name".into(), "Count".into(), "Ratio".into(), "Ok".into(), "At".into(), "Note".into() ],
+    vec![
+      vec![ "a<b".into(), CellValue::Int( 30 ), CellValue::Float( 2.5 ), CellValue::Bool( true ), CellValue::Timestamp( 86_400 * 19_783 + 43_200 ), CellValue::Null ],
+      vec![ "two  spaces".into(), CellValue::Int( -1 ), CellValue::Float( 1.0 ), CellValue::Bool( false ), CellValue::Null, "x".into() ],
+    ],
+  )
+}
+
+/// FT-1 — `feature/014`: XLSX is a valid ZIP with the OOXML parts and relationships.
+// test_kind: standard
+#[ cfg( feature = "format_xlsx" ) ]
+#[ test ]
+fn xlsx_container_parts_ft1()
+{
+  let bytes = data_fmt::XlsxFormatter::new().format_bytes( &typed_view() ).unwrap();
+  let entries = unzip( &bytes );
+  let names : Vec< &str > = entries.iter().map( | ( n, _ ) | n.as_str() ).collect();
+  assert_eq!( names, vec![
+    "[Content_Types].xml", "_rels/.rels", "xl/workbook.xml",
+    "xl/_rels/workbook.xml.rels", "xl/styles.xml", "xl/worksheets/sheet1.xml",
+  ] );
+  assert!( part( &entries, "[Content_Types].xml" ).contains( "/xl/worksheets/sheet1.xml" ) );
+  assert!( part( &entries, "xl/workbook.xml" ).contains( "<sheet name=\"Sheet1\" sheetId=\"1\" r:id=\"rId1\"/>" ) );
+}
+
+/// FT-2 — `feature/014`: XLSX cells are typed — numbers, booleans, date serials, inline text; nulls omitted.
+// test_kind: standard
+#[ cfg( feature = "format_xlsx" ) ]
+#[ test ]
+fn xlsx_typed_cells_ft2()
+{
+  let entries = unzip( &data_fmt::XlsxFormatter::new().format_bytes( &typed_view() ).unwrap() );
+  let sheet = part( &entries, "xl/worksheets/sheet1.xml" );
+  assert!( sheet.contains( "<c r=\"A2\" t=\"inlineStr\"><is><t xml:space=\"preserve\">a&lt;b</t></is></c>" ) );
+  assert!( sheet.contains( "<c r=\"B2\"><v>30</v></c>" ) );
+  assert!( sheet.contains( "<c r=\"C3\"><v>1.0</v></c>" ) );
+  assert!( sheet.contains( "<c r=\"D2\" t=\"b\"><v>1</v></c>" ) );
+  assert!( sheet.contains( "<c r=\"D3\" t=\"b\"><v>0</v></c>" ) );
+  // 2024-03-01 12:00 UTC = serial 45352.5, with the date number format.
+  let date = sheet.split( "<c r=\"E2\"" ).nth( 1 ).unwrap();
+  assert!( date.starts_with( " s=\"" ) && date.contains( "<v>45352.5</v>" ), "{date}" );
+  assert!( !sheet.contains( "r=\"F2\"" ) && !sheet.contains( "r=\"E3\"" ) );
+  assert!( part( &entries, "xl/styles.xml" ).contains( "formatCode=\"yyyy-mm-dd hh:mm:ss\"" ) );
+}
+
+/// FT-3 — `feature/014`: heading and footer become merged rows; the header row is frozen.
+// test_kind: standard
+#[ cfg( feature = "format_xlsx" ) ]
+#[ test ]
+fn xlsx_heading_footer_and_frozen_header_ft3()
+{
+  let formatter = data_fmt::XlsxFormatter::new()
+    .with_heading( Heading::new( "Report" ).with_field( "Q1" ) )
+    .with_footer( Heading::new( "2 rows" ) );
+  let sheet = part( &unzip( &formatter.format_bytes( &typed_view() ).unwrap() ), "xl/worksheets/sheet1.xml" );
+  assert!( sheet.contains( "<pane ySplit=\"2\" topLeftCell=\"A3\" activePane=\"bottomLeft\" state=\"frozen\"/>" ) );
+  assert!( sheet.contains( "<t xml:space=\"preserve\">Report · Q1</t>" ) );
+  assert!( sheet.contains( "<mergeCells count=\"2\"><mergeCell ref=\"A1:F1\"/><mergeCell ref=\"A5:F5\"/></mergeCells>" ) );
+  assert!( sheet.contains( "<row r=\"2\"><c r=\"A2\" s=\"" ) );
+}
+
+/// FT-4 — `feature/014`: ANSI colours on cells, the header and alternating rows become styles.
+// test_kind: standard
+#[ cfg( feature = "format_xlsx" ) ]
+#[ test ]
+fn xlsx_colors_become_styles_ft4()
+{
+  let mut view = typed_view();
+  view.rows[ 0 ][ 0 ] = view.rows[ 0 ][ 0 ].clone().with_color( "\x1b[31m" ).with_bold();
+  view.rows[ 1 ][ 5 ] = view.rows[ 1 ][ 5 ].clone().with_color( "\x1b[38;2;1;2;3m" );
+  let formatter = data_fmt::XlsxFormatter::new()
+    .with_header_color( "\x1b[36m" )
+    .with_row_colors( "\x1b[0m", "\x1b[48;5;235m" );
+  let styles = part( &unzip( &formatter.format_bytes( &view ).unwrap() ), "xl/styles.xml" );
+  assert!( styles.contains( "<font><b/><color rgb=\"FF00CDCD\"/>" ), "header: {styles}" );
+  assert!( styles.contains( "<font><b/><color rgb=\"FFCD0000\"/>" ), "bold red cell" );
+  assert!( styles.contains( "<color rgb=\"FF010203\"/>" ), "true colour" );
+  assert!( styles.contains( "<fgColor rgb=\"FF262626\"/>" ), "odd row fill" );
+}
+
+/// FT-5 — `feature/014`: ODS starts with a stored `mimetype` entry and carries typed cells.
+// test_kind: standard
+#[ cfg( feature = "format_ods" ) ]
+#[ test ]
+fn ods_container_and_typed_cells_ft5()
+{
+  let bytes = data_fmt::OdsFormatter::new().with_sheet_name( "Data" ).format_bytes( &typed_view() ).unwrap();
+  let entries = unzip( &bytes );
+  assert_eq!( entries[ 0 ], ( "mimetype".to_string(), b"application/vnd.oasis.opendocument.spreadsheet".to_vec() ) );
+  assert!( part( &entries, "META-INF/manifest.xml" ).contains( "manifest:full-path=\"content.xml\"" ) );
+
+  let content = part( &entries, "content.xml" );
+  assert!( content.contains( "<table:table table:name=\"Data\">" ) );
+  assert!( content.contains( "office:value-type=\"float\" office:value=\"30\"><text:p>30</text:p>" ) );
+  assert!( content.contains( "office:value-type=\"float\" office:value=\"2.5\"" ) );
+  assert!( content.contains( "office:value-type=\"boolean\" office:boolean-value=\"false\"><text:p>FALSE</text:p>" ) );
+  assert!( content.contains( "office:value-type=\"date\" office:date-value=\"2024-03-01T12:00:00\"" ) );
+  assert!( content.contains( "<text:p>a&lt;b</text:p>" ) );
+  assert!( content.contains( "<text:p>two <text:s text:c=\"1\"/>spaces</text:p>" ) );
+}
+
+/// FT-6 — `feature/014`: ODS heading/footer span all columns; the header sits in `table-header-rows`.
+// test_kind: standard
+#[ cfg( feature = "format_ods" ) ]
+#[ test ]
+fn ods_spanned_rows_and_header_rows_ft6()
+{
+  let formatter = data_fmt::OdsFormatter::new()
+    .with_heading( Heading::new( "Report" ) )
+    .with_footer( Heading::new( "end" ) )
+    .with_header_color( "\x1b[34m" );
+  let content = part( &unzip( &formatter.format_bytes( &typed_view() ).unwrap() ), "content.xml" );
+  let covered = "<table:covered-table-cell/>".repeat( 5 );
+  assert!( content.contains( &format!( "table:number-columns-spanned=\"6\" office:value-type=\"string\"><text:p>Report</text:p></table:table-cell>{covered}" ) ) );
+  assert!( content.contains( &format!( "<text:p>end</text:p></table:table-cell>{covered}</table:table-row>" ) ) );
+  assert!( content.contains( "</table:table-row><table:table-header-rows><table:table-row>" ) );
+  assert!( content.contains( "fo:font-weight=\"bold\" fo:color=\"#0000EE\"" ) );
+  assert!( content.contains( "fo:font-style=\"italic\"" ) );
+}
+
+/// FT-7 — `feature/014`: binary formatters reject `format`; `format_bytes` defaults to UTF-8 text elsewhere.
+// test_kind: standard
+#[ test ]
+fn format_bytes_entry_point_ft7()
+{
+  #[ cfg( feature = "format_xlsx" ) ]
+  assert!( matches!( data_fmt::XlsxFormatter::new().format( &typed_view() ), Err( data_fmt::FormatError::UnsupportedOperation( _ ) ) ) );
+  #[ cfg( feature = "format_ods" ) ]
+  assert!( matches!( data_fmt::OdsFormatter::new().format( &typed_view() ), Err( data_fmt::FormatError::UnsupportedOperation( _ ) ) ) );
+
+  let table = data_fmt::TableFormatter::new();
+  let view = typed_view();
+  assert_eq!( table.format_bytes( &view ).unwrap(), table.format( &view ).unwrap().into_bytes() );
+}
+
+/// FT-8 — `feature/014`: sheet names are sanitized, and equal input yields byte-identical output.
+// test_kind: standard
+#[ cfg( feature = "format_xlsx" ) ]
+#[ test ]
+fn sheet_name_sanitized_and_output_deterministic_ft8()
+{
+  let formatter = data_fmt::XlsxFormatter::new().with_sheet_name( "'Q1/Q2: [draft] sales report for the year'" );
+  let bytes = formatter.format_bytes( &typed_view() ).unwrap();
+  assert_eq!( bytes, formatter.format_bytes( &typed_view() ).unwrap() );
+  let workbook = part( &unzip( &bytes ), "xl/workbook.xml" );
+  assert!( workbook.contains( "<sheet name=\"Q1Q2 draft sales report for th\"" ), "{workbook}" );
+
+  let fallback = data_fmt::XlsxFormatter::new().with_sheet_name( "[]" ).format_bytes( &typed_view() ).unwrap();
+  assert!( part( &unzip( &fallback ), "xl/workbook.xml" ).contains( "name=\"Sheet1\"" ) );
+}
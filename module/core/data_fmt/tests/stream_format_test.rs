@@ -0,0 +1,205 @@
+//! Tests for streaming output via `StreamFormat` / `RowSink` (`feature/009_streaming_output`)
+//!
+//! ## What This Tests
+//!
+//! Verifies that rows pushed one at a time into a `RowSink` produce the same bytes
+//! as `Format::format()` on the equivalent `TableView` (for table/expanded/json/sql),
+//! that width-dependent formatters honor the look-ahead window and fixed-width mode,
+//! and that misuse (wrong row length, writes after `finish()`) surfaces as
+//! `FormatError::InvalidData` rather than a panic.
+
+#![ cfg( feature = "enabled" ) ]
+
+use data_fmt::{ RowBuilder, TableFormatter, TableConfig, ExpandedFormatter, ExpandedConfig, Heading, TableView, Format, StreamFormat, FormatError };
+
+// --- Test helpers ---
+
+fn sample_view() -> TableView
+{
+  RowBuilder::new( vec![ "Name".into(), "Role".into() ] )
+    .add_row( vec![ "Alice".into(), "admin".into() ] )
+    .add_row_with_detail( vec![ "Bob".into(), "dev".into() ], Some( "on leave".into() ) )
+    .add_row( vec![ "Carol".into(), "ops".into() ] )
+    .build_view()
+}
+
+fn streamed< F : StreamFormat >( formatter : &F, view : &TableView ) -> String
+{
+  let mut out = Vec::new();
+  formatter.write_view( view, &mut out ).unwrap();
+  String::from_utf8( out ).unwrap()
+}
+
+/// FT-1 — `feature/009`: streamed table output is byte-identical to `Format::format()`
+/// when the whole table fits in the look-ahead window.
+// test_kind: standard
+#[ test ]
+fn table_stream_matches_buffered_output_ft1()
+{
+  let config = TableConfig::bordered()
+    .with_terminal_width( Some( 200 ) )
+    .with_heading( Heading::new( "Users" ) )
+    .with_footer( Heading::new( "3 rows" ) );
+  let formatter = TableFormatter::with_config( config );
+  let view = sample_view();
+
+  assert_eq!( streamed( &formatter, &view ), formatter.format( &view ).unwrap() );
+}
+
+/// FT-2 — `feature/009`: rows past the window that exceed the locked width are truncated.
+// test_kind: standard
+#[ test ]
+fn table_stream_truncates_rows_after_window_ft2()
+{
+  let config = TableConfig::plain().with_terminal_width( Some( 200 ) ).with_stream_window( 1 );
+  let formatter = TableFormatter::with_config( config );
+  let metadata = sample_view().metadata;
+  let mut out = Vec::new();
+  {
+    let mut sink = formatter.row_sink( &metadata, &mut out ).unwrap();
+    sink.write_row( &[ "Al".into(), "admin".into() ] ).unwrap();
+    sink.write_row( &[ "Maximilian".into(), "dev".into() ] ).unwrap();
+    sink.finish().unwrap();
+  }
+  let output = String::from_utf8( out ).unwrap();
+  let lines : Vec< &str > = output.lines().collect();
+
+  // Name column locked at width 4 ("Name") from header + first row.
+  assert_eq!( lines.len(), 4, "header, separator, two rows; got: {output}" );
+  assert!( lines[ 3 ].starts_with( "M..." ), "wide cell truncated to locked width; got: '{}'", lines[ 3 ] );
+  assert_eq!( lines[ 2 ].find( "admin" ), lines[ 3 ].find( "dev" ), "second column stays aligned" );
+}
+
+/// FT-3 — `feature/009`: fixed-width mode writes the header before any row arrives.
+// test_kind: standard
+#[ test ]
+fn table_stream_fixed_width_writes_header_eagerly_ft3()
+{
+  let config = TableConfig::plain().with_column_widths( vec![ 6, 6 ] );
+  let formatter = TableFormatter::with_config( config );
+  let metadata = sample_view().metadata;
+  let mut out = Vec::new();
+  let sink = formatter.row_sink( &metadata, &mut out ).unwrap();
+  drop( sink );
+
+  let output = String::from_utf8( out ).unwrap();
+  assert!( output.starts_with( "Name" ), "header written on open; got: '{output}'" );
+  assert_eq!( output.lines().count(), 2, "header + separator only; got: '{output}'" );
+}
+
+/// FT-4 — `feature/009`: streamed expanded output matches buffered output, with and without rules.
+// test_kind: standard
+#[ test ]
+fn expanded_stream_matches_buffered_output_ft4()
+{
+  let view = sample_view();
+
+  let plain = ExpandedFormatter::new();
+  assert_eq!( streamed( &plain, &view ), plain.format( &view ).unwrap() );
+
+  let ruled = ExpandedFormatter::with_config(
+    ExpandedConfig::new().with_heading( Heading::new( "Users" ) ).with_footer( Heading::new( "end" ) )
+  );
+  assert_eq!( streamed( &ruled, &view ), ruled.format( &view ).unwrap() );
+}
+
+/// FT-5 — `feature/009`: streamed SQL matches buffered SQL, including the empty case.
+// test_kind: standard
+#[ cfg( feature = "sql_ansi" ) ]
+#[ test ]
+fn sql_stream_matches_buffered_output_ft5()
+{
+  let formatter = data_fmt::SqlFormatter::new( "users" );
+  let view = sample_view();
+  assert_eq!( streamed( &formatter, &view ), formatter.format( &view ).unwrap() );
+
+  let empty = RowBuilder::new( vec![ "Name".into() ] ).build_view();
+  assert_eq!( streamed( &formatter, &empty ), "" );
+}
+
+/// FT-6 — `feature/009`: streamed JSON keeps column order and parses back to the same rows.
+// test_kind: standard
+#[ cfg( feature = "format_json" ) ]
+#[ test ]
+fn json_stream_preserves_column_order_ft6()
+{
+  let view = sample_view();
+  for formatter in [ data_fmt::JsonFormatter::new(), data_fmt::JsonFormatter::compact() ]
+  {
+    let output = streamed( &formatter, &view );
+    let parsed : serde_json::Value = serde_json::from_str( &output ).unwrap();
+    let buffered : serde_json::Value = serde_json::from_str( &formatter.format( &view ).unwrap() ).unwrap();
+    assert_eq!( parsed, buffered );
+    assert!( output.find( "\"Name\"" ) < output.find( "\"Role\"" ), "keys in column order" );
+  }
+
+  let pretty = data_fmt::JsonFormatter::new();
+  let empty = RowBuilder::new( vec![ "Name".into() ] ).build_view();
+  assert_eq!( streamed( &pretty, &empty ), pretty.format( &empty ).unwrap() );
+  let single = RowBuilder::new( vec![ "Name".into() ] ).add_row( vec![ "Alice".into() ] ).build_view();
+  assert_eq!( streamed( &pretty, &single ), pretty.format( &single ).unwrap() );
+}
+
+/// FT-7 — `feature/009`: wrong row length and writes after finish are `InvalidData` errors.
+// test_kind: standard
+#[ test ]
+fn sink_misuse_is_reported_as_invalid_data_ft7()
+{
+  let formatter = TableFormatter::new();
+  let metadata = sample_view().metadata;
+  let mut out = Vec::new();
+  let mut sink = formatter.row_sink( &metadata, &mut out ).unwrap();
+
+  let err = sink.write_row( &[ "only one".into() ] ).unwrap_err();
+  assert!( matches!( err, FormatError::InvalidData( _ ) ), "got: {err:?}" );
+
+  sink.finish().unwrap();
+  let err = sink.write_row( &[ "a".into(), "b".into() ] ).unwrap_err();
+  assert!( matches!( err, FormatError::InvalidData( _ ) ), "got: {err:?}" );
+  assert!( matches!( sink.finish(), Err( FormatError::InvalidData( _ ) ) ) );
+}
+
+/// FT-8 — `feature/009`: I/O failures surface as `FormatError::Io`.
+// test_kind: standard
+#[ test ]
+fn write_failure_is_reported_as_io_error_ft8()
+{
+  struct Broken;
+  impl std::io::Write for Broken
+  {
+    fn write( &mut self, _ : &[ u8 ] ) -> std::io::Result< usize >
+    {
+      Err( std::io::Error::other( "disk full" ) )
+    }
+    fn flush( &mut self ) -> std::io::Result< () >
+    {
+      Ok( () )
+    }
+  }
+
+  let err = TableFormatter::new().write_view( &sample_view(), Broken ).unwrap_err();
+  assert!( matches!( err, FormatError::Io( ref msg ) if msg.contains( "disk full" ) ), "got: {err:?}" );
+}
+
+/// FT-9 — `feature/009`: SQL starts a new `INSERT` every `batch_size` rows; without one
+/// only the streaming sink splits, at `DEFAULT_SQL_BATCH_SIZE`.
+// test_kind: standard
+#[ cfg( feature = "sql_ansi" ) ]
+#[ test ]
+fn sql_stream_splits_statements_by_batch_size_ft9()
+{
+  let view = sample_view();
+  let batched = data_fmt::SqlFormatter::new( "users" ).with_batch_size( 2 );
+  let output = streamed( &batched, &view );
+  assert_eq!( output, batched.format( &view ).unwrap() );
+  assert_eq!( output.matches( "INSERT INTO" ).count(), 2, "{output}" );
+  assert_eq!( output.matches( ';' ).count(), 2, "{output}" );
+  assert!( output.contains( "('Bob', 'dev');\nINSERT INTO" ), "{output}" );
+
+  let names : Vec< String > = ( 0..=data_fmt::DEFAULT_SQL_BATCH_SIZE ).map( | idx | format!( "u{idx}" ) ).collect();
+  let large = names.iter().fold( RowBuilder::new( vec![ "Name".into() ] ), | builder, name | builder.add_row( vec![ name.as_str().into() ] ) ).build_view();
+  let unset = data_fmt::SqlFormatter::new( "users" );
+  assert_eq!( unset.batch_size, 0 );
+  assert_eq!( unset.format( &large ).unwrap().matches( "INSERT INTO" ).count(), 1, "buffered output stays one statement" );
+  assert_eq!( streamed( &unset, &large ).matches( "INSERT INTO" ).count(), 2, "the sink splits at the default batch size" );
+}
@@ -0,0 +1,216 @@
+//! Tests for `#[derive(TableRow)]` (`feature/013_table_row_derive`)
+//!
+//! ## What This Tests
+//!
+//! Verifies that the derive produces headers, per-field `DataType`s and typed
+//! values; that `rename`, `skip`, `format`, `quantity` and `flatten` shape the
+//! columns; that record slices format directly through `Format::format_rows`;
+//! and that records convert to `TreeNode`s with nested structs as subtrees.
+
+#![ cfg( feature = "derive" ) ]
+
+use std::path::PathBuf;
+use data_fmt::{ TableRow, TableView, TableFormatter, CellValue, DataType, Format, TreeNode };
+
+// --- Test helpers ---
+
+#[ derive( TableRow ) ]
+struct Owner
+{
+  name : String,
+  #[ table( rename = "Mail" ) ]
+  email : Option< String >,
+}
+
+#[ derive( TableRow ) ]
+struct Artifact
+{
+  #[ table( rename = "Name" ) ]
+  name : &'static str,
+  path : PathBuf,
+  #[ table( quantity = bytes_human ) ]
+  size : u64,
+  ratio : f32,
+  cached : bool,
+  #[ table( skip ) ]
+  #[ allow( dead_code ) ]
+  internal : Vec< u8 >,
+  #[ table( format = version_label ) ]
+  version : ( u32, u32, u32 ),
+  #[ table( flatten ) ]
+  owner : Owner,
+}
+
+fn version_label( version : &( u32, u32, u32 ) ) -> String
+{
+  format!( "v{}.{}.{}", version.0, version.1, version.2 )
+}
+
+fn artifacts() -> Vec< Artifact >
+{
+  vec![
+    Artifact
+    {
+      name : "core",
+      path : "target/core.rlib".into(),
+      size : 1536,
+      ratio : 0.5,
+      cached : true,
+      internal : vec![ 1 ],
+      version : ( 1, 2, 0 ),
+      owner : Owner { name : "ann".into(), email : Some( "ann@example.com".into() ) },
+    },
+    Artifact
+    {
+      name : "cli",
+      path : "target/cli".into(),
+      size : 12,
+      ratio : 1.25,
+      cached : false,
+      internal : vec![],
+      version : ( 0, 9, 1 ),
+      owner : Owner { name : "bob".into(), email : None },
+    },
+  ]
+}
+
+fn leaf< 'a >( node : &'a TreeNode< String >, name : &str ) -> Option< &'a str >
+{
+  node.children.iter().find( | child | child.name == name ).and_then( | child | child.data.as_deref() )
+}
+
+/// FT-1 — `feature/013`: headers follow field order, honoring `rename`, `skip` and `flatten` prefixes.
+// test_kind: standard
+#[ test ]
+fn headers_follow_fields_and_attributes_ft1()
+{
+  assert_eq!(
+    Artifact::headers(),
+    vec![ "Name", "path", "size", "ratio", "cached", "version", "owner.name", "owner.Mail" ],
+  );
+}
+
+/// FT-2 — `feature/013`: column types come from field types; formatted columns are text.
+// test_kind: standard
+#[ test ]
+fn column_types_follow_field_types_ft2()
+{
+  assert_eq!( Artifact::column_types(), vec![
+    DataType::String, DataType::Path, DataType::String, DataType::Float,
+    DataType::Boolean, DataType::String, DataType::String, DataType::String,
+  ] );
+}
+
+/// FT-3 — `feature/013`: values are typed; `format` and `quantity` hooks produce text.
+// test_kind: standard
+#[ test ]
+fn values_apply_hooks_ft3()
+{
+  let records = artifacts();
+  assert_eq!( records[ 0 ].values(), vec![
+    "core".into(),
+    "target/core.rlib".into(),
+    "1.50 KB".into(),
+    CellValue::Float( 0.5 ),
+    true.into(),
+    "v1.2.0".into(),
+    "ann".into(),
+    "ann@example.com".into(),
+  ] );
+  assert_eq!( records[ 1 ].values()[ 7 ], CellValue::Null );
+}
+
+/// FT-4 — `feature/013`: `TableView::from_rows` keeps declared types and typed values.
+// test_kind: standard
+#[ test ]
+fn from_rows_builds_typed_view_ft4()
+{
+  let view = TableView::from_rows( &artifacts() );
+  assert_eq!( view.metadata.column_types, Artifact::column_types() );
+  assert_eq!( view.rows.len(), 2 );
+  assert_eq!( view.rows[ 1 ][ 2 ].text, "12 bytes" );
+  assert_eq!( view.rows[ 1 ][ 7 ].text, "" );
+  assert_eq!( view.cell_value( 1, 3 ), CellValue::Float( 1.25 ) );
+}
+
+/// FT-5 — `feature/013`: `format_rows` matches formatting the built view.
+// test_kind: standard
+#[ test ]
+fn format_rows_matches_view_ft5()
+{
+  let records = artifacts();
+  let formatter = TableFormatter::new();
+  assert_eq!( formatter.format_rows( &records ).unwrap(), formatter.format( &TableView::from_rows( &records ) ).unwrap() );
+}
+
+/// FT-6 — `feature/013`: data formats receive native literals from derived rows.
+// test_kind: standard
+#[ cfg( feature = "format_json" ) ]
+#[ test ]
+fn json_gets_native_literals_ft6()
+{
+  let output = data_fmt::JsonFormatter::compact().format_rows( &artifacts()[ 1.. ] ).unwrap();
+  assert_eq!(
+    output,
+    r#"[{"Name":"cli","path":"target/cli","size":"12 bytes","ratio":1.25,"cached":false,"version":"v0.9.1","owner.name":"bob","owner.Mail":null}]"#
+  );
+}
+
+/// FT-7 — `feature/013`: records become tree nodes; flattened fields become subtrees.
+// test_kind: standard
+#[ test ]
+fn records_convert_to_tree_nodes_ft7()
+{
+  let node = artifacts()[ 0 ].to_tree_node( "core" );
+  assert_eq!( node.name, "core" );
+  assert!( node.data.is_none() );
+  assert_eq!( leaf( &node, "size" ), Some( "1.50 KB" ) );
+  assert_eq!( leaf( &node, "cached" ), Some( "true" ) );
+
+  let owner = node.children.iter().find( | child | child.name == "owner" ).unwrap();
+  assert_eq!( leaf( owner, "name" ), Some( "ann" ) );
+  assert_eq!( leaf( owner, "Mail" ), Some( "ann@example.com" ) );
+  assert_eq!( node.children.len(), 7 );
+}
+
+/// FT-8 — `feature/013`: generic structs derive with their bounds; `Option`/`Vec` map to null/list.
+// test_kind: standard
+#[ test ]
+fn generic_struct_and_containers_ft8()
+{
+  #[ derive( TableRow ) ]
+  struct Sample< T : data_fmt::ToCellValue >
+  {
+    value : T,
+    tags : Vec< &'static str >,
+    note : Option< char >,
+  }
+
+  assert_eq!( Sample::< i16 >::column_types(), vec![ DataType::Integer, DataType::List, DataType::String ] );
+  let row = Sample { value : -3i16, tags : vec![ "a", "b" ], note : None };
+  assert_eq!( row.values(), vec![
+    CellValue::Int( -3 ),
+    CellValue::List( vec![ "a".into(), "b".into() ] ),
+    CellValue::Null,
+  ] );
+}
+
+/// FT-9 — `feature/013`: a `quantity` field outside `u64` renders its raw value.
+// test_kind: standard
+#[ test ]
+fn quantity_out_of_range_renders_raw_value_ft9()
+{
+  #[ derive( TableRow ) ]
+  struct Delta
+  {
+    #[ table( quantity = bytes_human ) ]
+    change : i64,
+    #[ table( quantity = bytes_human ) ]
+    total : u128,
+  }
+
+  let row = Delta { change : -2048, total : u128::from( u64::MAX ) + 1 };
+  assert_eq!( row.values(), vec![ "-2048".into(), "18446744073709551616".into() ] );
+  let fits = Delta { change : 2048, total : 12 };
+  assert_eq!( fits.values(), vec![ "2.00 KB".into(), "12 bytes".into() ] );
+}
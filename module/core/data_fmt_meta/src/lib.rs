@@ -0,0 +1,39 @@
+#![ doc( html_logo_url = "https://raw.githubusercontent.com/Wandalen/wTools/master/asset/img/logo_v3_trans_square.png" ) ]
+#![ doc
+(
+  html_favicon_url = "https://raw.githubusercontent.com/Wandalen/wTools/alpha/asset/img/logo_v3_trans_square_icon_small_v2.ico"
+) ]
+#![ doc( html_root_url = "https://docs.rs/data_fmt_meta/latest/data_fmt_meta/" ) ]
+//! Procedural macros for the `data_fmt` crate.
+
+#[ cfg( feature = "enabled" ) ]
+mod table_row;
+
+/// Derive `data_fmt::TableRow` for a struct with named fields.
+///
+/// Every field becomes one column, in declaration order. The column header is
+/// the field name, its `DataType` and typed cell value come from the field
+/// type's `data_fmt::ToCellValue` impl.
+///
+/// Field attributes, all under `#[ table( ... ) ]`:
+///
+/// - `rename = "Header"` — column header instead of the field name
+/// - `skip` — no column for this field
+/// - `format = path` — `fn( &FieldType ) -> String`; the column becomes text
+/// - `quantity = bytes_human` — shorthand for a `data_fmt::quantity` formatter
+///   in plain style (needs the `quantity` feature); the field must convert to `u64`
+///   with `TryFrom` and implement `Display`, which renders values outside `u64`
+/// - `flatten` — the field's type implements `TableRow`; its columns are inlined
+///   as `header.column`, and it becomes a subtree in `TableRow::to_tree_node`
+///
+/// To learn more, study [`data_fmt`](https://docs.rs/data_fmt/latest/data_fmt/).
+#[ cfg( feature = "enabled" ) ]
+#[ proc_macro_derive( TableRow, attributes( table ) ) ]
+pub fn table_row( input : proc_macro::TokenStream ) -> proc_macro::TokenStream
+{
+  match table_row::table_row( input.into() )
+  {
+    Ok( stream ) => stream.into(),
+    Err( err ) => err.to_compile_error().into(),
+  }
+}
@@ -0,0 +1,200 @@
+//! Implementation of `#[derive(TableRow)]`.
+
+use macro_tools::{ proc_macro2::TokenStream, quote::quote, syn };
+
+/// How one field contributes to the row.
+enum Column
+{
+  /// Typed cell through `ToCellValue`.
+  Value,
+  /// Text cell produced by `fn( &FieldType ) -> String`.
+  Format( syn::Path ),
+  /// Text cell produced by a `data_fmt::quantity` formatter.
+  Quantity( syn::Ident ),
+  /// Columns of a nested `TableRow`.
+  Flatten,
+}
+
+/// Parsed `#[ table( ... ) ]` attributes of one field.
+struct FieldAttributes
+{
+  rename : Option< String >,
+  skip : bool,
+  column : Column,
+}
+
+impl FieldAttributes
+{
+  fn parse( field : &syn::Field ) -> syn::Result< Self >
+  {
+    let mut result = Self { rename : None, skip : false, column : Column::Value };
+    for attr in field.attrs.iter().filter( | attr | attr.path().is_ident( "table" ) )
+    {
+      attr.parse_nested_meta( | meta |
+      {
+        if meta.path.is_ident( "rename" )
+        {
+          let lit : syn::LitStr = meta.value()?.parse()?;
+          result.rename = Some( lit.value() );
+        }
+        else if meta.path.is_ident( "skip" )
+        {
+          result.skip = true;
+        }
+        else if meta.path.is_ident( "format" )
+        {
+          result.set_column( &meta, Column::Format( meta.value()?.parse()? ) )?;
+        }
+        else if meta.path.is_ident( "quantity" )
+        {
+          result.set_column( &meta, Column::Quantity( meta.value()?.parse()? ) )?;
+        }
+        else if meta.path.is_ident( "flatten" )
+        {
+          result.set_column( &meta, Column::Flatten )?;
+        }
+        else
+        {
+          return Err( meta.error( "unknown `table` attribute; expected `rename`, `skip`, `format`, `quantity` or `flatten`" ) );
+        }
+        Ok( () )
+      })?;
+    }
+    Ok( result )
+  }
+
+  fn set_column( &mut self, meta : &syn::meta::ParseNestedMeta< '_ >, column : Column ) -> syn::Result< () >
+  {
+    if !matches!( self.column, Column::Value )
+    {
+      return Err( meta.error( "`format`, `quantity` and `flatten` are mutually exclusive" ) );
+    }
+    self.column = column;
+    Ok( () )
+  }
+}
+
+/// Text cell of a `quantity` field.
+///
+/// A value outside `u64` (negative, too large) shows as written instead of a
+/// made-up quantity.
+fn quantity_value( formatter : &syn::Ident, ident : &syn::Ident ) -> TokenStream
+{
+  quote!
+  {
+    ::data_fmt::CellValue::Text
+    (
+      match < ::core::primitive::u64 as ::core::convert::TryFrom< _ > >::try_from( ::core::clone::Clone::clone( &self.#ident ) )
+      {
+        ::core::result::Result::Ok( amount ) => ::data_fmt::quantity::#formatter( amount, ::data_fmt::QuantityStyle::Plain ),
+        ::core::result::Result::Err( _ ) => ::std::string::ToString::to_string( &self.#ident ),
+      }
+    )
+  }
+}
+
+/// Generate `impl data_fmt::TableRow` for a struct with named fields.
+pub fn table_row( input : TokenStream ) -> syn::Result< TokenStream >
+{
+  let ast : syn::DeriveInput = syn::parse2( input )?;
+  let syn::Data::Struct( syn::DataStruct { fields : syn::Fields::Named( fields ), .. } ) = &ast.data else
+  {
+    return Err( syn::Error::new_spanned( &ast.ident, "TableRow can only be derived for structs with named fields" ) );
+  };
+
+  let mut headers = Vec::new();
+  let mut types = Vec::new();
+  let mut values = Vec::new();
+  let mut children = Vec::new();
+
+  for field in &fields.named
+  {
+    let attrs = FieldAttributes::parse( field )?;
+    if attrs.skip
+    {
+      continue;
+    }
+    let ident = field.ident.as_ref().ok_or_else( || syn::Error::new_spanned( field, "expected a named field" ) )?;
+    let ty = &field.ty;
+    let header = attrs.rename.unwrap_or_else( || ident.to_string() );
+
+    let value = match attrs.column
+    {
+      Column::Flatten =>
+      {
+        headers.push( quote!
+        {
+          headers.extend
+          (
+            < #ty as ::data_fmt::TableRow >::headers().into_iter().map( | h | ::std::format!( "{}.{}", #header, h ) )
+          );
+        });
+        types.push( quote! { types.extend( < #ty as ::data_fmt::TableRow >::column_types() ); } );
+        values.push( quote! { values.extend( ::data_fmt::TableRow::values( &self.#ident ) ); } );
+        children.push( quote! { children.push( ::data_fmt::TableRow::to_tree_node( &self.#ident, #header ) ); } );
+        continue;
+      }
+      Column::Value =>
+      {
+        types.push( quote! { types.push( < #ty as ::data_fmt::ToCellValue >::data_type() ); } );
+        quote! { ::data_fmt::ToCellValue::to_cell_value( &self.#ident ) }
+      }
+      Column::Format( path ) =>
+      {
+        types.push( quote! { types.push( ::data_fmt::DataType::String ); } );
+        quote! { ::data_fmt::CellValue::Text( ( #path )( &self.#ident ) ) }
+      }
+      Column::Quantity( formatter ) =>
+      {
+        types.push( quote! { types.push( ::data_fmt::DataType::String ); } );
+        quantity_value( &formatter, ident )
+      }
+    };
+
+    headers.push( quote! { headers.push( ::std::string::String::from( #header ) ); } );
+    values.push( quote! { values.push( #value ); } );
+    children.push( quote!
+    {
+      children.push( ::data_fmt::TreeNode::new( ::std::string::String::from( #header ), Some( ::std::string::ToString::to_string( &#value ) ) ) );
+    });
+  }
+
+  let name = &ast.ident;
+  let ( impl_generics, ty_generics, where_clause ) = ast.generics.split_for_impl();
+
+  Ok( quote!
+  {
+    #[ automatically_derived ]
+    #[ allow( unused_mut ) ]
+    impl #impl_generics ::data_fmt::TableRow for #name #ty_generics #where_clause
+    {
+      fn headers() -> ::std::vec::Vec< ::std::string::String >
+      {
+        let mut headers = ::std::vec::Vec::new();
+        #( #headers )*
+        headers
+      }
+
+      fn column_types() -> ::std::vec::Vec< ::data_fmt::DataType >
+      {
+        let mut types = ::std::vec::Vec::new();
+        #( #types )*
+        types
+      }
+
+      fn values( &self ) -> ::std::vec::Vec< ::data_fmt::CellValue >
+      {
+        let mut values = ::std::vec::Vec::new();
+        #( #values )*
+        values
+      }
+
+      fn tree_children( &self ) -> ::std::vec::Vec< ::data_fmt::TreeNode< ::std::string::String > >
+      {
+        let mut children = ::std::vec::Vec::new();
+        #( #children )*
+        children
+      }
+    }
+  })
+}
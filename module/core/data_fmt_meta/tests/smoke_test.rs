@@ -0,0 +1,19 @@
+//! Smoke tests for the `data_fmt_meta` crate.
+//!
+//! Validates that the proc macro crate compiles and links in both local and
+//! published contexts. Behavior of `#[derive(TableRow)]` is covered by
+//! `data_fmt/tests/table_row_test.rs`.
+
+/// Verifies crate compiles and links correctly in local workspace context.
+#[ test ]
+fn local_smoke_test()
+{
+  let _ = ::test_tools ::test ::smoke_test ::smoke_test_for_local_run();
+}
+
+/// Verifies crate compiles and links correctly as published crates.io package.
+#[ test ]
+fn published_smoke_test()
+{
+  let _ = ::test_tools ::test ::smoke_test ::smoke_test_for_published_run();
+}
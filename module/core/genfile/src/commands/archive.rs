@@ -23,6 +23,7 @@ pub fn register( registry : &mut CommandRegistry ) -> Result< (), Box< dyn core:
   register_load( registry )?;
   register_save( registry )?;
   register_from_directory( registry )?;
+  register_templatize( registry )?;
   Ok( () )
 }
 
@@ -193,3 +194,40 @@ fn register_from_directory( registry : &mut CommandRegistry ) -> Result< (), Box
   registry.register_with_routine( &cmd, Box::new( crate::handlers::archive::from_directory_handler ) )?;
   Ok( () )
 }
+
+/// Register `.archive.templatize` command
+fn register_templatize( registry : &mut CommandRegistry ) -> Result< (), Box< dyn core::error::Error > >
+{
+  let cmd = CommandDefinition::new
+  (
+    CommandName::new( ".archive.templatize" ).expect( "valid command name" ),
+    "Create archive from directory, replacing values with placeholders".to_string(),
+  )
+  .with_namespace( String::new() )
+  .with_status( CommandStatus::Active )
+  .with_version( VersionType::new( "0.1.0" ).expect( "valid version" ) )
+  .with_tags( vec![ "archive".to_string(), "create".to_string(), "directory".to_string() ] )
+  .with_aliases( vec![] )
+  .with_permissions( vec![] )
+  .with_idempotent( true )
+  .with_deprecation_message( "" )
+  .with_http_method_hint( "" )
+  .with_examples( vec!
+  [
+    ".archive.templatize source::\"./my_crate\" values::\"name=my_crate,author=Alice\"".to_string(),
+  ] )
+  .with_auto_help( true )
+  .with_arguments( vec!
+  [
+    ArgumentDefinition::new( "source", Kind::Directory )
+      .with_description( "Source directory to scan" ),
+    ArgumentDefinition::new( "values", Kind::Map( Box::new( Kind::String ), Box::new( Kind::String ), Some( ',' ), Some( '=' ) ) )
+      .with_description( "Values to replace with placeholders (name=value,...)" ),
+    ArgumentDefinition::new( "verbosity", Kind::Integer )
+      .with_description( "Output verbosity level (0-5)" )
+      .with_optional( Some( "1" ) ),
+  ] );
+
+  registry.register_with_routine( &cmd, Box::new( crate::handlers::archive::templatize_handler ) )?;
+  Ok( () )
+}
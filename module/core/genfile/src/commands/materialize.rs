@@ -47,6 +47,7 @@ fn register_materialize( registry : &mut CommandRegistry ) -> Result< (), Box< d
     ".materialize destination::\"./output\"".to_string(),
     ".materialize destination::\"./my-project\" verbosity::2".to_string(),
     ".materialize destination::\"./preview\" dry::1".to_string(),
+    ".materialize destination::\"./my-project\" dry::true diff::true".to_string(),
   ] )
   .with_auto_help( true )
   .with_arguments( vec!
@@ -59,6 +60,9 @@ fn register_materialize( registry : &mut CommandRegistry ) -> Result< (), Box< d
     ArgumentDefinition::new( "dry", Kind::Boolean )
       .with_description( "Dry run mode (0 or 1)" )
       .with_optional( Some( "0" ) ),
+    ArgumentDefinition::new( "diff", Kind::Boolean )
+      .with_description( "With dry::1, print a unified diff against the destination (0 or 1)" )
+      .with_optional( Some( "0" ) ),
   ] );
 
   registry.register_with_routine( &cmd, Box::new( crate::handlers::materialize::materialize_handler ) )?;
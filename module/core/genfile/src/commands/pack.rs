@@ -46,6 +46,7 @@ fn register_pack( registry : &mut CommandRegistry ) -> Result< (), Box< dyn core
     ".pack input::\"./my-template\" output::\"template.json\"".to_string(),
     ".pack input::\"./src\" output::\"backup.yaml\" verbosity::2".to_string(),
     ".pack input::\"./templates\" output::\"archive.json\" dry::1".to_string(),
+    ".pack input::\"./assets\" output::\"assets.tar.gz\" format::tar.gz".to_string(),
   ] )
   .with_auto_help( true )
   .with_arguments( vec!
@@ -53,7 +54,10 @@ fn register_pack( registry : &mut CommandRegistry ) -> Result< (), Box< dyn core
     ArgumentDefinition::new( "input", Kind::Directory )
       .with_description( "Source directory to pack" ),
     ArgumentDefinition::new( "output", Kind::Path )
-      .with_description( "Output file path (JSON or YAML)" ),
+      .with_description( "Output file path (JSON, YAML, .tar or .tar.gz)" ),
+    ArgumentDefinition::new( "format", Kind::Enum( vec![ "json".to_string(), "yaml".to_string(), "tar".to_string(), "tar.gz".to_string() ] ) )
+      .with_description( "Archive format (default: from output extension)" )
+      .with_optional( None::< &str > ),
     ArgumentDefinition::new( "verbosity", Kind::Integer )
       .with_description( "Output verbosity level (0-5)" )
       .with_optional( Some( "1" ) ),
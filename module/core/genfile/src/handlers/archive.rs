@@ -9,6 +9,7 @@
 use unilang::semantic::VerifiedCommand;
 use unilang::data::{ OutputData, ErrorData };
 use unilang::interpreter::ExecutionContext;
+use std::collections::BTreeMap;
 use genfile_core::TemplateArchive;
 
 
@@ -60,7 +61,8 @@ pub fn new_handler(
 
 /// Handler for .archive.load command
 ///
-/// Loads an archive from a JSON or YAML file.
+/// Loads an archive from a JSON or YAML file, composing it over the parent
+/// archives it `extends`.
 ///
 /// # Errors
 /// Returns usage error if required parameters are missing.
@@ -75,9 +77,9 @@ pub fn load_handler(
     .ok_or_else( || crate::error::usage_error( "Missing required parameter: path" ) )?;
   let verbosity = cmd.get_integer( "verbosity" ).unwrap_or( 1 );
 
-  // Load archive from file
+  // Load archive from file, with inherited files and parameters of its parents
   let path_buf = path;
-  let archive = TemplateArchive::load_from_file( path_buf )
+  let archive = TemplateArchive::load_resolved( path_buf )
     .map_err( | e | crate::error::format_error( &e, "ARCHIVE" ) )?;
 
   let archive_name = archive.name.clone();
@@ -158,9 +160,10 @@ pub fn save_handler(
   }
 
   // Save archive to file
-  // Workaround(issue-002): Format parameter is accepted but ignored; always saves as JSON.
-  // Root cause: TemplateArchive::save_to_file has no format parameter in the genfile_core API.
-  // Pitfall: The `format` and `pretty` args are silently unused; YAML save requests produce JSON.
+  // Workaround(issue-002): Format parameter is accepted but ignored; the path extension decides.
+  // Root cause: `format` defaults to "json", so an explicit choice is indistinguishable from none,
+  // and TemplateArchive::save_to_file picks JSON, YAML or a tar container by extension.
+  // Pitfall: The `format` and `pretty` args are silently unused; `format::yaml` with a .json path produces JSON.
   archive.save_to_file( path_buf )
     .map_err( | e | crate::error::format_error( &e, "ARCHIVE" ) )?;
 
@@ -272,3 +275,82 @@ pub fn from_directory_handler(
     execution_time_ms : None,
   } )
 }
+
+/// Handler for .`archive.templatize` command
+///
+/// Creates an archive from a filesystem directory and replaces the given
+/// values in file contents and paths with `{{name}}` placeholders.
+///
+/// # Errors
+/// Returns usage error if required parameters are missing or a value is invalid.
+/// Returns file error if the source directory does not exist or is not a directory.
+/// Returns format error if archive creation fails.
+pub fn templatize_handler(
+  cmd : VerifiedCommand,
+  _ctx : ExecutionContext
+) -> Result< OutputData, ErrorData >
+{
+  // Extract arguments
+  let source_path = cmd.get_path( "source" )
+    .ok_or_else( || crate::error::usage_error( "Missing required parameter: source" ) )?;
+  let values : BTreeMap< String, String > = match cmd.get_value( "values" )
+  {
+    Some( unilang::types::Value::Map( map ) ) => map
+      .iter()
+      .map( | ( name, value ) | ( name.clone(), value.to_string() ) )
+      .collect(),
+    _ => return Err( crate::error::usage_error( "Missing required parameter: values" ) ),
+  };
+  let verbosity = cmd.get_integer( "verbosity" ).unwrap_or( 1 );
+
+  if !source_path.is_dir()
+  {
+    return Err( crate::error::file_error( format!( "Source directory not found: {}", source_path.display() ) ) );
+  }
+
+  let archive_name = source_path
+    .file_name()
+    .and_then( | n | n.to_str() )
+    .unwrap_or( "archive" );
+
+  let mut archive = TemplateArchive::pack_from_dir( archive_name, source_path )
+    .map_err( | e | crate::error::format_error( &e, "ARCHIVE" ) )?;
+  let report = archive.templatize( &values )
+    .map_err( | e | crate::error::usage_error( e.to_string() ) )?;
+
+  let file_count = archive.file_count();
+
+  // Store in thread-local state
+  set_current_archive( archive );
+
+  // Format output based on verbosity
+  let output = match verbosity
+  {
+    0 => String::new(),
+    1 => format!(
+      "Templatized archive from directory: {} ({} files, {} replacements)",
+      source_path.display(), file_count, report.total_replacements()
+    ),
+    _ =>
+    {
+      let mut lines = vec!
+      [
+        "Templatized archive from directory".to_string(),
+        format!( "Source: {}", source_path.display() ),
+        format!( "Files: {file_count}" ),
+        format!( "Files changed: {}", report.files_changed.len() ),
+      ];
+      lines.extend( report.replacements.iter().map( | ( name, count ) | format!( "  {{{{{name}}}}}: {count} replacements" ) ) );
+      lines.extend( report.files_renamed.iter().map( | ( old, new ) | format!( "  Renamed: {} -> {}", old.display(), new.display() ) ) );
+      lines.push( format!( "Parameters added: {}", report.parameters_added.join( ", " ) ) );
+      lines.join( "\n" )
+    }
+  };
+
+  Ok( OutputData
+  {
+    content : output,
+    format : "text".to_string(),
+    execution_time_ms : None,
+  } )
+}
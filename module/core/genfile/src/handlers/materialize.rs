@@ -15,7 +15,10 @@
 //!
 //! **Dry Run Safety:**
 //! Dry run mode previews what would be done without creating files, preventing
-//! accidental overwrites. Critical for user confidence.
+//! accidental overwrites. Critical for user confidence. The preview comes from
+//! `TemplateArchive::plan`, so it lists the same per-file actions (create,
+//! update, merge, skip) a real run performs, and `diff::1` adds a unified
+//! diff against the current destination contents.
 
 // Handler functions are registered via unilang::CommandRegistry::register_with_routine,
 // which requires fn(VerifiedCommand, ExecutionContext) -> ... by value.
@@ -34,12 +37,13 @@ use core::fmt::Write as _;
 /// - `destination` - Output directory path
 /// - `verbosity` - Output verbosity (0-5, default: 1)
 /// - `dry` - Dry run mode (default: 0)
+/// - `diff` - With `dry`, append a unified diff against the destination (default: 0)
 ///
 /// # Errors
 /// Returns usage error if required parameters are missing.
 /// Returns state error if no archive is loaded.
 /// Returns validation error if mandatory parameter values are missing.
-/// Returns format error if materialization fails.
+/// Returns format error if planning or materialization fails.
 #[ allow( clippy::too_many_lines ) ]
 pub fn materialize_handler(
   cmd : VerifiedCommand,
@@ -84,56 +88,14 @@ pub fn materialize_handler(
   // Dry run preview
   if dry
   {
-    let file_count = archive.files.len();
-    let param_count = archive.values.as_ref().map_or( 0, genfile_core::Values::len );
-
-    let output_content = match verbosity
-    {
-      0 => String::new(),
-      1 => format!( "Dry run: Would materialize {} files to {}", file_count, destination.display() ),
-      _ =>
-      {
-        let files_preview = archive
-          .files
-          .iter()
-          .take( 5 )
-          .map( | f | format!( "  - {}", f.path.display() ) )
-          .collect::< Vec< _ > >()
-          .join( "\n" );
-
-        let more = if file_count > 5 { format!( "\n  ... and {} more files", file_count - 5 ) } else { String::new() };
-
-        format!(
-          "Dry run: Would materialize templates\n\
-          Destination: {}\n\
-          Archive: {}\n\
-          Files: {}\n\
-          Parameters: {}\n\
-          Files to create:\n\
-          {}{}",
-          destination.display(),
-          archive.name,
-          file_count,
-          param_count,
-          files_preview,
-          more
-        )
-      }
-    };
-
-    return Ok( OutputData
-    {
-      content : output_content,
-      format : "text".to_string(),
-      execution_time_ms : None,
-    } );
+    return dry_run( &archive, destination, verbosity, cmd.get_boolean( "diff" ).unwrap_or( false ) );
   }
 
   // Materialize templates to destination
   let report = archive.materialize( destination )
     .map_err( | e | crate::error::format_error( &e, "MATERIALIZE" ) )?;
 
-  let total_files = report.files_created.len() + report.files_updated.len() + report.files_skipped.len();
+  let total_files = report.files_created.len() + report.files_updated.len() + report.files_merged.len() + report.files_skipped.len();
 
   // Format output based on verbosity
   let output_content = match verbosity
@@ -148,12 +110,16 @@ pub fn materialize_handler(
         Destination: {}\n\
         Created: {}\n\
         Updated: {}\n\
-        Skipped: {}",
+        Merged: {}\n\
+        Skipped: {}\n\
+        Excluded: {}",
         archive.name,
         destination.display(),
         report.files_created.len(),
         report.files_updated.len(),
-        report.files_skipped.len()
+        report.files_merged.len(),
+        report.files_skipped.len(),
+        report.files_excluded.len()
       );
 
       if verbosity >= 3 && !report.files_created.is_empty()
@@ -174,6 +140,15 @@ pub fn materialize_handler(
         }
       }
 
+      if verbosity >= 3 && !report.files_merged.is_empty()
+      {
+        details.push_str( "\nMerged files:\n" );
+        for file in &report.files_merged
+        {
+          let _ = writeln!( &mut details, "  - {}", file.display() );
+        }
+      }
+
       details
     }
   };
@@ -186,6 +161,73 @@ pub fn materialize_handler(
   } )
 }
 
+/// Preview materialization: per-file actions from `TemplateArchive::plan`, optionally with diffs
+fn dry_run(
+  archive : &genfile_core::TemplateArchive,
+  destination : &std::path::Path,
+  verbosity : i64,
+  diff : bool
+) -> Result< OutputData, ErrorData >
+{
+  let report = archive.plan( destination )
+    .map_err( | e | crate::error::format_error( &e, "MATERIALIZE" ) )?;
+
+  let mut output_content = match verbosity
+  {
+    0 => String::new(),
+    1 => format!(
+      "Dry run: Would materialize {} files to {} (create: {}, update: {}, merge: {}, skip: {})",
+      report.plan.len(),
+      destination.display(),
+      report.files_created.len(),
+      report.files_updated.len(),
+      report.files_merged.len(),
+      report.files_skipped.len()
+    ),
+    _ =>
+    {
+      let mut details = format!(
+        "Dry run: Would materialize templates\n\
+        Destination: {}\n\
+        Archive: {}\n\
+        Files: {}\n\
+        Parameters: {}\n\
+        Actions:",
+        destination.display(),
+        archive.name,
+        report.plan.len(),
+        archive.values.as_ref().map_or( 0, genfile_core::Values::len )
+      );
+      for file in &report.plan
+      {
+        let _ = write!( &mut details, "\n  {:<6} {}", file.action.to_string(), file.path.display() );
+      }
+      for file in &report.files_excluded
+      {
+        let _ = write!( &mut details, "\n  {:<6} {}", "omit", file.display() );
+      }
+      details
+    }
+  };
+
+  if diff
+  {
+    let unified = report.unified_diff();
+    if !output_content.is_empty()
+    {
+      output_content.push_str( "\n\n" );
+    }
+    output_content.push_str( if unified.is_empty() { "No changes" } else { unified.trim_end() } );
+  }
+
+  Ok( OutputData
+  {
+    content : output_content,
+    format : "text".to_string(),
+    execution_time_ms : None,
+  } )
+}
+
 /// Handler for .unpack command
 ///
 /// Unpacks raw template files to destination without rendering.
@@ -297,6 +339,13 @@ pub fn unpack_handler(
       }
     }
 
+    // Restore recorded Unix permissions (e.g. executable scripts)
+    if let Some( mode ) = file.permissions()
+    {
+      genfile_core::FileSystem::set_permissions( &mut genfile_core::RealFileSystem::new(), &file_path, mode )
+        .map_err( | e | crate::error::file_error( format!( "Failed to set permissions of {}: {}", file_path.display(), e ) ) )?;
+    }
+
     files_created.push( file_path );
   }
 
@@ -34,7 +34,7 @@
 use unilang::semantic::VerifiedCommand;
 use unilang::data::{ OutputData, ErrorData };
 use unilang::interpreter::ExecutionContext;
-use genfile_core::TemplateArchive;
+use genfile_core::{ ArchiveFormat, TemplateArchive };
 
 /// Handler for .pack command
 ///
@@ -44,6 +44,7 @@ use genfile_core::TemplateArchive;
 /// # Parameters
 /// - `input` - Source directory or archive path
 /// - `output` - Output file path
+/// - `format` - Archive format (json, yaml, tar, tar.gz; default: from output extension)
 /// - `verbosity` - Output verbosity (0-5, default: 1)
 /// - `dry` - Dry run mode (default: 0)
 ///
@@ -64,6 +65,12 @@ pub fn pack_handler(
     .ok_or_else( || crate::error::usage_error( "Missing required parameter: output" ) )?;
   let verbosity = cmd.get_integer( "verbosity" ).unwrap_or( 1 );
   let dry = cmd.get_boolean( "dry" ).unwrap_or( false );
+  let format = match cmd.get_string( "format" )
+  {
+    Some( name ) => ArchiveFormat::from_name( name )
+      .ok_or_else( || crate::error::usage_error( format!( "Unsupported format: {name} (expected json, yaml, tar or tar.gz)" ) ) )?,
+    None => ArchiveFormat::from_path( output ),
+  };
 
   // Check if input exists and is a directory
   if !input.exists()
@@ -103,11 +110,13 @@ pub fn pack_handler(
           Output: {}\n\
           Archive: {}\n\
           Files: {}\n\
+          Format: {}\n\
           Mode: inline (portable)",
           input.display(),
           output.display(),
           archive_name,
-          file_count
+          file_count,
+          format
         )
       }
     };
@@ -121,7 +130,7 @@ pub fn pack_handler(
   }
 
   // Save archive to output file
-  archive.save_to_file( output )
+  archive.save_as( output, format )
     .map_err( | e | crate::error::format_error( &e, "PACK" ) )?;
 
   // Format output based on verbosity
@@ -136,11 +145,13 @@ pub fn pack_handler(
         Input: {}\n\
         Output: {}\n\
         Files: {}\n\
+        Format: {}\n\
         Mode: inline (portable)",
         archive_name,
         input.display(),
         output.display(),
-        file_count
+        file_count,
+        format
       )
     }
   };
@@ -39,6 +39,7 @@ pub fn add_handler(
     is_mandatory : mandatory,
     default_value : default_value.clone(),
     description : description.clone(),
+    kind: None,
   };
 
   // Add parameter to archive
@@ -431,10 +431,9 @@ fn pack_nonexistent_input_returns_error()
 
 // FT-03 (feature/001): Load reads archive saved with .yaml extension
 //
-// WHY: load_from_file always parses as JSON. Since JSON is a valid subset of
-// YAML, saving to a .yaml path produces a JSON file with that extension.
-// Loading it back should succeed because the parser reads JSON bytes regardless
-// of extension. Proves that save→load round-trip works with .yaml paths.
+// WHY: save_to_file and load_from_file pick the format from the extension, so
+// saving to a .yaml path produces YAML and loading it back parses YAML.
+// Proves that save→load round-trip works with .yaml paths.
 #[ test ]
 fn test_load_reads_yaml_archive_by_extension()
 {
@@ -536,3 +535,141 @@ fn test_pack_output_loads_in_new_session()
   let _ = fs::remove_dir_all( &source_dir );
   let _ = fs::remove_file( &archive_path );
 }
+
+// FR7: Pack to a compressed tar container, then load and materialize it
+//
+// WHY: JSON archives base64-encode binary files and grow with every template.
+// `format::tar.gz` writes a gzip-compressed tar with a manifest and raw entries;
+// loading detects the container from its content.
+#[ test ]
+fn pack_tar_gz_container_loads_and_materializes()
+{
+  let temp_dir = std::env::temp_dir();
+  let source_dir = temp_dir.join( "test_pack_tar_source" );
+  let archive_path = temp_dir.join( "test_pack_tar.tar.gz" );
+  let destination = temp_dir.join( "test_pack_tar_output" );
+
+  let cleanup = ||
+  {
+    let _ = fs::remove_dir_all( &source_dir );
+    let _ = fs::remove_file( &archive_path );
+    let _ = fs::remove_dir_all( &destination );
+  };
+  cleanup();
+
+  fs::create_dir_all( &source_dir ).expect( "Should create source dir" );
+  fs::write( source_dir.join( "readme.md" ), "# {{name}}" ).expect( "Should write readme" );
+  fs::write( source_dir.join( "icon.bin" ), [ 0x00, 0xFF, 0x10 ] ).expect( "Should write binary" );
+
+  let script = format!(
+    ".pack input::{} output::{} format::tar.gz verbosity::2\n\
+     .archive.load path::{}\n\
+     .value.set name::name value::demo\n\
+     .materialize destination::{}\n\
+     exit",
+    source_dir.display(),
+    archive_path.display(),
+    archive_path.display(),
+    destination.display()
+  );
+
+  let output = cli_runner::repl_command( &script )
+    .output()
+    .expect( "Workflow should execute" );
+
+  let stdout = String::from_utf8_lossy( &output.stdout );
+  assert!( output.status.success(), "Workflow should succeed. stdout: {stdout}" );
+  assert!( stdout.contains( "Format: tar.gz" ), "Verbose pack output should name the format. stdout: {stdout}" );
+
+  let packed = fs::read( &archive_path ).expect( "Should read container" );
+  assert_eq!( &packed[ .. 2 ], &[ 0x1F, 0x8B ], "Container should be gzip-compressed" );
+
+  assert_eq!( fs::read_to_string( destination.join( "readme.md" ) ).expect( "Should read readme" ), "# demo" );
+  assert_eq!( fs::read( destination.join( "icon.bin" ) ).expect( "Should read binary" ), vec![ 0x00, 0xFF, 0x10 ] );
+
+  cleanup();
+}
+
+#[ test ]
+fn load_composes_variant_over_parent_archive()
+{
+  let temp_dir = std::env::temp_dir().join( "test_load_variant" );
+  let destination = temp_dir.join( "output" );
+  let _ = fs::remove_dir_all( &temp_dir );
+  fs::create_dir_all( temp_dir.join( "base" ) ).expect( "Should create template dir" );
+
+  fs::write( temp_dir.join( "base/crate.json" ), r#"{
+  "name": "rust-crate",
+  "files": [
+    { "path": "Cargo.toml", "content": { "type": "Text", "data": "name = \"{{name}}\"" }, "write_mode": "Rewrite" },
+    { "path": "src/lib.rs", "content": { "type": "Text", "data": "//! {{name}}" }, "write_mode": "Rewrite" }
+  ]
+}"# ).expect( "Should write parent" );
+  fs::write( temp_dir.join( "cli.json" ), r#"{
+  "name": "cli-crate",
+  "extends": [ "base/crate.json" ],
+  "remove_files": [ "src/lib.rs" ],
+  "files": [
+    { "path": "src/main.rs", "content": { "type": "Text", "data": "// {{name}}" }, "write_mode": "Rewrite" }
+  ]
+}"# ).expect( "Should write variant" );
+
+  let script = format!(
+    ".archive.load path::{} verbosity::2\n\
+     .value.set name::name value::demo\n\
+     .materialize destination::{}\n\
+     exit",
+    temp_dir.join( "cli.json" ).display(),
+    destination.display()
+  );
+
+  let output = cli_runner::repl_command( &script )
+    .output()
+    .expect( "Workflow should execute" );
+
+  let stdout = String::from_utf8_lossy( &output.stdout );
+  assert!( output.status.success(), "Workflow should succeed. stdout: {stdout}" );
+  assert!( stdout.contains( "Files: 2" ), "Composed archive should hold inherited and own files. stdout: {stdout}" );
+  assert_eq!( fs::read_to_string( destination.join( "Cargo.toml" ) ).expect( "Should read inherited file" ), "name = \"demo\"" );
+  assert_eq!( fs::read_to_string( destination.join( "src/main.rs" ) ).expect( "Should read own file" ), "// demo" );
+  assert!( !destination.join( "src/lib.rs" ).exists(), "Removed inherited file should not be materialized" );
+
+  let _ = fs::remove_dir_all( &temp_dir );
+}
+
+#[ test ]
+fn templatize_replaces_values_with_placeholders()
+{
+  let temp_dir = std::env::temp_dir().join( "test_templatize" );
+  let source_dir = temp_dir.join( "my_crate" );
+  let destination = temp_dir.join( "output" );
+  let _ = fs::remove_dir_all( &temp_dir );
+  fs::create_dir_all( source_dir.join( "src" ) ).expect( "Should create source dir" );
+  fs::write( source_dir.join( "Cargo.toml" ), "name = \"my_crate\"\nauthors = [ \"Alice\" ]\n" ).expect( "Should write manifest" );
+  fs::write( source_dir.join( "src/my_crate.rs" ), "//! my_crate\n" ).expect( "Should write source" );
+
+  let script = format!(
+    ".archive.templatize source::{} values::\"name=my_crate,author=Alice\" verbosity::2\n\
+     .value.set name::name value::other\n\
+     .value.set name::author value::Bob\n\
+     .materialize destination::{}\n\
+     exit",
+    source_dir.display(),
+    destination.display()
+  );
+
+  let output = cli_runner::repl_command( &script )
+    .output()
+    .expect( "Workflow should execute" );
+
+  let stdout = String::from_utf8_lossy( &output.stdout );
+  assert!( output.status.success(), "Workflow should succeed. stdout: {stdout}" );
+  assert!( stdout.contains( "Renamed: src/my_crate.rs -> src/{{name}}.rs" ), "Verbose output should list renames. stdout: {stdout}" );
+  assert_eq!(
+    fs::read_to_string( destination.join( "Cargo.toml" ) ).expect( "Should read manifest" ),
+    "name = \"other\"\nauthors = [ \"Bob\" ]\n"
+  );
+  assert_eq!( fs::read_to_string( destination.join( "src/other.rs" ) ).expect( "Should read renamed file" ), "//! other\n" );
+
+  let _ = fs::remove_dir_all( &temp_dir );
+}
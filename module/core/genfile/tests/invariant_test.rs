@@ -104,6 +104,7 @@ fn test_all_implemented_commands_have_coverage()
     ".archive.load",
     ".archive.save",
     ".archive.from_directory",
+    ".archive.templatize",
     ".file.add",
     ".file.remove",
     ".file.list",
@@ -240,6 +240,63 @@ fn materialize_dry_run_preview()
   let _ = fs::remove_file( &archive_path );
 }
 
+#[ test ]
+fn materialize_dry_run_diff_preview()
+{
+  // Test: dry::1 diff::1 lists per-file actions and a unified diff against disk
+  //
+  // WHY: Before overwriting an existing project users need to see exactly
+  // which files change and how.
+  //
+  // CRITICAL: Existing destination files must be left untouched
+
+  let temp_dir = std::env::temp_dir();
+  let source_dir = temp_dir.join( "test_materialize_diff_source" );
+  let archive_path = temp_dir.join( "test_materialize_diff.json" );
+  let destination = temp_dir.join( "test_materialize_diff_output" );
+
+  // Clean up
+  let _ = fs::remove_dir_all( &source_dir );
+  let _ = fs::remove_file( &archive_path );
+  let _ = fs::remove_dir_all( &destination );
+
+  fs::create_dir_all( &source_dir ).expect( "Should create source dir" );
+  fs::write( source_dir.join( "test.txt" ), "Hello {{name}}\n" ).expect( "Should write template" );
+  fs::create_dir_all( &destination ).expect( "Should create destination" );
+  fs::write( destination.join( "test.txt" ), "Hello Old\n" ).expect( "Should write existing file" );
+
+  let script = format!(
+    ".pack input::{} output::{}\n\
+     .archive.load path::{}\n\
+     .value.set name::name value::\"World\"\n\
+     .materialize destination::{} dry::1 diff::1 verbosity::2\n\
+     exit",
+    source_dir.display(),
+    archive_path.display(),
+    archive_path.display(),
+    destination.display()
+  );
+
+  let output = cli_runner::repl_command( &script )
+    .output()
+    .expect( "Dry run should execute" );
+
+  let stdout = String::from_utf8_lossy( &output.stdout );
+
+  assert!( output.status.success(), "Dry run should succeed" );
+  assert!( stdout.contains( "update test.txt" ), "Should list the action: {stdout}" );
+  assert!( stdout.contains( "--- a/test.txt" ) && stdout.contains( "+++ b/test.txt" ), "Should print diff headers: {stdout}" );
+  assert!( stdout.contains( "-Hello Old" ) && stdout.contains( "+Hello World" ), "Should print changed lines: {stdout}" );
+
+  // CRITICAL: Existing file untouched
+  assert_eq!( fs::read_to_string( destination.join( "test.txt" ) ).unwrap(), "Hello Old\n" );
+
+  // Clean up
+  let _ = fs::remove_dir_all( &source_dir );
+  let _ = fs::remove_file( &archive_path );
+  let _ = fs::remove_dir_all( &destination );
+}
+
 #[ test ]
 fn materialize_without_archive_returns_error()
 {
@@ -354,6 +411,68 @@ fn unpack_preserves_template_variables()
   let _ = fs::remove_dir_all( &destination );
 }
 
+#[ cfg( unix ) ]
+#[ test ]
+fn unpack_and_materialize_preserve_executable_bits()
+{
+  // Test: Executable scripts keep their mode through pack → unpack/materialize
+  //
+  // WHY: Templates often ship scripts (build.sh, git hooks). Losing the
+  // executable bit forces users to chmod every generated project.
+  //
+  // VALIDATES:
+  // - .pack records the 0o755 mode in file metadata
+  // - .unpack and .materialize both restore it
+
+  use std::os::unix::fs::PermissionsExt;
+
+  let temp_dir = std::env::temp_dir();
+  let source_dir = temp_dir.join( "test_mode_source" );
+  let archive_path = temp_dir.join( "test_mode_archive.json" );
+  let unpacked = temp_dir.join( "test_mode_unpacked" );
+  let materialized = temp_dir.join( "test_mode_materialized" );
+
+  let cleanup = ||
+  {
+    let _ = fs::remove_dir_all( &source_dir );
+    let _ = fs::remove_file( &archive_path );
+    let _ = fs::remove_dir_all( &unpacked );
+    let _ = fs::remove_dir_all( &materialized );
+  };
+  cleanup();
+
+  fs::create_dir_all( &source_dir ).expect( "Should create source dir" );
+  fs::write( source_dir.join( "run.sh" ), "#!/bin/sh\n" ).expect( "Should write script" );
+  fs::set_permissions( source_dir.join( "run.sh" ), fs::Permissions::from_mode( 0o755 ) ).expect( "Should chmod script" );
+
+  let script = format!(
+    ".pack input::{} output::{}\n\
+     .archive.load path::{}\n\
+     .unpack destination::{}\n\
+     .materialize destination::{}\n\
+     exit",
+    source_dir.display(),
+    archive_path.display(),
+    archive_path.display(),
+    unpacked.display(),
+    materialized.display()
+  );
+
+  let output = cli_runner::repl_command( &script )
+    .output()
+    .expect( "Workflow should execute" );
+
+  let stdout = String::from_utf8_lossy( &output.stdout );
+  let stderr = String::from_utf8_lossy( &output.stderr );
+  assert!( output.status.success(), "Workflow should succeed. stdout: {stdout}, stderr: {stderr}" );
+
+  let mode = | dir: &std::path::Path | fs::metadata( dir.join( "run.sh" ) ).expect( "Script should exist" ).permissions().mode() & 0o7777;
+  assert_eq!( mode( &unpacked ), 0o755, "Unpack should restore the executable bit" );
+  assert_eq!( mode( &materialized ), 0o755, "Materialize should restore the executable bit" );
+
+  cleanup();
+}
+
 #[ test ]
 fn unpack_dry_run_preview()
 {
@@ -50,37 +50,7 @@ fn main() -> Result< (), Box< dyn core::error::Error > >
   println!();
 
   // Add parameter definitions with metadata
-  archive.add_parameter( ParameterDescriptor
-  {
-    parameter: "project_name".into(),
-    is_mandatory: true,
-    default_value: None,
-    description: Some( "Name of the Rust project".into() ),
-  });
-
-  archive.add_parameter( ParameterDescriptor
-  {
-    parameter: "version".into(),
-    is_mandatory: true,
-    default_value: Some( "0.1.0".into() ),
-    description: Some( "Project version following semver".into() ),
-  });
-
-  archive.add_parameter( ParameterDescriptor
-  {
-    parameter: "description".into(),
-    is_mandatory: false,
-    default_value: Some( "A Rust project".into() ),
-    description: Some( "Project description for README".into() ),
-  });
-
-  archive.add_parameter( ParameterDescriptor
-  {
-    parameter: "greeting".into(),
-    is_mandatory: false,
-    default_value: Some( "Hello, world!".into() ),
-    description: Some( "Greeting message".into() ),
-  });
+  define_parameters( &mut archive );
 
   // Analyze parameter usage
   let usage = archive.analyze_parameter_usage();
@@ -151,3 +121,43 @@ fn main() -> Result< (), Box< dyn core::error::Error > >
 
   Ok( () )
 }
+
+/// Define the example's parameters with their metadata
+fn define_parameters( archive: &mut TemplateArchive )
+{
+  archive.add_parameter( ParameterDescriptor
+  {
+    parameter: "project_name".into(),
+    is_mandatory: true,
+    default_value: None,
+    description: Some( "Name of the Rust project".into() ),
+    kind: None,
+  });
+
+  archive.add_parameter( ParameterDescriptor
+  {
+    parameter: "version".into(),
+    is_mandatory: true,
+    default_value: Some( "0.1.0".into() ),
+    description: Some( "Project version following semver".into() ),
+    kind: None,
+  });
+
+  archive.add_parameter( ParameterDescriptor
+  {
+    parameter: "description".into(),
+    is_mandatory: false,
+    default_value: Some( "A Rust project".into() ),
+    description: Some( "Project description for README".into() ),
+    kind: None,
+  });
+
+  archive.add_parameter( ParameterDescriptor
+  {
+    parameter: "greeting".into(),
+    is_mandatory: false,
+    default_value: Some( "Hello, world!".into() ),
+    description: Some( "Greeting message".into() ),
+    kind: None,
+  });
+}
@@ -45,6 +45,7 @@ fn main() -> Result< (), Box< dyn core::error::Error > >
     is_mandatory: true,
     default_value: Some( "localhost".into() ),
     description: Some( "Server hostname".into() ),
+    kind: None,
   });
 
   archive.add_parameter( ParameterDescriptor
@@ -53,6 +54,7 @@ fn main() -> Result< (), Box< dyn core::error::Error > >
     is_mandatory: true,
     default_value: Some( "8080".into() ),
     description: Some( "Server port".into() ),
+    kind: None,
   });
 
   archive.add_parameter( ParameterDescriptor
@@ -61,6 +63,7 @@ fn main() -> Result< (), Box< dyn core::error::Error > >
     is_mandatory: false,
     default_value: Some( "false".into() ),
     description: Some( "Debug mode".into() ),
+    kind: None,
   });
 
   archive.add_parameter( ParameterDescriptor
@@ -69,6 +72,7 @@ fn main() -> Result< (), Box< dyn core::error::Error > >
     is_mandatory: false,
     default_value: Some( "info".into() ),
     description: Some( "Logging level".into() ),
+    kind: None,
   });
 
   archive.add_parameter( ParameterDescriptor
@@ -77,6 +81,7 @@ fn main() -> Result< (), Box< dyn core::error::Error > >
     is_mandatory: false,
     default_value: Some( "stdout".into() ),
     description: Some( "Log output destination".into() ),
+    kind: None,
   });
 
   archive.add_parameter( ParameterDescriptor
@@ -85,6 +90,7 @@ fn main() -> Result< (), Box< dyn core::error::Error > >
     is_mandatory: false,
     default_value: Some( "production, web".into() ),
     description: Some( "Comma-separated tags".into() ),
+    kind: None,
   });
 
   // Set values (demonstrates all 4 Value variants: String, Number, Bool, List)
@@ -0,0 +1,257 @@
+/// Template inheritance for [`TemplateArchive`]: parent archives, overrides and removals.
+///
+/// A variant archive lists its parents in `extends` and carries only what
+/// differs from them. Composition overlays the variant's files, parameters and
+/// values onto the parents' and drops what `remove_files` and
+/// `remove_parameters` name, so edits to a parent reach every variant.
+use std ::path :: { Component, Path, PathBuf };
+
+use crate ::
+{
+  Error,
+  FileSystem,
+  RealFileSystem,
+};
+
+use super ::
+{
+  ArchiveFormat,
+  TemplateArchive,
+};
+
+impl TemplateArchive
+{
+  /// Declare a parent archive, relative to the directory of this archive's file.
+  ///
+  /// # Examples
+  ///
+  /// ```rust
+  /// use genfile_core::TemplateArchive;
+  /// use std::path::PathBuf;
+  ///
+  /// let mut variant = TemplateArchive::new( "cli-crate" );
+  /// variant.add_parent( "base.json" );
+  /// assert_eq!( variant.extends, vec![ PathBuf::from( "base.json" ) ] );
+  /// ```
+  pub fn add_parent( &mut self, path: impl Into< PathBuf > ) -> &mut Self
+  {
+    self.extends.push( path.into() );
+    self
+  }
+
+  /// Leave an inherited file out of the composed archive.
+  ///
+  /// A directory path removes every inherited file below it.
+  pub fn remove_inherited_file( &mut self, path: impl Into< PathBuf > ) -> &mut Self
+  {
+    self.remove_files.push( path.into() );
+    self
+  }
+
+  /// Leave an inherited parameter, and its inherited value, out of the composed archive.
+  pub fn remove_inherited_parameter( &mut self, name: impl Into< String > ) -> &mut Self
+  {
+    self.remove_parameters.push( name.into() );
+    self
+  }
+
+  /// Compose this archive over `parent`.
+  ///
+  /// Inherited files and parameters named by `remove_files` and
+  /// `remove_parameters` are dropped first. A file or parameter of this
+  /// archive then replaces the inherited one with the same path or name in
+  /// place; new ones are appended. Values set here override inherited values.
+  /// Name and version come from this archive, description and metadata from
+  /// the first of the two that has them. `extends` of the result is empty.
+  ///
+  /// # Examples
+  ///
+  /// ```rust
+  /// use genfile_core::{ TemplateArchive, WriteMode };
+  /// use std::path::{ Path, PathBuf };
+  ///
+  /// let mut base = TemplateArchive::new( "rust-crate" );
+  /// base.add_text_file( PathBuf::from( "Cargo.toml" ), "[package]\nname = \"{{name}}\"", WriteMode::Rewrite );
+  /// base.add_text_file( PathBuf::from( "src/lib.rs" ), "//! {{name}}", WriteMode::Rewrite );
+  ///
+  /// let mut variant = TemplateArchive::new( "cli-crate" );
+  /// variant.add_text_file( PathBuf::from( "src/main.rs" ), "fn main() {}", WriteMode::Rewrite );
+  /// variant.remove_inherited_file( "src/lib.rs" );
+  ///
+  /// let composed = variant.compose( &base );
+  /// assert_eq!( composed.list_files(), vec![ Path::new( "Cargo.toml" ), Path::new( "src/main.rs" ) ] );
+  /// ```
+  #[must_use]
+  pub fn compose( &self, parent: &TemplateArchive ) -> TemplateArchive
+  {
+    let mut files: Vec< _ > = parent.files
+      .iter()
+      .filter( | file | !self.remove_files.iter().any( | removed | file.path.starts_with( removed ) ) )
+      .cloned()
+      .collect();
+    for file in &self.files
+    {
+      match files.iter_mut().find( | inherited | inherited.path == file.path )
+      {
+        Some( inherited ) => *inherited = file.clone(),
+        None => files.push( file.clone() ),
+      }
+    }
+
+    let mut parameters = parent.parameters.clone();
+    parameters.descriptors.retain( | descriptor | !self.remove_parameters.contains( &descriptor.parameter ) );
+    for descriptor in &self.parameters.descriptors
+    {
+      match parameters.descriptors.iter_mut().find( | inherited | inherited.parameter == descriptor.parameter )
+      {
+        Some( inherited ) => *inherited = descriptor.clone(),
+        None => parameters.descriptors.push( descriptor.clone() ),
+      }
+    }
+
+    let mut values = parent.values.clone();
+    if let Some( inherited ) = values.as_mut()
+    {
+      for name in &self.remove_parameters
+      {
+        inherited.remove( name );
+      }
+    }
+    if let Some( own ) = self.values.clone()
+    {
+      values.get_or_insert_with( Default::default ).merge( own );
+    }
+
+    TemplateArchive
+    {
+      name: self.name.clone(),
+      version: self.version.clone(),
+      description: self.description.clone().or_else( || parent.description.clone() ),
+      files,
+      parameters,
+      values,
+      metadata: self.metadata.clone().or_else( || parent.metadata.clone() ),
+      extends: Vec::new(),
+      remove_files: Vec::new(),
+      remove_parameters: Vec::new(),
+    }
+  }
+
+  /// Load every archive in `extends` from disk and compose this archive over them.
+  ///
+  /// Parent paths are relative to `base_dir`, the directory of this archive's
+  /// file; see [`TemplateArchive::resolve_parents_with`].
+  ///
+  /// # Errors
+  ///
+  /// Returns error if a parent cannot be read or parsed, or the parents form a cycle.
+  pub fn resolve_parents( &self, base_dir: &Path ) -> Result< Self, Error >
+  {
+    self.resolve_parents_with( base_dir, &RealFileSystem::new() )
+  }
+
+  /// Load every archive in `extends` from `filesystem` and compose this archive over them.
+  ///
+  /// Parents may extend further archives, relative to their own directory.
+  /// Parents are composed in order, later over earlier, and this archive over
+  /// the result. Removals apply only to the declaring archive's own ancestors,
+  /// so a later parent never removes what an earlier parent contributes. Formats are detected as by [`TemplateArchive::load_from_file`].
+  /// Without parents the archive is returned unchanged.
+  ///
+  /// # Errors
+  ///
+  /// Returns error if a parent cannot be read or parsed, or `Error::InvalidTemplate`
+  /// if an archive extends itself directly or indirectly.
+  ///
+  /// # Examples
+  ///
+  /// ```rust
+  /// use genfile_core::{ TemplateArchive, MemoryFileSystem, FileSystem, WriteMode };
+  /// use std::path::{ Path, PathBuf };
+  ///
+  /// let mut base = TemplateArchive::new( "rust-crate" );
+  /// base.add_text_file( PathBuf::from( "readme.md" ), "# {{name}}", WriteMode::Rewrite );
+  ///
+  /// let mut fs = MemoryFileSystem::new();
+  /// fs.write( Path::new( "/templates/base.json" ), &base.to_json().unwrap() ).unwrap();
+  ///
+  /// let mut variant = TemplateArchive::new( "cli-crate" );
+  /// variant.add_parent( "base.json" );
+  ///
+  /// let composed = variant.resolve_parents_with( Path::new( "/templates" ), &fs ).unwrap();
+  /// assert!( composed.has_file( Path::new( "readme.md" ) ) );
+  /// ```
+  pub fn resolve_parents_with< FS: FileSystem >( &self, base_dir: &Path, filesystem: &FS ) -> Result< Self, Error >
+  {
+    self.resolve_in( base_dir, filesystem, &mut Vec::new() )
+  }
+
+  /// Compose over the parents, tracking the chain of parent files being resolved.
+  fn resolve_in< FS: FileSystem >( &self, base_dir: &Path, filesystem: &FS, chain: &mut Vec< PathBuf > ) -> Result< Self, Error >
+  {
+    if self.extends.is_empty()
+    {
+      return Ok( self.clone() );
+    }
+
+    let mut inherited: Option< TemplateArchive > = None;
+    for parent in &self.extends
+    {
+      let path = parent_path( base_dir, parent );
+      if chain.contains( &path )
+      {
+        let cycle: Vec< _ > = chain.iter().chain( [ &path ] ).map( | p | p.display().to_string() ).collect();
+        return Err( Error::InvalidTemplate( format!( "archive inheritance cycle: {}", cycle.join( " -> " ) ) ) );
+      }
+
+      let bytes = filesystem.read_bytes( &path )?;
+      let archive = Self::from_bytes( &bytes, ArchiveFormat::detect( &path, &bytes ) )?;
+      chain.push( path.clone() );
+      let mut resolved = archive.resolve_in( path.parent().unwrap_or( Path::new( "" ) ), filesystem, chain )?;
+      chain.pop();
+      // removals reach only the declaring archive's own ancestors, never earlier parents
+      resolved.remove_files.clear();
+      resolved.remove_parameters.clear();
+
+      inherited = Some( match inherited
+      {
+        Some( earlier ) => resolved.compose( &earlier ),
+        None => resolved,
+      });
+    }
+
+    Ok( match inherited
+    {
+      Some( parent ) => self.compose( &parent ),
+      None => self.clone(),
+    })
+  }
+
+  /// Load an archive file and compose it over its parents.
+  ///
+  /// # Errors
+  ///
+  /// Returns error if the archive or one of its parents cannot be loaded, or the parents form a cycle.
+  #[cfg(feature = "json")]
+  pub fn load_resolved( path: &Path ) -> Result< Self, Error >
+  {
+    Self::load_from_file( path )?.resolve_parents( path.parent().unwrap_or( Path::new( "" ) ) )
+  }
+}
+
+/// Join `relative` onto `base_dir`, folding `.` and `..` lexically so every
+/// parent file has one spelling, on disk and in memory.
+fn parent_path( base_dir: &Path, relative: &Path ) -> PathBuf
+{
+  let mut path = PathBuf::new();
+  for component in base_dir.join( relative ).components()
+  {
+    match component
+    {
+      Component::CurDir => {}
+      Component::ParentDir if matches!( path.components().next_back(), Some( Component::Normal( _ ) ) ) => { path.pop(); }
+      other => path.push( other ),
+    }
+  }
+  path
+}
@@ -0,0 +1,235 @@
+/// Per-file conditions, path templating and fan-out for [`TemplateArchive`].
+///
+/// Expands every `TemplateFile` into the outputs it produces for the current
+/// values before materialization writes them.
+use std ::collections :: { BTreeMap, HashSet };
+use std ::path :: { Component, Path, PathBuf };
+
+use serde_json ::Value as JsonValue;
+
+use crate ::
+{
+  Error,
+  TemplateRenderer,
+  Value,
+  Values,
+  validate_path,
+};
+
+use super ::
+{
+  FileMetadata,
+  MaterializationReport,
+  TemplateArchive,
+  TemplateFile,
+};
+
+/// Variable bound to the current list element while fanning out a file
+pub const LOOP_ITEM: &str = "item";
+
+/// Variable bound to the zero-based position of the current list element
+pub const LOOP_INDEX: &str = "item_index";
+
+/// One output of a `TemplateFile`: rendered relative path and the values to render it with
+pub( crate ) struct Output< 'a >
+{
+  pub( crate ) file: &'a TemplateFile,
+  pub( crate ) path: PathBuf,
+  pub( crate ) values: BTreeMap< String, JsonValue >,
+}
+
+impl TemplateFile
+{
+  /// Emit this file only when `condition` holds.
+  ///
+  /// `condition` names a parameter whose value must be truthy; a leading `!`
+  /// negates it. Truthy values are `true`, non-zero numbers, non-empty lists
+  /// and maps, and strings other than `""`, `"false"`, `"no"`, `"off"` and `"0"`; an
+  /// unset parameter is false. Excluded files are listed in
+  /// `MaterializationReport::files_excluded`.
+  ///
+  /// # Examples
+  ///
+  /// ```rust
+  /// use genfile_core::{ TemplateArchive, WriteMode };
+  /// use std::path::{ Path, PathBuf };
+  ///
+  /// let mut archive = TemplateArchive::new( "service" );
+  /// archive.add_text_file( PathBuf::from( "Dockerfile" ), "FROM rust", WriteMode::Rewrite );
+  /// archive.get_file_mut( Path::new( "Dockerfile" ) ).unwrap().set_condition( "docker" );
+  ///
+  /// assert_eq!( archive.get_file( Path::new( "Dockerfile" ) ).unwrap().condition(), Some( "docker" ) );
+  /// ```
+  pub fn set_condition( &mut self, condition: impl Into< String > ) -> &mut Self
+  {
+    self.metadata.get_or_insert_with( FileMetadata::default ).condition = Some( condition.into() );
+    self
+  }
+
+  /// Emit this file once per element of the list parameter `parameter`.
+  ///
+  /// While rendering each copy, the element is bound to `item` ([`LOOP_ITEM`])
+  /// and its position to `item_index` ([`LOOP_INDEX`]); the path should use
+  /// `{{item}}` so the copies do not collide.
+  pub fn set_for_each( &mut self, parameter: impl Into< String > ) -> &mut Self
+  {
+    self.metadata.get_or_insert_with( FileMetadata::default ).for_each = Some( parameter.into() );
+    self
+  }
+
+  /// Condition guarding this file, if any
+  #[must_use]
+  pub fn condition( &self ) -> Option< &str >
+  {
+    self.metadata.as_ref().and_then( | metadata | metadata.condition.as_deref() )
+  }
+
+  /// List parameter this file fans out over, if any
+  #[must_use]
+  pub fn for_each( &self ) -> Option< &str >
+  {
+    self.metadata.as_ref().and_then( | metadata | metadata.for_each.as_deref() )
+  }
+}
+
+impl TemplateArchive
+{
+  /// Expand files into the outputs materialization will write.
+  ///
+  /// Set values are first checked against their parameters' `ParameterKind`.
+  /// Then, for each file, in order:
+  ///
+  /// 1. **Condition** - a file whose condition is false is dropped and listed
+  ///    in `report.files_excluded`. A parameter is truthy when its value is
+  ///    `true`, a non-zero number, a non-empty list or map, or a string other than
+  ///    `""`, `"false"`, `"no"`, `"off"` and `"0"`; unset parameters are false.
+  /// 2. **Fan-out** - a file with `for_each` yields one output per list
+  ///    element with `item`/`item_index` bound; an unset or empty list yields
+  ///    none and the file is listed in `report.files_excluded`.
+  /// 3. **Path templating** - a path containing `{{` is rendered with the
+  ///    output's values. The result must be a non-empty relative path.
+  ///
+  /// Directories of the outputs are recorded in `report.directories_created`.
+  ///
+  /// # Errors
+  ///
+  /// Returns `Error::InvalidParameter` if a value violates its parameter's
+  /// kind; `Error::InvalidTemplate` if `for_each` names a non-list value, a
+  /// rendered path is empty, absolute or escapes via `..`, or two outputs
+  /// share a path; `Error::Render` if a path fails to render.
+  pub( crate ) fn expand_outputs< R >( &self, renderer: &R, report: &mut MaterializationReport ) -> Result< Vec< Output< '_ > >, Error >
+  where
+    R: TemplateRenderer,
+  {
+    let empty = Values::new();
+    let values = self.values.as_ref().unwrap_or( &empty );
+    self.parameters.validate( values )?;
+    let base = values.to_serializable();
+
+    let mut outputs = Vec::with_capacity( self.files.len() );
+    let mut seen = HashSet::new();
+    for file in &self.files
+    {
+      if let Some( condition ) = file.condition()
+      {
+        if !condition_holds( condition, values )
+        {
+          report.files_excluded.push( file.path.clone() );
+          continue;
+        }
+      }
+
+      let bindings = match file.for_each()
+      {
+        None => vec![ base.clone() ],
+        Some( parameter ) => match values.get( parameter )
+        {
+          None => Vec::new(),
+          Some( Value::List( items ) ) => items.iter().enumerate().map( | ( index, item ) |
+          {
+            let mut bound = base.clone();
+            bound.insert( LOOP_ITEM.to_string(), JsonValue::String( item.clone() ) );
+            bound.insert( LOOP_INDEX.to_string(), JsonValue::from( index ) );
+            bound
+          }).collect(),
+          Some( other ) => return Err( Error::InvalidTemplate( format!(
+            "{}: for_each parameter '{parameter}' must be a list, got {other:?}",
+            file.path.display()
+          ) ) ),
+        },
+      };
+      if bindings.is_empty()
+      {
+        report.files_excluded.push( file.path.clone() );
+        continue;
+      }
+
+      for bound in bindings
+      {
+        let path = render_path( &file.path, renderer, &bound )?;
+        if !seen.insert( path.clone() )
+        {
+          return Err( Error::InvalidTemplate( format!(
+            "{}: more than one output renders to {}", file.path.display(), path.display()
+          ) ) );
+        }
+        outputs.push( Output { file, path, values: bound } );
+      }
+    }
+
+    let mut dirs: Vec< PathBuf > = outputs.iter()
+      .flat_map( | output | output.path.ancestors().skip( 1 ).map( Path::to_path_buf ) )
+      .filter( | dir | !dir.as_os_str().is_empty() )
+      .collect::< HashSet< _ > >()
+      .into_iter()
+      .collect();
+    dirs.sort();
+    report.directories_created.extend( dirs );
+
+    Ok( outputs )
+  }
+}
+
+/// Whether `condition` (`name` or `!name`) holds for `values`.
+fn condition_holds( condition: &str, values: &Values< Value > ) -> bool
+{
+  let ( negate, name ) = match condition.trim().strip_prefix( '!' )
+  {
+    Some( name ) => ( true, name.trim() ),
+    None => ( false, condition.trim() ),
+  };
+  let truthy = match values.get( name )
+  {
+    None => false,
+    Some( Value::Bool( flag ) ) => *flag,
+    Some( Value::Number( number ) ) => *number != 0,
+    Some( Value::List( items ) ) => !items.is_empty(),
+    Some( Value::Map( fields ) ) => !fields.is_empty(),
+    Some( Value::String( text ) ) => !matches!( text.trim().to_ascii_lowercase().as_str(), "" | "false" | "no" | "off" | "0" ),
+  };
+  truthy != negate
+}
+
+/// Render `path` if it contains a template expression, then validate it.
+fn render_path< R >( path: &Path, renderer: &R, values: &BTreeMap< String, JsonValue > ) -> Result< PathBuf, Error >
+where
+  R: TemplateRenderer,
+{
+  let raw = path.to_string_lossy();
+  if !raw.contains( "{{" )
+  {
+    validate_path( path )?;
+    return Ok( path.to_path_buf() );
+  }
+
+  let output_path = PathBuf::from( renderer.render( &raw, values )? );
+  let escapes = output_path.components().any( | c | matches!( c, Component::RootDir | Component::Prefix( _ ) ) );
+  if output_path.as_os_str().is_empty() || escapes
+  {
+    return Err( Error::InvalidTemplate( format!(
+      "{}: rendered path '{}' is not a relative file path", path.display(), output_path.display()
+    ) ) );
+  }
+  validate_path( &output_path )?;
+  Ok( output_path )
+}
@@ -0,0 +1,408 @@
+/// Saving and loading [`TemplateArchive`] files in every supported format.
+///
+/// JSON and YAML store the archive as one document with binary content
+/// base64-encoded. The tar and tar.gz containers (feature `container`) store a
+/// `manifest.json` with the archive structure and a `sha256:` checksum per
+/// file, followed by one raw entry per file under `files/`.
+use std ::path ::Path;
+
+use crate ::Error;
+
+#[cfg(feature = "container")]
+use super ::FileContent;
+use super ::TemplateArchive;
+
+/// Name of the manifest entry in tar containers
+#[cfg(feature = "container")]
+const MANIFEST_ENTRY: &str = "manifest.json";
+
+/// Version of the container layout written by this crate
+#[cfg(feature = "container")]
+const CONTAINER_VERSION: u32 = 1;
+
+/// On-disk format of a saved archive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat
+{
+  /// Single JSON document (`.json` and unrecognized extensions)
+  Json,
+  /// Single YAML document (`.yaml`, `.yml`)
+  Yaml,
+  /// Uncompressed tar container (`.tar`)
+  Tar,
+  /// Gzip-compressed tar container (`.tar.gz`, `.tgz`)
+  TarGz,
+}
+
+impl ArchiveFormat
+{
+  /// Format implied by the extension of `path`; JSON when unrecognized.
+  ///
+  /// # Examples
+  ///
+  /// ```rust
+  /// use genfile_core::ArchiveFormat;
+  /// use std::path::Path;
+  ///
+  /// assert_eq!( ArchiveFormat::from_path( Path::new( "t.tar.gz" ) ), ArchiveFormat::TarGz );
+  /// assert_eq!( ArchiveFormat::from_path( Path::new( "t.yml" ) ), ArchiveFormat::Yaml );
+  /// assert_eq!( ArchiveFormat::from_path( Path::new( "t.txt" ) ), ArchiveFormat::Json );
+  /// ```
+  #[must_use]
+  pub fn from_path( path: &Path ) -> Self
+  {
+    let extension = | path: &Path | path.extension().map( | ext | ext.to_string_lossy().to_ascii_lowercase() );
+    match extension( path ).as_deref()
+    {
+      Some( "tgz" ) => Self::TarGz,
+      Some( "gz" ) if path.file_stem().is_some_and( | stem | extension( Path::new( stem ) ).as_deref() == Some( "tar" ) ) => Self::TarGz,
+      Some( "tar" ) => Self::Tar,
+      Some( "yaml" | "yml" ) => Self::Yaml,
+      _ => Self::Json,
+    }
+  }
+
+  /// Format of `bytes`: containers are recognized by their gzip or tar
+  /// magic, documents fall back to the extension of `path`.
+  #[must_use]
+  pub fn detect( path: &Path, bytes: &[ u8 ] ) -> Self
+  {
+    if bytes.starts_with( &[ 0x1F, 0x8B ] )
+    {
+      Self::TarGz
+    }
+    else if bytes.get( 257..262 ) == Some( b"ustar" )
+    {
+      Self::Tar
+    }
+    else
+    {
+      match Self::from_path( path )
+      {
+        Self::Tar | Self::TarGz => Self::Json,
+        format => format,
+      }
+    }
+  }
+
+  /// Parse a format name: `json`, `yaml`, `tar`, or `tar.gz` / `tgz`.
+  #[must_use]
+  pub fn from_name( name: &str ) -> Option< Self >
+  {
+    match name.to_ascii_lowercase().as_str()
+    {
+      "json" => Some( Self::Json ),
+      "yaml" | "yml" => Some( Self::Yaml ),
+      "tar" => Some( Self::Tar ),
+      "tar.gz" | "tgz" => Some( Self::TarGz ),
+      _ => None,
+    }
+  }
+}
+
+impl core::fmt::Display for ArchiveFormat
+{
+  fn fmt( &self, f: &mut core::fmt::Formatter< '_ > ) -> core::fmt::Result
+  {
+    f.write_str( match self
+    {
+      Self::Json => "json",
+      Self::Yaml => "yaml",
+      Self::Tar => "tar",
+      Self::TarGz => "tar.gz",
+    })
+  }
+}
+
+/// Error for a format whose cargo feature is disabled
+fn disabled( format: ArchiveFormat ) -> Error
+{
+  let feature = match format
+  {
+    ArchiveFormat::Json => "json",
+    ArchiveFormat::Yaml => "yaml",
+    ArchiveFormat::Tar | ArchiveFormat::TarGz => "container",
+  };
+  Error::InvalidTemplate( format!( "{format} archives require the `{feature}` feature" ) )
+}
+
+impl TemplateArchive
+{
+  /// Serialize to bytes in `format`.
+  ///
+  /// # Errors
+  ///
+  /// Returns error if serialization fails or the format's feature is disabled.
+  pub fn to_bytes( &self, format: ArchiveFormat ) -> Result< Vec< u8 >, Error >
+  {
+    match format
+    {
+      #[cfg(feature = "json")]
+      ArchiveFormat::Json => self.to_json_pretty().map( String::into_bytes ),
+      #[cfg(feature = "yaml")]
+      ArchiveFormat::Yaml => self.to_yaml().map( String::into_bytes ),
+      #[cfg(feature = "container")]
+      ArchiveFormat::Tar => self.to_container( false ),
+      #[cfg(feature = "container")]
+      ArchiveFormat::TarGz => self.to_container( true ),
+      #[allow(unreachable_patterns)]
+      other => Err( disabled( other ) ),
+    }
+  }
+
+  /// Deserialize from bytes in `format`.
+  ///
+  /// # Errors
+  ///
+  /// Returns error if the bytes are malformed, a container checksum does not
+  /// match, or the format's feature is disabled.
+  #[cfg_attr(not(any(feature = "json", feature = "yaml")), allow(unused_variables))]
+  pub fn from_bytes( bytes: &[ u8 ], format: ArchiveFormat ) -> Result< Self, Error >
+  {
+    match format
+    {
+      #[cfg(feature = "json")]
+      ArchiveFormat::Json => Self::from_json( &document( bytes )? ),
+      #[cfg(feature = "yaml")]
+      ArchiveFormat::Yaml => Self::from_yaml( &document( bytes )? ),
+      #[cfg(feature = "container")]
+      ArchiveFormat::Tar => Self::from_container( bytes, false ),
+      #[cfg(feature = "container")]
+      ArchiveFormat::TarGz => Self::from_container( bytes, true ),
+      #[allow(unreachable_patterns)]
+      other => Err( disabled( other ) ),
+    }
+  }
+
+  /// Save archive to file, in the format implied by its extension.
+  ///
+  /// `.yaml` / `.yml` save YAML, `.tar` and `.tar.gz` / `.tgz` save a
+  /// container, anything else pretty-printed JSON (see [`ArchiveFormat`]).
+  ///
+  /// # Parameters
+  ///
+  /// - `path`: File path where archive will be saved
+  ///
+  /// # Errors
+  ///
+  /// Returns error if serialization or file writing fails.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use genfile_core::TemplateArchive;
+  /// use std::path::Path;
+  ///
+  /// let archive = TemplateArchive::new( "test" );
+  /// archive.save_to_file( Path::new( "archive.json" ) ).unwrap();
+  /// ```
+  #[cfg(feature = "json")]
+  pub fn save_to_file( &self, path: &Path ) -> Result< (), Error >
+  {
+    self.save_as( path, ArchiveFormat::from_path( path ) )
+  }
+
+  /// Save archive to file in an explicit `format`, whatever its extension.
+  ///
+  /// # Errors
+  ///
+  /// Returns error if serialization or file writing fails.
+  pub fn save_as( &self, path: &Path, format: ArchiveFormat ) -> Result< (), Error >
+  {
+    std::fs::write( path, self.to_bytes( format )? )?;
+    Ok( () )
+  }
+
+  /// Load archive from file.
+  ///
+  /// Containers are recognized by content, documents by extension
+  /// (see [`ArchiveFormat::detect`]).
+  ///
+  /// # Parameters
+  ///
+  /// - `path`: File path to load archive from
+  ///
+  /// # Errors
+  ///
+  /// Returns error if file reading or deserialization fails, or a container
+  /// checksum does not match.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use genfile_core::TemplateArchive;
+  /// use std::path::Path;
+  ///
+  /// let archive = TemplateArchive::load_from_file( Path::new( "archive.json" ) ).unwrap();
+  /// ```
+  #[cfg(feature = "json")]
+  pub fn load_from_file( path: &Path ) -> Result< Self, Error >
+  {
+    let bytes = std::fs::read( path )?;
+    Self::from_bytes( &bytes, ArchiveFormat::detect( path, &bytes ) )
+  }
+}
+
+/// UTF-8 text of a JSON or YAML document
+#[cfg(any(feature = "json", feature = "yaml"))]
+fn document( bytes: &[ u8 ] ) -> Result< String, Error >
+{
+  String::from_utf8( bytes.to_vec() )
+    .map_err( | e | Error::Render( format!( "Archive document is not valid UTF-8: {e}" ) ) )
+}
+
+/// Contents of `manifest.json`
+#[cfg(feature = "container")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Manifest
+{
+  /// Container layout version
+  version: u32,
+  /// Archive with file contents left empty
+  archive: TemplateArchive,
+  /// One entry per archive file, in the same order
+  entries: Vec< ManifestEntry >,
+}
+
+/// Where and how one file's content is stored in a container
+#[cfg(feature = "container")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct ManifestEntry
+{
+  /// Tar entry name, `files/<path>`
+  name: String,
+  /// Whether the content is binary
+  binary: bool,
+  /// Checksum of the content (`sha256:<hex>`)
+  checksum: String,
+}
+
+#[cfg(feature = "container")]
+impl TemplateArchive
+{
+  /// Write the tar container, gzip-compressed when `gzip` is set.
+  ///
+  /// Entries have fixed timestamps and owners, so equal archives produce
+  /// identical bytes.
+  fn to_container( &self, gzip: bool ) -> Result< Vec< u8 >, Error >
+  {
+    let mut skeleton = self.clone();
+    let mut entries = Vec::with_capacity( self.files.len() );
+    let mut blobs = Vec::with_capacity( self.files.len() );
+    for file in &mut skeleton.files
+    {
+      let name = format!( "files/{}", file.path.to_string_lossy().replace( '\\', "/" ) );
+      let ( content, empty ) = match core::mem::replace( &mut file.content, FileContent::Text( String::new() ) )
+      {
+        FileContent::Text( text ) => ( text.into_bytes(), FileContent::Text( String::new() ) ),
+        FileContent::Binary( bytes ) => ( bytes, FileContent::Binary( Vec::new() ) ),
+      };
+      let binary = matches!( empty, FileContent::Binary( _ ) );
+      let checksum = format!( "sha256:{}", crate::content_source::sha256_hex( &content ) );
+      file.content = empty;
+      blobs.push( ( name.clone(), content, file.permissions().unwrap_or( crate::DEFAULT_FILE_MODE ) ) );
+      entries.push( ManifestEntry { name, binary, checksum } );
+    }
+
+    let manifest = serde_json::to_vec_pretty( &Manifest { version: CONTAINER_VERSION, archive: skeleton, entries } )
+      .map_err( | e | Error::Render( format!( "Manifest serialization failed: {e}" ) ) )?;
+
+    let mut builder = tar::Builder::new( Vec::new() );
+    append( &mut builder, MANIFEST_ENTRY, &manifest, crate::DEFAULT_FILE_MODE )?;
+    for ( name, content, mode ) in &blobs
+    {
+      append( &mut builder, name, content, *mode )?;
+    }
+    let tar = builder.into_inner()?;
+
+    if !gzip
+    {
+      return Ok( tar );
+    }
+    let mut encoder = flate2::write::GzEncoder::new( Vec::new(), flate2::Compression::default() );
+    std::io::Write::write_all( &mut encoder, &tar )?;
+    Ok( encoder.finish()? )
+  }
+
+  /// Read a tar container, verifying every file's checksum.
+  ///
+  /// The unpacked entries may total at most `DEFAULT_MAX_SIZE` bytes, and every
+  /// entry must be listed in the manifest exactly once.
+  fn from_container( bytes: &[ u8 ], gzip: bool ) -> Result< Self, Error >
+  {
+    let limit = crate::content_source::DEFAULT_MAX_SIZE;
+    let mut remaining = limit;
+    let mut blobs = std::collections::HashMap::new();
+    let reader: Box< dyn std::io::Read + '_ > = if gzip { Box::new( flate2::read::GzDecoder::new( bytes ) ) } else { Box::new( bytes ) };
+    let mut tar = tar::Archive::new( reader );
+    for entry in tar.entries()?
+    {
+      let mut entry = entry?;
+      let name = entry.path()?.to_string_lossy().into_owned();
+      let mut content = Vec::new();
+      // One byte past the budget tells an entry that fits exactly from one that does not.
+      std::io::Read::read_to_end( &mut std::io::Read::take( &mut entry, remaining + 1 ), &mut content )?;
+      remaining = remaining.checked_sub( content.len() as u64 )
+        .ok_or_else( || Error::ContentTooLarge { location: "archive container".into(), limit } )?;
+      if blobs.insert( name.clone(), content ).is_some()
+      {
+        return Err( Error::InvalidTemplate( format!( "archive container has duplicate entry {name}" ) ) );
+      }
+    }
+
+    let manifest = blobs.remove( MANIFEST_ENTRY )
+      .ok_or_else( || Error::InvalidTemplate( format!( "archive container has no {MANIFEST_ENTRY}" ) ) )?;
+    let manifest: Manifest = serde_json::from_slice( &manifest )
+      .map_err( | e | Error::Render( format!( "Manifest deserialization failed: {e}" ) ) )?;
+    if manifest.version > CONTAINER_VERSION
+    {
+      return Err( Error::InvalidTemplate( format!(
+        "archive container version {} is newer than supported version {CONTAINER_VERSION}", manifest.version
+      ) ) );
+    }
+    if manifest.entries.len() != manifest.archive.files.len()
+    {
+      return Err( Error::InvalidTemplate( format!(
+        "archive container lists {} entries for {} files", manifest.entries.len(), manifest.archive.files.len()
+      ) ) );
+    }
+
+    let mut archive = manifest.archive;
+    for ( file, entry ) in archive.files.iter_mut().zip( manifest.entries )
+    {
+      let bytes = blobs.remove( &entry.name )
+        .ok_or_else( || Error::InvalidTemplate( format!( "archive container has no entry {}", entry.name ) ) )?;
+      let content = if entry.binary
+      {
+        FileContent::Binary( bytes )
+      }
+      else
+      {
+        FileContent::Text( String::from_utf8( bytes )
+          .map_err( | _ | Error::InvalidTemplate( format!( "text entry {} is not valid UTF-8", entry.name ) ) )? )
+      };
+      crate::content_source::verify_checksum( &entry.name, &content, &entry.checksum )?;
+      file.content = content;
+    }
+    if let Some( name ) = blobs.keys().min()
+    {
+      return Err( Error::InvalidTemplate( format!( "archive container entry {name} is not listed in the manifest" ) ) );
+    }
+    Ok( archive )
+  }
+}
+
+/// Append one regular file entry with fixed owner and timestamp
+#[cfg(feature = "container")]
+fn append( builder: &mut tar::Builder< Vec< u8 > >, name: &str, content: &[ u8 ], mode: u32 ) -> Result< (), Error >
+{
+  let mut header = tar::Header::new_gnu();
+  header.set_size( content.len() as u64 );
+  header.set_mode( mode );
+  header.set_mtime( 0 );
+  header.set_uid( 0 );
+  header.set_gid( 0 );
+  header.set_entry_type( tar::EntryType::Regular );
+  builder.append_data( &mut header, name, content )?;
+  Ok( () )
+}
@@ -1,7 +1,8 @@
 /// Serialization and file I/O operations for [`TemplateArchive`].
 ///
-/// Provides JSON/YAML serialization, file save/load, directory packing,
-/// and content internalize/externalize operations.
+/// Provides JSON/YAML serialization, materialization, directory packing,
+/// and content internalize/externalize operations; file save/load lives in
+/// format.rs.
 /// Split from mod.rs to keep both files within source file size limits.
 use std ::path ::Path;
 
@@ -12,13 +13,17 @@ use crate ::
   HandlebarsRenderer,
   RealFileSystem,
   TemplateRenderer,
-  Values,
   WriteMode,
-  validate_path,
+  merge::Planned,
+  DEFAULT_FILE_MODE,
 };
 
+#[cfg(feature = "external_content")]
+use crate::merge::plan_write;
+
 use super ::
 {
+  plan::Step,
   FileContent,
   MaterializationReport,
   TemplateArchive,
@@ -113,7 +118,8 @@ impl TemplateArchive
   /// Materialize archive to filesystem at `base_path`
   ///
   /// Creates all directories and files, applying template rendering
-  /// with current parameter values.
+  /// with current parameter values. Existing files are handled according to
+  /// each file's `WriteMode` (rewritten, merged, or skipped).
   ///
   /// # Errors
   ///
@@ -130,6 +136,7 @@ impl TemplateArchive
   /// # Errors
   ///
   /// Returns error if template rendering, file creation, or path validation fails.
+  /// Rendering and merge errors are reported before anything is written.
   pub fn materialize_with_components<R, FS>(
     &self,
     base_path: &Path,
@@ -142,55 +149,14 @@ impl TemplateArchive
   {
     let mut report = MaterializationReport::default();
 
-    // Get values or use empty if none set
-    let values = self.values.as_ref().map( Values::to_serializable ).unwrap_or_default();
-
-    // Track directories that will be created
-    // RealFileSystem creates directories automatically in write()
-    // MemoryFileSystem doesnt need directory creation
-    for dir in self.list_directories()
+    // Render and plan every output first (see plan.rs), so nothing is written
+    // when rendering or a merge fails
+    let steps = self.plan_steps( base_path, renderer, filesystem, &mut report )?;
+    report.create_directories( base_path, filesystem )?;
+    for step in steps
     {
-      report.directories_created.push( dir );
-    }
-
-    // Process each file
-    for file in &self.files
-    {
-      // Validate path for security (prevent directory traversal)
-      validate_path( &file.path )?;
-
-      let full_path = base_path.join( &file.path );
-
-      let final_content = match &file.content
-      {
-        FileContent::Text( template ) =>
-        {
-          // Render template
-          renderer.render( template, &values )?
-        }
-        FileContent::Binary( bytes ) =>
-        {
-          // Convert binary to string for filesystem write
-          // In real impl, FileSystem trait would need write_bytes method
-          String::from_utf8_lossy( bytes ).to_string()
-        }
-      };
-
-      let existed = filesystem.exists( &full_path );
-
-      // Write file (RealFileSystem creates parent dirs automatically)
-      filesystem.write( &full_path, &final_content )?;
-
-      report.total_bytes_written += final_content.len();
-
-      if existed
-      {
-        report.files_updated.push( file.path.clone() );
-      }
-      else
-      {
-        report.files_created.push( file.path.clone() );
-      }
+      step.apply( filesystem )?;
+      report.record( &step.path, &step.planned, step.len() );
     }
 
     Ok( report )
@@ -245,28 +211,17 @@ impl TemplateArchive
   {
     let mut report = MaterializationReport::default();
 
-    // Get values or use empty if none set
-    let values = self.values.as_ref().map( Values::to_serializable ).unwrap_or_default();
-
-    // Track directories
-    for dir in self.list_directories()
-    {
-      report.directories_created.push( dir );
-    }
-
-    // Process each file
-    for file in &self.files
+    // Expand conditions, fan-out and path templates; paths are validated there
+    let mut steps = Vec::new();
+    for output in self.expand_outputs( renderer, &mut report )?
     {
-      // Validate path for security (prevent directory traversal)
-      validate_path( &file.path )?;
-
-      let full_path = base_path.join( &file.path );
+      let file = output.file;
 
       // Resolve content from source (external or inline)
       let content = if let Some( source ) = &file.content_source
       {
-        // Use external source via resolver
-        resolver.resolve( source )?
+        // Use external source via resolver, verifying any pinned checksum
+        resolver.resolve_pinned( source, file.checksum() )?
       }
       else
       {
@@ -274,34 +229,14 @@ impl TemplateArchive
         file.content.clone()
       };
 
-      // Render content
-      let final_content = match &content
-      {
-        FileContent::Text( template ) =>
-        {
-          renderer.render( template, &values )?
-        }
-        FileContent::Binary( bytes ) =>
-        {
-          String::from_utf8_lossy( bytes ).to_string()
-        }
-      };
-
-      let existed = filesystem.exists( &full_path );
-
-      // Write file
-      filesystem.write( &full_path, &final_content )?;
-
-      report.total_bytes_written += final_content.len();
+      steps.push( Step::new( output, &content, base_path, renderer, filesystem )? );
+    }
 
-      if existed
-      {
-        report.files_updated.push( file.path.clone() );
-      }
-      else
-      {
-        report.files_created.push( file.path.clone() );
-      }
+    report.create_directories( base_path, filesystem )?;
+    for step in steps
+    {
+      step.apply( filesystem )?;
+      report.record( &step.path, &step.planned, step.len() );
     }
 
     Ok( report )
@@ -311,6 +246,8 @@ impl TemplateArchive
   ///
   /// Instead of using `FileSystem` trait, uses `ContentStorage` for maximum
   /// flexibility. This allows writing to databases, cloud storage, etc.
+  /// Merge and `CreateOnly` write modes see existing content through
+  /// `ContentStorage::load`.
   ///
   /// # Parameters
   ///
@@ -356,27 +293,17 @@ impl TemplateArchive
   {
     let mut report = MaterializationReport::default();
 
-    // Get values or use empty if none set
-    let values = self.values.as_ref().map( Values::to_serializable ).unwrap_or_default();
-
-    // Track directories
-    for dir in self.list_directories()
-    {
-      report.directories_created.push( dir );
-    }
-
-    // Process each file
-    for file in &self.files
+    // Expand conditions, fan-out and path templates; paths are validated there
+    for output in self.expand_outputs( renderer, &mut report )?
     {
-      // Validate path for security (prevent directory traversal)
-      validate_path( &file.path )?;
-
-      let full_path = base_path.join( &file.path );
+      let file = output.file;
+      let values = &output.values;
+      let full_path = base_path.join( &output.path );
 
       // Resolve content from source (external or inline)
       let content = if let Some( source ) = &file.content_source
       {
-        resolver.resolve( source )?
+        resolver.resolve_pinned( source, file.checksum() )?
       }
       else
       {
@@ -388,7 +315,7 @@ impl TemplateArchive
       {
         FileContent::Text( template ) =>
         {
-          FileContent::Text( renderer.render( template, &values )? )
+          FileContent::Text( renderer.render( template, values )? )
         }
         FileContent::Binary( bytes ) =>
         {
@@ -396,16 +323,34 @@ impl TemplateArchive
         }
       };
 
-      // Store using ContentStorage
-      storage.store( &full_path, &rendered_content )?;
-
-      report.total_bytes_written += match &rendered_content
+      // Apply write mode against what the storage already holds
+      let existing = storage.load( &full_path )?;
+      let ( rendered_text, rendered_len ) = match &rendered_content
       {
-        FileContent::Text( s ) => s.len(),
-        FileContent::Binary( b ) => b.len(),
+        FileContent::Text( text ) => ( Some( text.as_str() ), text.len() ),
+        FileContent::Binary( bytes ) => ( None, bytes.len() ),
       };
+      let planned = plan_write
+      (
+        &file.write_mode,
+        &full_path,
+        existing.is_some(),
+        || match ( existing, rendered_text )
+        {
+          ( Some( FileContent::Text( current ) ), Some( _ ) ) => Ok( current ),
+          _ => Err( Error::InvalidTemplate( format!( "{}: binary content cannot be merged", full_path.display() ) ) ),
+        },
+        rendered_text.unwrap_or_default(),
+      )?;
 
-      report.files_created.push( file.path.clone() );
+      // Store using ContentStorage
+      match &planned
+      {
+        Planned::Skip => {}
+        Planned::Merge( merged ) => storage.store( &full_path, &FileContent::Text( merged.clone() ) )?,
+        Planned::Create | Planned::Update => storage.store( &full_path, &rendered_content )?,
+      }
+      report.record( &output.path, &planned, rendered_len );
     }
 
     Ok( report )
@@ -445,46 +390,77 @@ impl TemplateArchive
   /// println!( "Packed {} files", archive.file_count() );
   /// ```
   pub fn pack_from_dir( name: impl Into< String >, base_path: &Path ) -> Result< Self, Error >
+  {
+    Self::pack_from_dir_with( name, base_path, &RealFileSystem::new() )
+  }
+
+  /// Create archive from a directory tree on a custom file system.
+  ///
+  /// Like [`TemplateArchive::pack_from_dir`]; files are visited in sorted
+  /// order and a mode other than `DEFAULT_FILE_MODE` is recorded in
+  /// `FileMetadata::permissions`, so executables keep their bits through
+  /// materialization.
+  ///
+  /// # Examples
+  ///
+  /// ```rust
+  /// use genfile_core::{ TemplateArchive, FileSystem, MemoryFileSystem };
+  /// use std::path::Path;
+  ///
+  /// let mut fs = MemoryFileSystem::new();
+  /// fs.write( Path::new( "/tpl/run.sh" ), "#!/bin/sh\n" ).unwrap();
+  /// fs.set_permissions( Path::new( "/tpl/run.sh" ), 0o755 ).unwrap();
+  ///
+  /// let archive = TemplateArchive::pack_from_dir_with( "scripts", Path::new( "/tpl" ), &fs ).unwrap();
+  /// assert_eq!( archive.get_file( Path::new( "run.sh" ) ).unwrap().permissions(), Some( 0o755 ) );
+  /// ```
+  ///
+  /// # Errors
+  ///
+  /// Returns error if directory cant be read or files cant be accessed
+  pub fn pack_from_dir_with< FS: FileSystem >( name: impl Into< String >, base_path: &Path, filesystem: &FS ) -> Result< Self, Error >
   {
     let mut archive = Self::new( name );
 
     // Recursively walk directory
-    fn visit_dir( archive: &mut TemplateArchive, base: &Path, current: &Path ) -> Result< (), Error >
+    fn visit_dir< FS: FileSystem >( archive: &mut TemplateArchive, base: &Path, current: &Path, filesystem: &FS ) -> Result< (), Error >
     {
-      for entry in std::fs::read_dir( current )?
+      for path in filesystem.read_dir( current )?
       {
-        let entry = entry?;
-        let path = entry.path();
-
-        if path.is_dir()
+        let metadata = filesystem.metadata( &path )?;
+        if metadata.is_dir()
         {
-          visit_dir( archive, base, &path )?;
+          visit_dir( archive, base, &path, filesystem )?;
+          continue;
         }
-        else if path.is_file()
-        {
-          // Get relative path from base
-          let rel_path = path.strip_prefix( base )
-            .map_err( | e | Error::Render( format!( "Path error: {e}" ) ) )?
-            .to_path_buf();
 
-          // Read file
-          let data = std::fs::read( &path )?;
+        // Get relative path from base
+        let rel_path = path.strip_prefix( base )
+          .map_err( | e | Error::Render( format!( "Path error: {e}" ) ) )?
+          .to_path_buf();
 
-          // Detect if text or binary
-          let content = match String::from_utf8( data.clone() )
-          {
-            Ok( text ) => FileContent::Text( text ),
-            Err( _ ) => FileContent::Binary( data ),
-          };
+        // Detect if text or binary
+        let data = filesystem.read_bytes( &path )?;
+        let content = match String::from_utf8( data )
+        {
+          Ok( text ) => FileContent::Text( text ),
+          Err( err ) => FileContent::Binary( err.into_bytes() ),
+        };
 
-          archive.add_file( rel_path, content, WriteMode::Rewrite );
+        archive.add_file( rel_path.clone(), content, WriteMode::Rewrite );
+        if let Some( mode ) = metadata.mode.filter( | mode | *mode != DEFAULT_FILE_MODE )
+        {
+          if let Some( file ) = archive.get_file_mut( &rel_path )
+          {
+            file.set_permissions( mode );
+          }
         }
       }
 
       Ok( () )
     }
 
-    visit_dir( &mut archive, base_path, base_path )?;
+    visit_dir( &mut archive, base_path, base_path, filesystem )?;
 
     Ok( archive )
   }
@@ -528,8 +504,8 @@ impl TemplateArchive
     {
       if let Some( source ) = &file.content_source
       {
-        // Resolve external content
-        let content = resolver.resolve( source )?;
+        // Resolve external content, verifying any pinned checksum
+        let content = resolver.resolve_pinned( source, file.checksum() )?;
 
         // Replace inline content
         file.content = content;
@@ -542,6 +518,46 @@ impl TemplateArchive
     Ok( () )
   }
 
+  /// Pin the checksum of every external content reference.
+  ///
+  /// Resolves each external source and records `sha256:<hex>` of its content
+  /// in `FileMetadata::checksum`, creating metadata where absent. Later
+  /// `internalize` and `materialize_with_*` calls fail with
+  /// `Error::ChecksumMismatch` if a source changes. Already pinned files are
+  /// verified, not re-pinned.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use genfile_core::{ TemplateArchive, DefaultContentResolver };
+  ///
+  /// let mut archive = TemplateArchive::new( "test" );
+  /// // ... add files with external sources ...
+  ///
+  /// archive.pin_checksums( &DefaultContentResolver::new() ).unwrap();
+  /// ```
+  ///
+  /// # Errors
+  ///
+  /// Returns error if content resolution fails or an existing pin does not match.
+  #[cfg(feature = "external_content")]
+  pub fn pin_checksums< CR >( &mut self, resolver: &CR ) -> Result< (), Error >
+  where
+    CR: crate::ContentResolver,
+  {
+    for file in &mut self.files
+    {
+      if let Some( source ) = &file.content_source
+      {
+        let content = resolver.resolve_pinned( source, file.checksum() )?;
+        let checksum = crate::content_checksum( &content );
+        file.metadata.get_or_insert_with( super::FileMetadata::default ).checksum = Some( checksum );
+      }
+    }
+
+    Ok( () )
+  }
+
   /// Externalize inline content to file references.
   ///
   /// Writes all inline content to files in the specified directory
@@ -571,9 +587,22 @@ impl TemplateArchive
   /// ```
   #[cfg(feature = "external_content")]
   pub fn externalize( &mut self, base_path: &Path ) -> Result< (), Error >
+  {
+    self.externalize_with( base_path, &mut RealFileSystem::new() )
+  }
+
+  /// Externalize inline content to file references on a custom file system.
+  ///
+  /// Like [`TemplateArchive::externalize`]; binary content is written as is.
+  ///
+  /// # Errors
+  ///
+  /// Returns error if directory creation or file writing fails.
+  #[cfg(feature = "external_content")]
+  pub fn externalize_with< FS: FileSystem >( &mut self, base_path: &Path, filesystem: &mut FS ) -> Result< (), Error >
   {
     // Create base directory
-    std::fs::create_dir_all( base_path )?;
+    filesystem.create_dir_all( base_path )?;
 
     for file in &mut self.files
     {
@@ -588,11 +617,11 @@ impl TemplateArchive
         {
           FileContent::Text( text ) =>
           {
-            std::fs::write( &content_path, text )?;
+            filesystem.write( &content_path, text )?;
           }
           FileContent::Binary( bytes ) =>
           {
-            std::fs::write( &content_path, bytes )?;
+            filesystem.write_bytes( &content_path, bytes )?;
           }
         }
 
@@ -606,56 +635,52 @@ impl TemplateArchive
 
     Ok( () )
   }
+}
 
-  /// Save archive to file using JSON format.
-  ///
-  /// # Parameters
-  ///
-  /// - `path`: File path where archive will be saved
-  ///
-  /// # Errors
-  ///
-  /// Returns error if JSON serialization or file writing fails.
-  ///
-  /// # Examples
-  ///
-  /// ```rust,no_run
-  /// use genfile_core::TemplateArchive;
-  /// use std::path::Path;
-  ///
-  /// let archive = TemplateArchive::new( "test" );
-  /// archive.save_to_file( Path::new( "archive.json" ) ).unwrap();
-  /// ```
-  #[cfg(feature = "json")]
-  pub fn save_to_file( &self, path: &Path ) -> Result< (), Error >
+impl Step
+{
+  /// Write this step to `filesystem` and apply its permissions.
+  pub( crate ) fn apply< FS: FileSystem >( &self, filesystem: &mut FS ) -> Result< (), Error >
   {
-    let json = self.to_json_pretty()?;
-    std::fs::write( path, json )?;
+    match ( &self.planned, &self.bytes )
+    {
+      ( Planned::Skip, _ ) => return Ok( () ),
+      ( Planned::Create | Planned::Update, Some( bytes ) ) => filesystem.write_bytes( &self.full_path, bytes )?,
+      ( Planned::Create | Planned::Update, None ) => filesystem.write( &self.full_path, &self.content )?,
+      ( Planned::Merge( merged ), _ ) => filesystem.write( &self.full_path, merged )?,
+    }
+    match self.mode
+    {
+      Some( mode ) => filesystem.set_permissions( &self.full_path, mode ),
+      None => Ok( () ),
+    }
+  }
+}
+
+impl MaterializationReport
+{
+  /// Create the recorded `directories_created` under `base_path`.
+  fn create_directories< FS: FileSystem >( &self, base_path: &Path, filesystem: &mut FS ) -> Result< (), Error >
+  {
+    filesystem.create_dir_all( base_path )?;
+    for dir in &self.directories_created
+    {
+      filesystem.create_dir_all( &base_path.join( dir ) )?;
+    }
     Ok( () )
   }
 
-  /// Load archive from JSON file.
-  ///
-  /// # Parameters
-  ///
-  /// - `path`: File path to load archive from
-  ///
-  /// # Errors
-  ///
-  /// Returns error if file reading or JSON deserialization fails.
-  ///
-  /// # Examples
-  ///
-  /// ```rust,no_run
-  /// use genfile_core::TemplateArchive;
-  /// use std::path::Path;
-  ///
-  /// let archive = TemplateArchive::load_from_file( Path::new( "archive.json" ) ).unwrap();
-  /// ```
-  #[cfg(feature = "json")]
-  pub fn load_from_file( path: &Path ) -> Result< Self, Error >
+  /// Record the outcome of one file write; `rendered_len` is the size of the rendered content.
+  pub( super ) fn record( &mut self, path: &Path, planned: &Planned, rendered_len: usize )
   {
-    let json = std::fs::read_to_string( path )?;
-    Self::from_json( &json )
+    let ( list, written ) = match planned
+    {
+      Planned::Create => ( &mut self.files_created, rendered_len ),
+      Planned::Update => ( &mut self.files_updated, rendered_len ),
+      Planned::Merge( merged ) => ( &mut self.files_merged, merged.len() ),
+      Planned::Skip => ( &mut self.files_skipped, 0 ),
+    };
+    list.push( path.to_path_buf() );
+    self.total_bytes_written += written;
   }
 }
@@ -51,6 +51,7 @@ use crate::
 ///       is_mandatory: true,
 ///       default_value: None,
 ///       description: Some("Name of the project".into()),
+///       kind: None,
 ///     }
 /// );
 ///
@@ -88,6 +89,19 @@ pub struct TemplateArchive
   /// Archive metadata
   #[cfg_attr(any(feature = "json", feature = "yaml"), serde(skip_serializing_if = "Option::is_none"))]
   pub metadata: Option< ArchiveMetadata >,
+
+  /// Parent archives this one extends, relative to the directory of its archive file
+  /// Composed by `resolve_parents`; later parents override earlier ones
+  #[cfg_attr(any(feature = "json", feature = "yaml"), serde(default, skip_serializing_if = "Vec::is_empty"))]
+  pub extends: Vec< PathBuf >,
+
+  /// Inherited files, or whole inherited directories, left out of the composed archive
+  #[cfg_attr(any(feature = "json", feature = "yaml"), serde(default, skip_serializing_if = "Vec::is_empty"))]
+  pub remove_files: Vec< PathBuf >,
+
+  /// Inherited parameters left out of the composed archive, together with their values
+  #[cfg_attr(any(feature = "json", feature = "yaml"), serde(default, skip_serializing_if = "Vec::is_empty"))]
+  pub remove_parameters: Vec< String >,
 }
 
 fn default_version() -> String
@@ -146,6 +160,47 @@ pub struct TemplateFile
   pub content_source: Option< crate::ContentSource >,
 }
 
+#[cfg(feature = "external_content")]
+impl TemplateFile
+{
+  /// Pinned checksum of the external content, if any
+  #[must_use]
+  pub fn checksum( &self ) -> Option< &str >
+  {
+    self.metadata.as_ref().and_then( | metadata | metadata.checksum.as_deref() )
+  }
+}
+
+impl TemplateFile
+{
+  /// Set the Unix permissions the file is materialized with (e.g. `0o755`).
+  ///
+  /// # Examples
+  ///
+  /// ```rust
+  /// use genfile_core::{ TemplateArchive, WriteMode };
+  /// use std::path::{ Path, PathBuf };
+  ///
+  /// let mut archive = TemplateArchive::new( "scripts" );
+  /// archive.add_text_file( PathBuf::from( "run.sh" ), "#!/bin/sh\n", WriteMode::Rewrite );
+  /// archive.get_file_mut( Path::new( "run.sh" ) ).unwrap().set_permissions( 0o755 );
+  ///
+  /// assert_eq!( archive.get_file( Path::new( "run.sh" ) ).unwrap().permissions(), Some( 0o755 ) );
+  /// ```
+  pub fn set_permissions( &mut self, mode: u32 ) -> &mut Self
+  {
+    self.metadata.get_or_insert_with( FileMetadata::default ).permissions = Some( mode );
+    self
+  }
+
+  /// Unix permissions the file is materialized with, if set
+  #[must_use]
+  pub fn permissions( &self ) -> Option< u32 >
+  {
+    self.metadata.as_ref().and_then( | metadata | metadata.permissions )
+  }
+}
+
 /// File content representation
 #[derive(Debug, Clone)]
 #[cfg_attr(any(feature = "json", feature = "yaml"), derive(serde::Serialize, serde::Deserialize))]
@@ -190,11 +245,12 @@ mod base64_encoding
 }
 
 /// Optional metadata for individual files
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 #[cfg_attr(any(feature = "json", feature = "yaml"), derive(serde::Serialize, serde::Deserialize))]
 pub struct FileMetadata
 {
   /// Unix permissions (e.g., 0o755 for executables)
+  /// Applied after writing; `pack_from_dir` records modes other than `DEFAULT_FILE_MODE`
   #[cfg_attr(any(feature = "json", feature = "yaml"), serde(skip_serializing_if = "Option::is_none"))]
   pub permissions: Option< u32 >,
 
@@ -205,6 +261,21 @@ pub struct FileMetadata
   /// File-specific comments
   #[cfg_attr(any(feature = "json", feature = "yaml"), serde(skip_serializing_if = "Option::is_none"))]
   pub comment: Option< String >,
+
+  /// Pinned checksum of the external content (`sha256:<hex>`)
+  /// Resolving `content_source` fails unless the content hashes to this value
+  #[cfg_attr(any(feature = "json", feature = "yaml"), serde(skip_serializing_if = "Option::is_none"))]
+  #[cfg(feature = "external_content")]
+  pub checksum: Option< String >,
+
+  /// Parameter that must be truthy for the file to be emitted (`!name` negates)
+  #[cfg_attr(any(feature = "json", feature = "yaml"), serde(skip_serializing_if = "Option::is_none"))]
+  pub condition: Option< String >,
+
+  /// List parameter to emit the file once per element of
+  /// Each copy renders with `item` and `item_index` bound
+  #[cfg_attr(any(feature = "json", feature = "yaml"), serde(skip_serializing_if = "Option::is_none"))]
+  pub for_each: Option< String >,
 }
 
 impl TemplateArchive
@@ -233,6 +304,9 @@ impl TemplateArchive
       parameters: Parameters::default(),
       values: None,
       metadata: None,
+      extends: Vec::new(),
+      remove_files: Vec::new(),
+      remove_parameters: Vec::new(),
     }
   }
 
@@ -529,6 +603,7 @@ impl TemplateArchive
   ///       is_mandatory: true,
   ///       default_value: None,
   ///       description: None,
+  ///       kind: None,
   ///     }
   /// );
   /// ```
@@ -819,6 +894,16 @@ impl TemplateArchive
 }
 
 mod io;
+mod expand;
+mod plan;
+mod format;
+mod compose;
+mod templatize;
+
+pub use expand :: { LOOP_ITEM, LOOP_INDEX };
+pub use plan :: { FileAction, PlannedFile };
+pub use format ::ArchiveFormat;
+pub use templatize ::TemplatizeReport;
 
 /// Materialization report
 #[derive(Debug, Clone, Default)]
@@ -830,15 +915,25 @@ pub struct MaterializationReport
   /// Files successfully updated
   pub files_updated: Vec< PathBuf >,
 
-  /// Files skipped (already exist, no changes)
+  /// Existing files merged with rendered content (`JsonMerge`, `YamlMerge`, `MarkedRegions`)
+  pub files_merged: Vec< PathBuf >,
+
+  /// Files skipped (already exist, left untouched by `CreateOnly`)
   pub files_skipped: Vec< PathBuf >,
 
+  /// Archive files not emitted: condition false or `for_each` list empty
+  pub files_excluded: Vec< PathBuf >,
+
   /// Directories created
   pub directories_created: Vec< PathBuf >,
 
-  /// Total bytes written
+  /// Total bytes written (that would be written, for a plan)
   pub total_bytes_written: usize,
 
   /// Errors encountered (if any)
   pub errors: Vec< ( PathBuf, String ) >,
+
+  /// Per-output actions and diffs; filled only by the dry-run
+  /// `TemplateArchive::plan*` methods, which write nothing
+  pub plan: Vec< PlannedFile >,
 }
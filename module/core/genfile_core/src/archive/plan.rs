@@ -0,0 +1,265 @@
+/// Dry-run planning for [`TemplateArchive`] materialization.
+///
+/// Computes what each output would do to the destination (create, update,
+/// merge, skip) and a unified diff against the current contents, without
+/// writing. `materialize_with_components` writes the same steps, so a plan
+/// and the following materialization agree.
+use std ::path :: { Path, PathBuf };
+
+use similar ::TextDiff;
+
+use crate ::
+{
+  Error,
+  FileSystem,
+  HandlebarsRenderer,
+  RealFileSystem,
+  TemplateRenderer,
+  merge :: { plan_write, Planned },
+};
+
+use super ::
+{
+  expand::Output,
+  FileContent,
+  MaterializationReport,
+  TemplateArchive,
+};
+
+/// What materialization does, or would do, to one output
+#[ derive( Debug, Clone, Copy, PartialEq, Eq ) ]
+pub enum FileAction
+{
+  /// Target does not exist and is created
+  Create,
+  /// Target exists and is rewritten with the rendered content
+  Update,
+  /// Target exists and is merged with the rendered content
+  Merge,
+  /// Target exists and is left untouched (`CreateOnly`)
+  Skip,
+}
+
+impl core ::fmt ::Display for FileAction
+{
+  fn fmt( &self, f: &mut core ::fmt ::Formatter< '_ > ) -> core ::fmt ::Result
+  {
+    f.write_str( match self
+    {
+      FileAction ::Create => "create",
+      FileAction ::Update => "update",
+      FileAction ::Merge => "merge",
+      FileAction ::Skip => "skip",
+    })
+  }
+}
+
+/// One output of a dry-run plan
+#[ derive( Debug, Clone, PartialEq, Eq ) ]
+pub struct PlannedFile
+{
+  /// Output path relative to the destination
+  pub path: PathBuf,
+  /// What materialization would do
+  pub action: FileAction,
+  /// Unified diff from the current to the resulting contents; `None` when
+  /// the file would not change (skipped, or rewritten with identical content)
+  pub diff: Option< String >,
+}
+
+/// One rendered output and how it is written
+pub( crate ) struct Step
+{
+  pub( crate ) path: PathBuf,
+  pub( crate ) full_path: PathBuf,
+  pub( crate ) planned: Planned,
+  /// Rendered text; binary content converted lossily, for planning only
+  pub( crate ) content: String,
+  /// Binary content, written as is
+  pub( crate ) bytes: Option< Vec< u8 > >,
+  /// Unix permissions applied after writing
+  pub( crate ) mode: Option< u32 >,
+}
+
+impl TemplateArchive
+{
+  /// Plan materialization to `base_path` on disk without writing anything.
+  ///
+  /// Returns the report `materialize` would produce, with
+  /// `MaterializationReport::plan` listing each output's action and diff.
+  ///
+  /// # Errors
+  ///
+  /// Returns error if values are invalid, rendering fails, or a merge fails.
+  pub fn plan( &self, base_path: &Path ) -> Result< MaterializationReport, Error >
+  {
+    self.plan_with_components( base_path, &HandlebarsRenderer::new(), &RealFileSystem::new() )
+  }
+
+  /// Plan materialization with custom components without writing anything.
+  ///
+  /// # Examples
+  ///
+  /// ```rust
+  /// use genfile_core::{ TemplateArchive, WriteMode, HandlebarsRenderer, MemoryFileSystem, FileSystem, FileAction };
+  /// use std::path::{ Path, PathBuf };
+  ///
+  /// let mut archive = TemplateArchive::new( "demo" );
+  /// archive.add_text_file( PathBuf::from( "greeting.txt" ), "Hello, new\n", WriteMode::Rewrite );
+  ///
+  /// let mut fs = MemoryFileSystem::new();
+  /// fs.write( Path::new( "/out/greeting.txt" ), "Hello, old\n" ).unwrap();
+  ///
+  /// let report = archive.plan_with_components( Path::new( "/out" ), &HandlebarsRenderer::new(), &fs ).unwrap();
+  /// assert_eq!( report.plan[ 0 ].action, FileAction::Update );
+  /// assert!( report.unified_diff().contains( "-Hello, old\n+Hello, new\n" ) );
+  /// assert_eq!( fs.read( Path::new( "/out/greeting.txt" ) ).unwrap(), "Hello, old\n" );
+  /// ```
+  ///
+  /// # Errors
+  ///
+  /// Returns error if values are invalid, rendering fails, or a merge fails.
+  pub fn plan_with_components< R, FS >( &self, base_path: &Path, renderer: &R, filesystem: &FS ) -> Result< MaterializationReport, Error >
+  where
+    R: TemplateRenderer,
+    FS: FileSystem,
+  {
+    let mut report = MaterializationReport::default();
+    for step in self.plan_steps( base_path, renderer, filesystem, &mut report )?
+    {
+      let diff = step.diff( filesystem );
+      report.record( &step.path, &step.planned, step.len() );
+      report.plan.push( PlannedFile { path: step.path, action: step.planned.action(), diff } );
+    }
+    Ok( report )
+  }
+
+  /// Render every output and decide how it is written, without writing.
+  pub( crate ) fn plan_steps< R, FS >
+  (
+    &self,
+    base_path: &Path,
+    renderer: &R,
+    filesystem: &FS,
+    report: &mut MaterializationReport,
+  ) -> Result< Vec< Step >, Error >
+  where
+    R: TemplateRenderer,
+    FS: FileSystem,
+  {
+    // Expand conditions, fan-out and path templates; paths are validated there
+    let outputs = self.expand_outputs( renderer, report )?;
+    let mut steps = Vec::with_capacity( outputs.len() );
+    for output in outputs
+    {
+      let content = &output.file.content;
+      steps.push( Step::new( output, content, base_path, renderer, filesystem )? );
+    }
+    Ok( steps )
+  }
+}
+
+impl Step
+{
+  /// Render `content` for `output` and decide how it is written under `base_path`.
+  pub( crate ) fn new< R, FS >
+  (
+    output: Output< '_ >,
+    content: &FileContent,
+    base_path: &Path,
+    renderer: &R,
+    filesystem: &FS,
+  ) -> Result< Self, Error >
+  where
+    R: TemplateRenderer,
+    FS: FileSystem,
+  {
+    let full_path = base_path.join( &output.path );
+    let ( text, bytes ) = match content
+    {
+      FileContent::Text( template ) => ( renderer.render( template, &output.values )?, None ),
+      FileContent::Binary( bytes ) => ( String::from_utf8_lossy( bytes ).to_string(), Some( bytes.clone() ) ),
+    };
+
+    let exists = filesystem.exists( &full_path );
+    let planned = plan_write( &output.file.write_mode, &full_path, exists, || filesystem.read( &full_path ), &text )?;
+    Ok( Step { path: output.path, full_path, planned, content: text, bytes, mode: output.file.permissions() } )
+  }
+
+  /// Size of the content written on create or update
+  pub( crate ) fn len( &self ) -> usize
+  {
+    self.bytes.as_ref().map_or( self.content.len(), Vec::len )
+  }
+
+  /// Unified diff of this step against the current contents of `filesystem`.
+  fn diff< FS: FileSystem >( &self, filesystem: &FS ) -> Option< String >
+  {
+    let new_label = format!( "b/{}", self.path.display() );
+    let old_label = match self.planned
+    {
+      Planned::Create => "/dev/null".to_string(),
+      _ => format!( "a/{}", self.path.display() ),
+    };
+
+    if let Some( bytes ) = &self.bytes
+    {
+      let unchanged = match self.planned
+      {
+        Planned::Skip => true,
+        Planned::Update => filesystem.read_bytes( &self.full_path ).is_ok_and( | old | &old == bytes ),
+        Planned::Create | Planned::Merge( _ ) => false,
+      };
+      return ( !unchanged ).then( || format!( "Binary files {old_label} and {new_label} differ\n" ) );
+    }
+
+    let ( old, new ) = match &self.planned
+    {
+      Planned::Skip => return None,
+      Planned::Create => ( Some( String::new() ), self.content.as_str() ),
+      Planned::Update => ( filesystem.read( &self.full_path ).ok(), self.content.as_str() ),
+      Planned::Merge( merged ) => ( filesystem.read( &self.full_path ).ok(), merged.as_str() ),
+    };
+
+    match old
+    {
+      Some( old ) if old == new => None,
+      Some( old ) => Some
+      (
+        TextDiff::from_lines( &old, new )
+          .unified_diff()
+          .context_radius( 3 )
+          .header( &old_label, &new_label )
+          .to_string()
+      ),
+      None => Some( format!( "Binary files {old_label} and {new_label} differ\n" ) ),
+    }
+  }
+}
+
+impl Planned
+{
+  /// Public action corresponding to this write decision
+  fn action( &self ) -> FileAction
+  {
+    match self
+    {
+      Planned::Create => FileAction::Create,
+      Planned::Update => FileAction::Update,
+      Planned::Merge( _ ) => FileAction::Merge,
+      Planned::Skip => FileAction::Skip,
+    }
+  }
+}
+
+impl MaterializationReport
+{
+  /// All diffs of a dry-run plan, concatenated in output order.
+  ///
+  /// Empty for reports from a real materialization, whose `plan` is empty.
+  #[ must_use ]
+  pub fn unified_diff( &self ) -> String
+  {
+    self.plan.iter().filter_map( | file | file.diff.as_deref() ).collect()
+  }
+}
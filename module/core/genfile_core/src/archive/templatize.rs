@@ -0,0 +1,222 @@
+/// Reverse-engineering templates for [`TemplateArchive`]: concrete values back to placeholders.
+///
+/// Replaces every occurrence of the given values in file contents and paths
+/// with `{{name}}` placeholders and registers a parameter for each, so a
+/// packed project becomes a template that reproduces it.
+use std ::collections ::BTreeMap;
+use std ::path :: { Path, PathBuf };
+
+use crate ::
+{
+  Error,
+  ParameterDescriptor,
+};
+
+use super ::
+{
+  FileContent,
+  FileMetadata,
+  TemplateArchive,
+};
+
+/// Outcome of [`TemplateArchive::templatize`]
+#[derive(Debug, Clone, Default)]
+pub struct TemplatizeReport
+{
+  /// Occurrences replaced per parameter, in contents and paths together
+  pub replacements: BTreeMap< String, usize >,
+
+  /// Files whose content now contains placeholders (paths after renaming)
+  pub files_changed: Vec< PathBuf >,
+
+  /// Renamed files as `( old, new )` paths
+  pub files_renamed: Vec< ( PathBuf, PathBuf ) >,
+
+  /// Parameters registered by this call; already defined ones are kept as they are
+  pub parameters_added: Vec< String >,
+}
+
+impl TemplatizeReport
+{
+  /// Total number of replaced occurrences
+  #[must_use]
+  pub fn total_replacements( &self ) -> usize
+  {
+    self.replacements.values().sum()
+  }
+}
+
+impl TemplateArchive
+{
+  /// Turn concrete values back into placeholders.
+  ///
+  /// `values` maps parameter names to the text they stand for, e.g.
+  /// `name` → `my_crate`. Every occurrence of a value in text content and in
+  /// file paths becomes `{{name}}`; where values overlap, the longest wins.
+  /// Braces already in the content are escaped (`\{{`) and backslashes right
+  /// before a placeholder or an escaped brace are preserved, so materializing
+  /// the result with the same values reproduces the original files. Binary and
+  /// external files keep their content; their paths are still rewritten.
+  /// Each parameter not yet defined is registered as mandatory.
+  ///
+  /// Occurrences are matched as plain text, also inside longer words: a
+  /// value like `app` also matches in `application`.
+  ///
+  /// # Errors
+  ///
+  /// Returns `Error::InvalidParameter` for a name that is not a valid
+  /// placeholder identifier, an empty value, or a value shared by two names.
+  ///
+  /// # Examples
+  ///
+  /// ```rust
+  /// use genfile_core::{ TemplateArchive, FileContent, WriteMode };
+  /// use std::collections::BTreeMap;
+  /// use std::path::{ Path, PathBuf };
+  ///
+  /// let mut archive = TemplateArchive::new( "project" );
+  /// archive.add_text_file( PathBuf::from( "src/my_crate.rs" ), "//! my_crate by Alice", WriteMode::Rewrite );
+  ///
+  /// let values = BTreeMap::from( [ ( "name".to_string(), "my_crate".to_string() ), ( "author".to_string(), "Alice".to_string() ) ] );
+  /// let report = archive.templatize( &values ).unwrap();
+  ///
+  /// assert_eq!( report.total_replacements(), 3 );
+  /// let file = archive.get_file( Path::new( "src/{{name}}.rs" ) ).unwrap();
+  /// assert!( matches!( &file.content, FileContent::Text( text ) if text == "//! {{name}} by {{author}}" ) );
+  /// assert_eq!( archive.list_parameters(), vec![ "author", "name" ] );
+  /// ```
+  pub fn templatize( &mut self, values: &BTreeMap< String, String > ) -> Result< TemplatizeReport, Error >
+  {
+    let replacements = replacements( values )?;
+    let mut report = TemplatizeReport::default();
+
+    for file in &mut self.files
+    {
+      let path = replace_path( &file.path, &replacements, &mut report );
+      if path != file.path
+      {
+        report.files_renamed.push( ( file.path.clone(), path.clone() ) );
+        file.path = path;
+      }
+
+      #[cfg(feature = "external_content")]
+      if file.content_source.is_some()
+      {
+        continue;
+      }
+      if let FileContent::Text( text ) = &mut file.content
+      {
+        let before = report.total_replacements();
+        let templated = replace( text, &replacements, true, &mut report );
+        if report.total_replacements() > before
+        {
+          file.metadata.get_or_insert_with( FileMetadata::default ).is_template = true;
+          report.files_changed.push( file.path.clone() );
+        }
+        *text = templated;
+      }
+    }
+
+    for ( name, value ) in values
+    {
+      if self.get_parameter( name ).is_none()
+      {
+        self.add_parameter( ParameterDescriptor
+        {
+          parameter: name.clone(),
+          is_mandatory: true,
+          default_value: None,
+          description: Some( format!( "Templatized from \"{value}\"" ) ),
+          kind: None,
+        });
+        report.parameters_added.push( name.clone() );
+      }
+    }
+
+    Ok( report )
+  }
+}
+
+/// Validate `values` and order them longest value first
+fn replacements( values: &BTreeMap< String, String > ) -> Result< Vec< ( &str, &str ) >, Error >
+{
+  let invalid = | parameter: &str, reason: String | Error::InvalidParameter { parameter: parameter.to_string(), reason };
+  let mut seen: BTreeMap< &str, &str > = BTreeMap::new();
+  for ( name, value ) in values
+  {
+    let identifier = name.chars().next().is_some_and( | c | c.is_ascii_alphabetic() || c == '_' )
+      && name.chars().all( | c | c.is_ascii_alphanumeric() || c == '_' );
+    if !identifier
+    {
+      return Err( invalid( name, "not a valid placeholder name".into() ) );
+    }
+    if value.is_empty()
+    {
+      return Err( invalid( name, "value to templatize is empty".into() ) );
+    }
+    if let Some( other ) = seen.insert( value, name )
+    {
+      return Err( invalid( name, format!( "value \"{value}\" is also given for '{other}'" ) ) );
+    }
+  }
+
+  let mut ordered: Vec< _ > = values.iter().map( | ( name, value ) | ( name.as_str(), value.as_str() ) ).collect();
+  ordered.sort_by_key( | ( _, value ) | core::cmp::Reverse( value.len() ) );
+  Ok( ordered )
+}
+
+/// Replace values in one pass, so inserted placeholders are never matched again
+fn replace( text: &str, replacements: &[ ( &str, &str ) ], escape: bool, report: &mut TemplatizeReport ) -> String
+{
+  let mut result = String::with_capacity( text.len() );
+  let mut rest = text;
+  while let Some( c ) = rest.chars().next()
+  {
+    if let Some( ( name, value ) ) = replacements.iter().find( | ( _, value ) | rest.starts_with( value ) )
+    {
+      // a run of n >= 2 backslashes before `{{` renders as n - 1 of them
+      if result.ends_with( '\\' )
+      {
+        result.push( '\\' );
+      }
+      result.push_str( "{{" );
+      result.push_str( name );
+      result.push_str( "}}" );
+      *report.replacements.entry( ( *name ).to_string() ).or_default() += 1;
+      rest = &rest[ value.len() .. ];
+    }
+    else if escape && rest.starts_with( "{{" )
+    {
+      // backslashes right before `\{{` would join the escape, so render them from a string literal
+      let backslashes = result.len() - result.trim_end_matches( '\\' ).len();
+      if backslashes > 0
+      {
+        result.truncate( result.len() - backslashes );
+        result.push_str( "{{#with \"" );
+        result.push_str( &"\\\\".repeat( backslashes ) );
+        result.push_str( "\"}}{{this}}{{/with}}" );
+      }
+      result.push_str( "\\{{" );
+      rest = &rest[ 2 .. ];
+    }
+    else
+    {
+      result.push( c );
+      rest = &rest[ c.len_utf8() .. ];
+    }
+  }
+  result
+}
+
+/// Replace values in every component of `path`
+fn replace_path( path: &Path, replacements: &[ ( &str, &str ) ], report: &mut TemplatizeReport ) -> PathBuf
+{
+  path
+    .components()
+    .map( | component | match component.as_os_str().to_str()
+    {
+      Some( text ) => replace( text, replacements, false, report ),
+      None => component.as_os_str().to_string_lossy().into_owned(),
+    })
+    .collect()
+}
@@ -376,6 +376,141 @@ pub trait ContentResolver
   ///
   /// Returns error if content cannot be retrieved from the source
   fn resolve( &self, source: &ContentSource ) -> Result< FileContent, Error >;
+
+  /// Resolve content and check it against a pinned checksum.
+  ///
+  /// Archives call this with `FileMetadata::checksum` for every external
+  /// source. The default implementation resolves via `resolve` and then
+  /// verifies; resolvers with a content-addressed cache may override it to
+  /// serve pinned content without fetching.
+  ///
+  /// # Parameters
+  ///
+  /// - `source`: The content source to resolve
+  /// - `checksum`: Expected `sha256:<hex>` checksum, or `None` to skip verification
+  ///
+  /// # Errors
+  ///
+  /// Returns error if content cannot be retrieved, the checksum is malformed,
+  /// or the content does not match it (`Error::ChecksumMismatch`)
+  fn resolve_pinned( &self, source: &ContentSource, checksum: Option< &str > ) -> Result< FileContent, Error >
+  {
+    let content = self.resolve( source )?;
+    if let Some( expected ) = checksum
+    {
+      verify_checksum( &source_location( source ), &content, expected )?;
+    }
+    Ok( content )
+  }
+}
+
+/// Checksum prefix for SHA-256 digests
+const SHA256_PREFIX: &str = "sha256:";
+
+/// Compute the checksum of file content, as stored in `FileMetadata::checksum`.
+///
+/// The checksum is the SHA-256 digest of the raw bytes (UTF-8 for text),
+/// formatted as `sha256:<lowercase hex>`.
+///
+/// # Examples
+///
+/// ```rust
+/// use genfile_core::{ content_checksum, FileContent };
+///
+/// let checksum = content_checksum( &FileContent::Text( "abc".into() ) );
+/// assert_eq!( checksum, "sha256:ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad" );
+/// ```
+#[must_use]
+pub fn content_checksum( content: &FileContent ) -> String
+{
+  format!( "{SHA256_PREFIX}{}", sha256_hex( content_bytes( content ) ) )
+}
+
+/// Default cap on the size of fetched or unpacked content: 32 MiB
+#[ cfg( any( feature = "remote_content", feature = "container" ) ) ]
+pub( crate ) const DEFAULT_MAX_SIZE: u64 = 32 * 1024 * 1024;
+
+/// Lowercase hex SHA-256 digest of `bytes`.
+pub( crate ) fn sha256_hex( bytes: &[ u8 ] ) -> String
+{
+  use core::fmt::Write;
+  use sha2::Digest;
+  sha2::Sha256::digest( bytes ).iter().fold( String::with_capacity( 64 ), | mut hex, byte |
+  {
+    let _ = write!( hex, "{byte:02x}" );
+    hex
+  })
+}
+
+/// Raw bytes of file content.
+pub( crate ) fn content_bytes( content: &FileContent ) -> &[ u8 ]
+{
+  match content
+  {
+    FileContent::Text( text ) => text.as_bytes(),
+    FileContent::Binary( bytes ) => bytes,
+  }
+}
+
+/// Classify fetched bytes: valid UTF-8 is text, anything else binary.
+pub( crate ) fn content_from_bytes( data: Vec< u8 > ) -> FileContent
+{
+  match String::from_utf8( data )
+  {
+    Ok( text ) => FileContent::Text( text ),
+    Err( err ) => FileContent::Binary( err.into_bytes() ),
+  }
+}
+
+/// Hex digest part of a `sha256:<hex>` checksum.
+///
+/// # Errors
+///
+/// Returns `Error::InvalidTemplate` if the checksum is not a SHA-256 checksum.
+pub( crate ) fn checksum_digest( checksum: &str ) -> Result< String, Error >
+{
+  checksum.strip_prefix( SHA256_PREFIX )
+    .filter( | hex | hex.len() == 64 && hex.bytes().all( | b | b.is_ascii_hexdigit() ) )
+    .map( str::to_ascii_lowercase )
+    .ok_or_else( || Error::InvalidTemplate( format!(
+      "unsupported checksum `{checksum}`, expected {SHA256_PREFIX}<64 hex digits>"
+    ) ) )
+}
+
+/// Check `content` against a pinned `sha256:<hex>` checksum.
+///
+/// # Errors
+///
+/// Returns `Error::InvalidTemplate` for a malformed checksum and
+/// `Error::ChecksumMismatch` if the content hashes differently.
+pub( crate ) fn verify_checksum( location: &str, content: &FileContent, expected: &str ) -> Result< (), Error >
+{
+  let digest = checksum_digest( expected )?;
+  let actual = sha256_hex( content_bytes( content ) );
+  if actual == digest
+  {
+    Ok( () )
+  }
+  else
+  {
+    Err( Error::ChecksumMismatch
+    {
+      location: location.to_string(),
+      expected: expected.to_string(),
+      actual: format!( "{SHA256_PREFIX}{actual}" ),
+    })
+  }
+}
+
+/// Human-readable location of a source for error messages.
+pub( crate ) fn source_location( source: &ContentSource ) -> String
+{
+  match source
+  {
+    ContentSource::Inline { .. } => "inline content".to_string(),
+    ContentSource::File { path } => path.display().to_string(),
+    ContentSource::Url { url } => url.clone(),
+  }
 }
 
 /// Trait for storing content to various destinations.
@@ -418,13 +553,29 @@ pub trait ContentStorage
   ///
   /// Returns error if content cannot be written to destination
   fn store( &mut self, path: &Path, content: &FileContent ) -> Result< (), Error >;
+
+  /// Load existing content from a destination, if present.
+  ///
+  /// Used by write modes that depend on the current content (merges,
+  /// `CreateOnly`). The default reports every destination as absent, so
+  /// storages that cannot read back always receive the rendered content.
+  ///
+  /// # Errors
+  ///
+  /// Returns error if existing content cannot be read
+  fn load( &self, path: &Path ) -> Result< Option< FileContent >, Error >
+  {
+    let _ = path;
+    Ok( None )
+  }
 }
 
 /// Default content resolver implementation.
 ///
 /// Handles inline content, local filesystem files, and provides
-/// basic URL support (returns error by default - users should
-/// implement custom resolver for URL fetching).
+/// basic URL support (returns error by default - enable feature
+/// `remote_content` for `RemoteContentResolver`, or implement a
+/// custom resolver for URL fetching).
 ///
 /// # Examples
 ///
@@ -479,20 +630,16 @@ impl ContentResolver for DefaultContentResolver
 
         // Try to detect if its text or binary
         // Simple heuristic: if valid UTF-8, treat as text
-        match String::from_utf8( data.clone() )
-        {
-          Ok( text ) => Ok( FileContent::Text( text ) ),
-          Err( _ ) => Ok( FileContent::Binary( data ) ),
-        }
+        Ok( content_from_bytes( data ) )
       }
 
       ContentSource::Url { url } =>
       {
         // Default implementation doesnt support URL fetching
-        // Users should implement custom resolver with HTTP client
+        // Use `RemoteContentResolver` (feature `remote_content`) or a custom resolver
         Err( Error::Render( format!(
           "URL fetching not supported in default resolver. \
-          Implement custom ContentResolver to fetch from: {url}"
+          Use RemoteContentResolver or implement custom ContentResolver to fetch from: {url}"
         ) ) )
       }
     }
@@ -565,4 +712,14 @@ impl ContentStorage for DefaultContentStorage
 
     Ok( () )
   }
+
+  fn load( &self, path: &Path ) -> Result< Option< FileContent >, Error >
+  {
+    match std::fs::read( path )
+    {
+      Ok( data ) => Ok( Some( content_from_bytes( data ) ) ),
+      Err( err ) if err.kind() == std::io::ErrorKind::NotFound => Ok( None ),
+      Err( err ) => Err( err.into() ),
+    }
+  }
 }
@@ -39,6 +39,47 @@ pub enum Error
   ///
   /// Template content is malformed or unsupported.
   InvalidTemplate( String ),
+
+  /// Fetching external content failed.
+  ///
+  /// Network, protocol or `git` failure, an unsupported URL scheme, or a
+  /// source missing from the cache in offline mode.
+  Fetch( String ),
+
+  /// Fetched content is larger than the resolver allows.
+  ///
+  /// Guards against a source streaming without end into memory.
+  ContentTooLarge
+  {
+    /// URL of the content source
+    location: String,
+    /// Size limit in bytes
+    limit: u64,
+  },
+
+  /// Resolved content does not match its pinned checksum.
+  ///
+  /// The source changed since it was pinned, or was tampered with.
+  ChecksumMismatch
+  {
+    /// Path or URL of the content source
+    location: String,
+    /// Pinned checksum from `FileMetadata::checksum`
+    expected: String,
+    /// Checksum of the resolved content
+    actual: String,
+  },
+
+  /// A parameter value violates its declared `ParameterKind`.
+  ///
+  /// Names the offending parameter (dotted path for nested map fields).
+  InvalidParameter
+  {
+    /// Parameter name, e.g. `port` or `database.port`
+    parameter: String,
+    /// What is wrong with the value
+    reason: String,
+  },
 }
 
 impl core ::fmt ::Display for Error
@@ -52,6 +93,13 @@ impl core ::fmt ::Display for Error
         write!( f, "Missing mandatory parameters: {params:?}" ),
       Error ::Fs( err ) => write!( f, "File system error: {err}" ),
       Error ::InvalidTemplate( msg ) => write!( f, "Invalid template: {msg}" ),
+      Error ::Fetch( msg ) => write!( f, "Content fetch failed: {msg}" ),
+      Error ::ContentTooLarge { location, limit } =>
+        write!( f, "Content of {location} exceeds the size limit of {limit} bytes" ),
+      Error ::ChecksumMismatch { location, expected, actual } =>
+        write!( f, "Checksum mismatch for {location}: expected {expected}, got {actual}" ),
+      Error ::InvalidParameter { parameter, reason } =>
+        write!( f, "Invalid value for parameter '{parameter}': {reason}" ),
     }
   }
 }
@@ -9,6 +9,10 @@ use std ::path ::PathBuf;
 ///
 /// - `Rewrite`: Completely replace existing file content
 /// - `TomlExtend`: Smart merge with existing TOML file (preserves comments and order)
+/// - `JsonMerge`: Structural merge into an existing JSON document
+/// - `YamlMerge`: Structural merge into an existing YAML document
+/// - `MarkedRegions`: Replace only `genfile:begin` / `genfile:end` regions of an existing file
+/// - `CreateOnly`: Write the file only if it does not exist yet
 ///
 /// # Examples
 ///
@@ -20,6 +24,9 @@ use std ::path ::PathBuf;
 ///
 /// // For configuration files - merge intelligently
 /// let mode = WriteMode::TomlExtend;
+///
+/// // For scaffolds that must not clobber user files
+/// let mode = WriteMode::CreateOnly;
 /// ```
 #[ derive( Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize ) ]
 pub enum WriteMode
@@ -34,6 +41,31 @@ pub enum WriteMode
   /// Preserves comments, formatting, and key order while updating values.
   /// New keys are appended at appropriate locations. Only works with TOML files.
   TomlExtend,
+
+  /// Structural merge with existing JSON file.
+  ///
+  /// Objects merge key by key (existing order kept, new keys appended), arrays
+  /// get missing elements appended, other values are replaced. Output is
+  /// re-serialized, so formatting is normalized. See [`crate::merge::merge_json`].
+  JsonMerge,
+
+  /// Structural merge with existing YAML file.
+  ///
+  /// Same rules as `JsonMerge`. Comments in the existing file are not
+  /// preserved. See [`crate::merge::merge_yaml`].
+  YamlMerge,
+
+  /// Replace marked regions of existing file.
+  ///
+  /// Only lines between `genfile:begin <name>` and `genfile:end <name>` markers
+  /// are rewritten; user edits outside the regions are preserved.
+  /// See [`crate::merge::merge_regions`].
+  MarkedRegions,
+
+  /// Write only if the file does not exist.
+  ///
+  /// Existing files are left untouched and reported as skipped.
+  CreateOnly,
 }
 
 /// File descriptor for template materialization.
@@ -1,8 +1,62 @@
 /// File system abstractions for testability
-use std ::path ::Path;
+use std ::path :: { Path, PathBuf };
 use std ::collections ::HashMap;
+use std ::time ::SystemTime;
 use crate ::Error;
 
+/// Mode of files created without explicit permissions
+pub const DEFAULT_FILE_MODE: u32 = 0o644;
+
+/// Mode of directories created without explicit permissions
+pub const DEFAULT_DIR_MODE: u32 = 0o755;
+
+/// Kind of a file system entry
+#[ derive( Debug, Clone, Copy, PartialEq, Eq ) ]
+pub enum EntryKind
+{
+  /// Regular file
+  File,
+  /// Directory
+  Directory,
+  /// Symbolic link (only reported by `symlink_metadata`)
+  Symlink,
+}
+
+/// Metadata of a file system entry.
+///
+/// `mode` holds the Unix permission bits (`0o7777` mask) and is `None` where
+/// the file system has no notion of them; `modified` is `None` where the
+/// modification time is unavailable.
+#[ derive( Debug, Clone, PartialEq, Eq ) ]
+pub struct FsMetadata
+{
+  /// Kind of the entry
+  pub kind: EntryKind,
+  /// Size in bytes (file contents, link target length, or 0 for directories)
+  pub len: u64,
+  /// Unix permission bits
+  pub mode: Option< u32 >,
+  /// Last modification time
+  pub modified: Option< SystemTime >,
+}
+
+impl FsMetadata
+{
+  /// Whether the entry is a regular file
+  #[ must_use ]
+  pub fn is_file( &self ) -> bool
+  {
+    self.kind == EntryKind ::File
+  }
+
+  /// Whether the entry is a directory
+  #[ must_use ]
+  pub fn is_dir( &self ) -> bool
+  {
+    self.kind == EntryKind ::Directory
+  }
+}
+
 /// File system operations trait.
 ///
 /// Abstracts file I/O operations to enable both real filesystem usage and
@@ -14,6 +68,20 @@ use crate ::Error;
 /// - `write()`: Write string content to file
 /// - `exists()`: Check if file exists
 ///
+/// Binary contents, metadata (permissions, modification time), directories,
+/// removal and symbolic links have default implementations, so a minimal
+/// implementor only provides the three methods above:
+///
+/// - `read_bytes()` / `write_bytes()`: Raw contents, defaulting to UTF-8 `read()` / `write()`
+/// - `metadata()` / `symlink_metadata()`: Kind, size, mode bits and mtime
+/// - `set_permissions()`: Unix mode bits (default: ignored)
+/// - `create_dir_all()` / `read_dir()`: Directory creation and listing
+/// - `remove_file()` / `remove_dir_all()`: Removal
+/// - `symlink()` / `read_link()`: Symbolic links
+///
+/// Defaults that cannot be expressed through the three required methods fail
+/// with an `Unsupported` `Error::Fs`.
+///
 /// # Examples
 ///
 /// ```rust
@@ -68,14 +136,213 @@ pub trait FileSystem
   ///
   /// `true` if file exists, `false` otherwise
   fn exists( &self, path: &Path ) -> bool;
+
+  /// Reads file contents as raw bytes.
+  ///
+  /// # Errors
+  ///
+  /// Returns `Error::Fs` if file doesn't exist or can't be read
+  fn read_bytes( &self, path: &Path ) -> Result< Vec< u8 >, Error >
+  {
+    self.read( path ).map( String ::into_bytes )
+  }
+
+  /// Writes raw bytes to a file.
+  ///
+  /// The default implementation accepts UTF-8 content only and forwards it
+  /// to `write()`.
+  ///
+  /// # Errors
+  ///
+  /// Returns `Error::Fs` if file can't be written, or if the default
+  /// implementation is given non-UTF-8 content
+  fn write_bytes( &mut self, path: &Path, content: &[ u8 ] ) -> Result< (), Error >
+  {
+    match core ::str ::from_utf8( content )
+    {
+      Ok( text ) => self.write( path, text ),
+      Err( _ ) => Err( unsupported( "binary write", path ) ),
+    }
+  }
+
+  /// Returns metadata of the entry at `path`, following symbolic links.
+  ///
+  /// The default implementation reports every existing path as a file
+  /// without mode bits or modification time.
+  ///
+  /// # Errors
+  ///
+  /// Returns `Error::Fs` if the entry doesn't exist
+  fn metadata( &self, path: &Path ) -> Result< FsMetadata, Error >
+  {
+    if !self.exists( path )
+    {
+      return Err( not_found( path ) );
+    }
+    let len = self.read_bytes( path )?.len() as u64;
+    Ok( FsMetadata { kind: EntryKind ::File, len, mode: None, modified: None } )
+  }
+
+  /// Returns metadata of the entry at `path` without following a final symbolic link.
+  ///
+  /// # Errors
+  ///
+  /// Returns `Error::Fs` if the entry doesn't exist
+  fn symlink_metadata( &self, path: &Path ) -> Result< FsMetadata, Error >
+  {
+    self.metadata( path )
+  }
+
+  /// Sets the Unix permission bits of the entry at `path`.
+  ///
+  /// The default implementation ignores the request, for file systems
+  /// without permissions.
+  ///
+  /// # Errors
+  ///
+  /// Returns `Error::Fs` if the entry doesn't exist or can't be changed
+  fn set_permissions( &mut self, _path: &Path, _mode: u32 ) -> Result< (), Error >
+  {
+    Ok( () )
+  }
+
+  /// Creates a directory and all missing parents.
+  ///
+  /// The default implementation does nothing, for file systems whose
+  /// `write()` needs no directories.
+  ///
+  /// # Errors
+  ///
+  /// Returns `Error::Fs` if a directory can't be created
+  fn create_dir_all( &mut self, _path: &Path ) -> Result< (), Error >
+  {
+    Ok( () )
+  }
+
+  /// Lists the entries of a directory as full paths, sorted.
+  ///
+  /// # Errors
+  ///
+  /// Returns `Error::Fs` if `path` is not a readable directory, or if listing
+  /// is unsupported
+  fn read_dir( &self, path: &Path ) -> Result< Vec< PathBuf >, Error >
+  {
+    Err( unsupported( "directory listing", path ) )
+  }
+
+  /// Removes a file or symbolic link.
+  ///
+  /// # Errors
+  ///
+  /// Returns `Error::Fs` if the file doesn't exist, is a directory, or if
+  /// removal is unsupported
+  fn remove_file( &mut self, path: &Path ) -> Result< (), Error >
+  {
+    Err( unsupported( "removal", path ) )
+  }
+
+  /// Removes a directory with all its contents.
+  ///
+  /// # Errors
+  ///
+  /// Returns `Error::Fs` if the directory doesn't exist, or if removal is
+  /// unsupported
+  fn remove_dir_all( &mut self, path: &Path ) -> Result< (), Error >
+  {
+    Err( unsupported( "removal", path ) )
+  }
+
+  /// Creates a symbolic link at `link` pointing to `target`.
+  ///
+  /// # Errors
+  ///
+  /// Returns `Error::Fs` if the link can't be created, or if symbolic links
+  /// are unsupported
+  fn symlink( &mut self, _target: &Path, link: &Path ) -> Result< (), Error >
+  {
+    Err( unsupported( "symbolic links", link ) )
+  }
+
+  /// Returns the target of the symbolic link at `path`.
+  ///
+  /// # Errors
+  ///
+  /// Returns `Error::Fs` if `path` is not a symbolic link, or if symbolic
+  /// links are unsupported
+  fn read_link( &self, path: &Path ) -> Result< PathBuf, Error >
+  {
+    Err( unsupported( "symbolic links", path ) )
+  }
 }
 
+#[ allow( clippy ::std_instead_of_core ) ]
+fn fs_error( kind: std ::io ::ErrorKind, message: String ) -> Error
+{
+  Error ::Fs( std ::io ::Error ::new( kind, message ) )
+}
+
+fn not_found( path: &Path ) -> Error
+{
+  fs_error( std ::io ::ErrorKind ::NotFound, format!( "File not found: {}", path.display() ) )
+}
+
+fn unsupported( operation: &str, path: &Path ) -> Error
+{
+  fs_error( std ::io ::ErrorKind ::Unsupported, format!( "{operation} is not supported: {}", path.display() ) )
+}
+
+/// Contents of an in-memory entry
+#[ derive( Debug, Clone ) ]
+enum Node
+{
+  File( Vec< u8 > ),
+  Directory,
+  Symlink( PathBuf ),
+}
+
+/// In-memory entry with its metadata
+#[ derive( Debug, Clone ) ]
+struct Entry
+{
+  node: Node,
+  mode: u32,
+  modified: SystemTime,
+}
+
+impl Entry
+{
+  fn new( node: Node, mode: u32 ) -> Self
+  {
+    Self { node, mode, modified: SystemTime ::now() }
+  }
+
+  fn metadata( &self ) -> FsMetadata
+  {
+    let ( kind, len ) = match &self.node
+    {
+      Node ::File( bytes ) => ( EntryKind ::File, bytes.len() ),
+      Node ::Directory => ( EntryKind ::Directory, 0 ),
+      Node ::Symlink( target ) => ( EntryKind ::Symlink, target.as_os_str().len() ),
+    };
+    FsMetadata { kind, len: len as u64, mode: Some( self.mode ), modified: Some( self.modified ) }
+  }
+}
+
+/// Maximum number of symbolic links followed when resolving a path
+const MAX_SYMLINK_DEPTH: usize = 40;
+
 /// In-memory file system for testing.
 ///
 /// Stores files in a `HashMap` for fast, isolated testing without disk I/O.
 /// Useful for unit tests and integration tests that need predictable,
 /// reproducible file system state.
 ///
+/// Directories, symbolic links, mode bits and modification times are kept
+/// alongside file contents. Writing a file creates its missing parent
+/// directories, like `RealFileSystem`; new files get `DEFAULT_FILE_MODE` and
+/// new directories `DEFAULT_DIR_MODE`. Only a final symbolic link in a path
+/// is followed.
+///
 /// # Examples
 ///
 /// ```rust
@@ -92,8 +359,8 @@ pub trait FileSystem
 #[ derive( Debug, Default ) ]
 pub struct MemoryFileSystem
 {
-  /// In-memory storage of file paths to contents
-  files: HashMap< std ::path ::PathBuf, String >,
+  /// In-memory storage of paths to entries
+  entries: HashMap< PathBuf, Entry >,
 }
 
 impl MemoryFileSystem
@@ -111,47 +378,207 @@ impl MemoryFileSystem
   ///
   /// let fs = MemoryFileSystem::new();
   /// ```
-  #[must_use] 
+  #[must_use]
   pub fn new() -> Self
   {
     Self
     {
-      files: HashMap ::new(),
+      entries: HashMap ::new(),
+    }
+  }
+
+  /// Path with a final symbolic link followed, up to `MAX_SYMLINK_DEPTH` links
+  fn resolve( &self, path: &Path ) -> Result< PathBuf, Error >
+  {
+    let mut current = path.to_path_buf();
+    for _ in 0..MAX_SYMLINK_DEPTH
+    {
+      match self.entries.get( &current ).map( | entry | &entry.node )
+      {
+        Some( Node ::Symlink( target ) ) =>
+        {
+          current = match current.parent()
+          {
+            Some( parent ) if target.is_relative() => parent.join( target ),
+            _ => target.clone(),
+          };
+        }
+        _ => return Ok( current ),
+      }
+    }
+    Err( fs_error( std ::io ::ErrorKind ::InvalidInput, format!( "Too many levels of symbolic links: {}", path.display() ) ) )
+  }
+
+  /// Entry at `path` with a final symbolic link followed
+  fn entry( &self, path: &Path ) -> Result< &Entry, Error >
+  {
+    let resolved = self.resolve( path )?;
+    self.entries.get( &resolved ).ok_or_else( || not_found( path ) )
+  }
+
+  /// Creates `dir` and its missing ancestors as directories
+  fn create_dirs( &mut self, dir: &Path ) -> Result< (), Error >
+  {
+    let mut missing: Vec< &Path > = dir.ancestors().filter( | dir | !dir.as_os_str().is_empty() ).collect();
+    missing.reverse();
+    for dir in missing
+    {
+      match self.entries.get( dir ).map( | entry | &entry.node )
+      {
+        None => { self.entries.insert( dir.to_path_buf(), Entry ::new( Node ::Directory, DEFAULT_DIR_MODE ) ); }
+        Some( Node ::Directory | Node ::Symlink( _ ) ) => {}
+        Some( Node ::File( _ ) ) => return Err( fs_error
+        (
+          std ::io ::ErrorKind ::AlreadyExists,
+          format!( "Not a directory: {}", dir.display() ),
+        ) ),
+      }
+    }
+    Ok( () )
+  }
+
+  /// Creates the missing parent directories of `path`
+  fn create_parents( &mut self, path: &Path ) -> Result< (), Error >
+  {
+    match path.parent()
+    {
+      Some( parent ) => self.create_dirs( parent ),
+      None => Ok( () ),
     }
   }
+
+  /// Stores file `bytes` at `path`, keeping the mode of an existing file
+  fn insert_file( &mut self, path: &Path, bytes: Vec< u8 > ) -> Result< (), Error >
+  {
+    let resolved = self.resolve( path )?;
+    self.create_parents( &resolved )?;
+    let mode = match self.entries.get( &resolved )
+    {
+      Some( Entry { node: Node ::Directory, .. } ) => return Err( fs_error
+      (
+        std ::io ::ErrorKind ::AlreadyExists,
+        format!( "Is a directory: {}", path.display() ),
+      ) ),
+      Some( entry ) => entry.mode,
+      None => DEFAULT_FILE_MODE,
+    };
+    self.entries.insert( resolved, Entry ::new( Node ::File( bytes ), mode ) );
+    Ok( () )
+  }
 }
 
 impl FileSystem for MemoryFileSystem
 {
-  #[ allow( clippy ::std_instead_of_core ) ]
   fn read( &self, path: &Path ) -> Result< String, Error >
   {
-    self
-      .files
-      .get( path )
-      .cloned()
-      .ok_or_else( ||
-      {
-        Error ::Fs
-        (
-          std ::io ::Error ::new
-          (
-            std ::io ::ErrorKind ::NotFound,
-            format!( "File not found: {}", path.display() )
-          )
-        )
-      })
+    String ::from_utf8( self.read_bytes( path )? ).map_err( | _ | fs_error
+    (
+      std ::io ::ErrorKind ::InvalidData,
+      format!( "File is not valid UTF-8: {}", path.display() ),
+    ) )
   }
 
   fn write( &mut self, path: &Path, content: &str ) -> Result< (), Error >
   {
-    self.files.insert( path.to_path_buf(), content.to_string() );
-    Ok(())
+    self.insert_file( path, content.as_bytes().to_vec() )
   }
 
   fn exists( &self, path: &Path ) -> bool
   {
-    self.files.contains_key( path )
+    self.entry( path ).is_ok()
+  }
+
+  fn read_bytes( &self, path: &Path ) -> Result< Vec< u8 >, Error >
+  {
+    match &self.entry( path )?.node
+    {
+      Node ::File( bytes ) => Ok( bytes.clone() ),
+      _ => Err( fs_error( std ::io ::ErrorKind ::InvalidInput, format!( "Not a file: {}", path.display() ) ) ),
+    }
+  }
+
+  fn write_bytes( &mut self, path: &Path, content: &[ u8 ] ) -> Result< (), Error >
+  {
+    self.insert_file( path, content.to_vec() )
+  }
+
+  fn metadata( &self, path: &Path ) -> Result< FsMetadata, Error >
+  {
+    Ok( self.entry( path )?.metadata() )
+  }
+
+  fn symlink_metadata( &self, path: &Path ) -> Result< FsMetadata, Error >
+  {
+    self.entries.get( path ).map( Entry ::metadata ).ok_or_else( || not_found( path ) )
+  }
+
+  fn set_permissions( &mut self, path: &Path, mode: u32 ) -> Result< (), Error >
+  {
+    let resolved = self.resolve( path )?;
+    let entry = self.entries.get_mut( &resolved ).ok_or_else( || not_found( path ) )?;
+    entry.mode = mode & 0o7777;
+    Ok( () )
+  }
+
+  fn create_dir_all( &mut self, path: &Path ) -> Result< (), Error >
+  {
+    self.create_dirs( path )
+  }
+
+  fn read_dir( &self, path: &Path ) -> Result< Vec< PathBuf >, Error >
+  {
+    let resolved = self.resolve( path )?;
+    if !matches!( self.entry( path )?.node, Node ::Directory )
+    {
+      return Err( fs_error( std ::io ::ErrorKind ::InvalidInput, format!( "Not a directory: {}", path.display() ) ) );
+    }
+    let mut children: Vec< PathBuf > = self.entries.keys()
+      .filter( | child | child.parent() == Some( resolved.as_path() ) )
+      .map( | child | path.join( child.strip_prefix( &resolved ).unwrap_or( child ) ) )
+      .collect();
+    children.sort();
+    Ok( children )
+  }
+
+  fn remove_file( &mut self, path: &Path ) -> Result< (), Error >
+  {
+    match self.entries.get( path ).map( | entry | &entry.node )
+    {
+      Some( Node ::Directory ) => Err( fs_error( std ::io ::ErrorKind ::InvalidInput, format!( "Is a directory: {}", path.display() ) ) ),
+      Some( _ ) => { self.entries.remove( path ); Ok( () ) }
+      None => Err( not_found( path ) ),
+    }
+  }
+
+  fn remove_dir_all( &mut self, path: &Path ) -> Result< (), Error >
+  {
+    match self.entries.get( path ).map( | entry | &entry.node )
+    {
+      Some( Node ::Directory ) => { self.entries.retain( | entry, _ | !entry.starts_with( path ) ); Ok( () ) }
+      Some( _ ) => Err( fs_error( std ::io ::ErrorKind ::InvalidInput, format!( "Not a directory: {}", path.display() ) ) ),
+      None => Err( not_found( path ) ),
+    }
+  }
+
+  fn symlink( &mut self, target: &Path, link: &Path ) -> Result< (), Error >
+  {
+    if self.entries.contains_key( link )
+    {
+      return Err( fs_error( std ::io ::ErrorKind ::AlreadyExists, format!( "File exists: {}", link.display() ) ) );
+    }
+    self.create_parents( link )?;
+    self.entries.insert( link.to_path_buf(), Entry ::new( Node ::Symlink( target.to_path_buf() ), 0o777 ) );
+    Ok( () )
+  }
+
+  fn read_link( &self, path: &Path ) -> Result< PathBuf, Error >
+  {
+    match self.entries.get( path ).map( | entry | &entry.node )
+    {
+      Some( Node ::Symlink( target ) ) => Ok( target.clone() ),
+      Some( _ ) => Err( fs_error( std ::io ::ErrorKind ::InvalidInput, format!( "Not a symbolic link: {}", path.display() ) ) ),
+      None => Err( not_found( path ) ),
+    }
   }
 }
 
@@ -160,6 +587,10 @@ impl FileSystem for MemoryFileSystem
 /// Performs actual disk I/O operations. Use this for production code.
 /// For testing, prefer `MemoryFileSystem`.
 ///
+/// Mode bits are read and written on Unix only; elsewhere `metadata()`
+/// reports no mode, `set_permissions()` is ignored and `symlink()` is
+/// unsupported.
+///
 /// # Examples
 ///
 /// ```rust,no_run
@@ -189,11 +620,21 @@ impl RealFileSystem
   ///
   /// let fs = RealFileSystem::new();
   /// ```
-  #[must_use] 
+  #[must_use]
   pub fn new() -> Self
   {
     Self
   }
+
+  /// Creates the parent directory of `path` if it doesn't exist
+  fn create_parent( path: &Path ) -> Result< (), Error >
+  {
+    if let Some( parent ) = path.parent()
+    {
+      std ::fs ::create_dir_all( parent )?;
+    }
+    Ok( () )
+  }
 }
 
 impl Default for RealFileSystem
@@ -204,6 +645,37 @@ impl Default for RealFileSystem
   }
 }
 
+/// Converts standard metadata into `FsMetadata`
+fn real_metadata( metadata: &std ::fs ::Metadata ) -> FsMetadata
+{
+  let file_type = metadata.file_type();
+  let kind = if file_type.is_symlink()
+  {
+    EntryKind ::Symlink
+  }
+  else if file_type.is_dir()
+  {
+    EntryKind ::Directory
+  }
+  else
+  {
+    EntryKind ::File
+  };
+
+  #[ cfg( unix ) ]
+  let mode = { use std ::os ::unix ::fs ::PermissionsExt; Some( metadata.permissions().mode() & 0o7777 ) };
+  #[ cfg( not( unix ) ) ]
+  let mode = None;
+
+  FsMetadata
+  {
+    kind,
+    len: if kind == EntryKind ::Directory { 0 } else { metadata.len() },
+    mode,
+    modified: metadata.modified().ok(),
+  }
+}
+
 impl FileSystem for RealFileSystem
 {
   fn read( &self, path: &Path ) -> Result< String, Error >
@@ -214,11 +686,7 @@ impl FileSystem for RealFileSystem
   fn write( &mut self, path: &Path, content: &str ) -> Result< (), Error >
   {
     // Create parent directories if they don't exist
-    if let Some( parent ) = path.parent()
-    {
-      std ::fs ::create_dir_all( parent )?;
-    }
-
+    Self ::create_parent( path )?;
     std ::fs ::write( path, content ).map_err( Error ::from )
   }
 
@@ -226,4 +694,68 @@ impl FileSystem for RealFileSystem
   {
     path.exists()
   }
+
+  fn read_bytes( &self, path: &Path ) -> Result< Vec< u8 >, Error >
+  {
+    std ::fs ::read( path ).map_err( Error ::from )
+  }
+
+  fn write_bytes( &mut self, path: &Path, content: &[ u8 ] ) -> Result< (), Error >
+  {
+    Self ::create_parent( path )?;
+    std ::fs ::write( path, content ).map_err( Error ::from )
+  }
+
+  fn metadata( &self, path: &Path ) -> Result< FsMetadata, Error >
+  {
+    Ok( real_metadata( &std ::fs ::metadata( path )? ) )
+  }
+
+  fn symlink_metadata( &self, path: &Path ) -> Result< FsMetadata, Error >
+  {
+    Ok( real_metadata( &std ::fs ::symlink_metadata( path )? ) )
+  }
+
+  #[ cfg( unix ) ]
+  fn set_permissions( &mut self, path: &Path, mode: u32 ) -> Result< (), Error >
+  {
+    use std ::os ::unix ::fs ::PermissionsExt;
+    std ::fs ::set_permissions( path, std ::fs ::Permissions ::from_mode( mode & 0o7777 ) ).map_err( Error ::from )
+  }
+
+  fn create_dir_all( &mut self, path: &Path ) -> Result< (), Error >
+  {
+    std ::fs ::create_dir_all( path ).map_err( Error ::from )
+  }
+
+  fn read_dir( &self, path: &Path ) -> Result< Vec< PathBuf >, Error >
+  {
+    let mut children = std ::fs ::read_dir( path )?
+      .map( | entry | entry.map( | entry | entry.path() ) )
+      .collect::< Result< Vec< _ >, _ > >()?;
+    children.sort();
+    Ok( children )
+  }
+
+  fn remove_file( &mut self, path: &Path ) -> Result< (), Error >
+  {
+    std ::fs ::remove_file( path ).map_err( Error ::from )
+  }
+
+  fn remove_dir_all( &mut self, path: &Path ) -> Result< (), Error >
+  {
+    std ::fs ::remove_dir_all( path ).map_err( Error ::from )
+  }
+
+  #[ cfg( unix ) ]
+  fn symlink( &mut self, target: &Path, link: &Path ) -> Result< (), Error >
+  {
+    Self ::create_parent( link )?;
+    std ::os ::unix ::fs ::symlink( target, link ).map_err( Error ::from )
+  }
+
+  fn read_link( &self, path: &Path ) -> Result< PathBuf, Error >
+  {
+    std ::fs ::read_link( path ).map_err( Error ::from )
+  }
 }
@@ -44,12 +44,14 @@
 //! - [`FileSystem`] - Abstraction for file I/O operations
 //! - [`RealFileSystem`] - Production filesystem implementation
 //! - [`MemoryFileSystem`] - In-memory filesystem for testing
+//! - [`FsMetadata`] - Entry kind, size, Unix mode bits and modification time
 //!
 //! ## Content Sources
 //!
 //! - [`ContentSource`] - Inline, File, or URL references
 //! - [`ContentResolver`] - Custom content fetching logic
 //! - [`ContentStorage`] - Custom storage backends (S3, Azure, etc.)
+//! - [`RemoteContentResolver`] - http(s)/`file://`/git fetching with cache and offline mode (feature `remote_content`)
 //!
 //! # Module Organization
 //!
@@ -63,6 +65,7 @@
 //! - [`archive`] - Self-contained template archives
 //! - [`template`] - Low-level template processor
 //! - [`content_source`] - External content references
+//! - [`remote_content`] - Fetching resolver for http(s), `file://` and git URLs
 //! - [`security`] - Path traversal validation
 //! - [`error`] - Error types
 //!
@@ -134,10 +137,30 @@ pub mod renderer;
 #[ cfg( any( feature = "archive", feature = "template" ) ) ]
 pub mod file_descriptor;
 
+/// Write mode application for existing files.
+///
+/// Provides [`merge::merge_json`], [`merge::merge_yaml`] and
+/// [`merge::merge_regions`] used by [`WriteMode`] when a target file exists.
+///
+/// See also: [`archive::MaterializationReport::files_merged`].
+#[ cfg( any( feature = "archive", feature = "template" ) ) ]
+pub mod merge;
+
+/// Interactive prompting for parameter values.
+///
+/// Provides the [`Prompter`] trait with [`TerminalPrompter`] and
+/// [`ScriptedPrompter`], driven by [`Parameters::prompt_missing`] for every
+/// parameter where [`Values::needs_prompt`] holds. Answers are checked
+/// against [`ParameterKind`].
+#[ cfg( any( feature = "archive", feature = "template" ) ) ]
+pub mod prompt;
+
 /// File system abstractions for testability.
 ///
 /// Provides [`FileSystem`] trait with implementations for real disk I/O
 /// ([`RealFileSystem`]) and in-memory testing ([`MemoryFileSystem`]).
+/// Covers text and binary contents, [`FsMetadata`] with Unix mode bits,
+/// directories, removal and symbolic links.
 ///
 /// See also: [`Template`] for usage with custom filesystems.
 #[ cfg( feature = "filesystem" ) ]
@@ -172,11 +195,23 @@ pub mod archive;
 #[ cfg( feature = "external_content" ) ]
 pub mod content_source;
 
+/// Fetching resolver for URL content sources.
+///
+/// Provides [`RemoteContentResolver`], which resolves http(s), `file://` and
+/// `git+` URLs with an optional content-addressed cache and offline mode.
+///
+/// See also: [`content_source::ContentResolver::resolve_pinned`] for checksum pinning.
+#[ cfg( feature = "remote_content" ) ]
+pub mod remote_content;
+
 #[ cfg( any( feature = "archive", feature = "template" ) ) ]
 pub use value :: { TemplateValue, Value };
 
 #[ cfg( any( feature = "archive", feature = "template" ) ) ]
-pub use parameter :: { ParameterDescriptor, Parameters };
+pub use parameter :: { ParameterDescriptor, ParameterKind, Parameters };
+
+#[ cfg( any( feature = "archive", feature = "template" ) ) ]
+pub use prompt :: { Prompter, PromptRequest, ScriptedPrompter, TerminalPrompter };
 
 #[ cfg( any( feature = "archive", feature = "template" ) ) ]
 pub use values ::Values;
@@ -194,7 +229,7 @@ pub use renderer :: { TemplateRenderer, HandlebarsRenderer };
 pub use file_descriptor :: { FileDescriptor, WriteMode };
 
 #[ cfg( feature = "filesystem" ) ]
-pub use filesystem :: { FileSystem, MemoryFileSystem, RealFileSystem };
+pub use filesystem :: { FileSystem, MemoryFileSystem, RealFileSystem, FsMetadata, EntryKind, DEFAULT_FILE_MODE, DEFAULT_DIR_MODE };
 
 #[ cfg( feature = "template" ) ]
 pub use template ::Template;
@@ -208,6 +243,12 @@ pub use archive ::
   FileMetadata,
   ArchiveMetadata,
   MaterializationReport,
+  FileAction,
+  PlannedFile,
+  ArchiveFormat,
+  TemplatizeReport,
+  LOOP_ITEM,
+  LOOP_INDEX,
 };
 
 #[ cfg( feature = "external_content" ) ]
@@ -222,14 +263,21 @@ pub use content_source ::
   ContentStorage,
   DefaultContentResolver,
   DefaultContentStorage,
+  content_checksum,
 };
 
+#[ cfg( feature = "remote_content" ) ]
+pub use remote_content ::RemoteContentResolver;
+
 /// Prelude for convenient imports.
 #[ cfg( any( feature = "archive", feature = "template", feature = "renderer", feature = "filesystem" ) ) ]
 pub mod prelude
 {
   #[ cfg( any( feature = "archive", feature = "template" ) ) ]
-  pub use super ::{ TemplateValue, Value, ParameterDescriptor, Parameters, Values, validate_path, FileDescriptor, WriteMode };
+  pub use super ::{ TemplateValue, Value, ParameterDescriptor, ParameterKind, Parameters, Values, validate_path, FileDescriptor, WriteMode };
+
+  #[ cfg( any( feature = "archive", feature = "template" ) ) ]
+  pub use super ::{ Prompter, PromptRequest, ScriptedPrompter, TerminalPrompter };
 
   #[ cfg( any( feature = "archive", feature = "template", feature = "renderer", feature = "filesystem" ) ) ]
   pub use super ::Error;
@@ -238,14 +286,17 @@ pub mod prelude
   pub use super ::{ TemplateRenderer, HandlebarsRenderer };
 
   #[ cfg( feature = "filesystem" ) ]
-  pub use super ::{ FileSystem, MemoryFileSystem, RealFileSystem };
+  pub use super ::{ FileSystem, MemoryFileSystem, RealFileSystem, FsMetadata, EntryKind };
 
   #[ cfg( feature = "template" ) ]
   pub use super ::Template;
 
   #[ cfg( feature = "archive" ) ]
-  pub use super ::{ TemplateArchive, TemplateFile, FileContent, FileMetadata, ArchiveMetadata, MaterializationReport };
+  pub use super ::{ TemplateArchive, TemplateFile, FileContent, FileMetadata, ArchiveMetadata, MaterializationReport, FileAction, PlannedFile, ArchiveFormat, TemplatizeReport };
 
   #[ cfg( feature = "external_content" ) ]
-  pub use super ::{ ContentSource, IntoContentSource, FileRef, UrlRef, InlineContent, ContentResolver, ContentStorage, DefaultContentResolver, DefaultContentStorage };
+  pub use super ::{ ContentSource, IntoContentSource, FileRef, UrlRef, InlineContent, ContentResolver, ContentStorage, DefaultContentResolver, DefaultContentStorage, content_checksum };
+
+  #[ cfg( feature = "remote_content" ) ]
+  pub use super ::RemoteContentResolver;
 }
@@ -0,0 +1,440 @@
+/// Write mode application: structural merges and marked regions
+///
+/// Functions here combine freshly rendered output with the current content of
+/// a target file, as selected by `WriteMode`:
+///
+/// - [`merge_json`] / [`merge_yaml`] - structural merge of documents
+/// - [`merge_regions`] - replace `genfile:begin <name>` / `genfile:end <name>` regions
+///
+/// Structural merges keep the existing key order and append new keys, so a
+/// patched `package.json` diffs cleanly. Comments and formatting of merged
+/// JSON/YAML files are not preserved; use `MarkedRegions` where they matter.
+use std::path::Path;
+use crate::{ Error, WriteMode };
+
+/// What to do with one target file under its `WriteMode`.
+#[ derive( Debug, Clone, PartialEq ) ]
+pub( crate ) enum Planned
+{
+  /// Target does not exist; write the rendered content
+  Create,
+  /// Target exists; overwrite it with the rendered content
+  Update,
+  /// Target exists; write this merge of its content and the rendered content
+  Merge( String ),
+  /// Target exists and the mode leaves it untouched
+  Skip,
+}
+
+/// Decide what to do with `path` under `mode`.
+///
+/// `read` loads the current content and is only called when `exists` is true
+/// and the mode merges.
+///
+/// # Errors
+///
+/// Returns `read`'s error, or `Error::InvalidTemplate` prefixed with `path`
+/// if a merge cannot parse either side.
+pub( crate ) fn plan_write< F >( mode: &WriteMode, path: &Path, exists: bool, read: F, rendered: &str ) -> Result< Planned, Error >
+where
+  F: FnOnce() -> Result< String, Error >,
+{
+  if !exists
+  {
+    return Ok( Planned::Create );
+  }
+  let merged = match mode
+  {
+    WriteMode::CreateOnly => return Ok( Planned::Skip ),
+    // Workaround(issue-005): TomlExtend falls back to full rewrite; merging not implemented.
+    // Root cause: TOML-aware merging requires parsing both files; not yet integrated with genfile_core.
+    // Pitfall: Existing TOML keys in the destination file are silently overwritten (FR12, FR19 gap).
+    WriteMode::Rewrite | WriteMode::TomlExtend => return Ok( Planned::Update ),
+    WriteMode::JsonMerge => merge_json( &read()?, rendered ),
+    WriteMode::YamlMerge => merge_yaml( &read()?, rendered ),
+    WriteMode::MarkedRegions => merge_regions( &read()?, rendered ),
+  };
+  merged.map( Planned::Merge ).map_err( | err | match err
+  {
+    Error::InvalidTemplate( msg ) => Error::InvalidTemplate( format!( "{}: {msg}", path.display() ) ),
+    other => other,
+  })
+}
+
+/// Merge a rendered JSON document into an existing one.
+///
+/// Objects merge key by key: existing keys keep their position, new keys are
+/// appended. Arrays get the rendered elements not already present appended.
+/// Any other conflict takes the rendered value. Output is pretty-printed with
+/// two-space indentation; a trailing newline is kept if the existing file had one.
+///
+/// # Examples
+///
+/// ```rust
+/// use genfile_core::merge::merge_json;
+///
+/// let existing = "{\"name\":\"app\",\"scripts\":{\"start\":\"node .\"}}";
+/// let rendered = "{\"scripts\":{\"lint\":\"eslint .\"}}";
+///
+/// let merged = merge_json( existing, rendered ).unwrap();
+/// assert_eq!( merged, "{\n  \"name\": \"app\",\n  \"scripts\": {\n    \"start\": \"node .\",\n    \"lint\": \"eslint .\"\n  }\n}" );
+/// ```
+///
+/// # Errors
+///
+/// Returns `Error::InvalidTemplate` if either side is not valid JSON.
+#[ cfg( feature = "json" ) ]
+pub fn merge_json( existing: &str, rendered: &str ) -> Result< String, Error >
+{
+  if existing.trim().is_empty()
+  {
+    return Ok( rendered.to_string() );
+  }
+  let mut base: Tree = serde_json::from_str( existing )
+    .map_err( | err | Error::InvalidTemplate( format!( "existing file is not valid JSON: {err}" ) ) )?;
+  let patch: Tree = serde_json::from_str( rendered )
+    .map_err( | err | Error::InvalidTemplate( format!( "rendered template is not valid JSON: {err}" ) ) )?;
+  base.merge( patch );
+  let mut out = serde_json::to_string_pretty( &base )
+    .map_err( | err | Error::InvalidTemplate( format!( "cannot serialize merged JSON: {err}" ) ) )?;
+  if existing.ends_with( '\n' )
+  {
+    out.push( '\n' );
+  }
+  Ok( out )
+}
+
+/// Merge JSON; unavailable without feature `json`.
+///
+/// # Errors
+///
+/// Always returns `Error::InvalidTemplate`.
+#[ cfg( not( feature = "json" ) ) ]
+pub fn merge_json( _existing: &str, _rendered: &str ) -> Result< String, Error >
+{
+  Err( Error::InvalidTemplate( "WriteMode::JsonMerge requires feature `json`".into() ) )
+}
+
+/// Merge a rendered YAML document into an existing one.
+///
+/// Same rules as [`merge_json`]: mappings merge key by key keeping existing
+/// order, sequences get new elements appended, other conflicts take the
+/// rendered value.
+///
+/// # Examples
+///
+/// ```rust
+/// use genfile_core::merge::merge_yaml;
+///
+/// let existing = "name: ci\non: push\njobs:\n  test:\n    runs-on: ubuntu-latest\n";
+/// let rendered = "jobs:\n  lint:\n    runs-on: ubuntu-latest\n";
+///
+/// let merged = merge_yaml( existing, rendered ).unwrap();
+/// assert_eq!( merged, "name: ci\non: push\njobs:\n  test:\n    runs-on: ubuntu-latest\n  lint:\n    runs-on: ubuntu-latest\n" );
+/// ```
+///
+/// # Errors
+///
+/// Returns `Error::InvalidTemplate` if either side is not a valid YAML document.
+#[ cfg( feature = "yaml" ) ]
+pub fn merge_yaml( existing: &str, rendered: &str ) -> Result< String, Error >
+{
+  if existing.trim().is_empty()
+  {
+    return Ok( rendered.to_string() );
+  }
+  let mut base: Tree = serde_yaml::from_str( existing )
+    .map_err( | err | Error::InvalidTemplate( format!( "existing file is not valid YAML: {err}" ) ) )?;
+  let patch: Tree = serde_yaml::from_str( rendered )
+    .map_err( | err | Error::InvalidTemplate( format!( "rendered template is not valid YAML: {err}" ) ) )?;
+  base.merge( patch );
+  serde_yaml::to_string( &base )
+    .map_err( | err | Error::InvalidTemplate( format!( "cannot serialize merged YAML: {err}" ) ) )
+}
+
+/// Merge YAML; unavailable without feature `yaml`.
+///
+/// # Errors
+///
+/// Always returns `Error::InvalidTemplate`.
+#[ cfg( not( feature = "yaml" ) ) ]
+pub fn merge_yaml( _existing: &str, _rendered: &str ) -> Result< String, Error >
+{
+  Err( Error::InvalidTemplate( "WriteMode::YamlMerge requires feature `yaml`".into() ) )
+}
+
+/// Replace marked regions of an existing file with those of the rendered template.
+///
+/// A region starts at a line containing `genfile:begin <name>` and ends at a
+/// line containing `genfile:end <name>`, behind any comment leader
+/// (`// genfile:begin imports`, `# genfile:begin steps`,
+/// `<!-- genfile:begin nav -->`). For every region of the rendered template,
+/// the lines between the markers of the same-named region in `existing` are
+/// replaced; marker lines and everything outside regions keep the user's
+/// text. Regions missing from `existing` are appended with their markers.
+///
+/// # Examples
+///
+/// ```rust
+/// use genfile_core::merge::merge_regions;
+///
+/// let existing = "fn user() {}\n// genfile:begin consts\nconst A: u8 = 1;\n// genfile:end consts\n";
+/// let rendered = "// genfile:begin consts\nconst A: u8 = 2;\nconst B: u8 = 3;\n// genfile:end consts\n";
+///
+/// let merged = merge_regions( existing, rendered ).unwrap();
+/// assert_eq!( merged, "fn user() {}\n// genfile:begin consts\nconst A: u8 = 2;\nconst B: u8 = 3;\n// genfile:end consts\n" );
+/// ```
+///
+/// # Errors
+///
+/// Returns `Error::InvalidTemplate` if the template has no regions, or either
+/// side has unbalanced, nested or duplicate markers.
+pub fn merge_regions( existing: &str, rendered: &str ) -> Result< String, Error >
+{
+  let template_lines: Vec< &str > = rendered.split_inclusive( '\n' ).collect();
+  let template_regions = find_regions( &template_lines )
+    .map_err( | msg | Error::InvalidTemplate( format!( "rendered template: {msg}" ) ) )?;
+  if template_regions.is_empty()
+  {
+    return Err( Error::InvalidTemplate( "rendered template has no `genfile:begin <name>` regions".into() ) );
+  }
+  let lines: Vec< &str > = existing.split_inclusive( '\n' ).collect();
+  let regions = find_regions( &lines )
+    .map_err( | msg | Error::InvalidTemplate( format!( "existing file: {msg}" ) ) )?;
+
+  let body = | region: &Region< '_ > | template_lines[ region.begin + 1..region.end ].concat();
+  let mut out = String::with_capacity( existing.len() + rendered.len() );
+  let mut line = 0;
+  for region in &regions
+  {
+    let Some( replacement ) = template_regions.iter().find( | r | r.name == region.name ) else
+    {
+      continue;
+    };
+    out.push_str( &lines[ line..=region.begin ].concat() );
+    out.push_str( &body( replacement ) );
+    line = region.end;
+  }
+  out.push_str( &lines[ line.. ].concat() );
+
+  for region in template_regions.iter().filter( | r | !regions.iter().any( | e | e.name == r.name ) )
+  {
+    if !out.is_empty() && !out.ends_with( '\n' )
+    {
+      out.push( '\n' );
+    }
+    out.push_str( &template_lines[ region.begin..=region.end ].concat() );
+    if !out.ends_with( '\n' )
+    {
+      out.push( '\n' );
+    }
+  }
+  Ok( out )
+}
+
+/// One `genfile:begin` / `genfile:end` region: marker line indices.
+#[ derive( Debug ) ]
+struct Region< 'a >
+{
+  name: &'a str,
+  begin: usize,
+  end: usize,
+}
+
+/// Marker on a line: `( is_begin, name )`.
+fn marker( line: &str ) -> Option< ( bool, &str ) >
+{
+  let mut words = line.split_whitespace();
+  while let Some( word ) = words.next()
+  {
+    let is_begin = match word.trim_start_matches( | c: char | !c.is_alphanumeric() )
+    {
+      "genfile:begin" => true,
+      "genfile:end" => false,
+      _ => continue,
+    };
+    return words.next().map( | name | ( is_begin, name ) );
+  }
+  None
+}
+
+/// Regions of `lines`, rejecting unbalanced, nested or duplicate markers.
+fn find_regions< 'a >( lines: &[ &'a str ] ) -> Result< Vec< Region< 'a > >, String >
+{
+  let mut regions: Vec< Region< 'a > > = Vec::new();
+  let mut open: Option< ( &'a str, usize ) > = None;
+  for ( index, line ) in lines.iter().enumerate()
+  {
+    match ( marker( line ), open )
+    {
+      ( Some( ( true, name ) ), None ) =>
+      {
+        if regions.iter().any( | r | r.name == name )
+        {
+          return Err( format!( "duplicate region `{name}` on line {}", index + 1 ) );
+        }
+        open = Some( ( name, index ) );
+      }
+      ( Some( ( true, name ) ), Some( ( outer, _ ) ) ) =>
+        return Err( format!( "region `{name}` on line {} is nested in `{outer}`", index + 1 ) ),
+      ( Some( ( false, name ) ), Some( ( open_name, begin ) ) ) if name == open_name =>
+      {
+        regions.push( Region { name, begin, end: index } );
+        open = None;
+      }
+      ( Some( ( false, name ) ), _ ) =>
+        return Err( format!( "`genfile:end {name}` on line {} has no matching begin", index + 1 ) ),
+      ( None, _ ) => {}
+    }
+  }
+  match open
+  {
+    Some( ( name, begin ) ) => Err( format!( "region `{name}` opened on line {} is never closed", begin + 1 ) ),
+    None => Ok( regions ),
+  }
+}
+
+/// Order-preserving document tree shared by JSON and YAML merges.
+///
+/// `serde_json::Value` sorts object keys unless `preserve_order` is enabled
+/// workspace-wide, so merges deserialize into this instead.
+#[ cfg( any( feature = "json", feature = "yaml" ) ) ]
+#[ derive( Debug, Clone, PartialEq ) ]
+enum Tree
+{
+  Null,
+  Bool( bool ),
+  Int( i64 ),
+  UInt( u64 ),
+  Float( f64 ),
+  String( String ),
+  Seq( Vec< Tree > ),
+  Map( Vec< ( Tree, Tree ) > ),
+}
+
+#[ cfg( any( feature = "json", feature = "yaml" ) ) ]
+impl Tree
+{
+  /// Merge `patch` into `self` (see [`merge_json`]).
+  fn merge( &mut self, patch: Tree )
+  {
+    match ( self, patch )
+    {
+      ( Tree::Map( entries ), Tree::Map( patch ) ) =>
+      {
+        for ( key, value ) in patch
+        {
+          match entries.iter_mut().find( | ( k, _ ) | *k == key )
+          {
+            Some( ( _, existing ) ) => existing.merge( value ),
+            None => entries.push( ( key, value ) ),
+          }
+        }
+      }
+      ( Tree::Seq( items ), Tree::Seq( patch ) ) =>
+      {
+        for item in patch
+        {
+          if !items.contains( &item )
+          {
+            items.push( item );
+          }
+        }
+      }
+      ( this, patch ) => *this = patch,
+    }
+  }
+}
+
+#[ cfg( any( feature = "json", feature = "yaml" ) ) ]
+impl serde::Serialize for Tree
+{
+  fn serialize< S: serde::Serializer >( &self, serializer: S ) -> Result< S::Ok, S::Error >
+  {
+    use serde::ser::{ SerializeMap, SerializeSeq };
+    match self
+    {
+      Tree::Null => serializer.serialize_unit(),
+      Tree::Bool( value ) => serializer.serialize_bool( *value ),
+      Tree::Int( value ) => serializer.serialize_i64( *value ),
+      Tree::UInt( value ) => serializer.serialize_u64( *value ),
+      Tree::Float( value ) => serializer.serialize_f64( *value ),
+      Tree::String( value ) => serializer.serialize_str( value ),
+      Tree::Seq( items ) =>
+      {
+        let mut seq = serializer.serialize_seq( Some( items.len() ) )?;
+        for item in items
+        {
+          seq.serialize_element( item )?;
+        }
+        seq.end()
+      }
+      Tree::Map( entries ) =>
+      {
+        let mut map = serializer.serialize_map( Some( entries.len() ) )?;
+        for ( key, value ) in entries
+        {
+          map.serialize_entry( key, value )?;
+        }
+        map.end()
+      }
+    }
+  }
+}
+
+#[ cfg( any( feature = "json", feature = "yaml" ) ) ]
+impl< 'de > serde::Deserialize< 'de > for Tree
+{
+  fn deserialize< D: serde::Deserializer< 'de > >( deserializer: D ) -> Result< Self, D::Error >
+  {
+    deserializer.deserialize_any( TreeVisitor )
+  }
+}
+
+#[ cfg( any( feature = "json", feature = "yaml" ) ) ]
+struct TreeVisitor;
+
+#[ cfg( any( feature = "json", feature = "yaml" ) ) ]
+impl< 'de > serde::de::Visitor< 'de > for TreeVisitor
+{
+  type Value = Tree;
+
+  fn expecting( &self, f: &mut core::fmt::Formatter< '_ > ) -> core::fmt::Result
+  {
+    f.write_str( "a JSON or YAML value" )
+  }
+
+  fn visit_unit< E >( self ) -> Result< Tree, E > { Ok( Tree::Null ) }
+  fn visit_none< E >( self ) -> Result< Tree, E > { Ok( Tree::Null ) }
+  fn visit_bool< E >( self, value: bool ) -> Result< Tree, E > { Ok( Tree::Bool( value ) ) }
+  fn visit_i64< E >( self, value: i64 ) -> Result< Tree, E > { Ok( Tree::Int( value ) ) }
+  fn visit_u64< E >( self, value: u64 ) -> Result< Tree, E > { Ok( Tree::UInt( value ) ) }
+  fn visit_f64< E >( self, value: f64 ) -> Result< Tree, E > { Ok( Tree::Float( value ) ) }
+  fn visit_str< E >( self, value: &str ) -> Result< Tree, E > { Ok( Tree::String( value.to_string() ) ) }
+  fn visit_string< E >( self, value: String ) -> Result< Tree, E > { Ok( Tree::String( value ) ) }
+
+  fn visit_some< D: serde::Deserializer< 'de > >( self, deserializer: D ) -> Result< Tree, D::Error >
+  {
+    serde::Deserialize::deserialize( deserializer )
+  }
+
+  fn visit_seq< A: serde::de::SeqAccess< 'de > >( self, mut seq: A ) -> Result< Tree, A::Error >
+  {
+    let mut items = Vec::new();
+    while let Some( item ) = seq.next_element()?
+    {
+      items.push( item );
+    }
+    Ok( Tree::Seq( items ) )
+  }
+
+  fn visit_map< A: serde::de::MapAccess< 'de > >( self, mut map: A ) -> Result< Tree, A::Error >
+  {
+    let mut entries = Vec::new();
+    while let Some( entry ) = map.next_entry()?
+    {
+      entries.push( entry );
+    }
+    Ok( Tree::Map( entries ) )
+  }
+}
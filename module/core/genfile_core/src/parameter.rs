@@ -1,8 +1,11 @@
 /// Parameter management for templates
+use crate :: { Error, Value };
+
 /// Parameter descriptor defining a single template parameter.
 ///
 /// Stores metadata about a parameter including its name, whether it's mandatory,
-/// optional default value, and description.
+/// optional default value, description, and optional typed schema (`kind`).
+/// Descriptors without a `kind` accept any value.
 ///
 /// # Examples
 ///
@@ -15,11 +18,12 @@
 ///   is_mandatory: true,
 ///   default_value: None,
 ///   description: Some( "Name of the project".into() ),
+///   kind: None,
 /// };
 /// assert_eq!( param.parameter, "project_name" );
 /// assert!( param.is_mandatory );
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Default)]
 #[cfg_attr(any(feature = "json", feature = "yaml"), derive(serde::Serialize, serde::Deserialize))]
 pub struct ParameterDescriptor
 {
@@ -31,6 +35,368 @@ pub struct ParameterDescriptor
   pub default_value: Option< String >,
   /// Optional description
   pub description: Option< String >,
+  /// Optional value type and constraints; `None` accepts any value
+  #[cfg_attr(any(feature = "json", feature = "yaml"), serde(default, skip_serializing_if = "Option::is_none"))]
+  pub kind: Option< ParameterKind >,
+}
+
+/// Type and constraints of a parameter value.
+///
+/// Values are checked by [`ParameterDescriptor::validate`]. String forms are
+/// accepted wherever they parse to the kind (`"8080"` for an integer), since
+/// values set from a CLI arrive as text.
+///
+/// # Examples
+///
+/// ```rust
+/// use genfile_core::{ ParameterDescriptor, ParameterKind, Value };
+///
+/// let port = ParameterDescriptor
+/// {
+///   parameter: "port".into(),
+///   is_mandatory: true,
+///   default_value: Some( "8080".into() ),
+///   description: None,
+///   kind: Some( ParameterKind::Integer { min: Some( 1 ), max: Some( 65535 ) } ),
+/// };
+///
+/// assert!( port.validate( &Value::Number( 443 ) ).is_ok() );
+/// let err = port.validate( &Value::Number( 70000 ) ).unwrap_err();
+/// assert_eq!( err.to_string(), "Invalid value for parameter 'port': 70000 is greater than maximum 65535" );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(any(feature = "json", feature = "yaml"), derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(feature = "json", feature = "yaml"), serde(tag = "type", rename_all = "snake_case"))]
+pub enum ParameterKind
+{
+  /// Text, optionally required to match a regular expression (whole value)
+  String
+  {
+    /// Regular expression the value must match
+    #[cfg_attr(any(feature = "json", feature = "yaml"), serde(default, skip_serializing_if = "Option::is_none"))]
+    pattern: Option< String >,
+  },
+  /// Whole number within optional inclusive bounds
+  Integer
+  {
+    /// Smallest allowed value
+    #[cfg_attr(any(feature = "json", feature = "yaml"), serde(default, skip_serializing_if = "Option::is_none"))]
+    min: Option< i64 >,
+    /// Largest allowed value
+    #[cfg_attr(any(feature = "json", feature = "yaml"), serde(default, skip_serializing_if = "Option::is_none"))]
+    max: Option< i64 >,
+  },
+  /// `true` or `false` (also `yes`/`no`, `on`/`off`, `1`/`0`)
+  Bool,
+  /// One of a fixed set of strings
+  Choice
+  {
+    /// Allowed values
+    options: Vec< String >,
+  },
+  /// List of strings (comma-separated when given as text)
+  List,
+  /// Filesystem path, optionally required to exist (relative to the working directory)
+  Path
+  {
+    /// Whether the path must exist when validated
+    #[cfg_attr(any(feature = "json", feature = "yaml"), serde(default))]
+    must_exist: bool,
+  },
+  /// Nested map whose fields are described by their own descriptors
+  Map
+  {
+    /// Field descriptors; field names are addressed as `parent.field`
+    fields: Vec< ParameterDescriptor >,
+  },
+}
+
+impl ParameterDescriptor
+{
+  /// Create an optional, untyped parameter without default or description.
+  ///
+  /// ```rust
+  /// use genfile_core::{ ParameterDescriptor, ParameterKind };
+  ///
+  /// let port = ParameterDescriptor::new( "port" )
+  ///   .mandatory( true )
+  ///   .with_default( "8080" )
+  ///   .with_kind( ParameterKind::Integer { min: Some( 1 ), max: None } );
+  /// assert_eq!( port.default_value.as_deref(), Some( "8080" ) );
+  /// ```
+  #[must_use]
+  pub fn new( parameter: impl Into< String > ) -> Self
+  {
+    Self
+    {
+      parameter: parameter.into(),
+      ..Self::default()
+    }
+  }
+
+  /// Set whether the parameter must be given a value.
+  #[must_use]
+  pub fn mandatory( mut self, is_mandatory: bool ) -> Self
+  {
+    self.is_mandatory = is_mandatory;
+    self
+  }
+
+  /// Set the default value.
+  #[must_use]
+  pub fn with_default( mut self, value: impl Into< String > ) -> Self
+  {
+    self.default_value = Some( value.into() );
+    self
+  }
+
+  /// Set the description.
+  #[must_use]
+  pub fn with_description( mut self, description: impl Into< String > ) -> Self
+  {
+    self.description = Some( description.into() );
+    self
+  }
+
+  /// Set the value type and constraints.
+  #[must_use]
+  pub fn with_kind( mut self, kind: ParameterKind ) -> Self
+  {
+    self.kind = Some( kind );
+    self
+  }
+
+  /// Check `value` against this descriptor's `kind`.
+  ///
+  /// Map fields are checked recursively; mandatory fields must be present.
+  ///
+  /// # Errors
+  ///
+  /// Returns `Error::InvalidParameter` naming this parameter (or the dotted
+  /// path of the offending map field).
+  pub fn validate( &self, value: &Value ) -> Result< (), Error >
+  {
+    self.validate_at( &self.parameter, value )
+  }
+
+  /// Parse a text answer into a value of this descriptor's `kind`, then validate it.
+  ///
+  /// Untyped and string parameters yield `Value::String`, integers
+  /// `Value::Number`, booleans `Value::Bool`, lists `Value::List` split on
+  /// commas, and maps `Value::Map` from a JSON object.
+  ///
+  /// # Errors
+  ///
+  /// Returns `Error::InvalidParameter` if `input` does not parse or validate.
+  pub fn parse_value( &self, input: &str ) -> Result< Value, Error >
+  {
+    let invalid = | reason: String | Error::InvalidParameter { parameter: self.parameter.clone(), reason };
+    let input = input.trim();
+    let value = match &self.kind
+    {
+      None | Some( ParameterKind::String { .. } | ParameterKind::Choice { .. } | ParameterKind::Path { .. } ) =>
+        Value::String( input.to_string() ),
+      Some( ParameterKind::Integer { .. } ) => Value::Number( parse_integer( input ).map_err( invalid )? ),
+      Some( ParameterKind::Bool ) => Value::Bool( parse_bool( input ).map_err( invalid )? ),
+      Some( ParameterKind::List ) => Value::List( split_list( input ) ),
+      Some( ParameterKind::Map { .. } ) => parse_map( input ).map_err( invalid )?,
+    };
+    self.validate( &value )?;
+    Ok( value )
+  }
+
+  /// Default value parsed according to `kind`, if a default is declared.
+  ///
+  /// # Errors
+  ///
+  /// Returns `Error::InvalidParameter` if the declared default is itself invalid.
+  pub fn typed_default( &self ) -> Result< Option< Value >, Error >
+  {
+    self.default_value.as_deref().map( | text | self.parse_value( text ) ).transpose()
+  }
+
+  fn validate_at( &self, name: &str, value: &Value ) -> Result< (), Error >
+  {
+    let Some( kind ) = &self.kind else { return Ok( () ) };
+    kind.check( name, value ).map_err( | ( parameter, reason ) | Error::InvalidParameter { parameter, reason } )
+  }
+}
+
+impl ParameterKind
+{
+  /// Check `value` for parameter `name`; `Err( ( parameter, reason ) )` on violation.
+  fn check( &self, name: &str, value: &Value ) -> Result< (), ( String, String ) >
+  {
+    let fail = | reason: String | Err( ( name.to_string(), reason ) );
+    match ( self, value )
+    {
+      ( Self::String { pattern }, Value::String( text ) ) => match pattern
+      {
+        Some( pattern ) => match matches_pattern( pattern, text )
+        {
+          Ok( true ) => Ok( () ),
+          Ok( false ) => fail( format!( "'{text}' does not match pattern '{pattern}'" ) ),
+          Err( reason ) => fail( reason ),
+        },
+        None => Ok( () ),
+      },
+      ( Self::Integer { min, max }, Value::Number( _ ) | Value::String( _ ) ) =>
+      {
+        let number = match value
+        {
+          Value::Number( number ) => *number,
+          _ => match parse_integer( &value_text( value ) ) { Ok( number ) => number, Err( reason ) => return fail( reason ) },
+        };
+        match ( min, max )
+        {
+          ( Some( min ), _ ) if number < *min => fail( format!( "{number} is less than minimum {min}" ) ),
+          ( _, Some( max ) ) if number > *max => fail( format!( "{number} is greater than maximum {max}" ) ),
+          _ => Ok( () ),
+        }
+      }
+      ( Self::Bool, Value::Bool( _ ) ) | ( Self::List, Value::List( _ ) | Value::String( _ ) ) => Ok( () ),
+      ( Self::Bool, Value::String( text ) ) => parse_bool( text ).map( drop ).or_else( fail ),
+      ( Self::Choice { options }, Value::String( text ) ) =>
+      {
+        if options.iter().any( | option | option == text )
+        {
+          Ok( () )
+        }
+        else
+        {
+          fail( format!( "'{text}' is not one of {}", options.join( ", " ) ) )
+        }
+      }
+      ( Self::Path { must_exist }, Value::String( text ) ) =>
+      {
+        if text.is_empty()
+        {
+          fail( "path is empty".into() )
+        }
+        else if *must_exist && !std::path::Path::new( text ).exists()
+        {
+          fail( format!( "path '{text}' does not exist" ) )
+        }
+        else
+        {
+          Ok( () )
+        }
+      }
+      ( Self::Map { fields }, Value::Map( entries ) ) =>
+      {
+        if let Some( unknown ) = entries.keys().find( | key | !fields.iter().any( | field | &field.parameter == *key ) )
+        {
+          return Err( ( format!( "{name}.{unknown}" ), "unknown field".into() ) );
+        }
+        for field in fields
+        {
+          let field_name = format!( "{name}.{}", field.parameter );
+          match entries.get( &field.parameter )
+          {
+            Some( field_value ) =>
+            {
+              if let Some( kind ) = &field.kind
+              {
+                kind.check( &field_name, field_value )?;
+              }
+            }
+            None if field.is_mandatory && field.default_value.is_none() =>
+              return Err( ( field_name, "mandatory field is missing".into() ) ),
+            None => {}
+          }
+        }
+        Ok( () )
+      }
+      ( kind, value ) => fail( format!( "expected {}, got {}", kind.describe(), describe_value( value ) ) ),
+    }
+  }
+
+  /// Short human-readable name of the kind, for messages and prompts.
+  #[must_use]
+  pub fn describe( &self ) -> String
+  {
+    match self
+    {
+      Self::String { pattern: Some( pattern ) } => format!( "text matching '{pattern}'" ),
+      Self::String { pattern: None } => "text".into(),
+      Self::Integer { min, max } => match ( min, max )
+      {
+        ( Some( min ), Some( max ) ) => format!( "integer {min}..={max}" ),
+        ( Some( min ), None ) => format!( "integer >= {min}" ),
+        ( None, Some( max ) ) => format!( "integer <= {max}" ),
+        ( None, None ) => "integer".into(),
+      },
+      Self::Bool => "true/false".into(),
+      Self::Choice { options } => format!( "one of {}", options.join( ", " ) ),
+      Self::List => "comma-separated list".into(),
+      Self::Path { must_exist: true } => "existing path".into(),
+      Self::Path { must_exist: false } => "path".into(),
+      Self::Map { .. } => "map".into(),
+    }
+  }
+}
+
+fn describe_value( value: &Value ) -> &'static str
+{
+  match value
+  {
+    Value::Bool( _ ) => "a boolean",
+    Value::Number( _ ) => "a number",
+    Value::List( _ ) => "a list",
+    Value::String( _ ) => "a string",
+    Value::Map( _ ) => "a map",
+  }
+}
+
+fn value_text( value: &Value ) -> String
+{
+  use crate::TemplateValue;
+  value.to_template_string()
+}
+
+fn parse_integer( text: &str ) -> Result< i64, String >
+{
+  text.trim().parse().map_err( | _ | format!( "'{text}' is not an integer" ) )
+}
+
+fn parse_bool( text: &str ) -> Result< bool, String >
+{
+  match text.trim().to_ascii_lowercase().as_str()
+  {
+    "true" | "yes" | "y" | "on" | "1" => Ok( true ),
+    "false" | "no" | "n" | "off" | "0" => Ok( false ),
+    _ => Err( format!( "'{text}' is not a boolean (true/false)" ) ),
+  }
+}
+
+fn split_list( text: &str ) -> Vec< String >
+{
+  text.split( ',' ).map( str::trim ).filter( | item | !item.is_empty() ).map( String::from ).collect()
+}
+
+fn parse_map( text: &str ) -> Result< Value, String >
+{
+  match serde_json::from_str::< Value >( text )
+  {
+    Ok( value @ Value::Map( _ ) ) => Ok( value ),
+    Ok( _ ) => Err( "expected a JSON object".into() ),
+    Err( err ) => Err( format!( "not a JSON object: {err}" ) ),
+  }
+}
+
+#[cfg(feature = "parameter_validation")]
+fn matches_pattern( pattern: &str, text: &str ) -> Result< bool, String >
+{
+  let anchored = format!( "^(?:{pattern})$" );
+  regex::Regex::new( &anchored )
+    .map( | regex | regex.is_match( text ) )
+    .map_err( | err | format!( "invalid pattern '{pattern}': {err}" ) )
+}
+
+#[cfg(not(feature = "parameter_validation"))]
+fn matches_pattern( pattern: &str, _text: &str ) -> Result< bool, String >
+{
+  Err( format!( "pattern '{pattern}' cannot be checked without feature `parameter_validation`" ) )
 }
 
 /// Collection of parameter descriptors for a template.
@@ -52,6 +418,7 @@ pub struct ParameterDescriptor
 ///       is_mandatory: true,
 ///       default_value: None,
 ///       description: None,
+///       kind: None,
 ///     },
 ///   ],
 /// };
@@ -88,6 +455,7 @@ impl Parameters
   ///       is_mandatory: true,
   ///       default_value: None,
   ///       description: None,
+  ///       kind: None,
   ///     },
   ///   ],
   /// };
@@ -105,4 +473,51 @@ impl Parameters
       .map( | d | d.parameter.as_str() )
       .collect()
   }
+
+  /// Validate every set value that has a descriptor.
+  ///
+  /// # Errors
+  ///
+  /// Returns the first `Error::InvalidParameter`, naming the parameter.
+  ///
+  /// # Examples
+  ///
+  /// ```rust
+  /// use genfile_core::{ Parameters, ParameterDescriptor, ParameterKind, Values, Value };
+  ///
+  /// let params = Parameters
+  /// {
+  ///   descriptors: vec!
+  ///   [
+  ///     ParameterDescriptor
+  ///     {
+  ///       parameter: "license".into(),
+  ///       is_mandatory: true,
+  ///       default_value: None,
+  ///       description: None,
+  ///       kind: Some( ParameterKind::Choice { options: vec![ "MIT".into(), "Apache-2.0".into() ] } ),
+  ///     },
+  ///   ],
+  /// };
+  ///
+  /// let mut values = Values::new();
+  /// values.insert( "license", Value::String( "GPL".into() ) );
+  /// let err = params.validate( &values ).unwrap_err();
+  /// assert!( err.to_string().contains( "'license'" ) );
+  /// ```
+  pub fn validate( &self, values: &crate::Values< Value > ) -> Result< (), Error >
+  {
+    self
+      .descriptors
+      .iter()
+      .filter_map( | d | values.get( &d.parameter ).map( | value | ( d, value ) ) )
+      .try_for_each( | ( d, value ) | d.validate( value ) )
+  }
+
+  /// Descriptor by parameter name
+  #[must_use]
+  pub fn get( &self, name: &str ) -> Option< &ParameterDescriptor >
+  {
+    self.descriptors.iter().find( | d | d.parameter == name )
+  }
 }
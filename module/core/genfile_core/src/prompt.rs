@@ -0,0 +1,307 @@
+/// Interactive collection of parameter values
+///
+/// [`Parameters::prompt_missing`] walks the declared parameters and asks a
+/// [`Prompter`] for each one that [`Values::needs_prompt`] (or that is
+/// mandatory and unset). Answers are parsed and validated against the
+/// parameter's `ParameterKind`; rejected answers are asked again with the
+/// reason, up to [`MAX_ATTEMPTS`] times. Map parameters are asked field by
+/// field under dotted names (`database.port`).
+use std ::collections :: { BTreeMap, HashMap, VecDeque };
+use std ::io :: { BufRead, Write };
+
+use crate :: { Error, ParameterDescriptor, ParameterKind, Parameters, Value, Values };
+
+/// How many answers are asked for one parameter before giving up
+pub const MAX_ATTEMPTS: usize = 3;
+
+/// One question put to a [`Prompter`]
+#[ derive( Debug, Clone, Copy ) ]
+pub struct PromptRequest< 'a >
+{
+  /// Parameter name; dotted (`database.port`) for map fields
+  pub name: &'a str,
+  /// Descriptor of the parameter or map field being asked
+  pub descriptor: &'a ParameterDescriptor,
+  /// Why the previous answer was rejected, when asking again
+  pub error: Option< &'a str >,
+}
+
+impl PromptRequest< '_ >
+{
+  /// Question text: name, description, expected kind and default.
+  ///
+  /// # Examples
+  ///
+  /// ```rust
+  /// use genfile_core::{ ParameterDescriptor, ParameterKind, PromptRequest };
+  ///
+  /// let descriptor = ParameterDescriptor
+  /// {
+  ///   parameter: "license".into(),
+  ///   is_mandatory: true,
+  ///   default_value: Some( "MIT".into() ),
+  ///   description: Some( "License".into() ),
+  ///   kind: Some( ParameterKind::Choice { options: vec![ "MIT".into(), "Apache-2.0".into() ] } ),
+  /// };
+  /// let request = PromptRequest { name: "license", descriptor: &descriptor, error: None };
+  ///
+  /// assert_eq!( request.question(), "license - License (one of MIT, Apache-2.0) [MIT]: " );
+  /// ```
+  #[ must_use ]
+  pub fn question( &self ) -> String
+  {
+    let descriptor = self.descriptor;
+    let description = descriptor.description.as_ref().map( | text | format!( " - {text}" ) ).unwrap_or_default();
+    let kind = descriptor.kind.as_ref().map( | kind | format!( " ({})", kind.describe() ) ).unwrap_or_default();
+    let default = descriptor.default_value.as_ref().map( | text | format!( " [{text}]" ) ).unwrap_or_default();
+    format!( "{}{description}{kind}{default}: ", self.name )
+  }
+}
+
+/// Source of answers for parameter prompts.
+///
+/// Implement this to plug a terminal, a GUI or a scripted source into
+/// [`Parameters::prompt_missing`].
+pub trait Prompter
+{
+  /// Ask for the value of one parameter.
+  ///
+  /// Returns the raw answer, or `None` (or an empty answer) to accept the
+  /// default; an optional parameter without a default is then left unset.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the answer cannot be obtained (e.g. I/O failure).
+  fn ask( &mut self, request: &PromptRequest< '_ > ) -> Result< Option< String >, Error >;
+}
+
+/// Prompter answering from a fixed script, for tests and non-interactive runs.
+///
+/// Each parameter name has a queue of answers consumed in order, so retries
+/// after a rejected answer take the next one. Unscripted questions are
+/// answered with `None` (accept the default).
+///
+/// # Examples
+///
+/// ```rust
+/// use genfile_core::{ Parameters, ParameterDescriptor, ScriptedPrompter, Values, Value };
+///
+/// let params = Parameters
+/// {
+///   descriptors: vec!
+///   [
+///     ParameterDescriptor
+///     {
+///       parameter: "name".into(),
+///       is_mandatory: true,
+///       default_value: None,
+///       description: None,
+///       kind: None,
+///     },
+///   ],
+/// };
+///
+/// let mut values = Values::new();
+/// values.insert_none( "name" );
+/// let mut prompter = ScriptedPrompter::new().answer( "name", "demo" );
+///
+/// let prompted = params.prompt_missing( &mut values, &mut prompter ).unwrap();
+/// assert_eq!( prompted, vec![ "name".to_string() ] );
+/// assert_eq!( values.get( "name" ), Some( &Value::String( "demo".into() ) ) );
+/// ```
+#[ derive( Debug, Default, Clone ) ]
+pub struct ScriptedPrompter
+{
+  answers: HashMap< String, VecDeque< String > >,
+  asked: Vec< String >,
+}
+
+impl ScriptedPrompter
+{
+  /// Create a prompter with no scripted answers
+  #[ must_use ]
+  pub fn new() -> Self
+  {
+    Self::default()
+  }
+
+  /// Queue `answer` for parameter `name` (dotted for map fields)
+  #[ must_use ]
+  pub fn answer( mut self, name: impl Into< String >, answer: impl Into< String > ) -> Self
+  {
+    self.answers.entry( name.into() ).or_default().push_back( answer.into() );
+    self
+  }
+
+  /// Names asked so far, in order, including repeated questions
+  #[ must_use ]
+  pub fn asked( &self ) -> &[ String ]
+  {
+    &self.asked
+  }
+}
+
+impl Prompter for ScriptedPrompter
+{
+  fn ask( &mut self, request: &PromptRequest< '_ > ) -> Result< Option< String >, Error >
+  {
+    self.asked.push( request.name.to_string() );
+    Ok( self.answers.get_mut( request.name ).and_then( VecDeque::pop_front ) )
+  }
+}
+
+/// Prompter reading answers line by line, writing questions to `output`.
+///
+/// End of input answers `None` (accept the default).
+#[ derive( Debug ) ]
+pub struct TerminalPrompter< R, W >
+{
+  input: R,
+  output: W,
+}
+
+impl< R: BufRead, W: Write > TerminalPrompter< R, W >
+{
+  /// Create a prompter over any reader and writer
+  pub fn new( input: R, output: W ) -> Self
+  {
+    Self { input, output }
+  }
+}
+
+impl TerminalPrompter< std ::io ::StdinLock< 'static >, std ::io ::Stdout >
+{
+  /// Prompter over the process's standard input and output
+  #[ must_use ]
+  pub fn stdio() -> Self
+  {
+    Self::new( std ::io ::stdin().lock(), std ::io ::stdout() )
+  }
+}
+
+impl< R: BufRead, W: Write > Prompter for TerminalPrompter< R, W >
+{
+  fn ask( &mut self, request: &PromptRequest< '_ > ) -> Result< Option< String >, Error >
+  {
+    if let Some( error ) = request.error
+    {
+      writeln!( self.output, "  {error}" ).map_err( Error::Fs )?;
+    }
+    write!( self.output, "{}", request.question() ).map_err( Error::Fs )?;
+    self.output.flush().map_err( Error::Fs )?;
+
+    let mut line = String::new();
+    if self.input.read_line( &mut line ).map_err( Error::Fs )? == 0
+    {
+      return Ok( None );
+    }
+    Ok( Some( line.trim_end_matches( [ '\r', '\n' ] ).to_string() ) )
+  }
+}
+
+impl Parameters
+{
+  /// Prompt for every parameter that needs a value and store the answers.
+  ///
+  /// A parameter is asked when `values.needs_prompt( name )`, or when it is
+  /// mandatory and has no value. An empty answer takes the declared default;
+  /// an optional parameter without a default is then left unset, while a
+  /// mandatory one is asked again. Returns the names of the parameters that
+  /// were asked, in declaration order.
+  ///
+  /// # Errors
+  ///
+  /// Returns `Error::InvalidParameter` with the last rejection reason after
+  /// [`MAX_ATTEMPTS`] rejected answers, or any error from the prompter.
+  pub fn prompt_missing< P >( &self, values: &mut Values< Value >, prompter: &mut P ) -> Result< Vec< String >, Error >
+  where
+    P: Prompter + ?Sized,
+  {
+    let mut asked = Vec::new();
+    for descriptor in &self.descriptors
+    {
+      let name = &descriptor.parameter;
+      let missing = descriptor.is_mandatory && !values.has_value( name );
+      if !values.needs_prompt( name ) && !missing
+      {
+        continue;
+      }
+      if let Some( value ) = ask( name, descriptor, prompter )?
+      {
+        values.insert( name, value );
+      }
+      asked.push( name.clone() );
+    }
+    Ok( asked )
+  }
+}
+
+#[ cfg( feature = "archive" ) ]
+impl crate ::TemplateArchive
+{
+  /// Prompt for the archive's parameters that still need values.
+  ///
+  /// See [`Parameters::prompt_missing`].
+  ///
+  /// # Errors
+  ///
+  /// Returns `Error::InvalidParameter` after [`MAX_ATTEMPTS`] rejected
+  /// answers, or any error from the prompter.
+  pub fn prompt_values< P >( &mut self, prompter: &mut P ) -> Result< Vec< String >, Error >
+  where
+    P: Prompter + ?Sized,
+  {
+    let values = self.values.get_or_insert_with( Values::new );
+    self.parameters.prompt_missing( values, prompter )
+  }
+}
+
+/// Ask for one parameter (or each field of a map parameter) until a valid answer.
+fn ask< P >( name: &str, descriptor: &ParameterDescriptor, prompter: &mut P ) -> Result< Option< Value >, Error >
+where
+  P: Prompter + ?Sized,
+{
+  if let Some( ParameterKind::Map { fields } ) = &descriptor.kind
+  {
+    let mut map = BTreeMap::new();
+    for field in fields
+    {
+      if let Some( value ) = ask( &format!( "{name}.{}", field.parameter ), field, prompter )?
+      {
+        map.insert( field.parameter.clone(), value );
+      }
+    }
+    return Ok( Some( Value::Map( map ) ) );
+  }
+
+  let mut rejection: Option< String > = None;
+  for _ in 0..MAX_ATTEMPTS
+  {
+    let request = PromptRequest { name, descriptor, error: rejection.as_deref() };
+    let answer = prompter.ask( &request )?.filter( | text | !text.trim().is_empty() );
+    let parsed = match answer
+    {
+      Some( text ) => descriptor.parse_value( &text ).map( Some ),
+      None => match descriptor.typed_default()
+      {
+        Ok( None ) if descriptor.is_mandatory => Err( Error::InvalidParameter
+        {
+          parameter: name.to_string(),
+          reason: "a value is required".into(),
+        }),
+        other => other,
+      },
+    };
+    match parsed
+    {
+      Ok( value ) => return Ok( value ),
+      Err( Error::InvalidParameter { reason, .. } ) => rejection = Some( reason ),
+      Err( err ) => return Err( err ),
+    }
+  }
+  Err( Error::InvalidParameter
+  {
+    parameter: name.to_string(),
+    reason: rejection.unwrap_or_default(),
+  })
+}
@@ -0,0 +1,389 @@
+/// Fetching content resolver for URL sources
+///
+/// `RemoteContentResolver` resolves `ContentSource::Url` by scheme:
+///
+/// - `http://`, `https://` - HTTP GET; any non-2xx status is an error
+/// - `file://` - absolute local path (`file:///templates/main.hbs`)
+/// - `git+<repo>[?rev=<ref>]#<path>` - `<path>` at `<ref>` (default `HEAD`) of a
+///   git repository, fetched with the `git` command
+///   (`git+https://github.com/org/templates.git?rev=v1.2#rust/main.rs.hbs`)
+///
+/// Inline and file sources resolve as with `DefaultContentResolver`.
+///
+/// With a cache directory, fetched content is stored by its SHA-256 hash and
+/// each URL remembers the hash it last served. Content with a pinned checksum
+/// (`FileMetadata::checksum`) is served from the cache without fetching when
+/// present; offline mode never fetches and only serves cached content.
+use std::path::{ Path, PathBuf };
+use std::process::Command;
+use core::sync::atomic::{ AtomicUsize, Ordering };
+use core::time::Duration;
+
+use crate::{ ContentResolver, ContentSource, DefaultContentResolver, FileContent, Error };
+use crate::content_source::{ checksum_digest, content_bytes, content_from_bytes, sha256_hex, verify_checksum, DEFAULT_MAX_SIZE };
+
+/// Default timeout for a whole HTTP request
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs( 30 );
+
+/// Counter making scratch names unique within the process
+static COUNTER: AtomicUsize = AtomicUsize::new( 0 );
+
+/// Resolver that fetches `ContentSource::Url` over http(s), `file://` and git.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use genfile_core::{ RemoteContentResolver, ContentResolver, UrlRef, IntoContentSource };
+///
+/// let resolver = RemoteContentResolver::new()
+///   .with_cache_dir( ".genfile/cache" );
+///
+/// let source = UrlRef::new( "https://example.com/template.hbs" ).into_content_source();
+/// let content = resolver.resolve( &source ).unwrap();
+///
+/// // Later, without network: served from the cache
+/// let offline = RemoteContentResolver::new()
+///   .with_cache_dir( ".genfile/cache" )
+///   .offline( true );
+/// let cached = offline.resolve( &source ).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct RemoteContentResolver
+{
+  cache_dir: Option< PathBuf >,
+  offline: bool,
+  timeout: Duration,
+  max_size: u64,
+}
+
+impl RemoteContentResolver
+{
+  /// Create a resolver without cache, online, with a 30 second HTTP timeout
+  /// and a 32 MiB cap on HTTP response bodies
+  #[must_use]
+  pub fn new() -> Self
+  {
+    Self
+    {
+      cache_dir: None,
+      offline: false,
+      timeout: DEFAULT_TIMEOUT,
+      max_size: DEFAULT_MAX_SIZE,
+    }
+  }
+
+  /// Store fetched content under `dir`, keyed by content hash
+  #[must_use]
+  pub fn with_cache_dir( mut self, dir: impl Into< PathBuf > ) -> Self
+  {
+    self.cache_dir = Some( dir.into() );
+    self
+  }
+
+  /// Serve URL sources only from the cache, never fetching
+  #[must_use]
+  pub fn offline( mut self, offline: bool ) -> Self
+  {
+    self.offline = offline;
+    self
+  }
+
+  /// Limit the total duration of an HTTP request
+  #[must_use]
+  pub fn with_timeout( mut self, timeout: Duration ) -> Self
+  {
+    self.timeout = timeout;
+    self
+  }
+
+  /// Limit the size of an HTTP response body, in bytes
+  ///
+  /// Larger bodies fail with `Error::ContentTooLarge` instead of being read into memory.
+  #[must_use]
+  pub fn with_max_size( mut self, bytes: u64 ) -> Self
+  {
+    self.max_size = bytes;
+    self
+  }
+
+  /// Cache directory, if configured
+  #[must_use]
+  pub fn cache_dir( &self ) -> Option< &Path >
+  {
+    self.cache_dir.as_deref()
+  }
+
+  /// Whether the resolver is in offline mode
+  #[must_use]
+  pub fn is_offline( &self ) -> bool
+  {
+    self.offline
+  }
+
+  /// Fetch the raw bytes behind `url`, bypassing the cache.
+  ///
+  /// # Errors
+  ///
+  /// Returns `Error::Fetch` for unsupported schemes, malformed git URLs and
+  /// failed requests, `Error::ContentTooLarge` for an HTTP body over the size
+  /// limit, `Error::Fs` if a `file://` path cannot be read.
+  pub fn fetch( &self, url: &str ) -> Result< Vec< u8 >, Error >
+  {
+    if url.starts_with( "http://" ) || url.starts_with( "https://" )
+    {
+      self.fetch_http( url )
+    }
+    else if let Some( path ) = url.strip_prefix( "file://" )
+    {
+      Ok( std::fs::read( file_url_path( url, path )? )? )
+    }
+    else if let Some( spec ) = url.strip_prefix( "git+" )
+    {
+      fetch_git( url, spec )
+    }
+    else
+    {
+      Err( Error::Fetch( format!( "unsupported URL scheme: {url}" ) ) )
+    }
+  }
+
+  fn fetch_http( &self, url: &str ) -> Result< Vec< u8 >, Error >
+  {
+    let config = ureq::Agent::config_builder()
+      .timeout_global( Some( self.timeout ) )
+      .build();
+    let agent = ureq::Agent::new_with_config( config );
+
+    let mut response = agent.get( url ).call()
+      .map_err( | err | Error::Fetch( format!( "{url}: {err}" ) ) )?;
+    // ureq fails on reaching its limit, so one extra byte lets a body of exactly `max_size` through
+    response.body_mut().with_config().limit( self.max_size.saturating_add( 1 ) ).read_to_vec()
+      .map_err( | err | match err
+      {
+        ureq::Error::BodyExceedsLimit( _ ) => Error::ContentTooLarge { location: url.to_string(), limit: self.max_size },
+        err => Error::Fetch( format!( "{url}: {err}" ) ),
+      })
+  }
+
+  /// Bytes of the URL: fetched when online, from the cache index when offline.
+  fn load( &self, url: &str ) -> Result< Vec< u8 >, Error >
+  {
+    if !self.offline
+    {
+      return self.fetch( url );
+    }
+
+    let cached = match self.url_entry( url )
+    {
+      Some( entry ) => match std::fs::read_to_string( entry )
+      {
+        Ok( checksum ) => self.cached_object( &checksum_digest( checksum.trim() )? ),
+        Err( _ ) => None,
+      },
+      None => None,
+    };
+    cached.ok_or_else( || Error::Fetch( format!( "{url} is not cached (offline mode)" ) ) )
+  }
+
+  /// Cached object with this hex digest, if present and intact.
+  fn cached_object( &self, digest: &str ) -> Option< Vec< u8 > >
+  {
+    let path = self.cache_dir.as_ref()?.join( "sha256" ).join( digest );
+    std::fs::read( path ).ok().filter( | bytes | sha256_hex( bytes ) == digest )
+  }
+
+  /// Cache index file remembering which content `url` last served.
+  fn url_entry( &self, url: &str ) -> Option< PathBuf >
+  {
+    Some( self.cache_dir.as_ref()?.join( "url" ).join( sha256_hex( url.as_bytes() ) ) )
+  }
+
+  /// Store fetched bytes and point the URL's index entry at them.
+  fn store( &self, url: &str, bytes: &[ u8 ] ) -> Result< (), Error >
+  {
+    let ( Some( dir ), Some( entry ) ) = ( &self.cache_dir, self.url_entry( url ) ) else
+    {
+      return Ok( () );
+    };
+    let digest = sha256_hex( bytes );
+    let object = dir.join( "sha256" ).join( &digest );
+    if self.cached_object( &digest ).is_none()
+    {
+      write_atomic( &object, bytes )?;
+    }
+    write_atomic( &entry, format!( "sha256:{digest}" ).as_bytes() )
+  }
+}
+
+impl Default for RemoteContentResolver
+{
+  fn default() -> Self
+  {
+    Self::new()
+  }
+}
+
+impl ContentResolver for RemoteContentResolver
+{
+  fn resolve( &self, source: &ContentSource ) -> Result< FileContent, Error >
+  {
+    self.resolve_pinned( source, None )
+  }
+
+  fn resolve_pinned( &self, source: &ContentSource, checksum: Option< &str > ) -> Result< FileContent, Error >
+  {
+    let ContentSource::Url { url } = source else
+    {
+      return DefaultContentResolver::new().resolve_pinned( source, checksum );
+    };
+
+    // Pinned content already in the cache needs no fetch
+    if let Some( checksum ) = checksum
+    {
+      if let Some( bytes ) = self.cached_object( &checksum_digest( checksum )? )
+      {
+        return Ok( content_from_bytes( bytes ) );
+      }
+    }
+
+    let content = content_from_bytes( self.load( url )? );
+    if let Some( checksum ) = checksum
+    {
+      verify_checksum( url, &content, checksum )?;
+    }
+    if !self.offline
+    {
+      self.store( url, content_bytes( &content ) )?;
+    }
+    Ok( content )
+  }
+}
+
+/// Local path of a `file://` URL (`rest` is the part after the scheme).
+fn file_url_path( url: &str, rest: &str ) -> Result< PathBuf, Error >
+{
+  let path = rest.strip_prefix( "localhost" ).unwrap_or( rest );
+  if !path.starts_with( '/' )
+  {
+    return Err( Error::Fetch( format!( "file URL must have an absolute path: {url}" ) ) );
+  }
+  // `file:///C:/dir` names `C:/dir` on Windows
+  let path = match path.as_bytes()
+  {
+    [ b'/', drive, b':', .. ] if drive.is_ascii_alphabetic() => &path[ 1.. ],
+    _ => path,
+  };
+  Ok( PathBuf::from( percent_decode( url, path )? ) )
+}
+
+/// Decode `%XX` escapes of a URL path.
+fn percent_decode( url: &str, path: &str ) -> Result< String, Error >
+{
+  let bytes = path.as_bytes();
+  let mut out = Vec::with_capacity( bytes.len() );
+  let mut i = 0;
+  while i < bytes.len()
+  {
+    if bytes[ i ] == b'%'
+    {
+      let byte = path.get( i + 1..i + 3 ).and_then( | hex | u8::from_str_radix( hex, 16 ).ok() )
+        .ok_or_else( || Error::Fetch( format!( "invalid percent escape in {url}" ) ) )?;
+      out.push( byte );
+      i += 3;
+    }
+    else
+    {
+      out.push( bytes[ i ] );
+      i += 1;
+    }
+  }
+  String::from_utf8( out ).map_err( | _ | Error::Fetch( format!( "path is not UTF-8: {url}" ) ) )
+}
+
+/// Fetch one file from a git repository: `spec` is `<repo>[?rev=<ref>]#<path>`.
+///
+/// `branch=` and `tag=` are accepted as synonyms of `rev=`. The URL may come
+/// from an untrusted archive, so `repo` and `rev` are checked before `git` runs:
+/// neither may look like an option, and `rev` is limited to ref-name characters.
+fn fetch_git( url: &str, spec: &str ) -> Result< Vec< u8 >, Error >
+{
+  let ( repo, path ) = spec.rsplit_once( '#' )
+    .filter( | ( _, path ) | !path.is_empty() )
+    .ok_or_else( || Error::Fetch( format!( "git URL needs a #<path> fragment: {url}" ) ) )?;
+  let ( repo, rev ) = match repo.split_once( '?' )
+  {
+    None => ( repo, "HEAD" ),
+    Some( ( repo, query ) ) =>
+    {
+      let rev = query.strip_prefix( "rev=" )
+        .or_else( || query.strip_prefix( "branch=" ) )
+        .or_else( || query.strip_prefix( "tag=" ) )
+        .filter( | rev | !rev.is_empty() && !rev.contains( '&' ) )
+        .ok_or_else( || Error::Fetch( format!( "unsupported git URL query `{query}`, expected rev=<ref>: {url}" ) ) )?;
+      ( repo, rev )
+    }
+  };
+  if repo.is_empty() || repo.starts_with( '-' )
+  {
+    return Err( Error::Fetch( format!( "invalid git repository `{repo}`: {url}" ) ) );
+  }
+  let ref_char = | c: char | c.is_ascii_alphanumeric() || matches!( c, '.' | '_' | '/' | '-' );
+  if rev.starts_with( '-' ) || !rev.chars().all( ref_char )
+  {
+    return Err( Error::Fetch( format!( "invalid git revision `{rev}`, expected a ref name or commit hash: {url}" ) ) );
+  }
+
+  // A fresh, owner-only directory: a predictable name could be created first by
+  // another local user, whose git config would then apply.
+  let work = tempfile::Builder::new().prefix( "genfile_git" ).tempdir()?;
+  let git_dir = work.path().as_os_str();
+  run_git( url, &[ "init".as_ref(), "--quiet".as_ref(), "--bare".as_ref(), git_dir ] )?;
+  run_git( url, &[ "--git-dir".as_ref(), git_dir, "fetch".as_ref(), "--quiet".as_ref(), "--depth".as_ref(), "1".as_ref(), "--".as_ref(), repo.as_ref(), rev.as_ref() ] )?;
+  run_git( url, &[ "--git-dir".as_ref(), git_dir, "cat-file".as_ref(), "blob".as_ref(), format!( "FETCH_HEAD:{path}" ).as_ref() ] )
+}
+
+/// Run `git` non-interactively and return its stdout.
+///
+/// The `ext::` transport, which runs an arbitrary command, is refused, as is any
+/// transport git only allows when a user typed the URL. Local repositories stay
+/// allowed, like `file://` URLs.
+fn run_git( url: &str, args: &[ &std::ffi::OsStr ] ) -> Result< Vec< u8 >, Error >
+{
+  let output = Command::new( "git" )
+    .args( [ "-c", "protocol.ext.allow=never", "-c", "protocol.file.allow=always" ] )
+    .args( args )
+    .env( "GIT_TERMINAL_PROMPT", "0" )
+    .env( "GIT_PROTOCOL_FROM_USER", "0" )
+    .output()
+    .map_err( | err | Error::Fetch( format!( "{url}: cannot run git: {err}" ) ) )?;
+  if output.status.success()
+  {
+    Ok( output.stdout )
+  }
+  else
+  {
+    Err( Error::Fetch( format!( "{url}: git failed: {}", String::from_utf8_lossy( &output.stderr ).trim() ) ) )
+  }
+}
+
+/// Write through a temporary sibling and rename, so readers never see partial files.
+///
+/// The sibling's name is unique to the process and the call, so concurrent
+/// writers of the same path never share a temporary file.
+fn write_atomic( path: &Path, bytes: &[ u8 ] ) -> Result< (), Error >
+{
+  if let Some( parent ) = path.parent()
+  {
+    std::fs::create_dir_all( parent )?;
+  }
+  let tmp = path.with_extension( format!( "tmp{}_{}", std::process::id(), COUNTER.fetch_add( 1, Ordering::Relaxed ) ) );
+  let written = std::fs::OpenOptions::new().write( true ).create_new( true ).open( &tmp )
+    .and_then( | mut file | std::io::Write::write_all( &mut file, bytes ) )
+    .and_then( | () | std::fs::rename( &tmp, path ) );
+  if let Err( err ) = written
+  {
+    let _ = std::fs::remove_file( &tmp );
+    return Err( err.into() );
+  }
+  Ok( () )
+}
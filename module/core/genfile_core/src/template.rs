@@ -6,8 +6,8 @@ use crate ::
   FileSystem,
   Values,
   FileDescriptor,
-  WriteMode,
   Error,
+  merge::Planned,
 };
 
 /// Template holder for materializing templates into files.
@@ -176,7 +176,7 @@ where
   /// - Template file can't be read
   /// - Template rendering fails
   /// - Output file can't be written
-  /// - TOML merging fails (for `TomlExtend` mode)
+  /// - JSON/YAML merging or region replacement fails (for merge write modes)
   ///
   /// # Examples
   ///
@@ -198,19 +198,13 @@ where
       let rendered = self.renderer.render( &template_content, &serialized_values )?;
 
       // Write based on mode
-      match file_desc.write_mode
+      let path = &file_desc.file_path;
+      let exists = self.filesystem.exists( path );
+      match crate::merge::plan_write( &file_desc.write_mode, path, exists, || self.filesystem.read( path ), &rendered )?
       {
-        WriteMode ::Rewrite =>
-        {
-          self.filesystem.write( &file_desc.file_path, &rendered )?;
-        }
-        WriteMode ::TomlExtend =>
-        {
-          // Workaround(issue-005): TomlExtend falls back to full rewrite; merging not implemented.
-          // Root cause: TOML-aware merging requires parsing both files; not yet integrated with genfile_core.
-          // Pitfall: Existing TOML keys in the destination file are silently overwritten (FR12, FR19 gap).
-          self.filesystem.write( &file_desc.file_path, &rendered )?;
-        }
+        Planned::Create | Planned::Update => self.filesystem.write( path, &rendered )?,
+        Planned::Merge( merged ) => self.filesystem.write( path, &merged )?,
+        Planned::Skip => {}
       }
     }
 
@@ -58,6 +58,7 @@ pub trait TemplateValue: Clone + Send + Sync
 /// - `Number`: Signed 64-bit integer
 /// - `Bool`: Boolean true/false
 /// - `List`: Collection of strings (rendered as comma-separated)
+/// - `Map`: Nested named values (rendered as `key: value` pairs; fields reachable as `{{map.key}}`)
 ///
 /// # Examples
 ///
@@ -75,6 +76,9 @@ pub trait TemplateValue: Clone + Send + Sync
 ///
 /// let items = Value::List( vec![ "a".into(), "b".into() ] );
 /// assert_eq!( items.to_template_string(), "a, b" );
+///
+/// let db = Value::Map( [ ( "host".to_string(), Value::String( "localhost".into() ) ) ].into() );
+/// assert_eq!( db.to_template_string(), "host: localhost" );
 /// ```
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(any(feature = "archive", feature = "template"), derive(serde::Deserialize))]
@@ -89,6 +93,8 @@ pub enum Value
   List( Vec< String > ),
   /// String value
   String( String ),
+  /// Nested named values, e.g. a structured parameter's fields
+  Map( std ::collections ::BTreeMap< String, Value > ),
 }
 
 #[cfg(any(feature = "archive", feature = "template"))]
@@ -105,6 +111,8 @@ impl serde ::Serialize for Value
       Value ::Bool( b ) => serializer.serialize_bool( *b ),
       // Serialize lists as comma-separated strings for simple interpolation
       Value ::List( items ) => serializer.serialize_str( &items.join( ", " ) ),
+      // Serialize maps as objects so templates can address fields
+      Value ::Map( fields ) => serializer.collect_map( fields ),
     }
   }
 }
@@ -119,6 +127,11 @@ impl TemplateValue for Value
       Value ::Number( n ) => n.to_string(),
       Value ::Bool( b ) => b.to_string(),
       Value ::List( items ) => items.join( ", " ),
+      Value ::Map( fields ) => fields
+        .iter()
+        .map( | ( key, value ) | format!( "{key}: {}", value.to_template_string() ) )
+        .collect::< Vec< _ > >()
+        .join( ", " ),
     }
   }
 
@@ -134,6 +147,7 @@ impl TemplateValue for Value
       Value ::String( s ) => s.is_empty(),
       Value ::Number( _ ) | Value ::Bool( _ ) => false,
       Value ::List( items ) => items.is_empty(),
+      Value ::Map( fields ) => fields.is_empty(),
     }
   }
 }
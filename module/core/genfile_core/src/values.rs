@@ -226,6 +226,59 @@ where
     matches!( self.inner.get( key ), Some( None ) )
   }
 
+  /// Removes a parameter, returning its value if one was set.
+  ///
+  /// # Examples
+  ///
+  /// ```rust
+  /// use genfile_core::{ Values, Value };
+  ///
+  /// let mut values = Values::new();
+  /// values.insert( "name", Value::String( "test".into() ) );
+  ///
+  /// assert!( values.remove( "name" ).is_some() );
+  /// assert!( values.is_empty() );
+  /// ```
+  pub fn remove( &mut self, key: &str ) -> Option< V >
+  {
+    self.inner.remove( key ).flatten()
+  }
+
+  /// Overlays `other` onto this collection.
+  ///
+  /// Values set in `other` replace existing ones; parameters `other` leaves
+  /// unset are added only if they are missing here, so an explicit None never
+  /// hides a value.
+  ///
+  /// # Examples
+  ///
+  /// ```rust
+  /// use genfile_core::{ Values, Value, TemplateValue };
+  ///
+  /// let mut base = Values::new();
+  /// base.insert( "edition", Value::String( "2021".into() ) );
+  /// base.insert( "license", Value::String( "MIT".into() ) );
+  ///
+  /// let mut overlay = Values::new();
+  /// overlay.insert( "edition", Value::String( "2024".into() ) );
+  /// overlay.insert_none( "license" );
+  ///
+  /// base.merge( overlay );
+  /// assert_eq!( base.get( "edition" ).unwrap().to_template_string(), "2024" );
+  /// assert_eq!( base.get( "license" ).unwrap().to_template_string(), "MIT" );
+  /// ```
+  pub fn merge( &mut self, other: Self )
+  {
+    for ( key, value ) in other.inner
+    {
+      match value
+      {
+        Some( value ) => { self.inner.insert( key, Some( value ) ); }
+        None => { self.inner.entry( key ).or_insert( None ); }
+      }
+    }
+  }
+
   /// Returns the number of parameters in the collection.
   ///
   /// Includes both parameters with values and parameters explicitly set to None.
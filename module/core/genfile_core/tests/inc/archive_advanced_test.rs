@@ -369,6 +369,9 @@ fn internalize_preserves_metadata()
       permissions: Some( 0o755 ),
       is_template: true,
       comment: Some( "Executable script".into() ),
+      checksum: None,
+      condition: None,
+      for_each: None,
     }),
     content_source: Some( FileRef::new( PathBuf::from( "/templates/script.sh" ) ).into_content_source() ),
   });
@@ -0,0 +1,238 @@
+/// Tests for template inheritance and archive composition (docs/feature/025)
+use super :: *;
+use std ::path :: { Path, PathBuf };
+
+//
+
+fn parameter( name: &str, description: &str ) -> ParameterDescriptor
+{
+  ParameterDescriptor
+  {
+    parameter: name.into(),
+    is_mandatory: true,
+    default_value: None,
+    description: Some( description.into() ),
+    kind: None,
+  }
+}
+
+fn base() -> TemplateArchive
+{
+  let mut base = TemplateArchive ::new( "rust-crate" );
+  base.set_description( "Base Rust crate" );
+  base.add_text_file( PathBuf ::from( "Cargo.toml" ), "[package]\nname = \"{{name}}\"\nedition = \"{{edition}}\"\n", WriteMode ::Rewrite );
+  base.add_text_file( PathBuf ::from( "src/lib.rs" ), "//! {{name}}\n", WriteMode ::Rewrite );
+  base.add_text_file( PathBuf ::from( "docs/guide.md" ), "# Guide\n", WriteMode ::Rewrite );
+  base.add_text_file( PathBuf ::from( "docs/faq.md" ), "# FAQ\n", WriteMode ::Rewrite );
+  base.add_text_file( PathBuf ::from( "license" ), "{{license}}\n", WriteMode ::Rewrite );
+  base.add_parameter( parameter( "name", "Crate name" ) );
+  base.add_parameter( parameter( "edition", "Rust edition" ) );
+  base.add_parameter( parameter( "license", "License text" ) );
+  base.set_value( "edition", Value ::String( "2021".into() ) );
+  base.set_value( "license", Value ::String( "MIT".into() ) );
+  base
+}
+
+fn text( archive: &TemplateArchive, path: &str ) -> String
+{
+  match &archive.get_file( Path ::new( path ) ).unwrap().content
+  {
+    FileContent ::Text( text ) => text.clone(),
+    FileContent ::Binary( _ ) => panic!( "{path} is binary" ),
+  }
+}
+
+#[ test ]
+fn compose_overlays_overrides_and_removes_files()
+{
+  let mut variant = TemplateArchive ::new( "cli-crate" );
+  variant.add_text_file( PathBuf ::from( "src/main.rs" ), "fn main() {}\n", WriteMode ::Rewrite );
+  variant.add_text_file( PathBuf ::from( "Cargo.toml" ), "[package]\nname = \"{{name}}\"\n\n[[bin]]\nname = \"{{name}}\"\n", WriteMode ::Rewrite );
+  variant.remove_inherited_file( "src/lib.rs" ).remove_inherited_file( "docs" );
+
+  let composed = variant.compose( &base() );
+
+  assert_eq!( composed.list_files(), vec![ Path ::new( "Cargo.toml" ), Path ::new( "license" ), Path ::new( "src/main.rs" ) ] );
+  assert!( text( &composed, "Cargo.toml" ).contains( "[[bin]]" ) );
+  assert_eq!( composed.name, "cli-crate" );
+  assert_eq!( composed.description.as_deref(), Some( "Base Rust crate" ) );
+  assert!( composed.extends.is_empty() && composed.remove_files.is_empty() );
+}
+
+#[ test ]
+fn compose_overrides_and_removes_parameters_and_values()
+{
+  let mut variant = TemplateArchive ::new( "cli-crate" );
+  variant.add_parameter( parameter( "edition", "Edition of the binary crate" ) );
+  variant.add_parameter( parameter( "bin", "Binary name" ) );
+  variant.remove_inherited_parameter( "license" );
+  variant.set_value( "edition", Value ::String( "2024".into() ) );
+
+  let composed = variant.compose( &base() );
+
+  assert_eq!( composed.list_parameters(), vec![ "name", "edition", "bin" ] );
+  assert_eq!( composed.get_parameter( "edition" ).unwrap().description.as_deref(), Some( "Edition of the binary crate" ) );
+  assert_eq!( composed.get_value( "edition" ), Some( &Value ::String( "2024".into() ) ) );
+  assert!( composed.get_value( "license" ).is_none() );
+}
+
+#[ cfg( feature = "parameter_discovery" ) ]
+#[ test ]
+fn discovery_and_usage_span_the_composed_archive()
+{
+  let mut variant = TemplateArchive ::new( "cli-crate" );
+  variant.add_text_file( PathBuf ::from( "src/main.rs" ), "fn main() { println!( \"{{name}} {{greeting}}\" ); }\n", WriteMode ::Rewrite );
+  variant.remove_inherited_file( "license" );
+
+  let composed = variant.compose( &base() );
+
+  let discovered = composed.discover_parameters();
+  assert!( discovered.contains( "edition" ) && discovered.contains( "greeting" ) );
+  assert!( !discovered.contains( "license" ) );
+
+  let usage = composed.analyze_parameter_usage();
+  let mut files = usage[ "name" ].clone();
+  files.sort();
+  assert_eq!( files, vec![ PathBuf ::from( "Cargo.toml" ), PathBuf ::from( "src/lib.rs" ), PathBuf ::from( "src/main.rs" ) ] );
+  assert_eq!( composed.get_unused_parameters(), vec![ "license".to_string() ] );
+}
+
+#[ test ]
+fn resolve_loads_parent_chain_relative_to_each_archive()
+{
+  let mut fs = MemoryFileSystem ::new();
+  fs.write( Path ::new( "/templates/base/rust.json" ), &base().to_json().unwrap() ).unwrap();
+
+  let mut library = TemplateArchive ::new( "library" );
+  library.add_parent( "base/rust.json" );
+  library.add_text_file( PathBuf ::from( "src/lib.rs" ), "//! Library {{name}}\n", WriteMode ::Rewrite );
+  fs.write( Path ::new( "/templates/library.json" ), &library.to_json().unwrap() ).unwrap();
+
+  let mut variant = TemplateArchive ::new( "documented-library" );
+  variant.add_parent( "../templates/library.json" );
+  variant.remove_inherited_file( "docs/faq.md" );
+
+  let composed = variant.resolve_parents_with( Path ::new( "/variants" ), &fs ).unwrap();
+
+  assert_eq!( composed.file_count(), 4 );
+  assert_eq!( text( &composed, "src/lib.rs" ), "//! Library {{name}}\n" );
+  assert!( !composed.has_file( Path ::new( "docs/faq.md" ) ) );
+  assert_eq!( composed.get_value( "license" ), Some( &Value ::String( "MIT".into() ) ) );
+}
+
+#[ test ]
+fn later_parents_override_earlier_ones()
+{
+  let mut fs = MemoryFileSystem ::new();
+  fs.write( Path ::new( "/t/base.json" ), &base().to_json().unwrap() ).unwrap();
+  let mut apache = TemplateArchive ::new( "apache" );
+  apache.add_text_file( PathBuf ::from( "license" ), "Apache-2.0\n", WriteMode ::Rewrite );
+  fs.write( Path ::new( "/t/apache.json" ), &apache.to_json().unwrap() ).unwrap();
+
+  let mut variant = TemplateArchive ::new( "variant" );
+  variant.add_parent( "base.json" ).add_parent( "apache.json" );
+
+  let composed = variant.resolve_parents_with( Path ::new( "/t" ), &fs ).unwrap();
+
+  assert_eq!( text( &composed, "license" ), "Apache-2.0\n" );
+  assert_eq!( composed.file_count(), 5 );
+}
+
+#[ test ]
+fn removals_of_a_parent_never_reach_earlier_parents()
+{
+  let mut fs = MemoryFileSystem ::new();
+  fs.write( Path ::new( "/t/base.json" ), &base().to_json().unwrap() ).unwrap();
+  let mut extra = TemplateArchive ::new( "extra" );
+  extra.add_text_file( PathBuf ::from( "docs/extra.md" ), "# Extra\n", WriteMode ::Rewrite );
+  extra.add_parameter( parameter( "extra", "Extra setting" ) );
+  fs.write( Path ::new( "/t/extra.json" ), &extra.to_json().unwrap() ).unwrap();
+
+  // one parent with an ancestor of its own, one without; both remove the same things
+  let mut cli = TemplateArchive ::new( "cli" );
+  cli.add_parent( "extra.json" );
+  let mut trimmed = TemplateArchive ::new( "trimmed" );
+  for removing in [ &mut cli, &mut trimmed ]
+  {
+    removing.add_text_file( PathBuf ::from( "src/main.rs" ), "fn main() {}\n", WriteMode ::Rewrite );
+    removing.remove_inherited_file( "docs" ).remove_inherited_parameter( "license" ).remove_inherited_parameter( "extra" );
+  }
+  fs.write( Path ::new( "/t/cli.json" ), &cli.to_json().unwrap() ).unwrap();
+  fs.write( Path ::new( "/t/trimmed.json" ), &trimmed.to_json().unwrap() ).unwrap();
+
+  for second in [ "cli.json", "trimmed.json" ]
+  {
+    let mut variant = TemplateArchive ::new( "variant" );
+    variant.add_parent( "base.json" ).add_parent( second );
+
+    let composed = variant.resolve_parents_with( Path ::new( "/t" ), &fs ).unwrap();
+
+    assert!( composed.has_file( Path ::new( "docs/guide.md" ) ), "{second}" );
+    assert!( composed.has_file( Path ::new( "docs/faq.md" ) ), "{second}" );
+    assert!( !composed.has_file( Path ::new( "docs/extra.md" ) ), "{second}" );
+    assert!( composed.has_file( Path ::new( "src/main.rs" ) ), "{second}" );
+    assert!( composed.get_parameter( "license" ).is_some(), "{second}" );
+    assert!( composed.get_parameter( "extra" ).is_none(), "{second}" );
+    assert_eq!( composed.get_value( "license" ), Some( &Value ::String( "MIT".into() ) ), "{second}" );
+  }
+}
+
+#[ test ]
+fn parent_updates_propagate_to_variants()
+{
+  let mut fs = MemoryFileSystem ::new();
+  let mut variant = TemplateArchive ::new( "variant" );
+  variant.add_parent( "base.json" );
+
+  fs.write( Path ::new( "/t/base.json" ), &base().to_json().unwrap() ).unwrap();
+  assert!( !variant.resolve_parents_with( Path ::new( "/t" ), &fs ).unwrap().has_file( Path ::new( "rustfmt.toml" ) ) );
+
+  let mut updated = base();
+  updated.add_text_file( PathBuf ::from( "rustfmt.toml" ), "tab_spaces = 2\n", WriteMode ::Rewrite );
+  fs.write( Path ::new( "/t/base.json" ), &updated.to_json().unwrap() ).unwrap();
+  assert!( variant.resolve_parents_with( Path ::new( "/t" ), &fs ).unwrap().has_file( Path ::new( "rustfmt.toml" ) ) );
+}
+
+#[ test ]
+fn inheritance_cycle_is_rejected()
+{
+  let mut fs = MemoryFileSystem ::new();
+  let mut a = TemplateArchive ::new( "a" );
+  a.add_parent( "b.json" );
+  let mut b = TemplateArchive ::new( "b" );
+  b.add_parent( "a.json" );
+  fs.write( Path ::new( "/t/a.json" ), &a.to_json().unwrap() ).unwrap();
+  fs.write( Path ::new( "/t/b.json" ), &b.to_json().unwrap() ).unwrap();
+
+  let err = a.resolve_parents_with( Path ::new( "/t" ), &fs ).unwrap_err();
+
+  match err
+  {
+    Error ::InvalidTemplate( message ) => assert!( message.contains( "cycle" ) && message.contains( "b.json" ), "{message}" ),
+    other => panic!( "expected InvalidTemplate, got {other:?}" ),
+  }
+}
+
+#[ test ]
+fn missing_parent_is_an_error()
+{
+  let mut variant = TemplateArchive ::new( "variant" );
+  variant.add_parent( "absent.json" );
+
+  assert!( variant.resolve_parents_with( Path ::new( "/t" ), &MemoryFileSystem ::new() ).is_err() );
+}
+
+#[ test ]
+fn inheritance_fields_roundtrip_and_stay_out_of_plain_archives()
+{
+  let mut variant = TemplateArchive ::new( "variant" );
+  variant.add_parent( "base.json" ).remove_inherited_file( "docs" ).remove_inherited_parameter( "license" );
+
+  let restored = TemplateArchive ::from_json( &variant.to_json().unwrap() ).unwrap();
+  assert_eq!( restored.extends, vec![ PathBuf ::from( "base.json" ) ] );
+  assert_eq!( restored.remove_files, vec![ PathBuf ::from( "docs" ) ] );
+  assert_eq!( restored.remove_parameters, vec![ "license".to_string() ] );
+
+  let plain = base().to_json().unwrap();
+  assert!( !plain.contains( "extends" ) && !plain.contains( "remove_files" ) );
+}
@@ -0,0 +1,209 @@
+/// Tests for archive file formats and tar containers (docs/feature/024)
+use super :: *;
+use std ::path :: { Path, PathBuf };
+
+//
+
+fn archive() -> TemplateArchive
+{
+  let mut archive = TemplateArchive ::new( "formats" );
+  archive.add_text_file( PathBuf ::from( "src/main.rs" ), "fn main() { println!( \"{{greeting}}\" ); }\n", WriteMode ::Rewrite );
+  archive.add_text_file( PathBuf ::from( "run.sh" ), "#!/bin/sh\n", WriteMode ::CreateOnly );
+  archive.get_file_mut( Path ::new( "run.sh" ) ).unwrap().set_permissions( 0o755 );
+  archive.add_binary_file( PathBuf ::from( "logo.png" ), vec![ 0x89, 0x50, 0x4E, 0x47, 0x00, 0xFF ] );
+  archive.add_parameter( ParameterDescriptor
+  {
+    parameter: "greeting".into(),
+    is_mandatory: true,
+    default_value: None,
+    description: Some( "Greeting text".into() ),
+    kind: None,
+  });
+  archive.set_value( "greeting", Value ::String( "hello".into() ) );
+  archive
+}
+
+fn temp_dir( name: &str ) -> PathBuf
+{
+  let dir = std ::env ::temp_dir().join( format!( "genfile_core_format_{name}_{}", std ::process ::id() ) );
+  let _ = std ::fs ::remove_dir_all( &dir );
+  std ::fs ::create_dir_all( &dir ).unwrap();
+  dir
+}
+
+fn assert_same( restored: &TemplateArchive, original: &TemplateArchive )
+{
+  assert_eq!( restored.to_json().unwrap(), original.to_json().unwrap() );
+}
+
+#[ test ]
+fn container_roundtrips_every_part_of_the_archive()
+{
+  let original = archive();
+  for format in [ ArchiveFormat ::Tar, ArchiveFormat ::TarGz ]
+  {
+    let bytes = original.to_bytes( format ).unwrap();
+    let restored = TemplateArchive ::from_bytes( &bytes, format ).unwrap();
+
+    assert_same( &restored, &original );
+    assert_eq!( restored.get_file( Path ::new( "run.sh" ) ).unwrap().permissions(), Some( 0o755 ) );
+    assert!( matches!( &restored.get_file( Path ::new( "logo.png" ) ).unwrap().content, FileContent ::Binary( b ) if b == &[ 0x89, 0x50, 0x4E, 0x47, 0x00, 0xFF ] ) );
+  }
+}
+
+#[ test ]
+fn container_stores_raw_entries_beside_manifest()
+{
+  let bytes = archive().to_bytes( ArchiveFormat ::Tar ).unwrap();
+  let mut tar = tar ::Archive ::new( bytes.as_slice() );
+  let names: Vec< String > = tar.entries().unwrap()
+    .map( | entry | entry.unwrap().path().unwrap().to_string_lossy().into_owned() )
+    .collect();
+
+  assert_eq!( names, vec![ "manifest.json", "files/src/main.rs", "files/run.sh", "files/logo.png" ] );
+
+  // Binary content is stored raw, not base64 in the manifest
+  let text = String ::from_utf8_lossy( &bytes );
+  assert!( text.contains( "\"checksum\": \"sha256:" ) );
+  assert!( !text.contains( "iVBORwD/" ) );
+}
+
+#[ test ]
+fn container_is_deterministic_and_gzip_is_smaller()
+{
+  let mut big = archive();
+  big.add_text_file( PathBuf ::from( "data.txt" ), "repeated line of template text\n".repeat( 2000 ), WriteMode ::Rewrite );
+
+  assert_eq!( big.to_bytes( ArchiveFormat ::TarGz ).unwrap(), big.to_bytes( ArchiveFormat ::TarGz ).unwrap() );
+
+  let json = big.to_bytes( ArchiveFormat ::Json ).unwrap().len();
+  let gz = big.to_bytes( ArchiveFormat ::TarGz ).unwrap().len();
+  assert!( gz * 10 < json, "tar.gz {gz} bytes vs json {json} bytes" );
+}
+
+#[ test ]
+fn tampered_entry_fails_checksum()
+{
+  let mut bytes = archive().to_bytes( ArchiveFormat ::Tar ).unwrap();
+  let needle = b"#!/bin/sh";
+  let at = bytes.windows( needle.len() ).position( | window | window == needle ).unwrap();
+  bytes[ at + 2 ] = b'?';
+
+  let err = TemplateArchive ::from_bytes( &bytes, ArchiveFormat ::Tar ).unwrap_err();
+  match err
+  {
+    Error ::ChecksumMismatch { location, .. } => assert_eq!( location, "files/run.sh" ),
+    other => panic!( "expected ChecksumMismatch, got {other:?}" ),
+  }
+}
+
+/// Copy the entries of a tar container and append `extra` entries after them
+fn with_extra_entries( bytes: &[ u8 ], extra: &[ ( &str, &[ u8 ] ) ] ) -> Vec< u8 >
+{
+  let mut builder = tar ::Builder ::new( Vec ::new() );
+  let mut tar = tar ::Archive ::new( bytes );
+  for entry in tar.entries().unwrap()
+  {
+    let mut entry = entry.unwrap();
+    let header = entry.header().clone();
+    let mut content = Vec ::new();
+    std ::io ::Read ::read_to_end( &mut entry, &mut content ).unwrap();
+    builder.append( &header, content.as_slice() ).unwrap();
+  }
+  for ( name, content ) in extra
+  {
+    let mut header = tar ::Header ::new_gnu();
+    header.set_size( content.len() as u64 );
+    header.set_cksum();
+    builder.append_data( &mut header, name, *content ).unwrap();
+  }
+  builder.into_inner().unwrap()
+}
+
+#[ test ]
+fn duplicate_and_unlisted_entries_are_rejected()
+{
+  let bytes = archive().to_bytes( ArchiveFormat ::Tar ).unwrap();
+
+  let duplicate = with_extra_entries( &bytes, &[ ( "files/run.sh", b"#!/bin/sh\nrm -rf ~\n" ) ] );
+  let err = TemplateArchive ::from_bytes( &duplicate, ArchiveFormat ::Tar ).unwrap_err();
+  assert!( err.to_string().contains( "duplicate entry files/run.sh" ), "{err}" );
+
+  let unlisted = with_extra_entries( &bytes, &[ ( "files/extra.txt", b"surprise" ) ] );
+  let err = TemplateArchive ::from_bytes( &unlisted, ArchiveFormat ::Tar ).unwrap_err();
+  assert!( err.to_string().contains( "files/extra.txt is not listed" ), "{err}" );
+}
+
+#[ test ]
+fn oversized_container_is_rejected()
+{
+  let bytes = archive().to_bytes( ArchiveFormat ::Tar ).unwrap();
+  let zeros = vec![ 0_u8; 33 * 1024 * 1024 ];
+  let tar = with_extra_entries( &bytes, &[ ( "files/zeros.bin", &zeros ) ] );
+  let mut encoder = flate2 ::write ::GzEncoder ::new( Vec ::new(), flate2 ::Compression ::fast() );
+  std ::io ::Write ::write_all( &mut encoder, &tar ).unwrap();
+  let bomb = encoder.finish().unwrap();
+  assert!( bomb.len() < 1024 * 1024 );
+
+  let err = TemplateArchive ::from_bytes( &bomb, ArchiveFormat ::TarGz ).unwrap_err();
+  assert!( matches!( err, Error ::ContentTooLarge { limit, .. } if limit == 32 * 1024 * 1024 ), "{err:?}" );
+}
+
+#[ test ]
+fn malformed_container_is_rejected()
+{
+  let mut builder = tar ::Builder ::new( Vec ::new() );
+  let mut header = tar ::Header ::new_gnu();
+  header.set_size( 2 );
+  header.set_cksum();
+  builder.append_data( &mut header, "other.txt", &b"hi"[ .. ] ).unwrap();
+  let bytes = builder.into_inner().unwrap();
+
+  let err = TemplateArchive ::from_bytes( &bytes, ArchiveFormat ::Tar ).unwrap_err();
+  assert!( err.to_string().contains( "no manifest.json" ), "{err}" );
+  assert!( TemplateArchive ::from_bytes( b"not gzip", ArchiveFormat ::TarGz ).is_err() );
+}
+
+#[ test ]
+fn save_and_load_choose_format_by_extension()
+{
+  let dir = temp_dir( "ext" );
+  let original = archive();
+
+  for name in [ "a.json", "a.yaml", "a.tar", "a.tar.gz", "a.tgz" ]
+  {
+    let path = dir.join( name );
+    original.save_to_file( &path ).unwrap();
+    assert_same( &TemplateArchive ::load_from_file( &path ).unwrap(), &original );
+  }
+
+  let head = | name: &str | std ::fs ::read( dir.join( name ) ).unwrap()[ .. 2 ].to_vec();
+  assert_eq!( head( "a.json" ), b"{\n" );
+  assert_eq!( head( "a.yaml" ), b"na" );
+  assert_eq!( head( "a.tgz" ), [ 0x1F, 0x8B ] );
+  assert_eq!( ArchiveFormat ::detect( &dir.join( "a.tar" ), &std ::fs ::read( dir.join( "a.tar" ) ).unwrap() ), ArchiveFormat ::Tar );
+
+  std ::fs ::remove_dir_all( &dir ).unwrap();
+}
+
+#[ test ]
+fn load_detects_container_regardless_of_extension()
+{
+  let dir = temp_dir( "detect" );
+  let path = dir.join( "template.json" );
+  archive().save_as( &path, ArchiveFormat ::TarGz ).unwrap();
+
+  assert_same( &TemplateArchive ::load_from_file( &path ).unwrap(), &archive() );
+  std ::fs ::remove_dir_all( &dir ).unwrap();
+}
+
+#[ test ]
+fn format_names_parse()
+{
+  assert_eq!( ArchiveFormat ::from_name( "TAR.GZ" ), Some( ArchiveFormat ::TarGz ) );
+  assert_eq!( ArchiveFormat ::from_name( "yml" ), Some( ArchiveFormat ::Yaml ) );
+  assert_eq!( ArchiveFormat ::from_name( "zip" ), None );
+  assert_eq!( ArchiveFormat ::TarGz.to_string(), "tar.gz" );
+  assert_eq!( ArchiveFormat ::from_path( Path ::new( "x.TAR.GZ" ) ), ArchiveFormat ::TarGz );
+  assert_eq!( ArchiveFormat ::from_path( Path ::new( "x.gz" ) ), ArchiveFormat ::Json );
+}
@@ -203,6 +203,7 @@ fn add_parameter()
     is_mandatory: true,
     default_value: None,
     description: Some( "Project name".into() ),
+    kind: None,
   });
 
   let params = archive.list_parameters();
@@ -225,6 +226,7 @@ fn remove_parameter()
     is_mandatory: false,
     default_value: None,
     description: None,
+    kind: None,
   });
 
   let removed = archive.remove_parameter( "name" );
@@ -246,6 +248,7 @@ fn mandatory_parameters()
     is_mandatory: true,
     default_value: None,
     description: None,
+    kind: None,
   });
 
   archive.add_parameter( ParameterDescriptor
@@ -254,6 +257,7 @@ fn mandatory_parameters()
     is_mandatory: false,
     default_value: Some( "default".into() ),
     description: None,
+    kind: None,
   });
 
   let mandatory = archive.list_mandatory_parameters();
@@ -327,6 +331,7 @@ fn undefined_parameters()
     is_mandatory: true,
     default_value: None,
     description: None,
+    kind: None,
   });
 
   let undefined = archive.get_undefined_parameters();
@@ -351,6 +356,7 @@ fn unused_parameters()
     is_mandatory: true,
     default_value: None,
     description: None,
+    kind: None,
   });
 
   archive.add_parameter( ParameterDescriptor
@@ -359,6 +365,7 @@ fn unused_parameters()
     is_mandatory: false,
     default_value: None,
     description: None,
+    kind: None,
   });
 
   let unused = archive.get_unused_parameters();
@@ -662,6 +669,7 @@ fn json_roundtrip_complete_archive()
     is_mandatory: true,
     default_value: None,
     description: Some( "Greeting message".into() ),
+    kind: None,
   });
 
   archive.set_value( "greeting", Value::String( "Hello".into() ) );
@@ -695,6 +703,7 @@ fn yaml_roundtrip_complete_archive()
     is_mandatory: true,
     default_value: Some( "localhost".into() ),
     description: None,
+    kind: None,
   });
 
   let yaml = archive.to_yaml().unwrap();
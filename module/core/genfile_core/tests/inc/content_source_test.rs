@@ -121,7 +121,8 @@ fn default_resolver_url_not_supported()
       assert!( msg.contains( "URL fetching not supported" ) );
       assert!( msg.contains( "https://example.com/data.json" ) );
     }
-    Error::MissingParameters( _ ) | Error::Fs( _ ) | Error::InvalidTemplate( _ ) => panic!( "Expected render error" ),
+    Error::MissingParameters( _ ) | Error::Fs( _ ) | Error::InvalidTemplate( _ )
+    | Error::Fetch( _ ) | Error::ContentTooLarge { .. } | Error::ChecksumMismatch { .. } | Error::InvalidParameter { .. } => panic!( "Expected render error" ),
   }
 }
 
@@ -37,7 +37,7 @@ fn file_descriptor_holds_write_mode()
   match descriptor.write_mode
   {
     WriteMode ::Rewrite => {},
-    WriteMode ::TomlExtend => panic!( "Expected Rewrite mode" ),
+    WriteMode ::TomlExtend | WriteMode ::JsonMerge | WriteMode ::YamlMerge | WriteMode ::MarkedRegions | WriteMode ::CreateOnly => panic!( "Expected Rewrite mode" ),
   }
 }
 
@@ -50,7 +50,7 @@ fn write_mode_has_rewrite_variant()
   match mode
   {
     WriteMode ::Rewrite => {},
-    WriteMode ::TomlExtend => panic!( "Expected Rewrite variant" ),
+    WriteMode ::TomlExtend | WriteMode ::JsonMerge | WriteMode ::YamlMerge | WriteMode ::MarkedRegions | WriteMode ::CreateOnly => panic!( "Expected Rewrite variant" ),
   }
 }
 
@@ -63,7 +63,23 @@ fn write_mode_has_toml_extend_variant()
   match mode
   {
     WriteMode ::TomlExtend => {},
-    WriteMode ::Rewrite => panic!( "Expected TomlExtend variant" ),
+    WriteMode ::Rewrite | WriteMode ::JsonMerge | WriteMode ::YamlMerge | WriteMode ::MarkedRegions | WriteMode ::CreateOnly => panic!( "Expected TomlExtend variant" ),
+  }
+}
+
+#[ test ]
+fn write_mode_has_merge_and_create_only_variants()
+{
+  // docs/feature/009: WriteMode must have JsonMerge, YamlMerge, MarkedRegions and CreateOnly variants
+  let modes = [ WriteMode ::JsonMerge, WriteMode ::YamlMerge, WriteMode ::MarkedRegions, WriteMode ::CreateOnly ];
+
+  for mode in modes
+  {
+    match mode
+    {
+      WriteMode ::JsonMerge | WriteMode ::YamlMerge | WriteMode ::MarkedRegions | WriteMode ::CreateOnly => {},
+      WriteMode ::Rewrite | WriteMode ::TomlExtend => panic!( "Expected new variant" ),
+    }
   }
 }
 
@@ -77,7 +93,7 @@ fn write_mode_clone()
   match mode2
   {
     WriteMode ::Rewrite => {},
-    WriteMode ::TomlExtend => panic!( "Clone failed" ),
+    WriteMode ::TomlExtend | WriteMode ::JsonMerge | WriteMode ::YamlMerge | WriteMode ::MarkedRegions | WriteMode ::CreateOnly => panic!( "Clone failed" ),
   }
 }
 
@@ -0,0 +1,149 @@
+/// Tests for per-file conditions, path templating and fan-out (docs/feature/020)
+use super :: *;
+use std ::path :: { Path, PathBuf };
+
+//
+
+fn materialize( archive: &TemplateArchive ) -> Result< ( MaterializationReport, MemoryFileSystem ), Error >
+{
+  let mut fs = MemoryFileSystem ::new();
+  let report = archive.materialize_with_components( Path ::new( "/out" ), &HandlebarsRenderer ::new(), &mut fs )?;
+  Ok( ( report, fs ) )
+}
+
+fn service_archive() -> TemplateArchive
+{
+  let mut archive = TemplateArchive ::new( "service" );
+  archive.add_text_file( PathBuf ::from( "Dockerfile" ), "FROM {{image}}", WriteMode ::Rewrite );
+  archive.add_text_file( PathBuf ::from( "run.sh" ), "cargo run", WriteMode ::Rewrite );
+  archive.get_file_mut( Path ::new( "Dockerfile" ) ).unwrap().set_condition( "docker" );
+  archive.get_file_mut( Path ::new( "run.sh" ) ).unwrap().set_condition( "!docker" );
+  archive.set_value( "image", Value ::String( "rust:1".into() ) );
+  archive
+}
+
+#[ test ]
+fn condition_true_emits_file()
+{
+  let mut archive = service_archive();
+  archive.set_value( "docker", Value ::Bool( true ) );
+
+  let ( report, fs ) = materialize( &archive ).unwrap();
+
+  assert_eq!( report.files_created, vec![ PathBuf ::from( "Dockerfile" ) ] );
+  assert_eq!( report.files_excluded, vec![ PathBuf ::from( "run.sh" ) ] );
+  assert_eq!( fs.read( Path ::new( "/out/Dockerfile" ) ).unwrap(), "FROM rust:1" );
+  assert!( !fs.exists( Path ::new( "/out/run.sh" ) ) );
+}
+
+#[ test ]
+fn condition_false_or_unset_excludes_file()
+{
+  for docker in [ None, Some( Value ::Bool( false ) ), Some( Value ::String( "off".into() ) ), Some( Value ::Number( 0 ) ) ]
+  {
+    let mut archive = service_archive();
+    if let Some( value ) = docker.clone()
+    {
+      archive.set_value( "docker", value );
+    }
+
+    let ( report, fs ) = materialize( &archive ).unwrap();
+
+    assert_eq!( report.files_excluded, vec![ PathBuf ::from( "Dockerfile" ) ], "docker = {docker:?}" );
+    assert_eq!( report.files_created, vec![ PathBuf ::from( "run.sh" ) ], "docker = {docker:?}" );
+    assert!( !fs.exists( Path ::new( "/out/Dockerfile" ) ) );
+  }
+}
+
+#[ test ]
+fn path_is_rendered_from_values()
+{
+  let mut archive = TemplateArchive ::new( "module" );
+  archive.add_text_file( PathBuf ::from( "src/{{module}}/mod.rs" ), "//! {{module}}", WriteMode ::Rewrite );
+  archive.set_value( "module", Value ::String( "parser".into() ) );
+
+  let ( report, fs ) = materialize( &archive ).unwrap();
+
+  assert_eq!( report.files_created, vec![ PathBuf ::from( "src/parser/mod.rs" ) ] );
+  assert_eq!( report.directories_created, vec![ PathBuf ::from( "src" ), PathBuf ::from( "src/parser" ) ] );
+  assert_eq!( fs.read( Path ::new( "/out/src/parser/mod.rs" ) ).unwrap(), "//! parser" );
+}
+
+#[ test ]
+fn for_each_emits_one_file_per_list_element()
+{
+  let mut archive = TemplateArchive ::new( "crates" );
+  archive.add_text_file( PathBuf ::from( "src/{{item}}.rs" ), "// {{item_index}}: {{item}} of {{name}}", WriteMode ::Rewrite );
+  archive.get_file_mut( Path ::new( "src/{{item}}.rs" ) ).unwrap().set_for_each( "modules" );
+  archive.set_value( "modules", Value ::List( vec![ "lexer".into(), "parser".into() ] ) );
+  archive.set_value( "name", Value ::String( "compiler".into() ) );
+
+  let ( report, fs ) = materialize( &archive ).unwrap();
+
+  assert_eq!( report.files_created, vec![ PathBuf ::from( "src/lexer.rs" ), PathBuf ::from( "src/parser.rs" ) ] );
+  assert_eq!( fs.read( Path ::new( "/out/src/lexer.rs" ) ).unwrap(), "// 0: lexer of compiler" );
+  assert_eq!( fs.read( Path ::new( "/out/src/parser.rs" ) ).unwrap(), "// 1: parser of compiler" );
+}
+
+#[ test ]
+fn for_each_over_empty_or_unset_list_excludes_file()
+{
+  let mut archive = TemplateArchive ::new( "crates" );
+  archive.add_text_file( PathBuf ::from( "src/{{item}}.rs" ), "", WriteMode ::Rewrite );
+  archive.get_file_mut( Path ::new( "src/{{item}}.rs" ) ).unwrap().set_for_each( "modules" );
+
+  let ( report, _ ) = materialize( &archive ).unwrap();
+  assert_eq!( report.files_excluded, vec![ PathBuf ::from( "src/{{item}}.rs" ) ] );
+
+  archive.set_value( "modules", Value ::List( vec![] ) );
+  let ( report, _ ) = materialize( &archive ).unwrap();
+  assert_eq!( report.files_excluded, vec![ PathBuf ::from( "src/{{item}}.rs" ) ] );
+  assert!( report.files_created.is_empty() );
+}
+
+#[ test ]
+fn for_each_rejects_non_list_and_colliding_paths()
+{
+  let mut archive = TemplateArchive ::new( "crates" );
+  archive.add_text_file( PathBuf ::from( "lib.rs" ), "", WriteMode ::Rewrite );
+  archive.get_file_mut( Path ::new( "lib.rs" ) ).unwrap().set_for_each( "modules" );
+
+  archive.set_value( "modules", Value ::String( "lexer".into() ) );
+  let err = materialize( &archive ).unwrap_err();
+  assert!( err.to_string().contains( "must be a list" ), "{err}" );
+
+  archive.set_value( "modules", Value ::List( vec![ "a".into(), "b".into() ] ) );
+  let err = materialize( &archive ).unwrap_err();
+  assert!( err.to_string().contains( "more than one output renders to lib.rs" ), "{err}" );
+}
+
+#[ test ]
+fn rendered_path_cannot_escape_destination()
+{
+  for dir in [ "../escape", "/etc", "" ]
+  {
+    let mut archive = TemplateArchive ::new( "escape" );
+    archive.add_text_file( PathBuf ::from( "{{dir}}" ), "x", WriteMode ::Rewrite );
+    archive.set_value( "dir", Value ::String( dir.into() ) );
+
+    let result = materialize( &archive );
+    assert!( matches!( result, Err( Error ::InvalidTemplate( _ ) ) ), "dir = {dir:?}" );
+  }
+}
+
+#[ cfg( feature = "json" ) ]
+#[ test ]
+fn expansion_metadata_survives_json_roundtrip()
+{
+  let mut archive = TemplateArchive ::new( "roundtrip" );
+  archive.add_text_file( PathBuf ::from( "src/{{item}}.rs" ), "", WriteMode ::Rewrite );
+  archive.get_file_mut( Path ::new( "src/{{item}}.rs" ) ).unwrap()
+    .set_condition( "!minimal" )
+    .set_for_each( "modules" );
+
+  let restored = TemplateArchive ::from_json( &archive.to_json().unwrap() ).unwrap();
+  let file = restored.get_file( Path ::new( "src/{{item}}.rs" ) ).unwrap();
+
+  assert_eq!( file.condition(), Some( "!minimal" ) );
+  assert_eq!( file.for_each(), Some( "modules" ) );
+}
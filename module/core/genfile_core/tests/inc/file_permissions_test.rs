@@ -0,0 +1,96 @@
+/// Tests for binary content and permissions through pack and materialize (docs/feature/023)
+use super :: *;
+use std ::path :: { Path, PathBuf };
+
+//
+
+fn source() -> MemoryFileSystem
+{
+  let mut fs = MemoryFileSystem ::new();
+  fs.write( Path ::new( "/tpl/readme.md" ), "# {{name}}\n" ).unwrap();
+  fs.write( Path ::new( "/tpl/bin/run.sh" ), "#!/bin/sh\necho {{name}}\n" ).unwrap();
+  fs.set_permissions( Path ::new( "/tpl/bin/run.sh" ), 0o755 ).unwrap();
+  fs.write_bytes( Path ::new( "/tpl/logo.png" ), &[ 0x89, 0x50, 0x4E, 0x47, 0xFF ] ).unwrap();
+  fs
+}
+
+#[ test ]
+fn pack_records_non_default_modes_only()
+{
+  let archive = TemplateArchive ::pack_from_dir_with( "tpl", Path ::new( "/tpl" ), &source() ).unwrap();
+
+  let paths: Vec< &Path > = archive.files.iter().map( | file | file.path.as_path() ).collect();
+  assert_eq!( paths, vec![ Path ::new( "bin/run.sh" ), Path ::new( "logo.png" ), Path ::new( "readme.md" ) ] );
+
+  assert_eq!( archive.get_file( Path ::new( "bin/run.sh" ) ).unwrap().permissions(), Some( 0o755 ) );
+  assert_eq!( archive.get_file( Path ::new( "readme.md" ) ).unwrap().permissions(), None );
+  assert!( matches!( archive.get_file( Path ::new( "logo.png" ) ).unwrap().content, FileContent ::Binary( _ ) ) );
+}
+
+#[ test ]
+fn materialize_restores_modes_and_binary_bytes()
+{
+  let mut archive = TemplateArchive ::pack_from_dir_with( "tpl", Path ::new( "/tpl" ), &source() ).unwrap();
+  archive.set_value( "name", Value ::String( "demo".into() ) );
+
+  let mut fs = MemoryFileSystem ::new();
+  let report = archive.materialize_with_components( Path ::new( "/out" ), &HandlebarsRenderer ::new(), &mut fs ).unwrap();
+
+  assert_eq!( fs.read( Path ::new( "/out/bin/run.sh" ) ).unwrap(), "#!/bin/sh\necho demo\n" );
+  assert_eq!( fs.metadata( Path ::new( "/out/bin/run.sh" ) ).unwrap().mode, Some( 0o755 ) );
+  assert_eq!( fs.metadata( Path ::new( "/out/readme.md" ) ).unwrap().mode, Some( DEFAULT_FILE_MODE ) );
+  assert_eq!( fs.read_bytes( Path ::new( "/out/logo.png" ) ).unwrap(), vec![ 0x89, 0x50, 0x4E, 0x47, 0xFF ] );
+  assert!( fs.metadata( Path ::new( "/out/bin" ) ).unwrap().is_dir() );
+  assert_eq!( report.total_bytes_written, "#!/bin/sh\necho demo\n".len() + 5 + "# demo\n".len() );
+}
+
+#[ test ]
+fn skipped_files_keep_their_mode()
+{
+  let mut archive = TemplateArchive ::new( "keep" );
+  archive.add_text_file( PathBuf ::from( "run.sh" ), "new", WriteMode ::CreateOnly );
+  archive.get_file_mut( Path ::new( "run.sh" ) ).unwrap().set_permissions( 0o700 );
+
+  let mut fs = MemoryFileSystem ::new();
+  fs.write( Path ::new( "/out/run.sh" ), "user" ).unwrap();
+  archive.materialize_with_components( Path ::new( "/out" ), &HandlebarsRenderer ::new(), &mut fs ).unwrap();
+
+  assert_eq!( fs.metadata( Path ::new( "/out/run.sh" ) ).unwrap().mode, Some( DEFAULT_FILE_MODE ) );
+}
+
+#[ test ]
+fn plan_compares_binary_content_by_bytes()
+{
+  let mut archive = TemplateArchive ::new( "binary" );
+  archive.add_binary_file( PathBuf ::from( "logo.png" ), vec![ 0xFF, 1 ] );
+
+  let mut fs = MemoryFileSystem ::new();
+  fs.write_bytes( Path ::new( "/out/logo.png" ), &[ 0xFF, 1 ] ).unwrap();
+  let report = archive.plan_with_components( Path ::new( "/out" ), &HandlebarsRenderer ::new(), &fs ).unwrap();
+  assert_eq!( report.plan[ 0 ].diff, None );
+
+  fs.write_bytes( Path ::new( "/out/logo.png" ), &[ 0xFF, 2 ] ).unwrap();
+  let report = archive.plan_with_components( Path ::new( "/out" ), &HandlebarsRenderer ::new(), &fs ).unwrap();
+  assert_eq!( report.plan[ 0 ].diff.as_deref(), Some( "Binary files a/logo.png and b/logo.png differ\n" ) );
+}
+
+#[ cfg( all( unix, feature = "json" ) ) ]
+#[ test ]
+fn permissions_survive_real_pack_save_and_materialize()
+{
+  use std ::os ::unix ::fs ::PermissionsExt;
+
+  let root = std ::env ::temp_dir().join( format!( "genfile_core_permissions_{}", std ::process ::id() ) );
+  let _ = std ::fs ::remove_dir_all( &root );
+  std ::fs ::create_dir_all( root.join( "tpl" ) ).unwrap();
+  std ::fs ::write( root.join( "tpl/run.sh" ), "#!/bin/sh\n" ).unwrap();
+  std ::fs ::set_permissions( root.join( "tpl/run.sh" ), std ::fs ::Permissions ::from_mode( 0o755 ) ).unwrap();
+
+  let packed = TemplateArchive ::pack_from_dir( "tpl", &root.join( "tpl" ) ).unwrap();
+  let restored = TemplateArchive ::from_json( &packed.to_json().unwrap() ).unwrap();
+  restored.materialize( &root.join( "out" ) ).unwrap();
+
+  let mode = std ::fs ::metadata( root.join( "out/run.sh" ) ).unwrap().permissions().mode() & 0o7777;
+  assert_eq!( mode, 0o755 );
+  std ::fs ::remove_dir_all( &root ).unwrap();
+}
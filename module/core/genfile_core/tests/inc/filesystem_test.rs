@@ -1,6 +1,6 @@
 /// Tests for `FileSystem` trait and implementations (docs/feature/010, docs/feature/011, docs/feature/012)
 use super :: *;
-use std ::path ::PathBuf;
+use std ::path :: { Path, PathBuf };
 use std ::time ::{ SystemTime, UNIX_EPOCH };
 
 //
@@ -186,10 +186,8 @@ fn real_file_system_read_returns_written_bytes()
 #[ test ]
 fn memory_file_system_create_directory_all_is_noop()
 {
-  // docs/feature/012: MemoryFileSystem stores only file paths — writing a nested path
-  // must NOT create phantom directory entries accessible via read()
-  // (MemoryFileSystem has no create_directory_all method; this no-op behavior is verified
-  //  by confirming the parent path is not stored as a separate entry in the in-memory map)
+  // docs/feature/012: writing a nested path creates its parents as directory
+  // entries, which must NOT be readable as files via read()
   let mut fs = MemoryFileSystem ::new();
 
   let nested = PathBuf ::from( "a/b/c.txt" );
@@ -202,3 +200,174 @@ fn memory_file_system_create_directory_all_is_noop()
   let parent = PathBuf ::from( "a/b" );
   assert!( fs.read( &parent ).is_err(), "MemoryFileSystem must not create phantom directory entries" );
 }
+
+#[ test ]
+fn minimal_filesystem_gets_defaults_for_extended_methods()
+{
+  // docs/feature/010: only read/write/exists are required; bytes and metadata fall back to them
+  struct TextFs( std ::collections ::HashMap< PathBuf, String > );
+
+  impl FileSystem for TextFs
+  {
+    fn read( &self, path: &Path ) -> Result< String, Error >
+    {
+      self.0.get( path ).cloned().ok_or_else( || Error ::Fs( std ::io ::ErrorKind ::NotFound.into() ) )
+    }
+
+    fn write( &mut self, path: &Path, content: &str ) -> Result< (), Error >
+    {
+      self.0.insert( path.to_path_buf(), content.to_string() );
+      Ok(())
+    }
+
+    fn exists( &self, path: &Path ) -> bool
+    {
+      self.0.contains_key( path )
+    }
+  }
+
+  let mut fs = TextFs( std ::collections ::HashMap ::new() );
+  fs.write_bytes( Path ::new( "a.txt" ), b"text" ).unwrap();
+  assert_eq!( fs.read_bytes( Path ::new( "a.txt" ) ).unwrap(), b"text" );
+
+  let metadata = fs.metadata( Path ::new( "a.txt" ) ).unwrap();
+  assert_eq!( ( metadata.kind, metadata.len, metadata.mode ), ( EntryKind ::File, 4, None ) );
+  assert!( fs.set_permissions( Path ::new( "a.txt" ), 0o755 ).is_ok() );
+  assert!( fs.create_dir_all( Path ::new( "dir" ) ).is_ok() );
+
+  let unsupported = | err: Error | match err
+  {
+    Error ::Fs( err ) => err.kind() == std ::io ::ErrorKind ::Unsupported,
+    _ => false,
+  };
+  assert!( unsupported( fs.write_bytes( Path ::new( "b.bin" ), &[ 0xFF ] ).unwrap_err() ) );
+  assert!( unsupported( fs.read_dir( Path ::new( "dir" ) ).unwrap_err() ) );
+  assert!( unsupported( fs.remove_file( Path ::new( "a.txt" ) ).unwrap_err() ) );
+}
+
+#[ test ]
+fn memory_filesystem_stores_binary_content()
+{
+  // docs/feature/012: bytes round-trip unchanged; read() rejects non-UTF-8
+  let mut fs = MemoryFileSystem ::new();
+  let path = Path ::new( "logo.png" );
+
+  fs.write_bytes( path, &[ 0x89, 0x50, 0xFF, 0x00 ] ).unwrap();
+
+  assert_eq!( fs.read_bytes( path ).unwrap(), vec![ 0x89, 0x50, 0xFF, 0x00 ] );
+  assert!( fs.read( path ).is_err() );
+  assert_eq!( fs.metadata( path ).unwrap().len, 4 );
+}
+
+#[ test ]
+fn memory_filesystem_tracks_modes_and_mtime()
+{
+  // docs/feature/012: new files get DEFAULT_FILE_MODE, directories DEFAULT_DIR_MODE; rewrites keep the mode
+  let mut fs = MemoryFileSystem ::new();
+  let script = Path ::new( "/out/bin/run.sh" );
+  fs.write( script, "#!/bin/sh\n" ).unwrap();
+
+  let metadata = fs.metadata( script ).unwrap();
+  assert_eq!( metadata.mode, Some( DEFAULT_FILE_MODE ) );
+  assert!( metadata.modified.is_some() );
+  assert_eq!( fs.metadata( Path ::new( "/out/bin" ) ).unwrap().mode, Some( DEFAULT_DIR_MODE ) );
+
+  fs.set_permissions( script, 0o755 ).unwrap();
+  fs.write( script, "#!/bin/sh\nexit 0\n" ).unwrap();
+  assert_eq!( fs.metadata( script ).unwrap().mode, Some( 0o755 ) );
+
+  assert!( fs.set_permissions( Path ::new( "/out/missing" ), 0o600 ).is_err() );
+}
+
+#[ test ]
+fn memory_filesystem_lists_creates_and_removes_directories()
+{
+  // docs/feature/012: directories are entries of their own
+  let mut fs = MemoryFileSystem ::new();
+  fs.create_dir_all( Path ::new( "/out/empty" ) ).unwrap();
+  fs.write( Path ::new( "/out/src/lib.rs" ), "" ).unwrap();
+  fs.write( Path ::new( "/out/Cargo.toml" ), "" ).unwrap();
+
+  assert!( fs.exists( Path ::new( "/out/empty" ) ) );
+  assert!( fs.metadata( Path ::new( "/out/empty" ) ).unwrap().is_dir() );
+  assert_eq!( fs.read_dir( Path ::new( "/out" ) ).unwrap(), vec!
+  [
+    PathBuf ::from( "/out/Cargo.toml" ),
+    PathBuf ::from( "/out/empty" ),
+    PathBuf ::from( "/out/src" ),
+  ]);
+  assert!( fs.read_dir( Path ::new( "/out/Cargo.toml" ) ).is_err() );
+  assert!( fs.write( Path ::new( "/out/src" ), "x" ).is_err() );
+  assert!( fs.write( Path ::new( "/out/Cargo.toml/x" ), "x" ).is_err() );
+
+  assert!( fs.remove_file( Path ::new( "/out/src" ) ).is_err() );
+  fs.remove_file( Path ::new( "/out/Cargo.toml" ) ).unwrap();
+  fs.remove_dir_all( Path ::new( "/out/src" ) ).unwrap();
+  assert!( !fs.exists( Path ::new( "/out/src/lib.rs" ) ) );
+  assert_eq!( fs.read_dir( Path ::new( "/out" ) ).unwrap(), vec![ PathBuf ::from( "/out/empty" ) ] );
+}
+
+#[ test ]
+fn memory_filesystem_follows_symlinks()
+{
+  // docs/feature/012: a final symlink is followed by read/write/metadata, not by symlink_metadata
+  let mut fs = MemoryFileSystem ::new();
+  fs.write( Path ::new( "/out/real.txt" ), "data" ).unwrap();
+  fs.symlink( Path ::new( "real.txt" ), Path ::new( "/out/link.txt" ) ).unwrap();
+
+  assert_eq!( fs.read( Path ::new( "/out/link.txt" ) ).unwrap(), "data" );
+  assert_eq!( fs.read_link( Path ::new( "/out/link.txt" ) ).unwrap(), PathBuf ::from( "real.txt" ) );
+  assert_eq!( fs.metadata( Path ::new( "/out/link.txt" ) ).unwrap().kind, EntryKind ::File );
+  assert_eq!( fs.symlink_metadata( Path ::new( "/out/link.txt" ) ).unwrap().kind, EntryKind ::Symlink );
+
+  fs.write( Path ::new( "/out/link.txt" ), "changed" ).unwrap();
+  assert_eq!( fs.read( Path ::new( "/out/real.txt" ) ).unwrap(), "changed" );
+
+  fs.symlink( Path ::new( "/out/loop" ), Path ::new( "/out/loop" ) ).unwrap();
+  assert!( fs.read( Path ::new( "/out/loop" ) ).is_err() );
+  assert!( fs.symlink( Path ::new( "x" ), Path ::new( "/out/real.txt" ) ).is_err() );
+}
+
+#[ test ]
+fn real_file_system_handles_bytes_directories_and_removal()
+{
+  // docs/feature/011: bytes, listing and removal go to disk
+  let ts = SystemTime ::now().duration_since( UNIX_EPOCH ).unwrap().subsec_nanos();
+  let root = std ::env ::temp_dir().join( format!( "genfile_fs_ext_{ts}" ) );
+  let mut fs = RealFileSystem ::new();
+
+  fs.create_dir_all( &root.join( "empty" ) ).unwrap();
+  fs.write_bytes( &root.join( "data.bin" ), &[ 0, 0xFF, 7 ] ).unwrap();
+
+  assert_eq!( fs.read_bytes( &root.join( "data.bin" ) ).unwrap(), vec![ 0, 0xFF, 7 ] );
+  assert_eq!( fs.read_dir( &root ).unwrap(), vec![ root.join( "data.bin" ), root.join( "empty" ) ] );
+  assert!( fs.metadata( &root.join( "empty" ) ).unwrap().is_dir() );
+  assert!( fs.metadata( &root.join( "data.bin" ) ).unwrap().modified.is_some() );
+
+  fs.remove_file( &root.join( "data.bin" ) ).unwrap();
+  assert!( !fs.exists( &root.join( "data.bin" ) ) );
+  fs.remove_dir_all( &root ).unwrap();
+  assert!( !root.exists() );
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn real_file_system_reads_and_sets_unix_modes()
+{
+  // docs/feature/011: mode bits and symlinks on Unix
+  let ts = SystemTime ::now().duration_since( UNIX_EPOCH ).unwrap().subsec_nanos();
+  let root = std ::env ::temp_dir().join( format!( "genfile_fs_mode_{ts}" ) );
+  let script = root.join( "run.sh" );
+  let mut fs = RealFileSystem ::new();
+
+  fs.write( &script, "#!/bin/sh\n" ).unwrap();
+  fs.set_permissions( &script, 0o750 ).unwrap();
+  assert_eq!( fs.metadata( &script ).unwrap().mode, Some( 0o750 ) );
+
+  fs.symlink( Path ::new( "run.sh" ), &root.join( "start" ) ).unwrap();
+  assert_eq!( fs.read_link( &root.join( "start" ) ).unwrap(), PathBuf ::from( "run.sh" ) );
+  assert_eq!( fs.symlink_metadata( &root.join( "start" ) ).unwrap().kind, EntryKind ::Symlink );
+  assert_eq!( fs.metadata( &root.join( "start" ) ).unwrap().mode, Some( 0o750 ) );
+
+  std ::fs ::remove_dir_all( &root ).ok();
+}
@@ -24,12 +24,39 @@ mod integration_test;
 #[ cfg( feature = "archive" ) ]
 mod archive_test;
 
+#[ cfg( all( feature = "archive", feature = "serialization" ) ) ]
+mod write_mode_test;
+
+#[ cfg( feature = "archive" ) ]
+mod file_expansion_test;
+
+#[ cfg( feature = "archive" ) ]
+mod typed_parameter_test;
+
+#[ cfg( feature = "archive" ) ]
+mod plan_test;
+
+#[ cfg( feature = "archive" ) ]
+mod file_permissions_test;
+
+#[ cfg( feature = "container" ) ]
+mod archive_format_test;
+
+#[ cfg( feature = "json" ) ]
+mod archive_composition_test;
+
+#[ cfg( feature = "archive" ) ]
+mod templatize_test;
+
 #[ cfg( feature = "external_content" ) ]
 mod content_source_test;
 
 #[ cfg( feature = "external_content" ) ]
 mod content_source_example;
 
+#[ cfg( feature = "remote_content" ) ]
+mod remote_content_test;
+
 #[ cfg( feature = "archive" ) ]
 mod archive_advanced_test;
 
@@ -13,6 +13,7 @@ fn parameter_descriptor_stores_name()
     is_mandatory: false,
     default_value: None,
     description: None,
+    kind: None,
   };
 
   // Should be able to access parameter name
@@ -30,6 +31,7 @@ fn parameter_descriptor_mandatory_flag()
     is_mandatory: true,
     default_value: None,
     description: None,
+    kind: None,
   };
 
   let optional = ParameterDescriptor
@@ -38,6 +40,7 @@ fn parameter_descriptor_mandatory_flag()
     is_mandatory: false,
     default_value: None,
     description: None,
+    kind: None,
   };
 
   assert!( mandatory.is_mandatory );
@@ -54,6 +57,7 @@ fn parameter_descriptor_default_value()
     is_mandatory: false,
     default_value: Some( "us-east-1".into() ),
     description: None,
+    kind: None,
   };
 
   // Default value should be Some
@@ -71,6 +75,7 @@ fn parameter_descriptor_description()
     is_mandatory: false,
     default_value: None,
     description: Some( "API key for authentication".into() ),
+    kind: None,
   };
 
   assert!( with_desc.description.is_some() );
@@ -91,6 +96,7 @@ fn parameters_collection_stores_multiple()
         is_mandatory: true,
         default_value: None,
         description: None,
+        kind: None,
       },
       ParameterDescriptor
       {
@@ -98,6 +104,7 @@ fn parameters_collection_stores_multiple()
         is_mandatory: true,
         default_value: None,
         description: None,
+        kind: None,
       },
       ParameterDescriptor
       {
@@ -105,6 +112,7 @@ fn parameters_collection_stores_multiple()
         is_mandatory: false,
         default_value: None,
         description: None,
+        kind: None,
       },
     ],
   };
@@ -127,6 +135,7 @@ fn parameters_list_mandatory()
         is_mandatory: true,
         default_value: None,
         description: None,
+        kind: None,
       },
       ParameterDescriptor
       {
@@ -134,6 +143,7 @@ fn parameters_list_mandatory()
         is_mandatory: true,
         default_value: None,
         description: None,
+        kind: None,
       },
       ParameterDescriptor
       {
@@ -141,6 +151,7 @@ fn parameters_list_mandatory()
         is_mandatory: false,
         default_value: None,
         description: None,
+        kind: None,
       },
       ParameterDescriptor
       {
@@ -148,6 +159,7 @@ fn parameters_list_mandatory()
         is_mandatory: false,
         default_value: None,
         description: None,
+        kind: None,
       },
     ],
   };
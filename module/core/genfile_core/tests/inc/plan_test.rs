@@ -0,0 +1,124 @@
+/// Tests for dry-run planning and diff preview (docs/feature/022)
+use super :: *;
+use std ::path :: { Path, PathBuf };
+
+//
+
+fn archive() -> TemplateArchive
+{
+  let mut archive = TemplateArchive ::new( "plan" );
+  archive.add_text_file( PathBuf ::from( "new.txt" ), "fresh {{name}}\n", WriteMode ::Rewrite );
+  archive.add_text_file( PathBuf ::from( "config.txt" ), "a\nb = {{name}}\nc\n", WriteMode ::Rewrite );
+  archive.add_text_file( PathBuf ::from( "keep.txt" ), "template", WriteMode ::CreateOnly );
+  archive.add_text_file
+  (
+    PathBuf ::from( "main.rs" ),
+    "// genfile:begin body\nrun( \"{{name}}\" );\n// genfile:end body\n",
+    WriteMode ::MarkedRegions,
+  );
+  archive.set_value( "name", Value ::String( "demo".into() ) );
+  archive
+}
+
+fn existing() -> MemoryFileSystem
+{
+  let mut fs = MemoryFileSystem ::new();
+  fs.write( Path ::new( "/out/config.txt" ), "a\nb = old\nc\n" ).unwrap();
+  fs.write( Path ::new( "/out/keep.txt" ), "user edits" ).unwrap();
+  fs.write( Path ::new( "/out/main.rs" ), "fn main()\n{\n// genfile:begin body\nold();\n// genfile:end body\n}\n" ).unwrap();
+  fs
+}
+
+fn plan( archive: &TemplateArchive, fs: &MemoryFileSystem ) -> MaterializationReport
+{
+  archive.plan_with_components( Path ::new( "/out" ), &HandlebarsRenderer ::new(), fs ).unwrap()
+}
+
+#[ test ]
+fn plan_lists_action_per_output_without_writing()
+{
+  let fs = existing();
+  let report = plan( &archive(), &fs );
+
+  let actions: Vec< ( &Path, FileAction ) > = report.plan.iter().map( | file | ( file.path.as_path(), file.action ) ).collect();
+  assert_eq!( actions, vec!
+  [
+    ( Path ::new( "new.txt" ), FileAction ::Create ),
+    ( Path ::new( "config.txt" ), FileAction ::Update ),
+    ( Path ::new( "keep.txt" ), FileAction ::Skip ),
+    ( Path ::new( "main.rs" ), FileAction ::Merge ),
+  ]);
+
+  assert!( !fs.exists( Path ::new( "/out/new.txt" ) ) );
+  assert_eq!( fs.read( Path ::new( "/out/config.txt" ) ).unwrap(), "a\nb = old\nc\n" );
+}
+
+#[ test ]
+fn plan_diffs_are_unified_against_current_contents()
+{
+  let report = plan( &archive(), &existing() );
+  let diff = | path: &str | report.plan.iter().find( | file | file.path == Path ::new( path ) ).unwrap().diff.clone();
+
+  assert_eq!( diff( "new.txt" ).unwrap(), "--- /dev/null\n+++ b/new.txt\n@@ -0,0 +1 @@\n+fresh demo\n" );
+  assert_eq!( diff( "config.txt" ).unwrap(), "--- a/config.txt\n+++ b/config.txt\n@@ -1,3 +1,3 @@\n a\n-b = old\n+b = demo\n c\n" );
+  assert!( diff( "main.rs" ).unwrap().contains( "-old();\n+run( \"demo\" );\n" ) );
+  assert_eq!( diff( "keep.txt" ), None );
+
+  assert!( report.unified_diff().starts_with( "--- /dev/null\n+++ b/new.txt\n" ) );
+}
+
+#[ test ]
+fn plan_has_no_diff_for_identical_rewrite()
+{
+  let mut fs = existing();
+  fs.write( Path ::new( "/out/config.txt" ), "a\nb = demo\nc\n" ).unwrap();
+
+  let report = plan( &archive(), &fs );
+  let config = report.plan.iter().find( | file | file.path == Path ::new( "config.txt" ) ).unwrap();
+
+  assert_eq!( config.action, FileAction ::Update );
+  assert_eq!( config.diff, None );
+}
+
+#[ test ]
+fn plan_matches_following_materialization()
+{
+  let planned = plan( &archive(), &existing() );
+
+  let mut fs = existing();
+  let report = archive().materialize_with_components( Path ::new( "/out" ), &HandlebarsRenderer ::new(), &mut fs ).unwrap();
+
+  assert_eq!( planned.files_created, report.files_created );
+  assert_eq!( planned.files_updated, report.files_updated );
+  assert_eq!( planned.files_merged, report.files_merged );
+  assert_eq!( planned.files_skipped, report.files_skipped );
+  assert_eq!( planned.total_bytes_written, report.total_bytes_written );
+  assert!( report.plan.is_empty() );
+}
+
+#[ test ]
+fn plan_reports_binary_changes_without_text_diff()
+{
+  let mut archive = TemplateArchive ::new( "binary" );
+  archive.add_binary_file( PathBuf ::from( "logo.png" ), vec![ 0x89, 0x50, 0x4E, 0x47 ] );
+
+  let report = plan( &archive, &MemoryFileSystem ::new() );
+
+  assert_eq!( report.plan[ 0 ].diff.as_deref(), Some( "Binary files /dev/null and b/logo.png differ\n" ) );
+}
+
+#[ test ]
+fn plan_reads_real_filesystem()
+{
+  let dir = std ::env ::temp_dir().join( format!( "genfile_core_plan_{}", std ::process ::id() ) );
+  let _ = std ::fs ::remove_dir_all( &dir );
+  std ::fs ::create_dir_all( &dir ).unwrap();
+  std ::fs ::write( dir.join( "config.txt" ), "a\nb = old\nc\n" ).unwrap();
+
+  let report = archive().plan( &dir ).unwrap();
+
+  assert_eq!( report.files_updated, vec![ PathBuf ::from( "config.txt" ) ] );
+  assert_eq!( report.files_created.len(), 3 );
+  assert!( !dir.join( "new.txt" ).exists() );
+  std ::fs ::remove_dir_all( &dir ).unwrap();
+}
@@ -0,0 +1,394 @@
+//! Tests for `RemoteContentResolver` and checksum pinning
+//!
+//! # Test Coverage
+//!
+//! - http(s) fetching against a local stand-in server, response size limit
+//! - `file://` and `git+` URLs, unsupported schemes, option-like git arguments
+//! - Content-addressed cache, concurrent cache writes and offline mode
+//! - Checksum pinning in `FileMetadata` (resolver and archive level)
+//!
+//! The stand-in server binds `127.0.0.1:0`; no test touches the real network.
+
+use genfile_core::
+{
+  ContentResolver,
+  ContentSource,
+  RemoteContentResolver,
+  DefaultContentResolver,
+  TemplateArchive,
+  FileContent,
+  FileRef,
+  UrlRef,
+  IntoContentSource,
+  WriteMode,
+  Error,
+  content_checksum,
+};
+use std::io::{ Read, Write };
+use std::net::TcpListener;
+use std::path::{ Path, PathBuf };
+use std::sync::Arc;
+use core::sync::atomic::{ AtomicUsize, Ordering };
+
+//
+
+/// Local HTTP server answering every request with one status and body.
+struct StandInServer
+{
+  url: String,
+  hits: Arc< AtomicUsize >,
+}
+
+impl StandInServer
+{
+  fn start( status: &'static str, body: &'static [ u8 ] ) -> Self
+  {
+    let listener = TcpListener::bind( "127.0.0.1:0" ).unwrap();
+    let url = format!( "http://{}", listener.local_addr().unwrap() );
+    let hits = Arc::new( AtomicUsize::new( 0 ) );
+    let counter = Arc::clone( &hits );
+    std::thread::spawn( move ||
+    {
+      for stream in listener.incoming()
+      {
+        let Ok( mut stream ) = stream else { continue };
+        let mut request = Vec::new();
+        let mut buf = [ 0_u8; 1024 ];
+        while !request.ends_with( b"\r\n\r\n" )
+        {
+          match stream.read( &mut buf )
+          {
+            Ok( 0 ) | Err( _ ) => break,
+            Ok( n ) => request.extend_from_slice( &buf[ ..n ] ),
+          }
+        }
+        counter.fetch_add( 1, Ordering::SeqCst );
+        let head = format!( "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", body.len() );
+        let _ = stream.write_all( head.as_bytes() );
+        let _ = stream.write_all( body );
+      }
+    });
+    Self { url, hits }
+  }
+
+  fn hits( &self ) -> usize
+  {
+    self.hits.load( Ordering::SeqCst )
+  }
+}
+
+/// Fresh scratch directory under the system temp dir.
+fn scratch_dir( name: &str ) -> PathBuf
+{
+  let dir = std::env::temp_dir().join( format!( "genfile_remote_{name}_{}", std::process::id() ) );
+  let _ = std::fs::remove_dir_all( &dir );
+  std::fs::create_dir_all( &dir ).unwrap();
+  dir
+}
+
+fn url_source( url: impl Into< String > ) -> ContentSource
+{
+  UrlRef::new( url ).into_content_source()
+}
+
+fn file_url( path: &Path ) -> String
+{
+  format!( "file://{}", path.display() )
+}
+
+fn text( content: &FileContent ) -> &str
+{
+  match content
+  {
+    FileContent::Text( text ) => text,
+    FileContent::Binary( _ ) => panic!( "expected text content" ),
+  }
+}
+
+//
+
+#[ test ]
+fn http_url_fetched_from_stand_in_server()
+{
+  let server = StandInServer::start( "200 OK", b"Hello {{name}}" );
+  let resolver = RemoteContentResolver::new();
+
+  let content = resolver.resolve( &url_source( format!( "{}/template.hbs", server.url ) ) ).unwrap();
+
+  assert_eq!( text( &content ), "Hello {{name}}" );
+  assert_eq!( server.hits(), 1 );
+}
+
+#[ test ]
+fn http_binary_body_stays_binary()
+{
+  let server = StandInServer::start( "200 OK", &[ 0x89, 0x50, 0x4E, 0x47, 0xFF ] );
+  let content = RemoteContentResolver::new().resolve( &url_source( &server.url ) ).unwrap();
+  assert!( matches!( content, FileContent::Binary( ref bytes ) if bytes == &[ 0x89, 0x50, 0x4E, 0x47, 0xFF ] ) );
+}
+
+#[ test ]
+fn http_error_status_is_fetch_error()
+{
+  let server = StandInServer::start( "404 Not Found", b"missing" );
+  let url = format!( "{}/missing.hbs", server.url );
+
+  let err = RemoteContentResolver::new().resolve( &url_source( &url ) ).unwrap_err();
+
+  assert!( matches!( &err, Error::Fetch( msg ) if msg.contains( &url ) && msg.contains( "404" ) ), "{err}" );
+}
+
+#[ test ]
+fn http_body_over_size_limit_is_rejected()
+{
+  let server = StandInServer::start( "200 OK", b"0123456789" );
+
+  let err = RemoteContentResolver::new().with_max_size( 4 ).resolve( &url_source( &server.url ) ).unwrap_err();
+  assert!( matches!( &err, Error::ContentTooLarge { location, limit: 4 } if location == &server.url ), "{err}" );
+
+  let content = RemoteContentResolver::new().with_max_size( 10 ).resolve( &url_source( &server.url ) ).unwrap();
+  assert_eq!( text( &content ), "0123456789" );
+}
+
+#[ test ]
+fn file_url_and_unsupported_scheme()
+{
+  let dir = scratch_dir( "file_url" );
+  let path = dir.join( "with space.txt" );
+  std::fs::write( &path, "local" ).unwrap();
+  let resolver = RemoteContentResolver::new();
+
+  let url = file_url( &dir ).replace( '\\', "/" ) + "/with%20space.txt";
+  assert_eq!( text( &resolver.resolve( &url_source( url ) ).unwrap() ), "local" );
+
+  let err = resolver.resolve( &url_source( "ftp://example.com/a.txt" ) ).unwrap_err();
+  assert!( matches!( &err, Error::Fetch( msg ) if msg.contains( "unsupported URL scheme" ) ), "{err}" );
+
+  let err = resolver.resolve( &url_source( "file://relative/a.txt" ) ).unwrap_err();
+  assert!( matches!( err, Error::Fetch( _ ) ) );
+
+  std::fs::remove_dir_all( &dir ).unwrap();
+}
+
+#[ test ]
+fn non_url_sources_resolve_like_default_resolver()
+{
+  let dir = scratch_dir( "non_url" );
+  let path = dir.join( "a.txt" );
+  std::fs::write( &path, "from file" ).unwrap();
+
+  let content = RemoteContentResolver::new().resolve( &FileRef::new( &path ).into_content_source() ).unwrap();
+  assert_eq!( text( &content ), "from file" );
+
+  std::fs::remove_dir_all( &dir ).unwrap();
+}
+
+#[ test ]
+fn offline_mode_serves_cached_content_only()
+{
+  let cache = scratch_dir( "offline_cache" );
+  let server = StandInServer::start( "200 OK", b"cached body" );
+  let source = url_source( format!( "{}/a.hbs", server.url ) );
+
+  // Offline with an empty cache: nothing to serve, nothing fetched
+  let offline = RemoteContentResolver::new().with_cache_dir( &cache ).offline( true );
+  let err = offline.resolve( &source ).unwrap_err();
+  assert!( matches!( &err, Error::Fetch( msg ) if msg.contains( "offline" ) ), "{err}" );
+  assert_eq!( server.hits(), 0 );
+
+  // Online fetch fills the cache
+  let online = RemoteContentResolver::new().with_cache_dir( &cache );
+  assert_eq!( text( &online.resolve( &source ).unwrap() ), "cached body" );
+  assert_eq!( server.hits(), 1 );
+  let checksum = content_checksum( &FileContent::Text( "cached body".into() ) );
+  let object = cache.join( "sha256" ).join( checksum.trim_start_matches( "sha256:" ) );
+  assert_eq!( std::fs::read_to_string( object ).unwrap(), "cached body" );
+
+  // Offline now succeeds without contacting the server
+  assert_eq!( text( &offline.resolve( &source ).unwrap() ), "cached body" );
+  assert_eq!( server.hits(), 1 );
+
+  std::fs::remove_dir_all( &cache ).unwrap();
+}
+
+#[ test ]
+fn concurrent_cache_writes_of_one_url()
+{
+  let cache = scratch_dir( "concurrent_cache" );
+  let server = StandInServer::start( "200 OK", b"shared body" );
+  let source = url_source( &server.url );
+
+  std::thread::scope( | scope |
+  {
+    for _ in 0..8
+    {
+      scope.spawn( ||
+      {
+        let resolver = RemoteContentResolver::new().with_cache_dir( &cache );
+        assert_eq!( text( &resolver.resolve( &source ).unwrap() ), "shared body" );
+      });
+    }
+  });
+
+  let offline = RemoteContentResolver::new().with_cache_dir( &cache ).offline( true );
+  assert_eq!( text( &offline.resolve( &source ).unwrap() ), "shared body" );
+  for dir in [ "sha256", "url" ]
+  {
+    for entry in std::fs::read_dir( cache.join( dir ) ).unwrap()
+    {
+      let name = entry.unwrap().file_name();
+      assert!( !name.to_string_lossy().contains( ".tmp" ), "temporary file left behind: {name:?}" );
+    }
+  }
+
+  std::fs::remove_dir_all( &cache ).unwrap();
+}
+
+#[ test ]
+fn pinned_content_served_from_cache_without_fetch()
+{
+  let cache = scratch_dir( "pinned_cache" );
+  let server = StandInServer::start( "200 OK", b"pinned body" );
+  let source = url_source( &server.url );
+  let pin = content_checksum( &FileContent::Text( "pinned body".into() ) );
+  let resolver = RemoteContentResolver::new().with_cache_dir( &cache );
+
+  assert_eq!( text( &resolver.resolve_pinned( &source, Some( &pin ) ).unwrap() ), "pinned body" );
+  assert_eq!( server.hits(), 1 );
+
+  // Same pin, even under another URL: cache hit by content hash
+  let elsewhere = url_source( format!( "{}/mirror", server.url ) );
+  assert_eq!( text( &resolver.resolve_pinned( &elsewhere, Some( &pin ) ).unwrap() ), "pinned body" );
+  assert_eq!( server.hits(), 1 );
+
+  std::fs::remove_dir_all( &cache ).unwrap();
+}
+
+#[ test ]
+fn pinned_checksum_mismatch_is_rejected()
+{
+  let server = StandInServer::start( "200 OK", b"tampered" );
+  let pin = content_checksum( &FileContent::Text( "original".into() ) );
+
+  let err = RemoteContentResolver::new().resolve_pinned( &url_source( &server.url ), Some( &pin ) ).unwrap_err();
+  match err
+  {
+    Error::ChecksumMismatch { location, expected, actual } =>
+    {
+      assert_eq!( location, server.url );
+      assert_eq!( expected, pin );
+      assert_eq!( actual, content_checksum( &FileContent::Text( "tampered".into() ) ) );
+    }
+    other => panic!( "expected checksum mismatch, got {other}" ),
+  }
+
+  let err = RemoteContentResolver::new().resolve_pinned( &url_source( &server.url ), Some( "md5:abc" ) ).unwrap_err();
+  assert!( matches!( err, Error::InvalidTemplate( _ ) ) );
+}
+
+#[ test ]
+fn archive_pins_and_verifies_checksums()
+{
+  let dir = scratch_dir( "archive_pin" );
+  let path = dir.join( "main.hbs" );
+  std::fs::write( &path, "v1 {{name}}" ).unwrap();
+
+  let mut archive = TemplateArchive::new( "pinned" );
+  archive.add_file_from( PathBuf::from( "main.txt" ), FileRef::new( &path ), WriteMode::Rewrite );
+  archive.pin_checksums( &DefaultContentResolver::new() ).unwrap();
+  let pin = archive.files[ 0 ].checksum().unwrap().to_string();
+  assert_eq!( pin, content_checksum( &FileContent::Text( "v1 {{name}}".into() ) ) );
+
+  // Source changed after pinning: internalize refuses it
+  std::fs::write( &path, "v2 {{name}}" ).unwrap();
+  let err = archive.clone().internalize( &DefaultContentResolver::new() ).unwrap_err();
+  assert!( matches!( err, Error::ChecksumMismatch { .. } ), "{err}" );
+
+  // Restored content passes
+  std::fs::write( &path, "v1 {{name}}" ).unwrap();
+  archive.internalize( &RemoteContentResolver::new() ).unwrap();
+  assert_eq!( text( &archive.files[ 0 ].content ), "v1 {{name}}" );
+
+  std::fs::remove_dir_all( &dir ).unwrap();
+}
+
+#[ cfg( feature = "json" ) ]
+#[ test ]
+fn checksum_survives_json_roundtrip()
+{
+  let mut archive = TemplateArchive::new( "pinned" );
+  archive.add_file_from( PathBuf::from( "a.txt" ), UrlRef::new( "https://example.com/a" ), WriteMode::Rewrite );
+  archive.files[ 0 ].metadata = Some( genfile_core::FileMetadata
+  {
+    checksum: Some( content_checksum( &FileContent::Text( "a".into() ) ) ),
+    ..Default::default()
+  });
+
+  let restored = TemplateArchive::from_json( &archive.to_json().unwrap() ).unwrap();
+  assert_eq!( restored.files[ 0 ].checksum(), archive.files[ 0 ].checksum() );
+}
+
+#[ test ]
+fn git_url_reads_file_at_revision()
+{
+  let git = | dir: &Path, args: &[ &str ] | std::process::Command::new( "git" )
+    .args( [ "-c", "user.name=test", "-c", "user.email=test@example.com", "-c", "commit.gpgsign=false" ] )
+    .args( args )
+    .current_dir( dir )
+    .output();
+  let repo = scratch_dir( "git_repo" );
+  if git( &repo, &[ "init", "--quiet" ] ).is_err()
+  {
+    // `git` is not installed; nothing to test against
+    std::fs::remove_dir_all( &repo ).unwrap();
+    return;
+  }
+  std::fs::create_dir_all( repo.join( "tpl" ) ).unwrap();
+  std::fs::write( repo.join( "tpl/main.hbs" ), "first" ).unwrap();
+  git( &repo, &[ "add", "." ] ).unwrap();
+  git( &repo, &[ "commit", "--quiet", "-m", "first" ] ).unwrap();
+  git( &repo, &[ "tag", "v1" ] ).unwrap();
+  std::fs::write( repo.join( "tpl/main.hbs" ), "second" ).unwrap();
+  git( &repo, &[ "commit", "--quiet", "-am", "second" ] ).unwrap();
+
+  let resolver = RemoteContentResolver::new();
+  let base = format!( "git+{}", file_url( &repo ) );
+  assert_eq!( text( &resolver.resolve( &url_source( format!( "{base}#tpl/main.hbs" ) ) ).unwrap() ), "second" );
+  assert_eq!( text( &resolver.resolve( &url_source( format!( "{base}?rev=v1#tpl/main.hbs" ) ) ).unwrap() ), "first" );
+
+  let err = resolver.resolve( &url_source( format!( "{base}?rev=v1#tpl/missing.hbs" ) ) ).unwrap_err();
+  assert!( matches!( err, Error::Fetch( _ ) ) );
+  let err = resolver.resolve( &url_source( base ) ).unwrap_err();
+  assert!( matches!( &err, Error::Fetch( msg ) if msg.contains( "#<path>" ) ), "{err}" );
+
+  std::fs::remove_dir_all( &repo ).unwrap();
+}
+
+#[ test ]
+fn git_url_option_like_arguments_rejected_before_git_runs()
+{
+  let dir = scratch_dir( "git_injection" );
+  let marker = dir.join( "PWNED" );
+  let resolver = RemoteContentResolver::new();
+  let urls =
+  [
+    format!( "git+file:///nonexistent?rev=--upload-pack=touch {}; git-upload-pack#a.txt", marker.display() ),
+    "git+file:///nonexistent?rev=-v#a.txt".to_string(),
+    "git+file:///nonexistent?rev=main;id#a.txt".to_string(),
+    format!( "git+--upload-pack=touch {}#a.txt", marker.display() ),
+  ];
+
+  for url in urls
+  {
+    let err = resolver.resolve( &url_source( &url ) ).unwrap_err();
+    // Rejected by validation: no `git failed` message from a spawned git
+    assert!( matches!( &err, Error::Fetch( msg ) if msg.contains( "invalid git" ) ), "{url}: {err}" );
+  }
+
+  // `ext::` runs a command as the transport; git refuses it
+  let err = resolver.resolve( &url_source( format!( "git+ext::sh -c touch% {}#a.txt", marker.display() ) ) ).unwrap_err();
+  assert!( matches!( err, Error::Fetch( _ ) ), "{err}" );
+  assert!( !marker.exists() );
+
+  std::fs::remove_dir_all( &dir ).unwrap();
+}
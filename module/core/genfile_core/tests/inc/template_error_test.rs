@@ -339,8 +339,8 @@ fn check_missing_mandatory_returns_empty_when_all_filled()
   {
     descriptors: vec!
     [
-      ParameterDescriptor { parameter: "name".into(), is_mandatory: true, default_value: None, description: None },
-      ParameterDescriptor { parameter: "version".into(), is_mandatory: true, default_value: None, description: None },
+      ParameterDescriptor { parameter: "name".into(), is_mandatory: true, default_value: None, description: None, kind: None },
+      ParameterDescriptor { parameter: "version".into(), is_mandatory: true, default_value: None, description: None, kind: None },
     ],
   };
 
@@ -367,8 +367,8 @@ fn check_missing_mandatory_returns_names_when_mandatory_missing()
   {
     descriptors: vec!
     [
-      ParameterDescriptor { parameter: "name".into(), is_mandatory: true, default_value: None, description: None },
-      ParameterDescriptor { parameter: "version".into(), is_mandatory: true, default_value: None, description: None },
+      ParameterDescriptor { parameter: "name".into(), is_mandatory: true, default_value: None, description: None, kind: None },
+      ParameterDescriptor { parameter: "version".into(), is_mandatory: true, default_value: None, description: None, kind: None },
     ],
   };
 
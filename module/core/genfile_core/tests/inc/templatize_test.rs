@@ -0,0 +1,166 @@
+/// Tests for reverse-engineering templates from concrete projects (docs/feature/026)
+use super :: *;
+use std ::collections ::BTreeMap;
+use std ::path :: { Path, PathBuf };
+
+//
+
+fn values( pairs: &[ ( &str, &str ) ] ) -> BTreeMap< String, String >
+{
+  pairs.iter().map( | ( name, value ) | ( ( *name ).to_string(), ( *value ).to_string() ) ).collect()
+}
+
+fn project() -> MemoryFileSystem
+{
+  let mut fs = MemoryFileSystem ::new();
+  fs.write( Path ::new( "/my_crate/Cargo.toml" ), "[package]\nname = \"my_crate\"\nauthors = [ \"Alice\" ]\n" ).unwrap();
+  fs.write( Path ::new( "/my_crate/src/my_crate.rs" ), "//! my_crate_cli wraps my_crate.\n" ).unwrap();
+  fs.write( Path ::new( "/my_crate/.github/ci.yml" ), "run: echo ${{ secrets.TOKEN }} for my_crate\n" ).unwrap();
+  fs.write_bytes( Path ::new( "/my_crate/logo.png" ), &[ 0x89, b'm', b'y', 0xFF ] ).unwrap();
+  fs
+}
+
+fn text( archive: &TemplateArchive, path: &str ) -> String
+{
+  match &archive.get_file( Path ::new( path ) ).unwrap().content
+  {
+    FileContent ::Text( text ) => text.clone(),
+    FileContent ::Binary( _ ) => panic!( "{path} is binary" ),
+  }
+}
+
+#[ test ]
+fn replaces_values_in_contents_and_paths()
+{
+  let mut archive = TemplateArchive ::pack_from_dir_with( "my_crate", Path ::new( "/my_crate" ), &project() ).unwrap();
+
+  let report = archive.templatize( &values( &[ ( "name", "my_crate" ), ( "author", "Alice" ) ] ) ).unwrap();
+
+  assert_eq!( text( &archive, "Cargo.toml" ), "[package]\nname = \"{{name}}\"\nauthors = [ \"{{author}}\" ]\n" );
+  assert_eq!( text( &archive, "src/{{name}}.rs" ), "//! {{name}}_cli wraps {{name}}.\n" );
+  assert_eq!( report.files_renamed, vec![ ( PathBuf ::from( "src/my_crate.rs" ), PathBuf ::from( "src/{{name}}.rs" ) ) ] );
+  assert_eq!( report.replacements[ "name" ], 5 );
+  assert_eq!( report.replacements[ "author" ], 1 );
+  assert!( archive.get_file( Path ::new( "Cargo.toml" ) ).unwrap().metadata.as_ref().unwrap().is_template );
+}
+
+#[ test ]
+fn longest_value_wins_where_values_overlap()
+{
+  let mut archive = TemplateArchive ::new( "t" );
+  archive.add_text_file( PathBuf ::from( "readme.md" ), "my_crate_cli uses my_crate", WriteMode ::Rewrite );
+
+  archive.templatize( &values( &[ ( "name", "my_crate" ), ( "bin", "my_crate_cli" ) ] ) ).unwrap();
+
+  assert_eq!( text( &archive, "readme.md" ), "{{bin}} uses {{name}}" );
+}
+
+#[ test ]
+fn registers_missing_parameters_only()
+{
+  let mut archive = TemplateArchive ::new( "t" );
+  archive.add_text_file( PathBuf ::from( "readme.md" ), "my_crate by Alice", WriteMode ::Rewrite );
+  archive.add_parameter( ParameterDescriptor
+  {
+    parameter: "author".into(),
+    is_mandatory: false,
+    default_value: Some( "Bob".into() ),
+    description: None,
+    kind: None,
+  });
+
+  let report = archive.templatize( &values( &[ ( "name", "my_crate" ), ( "author", "Alice" ) ] ) ).unwrap();
+
+  assert_eq!( report.parameters_added, vec![ "name".to_string() ] );
+  assert!( archive.get_parameter( "name" ).unwrap().is_mandatory );
+  assert_eq!( archive.get_parameter( "author" ).unwrap().default_value.as_deref(), Some( "Bob" ) );
+}
+
+#[ test ]
+fn binary_content_is_left_alone()
+{
+  let mut archive = TemplateArchive ::pack_from_dir_with( "my_crate", Path ::new( "/my_crate" ), &project() ).unwrap();
+
+  archive.templatize( &values( &[ ( "initials", "my" ) ] ) ).unwrap();
+
+  let logo = archive.get_file( Path ::new( "logo.png" ) ).unwrap();
+  assert!( matches!( &logo.content, FileContent ::Binary( bytes ) if bytes == &[ 0x89, b'm', b'y', 0xFF ] ) );
+}
+
+#[ test ]
+fn materializing_with_same_values_reproduces_the_project()
+{
+  let source = project();
+  let mut archive = TemplateArchive ::pack_from_dir_with( "my_crate", Path ::new( "/my_crate" ), &source ).unwrap();
+  archive.templatize( &values( &[ ( "name", "my_crate" ), ( "author", "Alice" ) ] ) ).unwrap();
+  archive.set_value( "name", Value ::String( "my_crate".into() ) );
+  archive.set_value( "author", Value ::String( "Alice".into() ) );
+
+  let mut fs = MemoryFileSystem ::new();
+  archive.materialize_with_components( Path ::new( "/out" ), &HandlebarsRenderer ::new(), &mut fs ).unwrap();
+
+  for file in [ "Cargo.toml", "src/my_crate.rs", ".github/ci.yml" ]
+  {
+    assert_eq!( fs.read( &Path ::new( "/out" ).join( file ) ).unwrap(), source.read( &Path ::new( "/my_crate" ).join( file ) ).unwrap(), "{file}" );
+  }
+  assert_eq!( fs.read_bytes( Path ::new( "/out/logo.png" ) ).unwrap(), vec![ 0x89, b'm', b'y', 0xFF ] );
+}
+
+#[ test ]
+fn backslashes_before_placeholders_and_braces_round_trip()
+{
+  let sources =
+  [
+    r"C:\my_crate",
+    r"C:\\my_crate and \\\my_crate",
+    r"literal \{{raw}} and \\{{raw}} and {{raw}}",
+    r"\{{{{raw}}}} by Alice\",
+    "plain\\ back\\slashes \\n stay",
+  ];
+  for source in sources
+  {
+    let mut archive = TemplateArchive ::new( "t" );
+    archive.add_text_file( PathBuf ::from( "readme.md" ), source, WriteMode ::Rewrite );
+    archive.templatize( &values( &[ ( "name", "my_crate" ), ( "author", "Alice" ) ] ) ).unwrap();
+    archive.set_value( "name", Value ::String( "my_crate".into() ) );
+    archive.set_value( "author", Value ::String( "Alice".into() ) );
+
+    let mut fs = MemoryFileSystem ::new();
+    archive.materialize_with_components( Path ::new( "/out" ), &HandlebarsRenderer ::new(), &mut fs ).unwrap();
+
+    assert_eq!( fs.read( Path ::new( "/out/readme.md" ) ).unwrap(), source, "template: {}", text( &archive, "readme.md" ) );
+  }
+}
+
+#[ test ]
+fn materializing_with_new_values_renames_and_rewrites()
+{
+  let mut archive = TemplateArchive ::pack_from_dir_with( "my_crate", Path ::new( "/my_crate" ), &project() ).unwrap();
+  archive.templatize( &values( &[ ( "name", "my_crate" ), ( "author", "Alice" ) ] ) ).unwrap();
+  archive.set_value( "name", Value ::String( "other".into() ) );
+  archive.set_value( "author", Value ::String( "Bob".into() ) );
+
+  let mut fs = MemoryFileSystem ::new();
+  archive.materialize_with_components( Path ::new( "/out" ), &HandlebarsRenderer ::new(), &mut fs ).unwrap();
+
+  assert_eq!( fs.read( Path ::new( "/out/src/other.rs" ) ).unwrap(), "//! other_cli wraps other.\n" );
+  assert_eq!( fs.read( Path ::new( "/out/.github/ci.yml" ) ).unwrap(), "run: echo ${{ secrets.TOKEN }} for other\n" );
+}
+
+#[ test ]
+fn invalid_values_are_rejected()
+{
+  let mut archive = TemplateArchive ::new( "t" );
+  archive.add_text_file( PathBuf ::from( "readme.md" ), "x", WriteMode ::Rewrite );
+
+  let parameter = | result: Result< TemplatizeReport, Error > | match result
+  {
+    Err( Error ::InvalidParameter { parameter, .. } ) => parameter,
+    other => panic!( "expected InvalidParameter, got {other:?}" ),
+  };
+
+  assert_eq!( parameter( archive.templatize( &values( &[ ( "name", "" ) ] ) ) ), "name" );
+  assert_eq!( parameter( archive.templatize( &values( &[ ( "my-name", "x" ) ] ) ) ), "my-name" );
+  assert_eq!( parameter( archive.templatize( &values( &[ ( "a", "x" ), ( "b", "x" ) ] ) ) ), "b" );
+  assert_eq!( text( &archive, "readme.md" ), "x" );
+}
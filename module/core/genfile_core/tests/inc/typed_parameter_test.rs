@@ -0,0 +1,245 @@
+/// Tests for typed parameters, validation and prompting (docs/feature/021)
+use super :: *;
+use std ::path :: { Path, PathBuf };
+
+//
+
+fn typed( name: &str, kind: ParameterKind ) -> ParameterDescriptor
+{
+  ParameterDescriptor
+  {
+    parameter: name.into(),
+    is_mandatory: true,
+    default_value: None,
+    description: None,
+    kind: Some( kind ),
+  }
+}
+
+fn invalid_parameter( err: &Error ) -> &str
+{
+  match err
+  {
+    Error ::InvalidParameter { parameter, .. } => parameter,
+    other => panic!( "expected InvalidParameter, got {other:?}" ),
+  }
+}
+
+#[ test ]
+fn choice_accepts_only_listed_options()
+{
+  let license = typed( "license", ParameterKind ::Choice { options: vec![ "MIT".into(), "Apache-2.0".into() ] } );
+
+  assert!( license.validate( &Value ::String( "MIT".into() ) ).is_ok() );
+
+  let err = license.validate( &Value ::String( "GPL".into() ) ).unwrap_err();
+  assert_eq!( invalid_parameter( &err ), "license" );
+  assert_eq!( err.to_string(), "Invalid value for parameter 'license': 'GPL' is not one of MIT, Apache-2.0" );
+}
+
+#[ cfg( feature = "parameter_validation" ) ]
+#[ test ]
+fn string_pattern_must_match_whole_value()
+{
+  let crate_name = typed( "crate_name", ParameterKind ::String { pattern: Some( "[a-z][a-z0-9_]*".into() ) } );
+
+  assert!( crate_name.validate( &Value ::String( "genfile_core".into() ) ).is_ok() );
+  for bad in [ "Genfile", "my-crate", "ok but spaced" ]
+  {
+    let err = crate_name.validate( &Value ::String( bad.into() ) ).unwrap_err();
+    assert!( err.to_string().contains( "does not match pattern" ), "{bad}: {err}" );
+  }
+
+  let broken = typed( "x", ParameterKind ::String { pattern: Some( "(".into() ) } );
+  let err = broken.validate( &Value ::String( "a".into() ) ).unwrap_err();
+  assert!( err.to_string().contains( "invalid pattern" ), "{err}" );
+}
+
+#[ test ]
+fn integer_range_is_inclusive_and_accepts_text()
+{
+  let port = typed( "port", ParameterKind ::Integer { min: Some( 1 ), max: Some( 65535 ) } );
+
+  assert!( port.validate( &Value ::Number( 1 ) ).is_ok() );
+  assert!( port.validate( &Value ::Number( 65535 ) ).is_ok() );
+  assert!( port.validate( &Value ::String( "8080".into() ) ).is_ok() );
+
+  assert!( port.validate( &Value ::Number( 0 ) ).unwrap_err().to_string().contains( "less than minimum 1" ) );
+  assert!( port.validate( &Value ::String( "http".into() ) ).unwrap_err().to_string().contains( "not an integer" ) );
+  assert!( port.validate( &Value ::Bool( true ) ).unwrap_err().to_string().contains( "expected integer 1..=65535, got a boolean" ) );
+}
+
+#[ test ]
+fn path_must_exist_when_required()
+{
+  let existing = env!( "CARGO_MANIFEST_DIR" );
+  let workspace = typed( "workspace", ParameterKind ::Path { must_exist: true } );
+
+  assert!( workspace.validate( &Value ::String( existing.into() ) ).is_ok() );
+  let err = workspace.validate( &Value ::String( "/no/such/genfile/dir".into() ) ).unwrap_err();
+  assert!( err.to_string().contains( "does not exist" ), "{err}" );
+
+  let output = typed( "output", ParameterKind ::Path { must_exist: false } );
+  assert!( output.validate( &Value ::String( "/no/such/genfile/dir".into() ) ).is_ok() );
+}
+
+#[ test ]
+fn map_fields_are_validated_with_dotted_names()
+{
+  let database = typed( "database", ParameterKind ::Map
+  {
+    fields: vec!
+    [
+      typed( "host", ParameterKind ::String { pattern: None } ),
+      typed( "port", ParameterKind ::Integer { min: Some( 1 ), max: Some( 65535 ) } ),
+    ],
+  });
+
+  let value = | port: Value | Value ::Map( [ ( "host".to_string(), Value ::String( "db".into() ) ), ( "port".to_string(), port ) ].into() );
+  assert!( database.validate( &value( Value ::Number( 5432 ) ) ).is_ok() );
+
+  let err = database.validate( &value( Value ::Number( 70000 ) ) ).unwrap_err();
+  assert_eq!( invalid_parameter( &err ), "database.port" );
+
+  let err = database.validate( &Value ::Map( [ ( "host".to_string(), Value ::String( "db".into() ) ) ].into() ) ).unwrap_err();
+  assert_eq!( invalid_parameter( &err ), "database.port" );
+  assert!( err.to_string().contains( "mandatory field is missing" ) );
+
+  let mut extra = value( Value ::Number( 1 ) );
+  if let Value ::Map( fields ) = &mut extra
+  {
+    fields.insert( "user".into(), Value ::String( "root".into() ) );
+  }
+  assert_eq!( invalid_parameter( &database.validate( &extra ).unwrap_err() ), "database.user" );
+}
+
+#[ test ]
+fn parse_value_produces_typed_values()
+{
+  assert_eq!( typed( "n", ParameterKind ::Integer { min: None, max: None } ).parse_value( " 42 " ).unwrap(), Value ::Number( 42 ) );
+  assert_eq!( typed( "b", ParameterKind ::Bool ).parse_value( "yes" ).unwrap(), Value ::Bool( true ) );
+  assert_eq!( typed( "l", ParameterKind ::List ).parse_value( "a, b,,c" ).unwrap(), Value ::List( vec![ "a".into(), "b".into(), "c".into() ] ) );
+
+  let map = typed( "m", ParameterKind ::Map { fields: vec![ typed( "port", ParameterKind ::Integer { min: None, max: None } ) ] } );
+  assert_eq!( map.parse_value( r#"{ "port": 80 }"# ).unwrap(), Value ::Map( [ ( "port".to_string(), Value ::Number( 80 ) ) ].into() ) );
+  assert!( map.parse_value( "[1]" ).is_err() );
+}
+
+#[ test ]
+fn archive_materialization_rejects_invalid_values()
+{
+  let mut archive = TemplateArchive ::new( "service" );
+  archive.add_text_file( PathBuf ::from( "port.txt" ), "{{port}}", WriteMode ::Rewrite );
+  archive.add_parameter( typed( "port", ParameterKind ::Integer { min: Some( 1024 ), max: None } ) );
+  archive.set_value( "port", Value ::Number( 80 ) );
+
+  let mut fs = MemoryFileSystem ::new();
+  let err = archive.materialize_with_components( Path ::new( "/out" ), &HandlebarsRenderer ::new(), &mut fs ).unwrap_err();
+
+  assert_eq!( invalid_parameter( &err ), "port" );
+  assert!( !fs.exists( Path ::new( "/out/port.txt" ) ) );
+}
+
+#[ test ]
+fn prompt_asks_needs_prompt_and_missing_mandatory_only()
+{
+  let params = Parameters
+  {
+    descriptors: vec!
+    [
+      typed( "name", ParameterKind ::String { pattern: None } ),
+      typed( "license", ParameterKind ::Choice { options: vec![ "MIT".into() ] } ),
+      ParameterDescriptor { is_mandatory: false, ..typed( "notes", ParameterKind ::String { pattern: None } ) },
+      ParameterDescriptor { is_mandatory: false, ..typed( "tags", ParameterKind ::List ) },
+    ],
+  };
+
+  let mut values = Values ::new();
+  values.insert( "license", Value ::String( "MIT".into() ) );
+  values.insert_none( "tags" );
+
+  let mut prompter = ScriptedPrompter ::new().answer( "name", "demo" ).answer( "tags", "cli,io" );
+  let asked = params.prompt_missing( &mut values, &mut prompter ).unwrap();
+
+  assert_eq!( asked, vec![ "name".to_string(), "tags".to_string() ] );
+  assert_eq!( values.get( "name" ), Some( &Value ::String( "demo".into() ) ) );
+  assert_eq!( values.get( "tags" ), Some( &Value ::List( vec![ "cli".into(), "io".into() ] ) ) );
+  assert!( !values.has_value( "notes" ) );
+}
+
+#[ test ]
+fn prompt_reasks_with_reason_then_gives_up()
+{
+  let params = Parameters { descriptors: vec![ typed( "port", ParameterKind ::Integer { min: Some( 1 ), max: Some( 10 ) } ) ] };
+
+  let mut values = Values ::new();
+  let mut prompter = ScriptedPrompter ::new().answer( "port", "99" ).answer( "port", "7" );
+  params.prompt_missing( &mut values, &mut prompter ).unwrap();
+  assert_eq!( values.get( "port" ), Some( &Value ::Number( 7 ) ) );
+  assert_eq!( prompter.asked(), [ "port", "port" ] );
+
+  let mut values = Values ::new();
+  let mut prompter = ScriptedPrompter ::new().answer( "port", "a" ).answer( "port", "b" ).answer( "port", "c" );
+  let err = params.prompt_missing( &mut values, &mut prompter ).unwrap_err();
+  assert_eq!( invalid_parameter( &err ), "port" );
+  assert!( err.to_string().contains( "'c' is not an integer" ), "{err}" );
+  assert_eq!( prompter.asked().len(), prompt ::MAX_ATTEMPTS );
+}
+
+#[ test ]
+fn prompt_empty_answer_takes_default_and_maps_ask_per_field()
+{
+  let params = Parameters
+  {
+    descriptors: vec!
+    [
+      typed( "database", ParameterKind ::Map
+      {
+        fields: vec!
+        [
+          typed( "host", ParameterKind ::String { pattern: None } ),
+          ParameterDescriptor { default_value: Some( "5432".into() ), ..typed( "port", ParameterKind ::Integer { min: None, max: None } ) },
+        ],
+      }),
+    ],
+  };
+
+  let mut values = Values ::new();
+  values.insert_none( "database" );
+  let mut prompter = ScriptedPrompter ::new().answer( "database.host", "db" ).answer( "database.port", "" );
+  params.prompt_missing( &mut values, &mut prompter ).unwrap();
+
+  assert_eq!( prompter.asked(), [ "database.host", "database.port" ] );
+  let expected = Value ::Map( [ ( "host".to_string(), Value ::String( "db".into() ) ), ( "port".to_string(), Value ::Number( 5432 ) ) ].into() );
+  assert_eq!( values.get( "database" ), Some( &expected ) );
+}
+
+#[ test ]
+fn terminal_prompter_shows_question_and_rejection()
+{
+  let params = Parameters { descriptors: vec![ typed( "enabled", ParameterKind ::Bool ) ] };
+  let mut output = Vec ::new();
+  let mut values = Values ::new();
+
+  let mut prompter = TerminalPrompter ::new( "maybe\ny\n".as_bytes(), &mut output );
+  params.prompt_missing( &mut values, &mut prompter ).unwrap();
+
+  assert_eq!( values.get( "enabled" ), Some( &Value ::Bool( true ) ) );
+  let transcript = String ::from_utf8( output ).unwrap();
+  assert_eq!( transcript, "enabled (true/false):   'maybe' is not a boolean (true/false)\nenabled (true/false): " );
+}
+
+#[ cfg( feature = "json" ) ]
+#[ test ]
+fn kind_survives_json_roundtrip()
+{
+  let mut archive = TemplateArchive ::new( "roundtrip" );
+  archive.add_parameter( typed( "port", ParameterKind ::Integer { min: Some( 1 ), max: None } ) );
+  archive.add_parameter( typed( "license", ParameterKind ::Choice { options: vec![ "MIT".into() ] } ) );
+
+  let json = archive.to_json().unwrap();
+  assert!( json.contains( r#""kind":{"type":"integer","min":1}"# ), "{json}" );
+
+  let restored = TemplateArchive ::from_json( &json ).unwrap();
+  assert_eq!( restored.parameters.descriptors, archive.parameters.descriptors );
+}
@@ -331,6 +331,7 @@ fn workflow_roundtrip_with_parameters()
     is_mandatory: true,
     default_value: None,
     description: Some( "Application name".into() ),
+    kind: None,
   });
 
   archive.add_parameter( ParameterDescriptor
@@ -339,6 +340,7 @@ fn workflow_roundtrip_with_parameters()
     is_mandatory: true,
     default_value: Some( "1.0.0".into() ),
     description: None,
+    kind: None,
   });
 
   // Set values
@@ -0,0 +1,220 @@
+/// Tests for merge and create-only write modes (docs/feature/009)
+use super :: *;
+use genfile_core ::merge :: { merge_json, merge_yaml, merge_regions };
+use std ::path :: { Path, PathBuf };
+
+//
+
+fn materialize_one( mode: WriteMode, existing: Option< &str >, template: &str ) -> ( MaterializationReport, MemoryFileSystem )
+{
+  let mut archive = TemplateArchive ::new( "write-modes" );
+  archive.add_text_file( PathBuf ::from( "target" ), template, mode );
+  archive.set_value( "name", Value ::String( "demo".into() ) );
+
+  let mut fs = MemoryFileSystem ::new();
+  if let Some( content ) = existing
+  {
+    fs.write( Path ::new( "/out/target" ), content ).unwrap();
+  }
+
+  let report = archive.materialize_with_components( Path ::new( "/out" ), &HandlebarsRenderer ::new(), &mut fs ).unwrap();
+  ( report, fs )
+}
+
+#[ test ]
+fn json_merge_keeps_existing_keys_and_order()
+{
+  let existing = r#"{ "name": "app", "version": "1.0.0", "scripts": { "start": "node ." }, "files": [ "lib" ] }"#;
+  let rendered = r#"{ "scripts": { "lint": "eslint ." }, "files": [ "lib", "bin" ], "version": "2.0.0" }"#;
+
+  let merged = merge_json( existing, rendered ).unwrap();
+
+  let expected = "{\n  \"name\": \"app\",\n  \"version\": \"2.0.0\",\n  \"scripts\": {\n    \"start\": \"node .\",\n    \"lint\": \"eslint .\"\n  },\n  \"files\": [\n    \"lib\",\n    \"bin\"\n  ]\n}";
+  assert_eq!( merged, expected );
+}
+
+#[ test ]
+fn json_merge_rejects_invalid_input()
+{
+  let err = merge_json( "{ not json", "{}" ).unwrap_err();
+  assert!( err.to_string().contains( "existing file is not valid JSON" ), "{err}" );
+
+  let err = merge_json( "{}", "[ unterminated" ).unwrap_err();
+  assert!( err.to_string().contains( "rendered template is not valid JSON" ), "{err}" );
+}
+
+#[ test ]
+fn yaml_merge_appends_jobs_and_steps()
+{
+  let existing = "name: ci\njobs:\n  test:\n    steps:\n    - run: cargo test\n";
+  let rendered = "jobs:\n  test:\n    steps:\n    - run: cargo test\n    - run: cargo clippy\n  docs:\n    steps:\n    - run: cargo doc\n";
+
+  let merged = merge_yaml( existing, rendered ).unwrap();
+
+  let expected = "name: ci\njobs:\n  test:\n    steps:\n    - run: cargo test\n    - run: cargo clippy\n  docs:\n    steps:\n    - run: cargo doc\n";
+  assert_eq!( merged, expected );
+}
+
+#[ test ]
+fn regions_preserve_user_edits_outside_markers()
+{
+  let existing = "\
+use std::fmt;
+// user code
+// genfile:begin routes
+fn old() {}
+// genfile:end routes
+fn main() {}
+";
+  let rendered = "\
+// genfile:begin routes
+fn index() {}
+fn about() {}
+// genfile:end routes
+";
+
+  let merged = merge_regions( existing, rendered ).unwrap();
+
+  assert_eq!( merged, "\
+use std::fmt;
+// user code
+// genfile:begin routes
+fn index() {}
+fn about() {}
+// genfile:end routes
+fn main() {}
+" );
+}
+
+#[ test ]
+fn regions_missing_from_target_are_appended()
+{
+  let existing = "# user config\n# genfile:begin a\nold\n# genfile:end a\n";
+  let rendered = "# genfile:begin a\nnew\n# genfile:end a\n# genfile:begin b\nextra\n# genfile:end b\n";
+
+  let merged = merge_regions( existing, rendered ).unwrap();
+
+  assert_eq!( merged, "# user config\n# genfile:begin a\nnew\n# genfile:end a\n# genfile:begin b\nextra\n# genfile:end b\n" );
+}
+
+#[ test ]
+fn regions_reject_malformed_markers()
+{
+  let err = merge_regions( "text\n", "no markers\n" ).unwrap_err();
+  assert!( err.to_string().contains( "no `genfile:begin <name>` regions" ), "{err}" );
+
+  let err = merge_regions( "// genfile:begin a\n", "// genfile:begin a\nx\n// genfile:end a\n" ).unwrap_err();
+  assert!( err.to_string().contains( "never closed" ), "{err}" );
+
+  let err = merge_regions( "", "<!-- genfile:begin a -->\n<!-- genfile:begin b -->\n<!-- genfile:end b -->\n<!-- genfile:end a -->\n" ).unwrap_err();
+  assert!( err.to_string().contains( "nested" ), "{err}" );
+}
+
+#[ test ]
+fn materialize_create_only_skips_existing_file()
+{
+  let ( report, fs ) = materialize_one( WriteMode ::CreateOnly, Some( "user content" ), "generated {{name}}" );
+
+  assert_eq!( report.files_skipped, vec![ PathBuf ::from( "target" ) ] );
+  assert!( report.files_created.is_empty() );
+  assert_eq!( report.total_bytes_written, 0 );
+  assert_eq!( fs.read( Path ::new( "/out/target" ) ).unwrap(), "user content" );
+
+  let ( report, fs ) = materialize_one( WriteMode ::CreateOnly, None, "generated {{name}}" );
+  assert_eq!( report.files_created, vec![ PathBuf ::from( "target" ) ] );
+  assert_eq!( fs.read( Path ::new( "/out/target" ) ).unwrap(), "generated demo" );
+}
+
+#[ test ]
+fn materialize_reports_merged_files()
+{
+  let ( report, fs ) = materialize_one( WriteMode ::JsonMerge, Some( "{ \"private\": true }\n" ), "{ \"name\": \"{{name}}\" }" );
+
+  assert_eq!( report.files_merged, vec![ PathBuf ::from( "target" ) ] );
+  assert!( report.files_updated.is_empty() );
+  let written = fs.read( Path ::new( "/out/target" ) ).unwrap();
+  assert_eq!( written, "{\n  \"private\": true,\n  \"name\": \"demo\"\n}\n" );
+  assert_eq!( report.total_bytes_written, written.len() );
+}
+
+#[ test ]
+fn materialize_merge_error_names_file()
+{
+  let mut archive = TemplateArchive ::new( "write-modes" );
+  archive.add_text_file( PathBuf ::from( "ci.yml" ), "jobs: [", WriteMode ::YamlMerge );
+
+  let mut fs = MemoryFileSystem ::new();
+  fs.write( Path ::new( "/out/ci.yml" ), "jobs: {}\n" ).unwrap();
+
+  let err = archive.materialize_with_components( Path ::new( "/out" ), &HandlebarsRenderer ::new(), &mut fs ).unwrap_err();
+  assert!( err.to_string().contains( "/out/ci.yml: rendered template is not valid YAML" ), "{err}" );
+}
+
+#[ test ]
+fn template_materialize_honours_marked_regions()
+{
+  let mut fs = MemoryFileSystem ::new();
+  fs.write( Path ::new( "/t/lib.hbs" ), "// genfile:begin mods\nmod {{name}};\n// genfile:end mods\n" ).unwrap();
+  fs.write( Path ::new( "/src/lib.rs" ), "//! Crate docs\n// genfile:begin mods\n// genfile:end mods\npub fn keep() {}\n" ).unwrap();
+
+  let mut template: Template< Value, _, _ > = Template ::new( HandlebarsRenderer ::new(), fs );
+  template.insert_value( "name", Value ::String( "demo".into() ) );
+  template.add_file( FileDescriptor
+  {
+    file_path: PathBuf ::from( "/src/lib.rs" ),
+    template_path: PathBuf ::from( "/t/lib.hbs" ),
+    write_mode: WriteMode ::MarkedRegions,
+  });
+  template.materialize().unwrap();
+
+  let written = template.filesystem().read( Path ::new( "/src/lib.rs" ) ).unwrap();
+  assert_eq!( written, "//! Crate docs\n// genfile:begin mods\nmod demo;\n// genfile:end mods\npub fn keep() {}\n" );
+}
+
+#[ cfg( feature = "external_content" ) ]
+#[ test ]
+fn storage_materialize_loads_existing_content()
+{
+  use std ::collections ::HashMap;
+
+  #[ derive( Default ) ]
+  struct MapStorage( HashMap< PathBuf, FileContent > );
+
+  impl ContentStorage for MapStorage
+  {
+    fn store( &mut self, path: &Path, content: &FileContent ) -> Result< (), Error >
+    {
+      self.0.insert( path.to_path_buf(), content.clone() );
+      Ok( () )
+    }
+
+    fn load( &self, path: &Path ) -> Result< Option< FileContent >, Error >
+    {
+      Ok( self.0.get( path ).cloned() )
+    }
+  }
+
+  let mut archive = TemplateArchive ::new( "write-modes" );
+  archive.add_text_file( PathBuf ::from( "package.json" ), r#"{ "scripts": { "test": "jest" } }"#, WriteMode ::JsonMerge );
+  archive.add_text_file( PathBuf ::from( "readme.md" ), "generated", WriteMode ::CreateOnly );
+  archive.add_binary_file( PathBuf ::from( "logo.png" ), vec![ 0x89, 0x50 ] );
+
+  let mut storage = MapStorage ::default();
+  storage.0.insert( PathBuf ::from( "/out/package.json" ), FileContent ::Text( r#"{ "name": "app" }"#.into() ) );
+  storage.0.insert( PathBuf ::from( "/out/readme.md" ), FileContent ::Text( "mine".into() ) );
+
+  let report = archive
+    .materialize_with_storage( Path ::new( "/out" ), &HandlebarsRenderer ::new(), &mut storage, &DefaultContentResolver ::new() )
+    .unwrap();
+
+  assert_eq!( report.files_merged, vec![ PathBuf ::from( "package.json" ) ] );
+  assert_eq!( report.files_skipped, vec![ PathBuf ::from( "readme.md" ) ] );
+  assert_eq!( report.files_created, vec![ PathBuf ::from( "logo.png" ) ] );
+  let text = | path: &str | match &storage.0[ Path ::new( path ) ]
+  {
+    FileContent ::Text( text ) => text.clone(),
+    FileContent ::Binary( _ ) => panic!( "Expected text at {path}" ),
+  };
+  assert_eq!( text( "/out/package.json" ), "{\n  \"name\": \"app\",\n  \"scripts\": {\n    \"test\": \"jest\"\n  }\n}" );
+  assert_eq!( text( "/out/readme.md" ), "mine" );
+}
@@ -19,6 +19,12 @@ mod_interface! {
   // #[ cfg( not( feature = "no_std" ) ) ]
   layer process;
 
+  /// Commands connected by pipes without a shell.
+  layer pipeline;
+
+  /// Parallel execution of many commands with bounded concurrency.
+  layer pool;
+
   /// Inspection of running environment.
   // #[ cfg( not( feature = "no_std" ) ) ]
   layer environment;
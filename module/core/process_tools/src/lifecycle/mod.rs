@@ -12,4 +12,8 @@ crate ::mod_interface!
   /// Unix process daemonization and PID file management.
   #[ cfg( unix ) ]
   layer daemon;
+
+  /// Supervision of long-running child services with restarts, readiness checks and log rotation.
+  #[ cfg( all( unix, feature = "process_supervisor" ) ) ]
+  layer supervisor;
 }
@@ -0,0 +1,685 @@
+/// Define a private namespace for all its items.
+#[ allow( clippy ::std_instead_of_alloc, clippy ::std_instead_of_core ) ]
+mod private
+{
+  use std ::
+  {
+    fs ::{ self, File, OpenOptions },
+    io ::{ self, BufRead, BufReader, Read, Write },
+    net ::{ SocketAddr, TcpStream },
+    path ::PathBuf,
+    process ::{ Child, ExitStatus, Stdio },
+    sync ::{ Arc, Condvar, Mutex, MutexGuard, PoisonError, atomic ::{ AtomicBool, Ordering } },
+    thread,
+    time ::{ Duration, Instant },
+  };
+  use std ::os ::unix ::process ::{ CommandExt, ExitStatusExt };
+  use former ::Former;
+  use regex ::Regex;
+  use crate ::process ::{ self, Input, Run };
+  use crate ::lifecycle ::signal;
+
+  /// When a [`Service`] is restarted after its process exits.
+  #[ derive( Debug, Clone, Copy, PartialEq, Eq ) ]
+  pub enum Restart
+  {
+    /// Never restart; a failed exit leaves the service `Failed`.
+    Never,
+    /// Restart after a non-zero exit or a signal; a clean exit leaves the service `Stopped`.
+    OnFailure,
+    /// Restart after every exit.
+    Always,
+  }
+
+  /// Condition that marks a started [`Service`] as ready.
+  #[ derive( Debug, Clone ) ]
+  pub enum Readiness
+  {
+    /// A TCP connection to the address succeeds.
+    Port( SocketAddr ),
+    /// A line of stdout or stderr matches the expression.
+    LogLine( Regex ),
+    /// The file exists.
+    File( PathBuf ),
+  }
+
+  /// Lifecycle phase of a supervised service.
+  #[ derive( Debug, Clone, Copy, PartialEq, Eq ) ]
+  pub enum ServiceStatus
+  {
+    /// The process is running but has not passed its readiness check yet.
+    Starting,
+    /// The process is running and passed its readiness check, or has none.
+    Ready,
+    /// The process exited and is waiting out the restart delay.
+    Backoff,
+    /// The process exited cleanly or was shut down, and will not be restarted.
+    Stopped,
+    /// The process failed and will not be restarted.
+    Failed,
+  }
+
+  /// Snapshot of a supervised service.
+  #[ derive( Debug, Clone, PartialEq, Eq ) ]
+  pub struct ServiceState
+  {
+    /// Current phase.
+    pub status : ServiceStatus,
+    /// PID of the running process; `None` while no process runs.
+    pub pid : Option< u32 >,
+    /// Number of restarts so far.
+    pub restarts : u32,
+    /// Exit code of the previous process, if it exited with one.
+    pub last_exit_code : Option< i32 >,
+    /// Signal that terminated the previous process, if any.
+    pub last_signal : Option< i32 >,
+    /// Why the last spawn failed, if it did.
+    pub last_error : Option< String >,
+  }
+
+  ///
+  /// A long-running command kept alive by a [`Supervisor`].
+  ///
+  /// The command is spawned from `run` each time the service starts, with its
+  /// binary, arguments, working directory, environment and `stdin`. The line
+  /// callbacks, `timeout` and `joining_streams` of `run` are not used: stdout
+  /// and stderr both go to `log_file`, or are discarded without one.
+  ///
+  #[ derive( Debug, Former ) ]
+  pub struct Service
+  {
+    /// Unique name within the supervisor.
+    name : String,
+    /// Options of the command, formed with `Run::former()...form()`.
+    run : Run,
+    /// Restart policy.
+    #[ former( default = Restart ::OnFailure ) ]
+    restart : Restart,
+    /// Delay before the first restart; doubles after each further crash.
+    #[ former( default = Duration ::from_millis( 100 ) ) ]
+    backoff_initial : Duration,
+    /// Upper bound of the restart delay. An instance that stays up this long resets the delay.
+    #[ former( default = Duration ::from_secs( 30 ) ) ]
+    backoff_max : Duration,
+    /// Restarts allowed before the service is marked `Failed`; unlimited when `None`.
+    max_restarts : Option< u32 >,
+    /// File receiving stdout and stderr lines of every instance.
+    log_file : Option< PathBuf >,
+    /// Size at which the log file is rotated.
+    #[ former( default = 10_u64 * 1024 * 1024 ) ]
+    log_max_bytes : u64,
+    /// Rotated logs kept as `<log>.1` (newest) to `<log>.<log_keep>`.
+    #[ former( default = 5_usize ) ]
+    log_keep : usize,
+    /// Check the service must pass after each start before it counts as `Ready`.
+    readiness : Option< Readiness >,
+    /// How long [`Supervisor::start`] waits for readiness.
+    #[ former( default = Duration ::from_secs( 30 ) ) ]
+    ready_timeout : Duration,
+    /// Grace period between SIGTERM and SIGKILL on shutdown.
+    #[ former( default = Duration ::from_secs( 10 ) ) ]
+    stop_timeout : Duration,
+  }
+
+  /// State shared between a [`Supervisor`] and the monitor thread of one service.
+  struct Shared
+  {
+    state : Mutex< ( ServiceState, bool ) >,
+    changed : Condvar,
+  }
+
+  impl Shared
+  {
+    fn lock( &self ) -> MutexGuard< '_, ( ServiceState, bool ) >
+    {
+      self.state.lock().unwrap_or_else( PoisonError ::into_inner )
+    }
+
+    fn update( &self, change : impl FnOnce( &mut ServiceState ) )
+    {
+      change( &mut self.lock().0 );
+      self.changed.notify_all();
+    }
+
+    fn stopping( &self ) -> bool
+    {
+      self.lock().1
+    }
+  }
+
+  /// Supervised service: its name, shared state and monitor thread.
+  struct Supervised
+  {
+    name : String,
+    shared : Arc< Shared >,
+    monitor : Option< thread ::JoinHandle< () > >,
+  }
+
+  ///
+  /// Keeps long-running child processes alive.
+  ///
+  /// Each service gets a monitor thread that spawns the process, checks its
+  /// readiness, writes its output to a rotating log, and restarts it with
+  /// exponential backoff according to its [`Restart`] policy. Dropping the
+  /// supervisor shuts every service down.
+  ///
+  /// Each process leads its own process group; signals and shutdown reach the
+  /// whole group, so children of a shell script stop with it.
+  ///
+  /// # Examples
+  ///
+  /// ```rust,no_run
+  /// use process_tools ::lifecycle ::supervisor ::{ Readiness, Service, Supervisor };
+  /// use process_tools ::process ::Run;
+  ///
+  /// let mut supervisor = Supervisor ::new();
+  /// supervisor.start
+  /// (
+  ///   Service ::former()
+  ///   .name( "api" )
+  ///   .run( Run ::former().bin_path( "./api-server" ).current_path( "." ).form() )
+  ///   .readiness( Readiness ::Port( "127.0.0.1:8080".parse().unwrap() ) )
+  ///   .log_file( "/var/log/api.log" )
+  ///   .form()
+  /// ).expect( "api should become ready" );
+  ///
+  /// supervisor.signal( "api", "SIGHUP" ).expect( "reload" );
+  /// supervisor.shutdown().expect( "graceful shutdown" );
+  /// ```
+  ///
+  #[ derive( Default ) ]
+  pub struct Supervisor
+  {
+    services : Vec< Supervised >,
+  }
+
+  impl core ::fmt ::Debug for Supervisor
+  {
+    fn fmt( &self, f : &mut core ::fmt ::Formatter< '_ > ) -> core ::fmt ::Result
+    {
+      f.debug_map().entries( self.services.iter().map( | service | ( &service.name, service.shared.lock().0.clone() ) ) ).finish()
+    }
+  }
+
+  impl Supervisor
+  {
+    /// Create a supervisor without services.
+    #[ must_use ]
+    pub fn new() -> Self
+    {
+      Self ::default()
+    }
+
+    ///
+    /// Start supervising `service` and wait until it is ready.
+    ///
+    /// Crashes before readiness are restarted as usual while waiting.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AlreadyExists` if a service with the same name is supervised,
+    /// `TimedOut` if the service is not ready within `ready_timeout`, or `Other`
+    /// if it stops or fails first. A service that does not become ready is shut
+    /// down and not kept.
+    ///
+    pub fn start( &mut self, service : Service ) -> io ::Result< () >
+    {
+      if self.services.iter().any( | supervised | supervised.name == service.name )
+      {
+        return Err( io ::Error ::new( io ::ErrorKind ::AlreadyExists, format!( "service `{}` is already supervised", service.name ) ) );
+      }
+
+      let name = service.name.clone();
+      let ready_timeout = service.ready_timeout;
+      let log = match &service.log_file
+      {
+        Some( path ) => Some( Arc ::new( Mutex ::new( Log ::open( path.clone(), service.log_max_bytes, service.log_keep )? ) ) ),
+        None => None,
+      };
+      let shared = Arc ::new( Shared
+      {
+        state : Mutex ::new( ( ServiceState
+        {
+          status : ServiceStatus ::Starting,
+          pid : None,
+          restarts : 0,
+          last_exit_code : None,
+          last_signal : None,
+          last_error : None,
+        }, false ) ),
+        changed : Condvar ::new(),
+      });
+      let monitor =
+      {
+        let shared = Arc ::clone( &shared );
+        thread ::Builder ::new()
+        .name( format!( "supervisor-{name}" ) )
+        .spawn( move || monitor( &service, &shared, log.as_ref() ) )?
+      };
+      self.services.push( Supervised { name : name.clone(), shared : Arc ::clone( &shared ), monitor : Some( monitor ) } );
+
+      let deadline = Instant ::now() + ready_timeout;
+      let mut guard = shared.lock();
+      loop
+      {
+        let problem = match guard.0.status
+        {
+          ServiceStatus ::Ready => return Ok( () ),
+          ServiceStatus ::Stopped | ServiceStatus ::Failed => io ::Error ::other( format!( "service `{name}` ended before becoming ready: {:?}", guard.0 ) ),
+          ServiceStatus ::Starting | ServiceStatus ::Backoff =>
+          {
+            let now = Instant ::now();
+            if now < deadline
+            {
+              guard = shared.changed.wait_timeout( guard, deadline - now ).unwrap_or_else( PoisonError ::into_inner ).0;
+              continue;
+            }
+            io ::Error ::new( io ::ErrorKind ::TimedOut, format!( "service `{name}` not ready after {ready_timeout:?}" ) )
+          }
+        };
+        drop( guard );
+        self.stop( &name )?;
+        return Err( problem );
+      }
+    }
+
+    /// Snapshot of the named service, or `None` if it is not supervised.
+    #[ must_use ]
+    pub fn state( &self, name : &str ) -> Option< ServiceState >
+    {
+      self.find( name ).map( | supervised | supervised.shared.lock().0.clone() )
+    }
+
+    /// Names of supervised services in start order.
+    #[ must_use ]
+    pub fn services( &self ) -> Vec< &str >
+    {
+      self.services.iter().map( | supervised | supervised.name.as_str() ).collect()
+    }
+
+    ///
+    /// Forward a signal, given by name as in [`signal::signal_number`], to a service.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` for an unknown service, `InvalidInput` for an unknown
+    /// signal name, `Other` if no process of the service is running, or the OS
+    /// error of `kill`.
+    ///
+    pub fn signal( &self, name : &str, signal_name : &str ) -> io ::Result< () >
+    {
+      let number = signal ::signal_number( signal_name )
+      .ok_or_else( || io ::Error ::new( io ::ErrorKind ::InvalidInput, format!( "unknown signal `{signal_name}`" ) ) )?;
+      let supervised = self.find( name )
+      .ok_or_else( || io ::Error ::new( io ::ErrorKind ::NotFound, format!( "no service `{name}`" ) ) )?;
+      let pid = supervised.shared.lock().0.pid
+      .ok_or_else( || io ::Error ::other( format!( "service `{name}` has no running process" ) ) )?;
+      signal_group( pid, number )
+    }
+
+    ///
+    /// Forward a signal to every service that has a running process.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidInput` for an unknown signal name, or the first OS error of `kill`.
+    ///
+    pub fn signal_all( &self, signal_name : &str ) -> io ::Result< () >
+    {
+      let number = signal ::signal_number( signal_name )
+      .ok_or_else( || io ::Error ::new( io ::ErrorKind ::InvalidInput, format!( "unknown signal `{signal_name}`" ) ) )?;
+      for supervised in &self.services
+      {
+        if let Some( pid ) = supervised.shared.lock().0.pid
+        {
+          signal_group( pid, number )?;
+        }
+      }
+      Ok( () )
+    }
+
+    ///
+    /// Shut a service down gracefully and stop supervising it.
+    ///
+    /// The process gets SIGTERM, then SIGKILL if it is still running after
+    /// `stop_timeout`. Pending restarts are cancelled.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` for an unknown service, or `Other` if its monitor thread panicked.
+    ///
+    pub fn stop( &mut self, name : &str ) -> io ::Result< () >
+    {
+      let index = self.services.iter().position( | supervised | supervised.name == name )
+      .ok_or_else( || io ::Error ::new( io ::ErrorKind ::NotFound, format!( "no service `{name}`" ) ) )?;
+      let mut supervised = self.services.remove( index );
+      supervised.stop()
+    }
+
+    ///
+    /// Shut every service down, in reverse start order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Other` if a monitor thread panicked; remaining services are still shut down.
+    ///
+    pub fn shutdown( &mut self ) -> io ::Result< () >
+    {
+      let mut result = Ok( () );
+      while let Some( mut supervised ) = self.services.pop()
+      {
+        let stopped = supervised.stop();
+        if result.is_ok()
+        {
+          result = stopped;
+        }
+      }
+      result
+    }
+
+    fn find( &self, name : &str ) -> Option< &Supervised >
+    {
+      self.services.iter().find( | supervised | supervised.name == name )
+    }
+  }
+
+  impl Drop for Supervisor
+  {
+    fn drop( &mut self )
+    {
+      _ = self.shutdown();
+    }
+  }
+
+  impl Supervised
+  {
+    fn stop( &mut self ) -> io ::Result< () >
+    {
+      self.shared.lock().1 = true;
+      self.shared.changed.notify_all();
+      match self.monitor.take().map( thread ::JoinHandle ::join )
+      {
+        Some( Err( _ ) ) => Err( io ::Error ::other( format!( "monitor of service `{}` panicked", self.name ) ) ),
+        _ => Ok( () ),
+      }
+    }
+  }
+
+  /// Supervise one service until it ends for good or shutdown is requested.
+  fn monitor( service : &Service, shared : &Shared, log : Option< &Arc< Mutex< Log > > > )
+  {
+    let mut delay = service.backoff_initial;
+    loop
+    {
+      let started = Instant ::now();
+      let outcome = match spawn( service, log )
+      {
+        Ok( ( child, matched ) ) => supervise( service, shared, child, &matched ),
+        Err( error ) =>
+        {
+          shared.update( | state | state.last_error = Some( error.to_string() ) );
+          None
+        }
+      };
+      if started.elapsed() >= service.backoff_max
+      {
+        delay = service.backoff_initial;
+      }
+
+      let success = outcome.is_some_and( | status | status.success() );
+      let stopping = shared.stopping();
+      let wanted = match service.restart
+      {
+        Restart ::Never => false,
+        Restart ::OnFailure => !success,
+        Restart ::Always => true,
+      };
+      let allowed = service.max_restarts.is_none_or( | max | shared.lock().0.restarts < max );
+      if stopping || !wanted || !allowed
+      {
+        let status = if stopping || success { ServiceStatus ::Stopped } else { ServiceStatus ::Failed };
+        shared.update( | state | state.status = status );
+        return;
+      }
+
+      shared.update( | state | state.status = ServiceStatus ::Backoff );
+      let guard = shared.lock();
+      let ( guard, _ ) = shared.changed.wait_timeout_while( guard, delay, | ( _, stopping ) | !*stopping ).unwrap_or_else( PoisonError ::into_inner );
+      if guard.1
+      {
+        drop( guard );
+        shared.update( | state | state.status = ServiceStatus ::Stopped );
+        return;
+      }
+      drop( guard );
+      shared.update( | state | state.restarts += 1 );
+      delay = ( delay * 2 ).min( service.backoff_max );
+    }
+  }
+
+  /// Spawn one instance of the service, with output readers feeding the log and the log-line check.
+  fn spawn( service : &Service, log : Option< &Arc< Mutex< Log > > > ) -> io ::Result< ( Child, Arc< AtomicBool > ) >
+  {
+    let run = &service.run;
+    let mut command = process ::command( run );
+    if let Some( file ) = process ::input_file( run.stdin.as_ref() ).map_err( io ::Error ::other )?
+    {
+      command.stdin( file );
+    }
+    else if run.stdin.is_some()
+    {
+      command.stdin( Stdio ::piped() );
+    }
+    command.process_group( 0 );
+
+    let mut child = command.spawn()?;
+    if let ( Some( Input ::Bytes( bytes ) ), Some( stdin ) ) = ( &run.stdin, child.stdin.take() )
+    {
+      process ::feed( stdin, bytes.clone() );
+    }
+
+    let matched = Arc ::new( AtomicBool ::new( false ) );
+    let pattern = match &service.readiness
+    {
+      Some( Readiness ::LogLine( pattern ) ) => Some( pattern.clone() ),
+      _ => None,
+    };
+    let streams : [ Option< Box< dyn Read + Send > >; 2 ] =
+    [
+      child.stdout.take().map( | out | Box ::new( out ) as Box< dyn Read + Send > ),
+      child.stderr.take().map( | err | Box ::new( err ) as Box< dyn Read + Send > ),
+    ];
+    for stream in streams.into_iter().flatten()
+    {
+      let ( log, pattern, matched ) = ( log.cloned(), pattern.clone(), Arc ::clone( &matched ) );
+      thread ::spawn( move || drain( stream, log.as_ref(), pattern.as_ref(), &matched ) );
+    }
+    Ok( ( child, matched ) )
+  }
+
+  /// Copy output lines to the log and flag the first line matching `pattern`.
+  fn drain( stream : impl Read, log : Option< &Arc< Mutex< Log > > >, pattern : Option< &Regex >, matched : &AtomicBool )
+  {
+    let mut reader = BufReader ::new( stream );
+    let mut line = Vec ::new();
+    while reader.read_until( b'\n', &mut line ).is_ok_and( | read | read > 0 )
+    {
+      if let Some( log ) = log
+      {
+        // A log that cannot be written must not stop the service; the line is dropped.
+        _ = log.lock().unwrap_or_else( PoisonError ::into_inner ).write( &line );
+      }
+      if pattern.is_some_and( | pattern | pattern.is_match( String ::from_utf8_lossy( &line ).trim_end() ) )
+      {
+        matched.store( true, Ordering ::SeqCst );
+      }
+      line.clear();
+    }
+  }
+
+  /// Watch a running instance until it exits or is shut down; `None` only if waiting failed.
+  fn supervise( service : &Service, shared : &Shared, mut child : Child, matched : &AtomicBool ) -> Option< ExitStatus >
+  {
+    let pid = child.id();
+    let ready_now = service.readiness.is_none();
+    shared.update( | state |
+    {
+      state.pid = Some( pid );
+      state.last_error = None;
+      state.status = if ready_now { ServiceStatus ::Ready } else { ServiceStatus ::Starting };
+    });
+
+    let mut ready = ready_now;
+    let status = loop
+    {
+      match child.try_wait()
+      {
+        Ok( Some( status ) ) => break Some( status ),
+        Ok( None ) => {}
+        Err( _ ) => break None,
+      }
+      if !ready && is_ready( service.readiness.as_ref(), matched )
+      {
+        ready = true;
+        shared.update( | state | state.status = ServiceStatus ::Ready );
+      }
+      let guard = shared.lock();
+      let ( guard, _ ) = shared.changed.wait_timeout_while( guard, Duration ::from_millis( 20 ), | ( _, stopping ) | !*stopping )
+      .unwrap_or_else( PoisonError ::into_inner );
+      if guard.1
+      {
+        drop( guard );
+        break terminate( &mut child, service.stop_timeout );
+      }
+    };
+
+    shared.update( | state |
+    {
+      state.pid = None;
+      state.last_exit_code = status.and_then( | status | status.code() );
+      state.last_signal = status.and_then( | status | status.signal() );
+    });
+    status
+  }
+
+  /// Whether the readiness check passes now.
+  fn is_ready( readiness : Option< &Readiness >, matched : &AtomicBool ) -> bool
+  {
+    match readiness
+    {
+      None => true,
+      Some( Readiness ::Port( address ) ) => TcpStream ::connect_timeout( address, Duration ::from_millis( 100 ) ).is_ok(),
+      Some( Readiness ::LogLine( _ ) ) => matched.load( Ordering ::SeqCst ),
+      Some( Readiness ::File( path ) ) => path.exists(),
+    }
+  }
+
+  /// SIGTERM the process group, then SIGKILL it if it outlives `grace`.
+  fn terminate( child : &mut Child, grace : Duration ) -> Option< ExitStatus >
+  {
+    let pid = child.id();
+    if signal_group( pid, libc ::SIGTERM ).is_ok()
+    {
+      let deadline = Instant ::now() + grace;
+      while Instant ::now() < deadline
+      {
+        if let Ok( Some( status ) ) = child.try_wait()
+        {
+          return Some( status );
+        }
+        thread ::sleep( Duration ::from_millis( 20 ) );
+      }
+    }
+    _ = process ::terminate( pid, || child.kill() );
+    child.wait().ok()
+  }
+
+  /// Send `signal` to the process group led by `pid`.
+  #[ allow( unsafe_code ) ]
+  fn signal_group( pid : u32, signal : i32 ) -> io ::Result< () >
+  {
+    let group = i32 ::try_from( pid ).map_err( | _ | io ::Error ::new( io ::ErrorKind ::InvalidInput, format!( "invalid pid {pid}" ) ) )?;
+    // SAFETY: kill() only sends a signal. The negative pid addresses the process group
+    // the service was placed in at spawn, which contains no process of ours.
+    if unsafe { libc ::kill( -group, signal ) } == 0
+    {
+      Ok( () )
+    }
+    else
+    {
+      Err( io ::Error ::last_os_error() )
+    }
+  }
+
+  /// Append-only log file rotated by size.
+  struct Log
+  {
+    path : PathBuf,
+    file : File,
+    size : u64,
+    max_bytes : u64,
+    keep : usize,
+  }
+
+  impl Log
+  {
+    fn open( path : PathBuf, max_bytes : u64, keep : usize ) -> io ::Result< Self >
+    {
+      if let Some( parent ) = path.parent().filter( | parent | !parent.as_os_str().is_empty() )
+      {
+        fs ::create_dir_all( parent )?;
+      }
+      let file = OpenOptions ::new().create( true ).append( true ).open( &path )?;
+      let size = file.metadata()?.len();
+      Ok( Self { path, file, size, max_bytes, keep } )
+    }
+
+    /// Write one line, rotating first if it would push the file past `max_bytes`.
+    fn write( &mut self, line : &[ u8 ] ) -> io ::Result< () >
+    {
+      let length = line.len() as u64;
+      if self.size > 0 && self.size + length > self.max_bytes
+      {
+        self.rotate()?;
+      }
+      self.file.write_all( line )?;
+      self.size += length;
+      Ok( () )
+    }
+
+    /// Shift `<log>.N` to `<log>.N+1`, dropping the oldest, and start an empty log.
+    fn rotate( &mut self ) -> io ::Result< () >
+    {
+      let numbered = | n : usize |
+      {
+        let mut name = self.path.clone().into_os_string();
+        name.push( format!( ".{n}" ) );
+        PathBuf ::from( name )
+      };
+      if self.keep > 0
+      {
+        for n in ( 1 .. self.keep ).rev()
+        {
+          if numbered( n ).exists()
+          {
+            fs ::rename( numbered( n ), numbered( n + 1 ) )?;
+          }
+        }
+        fs ::rename( &self.path, numbered( 1 ) )?;
+      }
+      self.file = OpenOptions ::new().create( true ).write( true ).truncate( true ).open( &self.path )?;
+      self.size = 0;
+      Ok( () )
+    }
+  }
+}
+
+crate ::mod_interface!
+{
+  own use Restart;
+  own use Readiness;
+  own use ServiceStatus;
+  own use ServiceState;
+  own use Service;
+  own use Supervisor;
+}
@@ -0,0 +1,244 @@
+/// Define a private namespace for all its items.
+#[ allow( clippy ::std_instead_of_alloc, clippy ::std_instead_of_core ) ]
+mod private
+{
+  use std ::
+  {
+    process ::{ Child, ChildStdout, ExitStatus, Stdio },
+    time ::{ Duration, Instant },
+  };
+  use error_tools ::
+  {
+    untyped ::{ Error, Context, format_err },
+  };
+  use former ::Former;
+  use crate ::process ::{ self, Input, Report, Run };
+
+  /// Commands connected stdout to stdin, like `a | b | c`, run without a shell.
+  ///
+  /// Stages are [`Run`] options formed with `Run::former()...form()`. Each stage
+  /// keeps its own binary, arguments, working directory and environment. Only
+  /// the first stage may set `stdin`; the stdout line callback of the last stage
+  /// and the stderr line callbacks of all stages are called during execution.
+  /// The pipeline has one `timeout` for all stages; stages must not set
+  /// `timeout` or `joining_streams` themselves.
+  #[ derive( Debug, Former ) ]
+  pub struct Pipeline
+  {
+    /// Stages in order; the stdout of each stage becomes the stdin of the next.
+    stages : Vec< Run >,
+    /// Wall-clock limit for the whole pipeline; on expiry every stage is killed.
+    timeout : Option< Duration >,
+  }
+
+  impl PipelineFormer
+  {
+    /// Execute the configured pipeline, returning a [`Report`].
+    ///
+    /// # Returns
+    /// `Ok( Report )` if every stage exits with code zero, `Err( Report )` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[ cfg( unix ) ]
+    /// # {
+    /// use process_tools ::{ pipeline ::Pipeline, process ::Run };
+    ///
+    /// let report = Pipeline ::former()
+    /// .stages( vec!
+    /// [
+    ///   Run ::former().bin_path( "printf" ).args( vec![ "b\\na\\nb\\n".into() ] ).current_path( "." ).form(),
+    ///   Run ::former().bin_path( "sort" ).current_path( "." ).form(),
+    ///   Run ::former().bin_path( "uniq" ).current_path( "." ).form(),
+    /// ])
+    /// .run()
+    /// .expect( "pipeline should succeed" );
+    ///
+    /// assert_eq!( report.out, "a\nb\n" );
+    /// assert_eq!( report.command, "printf b\\na\\nb\\n | sort | uniq" );
+    /// # }
+    /// ```
+    pub fn run( self ) -> Result< Report, Report >
+    {
+      run( self.form() )
+    }
+  }
+
+  ///
+  /// Executes a pipeline, connecting the stdout of each stage to the stdin of the next.
+  ///
+  /// The report describes the pipeline as a whole: `command` joins the stages
+  /// with ` | `, `out` is the stdout of the last stage and `err` the stderr of
+  /// all stages in stage order. As with `pipefail` in a shell, the exit status
+  /// is that of the last stage that failed, or of the last stage if all succeeded.
+  ///
+  /// # Errors
+  /// Returns `Err( Report )` if:
+  /// - The pipeline has no stages, or a stage sets options a pipeline does not support.
+  /// - A stage cannot be spawned; stages already started are killed.
+  /// - A stage exits with a non-zero exit code or is killed by a signal.
+  /// - The pipeline timeout expires.
+  /// - Captured output is not valid UTF-8.
+  ///
+  pub fn run( pipeline : Pipeline ) -> Result< Report, Report >
+  {
+    let Pipeline { stages, timeout } = pipeline;
+    let report = Report
+    {
+      command : stages.iter().map( | stage | process ::command_line( stage ).trim_end().to_string() ).collect ::< Vec< _ > >().join( " | " ),
+      current_path : stages.first().map( | stage | stage.current_path.clone() ).unwrap_or_default(),
+      ..Report ::default()
+    };
+
+    let start = Instant ::now();
+    let stop = process ::Stop { deadline : timeout.map( | timeout | start + timeout ), cancel : None };
+    let execution = validate( &stages ).and_then( | () | execute( stages, stop ) );
+    process ::conclude( report, execution, start, timeout )
+  }
+
+  /// Reject stage options that have no meaning inside a pipeline.
+  fn validate( stages : &[ Run ] ) -> Result< (), Error >
+  {
+    if stages.is_empty()
+    {
+      return Err( format_err!( "Pipeline has no stages" ) );
+    }
+    for ( index, stage ) in stages.iter().enumerate()
+    {
+      let problem = if index > 0 && stage.stdin.is_some()
+      {
+        Some( "sets stdin, which only the first stage may do" )
+      }
+      else if stage.timeout.is_some()
+      {
+        Some( "sets its own timeout; set it on the pipeline" )
+      }
+      else if stage.joining_streams
+      {
+        Some( "joins streams, which a pipeline does not support" )
+      }
+      else
+      {
+        None
+      };
+      if let Some( problem ) = problem
+      {
+        return Err( format_err!( "Pipeline stage {} `{}` {problem}", index + 1, process ::command_line( stage ).trim_end() ) );
+      }
+    }
+    Ok( () )
+  }
+
+  /// Spawn all stages connected by pipes, wait for them and collect their output.
+  fn execute( stages : Vec< Run >, stop : process ::Stop< '_ > ) -> Result< process ::Execution, Error >
+  {
+    let count = stages.len();
+    let mut children : Vec< Child > = Vec ::with_capacity( count );
+    let mut previous : Option< ChildStdout > = None;
+    let mut stdout = None;
+    let mut stderr = Vec ::with_capacity( count );
+
+    for ( index, mut stage ) in stages.into_iter().enumerate()
+    {
+      let mut command = process ::command( &stage );
+      if let Some( output ) = previous.take()
+      {
+        command.stdin( output );
+      }
+      else if let Some( file ) = process ::input_file( stage.stdin.as_ref() )?
+      {
+        command.stdin( file );
+      }
+      else if stage.stdin.is_some()
+      {
+        command.stdin( Stdio ::piped() );
+      }
+      #[ cfg( unix ) ]
+      if stop.is_armed()
+      {
+        // All stages share the process group of the first, so a timeout kills them together.
+        use std ::os ::unix ::process ::CommandExt;
+        command.process_group( children.first().and_then( | first | i32 ::try_from( first.id() ).ok() ).unwrap_or( 0 ) );
+      }
+
+      let mut child = match command.spawn().with_context( || format!( "failed to spawn pipeline stage {}", index + 1 ) )
+      {
+        Ok( child ) => child,
+        Err( error ) =>
+        {
+          kill( &mut children );
+          return Err( error );
+        }
+      };
+      if let ( Some( Input ::Bytes( bytes ) ), Some( stdin ) ) = ( stage.stdin.take(), child.stdin.take() )
+      {
+        process ::feed( stdin, bytes );
+      }
+      if index + 1 == count
+      {
+        stdout = child.stdout.take().map( | output | process ::read_lines_in_background( output, stage.on_stdout_line.take() ) );
+      }
+      else
+      {
+        previous = child.stdout.take();
+      }
+      if let Some( output ) = child.stderr.take()
+      {
+        stderr.push( process ::read_lines_in_background( output, stage.on_stderr_line.take() ) );
+      }
+      children.push( child );
+    }
+
+    let mut statuses : Vec< Option< ExitStatus > > = vec![ None; count ];
+    let mut poll = || -> std ::io ::Result< Option< () > >
+    {
+      for ( child, status ) in children.iter_mut().zip( statuses.iter_mut() )
+      {
+        if status.is_none()
+        {
+          *status = child.try_wait()?;
+        }
+      }
+      Ok( statuses.iter().all( Option ::is_some ).then_some( () ) )
+    };
+    let finished = if stop.is_armed() { stop.wait( &mut poll )? } else { Some( wait( &mut children )? ) };
+    let timed_out = finished.is_none();
+    if timed_out
+    {
+      kill( &mut children );
+    }
+    let statuses = children.iter_mut().map( Child ::wait ).collect ::< Result< Vec< _ >, _ > >().context( "failed to wait on pipeline stage" )?;
+
+    let status = statuses.iter().rev().find( | status | !status.success() ).or( statuses.last() ).copied()
+    .context( "pipeline has no stages" )?;
+    let stdout = stdout.map( process ::join ).transpose()?.unwrap_or_default();
+    let stderr = stderr.into_iter().map( process ::join ).collect ::< Result< Vec< _ >, _ > >()?.concat();
+    Ok( process ::Execution { status, stdout, stderr, timed_out, cancelled : false } )
+  }
+
+  /// Block until every stage has exited.
+  fn wait( children : &mut [ Child ] ) -> Result< (), Error >
+  {
+    for child in children
+    {
+      child.wait().context( "failed to wait on pipeline stage" )?;
+    }
+    Ok( () )
+  }
+
+  /// Kill every stage, ignoring stages that already exited.
+  fn kill( children : &mut [ Child ] )
+  {
+    for child in children
+    {
+      _ = process ::terminate( child.id(), || child.kill() );
+    }
+  }
+}
+
+crate ::mod_interface!
+{
+  own use run;
+  own use Pipeline;
+}
@@ -0,0 +1,202 @@
+/// Define a private namespace for all its items.
+#[ allow( clippy ::std_instead_of_alloc, clippy ::std_instead_of_core ) ]
+mod private
+{
+  use std ::
+  {
+    num ::NonZeroUsize,
+    sync ::{ Arc, Mutex, PoisonError, atomic ::{ AtomicBool, Ordering } },
+    thread,
+  };
+  use error_tools ::
+  {
+    untyped ::format_err,
+  };
+  use former ::Former;
+  use crate ::process ::{ self, LineCallback, Report, Run };
+
+  /// A command run by a [`Pool`], with the label that identifies it in streamed output.
+  #[ derive( Debug ) ]
+  pub struct Job
+  {
+    /// Label of the job, e.g. the name of the crate it builds.
+    pub label : String,
+    /// Options of the command, formed with `Run::former()...form()`.
+    pub run : Run,
+  }
+
+  impl Job
+  {
+    /// Create a job from a label and formed [`Run`] options.
+    #[ must_use ]
+    pub fn new( label : impl Into< String >, run : Run ) -> Self
+    {
+      Self { label : label.into(), run }
+    }
+  }
+
+  /// Many commands run in parallel with bounded concurrency.
+  ///
+  /// Jobs start in submission order, at most `concurrency` at a time. Each job
+  /// keeps its own options, including line callbacks and timeout. The pool-wide
+  /// line callbacks additionally receive the output of every job while it runs,
+  /// prefixed with `[label] ` when `label_output` is set.
+  #[ derive( Debug, Former ) ]
+  pub struct Pool
+  {
+    /// Jobs in submission order.
+    jobs : Vec< Job >,
+    /// Maximum number of jobs running at once; defaults to the available parallelism.
+    concurrency : Option< usize >,
+    /// When `true`, the first failing job cancels the pool: running jobs are killed and pending ones never start.
+    #[ former( default = false ) ]
+    fail_fast : bool,
+    /// When `true`, lines passed to the pool-wide callbacks are prefixed with `[label] `.
+    #[ former( default = false ) ]
+    label_output : bool,
+    /// Called with each stdout line of every job; calls are serialized across jobs.
+    on_stdout_line : Option< LineCallback >,
+    /// Called with each stderr line of every job; calls are serialized across jobs.
+    on_stderr_line : Option< LineCallback >,
+  }
+
+  impl PoolFormer
+  {
+    /// Execute the configured jobs, returning their reports in submission order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # #[ cfg( unix ) ]
+    /// # {
+    /// use process_tools ::{ pool ::{ Job, Pool }, process ::Run };
+    /// use std ::sync ::{ Arc, Mutex };
+    ///
+    /// let lines = Arc ::new( Mutex ::new( Vec ::new() ) );
+    /// let sink = Arc ::clone( &lines );
+    /// let jobs = ( 1 ..= 3 )
+    /// .map( | n | Job ::new( format!( "job{n}" ), Run ::former().bin_path( "echo" ).args( vec![ n.to_string().into() ] ).current_path( "." ).form() ) )
+    /// .collect ::< Vec< _ > >();
+    ///
+    /// let reports = Pool ::former()
+    /// .jobs( jobs )
+    /// .concurrency( 2_usize )
+    /// .label_output( true )
+    /// .on_stdout_line( move | line : &str | sink.lock().unwrap().push( line.to_string() ) )
+    /// .run();
+    ///
+    /// let out : Vec< _ > = reports.iter().map( | report | report.as_ref().unwrap().out.clone() ).collect();
+    /// assert_eq!( out, vec![ "1\n", "2\n", "3\n" ] );
+    /// assert!( lines.lock().unwrap().contains( &"[job2] 2".to_string() ) );
+    /// # }
+    /// ```
+    #[ must_use ]
+    pub fn run( self ) -> Vec< Result< Report, Report > >
+    {
+      run( self.form() )
+    }
+  }
+
+  ///
+  /// Runs the jobs of a pool in parallel and collects one result per job.
+  ///
+  /// Results are in submission order, whatever order the jobs finish in. With
+  /// `fail_fast`, the first failure cancels the pool: running jobs are killed
+  /// and fail as cancelled, and jobs not yet started fail without running.
+  ///
+  #[ must_use ]
+  pub fn run( pool : Pool ) -> Vec< Result< Report, Report > >
+  {
+    let Pool { jobs, concurrency, fail_fast, label_output, on_stdout_line, on_stderr_line } = pool;
+    let count = jobs.len();
+    let workers = concurrency
+    .unwrap_or_else( || thread ::available_parallelism().map_or( 1, NonZeroUsize ::get ) )
+    .clamp( 1, count.max( 1 ) );
+
+    let sinks = Sinks
+    {
+      stdout : on_stdout_line.map( | callback | Arc ::new( Mutex ::new( callback ) ) ),
+      stderr : on_stderr_line.map( | callback | Arc ::new( Mutex ::new( callback ) ) ),
+      label_output,
+    };
+    let queue = Mutex ::new( jobs.into_iter().enumerate() );
+    let results : Mutex< Vec< Option< Result< Report, Report > > > > = Mutex ::new( ( 0 .. count ).map( | _ | None ).collect() );
+    let cancel = AtomicBool ::new( false );
+
+    thread ::scope( | scope |
+    {
+      for _ in 0 .. workers
+      {
+        scope.spawn( ||
+        {
+          while !cancel.load( Ordering ::SeqCst )
+          {
+            let Some( ( index, job ) ) = queue.lock().unwrap_or_else( PoisonError ::into_inner ).next() else { break };
+            let result = process ::run_until( sinks.attach( job ), fail_fast.then_some( &cancel ) );
+            if fail_fast && result.is_err()
+            {
+              cancel.store( true, Ordering ::SeqCst );
+            }
+            results.lock().unwrap_or_else( PoisonError ::into_inner )[ index ] = Some( result );
+          }
+        });
+      }
+    });
+
+    let mut results = results.into_inner().unwrap_or_else( PoisonError ::into_inner );
+    for ( index, job ) in queue.into_inner().unwrap_or_else( PoisonError ::into_inner )
+    {
+      results[ index ] = Some( Err( Report
+      {
+        command : process ::command_line( &job.run ),
+        current_path : job.run.current_path.clone(),
+        error : Err( format_err!( "Process was not started because the pool was cancelled" ) ),
+        ..Report ::default()
+      }));
+    }
+    results.into_iter().flatten().collect()
+  }
+
+  /// Pool-wide line callbacks shared by all jobs.
+  struct Sinks
+  {
+    stdout : Option< Arc< Mutex< LineCallback > > >,
+    stderr : Option< Arc< Mutex< LineCallback > > >,
+    label_output : bool,
+  }
+
+  impl Sinks
+  {
+    /// Options of `job` with its line callbacks extended to also feed the pool-wide ones.
+    fn attach( &self, job : Job ) -> Run
+    {
+      let Job { label, mut run } = job;
+      let prefix = if self.label_output { format!( "[{label}] " ) } else { String ::new() };
+      run.on_stdout_line = forward( run.on_stdout_line.take(), self.stdout.clone(), prefix.clone() );
+      run.on_stderr_line = forward( run.on_stderr_line.take(), self.stderr.clone(), prefix );
+      run
+    }
+  }
+
+  /// Callback passing each line to the job's own callback, then prefixed to the pool-wide one.
+  fn forward( own : Option< LineCallback >, shared : Option< Arc< Mutex< LineCallback > > >, prefix : String ) -> Option< LineCallback >
+  {
+    let Some( shared ) = shared else { return own };
+    let mut own = own;
+    Some( LineCallback ::from( move | line : &str |
+    {
+      if let Some( own ) = own.as_mut()
+      {
+        own.call( line );
+      }
+      shared.lock().unwrap_or_else( PoisonError ::into_inner ).call( &format!( "{prefix}{line}" ) );
+    }))
+  }
+}
+
+crate ::mod_interface!
+{
+  own use run;
+  own use Job;
+  own use Pool;
+}
@@ -5,8 +5,13 @@ mod private
   use std ::
   {
     fmt ::Formatter,
-    path ::{ Path, PathBuf },
-    process ::{ Command, Output, Stdio },
+    fs ::File,
+    io ::{ self, BufRead, BufReader, Read, Write },
+    path ::PathBuf,
+    process ::{ Command, ExitStatus, Stdio },
+    sync ::{ Arc, atomic ::{ AtomicBool, Ordering } },
+    thread,
+    time ::{ Duration, Instant },
   };
   use std ::collections ::HashMap;
   use std ::ffi ::OsString;
@@ -18,47 +23,356 @@ mod private
   use former ::Former;
   use ::itertools ::Itertools;
 
+  /// Data fed into the standard input of a process started by [`run`].
+  ///
+  /// Without input the process inherits stdin of the current process.
+  #[ derive( Debug, Clone, PartialEq, Eq ) ]
+  pub enum Input
+  {
+    /// Bytes written to stdin, which is closed afterwards.
+    Bytes( Vec< u8 > ),
+    /// File whose content becomes stdin.
+    File( PathBuf ),
+  }
+
+  impl From< &str > for Input
+  {
+    fn from( data : &str ) -> Self
+    {
+      Self ::Bytes( data.as_bytes().to_vec() )
+    }
+  }
+
+  impl From< String > for Input
+  {
+    fn from( data : String ) -> Self
+    {
+      Self ::Bytes( data.into_bytes() )
+    }
+  }
+
+  impl From< Vec< u8 > > for Input
+  {
+    fn from( data : Vec< u8 > ) -> Self
+    {
+      Self ::Bytes( data )
+    }
+  }
+
+  /// Callback receiving each line of output while the process is still running.
+  ///
+  /// Lines are passed without their line terminator; invalid UTF-8 is replaced
+  /// lossily. Any `FnMut( &str ) + Send` closure converts into a callback.
+  pub struct LineCallback( Box< dyn FnMut( &str ) + Send > );
+
+  impl< F > From< F > for LineCallback
+  where
+    F : FnMut( &str ) + Send + 'static,
+  {
+    fn from( callback : F ) -> Self
+    {
+      Self( Box ::new( callback ) )
+    }
+  }
+
+  impl LineCallback
+  {
+    /// Pass one line to the callback.
+    pub( crate ) fn call( &mut self, line : &str )
+    {
+      ( self.0 )( line );
+    }
+  }
+
+  impl core ::fmt ::Debug for LineCallback
+  {
+    fn fmt( &self, f : &mut Formatter< '_ > ) -> core ::fmt ::Result
+    {
+      f.write_str( "LineCallback" )
+    }
+  }
+
+  /// Raw outcome of an execution, before it is turned into a [`Report`].
+  pub( crate ) struct Execution
+  {
+    pub( crate ) status : ExitStatus,
+    pub( crate ) stdout : Vec< u8 >,
+    pub( crate ) stderr : Vec< u8 >,
+    pub( crate ) timed_out : bool,
+    pub( crate ) cancelled : bool,
+  }
+
+  /// Conditions that end a running process early: a deadline and a shared cancellation flag.
+  #[ derive( Clone, Copy ) ]
+  pub( crate ) struct Stop< 'a >
+  {
+    pub( crate ) deadline : Option< Instant >,
+    pub( crate ) cancel : Option< &'a AtomicBool >,
+  }
+
+  impl Stop< '_ >
+  {
+    /// Whether the process may be stopped early, so it must be polled and spawned killable.
+    pub( crate ) fn is_armed( &self ) -> bool
+    {
+      self.deadline.is_some() || self.cancel.is_some()
+    }
+
+    /// Whether cancellation was requested.
+    pub( crate ) fn is_cancelled( &self ) -> bool
+    {
+      self.cancel.is_some_and( | cancel | cancel.load( Ordering ::SeqCst ) )
+    }
+
+    /// Whether the process must be stopped now.
+    fn is_due( &self, now : Instant ) -> bool
+    {
+      self.is_cancelled() || self.deadline.is_some_and( | deadline | now >= deadline )
+    }
+
+    /// Poll `finished` until it yields a value or the process must be stopped; `None` means stop.
+    pub( crate ) fn wait< T >( &self, mut finished : impl FnMut() -> io ::Result< Option< T > > ) -> io ::Result< Option< T > >
+    {
+      let mut pause = Duration ::from_millis( 1 );
+      loop
+      {
+        if let Some( value ) = finished()?
+        {
+          return Ok( Some( value ) );
+        }
+        let now = Instant ::now();
+        if self.is_due( now )
+        {
+          return Ok( None );
+        }
+        let pause_until = self.deadline.map_or( pause, | deadline | pause.min( deadline - now ) );
+        thread ::sleep( pause_until );
+        pause = ( pause * 2 ).min( Duration ::from_millis( 50 ) );
+      }
+    }
+  }
+
+  /// The complete environment of the process: inherited variables, filtered by
+  /// `env_clear` and `env_keep`, with `env_variable` on top.
+  pub( crate ) fn environment( options : &Run ) -> HashMap< String, String >
+  {
+    let mut env : HashMap< String, String > = std ::env ::vars()
+    .filter( | ( name, _ ) | !options.env_clear || options.env_keep.contains( name ) )
+    .collect();
+    env.extend( options.env_variable.iter().map( | ( name, value ) | ( name.clone(), value.clone() ) ) );
+    env
+  }
+
+  /// Command line of `options` as shown in [`Report::command`].
+  pub( crate ) fn command_line( options : &Run ) -> String
+  {
+    format!( "{} {}", options.bin_path.display(), options.args.iter().map( | a | a.to_string_lossy() ).join( " " ) )
+  }
+
+  /// `std::process::Command` for `options` with piped stdout and stderr; stdin is left to the caller.
+  pub( crate ) fn command( options : &Run ) -> Command
+  {
+    let mut command = Command ::new( &options.bin_path );
+    command
+    .args( &options.args )
+    .env_clear()
+    .envs( environment( options ) )
+    .stdout( Stdio ::piped() )
+    .stderr( Stdio ::piped() )
+    .current_dir( &options.current_path );
+    command
+  }
+
+  /// Open the stdin file of `input`, if it names one.
+  pub( crate ) fn input_file( input : Option< &Input > ) -> Result< Option< File >, Error >
+  {
+    match input
+    {
+      Some( Input ::File( path ) ) => File ::open( path )
+      .map( Some )
+      .with_context( || format!( "failed to open stdin file {}", path.display() ) ),
+      _ => Ok( None ),
+    }
+  }
+
+  /// Write `bytes` to `stdin` on a separate thread and close it.
+  pub( crate ) fn feed( stdin : impl Write + Send + 'static, bytes : Vec< u8 > )
+  {
+    let mut stdin = stdin;
+    // A process that exits without reading its input closes the pipe; that is not an error of the run.
+    thread ::spawn( move || { _ = stdin.write_all( &bytes ); } );
+  }
+
   ///
   /// Executes a process with the given parameters.
   ///
   /// Routes to the duct backend when `joining_streams` is true (stderr merged into stdout),
   /// or to `std::process::Command` when false (separate stdout and stderr capture).
   ///
-  fn execute
-  (
-    bin_path : &Path,
-    args : &[ OsString ],
-    current_path : &Path,
-    joining_streams : bool,
-    env : HashMap< String, String >,
-  ) -> Result< Output, Error >
-  {
-    if joining_streams
-    {
-      cmd( bin_path.as_os_str(), args )
-      .dir( current_path )
-      .full_env( env )
-      .stderr_to_stdout()
-      .stdout_capture()
-      .unchecked()
-      .run()
-      .map_err( Into ::into )
+  fn execute( options : Run, stop : Stop< '_ > ) -> Result< Execution, Error >
+  {
+    if options.joining_streams
+    {
+      execute_joined( options, stop )
+    }
+    else
+    {
+      execute_separate( options, stop )
+    }
+  }
+
+  /// Run through duct with stderr merged into stdout, reading the merged stream line by line.
+  fn execute_joined( options : Run, stop : Stop< '_ > ) -> Result< Execution, Error >
+  {
+    let mut expression = cmd( options.bin_path.as_os_str(), &options.args )
+    .dir( &options.current_path )
+    .full_env( environment( &options ) )
+    .stderr_to_stdout()
+    .unchecked();
+    expression = match options.stdin
+    {
+      Some( Input ::Bytes( bytes ) ) => expression.stdin_bytes( bytes ),
+      Some( Input ::File( path ) ) => expression.stdin_path( path ),
+      None => expression,
+    };
+    #[ cfg( unix ) ]
+    if stop.is_armed()
+    {
+      use std ::os ::unix ::process ::CommandExt;
+      expression = expression.before_spawn( | command | { command.process_group( 0 ); Ok( () ) } );
+    }
+
+    let handle = Arc ::new( expression.reader().context( "failed to spawn process" )? );
+    let reader =
+    {
+      let handle = Arc ::clone( &handle );
+      let callback = options.on_stdout_line;
+      thread ::spawn( move || read_lines( &*handle, callback ) )
+    };
+
+    let mut stopped = false;
+    if stop.is_armed() && stop.wait( || handle.try_wait().map( | output | output.map( drop ) ) )?.is_none()
+    {
+      stopped = true;
+      let handle = Arc ::clone( &handle );
+      terminate( handle.pids()[ 0 ], move || handle.kill() ).context( "failed to kill stopped process" )?;
+    }
+
+    let stdout = join( reader )?;
+    let status = handle.try_wait()?.context( "process did not finish after its output was closed" )?.status;
+    let cancelled = stopped && stop.is_cancelled();
+    Ok( Execution { status, stdout, stderr : Vec ::new(), timed_out : stopped && !cancelled, cancelled } )
+  }
+
+  /// Run through `std::process::Command`, reading stdout and stderr on separate threads.
+  fn execute_separate( options : Run, stop : Stop< '_ > ) -> Result< Execution, Error >
+  {
+    let mut command = command( &options );
+    if let Some( file ) = input_file( options.stdin.as_ref() )?
+    {
+      command.stdin( file );
+    }
+    else if options.stdin.is_some()
+    {
+      command.stdin( Stdio ::piped() );
+    }
+    #[ cfg( unix ) ]
+    if stop.is_armed()
+    {
+      use std ::os ::unix ::process ::CommandExt;
+      command.process_group( 0 );
+    }
+
+    let mut child = command.spawn().context( "failed to spawn process" )?;
+    if let ( Some( Input ::Bytes( bytes ) ), Some( stdin ) ) = ( options.stdin, child.stdin.take() )
+    {
+      feed( stdin, bytes );
+    }
+    let stdout = child.stdout.take().map( | stdout | read_lines_in_background( stdout, options.on_stdout_line ) );
+    let stderr = child.stderr.take().map( | stderr | read_lines_in_background( stderr, options.on_stderr_line ) );
+
+    let finished = if stop.is_armed()
+    {
+      stop.wait( || child.try_wait() )?
     }
     else
     {
-      Command ::new( bin_path )
-      .args( args )
-      .envs( env )
-      .stdout( Stdio ::piped() )
-      .stderr( Stdio ::piped() )
-      .current_dir( current_path )
-      .spawn()
-      .context( "failed to spawn process" )?
-      .wait_with_output()
-      .context( "failed to wait on child" )
+      Some( child.wait().context( "failed to wait on child" )? )
+    };
+    let stopped = finished.is_none();
+    let status = if let Some( status ) = finished
+    {
+      status
+    }
+    else
+    {
+      terminate( child.id(), || child.kill() ).context( "failed to kill stopped process" )?;
+      child.wait().context( "failed to wait on child" )?
+    };
+
+    let stdout = stdout.map( join ).transpose()?.unwrap_or_default();
+    let stderr = stderr.map( join ).transpose()?.unwrap_or_default();
+    let cancelled = stopped && stop.is_cancelled();
+    Ok( Execution { status, stdout, stderr, timed_out : stopped && !cancelled, cancelled } )
+  }
+
+  /// Read `source` to its end, passing every line to `callback`, and return everything read.
+  fn read_lines( source : impl Read, mut callback : Option< LineCallback > ) -> io ::Result< Vec< u8 > >
+  {
+    let mut reader = BufReader ::new( source );
+    let mut captured = Vec ::new();
+    let mut line = Vec ::new();
+    loop
+    {
+      line.clear();
+      if reader.read_until( b'\n', &mut line )? == 0
+      {
+        return Ok( captured );
+      }
+      if let Some( callback ) = callback.as_mut()
+      {
+        callback.call( String ::from_utf8_lossy( &line ).trim_end_matches( [ '\n', '\r' ] ) );
+      }
+      captured.extend_from_slice( &line );
     }
   }
 
+  /// [`read_lines`] on a new thread.
+  pub( crate ) fn read_lines_in_background( source : impl Read + Send + 'static, callback : Option< LineCallback > )
+  -> thread ::JoinHandle< io ::Result< Vec< u8 > > >
+  {
+    thread ::spawn( move || read_lines( source, callback ) )
+  }
+
+  /// Collect the output gathered by a reader thread.
+  pub( crate ) fn join( reader : thread ::JoinHandle< io ::Result< Vec< u8 > > > ) -> Result< Vec< u8 >, Error >
+  {
+    match reader.join()
+    {
+      Ok( output ) => output.context( "failed to read process output" ),
+      Err( _ ) => Err( format_err!( "output line callback panicked" ) ),
+    }
+  }
+
+  /// Kill a process stopped early. On Unix it leads its own process group, which is killed
+  /// as a whole so that children it spawned do not keep the output pipes open.
+  #[ allow( unsafe_code ) ]
+  #[ cfg_attr( not( unix ), allow( unused_variables ) ) ]
+  pub( crate ) fn terminate( pid : u32, kill : impl FnOnce() -> io ::Result< () > ) -> io ::Result< () >
+  {
+    #[ cfg( unix ) ]
+    if let Ok( pid ) = i32 ::try_from( pid )
+    {
+      // SAFETY: kill() only sends a signal. The negative pid addresses the process group
+      // the child was placed in at spawn, which contains no process of ours.
+      if unsafe { libc ::kill( -pid, libc ::SIGKILL ) } == 0
+      {
+        return Ok( () );
+      }
+    }
+    kill()
+  }
+
   ///
   /// Executes an external process in a specified directory without using a shell.
   ///
@@ -77,24 +391,39 @@ mod private
   ///
   pub fn run( options : Run ) -> Result< Report, Report >
   {
-    // Destructure upfront to avoid partial-move conflicts when consuming env_variable.
-    let Run { bin_path, current_path, args, joining_streams, env_variable } = options;
-    let bin_path_ref : &Path = bin_path.as_ref();
-    let current_path_ref : &Path = current_path.as_ref();
-
-    let mut env : HashMap< String, String > = std ::env ::vars().collect();
-    env.extend( env_variable );
+    run_until( options, None )
+  }
 
-    let mut report = Report
+  /// [`run`], killing the process once `cancel` is raised.
+  pub( crate ) fn run_until( options : Run, cancel : Option< &AtomicBool > ) -> Result< Report, Report >
+  {
+    let report = Report
     {
-      command : format!( "{} {}", bin_path_ref.display(), args.iter().map( | a | a.to_string_lossy() ).join( " " ) ),
-      current_path : current_path_ref.to_path_buf(),
+      command : command_line( &options ),
+      current_path : options.current_path.clone(),
       ..Report ::default()
     };
+    let timeout = options.timeout;
 
-    let output = match execute( bin_path_ref, &args, current_path_ref, joining_streams, env )
+    let start = Instant ::now();
+    let stop = Stop { deadline : timeout.map( | timeout | start + timeout ), cancel };
+    let execution = execute( options, stop );
+    conclude( report, execution, start, timeout )
+  }
+
+  /// Complete `report` from the outcome of an execution started at `start`.
+  pub( crate ) fn conclude
+  (
+    mut report : Report,
+    execution : Result< Execution, Error >,
+    start : Instant,
+    timeout : Option< Duration >,
+  ) -> Result< Report, Report >
+  {
+    report.outcome.elapsed = start.elapsed();
+    let execution = match execution
     {
-      Ok( o ) => o,
+      Ok( e ) => e,
       Err( e ) =>
       {
         report.error = Err( e );
@@ -102,7 +431,15 @@ mod private
       }
     };
 
-    let out = match String ::from_utf8( output.stdout ).context( "Found invalid UTF-8" )
+    report.outcome.exit_code = execution.status.code();
+    #[ cfg( unix ) ]
+    {
+      use std ::os ::unix ::process ::ExitStatusExt;
+      report.outcome.signal = execution.status.signal();
+    }
+    report.outcome.timed_out = execution.timed_out;
+
+    let out = match String ::from_utf8( execution.stdout ).context( "Found invalid UTF-8" )
     {
       Ok( s ) => s,
       Err( e ) =>
@@ -113,7 +450,7 @@ mod private
     };
     report.out = out;
 
-    let err = match String ::from_utf8( output.stderr ).context( "Found invalid UTF-8" )
+    let err = match String ::from_utf8( execution.stderr ).context( "Found invalid UTF-8" )
     {
       Ok( s ) => s,
       Err( e ) =>
@@ -124,13 +461,23 @@ mod private
     };
     report.err = err;
 
-    if output.status.success()
+    if execution.timed_out
+    {
+      report.error = Err( format_err!( "Process timed out after {:?} and was killed", timeout.unwrap_or_default() ) );
+      Err( report )
+    }
+    else if execution.cancelled
+    {
+      report.error = Err( format_err!( "Process was cancelled and killed" ) );
+      Err( report )
+    }
+    else if execution.status.success()
     {
       Ok( report )
     }
     else
     {
-      report.error = Err( format_err!( "Process was finished with error code: {}", output.status ) );
+      report.error = Err( format_err!( "Process was finished with error code: {}", execution.status ) );
       Err( report )
     }
   }
@@ -140,16 +487,29 @@ mod private
   pub struct Run
   {
     /// Path to the executable to run.
-    bin_path : PathBuf,
+    pub( crate ) bin_path : PathBuf,
     /// Working directory for the process.
-    current_path : PathBuf,
+    pub( crate ) current_path : PathBuf,
     /// Command-line arguments passed to the executable.
-    args : Vec< OsString >,
+    pub( crate ) args : Vec< OsString >,
     /// When `true`, stderr is merged into stdout via duct. When `false`, streams are captured separately.
     #[ former( default = false ) ]
-    joining_streams : bool,
-    /// Additional environment variables merged on top of the current process environment.
-    env_variable : HashMap< String, String >,
+    pub( crate ) joining_streams : bool,
+    /// Additional environment variables merged on top of the inherited environment.
+    pub( crate ) env_variable : HashMap< String, String >,
+    /// When `true`, the current process environment is not inherited, except for `env_keep`.
+    #[ former( default = false ) ]
+    pub( crate ) env_clear : bool,
+    /// Variables inherited from the current process even when `env_clear` is set, e.g. `PATH`.
+    pub( crate ) env_keep : Vec< String >,
+    /// Data or file fed into stdin. Without it, stdin is inherited.
+    pub( crate ) stdin : Option< Input >,
+    /// Wall-clock limit; on expiry the process is killed and the run fails with `Report::timed_out()` set.
+    pub( crate ) timeout : Option< Duration >,
+    /// Called with each stdout line as it is produced. With `joining_streams`, receives stderr lines too.
+    pub( crate ) on_stdout_line : Option< LineCallback >,
+    /// Called with each stderr line as it is produced. Unused with `joining_streams`.
+    pub( crate ) on_stderr_line : Option< LineCallback >,
   }
 
   impl RunFormer
@@ -231,6 +591,53 @@ mod private
     pub err : String,
     /// `Ok(())` if the process succeeded, `Err` with the failure reason otherwise.
     pub error : Result< (), Error >,
+    /// How the process ended, boxed so `Result< Report, Report >` stays small.
+    pub outcome : Box< Outcome >,
+  }
+
+  /// Exit details of a [`Report`].
+  #[ derive( Debug, Clone, Copy, Default ) ]
+  pub struct Outcome
+  {
+    /// Exit code, or `None` if the process did not run or was terminated by a signal.
+    pub exit_code : Option< i32 >,
+    /// Signal that terminated the process. Always `None` on non-Unix platforms.
+    pub signal : Option< i32 >,
+    /// Wall-clock time from spawning the process until it finished.
+    pub elapsed : Duration,
+    /// `true` if the process was killed because it exceeded `Run::timeout`.
+    pub timed_out : bool,
+  }
+
+  impl Report
+  {
+    /// Exit code, or `None` if the process did not run or was terminated by a signal.
+    #[ must_use ]
+    pub fn exit_code( &self ) -> Option< i32 >
+    {
+      self.outcome.exit_code
+    }
+
+    /// Signal that terminated the process. Always `None` on non-Unix platforms.
+    #[ must_use ]
+    pub fn signal( &self ) -> Option< i32 >
+    {
+      self.outcome.signal
+    }
+
+    /// Wall-clock time from spawning the process until it finished.
+    #[ must_use ]
+    pub fn elapsed( &self ) -> Duration
+    {
+      self.outcome.elapsed
+    }
+
+    /// `true` if the process was killed because it exceeded `Run::timeout`.
+    #[ must_use ]
+    pub fn timed_out( &self ) -> bool
+    {
+      self.outcome.timed_out
+    }
   }
 
   impl Clone for Report
@@ -245,6 +652,7 @@ mod private
         err : self.err.clone(),
         // Error is not Clone; stringify to preserve the message across the clone boundary.
         error : self.error.as_ref().map_err( | e | Error ::msg( e.to_string() ) ).copied(),
+        outcome : self.outcome.clone(),
       }
     }
   }
@@ -260,6 +668,7 @@ mod private
         out : String ::default(),
         err : String ::default(),
         error : Ok( () ),
+        outcome : Box ::default(),
       }
     }
   }
@@ -292,4 +701,22 @@ crate ::mod_interface!
   own use run;
   own use Run;
   own use Report;
+  own use Outcome;
+  own use Input;
+  own use LineCallback;
 }
+
+pub( crate ) use private ::
+{
+  Execution,
+  Stop,
+  command,
+  command_line,
+  conclude,
+  feed,
+  input_file,
+  join,
+  read_lines_in_background,
+  run_until,
+  terminate,
+};
@@ -3,6 +3,9 @@ use super :: *;
 
 mod basic;
 mod process_run;
+mod process_options;
+mod pipeline_run;
+mod pool_run;
 
 #[ cfg( feature = "process_environment_is_cicd" ) ]
 mod environment_is_cicd;
@@ -0,0 +1,126 @@
+use super :: *;
+use the_module ::{ pipeline ::Pipeline, process ::Run };
+use core ::time ::Duration;
+use std ::sync ::{ Arc, Mutex };
+
+fn stage( bin : &str, args : &[ &str ] ) -> Run
+{
+  Run ::former()
+  .bin_path( bin )
+  .args( args.iter().map( Into ::into ).collect ::< Vec< _ > >() )
+  .current_path( "." )
+  .form()
+}
+
+fn sh( script : &str ) -> Run
+{
+  stage( "sh", &[ "-c", script ] )
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn stdout_flows_into_next_stdin()
+{
+  let report = Pipeline ::former()
+  .stages( vec![ stage( "printf", &[ "c\\na\\nb\\na\\n" ] ), stage( "sort", &[] ), stage( "uniq", &[ "-c" ] ) ] )
+  .run()
+  .unwrap();
+
+  let counts : Vec< _ > = report.out.lines().map( str ::split_whitespace ).map( Iterator ::collect ::< Vec< _ > > ).collect();
+  assert_eq!( counts, vec![ vec![ "2", "a" ], vec![ "1", "b" ], vec![ "1", "c" ] ] );
+  assert_eq!( report.command, "printf c\\na\\nb\\na\\n | sort | uniq -c" );
+  assert_eq!( report.exit_code(), Some( 0 ) );
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn first_stage_reads_stdin()
+{
+  let first = Run ::former().bin_path( "cat" ).current_path( "." ).stdin( "hello pipeline\n" ).form();
+
+  let report = Pipeline ::former()
+  .stages( vec![ first, stage( "tr", &[ "a-z", "A-Z" ] ) ] )
+  .run()
+  .unwrap();
+
+  assert_eq!( report.out, "HELLO PIPELINE\n" );
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn stderr_of_all_stages_is_collected_and_streamed()
+{
+  let lines = Arc ::new( Mutex ::new( Vec ::new() ) );
+  let sink = Arc ::clone( &lines );
+  let last = Run ::former()
+  .bin_path( "sh" )
+  .args( vec![ "-c".into(), "cat; echo second >&2".into() ] )
+  .current_path( "." )
+  .on_stderr_line( move | line : &str | sink.lock().unwrap().push( line.to_string() ) )
+  .form();
+
+  let report = Pipeline ::former()
+  .stages( vec![ sh( "echo data; echo first >&2" ), last ] )
+  .run()
+  .unwrap();
+
+  assert_eq!( report.out, "data\n" );
+  assert_eq!( report.err, "first\nsecond\n" );
+  assert_eq!( *lines.lock().unwrap(), vec![ "second" ] );
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn failing_stage_fails_pipeline_like_pipefail()
+{
+  let report = Pipeline ::former()
+  .stages( vec![ sh( "echo data; exit 3" ), stage( "cat", &[] ) ] )
+  .run()
+  .unwrap_err();
+
+  assert_eq!( report.out, "data\n" );
+  assert_eq!( report.exit_code(), Some( 3 ) );
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn timeout_kills_every_stage()
+{
+  let report = Pipeline ::former()
+  .stages( vec![ sh( "echo started; sleep 10" ), stage( "cat", &[] ) ] )
+  .timeout( Duration ::from_millis( 300 ) )
+  .run()
+  .unwrap_err();
+
+  assert!( report.timed_out() );
+  assert!( report.elapsed() < Duration ::from_secs( 5 ), "{:?}", report.elapsed() );
+  assert_eq!( report.out, "started\n" );
+}
+
+#[ test ]
+fn unsupported_stage_options_are_rejected()
+{
+  let empty = Pipeline ::former().run().unwrap_err();
+  assert!( empty.error.unwrap_err().to_string().contains( "no stages" ) );
+
+  let late_stdin = Run ::former().bin_path( "cat" ).current_path( "." ).stdin( "x" ).form();
+  let report = Pipeline ::former().stages( vec![ stage( "echo", &[] ), late_stdin ] ).run().unwrap_err();
+  assert!( report.error.unwrap_err().to_string().contains( "stage 2" ) );
+
+  let timed = Run ::former().bin_path( "cat" ).current_path( "." ).timeout( Duration ::from_secs( 1 ) ).form();
+  let report = Pipeline ::former().stages( vec![ timed ] ).run().unwrap_err();
+  assert_eq!( report.exit_code(), None );
+  assert!( report.error.unwrap_err().to_string().contains( "timeout" ) );
+}
+
+#[ test ]
+fn missing_binary_fails_pipeline()
+{
+  let report = Pipeline ::former()
+  .stages( vec![ stage( "echo", &[ "x" ] ), stage( "/nonexistent/binary/path", &[] ) ] )
+  .run()
+  .unwrap_err();
+
+  assert!( report.error.unwrap_err().to_string().contains( "stage 2" ) );
+  assert!( report.command.ends_with( "| /nonexistent/binary/path" ) );
+}
@@ -0,0 +1,127 @@
+use super :: *;
+use the_module ::{ pool ::{ Job, Pool }, process ::Run };
+use core ::time ::Duration;
+use std ::
+{
+  sync ::{ Arc, Mutex },
+  time ::Instant,
+};
+
+fn sh( label : &str, script : &str ) -> Job
+{
+  Job ::new( label, Run ::former().bin_path( "sh" ).args( vec![ "-c".into(), script.into() ] ).current_path( "." ).form() )
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn reports_follow_submission_order()
+{
+  let jobs = vec![ sh( "slow", "sleep 0.3; echo slow" ), sh( "fast", "echo fast" ), sh( "middle", "sleep 0.1; echo middle" ) ];
+
+  let reports = Pool ::former().jobs( jobs ).concurrency( 3_usize ).run();
+
+  let out : Vec< _ > = reports.into_iter().map( | report | report.unwrap().out ).collect();
+  assert_eq!( out, vec![ "slow\n", "fast\n", "middle\n" ] );
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn concurrency_bounds_running_jobs()
+{
+  let jobs : Vec< _ > = ( 0 .. 4 ).map( | n | sh( &n.to_string(), "sleep 0.2" ) ).collect();
+  let start = Instant ::now();
+
+  let reports = Pool ::former().jobs( jobs ).concurrency( 2_usize ).run();
+
+  assert!( reports.iter().all( Result ::is_ok ) );
+  assert!( start.elapsed() >= Duration ::from_millis( 400 ), "{:?}", start.elapsed() );
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn failures_do_not_stop_other_jobs_by_default()
+{
+  let jobs = vec![ sh( "a", "exit 1" ), sh( "b", "sleep 0.1; echo b" ), sh( "c", "echo c" ) ];
+
+  let reports = Pool ::former().jobs( jobs ).concurrency( 1_usize ).run();
+
+  assert_eq!( reports[ 0 ].as_ref().unwrap_err().exit_code(), Some( 1 ) );
+  assert_eq!( reports[ 1 ].as_ref().unwrap().out, "b\n" );
+  assert_eq!( reports[ 2 ].as_ref().unwrap().out, "c\n" );
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn fail_fast_kills_running_and_skips_pending_jobs()
+{
+  let jobs = vec![ sh( "long", "sleep 10" ), sh( "broken", "sleep 0.1; exit 2" ), sh( "pending", "echo never" ) ];
+  let start = Instant ::now();
+
+  let reports = Pool ::former().jobs( jobs ).concurrency( 2_usize ).fail_fast( true ).run();
+
+  assert!( start.elapsed() < Duration ::from_secs( 5 ), "{:?}", start.elapsed() );
+  assert_eq!( reports.len(), 3 );
+  let long = reports[ 0 ].as_ref().unwrap_err();
+  assert!( long.error.as_ref().unwrap_err().to_string().contains( "cancelled" ) );
+  assert!( !long.timed_out() );
+  assert_eq!( reports[ 1 ].as_ref().unwrap_err().exit_code(), Some( 2 ) );
+  let pending = reports[ 2 ].as_ref().unwrap_err();
+  assert!( pending.error.as_ref().unwrap_err().to_string().contains( "not started" ) );
+  assert!( pending.command.contains( "echo never" ) );
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn pool_callbacks_receive_labelled_lines_of_all_jobs()
+{
+  let out = Arc ::new( Mutex ::new( Vec ::new() ) );
+  let err = Arc ::new( Mutex ::new( Vec ::new() ) );
+  let own = Arc ::new( Mutex ::new( Vec ::new() ) );
+  let ( out_sink, err_sink, own_sink ) = ( Arc ::clone( &out ), Arc ::clone( &err ), Arc ::clone( &own ) );
+  let with_own = Job ::new
+  (
+    "b",
+    Run ::former()
+    .bin_path( "echo" )
+    .args( vec![ "two".into() ] )
+    .current_path( "." )
+    .on_stdout_line( move | line : &str | own_sink.lock().unwrap().push( line.to_string() ) )
+    .form(),
+  );
+
+  let reports = Pool ::former()
+  .jobs( vec![ sh( "a", "echo one; echo oops >&2" ), with_own ] )
+  .label_output( true )
+  .on_stdout_line( move | line : &str | out_sink.lock().unwrap().push( line.to_string() ) )
+  .on_stderr_line( move | line : &str | err_sink.lock().unwrap().push( line.to_string() ) )
+  .run();
+
+  assert!( reports.iter().all( Result ::is_ok ) );
+  let mut out = out.lock().unwrap().clone();
+  out.sort();
+  assert_eq!( out, vec![ "[a] one", "[b] two" ] );
+  assert_eq!( *err.lock().unwrap(), vec![ "[a] oops" ] );
+  assert_eq!( *own.lock().unwrap(), vec![ "two" ] );
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn unlabelled_output_is_passed_unchanged()
+{
+  let out = Arc ::new( Mutex ::new( Vec ::new() ) );
+  let sink = Arc ::clone( &out );
+
+  let reports = Pool ::former()
+  .jobs( vec![ sh( "a", "echo one" ) ] )
+  .on_stdout_line( move | line : &str | sink.lock().unwrap().push( line.to_string() ) )
+  .run();
+
+  assert!( reports[ 0 ].is_ok() );
+  assert_eq!( *out.lock().unwrap(), vec![ "one" ] );
+}
+
+#[ test ]
+fn empty_pool_returns_no_reports()
+{
+  assert!( Pool ::former().run().is_empty() );
+}
@@ -0,0 +1,215 @@
+use super :: *;
+use the_module ::process;
+use core ::time ::Duration;
+use std ::
+{
+  collections ::HashMap,
+  sync ::{ Arc, Mutex },
+};
+
+/// Collect lines passed to an output callback.
+fn lines() -> ( Arc< Mutex< Vec< String > > >, impl FnMut( &str ) + Send + 'static )
+{
+  let lines = Arc ::new( Mutex ::new( Vec ::new() ) );
+  let sink = Arc ::clone( &lines );
+  ( lines, move | line : &str | sink.lock().unwrap().push( line.to_string() ) )
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn timeout_kills_process_and_keeps_partial_output()
+{
+  let report = process ::Run ::former()
+  .current_path( "." )
+  .timeout( Duration ::from_millis( 300 ) )
+  .run_with_shell( "echo started; sleep 10; echo finished" )
+  .unwrap_err();
+
+  assert!( report.timed_out() );
+  assert!( report.elapsed() < Duration ::from_secs( 5 ), "{:?}", report.elapsed() );
+  assert_eq!( report.out, "started\n" );
+  assert_eq!( report.signal(), Some( 9 ) );
+  assert!( report.error.unwrap_err().to_string().contains( "timed out" ) );
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn timeout_not_reached_succeeds()
+{
+  let report = process ::Run ::former()
+  .current_path( "." )
+  .timeout( Duration ::from_secs( 30 ) )
+  .joining_streams( true )
+  .run_with_shell( "echo done" )
+  .unwrap();
+
+  assert!( !report.timed_out() );
+  assert_eq!( report.out, "done\n" );
+  assert_eq!( report.exit_code(), Some( 0 ) );
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn timeout_applies_to_joined_streams()
+{
+  let report = process ::Run ::former()
+  .current_path( "." )
+  .joining_streams( true )
+  .timeout( Duration ::from_millis( 300 ) )
+  .run_with_shell( "echo started >&2; sleep 10" )
+  .unwrap_err();
+
+  assert!( report.timed_out() );
+  assert_eq!( report.out, "started\n" );
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn stdin_bytes_are_fed_to_process()
+{
+  for joining_streams in [ false, true ]
+  {
+    let report = process ::Run ::former()
+    .bin_path( "cat" )
+    .current_path( "." )
+    .joining_streams( joining_streams )
+    .stdin( "first\nsecond\n" )
+    .run()
+    .unwrap();
+
+    assert_eq!( report.out, "first\nsecond\n" );
+  }
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn stdin_file_is_fed_to_process()
+{
+  let temp = assert_fs ::TempDir ::new().unwrap();
+  let input = temp.path().join( "input.txt" );
+  std ::fs ::write( &input, "from file\n" ).unwrap();
+
+  let report = process ::Run ::former()
+  .bin_path( "cat" )
+  .current_path( temp.path() )
+  .stdin( process ::Input ::File( input ) )
+  .run()
+  .unwrap();
+
+  assert_eq!( report.out, "from file\n" );
+}
+
+#[ test ]
+fn missing_stdin_file_fails_before_spawn()
+{
+  let report = process ::Run ::former()
+  .bin_path( "cat" )
+  .current_path( "." )
+  .stdin( process ::Input ::File( "/nonexistent/input.txt".into() ) )
+  .run()
+  .unwrap_err();
+
+  assert!( report.error.is_err() );
+  assert_eq!( report.exit_code(), None );
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn line_callbacks_receive_output_while_capturing()
+{
+  let ( out_lines, on_out ) = lines();
+  let ( err_lines, on_err ) = lines();
+
+  let report = process ::Run ::former()
+  .current_path( "." )
+  .on_stdout_line( on_out )
+  .on_stderr_line( on_err )
+  .run_with_shell( "echo one; echo warning >&2; printf 'two\\r\\nthree'" )
+  .unwrap();
+
+  assert_eq!( *out_lines.lock().unwrap(), vec![ "one", "two", "three" ] );
+  assert_eq!( *err_lines.lock().unwrap(), vec![ "warning" ] );
+  assert_eq!( report.out, "one\ntwo\r\nthree" );
+  assert_eq!( report.err, "warning\n" );
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn line_callback_receives_joined_streams()
+{
+  let ( out_lines, on_out ) = lines();
+
+  process ::Run ::former()
+  .current_path( "." )
+  .joining_streams( true )
+  .on_stdout_line( on_out )
+  .run_with_shell( "echo out; echo err >&2" )
+  .unwrap();
+
+  let mut received = out_lines.lock().unwrap().clone();
+  received.sort();
+  assert_eq!( received, vec![ "err", "out" ] );
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn env_clear_drops_inherited_variables()
+{
+  std ::env ::set_var( "PROCESS_TOOLS_INHERITED", "inherited" );
+  let script = "echo \"[$PROCESS_TOOLS_INHERITED][$EXTRA]\"";
+  let extra = HashMap ::from( [ ( "EXTRA".to_string(), "extra".to_string() ) ] );
+
+  let inherited = process ::Run ::former()
+  .current_path( "." )
+  .env_variable( extra.clone() )
+  .run_with_shell( script )
+  .unwrap();
+  assert_eq!( inherited.out, "[inherited][extra]\n" );
+
+  let cleared = process ::Run ::former()
+  .current_path( "." )
+  .env_variable( extra.clone() )
+  .env_clear( true )
+  .run_with_shell( script )
+  .unwrap();
+  assert_eq!( cleared.out, "[][extra]\n" );
+
+  let kept = process ::Run ::former()
+  .current_path( "." )
+  .env_variable( extra )
+  .env_clear( true )
+  .env_keep( vec![ "PROCESS_TOOLS_INHERITED".to_string() ] )
+  .joining_streams( true )
+  .run_with_shell( script )
+  .unwrap();
+  assert_eq!( kept.out, "[inherited][extra]\n" );
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn report_carries_exit_code_and_elapsed_time()
+{
+  let report = process ::Run ::former()
+  .current_path( "." )
+  .run_with_shell( "sleep 0.2; exit 3" )
+  .unwrap_err();
+
+  assert_eq!( report.exit_code(), Some( 3 ) );
+  assert_eq!( report.signal(), None );
+  assert!( !report.timed_out() );
+  assert!( report.elapsed() >= Duration ::from_millis( 200 ), "{:?}", report.elapsed() );
+  assert_eq!( report.clone().exit_code(), Some( 3 ) );
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn report_carries_terminating_signal()
+{
+  let report = process ::Run ::former()
+  .current_path( "." )
+  .run_with_shell( "kill -TERM $$" )
+  .unwrap_err();
+
+  assert_eq!( report.exit_code(), None );
+  assert_eq!( report.signal(), Some( 15 ) );
+}
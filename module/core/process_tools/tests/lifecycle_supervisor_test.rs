@@ -0,0 +1,254 @@
+#![ allow( missing_docs ) ]
+
+include!( "../../../../module/step/meta/src/module/terminal.rs" );
+
+#[ allow( unused_imports ) ]
+use ::process_tools as the_module;
+#[ allow( unused_imports ) ]
+use test_tools :: *;
+
+#[ cfg( all( feature = "process_supervisor", unix ) ) ]
+mod inc
+{
+  use super :: *;
+  use core ::time ::Duration;
+  use std ::time ::Instant;
+  use the_module ::lifecycle ::supervisor ::{ Readiness, Restart, Service, ServiceStatus, Supervisor };
+  use the_module ::process ::Run;
+
+  fn sh( script : &str ) -> Run
+  {
+    Run ::former()
+    .bin_path( "sh" )
+    .args( vec![ "-c".into(), script.into() ] )
+    .current_path( std ::env ::temp_dir() )
+    .form()
+  }
+
+  /// Poll the supervisor until `done` holds or five seconds pass.
+  fn eventually( supervisor : &Supervisor, name : &str, done : impl Fn( &the_module ::lifecycle ::supervisor ::ServiceState ) -> bool ) -> bool
+  {
+    let deadline = Instant ::now() + Duration ::from_secs( 5 );
+    while Instant ::now() < deadline
+    {
+      if supervisor.state( name ).is_some_and( | state | done( &state ) )
+      {
+        return true;
+      }
+      std ::thread ::sleep( Duration ::from_millis( 20 ) );
+    }
+    false
+  }
+
+  /// A service without a readiness check is ready as soon as it runs.
+  #[ test ]
+  fn start_without_readiness_is_ready()
+  {
+    let mut supervisor = Supervisor ::new();
+    supervisor.start( Service ::former().name( "sleeper" ).run( sh( "sleep 30" ) ).form() ).unwrap();
+
+    let state = supervisor.state( "sleeper" ).unwrap();
+    assert_eq!( state.status, ServiceStatus ::Ready );
+    assert!( state.pid.is_some() );
+    assert_eq!( supervisor.services(), vec![ "sleeper" ] );
+  }
+
+  /// A crashing service is restarted with backoff until `max_restarts`, then marked failed.
+  #[ test ]
+  fn crash_is_restarted_until_limit()
+  {
+    let crasher = | name : &str | Service ::former()
+    .name( name )
+    .run( sh( "exit 3" ) )
+    .backoff_initial( Duration ::from_millis( 10 ) )
+    .max_restarts( 2_u32 );
+    let mut supervisor = Supervisor ::new();
+
+    supervisor.start( crasher( "crasher" ).form() ).unwrap();
+    assert!( eventually( &supervisor, "crasher", | state | state.status == ServiceStatus ::Failed ) );
+    let state = supervisor.state( "crasher" ).unwrap();
+    assert_eq!( ( state.restarts, state.last_exit_code ), ( 2, Some( 3 ) ) );
+
+    let error = supervisor.start( crasher( "never-ready" ).readiness( Readiness ::File( "/nonexistent/ready".into() ) ).form() ).unwrap_err();
+    assert!( error.to_string().contains( "ended before becoming ready" ) && error.to_string().contains( "Failed" ), "{error}" );
+    assert!( supervisor.state( "never-ready" ).is_none(), "a service that never became ready is not kept" );
+  }
+
+  /// Restarts are counted and the exit code of the previous instance is recorded.
+  #[ test ]
+  fn restart_always_counts_restarts()
+  {
+    let mut supervisor = Supervisor ::new();
+    let service = Service ::former()
+    .name( "flapper" )
+    .run( sh( "sleep 0.05; exit 4" ) )
+    .restart( Restart ::Always )
+    .backoff_initial( Duration ::from_millis( 10 ) )
+    .backoff_max( Duration ::from_millis( 20 ) )
+    .form();
+    supervisor.start( service ).unwrap();
+
+    assert!( eventually( &supervisor, "flapper", | state | state.restarts >= 2 && state.last_exit_code == Some( 4 ) ) );
+    supervisor.stop( "flapper" ).unwrap();
+    assert!( supervisor.state( "flapper" ).is_none() );
+  }
+
+  /// A clean exit under `OnFailure` leaves the service stopped without restarting.
+  #[ test ]
+  fn clean_exit_is_not_restarted_on_failure_policy()
+  {
+    let mut supervisor = Supervisor ::new();
+    supervisor.start( Service ::former().name( "oneshot" ).run( sh( "sleep 0.05" ) ).form() ).unwrap();
+
+    assert!( eventually( &supervisor, "oneshot", | state | state.status == ServiceStatus ::Stopped ) );
+    let state = supervisor.state( "oneshot" ).unwrap();
+    assert_eq!( ( state.restarts, state.last_exit_code ), ( 0, Some( 0 ) ) );
+  }
+
+  /// Readiness by log line waits for a matching line.
+  #[ test ]
+  fn readiness_by_log_line()
+  {
+    let mut supervisor = Supervisor ::new();
+    let service = Service ::former()
+    .name( "logger" )
+    .run( sh( "echo booting; sleep 0.1; echo 'listening on 4000'; sleep 30" ) )
+    .readiness( Readiness ::LogLine( regex ::Regex ::new( r"listening on \d+" ).unwrap() ) )
+    .form();
+    let started = Instant ::now();
+    supervisor.start( service ).unwrap();
+
+    assert!( started.elapsed() >= Duration ::from_millis( 100 ) );
+    assert_eq!( supervisor.state( "logger" ).unwrap().status, ServiceStatus ::Ready );
+  }
+
+  /// Readiness by file waits for the file to appear.
+  #[ test ]
+  fn readiness_by_file()
+  {
+    let dir = assert_fs ::TempDir ::new().unwrap();
+    let marker = dir.path().join( "ready" );
+    let mut supervisor = Supervisor ::new();
+    let service = Service ::former()
+    .name( "marker" )
+    .run( sh( &format!( "sleep 0.1; touch '{}'; sleep 30", marker.display() ) ) )
+    .readiness( Readiness ::File( marker.clone() ) )
+    .form();
+    supervisor.start( service ).unwrap();
+
+    assert!( marker.exists() );
+  }
+
+  /// Readiness by port waits for a listener.
+  #[ test ]
+  fn readiness_by_port()
+  {
+    let listener = std ::net ::TcpListener ::bind( "127.0.0.1:0" ).unwrap();
+    let address = listener.local_addr().unwrap();
+    let mut supervisor = Supervisor ::new();
+    let service = Service ::former()
+    .name( "port" )
+    .run( sh( "sleep 30" ) )
+    .readiness( Readiness ::Port( address ) )
+    .form();
+    supervisor.start( service ).unwrap();
+
+    drop( listener );
+    let closed = Service ::former()
+    .name( "closed" )
+    .run( sh( "sleep 30" ) )
+    .readiness( Readiness ::Port( address ) )
+    .ready_timeout( Duration ::from_millis( 200 ) )
+    .form();
+    let error = supervisor.start( closed ).unwrap_err();
+    assert_eq!( error.kind(), std ::io ::ErrorKind ::TimedOut );
+    assert!( supervisor.state( "closed" ).is_none() );
+  }
+
+  /// Forwarded signals reach the service; unknown names and services are rejected.
+  #[ test ]
+  fn signal_is_forwarded()
+  {
+    let dir = assert_fs ::TempDir ::new().unwrap();
+    let marker = dir.path().join( "hup" );
+    let mut supervisor = Supervisor ::new();
+    let script = format!( "trap 'touch \"{}\"' HUP; echo up; while true; do sleep 0.02; done", marker.display() );
+    let service = Service ::former()
+    .name( "reloadable" )
+    .run( sh( &script ) )
+    .readiness( Readiness ::LogLine( regex ::Regex ::new( "up" ).unwrap() ) )
+    .form();
+    supervisor.start( service ).unwrap();
+
+    supervisor.signal( "reloadable", "SIGHUP" ).unwrap();
+    let deadline = Instant ::now() + Duration ::from_secs( 5 );
+    while !marker.exists() && Instant ::now() < deadline
+    {
+      std ::thread ::sleep( Duration ::from_millis( 20 ) );
+    }
+    assert!( marker.exists() );
+
+    assert_eq!( supervisor.signal( "reloadable", "SIGNOPE" ).unwrap_err().kind(), std ::io ::ErrorKind ::InvalidInput );
+    assert_eq!( supervisor.signal( "absent", "SIGHUP" ).unwrap_err().kind(), std ::io ::ErrorKind ::NotFound );
+  }
+
+  /// A service ignoring SIGTERM is killed after `stop_timeout`.
+  #[ test ]
+  fn shutdown_escalates_to_sigkill()
+  {
+    let mut supervisor = Supervisor ::new();
+    let service = Service ::former()
+    .name( "stubborn" )
+    .run( sh( "trap '' TERM; echo up; while true; do sleep 0.02; done" ) )
+    .readiness( Readiness ::LogLine( regex ::Regex ::new( "up" ).unwrap() ) )
+    .stop_timeout( Duration ::from_millis( 200 ) )
+    .form();
+    supervisor.start( service ).unwrap();
+    let pid = i32 ::try_from( supervisor.state( "stubborn" ).unwrap().pid.unwrap() ).unwrap();
+
+    let started = Instant ::now();
+    supervisor.shutdown().unwrap();
+
+    assert!( started.elapsed() >= Duration ::from_millis( 200 ) );
+    assert!( !the_module ::lifecycle ::check ::is_process_alive( pid ).unwrap() );
+    assert!( supervisor.services().is_empty() );
+  }
+
+  /// Output of every instance goes to the log, which is rotated by size.
+  #[ test ]
+  fn log_is_written_and_rotated()
+  {
+    let dir = assert_fs ::TempDir ::new().unwrap();
+    let log = dir.path().join( "logs/service.log" );
+    let mut supervisor = Supervisor ::new();
+    let service = Service ::former()
+    .name( "chatty" )
+    .run( sh( "for i in 1 2 3; do echo err-$i >&2; done; sleep 0.1; for i in 1 2 3 4 5 6; do echo line-$i; done; echo done; sleep 30" ) )
+    .readiness( Readiness ::LogLine( regex ::Regex ::new( "^done$" ).unwrap() ) )
+    .log_file( log.clone() )
+    .log_max_bytes( 20_u64 )
+    .log_keep( 2_usize )
+    .form();
+    supervisor.start( service ).unwrap();
+
+    let rotated = | n : usize | dir.path().join( format!( "logs/service.log.{n}" ) );
+    assert!( std ::fs ::read_to_string( &log ).unwrap().contains( "done" ) );
+    assert!( rotated( 1 ).exists() && rotated( 2 ).exists() );
+    assert!( !rotated( 3 ).exists() );
+    for file in [ log.clone(), rotated( 1 ), rotated( 2 ) ]
+    {
+      assert!( std ::fs ::metadata( &file ).unwrap().len() <= 20 );
+    }
+  }
+
+  /// Names are unique within a supervisor.
+  #[ test ]
+  fn duplicate_name_is_rejected()
+  {
+    let mut supervisor = Supervisor ::new();
+    supervisor.start( Service ::former().name( "one" ).run( sh( "sleep 30" ) ).form() ).unwrap();
+
+    let error = supervisor.start( Service ::former().name( "one" ).run( sh( "sleep 30" ) ).form() ).unwrap_err();
+    assert_eq!( error.kind(), std ::io ::ErrorKind ::AlreadyExists );
+  }
+}
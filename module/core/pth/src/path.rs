@@ -1177,8 +1177,20 @@ crate ::mod_interface!
   layer current_path;
   /// Type alias for `NormalizedPath` - emphasizes native path handling semantics.
   layer native_path;
+  /// Forward-slash UTF-8 path that reads the same on every platform.
+  layer portable_path;
 
   /// Convenient joining.
   layer joining;
 
+  /// Compiled glob patterns and ordered pattern sets.
+  #[ cfg( feature = "path_glob" ) ]
+  layer glob;
+  /// Recursive directory walk with glob filters and `.gitignore` support.
+  #[ cfg( feature = "path_walk" ) ]
+  layer walk;
+  /// Path resolution confined to a root directory.
+  #[ cfg( feature = "path_jail" ) ]
+  layer jail;
+
 }
@@ -0,0 +1,356 @@
+/// Define a private namespace for all its items.
+mod private
+{
+  use crate :: *;
+  use std ::
+  {
+    io,
+    path ::Path,
+  };
+  use core ::fmt;
+  use regex ::Regex;
+
+  /// A compiled glob pattern.
+  ///
+  /// Patterns match whole paths, with `/` as the separator on every platform:
+  ///
+  /// - `*` matches any run of characters within one component, `?` one character
+  /// - `**` as a whole component matches zero or more components
+  /// - `[abc]`, `[a-z]` match one character of a class, `[!abc]` or `[^abc]` one outside it
+  /// - `{a,b}` matches any of the alternatives, which may contain patterns and nest
+  /// - `\` escapes the next character
+  /// - a leading `!` negates the pattern; in a [`GlobSet`] it re-includes what earlier patterns matched
+  ///
+  /// Paths are normalized before matching, so `./src/../lib.rs` is matched as `lib.rs`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use pth ::path ::Glob;
+  ///
+  /// let glob = Glob ::new( "src/**/*.{rs,toml}" ).unwrap();
+  /// assert!( glob.is_match( "src/lib.rs" ) );
+  /// assert!( glob.is_match( "src/path/glob.rs" ) );
+  /// assert!( !glob.is_match( "tests/tests.rs" ) );
+  ///
+  /// let not_hidden = Glob ::new( "!**/.*" ).unwrap();
+  /// assert!( not_hidden.is_negated() );
+  /// assert!( !not_hidden.is_match( "a/.git" ) );
+  /// ```
+  #[ derive( Debug, Clone ) ]
+  pub struct Glob
+  {
+    pattern: String,
+    negated: bool,
+    matcher: Matcher,
+  }
+
+  /// How a pattern is matched: plain comparison for patterns without wildcards.
+  #[ derive( Debug, Clone ) ]
+  enum Matcher
+  {
+    Literal( String ),
+    Regex( Regex ),
+  }
+
+  impl Glob
+  {
+    /// Compile a pattern.
+    ///
+    /// A pattern without wildcards is matched literally after removing escapes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `io::ErrorKind::InvalidInput` for an unclosed `[` or `{`, an unmatched `}`,
+    /// or a trailing `\` — whether or not the pattern has other wildcards, so `a[b`
+    /// and `*[b` are both rejected; write `a\[b` for a literal bracket.
+    pub fn new( pattern: &str ) -> Result< Self, io ::Error >
+    {
+      let ( negated, body ) = match pattern.strip_prefix( '!' )
+      {
+        Some( body ) => ( true, body ),
+        None => ( false, pattern ),
+      };
+      // Translate every pattern, so malformed ones fail the same way with or without wildcards.
+      let expression = translate( body ).map_err( | reason | invalid( pattern, reason ) )?;
+      let matcher = if path ::is_glob( body )
+      {
+        Matcher ::Regex( Regex ::new( &format!( "^{expression}$" ) ).map_err( | err | invalid( pattern, &err.to_string() ) )? )
+      }
+      else
+      {
+        Matcher ::Literal( match_form( Path ::new( &unescape( body ).map_err( | reason | invalid( pattern, reason ) )? ) ) )
+      };
+      Ok( Self { pattern: pattern.to_string(), negated, matcher } )
+    }
+
+    /// The pattern as written, including a leading `!`.
+    #[ must_use ]
+    pub fn as_str( &self ) -> &str
+    {
+      &self.pattern
+    }
+
+    /// Whether the pattern starts with `!`.
+    #[ must_use ]
+    pub fn is_negated( &self ) -> bool
+    {
+      self.negated
+    }
+
+    /// Whether `path` matches the pattern, inverted for a negated pattern.
+    #[ must_use ]
+    pub fn is_match( &self, path: impl AsRef< Path > ) -> bool
+    {
+      self.matches_body( &match_form( path.as_ref() ) ) != self.negated
+    }
+
+    /// Whether a path in match form matches the pattern, ignoring negation.
+    pub( crate ) fn matches_body( &self, path: &str ) -> bool
+    {
+      match &self.matcher
+      {
+        Matcher ::Literal( literal ) => literal == path,
+        Matcher ::Regex( regex ) => regex.is_match( path ),
+      }
+    }
+  }
+
+  impl fmt ::Display for Glob
+  {
+    fn fmt( &self, f: &mut fmt ::Formatter< '_ > ) -> fmt ::Result
+    {
+      f.write_str( &self.pattern )
+    }
+  }
+
+  impl core ::str ::FromStr for Glob
+  {
+    type Err = io ::Error;
+
+    fn from_str( pattern: &str ) -> Result< Self, Self ::Err >
+    {
+      Self ::new( pattern )
+    }
+  }
+
+  /// An ordered set of glob patterns where the last matching pattern decides.
+  ///
+  /// A path matches the set if the last pattern that matches it, ignoring
+  /// negation, is not negated. So `[ "*.rs", "!main.rs" ]` matches every Rust
+  /// file except `main.rs`, the way `.gitignore` re-includes files.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use pth ::path ::GlobSet;
+  ///
+  /// let set = GlobSet ::new( [ "target/**", "**/*.bak", "!target/keep/**" ] ).unwrap();
+  /// assert!( set.is_match( "target/debug/app" ) );
+  /// assert!( set.is_match( "src/lib.rs.bak" ) );
+  /// assert!( !set.is_match( "target/keep/notes.md" ) );
+  /// assert!( !set.is_match( "src/lib.rs" ) );
+  /// ```
+  #[ derive( Debug, Clone, Default ) ]
+  pub struct GlobSet
+  {
+    globs: Vec< Glob >,
+  }
+
+  impl GlobSet
+  {
+    /// Compile every pattern, in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first pattern that does not compile.
+    pub fn new< I, S >( patterns: I ) -> Result< Self, io ::Error >
+    where
+      I: IntoIterator< Item = S >,
+      S: AsRef< str >,
+    {
+      let globs = patterns.into_iter().map( | pattern | Glob ::new( pattern.as_ref() ) ).collect::< Result< _, _ > >()?;
+      Ok( Self { globs } )
+    }
+
+    /// Append a compiled pattern; it takes precedence over earlier ones.
+    pub fn push( &mut self, glob: Glob ) -> &mut Self
+    {
+      self.globs.push( glob );
+      self
+    }
+
+    /// Patterns in order.
+    #[ must_use ]
+    pub fn globs( &self ) -> &[ Glob ]
+    {
+      &self.globs
+    }
+
+    /// Whether the set has no patterns.
+    #[ must_use ]
+    pub fn is_empty( &self ) -> bool
+    {
+      self.globs.is_empty()
+    }
+
+    /// Whether `path` matches the set.
+    #[ must_use ]
+    pub fn is_match( &self, path: impl AsRef< Path > ) -> bool
+    {
+      let path = match_form( path.as_ref() );
+      self.globs.iter().rev().find( | glob | glob.matches_body( &path ) ).is_some_and( | glob | !glob.negated )
+    }
+  }
+
+  /// Normalize `path` to the `/`-separated form patterns are matched against, without a leading `./`.
+  pub( crate ) fn match_form( path: &Path ) -> String
+  {
+    let normalized = path ::normalize( path ).to_string_lossy().replace( '\\', "/" );
+    match normalized.strip_prefix( "./" )
+    {
+      Some( rest ) => rest.to_string(),
+      None if normalized == "." => String ::new(),
+      None => normalized,
+    }
+  }
+
+  fn invalid( pattern: &str, reason: &str ) -> io ::Error
+  {
+    io ::Error ::new( io ::ErrorKind ::InvalidInput, format!( "Invalid glob `{pattern}`: {reason}" ) )
+  }
+
+  /// Remove escapes from a pattern without wildcards.
+  fn unescape( body: &str ) -> Result< String, &'static str >
+  {
+    let mut result = String ::with_capacity( body.len() );
+    let mut chars = body.chars();
+    while let Some( c ) = chars.next()
+    {
+      if c == '\\'
+      {
+        result.push( chars.next().ok_or( "trailing `\\`" )? );
+      }
+      else
+      {
+        result.push( c );
+      }
+    }
+    Ok( result )
+  }
+
+  /// Translate a pattern body into a regular expression without anchors.
+  fn translate( body: &str ) -> Result< String, &'static str >
+  {
+    let chars: Vec< char > = body.chars().collect();
+    let mut result = String ::with_capacity( body.len() * 2 );
+    let mut braces = 0_usize;
+    let mut i = 0;
+    while i < chars.len()
+    {
+      let c = chars[ i ];
+      match c
+      {
+        '\\' =>
+        {
+          let escaped = chars.get( i + 1 ).ok_or( "trailing `\\`" )?;
+          result.push_str( &regex ::escape( &escaped.to_string() ) );
+          i += 1;
+        }
+        '*' if chars.get( i + 1 ) == Some( &'*' ) =>
+        {
+          let at_start = i == 0 || matches!( chars[ i - 1 ], '/' | '{' | ',' );
+          let at_end = matches!( chars.get( i + 2 ), None | Some( '/' | '}' | ',' ) );
+          if at_start && chars.get( i + 2 ) == Some( &'/' )
+          {
+            // `**/` : zero or more leading components.
+            result.push_str( "(?:[^/]*/)*" );
+            i += 2;
+          }
+          else if at_start && at_end
+          {
+            // `**` as the last component: everything below, including nothing.
+            result.push_str( ".*" );
+            i += 1;
+          }
+          else
+          {
+            result.push_str( "[^/]*" );
+            i += 1;
+          }
+        }
+        '*' => result.push_str( "[^/]*" ),
+        '?' => result.push_str( "[^/]" ),
+        '[' => i = class( &chars, i, &mut result )?,
+        '{' =>
+        {
+          braces += 1;
+          result.push_str( "(?:" );
+        }
+        ',' if braces > 0 => result.push( '|' ),
+        '}' if braces > 0 =>
+        {
+          braces -= 1;
+          result.push( ')' );
+        }
+        '}' => return Err( "unmatched `}`" ),
+        _ => result.push_str( &regex ::escape( &c.to_string() ) ),
+      }
+      i += 1;
+    }
+    if braces > 0
+    {
+      return Err( "unclosed `{`" );
+    }
+    Ok( result )
+  }
+
+  /// Translate the character class starting at `start`; returns the index of its closing `]`.
+  fn class( chars: &[ char ], start: usize, result: &mut String ) -> Result< usize, &'static str >
+  {
+    let mut i = start + 1;
+    let negated = matches!( chars.get( i ), Some( '!' | '^' ) );
+    if negated
+    {
+      i += 1;
+    }
+    result.push_str( if negated { "[^/" } else { "[" } );
+    let first = i;
+    loop
+    {
+      let c = *chars.get( i ).ok_or( "unclosed `[`" )?;
+      match c
+      {
+        ']' if i > first => break,
+        '\\' =>
+        {
+          i += 1;
+          let escaped = *chars.get( i ).ok_or( "trailing `\\`" )?;
+          if matches!( escaped, '\\' | '[' | ']' | '^' | '&' | '~' | '-' )
+          {
+            result.push( '\\' );
+          }
+          result.push( escaped );
+        }
+        '-' if i > first && chars.get( i + 1 ).is_some_and( | next | *next != ']' ) => result.push( '-' ),
+        '[' | ']' | '^' | '&' | '~' | '-' =>
+        {
+          result.push( '\\' );
+          result.push( c );
+        }
+        _ => result.push( c ),
+      }
+      i += 1;
+    }
+    result.push( ']' );
+    Ok( i )
+  }
+}
+
+pub( crate ) use private ::match_form;
+
+crate ::mod_interface!
+{
+  orphan use Glob;
+  orphan use GlobSet;
+}
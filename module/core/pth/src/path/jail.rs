@@ -0,0 +1,372 @@
+/// Define a private namespace for all its items.
+mod private
+{
+  use crate :: *;
+  use std ::
+  {
+    collections ::VecDeque,
+    ffi ::OsString,
+    fs,
+    io,
+    path :: { Component, Path, PathBuf },
+  };
+  use core ::fmt;
+
+  /// Symbolic links followed while resolving one path before giving up, as Linux does.
+  const MAX_LINKS: usize = 40;
+
+  /// A root directory that resolved paths may not leave.
+  ///
+  /// [`Jail::resolve`] joins a user-supplied path to the root and follows symbolic
+  /// links one component at a time. A `..` above the root, or a link whose target
+  /// lies outside it, is an [`JailError::Escape`] rather than being clamped, so a
+  /// caller never silently writes somewhere else than asked. Components that do not
+  /// exist yet are accepted, so a path can be checked before the file is created.
+  ///
+  /// [`Jail::resolve_lexical`] applies the same rules without reading the filesystem,
+  /// for virtual filesystems and roots that do not exist yet.
+  ///
+  /// Resolution is a check, not a lock: a link created after `resolve` returns is
+  /// not seen. Unlike most of the crate, `resolve` reads the filesystem.
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// use pth ::path ::Jail;
+  ///
+  /// let jail = Jail ::new( "/srv/uploads" ).unwrap();
+  /// let file = jail.resolve( "user/avatar.png" ).unwrap();
+  /// assert_eq!( file.relative().as_ref(), std ::path ::Path ::new( "user/avatar.png" ) );
+  ///
+  /// assert!( jail.resolve( "../etc/passwd" ).is_err() );
+  /// assert!( jail.resolve( "/etc/passwd" ).is_err() );
+  /// ```
+  #[ derive( Debug, Clone, PartialEq, Eq ) ]
+  pub struct Jail
+  {
+    /// Root with links resolved.
+    root: AbsolutePath,
+    /// Root as given, normalized, to recognize absolute paths spelled through it.
+    given: PathBuf,
+  }
+
+  impl Jail
+  {
+    /// Confine paths to the directory `root`, relative to the current directory if relative.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`JailError::Io`] if `root` does not exist, is not a directory,
+    /// or cannot be resolved.
+    pub fn new( root: impl AsRef< Path > ) -> Result< Self, JailError >
+    {
+      let root = root.as_ref();
+      let io_error = | source | JailError ::Io { path: root.to_path_buf(), source };
+      let real = fs ::canonicalize( root ).map_err( io_error )?;
+      if !real.is_dir()
+      {
+        return Err( io_error( io ::Error ::new( io ::ErrorKind ::InvalidInput, "not a directory" ) ) );
+      }
+      let given = if root.is_absolute()
+      {
+        path ::normalize( root )
+      }
+      else
+      {
+        path ::normalize( std ::env ::current_dir().map_err( io_error )?.join( root ) )
+      };
+      Ok( Self { root: AbsolutePath ::try_from( real ).map_err( io_error )?, given } )
+    }
+
+    /// Confine paths to `root` without touching the filesystem.
+    ///
+    /// Only [`Jail::resolve_lexical`] is meaningful for such a jail: `resolve`
+    /// would read a root that was never checked to exist.
+    #[ must_use ]
+    pub fn lexical( root: AbsolutePath ) -> Self
+    {
+      let given = root.as_ref().to_path_buf();
+      Self { root, given }
+    }
+
+    /// The root, with links resolved for a jail made by [`Jail::new`].
+    #[ must_use ]
+    pub fn root( &self ) -> &AbsolutePath
+    {
+      &self.root
+    }
+
+    /// Resolve `path` inside the jail, following symbolic links.
+    ///
+    /// A relative `path` is joined to the root. An absolute `path` is accepted if it
+    /// lies under the root, spelled either as given to [`Jail::new`] or with links resolved.
+    ///
+    /// # Errors
+    ///
+    /// - [`JailError::Escape`] if `path` or a link on the way leads outside the root
+    /// - [`JailError::SymlinkLoop`] after following too many links
+    /// - [`JailError::Io`] if an existing component cannot be inspected
+    pub fn resolve( &self, path: impl AsRef< Path > ) -> Result< ScopedPath, JailError >
+    {
+      let requested = path.as_ref();
+      // Each pending component remembers the link it was read from, to report escapes.
+      let mut pending: VecDeque< ( OsString, Option< PathBuf > ) > = self.components( requested )?.into_iter().map( | name | ( name, None ) ).collect();
+      let mut current = self.root.as_ref().to_path_buf();
+      let mut depth = 0_usize;
+      let mut links = 0_usize;
+      while let Some( ( name, link ) ) = pending.pop_front()
+      {
+        if name == ".."
+        {
+          if depth == 0
+          {
+            return Err( JailError ::Escape { path: requested.to_path_buf(), link } );
+          }
+          current.pop();
+          depth -= 1;
+          continue;
+        }
+        let candidate = current.join( &name );
+        // Missing components are checked too: `missing/../link` still reaches `link`.
+        let metadata = match fs ::symlink_metadata( &candidate )
+        {
+          Ok( metadata ) => Some( metadata ),
+          Err( error ) if matches!( error.kind(), io ::ErrorKind ::NotFound | io ::ErrorKind ::NotADirectory ) => None,
+          Err( source ) => return Err( JailError ::Io { path: candidate, source } ),
+        };
+        if metadata.is_some_and( | metadata | metadata.file_type().is_symlink() )
+        {
+          links += 1;
+          if links > MAX_LINKS
+          {
+            return Err( JailError ::SymlinkLoop { path: requested.to_path_buf() } );
+          }
+          let target = fs ::read_link( &candidate ).map_err( | source | JailError ::Io { path: candidate.clone(), source } )?;
+          let escape = || JailError ::Escape { path: requested.to_path_buf(), link: Some( candidate.clone() ) };
+          let target_components = if target.is_absolute()
+          {
+            // Restart from the root; the target must be spelled under it.
+            current = self.root.as_ref().to_path_buf();
+            depth = 0;
+            self.inside( &target ).ok_or_else( escape )?
+          }
+          else
+          {
+            relative_components( &target ).ok_or_else( escape )?
+          };
+          for component in target_components.into_iter().rev()
+          {
+            pending.push_front( ( component, Some( candidate.clone() ) ) );
+          }
+          continue;
+        }
+        current = candidate;
+        depth += 1;
+      }
+      self.scoped( current )
+    }
+
+    /// Resolve `path` inside the jail by its text alone, without following links.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`JailError::Escape`] if `path` leads outside the root.
+    pub fn resolve_lexical( &self, path: impl AsRef< Path > ) -> Result< ScopedPath, JailError >
+    {
+      let requested = path.as_ref();
+      let mut current = self.root.as_ref().to_path_buf();
+      let mut depth = 0_usize;
+      for name in self.components( requested )?
+      {
+        if name == ".."
+        {
+          if depth == 0
+          {
+            return Err( JailError ::Escape { path: requested.to_path_buf(), link: None } );
+          }
+          current.pop();
+          depth -= 1;
+        }
+        else
+        {
+          current.push( name );
+          depth += 1;
+        }
+      }
+      self.scoped( current )
+    }
+
+    /// Whether `path` resolves inside the jail.
+    #[ must_use ]
+    pub fn contains( &self, path: impl AsRef< Path > ) -> bool
+    {
+      self.resolve( path ).is_ok()
+    }
+
+    /// Components of `path` relative to the root, for a relative or an absolute path.
+    fn components( &self, path: &Path ) -> Result< Vec< OsString >, JailError >
+    {
+      let components = if path.has_root()
+      {
+        self.inside( path )
+      }
+      else
+      {
+        relative_components( path )
+      };
+      components.ok_or_else( || JailError ::Escape { path: path.to_path_buf(), link: None } )
+    }
+
+    /// Components below the root of an absolute path, if it is spelled under the root.
+    ///
+    /// `..` is not collapsed first: it may step back across a link, so it is left to resolution.
+    fn inside( &self, path: &Path ) -> Option< Vec< OsString > >
+    {
+      let below = path.strip_prefix( &self.root ).or_else( | _ | path.strip_prefix( &self.given ) ).ok()?;
+      relative_components( below )
+    }
+
+    fn scoped( &self, path: PathBuf ) -> Result< ScopedPath, JailError >
+    {
+      let io_error = | source | JailError ::Io { path: path.clone(), source };
+      let relative = path.strip_prefix( &self.root ).unwrap_or( Path ::new( "" ) );
+      let relative = NormalizedPath ::try_from( if relative.as_os_str().is_empty() { Path ::new( "." ) } else { relative } ).map_err( io_error )?;
+      Ok( ScopedPath { path: AbsolutePath ::try_from( path.as_path() ).map_err( io_error )?, relative } )
+    }
+  }
+
+  /// Components of a path without a root, `.` removed; `None` for a path with a root or prefix.
+  fn relative_components( path: &Path ) -> Option< Vec< OsString > >
+  {
+    let mut components = Vec ::new();
+    for component in path.components()
+    {
+      match component
+      {
+        Component ::Normal( name ) => components.push( name.to_os_string() ),
+        Component ::ParentDir => components.push( "..".into() ),
+        Component ::CurDir => {}
+        Component ::RootDir | Component ::Prefix( _ ) => return None,
+      }
+    }
+    Some( components )
+  }
+
+  /// A path proven to lie inside a [`Jail`].
+  #[ derive( Debug, Clone, PartialEq, Eq ) ]
+  pub struct ScopedPath
+  {
+    path: AbsolutePath,
+    relative: NormalizedPath,
+  }
+
+  impl ScopedPath
+  {
+    /// Absolute path, with links resolved by [`Jail::resolve`].
+    #[ must_use ]
+    pub fn path( &self ) -> &AbsolutePath
+    {
+      &self.path
+    }
+
+    /// Path relative to the jail root; `.` for the root itself.
+    #[ must_use ]
+    pub fn relative( &self ) -> &NormalizedPath
+    {
+      &self.relative
+    }
+
+    /// Take the absolute path.
+    #[ must_use ]
+    pub fn into_path( self ) -> AbsolutePath
+    {
+      self.path
+    }
+  }
+
+  impl AsRef< Path > for ScopedPath
+  {
+    #[ inline ]
+    fn as_ref( &self ) -> &Path
+    {
+      self.path.as_ref()
+    }
+  }
+
+  /// Why a path could not be resolved inside a [`Jail`].
+  #[ derive( Debug ) ]
+  pub enum JailError
+  {
+    /// The path, or the symbolic link `link` on its way, leads outside the root.
+    Escape
+    {
+      /// Path as requested.
+      path: PathBuf,
+      /// Link whose target is outside the root, if a link caused the escape.
+      link: Option< PathBuf >,
+    },
+    /// Too many symbolic links were followed, as in a link cycle.
+    SymlinkLoop
+    {
+      /// Path as requested.
+      path: PathBuf,
+    },
+    /// The filesystem could not be read.
+    Io
+    {
+      /// Path being inspected.
+      path: PathBuf,
+      /// Underlying error.
+      source: io ::Error,
+    },
+  }
+
+  impl fmt ::Display for JailError
+  {
+    fn fmt( &self, f: &mut fmt ::Formatter< '_ > ) -> fmt ::Result
+    {
+      match self
+      {
+        Self ::Escape { path, link: None } => write!( f, "Path `{}` escapes the jail root", path.display() ),
+        Self ::Escape { path, link: Some( link ) } =>
+        write!( f, "Path `{}` escapes the jail root through link `{}`", path.display(), link.display() ),
+        Self ::SymlinkLoop { path } => write!( f, "Too many symbolic links resolving `{}`", path.display() ),
+        Self ::Io { path, source } => write!( f, "Cannot resolve `{}`: {source}", path.display() ),
+      }
+    }
+  }
+
+  impl std ::error ::Error for JailError
+  {
+    fn source( &self ) -> Option< &( dyn std ::error ::Error + 'static ) >
+    {
+      match self
+      {
+        Self ::Io { source, .. } => Some( source ),
+        _ => None,
+      }
+    }
+  }
+
+  impl From< JailError > for io ::Error
+  {
+    /// Escapes become `PermissionDenied` and link loops `InvalidInput`; I/O errors keep their kind.
+    fn from( error: JailError ) -> Self
+    {
+      let kind = match &error
+      {
+        JailError ::Escape { .. } => io ::ErrorKind ::PermissionDenied,
+        JailError ::SymlinkLoop { .. } => io ::ErrorKind ::InvalidInput,
+        JailError ::Io { source, .. } => source.kind(),
+      };
+      io ::Error ::new( kind, error )
+    }
+  }
+}
+
+crate ::mod_interface!
+{
+  orphan use Jail;
+  orphan use ScopedPath;
+  orphan use JailError;
+}
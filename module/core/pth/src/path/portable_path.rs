@@ -0,0 +1,606 @@
+/// Define a private namespace for all its items.
+mod private
+{
+  use crate :: *;
+  use std ::
+  {
+    borrow ::Cow,
+    io,
+    path :: { Component, Path, PathBuf, Prefix },
+  };
+  use core ::
+  {
+    fmt,
+    str ::FromStr,
+  };
+  #[ cfg( feature = "derive_serde" ) ]
+  use serde :: { Serialize, Deserialize };
+
+  /// A forward-slash, UTF-8 path that means the same on every platform.
+  ///
+  /// Unlike `AbsolutePath` and `NormalizedPath`, which wrap the host `PathBuf`,
+  /// a `PortablePath` stores one textual form, so a path written into a manifest
+  /// on Windows reads back unchanged on Linux. The forms are:
+  ///
+  /// - relative: `src/lib.rs`
+  /// - rooted: `/usr/share`
+  /// - Windows drive: `C:/Users/me`
+  /// - Windows UNC share: `//server/share/docs`
+  ///
+  /// Parsing text accepts `/` and `\` separators and the Windows verbatim prefixes
+  /// `\\?\C:\` and `\\?\UNC\`. It drops `.` components and repeated separators, but
+  /// keeps `..` components, so no information about the path is lost.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use pth ::PortablePath;
+  ///
+  /// let path = PortablePath ::parse( r"C:\Users\me\.\project" ).unwrap();
+  /// assert_eq!( path.as_str(), "C:/Users/me/project" );
+  /// assert_eq!( path.to_windows_string(), r"C:\Users\me\project" );
+  /// assert_eq!( path.drive(), Some( 'C' ) );
+  ///
+  /// let uri = path.to_file_uri().unwrap();
+  /// assert_eq!( uri, "file:///C:/Users/me/project" );
+  /// assert_eq!( PortablePath ::from_file_uri( &uri ).unwrap(), path );
+  /// ```
+  #[ cfg_attr( feature = "derive_serde", derive( Serialize, Deserialize ) ) ]
+  #[ cfg_attr( feature = "derive_serde", serde( try_from = "String", into = "String" ) ) ]
+  #[ derive( Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Hash ) ]
+  pub struct PortablePath( String );
+
+  impl PortablePath
+  {
+    /// Parse a path written with `/` or `\` separators.
+    ///
+    /// # Errors
+    ///
+    /// Returns `io::ErrorKind::InvalidInput` for a NUL character, a drive-relative
+    /// path such as `C:file`, a UNC path without a share, or a Windows device path.
+    pub fn parse( text: &str ) -> Result< Self, io ::Error >
+    {
+      if text.contains( '\0' )
+      {
+        return Err( invalid( text, "contains a NUL character" ) );
+      }
+      let slashed = text.replace( '\\', "/" );
+      let mut rest = slashed.as_str();
+      let verbatim_unc;
+      if let Some( verbatim ) = rest.strip_prefix( "//?/" )
+      {
+        rest = match verbatim.get( ..4 )
+        {
+          Some( unc ) if unc.eq_ignore_ascii_case( "UNC/" ) =>
+          {
+            verbatim_unc = format!( "//{}", &verbatim[ 4 .. ] );
+            &verbatim_unc
+          }
+          _ => verbatim,
+        };
+      }
+      if rest.starts_with( "//./" ) || rest.starts_with( "//?/" )
+      {
+        return Err( invalid( text, "Windows device paths are not portable" ) );
+      }
+
+      let ( prefix, rest ) = if let Some( unc ) = rest.strip_prefix( "//" ).filter( | unc | !unc.starts_with( '/' ) )
+      {
+        let mut parts = unc.splitn( 3, '/' );
+        let server = parts.next().unwrap_or_default();
+        let share = parts.next().unwrap_or_default();
+        if server.is_empty() || share.is_empty()
+        {
+          return Err( invalid( text, "UNC path needs a server and a share" ) );
+        }
+        ( format!( "//{server}/{share}" ), format!( "/{}", parts.next().unwrap_or_default() ) )
+      }
+      else if is_drive( rest )
+      {
+        let ( drive, tail ) = rest.split_at( 2 );
+        if !tail.starts_with( '/' )
+        {
+          return Err( invalid( text, "drive-relative paths are not portable" ) );
+        }
+        ( drive.to_string(), tail.to_string() )
+      }
+      else
+      {
+        ( String ::new(), rest.to_string() )
+      };
+
+      let components = rest.split( '/' ).filter( | part | !part.is_empty() && *part != "." );
+      Ok( Self ::assemble( &prefix, rest.starts_with( '/' ), components ) )
+    }
+
+    /// Join prefix, root and components into the stored form.
+    fn assemble< 'a >( prefix: &str, rooted: bool, components: impl Iterator< Item = &'a str > ) -> Self
+    {
+      let mut path = prefix.to_string();
+      if rooted
+      {
+        path.push( '/' );
+      }
+      for ( index, component ) in components.enumerate()
+      {
+        if index > 0
+        {
+          path.push( '/' );
+        }
+        path.push_str( component );
+      }
+      if path.is_empty()
+      {
+        path.push( '.' );
+      }
+      Self( path )
+    }
+
+    /// The portable form, always with `/` separators.
+    #[ must_use ]
+    pub fn as_str( &self ) -> &str
+    {
+      &self.0
+    }
+
+    /// The portable form rendered with `\` separators, as Windows shows it.
+    #[ must_use ]
+    pub fn to_windows_string( &self ) -> String
+    {
+      self.0.replace( '/', "\\" )
+    }
+
+    /// The portable form as Unix shows it, which is the portable form itself.
+    #[ must_use ]
+    pub fn to_unix_string( &self ) -> String
+    {
+      self.0.clone()
+    }
+
+    /// Convert to a host path.
+    ///
+    /// On Windows separators become `\`. Elsewhere the portable form is used as is,
+    /// so drive and UNC paths are not meaningful there.
+    #[ must_use ]
+    pub fn to_native( &self ) -> PathBuf
+    {
+      #[ cfg( windows ) ]
+      {
+        PathBuf ::from( self.to_windows_string() )
+      }
+      #[ cfg( not( windows ) ) ]
+      {
+        PathBuf ::from( &self.0 )
+      }
+    }
+
+    /// Drive letter of a Windows drive path.
+    #[ must_use ]
+    pub fn drive( &self ) -> Option< char >
+    {
+      is_drive( &self.0 ).then( || self.0.chars().next() ).flatten()
+    }
+
+    /// Whether the path is a Windows UNC share path.
+    #[ must_use ]
+    pub fn is_unc( &self ) -> bool
+    {
+      self.0.starts_with( "//" )
+    }
+
+    /// Whether the path starts at a root: `/`, a drive root or a UNC share.
+    #[ must_use ]
+    pub fn is_absolute( &self ) -> bool
+    {
+      self.split().1.starts_with( '/' )
+    }
+
+    /// The last component, unless the path ends in a root, `.` or `..`.
+    #[ must_use ]
+    pub fn file_name( &self ) -> Option< &str >
+    {
+      let ( _, body ) = self.split();
+      body.rsplit( '/' ).next().filter( | name | !name.is_empty() && *name != "." && *name != ".." )
+    }
+
+    /// The path without its last component, if it has one.
+    #[ must_use ]
+    pub fn parent( &self ) -> Option< PortablePath >
+    {
+      self.file_name()?;
+      let ( prefix, body ) = self.split();
+      let parent = match body.rfind( '/' )
+      {
+        Some( 0 ) => "/",
+        Some( index ) => &body[ .. index ],
+        None => "",
+      };
+      Some( Self ::assemble( prefix, parent.starts_with( '/' ), parent.split( '/' ).filter( | part | !part.is_empty() ) ) )
+    }
+
+    /// Append `path`; an absolute `path` replaces this one, like `Path::join`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of parsing `path`.
+    pub fn join( &self, path: &str ) -> Result< PortablePath, io ::Error >
+    {
+      let tail = Self ::parse( path )?;
+      if tail.is_absolute() || tail.drive().is_some()
+      {
+        return Ok( tail );
+      }
+      if self.0 == "."
+      {
+        return Ok( tail );
+      }
+      Self ::parse( &format!( "{}/{}", self.0, tail.0 ) )
+    }
+
+    /// Path from `base` to `self`, as computed by [`path::path_relative`].
+    ///
+    /// Paths under different drives or shares have no relative form; `self` is returned.
+    #[ must_use ]
+    pub fn relative_to( &self, base: &PortablePath ) -> PortablePath
+    {
+      let ( prefix, body ) = self.split();
+      let ( base_prefix, base_body ) = base.split();
+      if !prefix.eq_ignore_ascii_case( base_prefix ) || body.starts_with( '/' ) != base_body.starts_with( '/' )
+      {
+        return self.clone();
+      }
+      Self ::from_algorithm( "", &path ::path_relative( base_body, body ).to_string_lossy() )
+    }
+
+    /// Move `self` under `new_base`, first removing `old_base` if given, as [`path::rebase`] does.
+    ///
+    /// The drive or share of `self` is replaced by that of `new_base`.
+    /// Returns `None` if `old_base` is not a prefix of `self`.
+    #[ must_use ]
+    pub fn rebase( &self, new_base: &PortablePath, old_base: Option< &PortablePath > ) -> Option< PortablePath >
+    {
+      let ( prefix, body ) = self.split();
+      let ( new_prefix, new_body ) = new_base.split();
+      let rebased = match old_base.map( PortablePath ::split )
+      {
+        Some( ( old_prefix, old_body ) ) if old_prefix.eq_ignore_ascii_case( prefix ) => path ::rebase( body, new_body, Some( old_body ) )?,
+        Some( _ ) => return None,
+        None => path ::rebase( body, new_body, None )?,
+      };
+      Some( Self ::from_algorithm( new_prefix, &rebased.to_string_lossy() ) )
+    }
+
+    /// Longest common directory of `paths`, as computed by [`path::path_common`].
+    ///
+    /// Returns `None` for no paths or paths under different drives or shares.
+    pub fn common< 'a >( paths: impl IntoIterator< Item = &'a PortablePath > ) -> Option< PortablePath >
+    {
+      let mut prefix = None;
+      let mut bodies = Vec ::new();
+      for path in paths
+      {
+        let ( path_prefix, body ) = path.split();
+        match prefix
+        {
+          None => prefix = Some( path_prefix ),
+          Some( first ) if !first.eq_ignore_ascii_case( path_prefix ) => return None,
+          Some( _ ) => {}
+        }
+        bodies.push( body );
+      }
+      let common = path ::path_common( bodies.into_iter() )?;
+      Some( Self ::from_algorithm( prefix?, &common ) )
+    }
+
+    /// Rebuild a path from the output of a `path` algorithm on the body of a path.
+    fn from_algorithm( prefix: &str, body: &str ) -> Self
+    {
+      let body = body.replace( '\\', "/" );
+      let components = body.split( '/' ).filter( | part | !part.is_empty() && *part != "." );
+      Self ::assemble( prefix, body.starts_with( '/' ), components )
+    }
+
+    /// Encode an absolute path as a `file://` URI.
+    ///
+    /// Characters outside the URI path set, including spaces and non-ASCII text,
+    /// are percent-encoded as UTF-8. UNC shares become the URI host.
+    ///
+    /// # Errors
+    ///
+    /// Returns `io::ErrorKind::InvalidInput` for a relative path.
+    pub fn to_file_uri( &self ) -> Result< String, io ::Error >
+    {
+      if !self.is_absolute()
+      {
+        return Err( invalid( &self.0, "only absolute paths have a file URI" ) );
+      }
+      let ( authority, path ) = match self.0.strip_prefix( "//" )
+      {
+        Some( unc ) => unc.split_at( unc.find( '/' ).unwrap_or( unc.len() ) ),
+        None => ( "", self.0.as_str() ),
+      };
+      // A drive path keeps its letter in the URI path: `file:///C:/dir`.
+      let slash = if self.drive().is_some() { "/" } else { "" };
+      Ok( format!( "file://{}{slash}{}", encode( authority ), encode( path ) ) )
+    }
+
+    /// Decode a `file://` URI.
+    ///
+    /// Accepts `file:///path`, `file:/path`, `file://localhost/path`, Windows drive
+    /// paths such as `file:///C:/dir`, and `file://server/share` for UNC shares.
+    ///
+    /// # Errors
+    ///
+    /// Returns `io::ErrorKind::InvalidInput` for another scheme, a query or fragment,
+    /// an invalid percent escape, or text that is not UTF-8 once decoded.
+    pub fn from_file_uri( uri: &str ) -> Result< Self, io ::Error >
+    {
+      let rest = uri.get( .. 5 ).filter( | scheme | scheme.eq_ignore_ascii_case( "file:" ) ).map( | _ | &uri[ 5 .. ] )
+      .ok_or_else( || invalid( uri, "not a file URI" ) )?;
+      if rest.contains( [ '?', '#' ] )
+      {
+        return Err( invalid( uri, "file URIs with a query or fragment are not supported" ) );
+      }
+      let ( host, path ) = match rest.strip_prefix( "//" )
+      {
+        Some( authority ) => authority.split_at( authority.find( '/' ).unwrap_or( authority.len() ) ),
+        None => ( "", rest ),
+      };
+      let path = decode( path ).ok_or_else( || invalid( uri, "invalid percent encoding" ) )?;
+      let host = decode( host ).ok_or_else( || invalid( uri, "invalid percent encoding" ) )?;
+      if !host.is_empty() && !host.eq_ignore_ascii_case( "localhost" )
+      {
+        return Self ::parse( &format!( "//{host}{path}" ) );
+      }
+      match path.strip_prefix( '/' )
+      {
+        Some( drive ) if is_drive( drive ) => Self ::parse( drive ),
+        _ => Self ::parse( &path ),
+      }
+    }
+
+    /// Split into the drive or share prefix and the remaining body.
+    fn split( &self ) -> ( &str, &str )
+    {
+      if let Some( unc ) = self.0.strip_prefix( "//" )
+      {
+        let share_end = unc.find( '/' ).map_or( unc.len(), | server | server + 1 + unc[ server + 1 .. ].find( '/' ).unwrap_or( unc.len() - server - 1 ) );
+        return self.0.split_at( share_end + 2 );
+      }
+      if is_drive( &self.0 )
+      {
+        return self.0.split_at( 2 );
+      }
+      ( "", &self.0 )
+    }
+  }
+
+  /// Whether `text` starts with a drive letter and a colon, as in `C:` or `C:/dir`.
+  fn is_drive( text: &str ) -> bool
+  {
+    let bytes = text.as_bytes();
+    bytes.len() >= 2 && bytes[ 0 ].is_ascii_alphabetic() && bytes[ 1 ] == b':'
+  }
+
+  fn invalid( path: &str, reason: &str ) -> io ::Error
+  {
+    io ::Error ::new( io ::ErrorKind ::InvalidInput, format!( "Invalid portable path `{path}`: {reason}" ) )
+  }
+
+  /// Percent-encode everything outside the RFC 3986 path characters.
+  fn encode( text: &str ) -> String
+  {
+    let mut encoded = String ::with_capacity( text.len() );
+    for byte in text.bytes()
+    {
+      if byte.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:@/".contains( &byte )
+      {
+        encoded.push( char ::from( byte ) );
+      }
+      else
+      {
+        encoded.push_str( &format!( "%{byte:02X}" ) );
+      }
+    }
+    encoded
+  }
+
+  /// Decode percent escapes; `None` for a malformed escape or non-UTF-8 result.
+  fn decode( text: &str ) -> Option< String >
+  {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec ::with_capacity( bytes.len() );
+    let mut index = 0;
+    while index < bytes.len()
+    {
+      if bytes[ index ] == b'%'
+      {
+        let hex = text.get( index + 1 .. index + 3 )?;
+        decoded.push( u8 ::from_str_radix( hex, 16 ).ok()? );
+        index += 3;
+      }
+      else
+      {
+        decoded.push( bytes[ index ] );
+        index += 1;
+      }
+    }
+    String ::from_utf8( decoded ).ok()
+  }
+
+  impl TryFrom< &Path > for PortablePath
+  {
+    type Error = io ::Error;
+
+    /// Convert a host path component by component.
+    ///
+    /// Fails for non-UTF-8 names, Windows device paths, and, on other platforms,
+    /// names containing `\` or a first name that reads as a drive, which would
+    /// change meaning when parsed back.
+    fn try_from( value: &Path ) -> Result< Self, Self ::Error >
+    {
+      let display = value.to_string_lossy();
+      let mut prefix = String ::new();
+      let mut rooted = false;
+      let mut components = Vec ::new();
+      for component in value.components()
+      {
+        match component
+        {
+          Component ::Prefix( value_prefix ) => match value_prefix.kind()
+          {
+            Prefix ::Disk( drive ) | Prefix ::VerbatimDisk( drive ) => prefix = format!( "{}:", char ::from( drive ) ),
+            Prefix ::UNC( server, share ) | Prefix ::VerbatimUNC( server, share ) =>
+            {
+              let server = server.to_str().ok_or_else( || not_utf8( &display ) )?;
+              let share = share.to_str().ok_or_else( || not_utf8( &display ) )?;
+              prefix = format!( "//{server}/{share}" );
+            }
+            _ => return Err( invalid( &display, "Windows device paths are not portable" ) ),
+          },
+          Component ::RootDir => rooted = true,
+          Component ::CurDir => {}
+          Component ::ParentDir => components.push( ".." ),
+          Component ::Normal( name ) =>
+          {
+            let name = name.to_str().ok_or_else( || not_utf8( &display ) )?;
+            if cfg!( not( windows ) ) && ( name.contains( '\\' ) || components.is_empty() && !rooted && is_drive( name ) )
+            {
+              return Err( invalid( &display, "name would be read back as a Windows path" ) );
+            }
+            components.push( name );
+          }
+        }
+      }
+      Ok( Self ::assemble( &prefix, rooted || prefix.starts_with( "//" ), components.into_iter() ) )
+    }
+  }
+
+  fn not_utf8( path: &str ) -> io ::Error
+  {
+    io ::Error ::new( io ::ErrorKind ::InvalidData, format!( "Invalid portable path `{path}`: not UTF-8" ) )
+  }
+
+  impl TryFrom< PathBuf > for PortablePath
+  {
+    type Error = io ::Error;
+
+    #[ inline ]
+    fn try_from( value: PathBuf ) -> Result< Self, Self ::Error >
+    {
+      Self ::try_from( value.as_path() )
+    }
+  }
+
+  impl TryFrom< &PathBuf > for PortablePath
+  {
+    type Error = io ::Error;
+
+    #[ inline ]
+    fn try_from( value: &PathBuf ) -> Result< Self, Self ::Error >
+    {
+      Self ::try_from( value.as_path() )
+    }
+  }
+
+  impl TryFrom< &str > for PortablePath
+  {
+    type Error = io ::Error;
+
+    #[ inline ]
+    fn try_from( value: &str ) -> Result< Self, Self ::Error >
+    {
+      Self ::parse( value )
+    }
+  }
+
+  impl TryFrom< String > for PortablePath
+  {
+    type Error = io ::Error;
+
+    #[ inline ]
+    fn try_from( value: String ) -> Result< Self, Self ::Error >
+    {
+      Self ::parse( &value )
+    }
+  }
+
+  impl FromStr for PortablePath
+  {
+    type Err = io ::Error;
+
+    #[ inline ]
+    fn from_str( value: &str ) -> Result< Self, Self ::Err >
+    {
+      Self ::parse( value )
+    }
+  }
+
+  impl From< PortablePath > for String
+  {
+    #[ inline ]
+    fn from( src: PortablePath ) -> Self
+    {
+      src.0
+    }
+  }
+
+  impl From< &PortablePath > for PathBuf
+  {
+    #[ inline ]
+    fn from( src: &PortablePath ) -> Self
+    {
+      src.to_native()
+    }
+  }
+
+  impl From< PortablePath > for PathBuf
+  {
+    #[ inline ]
+    fn from( src: PortablePath ) -> Self
+    {
+      src.to_native()
+    }
+  }
+
+  impl AsRef< str > for PortablePath
+  {
+    #[ inline ]
+    fn as_ref( &self ) -> &str
+    {
+      &self.0
+    }
+  }
+
+  impl fmt ::Display for PortablePath
+  {
+    #[ inline ]
+    fn fmt( &self, f: &mut fmt ::Formatter< '_ > ) -> fmt ::Result
+    {
+      f.write_str( &self.0 )
+    }
+  }
+
+  impl TryIntoPath for PortablePath
+  {
+    #[ inline ]
+    fn try_into_path( self ) -> Result< PathBuf, io ::Error >
+    {
+      Ok( self.to_native() )
+    }
+  }
+
+  impl< 'a > TryIntoCowPath< 'a > for PortablePath
+  {
+    #[ inline ]
+    fn try_into_cow_path( self ) -> Result< Cow< 'a, Path >, io ::Error >
+    {
+      Ok( Cow ::Owned( self.to_native() ) )
+    }
+  }
+}
+
+crate ::mod_interface!
+{
+  exposed use PortablePath;
+}
@@ -0,0 +1,393 @@
+/// Define a private namespace for all its items.
+mod private
+{
+  use crate :: *;
+  use std ::
+  {
+    collections ::HashSet,
+    fs,
+    io,
+    path :: { Path, PathBuf },
+  };
+  use crate ::path ::glob :: { Glob, GlobSet, match_form };
+
+  /// Recursive directory walk yielding typed paths.
+  ///
+  /// Entries are produced depth-first, each directory before its contents and
+  /// siblings sorted by name, so the order is stable across runs. The root itself
+  /// is not yielded; its children have depth 1.
+  ///
+  /// Filters are matched against the path relative to the root:
+  ///
+  /// - `include`: if not empty, only matching entries are yielded; directories are
+  ///   still descended, since their contents may match
+  /// - `exclude`: matching entries are skipped, and matching directories are not descended
+  /// - `gitignore`: `.gitignore` files found during the walk are applied to their
+  ///   directory and below, and `.git` directories are skipped
+  ///
+  /// Unlike the rest of the crate, walking reads the filesystem.
+  ///
+  /// # Examples
+  ///
+  /// ```no_run
+  /// use pth :: { AbsolutePath, path :: { GlobSet, Walk } };
+  ///
+  /// let root = AbsolutePath ::try_from( "/project" ).unwrap();
+  /// let sources = Walk ::new( root )
+  /// .include( GlobSet ::new( [ "**/*.rs" ] ).unwrap() )
+  /// .exclude( GlobSet ::new( [ "target" ] ).unwrap() )
+  /// .gitignore( true )
+  /// .max_depth( 8 );
+  ///
+  /// for entry in sources
+  /// {
+  ///   let entry = entry.unwrap();
+  ///   println!( "{} at depth {}", entry.relative().display(), entry.depth() );
+  /// }
+  /// ```
+  #[ derive( Debug, Clone ) ]
+  pub struct Walk
+  {
+    root: AbsolutePath,
+    include: GlobSet,
+    exclude: GlobSet,
+    max_depth: Option< usize >,
+    gitignore: bool,
+    follow_links: bool,
+  }
+
+  impl Walk
+  {
+    /// Walk everything below `root`, without filters or depth limit.
+    #[ must_use ]
+    pub fn new( root: AbsolutePath ) -> Self
+    {
+      Self
+      {
+        root,
+        include: GlobSet ::default(),
+        exclude: GlobSet ::default(),
+        max_depth: None,
+        gitignore: false,
+        follow_links: false,
+      }
+    }
+
+    /// Yield only entries matching `include`.
+    #[ must_use ]
+    pub fn include( mut self, include: GlobSet ) -> Self
+    {
+      self.include = include;
+      self
+    }
+
+    /// Skip entries matching `exclude`, and do not descend into matching directories.
+    #[ must_use ]
+    pub fn exclude( mut self, exclude: GlobSet ) -> Self
+    {
+      self.exclude = exclude;
+      self
+    }
+
+    /// Yield entries down to `depth`; `1` lists the root's children only.
+    #[ must_use ]
+    pub fn max_depth( mut self, depth: usize ) -> Self
+    {
+      self.max_depth = Some( depth );
+      self
+    }
+
+    /// Honor `.gitignore` files found in the walked directories and skip `.git`.
+    #[ must_use ]
+    pub fn gitignore( mut self, enabled: bool ) -> Self
+    {
+      self.gitignore = enabled;
+      self
+    }
+
+    /// Descend into symbolic links to directories; each real directory is visited once.
+    #[ must_use ]
+    pub fn follow_links( mut self, follow: bool ) -> Self
+    {
+      self.follow_links = follow;
+      self
+    }
+  }
+
+  /// One path found by [`Walk`].
+  #[ derive( Debug, Clone, PartialEq, Eq ) ]
+  pub struct WalkEntry
+  {
+    path: AbsolutePath,
+    relative: NormalizedPath,
+    depth: usize,
+    is_dir: bool,
+  }
+
+  impl WalkEntry
+  {
+    /// Absolute path of the entry.
+    #[ must_use ]
+    pub fn path( &self ) -> &AbsolutePath
+    {
+      &self.path
+    }
+
+    /// Path relative to the walk root, as matched by the filters.
+    #[ must_use ]
+    pub fn relative( &self ) -> &NormalizedPath
+    {
+      &self.relative
+    }
+
+    /// Number of components below the root; children of the root have depth 1.
+    #[ must_use ]
+    pub fn depth( &self ) -> usize
+    {
+      self.depth
+    }
+
+    /// Whether the entry is a directory, or a followed link to one.
+    #[ must_use ]
+    pub fn is_dir( &self ) -> bool
+    {
+      self.is_dir
+    }
+
+    /// Take the absolute path.
+    #[ must_use ]
+    pub fn into_path( self ) -> AbsolutePath
+    {
+      self.path
+    }
+  }
+
+  /// A `.gitignore` line, anchored to the directory of its file.
+  #[ derive( Debug ) ]
+  struct Rule
+  {
+    glob: Glob,
+    negated: bool,
+    dir_only: bool,
+  }
+
+  /// A directory entry waiting to be visited.
+  #[ derive( Debug ) ]
+  struct Pending
+  {
+    path: PathBuf,
+    relative: String,
+    depth: usize,
+    is_dir: bool,
+  }
+
+  /// Iterator over the entries of a [`Walk`].
+  #[ derive( Debug ) ]
+  pub struct WalkIter
+  {
+    walk: Walk,
+    stack: Vec< Pending >,
+    rules: Vec< Rule >,
+    visited: HashSet< PathBuf >,
+    errors: Vec< io ::Error >,
+  }
+
+  impl IntoIterator for Walk
+  {
+    type Item = io ::Result< WalkEntry >;
+    type IntoIter = WalkIter;
+
+    fn into_iter( self ) -> WalkIter
+    {
+      let root = PathBuf ::from( self.root.clone() );
+      let mut iter = WalkIter { walk: self, stack: Vec ::new(), rules: Vec ::new(), visited: HashSet ::new(), errors: Vec ::new() };
+      if iter.walk.max_depth != Some( 0 )
+      {
+        iter.expand( &root, "", 0 );
+      }
+      iter
+    }
+  }
+
+  impl Iterator for WalkIter
+  {
+    type Item = io ::Result< WalkEntry >;
+
+    fn next( &mut self ) -> Option< Self ::Item >
+    {
+      if let Some( error ) = self.errors.pop()
+      {
+        return Some( Err( error ) );
+      }
+      while let Some( pending ) = self.stack.pop()
+      {
+        if self.walk.exclude.is_match( &pending.relative ) || self.ignored( &pending )
+        {
+          continue;
+        }
+        if pending.is_dir && self.walk.max_depth.is_none_or( | max | pending.depth < max )
+        {
+          self.expand( &pending.path, &pending.relative, pending.depth );
+        }
+        if self.walk.include.is_empty() || self.walk.include.is_match( &pending.relative )
+        {
+          return Some( entry( pending ) );
+        }
+        if let Some( error ) = self.errors.pop()
+        {
+          return Some( Err( error ) );
+        }
+      }
+      None
+    }
+  }
+
+  impl WalkIter
+  {
+    /// Queue the children of a directory and load its `.gitignore`.
+    fn expand( &mut self, dir: &Path, relative: &str, depth: usize )
+    {
+      if self.walk.follow_links
+      {
+        match fs ::canonicalize( dir )
+        {
+          Ok( real ) =>
+          {
+            if !self.visited.insert( real )
+            {
+              return;
+            }
+          }
+          Err( error ) => return self.errors.push( error ),
+        }
+      }
+      if self.walk.gitignore
+      {
+        self.load_gitignore( dir, relative );
+      }
+
+      let read = match fs ::read_dir( dir )
+      {
+        Ok( read ) => read,
+        Err( error ) => return self.errors.push( error ),
+      };
+      let mut children = Vec ::new();
+      for item in read
+      {
+        let item = match item
+        {
+          Ok( item ) => item,
+          Err( error ) =>
+          {
+            self.errors.push( error );
+            continue;
+          }
+        };
+        let name = item.file_name().to_string_lossy().into_owned();
+        if self.walk.gitignore && name == ".git"
+        {
+          continue;
+        }
+        let is_dir = match item.file_type()
+        {
+          Ok( kind ) if kind.is_symlink() && self.walk.follow_links => fs ::metadata( item.path() ).is_ok_and( | meta | meta.is_dir() ),
+          Ok( kind ) => kind.is_dir(),
+          Err( error ) =>
+          {
+            self.errors.push( error );
+            continue;
+          }
+        };
+        let child = if relative.is_empty() { name } else { format!( "{relative}/{name}" ) };
+        children.push( Pending { path: item.path(), relative: child, depth: depth + 1, is_dir } );
+      }
+      children.sort_by( | a, b | b.relative.cmp( &a.relative ) );
+      self.stack.extend( children );
+    }
+
+    /// Parse `dir/.gitignore`, if present, into rules anchored at `relative`.
+    fn load_gitignore( &mut self, dir: &Path, relative: &str )
+    {
+      let text = match fs ::read_to_string( dir.join( ".gitignore" ) )
+      {
+        Ok( text ) => text,
+        Err( error ) if error.kind() == io ::ErrorKind ::NotFound => return,
+        Err( error ) => return self.errors.push( error ),
+      };
+      for line in text.lines().map( str ::trim_end )
+      {
+        if line.is_empty() || line.starts_with( '#' )
+        {
+          continue;
+        }
+        let ( negated, line ) = match line.strip_prefix( '!' )
+        {
+          Some( rest ) => ( true, rest ),
+          None => ( false, line ),
+        };
+        let ( dir_only, line ) = match line.strip_suffix( '/' )
+        {
+          Some( rest ) => ( true, rest ),
+          None => ( false, line ),
+        };
+        // A pattern without an inner slash matches at any depth below its directory.
+        let pattern = match line.strip_prefix( '/' )
+        {
+          Some( anchored ) => anchored.to_string(),
+          None if line.contains( '/' ) => line.to_string(),
+          None => format!( "**/{line}" ),
+        };
+        let pattern = if relative.is_empty() { pattern } else { format!( "{}/{pattern}", escape( relative ) ) };
+        match Glob ::new( &pattern )
+        {
+          Ok( glob ) => self.rules.push( Rule { glob, negated, dir_only } ),
+          Err( error ) => self.errors.push( error ),
+        }
+      }
+    }
+
+    /// Whether the last `.gitignore` rule matching the entry ignores it.
+    fn ignored( &self, pending: &Pending ) -> bool
+    {
+      let path = match_form( Path ::new( &pending.relative ) );
+      self.rules
+      .iter()
+      .rev()
+      .find( | rule | ( pending.is_dir || !rule.dir_only ) && rule.glob.matches_body( &path ) )
+      .is_some_and( | rule | !rule.negated )
+    }
+  }
+
+  fn entry( pending: Pending ) -> io ::Result< WalkEntry >
+  {
+    Ok( WalkEntry
+    {
+      path: AbsolutePath ::try_from( pending.path )?,
+      relative: NormalizedPath ::try_from( pending.relative )?,
+      depth: pending.depth,
+      is_dir: pending.is_dir,
+    })
+  }
+
+  /// Escape glob syntax in a directory name used as a pattern prefix.
+  fn escape( text: &str ) -> String
+  {
+    let mut escaped = String ::with_capacity( text.len() );
+    for c in text.chars()
+    {
+      if matches!( c, '*' | '?' | '[' | ']' | '{' | '}' | '\\' | '!' | ',' )
+      {
+        escaped.push( '\\' );
+      }
+      escaped.push( c );
+    }
+    escaped
+  }
+}
+
+crate ::mod_interface!
+{
+  orphan use Walk;
+  orphan use WalkEntry;
+  orphan use WalkIter;
+}
@@ -17,8 +17,15 @@ mod path_exts;
 mod path_is_glob;
 mod path_normalize;
 mod path_relative;
+mod portable_path;
 mod rebase_path;
 mod without_ext;
 
 #[ cfg( feature = "path_unique_folder_name" ) ]
 mod path_unique_folder_name;
+#[ cfg( feature = "path_glob" ) ]
+mod path_glob;
+#[ cfg( feature = "path_walk" ) ]
+mod path_walk;
+#[ cfg( feature = "path_jail" ) ]
+mod path_jail;
@@ -0,0 +1,134 @@
+#[ allow( unused_imports ) ]
+use super :: *;
+use the_module ::path :: { Glob, GlobSet };
+
+#[ test ]
+fn star_stays_within_one_component()
+{
+  let glob = Glob ::new( "src/*.rs" ).unwrap();
+  assert!( glob.is_match( "src/lib.rs" ) );
+  assert!( !glob.is_match( "src/path/glob.rs" ) );
+  assert!( !glob.is_match( "lib.rs" ) );
+}
+
+#[ test ]
+fn double_star_spans_components()
+{
+  let glob = Glob ::new( "**/*.rs" ).unwrap();
+  assert!( glob.is_match( "lib.rs" ) );
+  assert!( glob.is_match( "src/path/glob.rs" ) );
+
+  let inner = Glob ::new( "a/**/z" ).unwrap();
+  assert!( inner.is_match( "a/z" ) );
+  assert!( inner.is_match( "a/b/c/z" ) );
+  assert!( !inner.is_match( "a/bz" ) );
+
+  let below = Glob ::new( "target/**" ).unwrap();
+  assert!( below.is_match( "target/debug/app" ) );
+  assert!( !below.is_match( "targets/x" ) );
+
+  let partial = Glob ::new( "a**b" ).unwrap();
+  assert!( partial.is_match( "axyb" ) );
+  assert!( !partial.is_match( "ax/yb" ) );
+}
+
+#[ test ]
+fn question_mark_and_classes()
+{
+  assert!( Glob ::new( "file?.txt" ).unwrap().is_match( "file1.txt" ) );
+  assert!( !Glob ::new( "file?.txt" ).unwrap().is_match( "file10.txt" ) );
+
+  let range = Glob ::new( "v[0-9].md" ).unwrap();
+  assert!( range.is_match( "v3.md" ) );
+  assert!( !range.is_match( "vx.md" ) );
+
+  for pattern in [ "[!a-c]x", "[^a-c]x" ]
+  {
+    let negated = Glob ::new( pattern ).unwrap();
+    assert!( negated.is_match( "dx" ), "{pattern}" );
+    assert!( !negated.is_match( "bx" ), "{pattern}" );
+    assert!( !negated.is_match( "/x" ), "{pattern}" );
+  }
+
+  let literal_bracket = Glob ::new( "[]-]*" ).unwrap();
+  assert!( literal_bracket.is_match( "]a" ) && literal_bracket.is_match( "-a" ) );
+}
+
+#[ test ]
+fn brace_alternation_nests()
+{
+  let glob = Glob ::new( "{src,tests}/**/*.{rs,toml}" ).unwrap();
+  assert!( glob.is_match( "src/lib.rs" ) );
+  assert!( glob.is_match( "tests/inc/mod.rs" ) );
+  assert!( !glob.is_match( "docs/readme.md" ) );
+
+  let nested = Glob ::new( "a.{b,c{d,e}}" ).unwrap();
+  assert!( nested.is_match( "a.b" ) && nested.is_match( "a.cd" ) && nested.is_match( "a.ce" ) );
+  assert!( !nested.is_match( "a.c" ) );
+}
+
+#[ test ]
+fn escapes_and_literals()
+{
+  assert!( Glob ::new( "\\*.txt" ).unwrap().is_match( "*.txt" ) );
+  assert!( !Glob ::new( "\\*.txt" ).unwrap().is_match( "a.txt" ) );
+  assert!( Glob ::new( "a/b.txt" ).unwrap().is_match( "./a/c/../b.txt" ) );
+  assert!( Glob ::new( "\\!important" ).unwrap().is_match( "!important" ) );
+}
+
+#[ test ]
+fn matches_normalized_paths()
+{
+  let glob = Glob ::new( "src/*.rs" ).unwrap();
+  let path = the_module ::NormalizedPath ::try_from( "./src/path/../lib.rs" ).unwrap();
+  assert!( glob.is_match( &path ) );
+}
+
+#[ test ]
+fn negation()
+{
+  let glob = Glob ::new( "!*.rs" ).unwrap();
+  assert!( glob.is_negated() );
+  assert!( !glob.is_match( "lib.rs" ) );
+  assert!( glob.is_match( "readme.md" ) );
+  assert_eq!( glob.as_str(), "!*.rs" );
+  assert_eq!( glob.to_string(), "!*.rs" );
+}
+
+#[ test ]
+fn set_last_match_wins()
+{
+  let set = GlobSet ::new( [ "**/*.rs", "!**/main.rs", "src/bin/main.rs" ] ).unwrap();
+  assert!( set.is_match( "src/lib.rs" ) );
+  assert!( !set.is_match( "examples/main.rs" ) );
+  assert!( set.is_match( "src/bin/main.rs" ) );
+  assert!( !set.is_match( "readme.md" ) );
+  assert_eq!( set.globs().len(), 3 );
+  assert!( GlobSet ::default().is_empty() && !GlobSet ::default().is_match( "a" ) );
+}
+
+#[ test ]
+fn malformed_patterns_are_rejected()
+{
+  for pattern in [ "*[a-z", "*{a,b", "*}", "*\\" ]
+  {
+    let err = Glob ::new( pattern ).unwrap_err();
+    assert_eq!( err.kind(), std ::io ::ErrorKind ::InvalidInput, "{pattern}" );
+    assert!( err.to_string().contains( pattern ), "{err}" );
+  }
+  assert!( GlobSet ::new( [ "*.rs", "[" ] ).is_err() );
+  assert!( GlobSet ::new( [ "*.rs", "*[" ] ).is_err() );
+}
+
+#[ test ]
+fn unclosed_brackets_are_rejected_with_or_without_wildcards()
+{
+  for pattern in [ "x.[ch", "*.[ch", "a{b", "*{b", "a}b" ]
+  {
+    let err = Glob ::new( pattern ).unwrap_err();
+    assert_eq!( err.kind(), std ::io ::ErrorKind ::InvalidInput, "{pattern}" );
+  }
+  let literal = Glob ::new( "x.\\[ch" ).unwrap();
+  assert!( literal.is_match( "x.[ch" ) );
+  assert!( !literal.is_match( "x.c" ) );
+}
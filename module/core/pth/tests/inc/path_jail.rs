@@ -0,0 +1,170 @@
+#[ allow( unused_imports ) ]
+use super :: *;
+use std ::path :: { Path, PathBuf };
+use the_module ::
+{
+  AbsolutePath,
+  path :: { Jail, JailError },
+};
+
+/// Temporary directory tree removed on drop.
+struct Tree( PathBuf );
+
+impl Tree
+{
+  fn new( name: &str, files: &[ &str ] ) -> Self
+  {
+    let root = std ::env ::temp_dir().join( format!( "pth_jail_{}_{name}", std ::process ::id() ) );
+    let _ = std ::fs ::remove_dir_all( &root );
+    std ::fs ::create_dir_all( root.join( "jail" ) ).unwrap();
+    for path in files
+    {
+      let path = root.join( path );
+      std ::fs ::create_dir_all( path.parent().unwrap() ).unwrap();
+      std ::fs ::write( path, "" ).unwrap();
+    }
+    Self( root )
+  }
+
+  fn jail( &self ) -> Jail
+  {
+    Jail ::new( self.0.join( "jail" ) ).unwrap()
+  }
+
+  #[ cfg( unix ) ]
+  fn link( &self, target: &str, link: &str )
+  {
+    std ::os ::unix ::fs ::symlink( target, self.0.join( link ) ).unwrap();
+  }
+}
+
+impl Drop for Tree
+{
+  fn drop( &mut self )
+  {
+    let _ = std ::fs ::remove_dir_all( &self.0 );
+  }
+}
+
+fn relative( jail: &Jail, path: &str ) -> PathBuf
+{
+  jail.resolve( path ).unwrap().relative().as_ref().to_path_buf()
+}
+
+#[ test ]
+fn resolves_relative_paths_inside_root()
+{
+  let tree = Tree ::new( "inside", &[ "jail/a/b.txt" ] );
+  let jail = tree.jail();
+
+  assert_eq!( relative( &jail, "a/b.txt" ), Path ::new( "a/b.txt" ) );
+  assert_eq!( relative( &jail, "./a/../a/./b.txt" ), Path ::new( "a/b.txt" ) );
+  assert_eq!( relative( &jail, "new/dir/file" ), Path ::new( "new/dir/file" ) );
+  assert_eq!( relative( &jail, "" ), Path ::new( "." ) );
+
+  let resolved = jail.resolve( "a/b.txt" ).unwrap();
+  assert!( resolved.path().as_ref().starts_with( jail.root() ) );
+  assert!( std ::fs ::metadata( &resolved ).unwrap().is_file() );
+}
+
+#[ test ]
+fn parent_components_cannot_leave_root()
+{
+  let tree = Tree ::new( "parent", &[ "secret.txt" ] );
+  let jail = tree.jail();
+
+  for path in [ "..", "../secret.txt", "a/../../secret.txt", "missing/../../jail" ]
+  {
+    assert!( matches!( jail.resolve( path ), Err( JailError ::Escape { link: None, .. } ) ), "{path}" );
+  }
+  assert!( jail.contains( "a/.." ) );
+}
+
+#[ test ]
+fn absolute_paths_must_lie_under_root()
+{
+  let tree = Tree ::new( "absolute", &[ "secret.txt" ] );
+  let jail = tree.jail();
+
+  let inside = tree.0.join( "jail/x/y" );
+  assert_eq!( relative( &jail, inside.to_str().unwrap() ), Path ::new( "x/y" ) );
+  assert!( jail.resolve( jail.root().as_ref().join( "z" ) ).is_ok() );
+  assert!( !jail.contains( tree.0.join( "secret.txt" ) ) );
+  assert!( !jail.contains( tree.0.join( "jail/../secret.txt" ) ) );
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn links_inside_root_are_followed()
+{
+  let tree = Tree ::new( "links_in", &[ "jail/real/file.txt" ] );
+  tree.link( "real", "jail/alias" );
+  tree.link( "../real/file.txt", "jail/real/self" );
+  let absolute = tree.0.join( "jail/real" );
+  tree.link( absolute.to_str().unwrap(), "jail/absolute" );
+  let jail = tree.jail();
+
+  assert_eq!( relative( &jail, "alias/file.txt" ), Path ::new( "real/file.txt" ) );
+  assert_eq!( relative( &jail, "real/self" ), Path ::new( "real/file.txt" ) );
+  assert_eq!( relative( &jail, "absolute/file.txt" ), Path ::new( "real/file.txt" ) );
+  assert_eq!( relative( &jail, "alias/.." ), Path ::new( "." ) );
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn links_out_of_root_are_escapes()
+{
+  let tree = Tree ::new( "links_out", &[ "outside/secret.txt" ] );
+  tree.link( "../outside", "jail/relative" );
+  tree.link( "/etc", "jail/absolute" );
+  tree.link( "relative/secret.txt", "jail/chained" );
+  let jail = tree.jail();
+
+  for path in [ "relative/secret.txt", "absolute/passwd", "chained", "missing/../relative" ]
+  {
+    match jail.resolve( path )
+    {
+      Err( JailError ::Escape { link: Some( link ), .. } ) => assert!( link.starts_with( jail.root() ), "{path}" ),
+      other => panic!( "{path}: {other:?}" ),
+    }
+  }
+  assert!( jail.resolve_lexical( "relative/secret.txt" ).is_ok(), "lexical resolution does not see links" );
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn link_cycles_are_reported()
+{
+  let tree = Tree ::new( "cycle", &[] );
+  tree.link( "b", "jail/a" );
+  tree.link( "a", "jail/b" );
+  let jail = tree.jail();
+
+  let error = jail.resolve( "a/file" ).unwrap_err();
+  assert!( matches!( error, JailError ::SymlinkLoop { .. } ), "{error}" );
+  assert_eq!( std ::io ::Error ::from( error ).kind(), std ::io ::ErrorKind ::InvalidInput );
+}
+
+#[ test ]
+fn lexical_jail_needs_no_filesystem()
+{
+  let jail = Jail ::lexical( AbsolutePath ::try_from( "/nonexistent/root" ).unwrap() );
+
+  let resolved = jail.resolve_lexical( "a/./b/../c" ).unwrap();
+  assert_eq!( resolved.relative().as_ref(), Path ::new( "a/c" ) );
+  assert_eq!( resolved.path().as_ref(), Path ::new( "/nonexistent/root/a/c" ) );
+  assert!( jail.resolve_lexical( "/nonexistent/root/x" ).is_ok() );
+
+  let error = jail.resolve_lexical( "a/../../x" ).unwrap_err();
+  assert_eq!( error.to_string(), "Path `a/../../x` escapes the jail root" );
+  assert_eq!( std ::io ::Error ::from( error ).kind(), std ::io ::ErrorKind ::PermissionDenied );
+}
+
+#[ test ]
+fn root_must_be_an_existing_directory()
+{
+  let tree = Tree ::new( "root", &[ "file" ] );
+
+  assert!( matches!( Jail ::new( tree.0.join( "missing" ) ), Err( JailError ::Io { .. } ) ) );
+  assert!( matches!( Jail ::new( tree.0.join( "file" ) ), Err( JailError ::Io { .. } ) ) );
+}
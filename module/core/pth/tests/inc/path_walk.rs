@@ -0,0 +1,165 @@
+#[ allow( unused_imports ) ]
+use super :: *;
+use std ::path ::Path;
+use the_module ::
+{
+  AbsolutePath,
+  path :: { GlobSet, Walk },
+};
+
+/// Temporary directory tree removed on drop.
+struct Tree( std ::path ::PathBuf );
+
+impl Tree
+{
+  fn new( name: &str, files: &[ ( &str, &str ) ] ) -> Self
+  {
+    let root = std ::env ::temp_dir().join( format!( "pth_walk_{}_{name}", std ::process ::id() ) );
+    let _ = std ::fs ::remove_dir_all( &root );
+    for ( path, content ) in files
+    {
+      let path = root.join( path );
+      std ::fs ::create_dir_all( path.parent().unwrap() ).unwrap();
+      std ::fs ::write( path, content ).unwrap();
+    }
+    Self( root )
+  }
+
+  fn root( &self ) -> AbsolutePath
+  {
+    AbsolutePath ::try_from( self.0.as_path() ).unwrap()
+  }
+}
+
+impl Drop for Tree
+{
+  fn drop( &mut self )
+  {
+    let _ = std ::fs ::remove_dir_all( &self.0 );
+  }
+}
+
+fn relatives( walk: Walk ) -> Vec< String >
+{
+  walk.into_iter().map( | entry | entry.unwrap().relative().to_string_lossy().into_owned() ).collect()
+}
+
+const PROJECT: &[ ( &str, &str ) ] =
+&[
+  ( "Cargo.toml", "" ),
+  ( "src/lib.rs", "" ),
+  ( "src/path/glob.rs", "" ),
+  ( "target/debug/app", "" ),
+  ( "readme.md", "" ),
+];
+
+#[ test ]
+fn walks_depth_first_in_name_order()
+{
+  let tree = Tree ::new( "order", PROJECT );
+
+  assert_eq!
+  (
+    relatives( Walk ::new( tree.root() ) ),
+    vec![ "Cargo.toml", "readme.md", "src", "src/lib.rs", "src/path", "src/path/glob.rs", "target", "target/debug", "target/debug/app" ],
+  );
+}
+
+#[ test ]
+fn entries_are_typed_paths()
+{
+  let tree = Tree ::new( "typed", PROJECT );
+
+  let entry = Walk ::new( tree.root() ).into_iter().map( Result ::unwrap ).find( | entry | entry.relative().ends_with( "glob.rs" ) ).unwrap();
+  assert_eq!( entry.path().as_ref(), tree.0.join( "src/path/glob.rs" ).as_path() );
+  assert_eq!( entry.relative().as_ref(), Path ::new( "src/path/glob.rs" ) );
+  assert_eq!( entry.depth(), 3 );
+  assert!( !entry.is_dir() );
+  assert!( entry.clone().into_path().is_absolute() );
+}
+
+#[ test ]
+fn include_filters_entries_but_descends_directories()
+{
+  let tree = Tree ::new( "include", PROJECT );
+
+  let walk = Walk ::new( tree.root() ).include( GlobSet ::new( [ "**/*.rs" ] ).unwrap() );
+  assert_eq!( relatives( walk ), vec![ "src/lib.rs", "src/path/glob.rs" ] );
+}
+
+#[ test ]
+fn exclude_prunes_directories()
+{
+  let tree = Tree ::new( "exclude", PROJECT );
+
+  let walk = Walk ::new( tree.root() ).exclude( GlobSet ::new( [ "target", "src/path" ] ).unwrap() );
+  assert_eq!( relatives( walk ), vec![ "Cargo.toml", "readme.md", "src", "src/lib.rs" ] );
+}
+
+#[ test ]
+fn depth_limit()
+{
+  let tree = Tree ::new( "depth", PROJECT );
+
+  assert_eq!( relatives( Walk ::new( tree.root() ).max_depth( 1 ) ), vec![ "Cargo.toml", "readme.md", "src", "target" ] );
+  assert_eq!( relatives( Walk ::new( tree.root() ).max_depth( 2 ) ).len(), 7 );
+  assert!( relatives( Walk ::new( tree.root() ).max_depth( 0 ) ).is_empty() );
+}
+
+#[ test ]
+fn gitignore_is_honored_per_directory()
+{
+  let tree = Tree ::new
+  (
+    "gitignore",
+    &[
+      ( ".gitignore", "# build output\ntarget/\n*.log\n!keep.log\n/top.txt\n" ),
+      ( ".git/HEAD", "" ),
+      ( "a.log", "" ),
+      ( "keep.log", "" ),
+      ( "top.txt", "" ),
+      ( "src/top.txt", "" ),
+      ( "src/.gitignore", "generated.rs\n" ),
+      ( "src/generated.rs", "" ),
+      ( "src/lib.rs", "" ),
+      ( "src/debug.log", "" ),
+      ( "other/generated.rs", "" ),
+      ( "target/debug/app", "" ),
+      ( "docs/target", "" ),
+    ],
+  );
+
+  let files: Vec< _ > = Walk ::new( tree.root() ).gitignore( true ).into_iter()
+  .map( Result ::unwrap )
+  .filter( | entry | !entry.is_dir() )
+  .map( | entry | entry.relative().to_string_lossy().into_owned() )
+  .collect();
+  assert_eq!( files, vec![ ".gitignore", "docs/target", "keep.log", "other/generated.rs", "src/.gitignore", "src/lib.rs", "src/top.txt" ] );
+
+  let all = relatives( Walk ::new( tree.root() ) );
+  assert!( all.contains( &".git/HEAD".to_string() ) && all.contains( &"a.log".to_string() ) );
+}
+
+#[ test ]
+fn missing_root_yields_an_error()
+{
+  let root = AbsolutePath ::try_from( std ::env ::temp_dir().join( "pth_walk_missing_root_does_not_exist" ) ).unwrap();
+
+  let mut iter = Walk ::new( root ).into_iter();
+  assert_eq!( iter.next().unwrap().unwrap_err().kind(), std ::io ::ErrorKind ::NotFound );
+  assert!( iter.next().is_none() );
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn follow_links_visits_each_directory_once()
+{
+  let tree = Tree ::new( "links", &[ ( "real/file", "" ) ] );
+  std ::os ::unix ::fs ::symlink( tree.0.join( "real" ), tree.0.join( "alias" ) ).unwrap();
+  std ::os ::unix ::fs ::symlink( &tree.0, tree.0.join( "real/loop" ) ).unwrap();
+
+  assert_eq!( relatives( Walk ::new( tree.root() ) ), vec![ "alias", "real", "real/file", "real/loop" ] );
+
+  let followed = relatives( Walk ::new( tree.root() ).follow_links( true ) );
+  assert_eq!( followed, vec![ "alias", "alias/file", "alias/loop", "real" ] );
+}
@@ -0,0 +1,150 @@
+#[ allow( unused_imports ) ]
+use super :: *;
+use std ::path ::{ Path, PathBuf };
+use the_module ::PortablePath;
+
+fn portable( text: &str ) -> PortablePath
+{
+  PortablePath ::parse( text ).unwrap()
+}
+
+#[ test ]
+fn parse_accepts_both_separators()
+{
+  assert_eq!( portable( r"src\path\.\lib.rs" ).as_str(), "src/path/lib.rs" );
+  assert_eq!( portable( "/usr//share/" ).as_str(), "/usr/share" );
+  assert_eq!( portable( "a/../b" ).as_str(), "a/../b" );
+  assert_eq!( portable( "" ).as_str(), "." );
+  assert_eq!( portable( "./" ).as_str(), "." );
+}
+
+#[ test ]
+fn parse_windows_prefixes()
+{
+  let drive = portable( r"c:\Users\me" );
+  assert_eq!( drive.as_str(), "c:/Users/me" );
+  assert_eq!( drive.drive(), Some( 'c' ) );
+  assert!( drive.is_absolute() && !drive.is_unc() );
+
+  let unc = portable( r"\\server\share\docs\a.txt" );
+  assert_eq!( unc.as_str(), "//server/share/docs/a.txt" );
+  assert!( unc.is_unc() && unc.is_absolute() );
+  assert_eq!( unc.drive(), None );
+
+  assert_eq!( portable( r"\\?\C:\x" ).as_str(), "C:/x" );
+  assert_eq!( portable( r"\\?\UNC\server\share\x" ).as_str(), "//server/share/x" );
+}
+
+#[ test ]
+fn parse_rejects_unportable_paths()
+{
+  for text in [ "C:relative", r"\\server", r"\\.\COM1", "a\0b" ]
+  {
+    let error = PortablePath ::parse( text ).unwrap_err();
+    assert_eq!( error.kind(), std ::io ::ErrorKind ::InvalidInput, "{text}" );
+  }
+}
+
+#[ test ]
+fn renders_per_platform()
+{
+  let path = portable( "C:/Program Files/app" );
+  assert_eq!( path.to_windows_string(), r"C:\Program Files\app" );
+  assert_eq!( path.to_unix_string(), "C:/Program Files/app" );
+  assert_eq!( path.to_string(), "C:/Program Files/app" );
+  assert_eq!( "a/b".parse::< PortablePath >().unwrap(), portable( r"a\b" ) );
+}
+
+#[ test ]
+fn native_round_trip()
+{
+  let native = PathBuf ::from( "/tmp/project/src/lib.rs" );
+  let path = PortablePath ::try_from( native.as_path() ).unwrap();
+  assert_eq!( path.as_str(), "/tmp/project/src/lib.rs" );
+  assert_eq!( PathBuf ::from( path.clone() ), native );
+  assert_eq!( path.to_native(), native );
+
+  let relative = PortablePath ::try_from( Path ::new( "./a/../b" ) ).unwrap();
+  assert_eq!( relative.as_str(), "a/../b" );
+}
+
+#[ cfg( unix ) ]
+#[ test ]
+fn native_names_that_would_change_meaning_are_rejected()
+{
+  use std ::os ::unix ::ffi ::OsStrExt;
+
+  assert!( PortablePath ::try_from( Path ::new( r"dir/a\b" ) ).is_err() );
+  assert!( PortablePath ::try_from( Path ::new( "c:name" ) ).is_err() );
+  let invalid = Path ::new( std ::ffi ::OsStr ::from_bytes( b"/a/\xff" ) );
+  assert_eq!( PortablePath ::try_from( invalid ).unwrap_err().kind(), std ::io ::ErrorKind ::InvalidData );
+}
+
+#[ test ]
+fn components()
+{
+  let path = portable( "/a/b/c.txt" );
+  assert_eq!( path.file_name(), Some( "c.txt" ) );
+  assert_eq!( path.parent(), Some( portable( "/a/b" ) ) );
+  assert_eq!( portable( "/a" ).parent(), Some( portable( "/" ) ) );
+  assert_eq!( portable( "C:/a" ).parent(), Some( portable( "C:/" ) ) );
+  assert_eq!( portable( "/" ).parent(), None );
+  assert_eq!( portable( "a" ).parent(), Some( portable( "." ) ) );
+
+  assert_eq!( path.join( r"d\e" ).unwrap(), portable( "/a/b/c.txt/d/e" ) );
+  assert_eq!( path.join( "D:/x" ).unwrap(), portable( "D:/x" ) );
+  assert_eq!( portable( "." ).join( "x" ).unwrap(), portable( "x" ) );
+}
+
+#[ test ]
+fn file_uri_round_trip()
+{
+  let cases =
+  [
+    ( "/home/me/my file.txt", "file:///home/me/my%20file.txt" ),
+    ( "C:/Users/me", "file:///C:/Users/me" ),
+    ( "//server/share/docs", "file://server/share/docs" ),
+    ( "/data/naïve#1", "file:///data/na%C3%AFve%231" ),
+  ];
+  for ( path, uri ) in cases
+  {
+    assert_eq!( portable( path ).to_file_uri().unwrap(), uri );
+    assert_eq!( PortablePath ::from_file_uri( uri ).unwrap(), portable( path ) );
+  }
+
+  assert_eq!( PortablePath ::from_file_uri( "FILE://localhost/etc/hosts" ).unwrap(), portable( "/etc/hosts" ) );
+  assert_eq!( PortablePath ::from_file_uri( "file:/etc/hosts" ).unwrap(), portable( "/etc/hosts" ) );
+  assert!( portable( "relative" ).to_file_uri().is_err() );
+  for uri in [ "http://host/x", "file:///a?b", "file:///a%zz", "file:///a%ff" ]
+  {
+    assert!( PortablePath ::from_file_uri( uri ).is_err(), "{uri}" );
+  }
+}
+
+#[ test ]
+fn relative_to()
+{
+  assert_eq!( portable( "/a/b/c" ).relative_to( &portable( "/a/d" ) ), portable( "../b/c" ) );
+  assert_eq!( portable( "C:/a/b" ).relative_to( &portable( "c:/a" ) ), portable( "b" ) );
+  assert_eq!( portable( "C:/a" ).relative_to( &portable( "D:/a" ) ), portable( "C:/a" ) );
+}
+
+#[ test ]
+fn rebase()
+{
+  let file = portable( "C:/work/project/src/lib.rs" );
+  assert_eq!( file.rebase( &portable( "/mnt/backup" ), Some( &portable( "C:/work" ) ) ), Some( portable( "/mnt/backup/project/src/lib.rs" ) ) );
+  assert_eq!( file.rebase( &portable( "//nas/share" ), Some( &portable( "C:/work/project" ) ) ), Some( portable( "//nas/share/src/lib.rs" ) ) );
+  assert_eq!( file.rebase( &portable( "/mnt" ), Some( &portable( "D:/work" ) ) ), None );
+}
+
+#[ test ]
+fn common()
+{
+  let paths = [ portable( "C:/a/b/c" ), portable( "C:/a/b/d" ), portable( "c:/a/e" ) ];
+  assert_eq!( PortablePath ::common( &paths ), Some( portable( "C:/a" ) ) );
+  assert_eq!( PortablePath ::common( &[ portable( "/x/y" ), portable( "/x/z" ) ] ), Some( portable( "/x" ) ) );
+  assert_eq!( PortablePath ::common( &[ portable( "C:/a" ), portable( "D:/a" ) ] ), None );
+  assert_eq!( PortablePath ::common( &[] ), None );
+}
+
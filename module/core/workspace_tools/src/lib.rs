@@ -71,6 +71,8 @@
 //! - **`secure`** : enables memory-safe secret handling with the secrecy crate
 //! - **`serde`** : provides configuration loading with serde support
 //! - **`validation`** : enables configuration validation with JSON Schema
+//! - **`watch`** : reloads typed configs when their files change
+//! - **`vault`** : keeps secrets in age-encrypted files
 //!
 //! ## security best practices
 //!
@@ -143,6 +145,9 @@ pub enum WorkspaceError
   /// secret injection error
   #[ cfg( feature = "secure" ) ]
   SecretInjectionError( String ),
+  /// encrypted secret store error
+  #[ cfg( feature = "vault" ) ]
+  VaultError( String ),
 }
 
 impl core::fmt::Display for WorkspaceError
@@ -182,6 +187,9 @@ impl core::fmt::Display for WorkspaceError
    #[ cfg( feature = "secure" ) ]
    WorkspaceError::SecretInjectionError( msg ) =>
   write!( f, "secret injection error: {msg}" ),
+   #[ cfg( feature = "vault" ) ]
+   WorkspaceError::VaultError( msg ) =>
+  write!( f, "vault error: {msg}" ),
   }
   }
 }
@@ -837,18 +845,7 @@ impl Workspace
   /// ```
   pub fn find_config( &self, name: &str ) -> Result< PathBuf >
   {
-  let candidates = vec!
-  [
-   self.config_dir().join( format!( "{name}.toml" ) ),
-   self.config_dir().join( format!( "{name}.yaml" ) ),
-   self.config_dir().join( format!( "{name}.yml" ) ),
-   self.config_dir().join( format!( "{name}.json" ) ),
-   self.root.join( format!( ".{name}.toml" ) ),
-   self.root.join( format!( ".{name}.yaml" ) ),
-   self.root.join( format!( ".{name}.yml" ) ),
- ];
-
-  for candidate in candidates
+  for candidate in self.config_candidates( name )
   {
    if candidate.exists()
    {
@@ -860,6 +857,21 @@ impl Workspace
    self.config_dir().join( format!( "{name}.toml" ) )
  ) )
   }
+
+  /// paths searched by `find_config`, in priority order
+  fn config_candidates( &self, name: &str ) -> Vec< PathBuf >
+  {
+  vec!
+  [
+   self.config_dir().join( format!( "{name}.toml" ) ),
+   self.config_dir().join( format!( "{name}.yaml" ) ),
+   self.config_dir().join( format!( "{name}.yml" ) ),
+   self.config_dir().join( format!( "{name}.json" ) ),
+   self.root.join( format!( ".{name}.toml" ) ),
+   self.root.join( format!( ".{name}.yaml" ) ),
+   self.root.join( format!( ".{name}.yml" ) ),
+  ]
+  }
 }
 
 // cargo integration types and implementations
@@ -2698,6 +2710,1034 @@ pub mod testing
   }
 }
 
+/// hot-reload of workspace configuration files
+///
+/// a `ConfigWatcher` polls the files behind a named config (the same candidates
+/// `find_config` searches) and, optionally, a secret file. when one changes and stays
+/// unchanged for the debounce period, the config is re-parsed, validated and handed
+/// out as a new `Arc< T >`. a reload that fails is reported and the last good config
+/// is kept.
+///
+/// polling is used instead of os notifications so the module needs no extra
+/// dependencies and behaves the same on every platform. file access goes through a
+/// `WatchBackend`: `FsBackend` reads the disk, `MemoryBackend` keeps files in memory
+/// for tests.
+#[ cfg( feature = "watch" ) ]
+pub mod watch
+{
+  use super :: { Workspace, WorkspaceError, Result };
+  use core ::
+  {
+    hash :: { Hash, Hasher },
+    time ::Duration,
+  };
+  use std ::
+  {
+    collections ::HashMap,
+    path :: { Path, PathBuf },
+    sync :: { Arc, Mutex, PoisonError, mpsc },
+    thread,
+    time ::Instant,
+  };
+
+  #[ cfg( feature = "validation" ) ]
+  use jsonschema ::Validator;
+
+  #[ cfg( feature = "validation" ) ]
+  use schemars ::JsonSchema;
+
+  /// source of the watched files
+  pub trait WatchBackend: Send + 'static
+  {
+    /// fingerprint of the file at `path`, or `None` if it does not exist
+    ///
+    /// any change of the fingerprint is treated as a change of the file
+    ///
+    /// # Errors
+    ///
+    /// returns error if the file exists but cannot be inspected
+    fn stamp( &self, path: &Path ) -> Result< Option< u64 > >;
+
+    /// read the file at `path` as text
+    ///
+    /// # Errors
+    ///
+    /// returns error if the file cannot be read
+    fn read( &self, path: &Path ) -> Result< String >;
+  }
+
+  /// backend polling the real filesystem
+  ///
+  /// the fingerprint is a hash of the file content, so rewrites within the
+  /// timestamp resolution of the filesystem are still seen
+  #[ derive( Debug, Default, Clone, Copy ) ]
+  pub struct FsBackend;
+
+  impl WatchBackend for FsBackend
+  {
+    fn stamp( &self, path: &Path ) -> Result< Option< u64 > >
+    {
+      match std ::fs ::read( path )
+      {
+        Ok( content ) =>
+        {
+          let mut hasher = std ::collections ::hash_map ::DefaultHasher ::new();
+          content.hash( &mut hasher );
+          Ok( Some( hasher.finish() ) )
+        }
+        Err( e ) if e.kind() == std ::io ::ErrorKind ::NotFound => Ok( None ),
+        Err( e ) => Err( WorkspaceError::IoError( format!( "failed to read {}: {}", path.display(), e ) ) ),
+      }
+    }
+
+    fn read( &self, path: &Path ) -> Result< String >
+    {
+      std ::fs ::read_to_string( path )
+        .map_err( | e | WorkspaceError::IoError( format!( "failed to read {}: {}", path.display(), e ) ) )
+    }
+  }
+
+  /// backend keeping files in memory, for tests
+  ///
+  /// clones share the same files, so a test keeps one handle to edit files
+  /// while the watcher owns another
+  #[ derive( Debug, Default, Clone ) ]
+  pub struct MemoryBackend
+  {
+    files: Arc< Mutex< MemoryFiles > >,
+  }
+
+  #[ derive( Debug, Default ) ]
+  struct MemoryFiles
+  {
+    content: HashMap< PathBuf, ( String, u64 ) >,
+    writes: u64,
+  }
+
+  impl MemoryBackend
+  {
+    /// create an empty backend
+    #[ must_use ]
+    #[ inline ]
+    pub fn new() -> Self
+    {
+      Self ::default()
+    }
+
+    /// create or replace the file at `path`
+    #[ inline ]
+    pub fn write< P: Into< PathBuf > >( &self, path: P, content: &str )
+    {
+      let mut files = self.files.lock().unwrap_or_else( PoisonError ::into_inner );
+      files.writes += 1;
+      let version = files.writes;
+      files.content.insert( path.into(), ( content.to_string(), version ) );
+    }
+
+    /// delete the file at `path`, if present
+    #[ inline ]
+    pub fn remove< P: AsRef< Path > >( &self, path: P )
+    {
+      self.files.lock().unwrap_or_else( PoisonError ::into_inner ).content.remove( path.as_ref() );
+    }
+  }
+
+  impl WatchBackend for MemoryBackend
+  {
+    fn stamp( &self, path: &Path ) -> Result< Option< u64 > >
+    {
+      let files = self.files.lock().unwrap_or_else( PoisonError ::into_inner );
+      Ok( files.content.get( path ).map( | ( _, version ) | *version ) )
+    }
+
+    fn read( &self, path: &Path ) -> Result< String >
+    {
+      let files = self.files.lock().unwrap_or_else( PoisonError ::into_inner );
+      files.content.get( path )
+        .map( | ( content, _ ) | content.clone() )
+        .ok_or_else( || WorkspaceError::PathNotFound( path.to_path_buf() ) )
+    }
+  }
+
+  /// outcome of a reload triggered by a file change
+  #[ derive( Debug ) ]
+  pub enum ConfigEvent< T >
+  {
+    /// the config was re-parsed and validated
+    Reloaded( Arc< T > ),
+    /// the reload failed; the last good config is kept
+    Failed( WorkspaceError ),
+  }
+
+  /// hook injecting secrets into a freshly loaded config
+  #[ cfg( feature = "secrets" ) ]
+  type SecretInjector< T > = Box< dyn Fn( &mut T, &HashMap< String, String > ) -> Result< () > + Send >;
+
+  /// watcher reloading a typed config when its files change
+  ///
+  /// created by `Workspace::watch_config`. drive it with `poll` from your own loop,
+  /// or move it to a background thread with `spawn`.
+  ///
+  /// # examples
+  ///
+  /// ```rust
+  /// use workspace_tools :: { Workspace, watch :: { ConfigEvent, MemoryBackend } };
+  /// use serde ::Deserialize;
+  /// use std ::time ::Duration;
+  ///
+  /// #[ derive( Debug, Deserialize ) ]
+  /// struct AppConfig
+  /// {
+  ///     port: u16,
+  /// }
+  ///
+  /// # fn main() -> Result< (), workspace_tools ::WorkspaceError > {
+  /// let ws = Workspace ::new( "/srv/app" );
+  /// let files = MemoryBackend ::new();
+  /// files.write( ws.config_dir().join( "app.toml" ), "port = 80" );
+  ///
+  /// let mut watcher = ws.watch_config ::< AppConfig >( "app" )
+  ///   .backend( files.clone() )
+  ///   .debounce( Duration ::ZERO );
+  /// assert_eq!( watcher.load()?.port, 80 );
+  ///
+  /// files.write( ws.config_dir().join( "app.toml" ), "port = 8080" );
+  /// match watcher.poll()
+  /// {
+  ///     Some( ConfigEvent ::Reloaded( config ) ) => assert_eq!( config.port, 8080 ),
+  ///     other => panic!( "unexpected {other:?}" ),
+  /// }
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub struct ConfigWatcher< T >
+  {
+    workspace: Workspace,
+    name: String,
+    backend: Box< dyn WatchBackend >,
+    interval: Duration,
+    debounce: Duration,
+    #[ cfg( feature = "validation" ) ]
+    schema: Option< Validator >,
+    #[ cfg( feature = "secrets" ) ]
+    secrets: Option< ( String, SecretInjector< T > ) >,
+    stamps: Vec< ( PathBuf, Option< u64 > ) >,
+    changed_at: Option< Instant >,
+    current: Option< Arc< T > >,
+    last_error: Option< WorkspaceError >,
+  }
+
+  impl< T > core ::fmt ::Debug for ConfigWatcher< T >
+  {
+    fn fmt( &self, f: &mut core ::fmt ::Formatter< '_ > ) -> core ::fmt ::Result
+    {
+      f.debug_struct( "ConfigWatcher" )
+        .field( "name", &self.name )
+        .field( "interval", &self.interval )
+        .field( "debounce", &self.debounce )
+        .field( "files", &self.stamps.iter().map( | ( path, _ ) | path ).collect::< Vec< _ > >() )
+        .field( "last_error", &self.last_error )
+        .finish_non_exhaustive()
+    }
+  }
+
+  impl< T > ConfigWatcher< T >
+  where
+    T: serde ::de ::DeserializeOwned + Send + Sync + 'static,
+  {
+    fn new( workspace: Workspace, name: &str ) -> Self
+    {
+      Self
+      {
+        workspace,
+        name: name.to_string(),
+        backend: Box ::new( FsBackend ),
+        interval: Duration ::from_millis( 500 ),
+        debounce: Duration ::from_millis( 200 ),
+        #[ cfg( feature = "validation" ) ]
+        schema: None,
+        #[ cfg( feature = "secrets" ) ]
+        secrets: None,
+        stamps: Vec ::new(),
+        changed_at: None,
+        current: None,
+        last_error: None,
+      }
+    }
+
+    /// read files through `backend` instead of the filesystem
+    #[ must_use ]
+    #[ inline ]
+    pub fn backend< B: WatchBackend >( mut self, backend: B ) -> Self
+    {
+      self.backend = Box ::new( backend );
+      self
+    }
+
+    /// time between polls of a spawned watcher, 500ms by default
+    #[ must_use ]
+    #[ inline ]
+    pub fn interval( mut self, interval: Duration ) -> Self
+    {
+      self.interval = interval;
+      self
+    }
+
+    /// time files must stay unchanged before reloading, 200ms by default
+    ///
+    /// editors often write a file in several steps; waiting avoids parsing half-written content
+    #[ must_use ]
+    #[ inline ]
+    pub fn debounce( mut self, debounce: Duration ) -> Self
+    {
+      self.debounce = debounce;
+      self
+    }
+
+    /// validate every reload against `schema` before deserializing
+    #[ cfg( feature = "validation" ) ]
+    #[ must_use ]
+    #[ inline ]
+    pub fn schema( mut self, schema: Validator ) -> Self
+    {
+      self.schema = Some( schema );
+      self
+    }
+
+    /// validate every reload against the json schema generated from `T`
+    ///
+    /// # Errors
+    ///
+    /// returns error if the schema cannot be generated or compiled
+    #[ cfg( feature = "validation" ) ]
+    pub fn with_validation( self ) -> Result< Self >
+    where
+      T: JsonSchema,
+    {
+      let schema = serde_json ::to_value( schemars ::schema_for!( T ) )
+        .map_err( | e | WorkspaceError::ValidationError( format!( "failed to serialize schema: {e}" ) ) )?;
+      let compiled = Validator ::new( &schema )
+        .map_err( | e | WorkspaceError::ValidationError( format!( "failed to compile schema: {e}" ) ) )?;
+      Ok( self.schema( compiled ) )
+    }
+
+    /// also watch `secret_dir()/filename` and pass its keys to `inject` on every reload
+    #[ cfg( feature = "secrets" ) ]
+    #[ must_use ]
+    #[ inline ]
+    pub fn with_secrets< F >( mut self, filename: &str, inject: F ) -> Self
+    where
+      F: Fn( &mut T, &HashMap< String, String > ) -> Result< () > + Send + 'static,
+    {
+      self.secrets = Some( ( filename.to_string(), Box ::new( inject ) ) );
+      self
+    }
+
+    /// last config loaded successfully
+    #[ must_use ]
+    #[ inline ]
+    pub fn current( &self ) -> Option< Arc< T > >
+    {
+      self.current.clone()
+    }
+
+    /// error of the last reload, cleared by the next successful one
+    #[ must_use ]
+    #[ inline ]
+    pub fn last_error( &self ) -> Option< &WorkspaceError >
+    {
+      self.last_error.as_ref()
+    }
+
+    /// load the config now and remember the state of its files
+    ///
+    /// # Errors
+    ///
+    /// returns error if the config is missing, cannot be parsed, fails validation
+    /// or secret injection; the previous config, if any, is kept
+    pub fn load( &mut self ) -> Result< Arc< T > >
+    {
+      self.stamps = self.scan()?;
+      self.changed_at = None;
+      match self.reload()
+      {
+        Ok( config ) =>
+        {
+          let config = Arc ::new( config );
+          self.current = Some( config.clone() );
+          self.last_error = None;
+          Ok( config )
+        }
+        Err( error ) =>
+        {
+          self.last_error = Some( error.clone() );
+          Err( error )
+        }
+      }
+    }
+
+    /// check the files once and reload if they changed and then settled
+    ///
+    /// returns `None` while nothing changed or the debounce period is running
+    pub fn poll( &mut self ) -> Option< ConfigEvent< T > >
+    {
+      match self.scan()
+      {
+        Ok( stamps ) if stamps != self.stamps =>
+        {
+          self.stamps = stamps;
+          self.changed_at = Some( Instant ::now() );
+        }
+        Ok( _ ) => {}
+        Err( error ) =>
+        {
+          self.last_error = Some( error.clone() );
+          return Some( ConfigEvent ::Failed( error ) );
+        }
+      }
+      let changed_at = self.changed_at?;
+      if changed_at.elapsed() < self.debounce
+      {
+        return None;
+      }
+      Some( match self.load()
+      {
+        Ok( config ) => ConfigEvent ::Reloaded( config ),
+        Err( error ) => ConfigEvent ::Failed( error ),
+      } )
+    }
+
+    /// load the config, then keep polling on a background thread
+    ///
+    /// `on_event` runs on that thread for every reload. the returned handle exposes
+    /// the last good config and stops the thread when dropped.
+    ///
+    /// # Errors
+    ///
+    /// returns error if the initial load fails
+    pub fn spawn< F >( mut self, mut on_event: F ) -> Result< WatchHandle< T > >
+    where
+      F: FnMut( &ConfigEvent< T > ) + Send + 'static,
+    {
+      let config = self.load()?;
+      let shared = Arc ::new( Mutex ::new( Shared { current: config, last_error: None } ) );
+      let ( stop, stopped ) = mpsc ::channel ::< () >();
+      let state = shared.clone();
+      let thread = thread ::Builder ::new()
+        .name( format!( "watch-config-{}", self.name ) )
+        .spawn( move ||
+        {
+          while let Err( mpsc ::RecvTimeoutError ::Timeout ) = stopped.recv_timeout( self.interval )
+          {
+            let Some( event ) = self.poll() else { continue };
+            {
+              let mut state = state.lock().unwrap_or_else( PoisonError ::into_inner );
+              match &event
+              {
+                ConfigEvent ::Reloaded( config ) =>
+                {
+                  state.current = config.clone();
+                  state.last_error = None;
+                }
+                ConfigEvent ::Failed( error ) => state.last_error = Some( error.clone() ),
+              }
+            }
+            on_event( &event );
+          }
+        } )
+        .map_err( | e | WorkspaceError::IoError( format!( "failed to spawn config watcher: {e}" ) ) )?;
+      Ok( WatchHandle { shared, stop: Some( stop ), thread: Some( thread ) } )
+    }
+
+    /// files that decide the config: every `find_config` candidate and the secret file
+    fn watched( &self ) -> Vec< PathBuf >
+    {
+      #[ allow( unused_mut ) ]
+      let mut files = self.workspace.config_candidates( &self.name );
+      #[ cfg( feature = "secrets" ) ]
+      if let Some( ( filename, _ ) ) = &self.secrets
+      {
+        files.push( self.workspace.secret_file( filename ) );
+      }
+      files
+    }
+
+    fn scan( &self ) -> Result< Vec< ( PathBuf, Option< u64 > ) > >
+    {
+      self.watched().into_iter()
+        .map( | path | Ok( ( path.clone(), self.backend.stamp( &path )? ) ) )
+        .collect()
+    }
+
+    /// parse the first existing candidate, validate it and inject secrets
+    fn reload( &self ) -> Result< T >
+    {
+      let candidates = self.workspace.config_candidates( &self.name );
+      let path = self.stamps.iter()
+        .find( | ( path, stamp ) | stamp.is_some() && candidates.contains( path ) )
+        .map( | ( path, _ ) | path.clone() )
+        .ok_or_else( || WorkspaceError::PathNotFound( self.workspace.config_dir().join( format!( "{}.toml", self.name ) ) ) )?;
+      let content = self.backend.read( &path )?;
+      let format = Workspace ::detect_format( &path );
+
+      #[ cfg( feature = "validation" ) ]
+      let config: T = match &self.schema
+      {
+        Some( schema ) =>
+        {
+          let json_value = Workspace ::parse_to_json( &content, &format )?;
+          Workspace ::validate_against_schema( &json_value, schema )?;
+          serde_json ::from_value( json_value )
+            .map_err( | e | WorkspaceError::SerdeError( format!( "deserialization error: {e}" ) ) )?
+        }
+        None => Workspace ::parse_content( &content, &format )?,
+      };
+      #[ cfg( not( feature = "validation" ) ) ]
+      let config: T = Workspace ::parse_content( &content, &format )?;
+
+      #[ cfg( feature = "secrets" ) ]
+      let config = match &self.secrets
+      {
+        Some( ( filename, inject ) ) =>
+        {
+          let mut config = config;
+          let secrets = Workspace ::parse_key_value_file( &self.backend.read( &self.workspace.secret_file( filename ) )? );
+          inject( &mut config, &secrets )?;
+          config
+        }
+        None => config,
+      };
+
+      Ok( config )
+    }
+  }
+
+  #[ derive( Debug ) ]
+  struct Shared< T >
+  {
+    current: Arc< T >,
+    last_error: Option< WorkspaceError >,
+  }
+
+  /// handle to a watcher running on a background thread
+  ///
+  /// dropping the handle stops the thread
+  #[ derive( Debug ) ]
+  pub struct WatchHandle< T >
+  {
+    shared: Arc< Mutex< Shared< T > > >,
+    stop: Option< mpsc ::Sender< () > >,
+    thread: Option< thread ::JoinHandle< () > >,
+  }
+
+  impl< T > WatchHandle< T >
+  {
+    /// last config loaded successfully
+    #[ must_use ]
+    #[ inline ]
+    pub fn current( &self ) -> Arc< T >
+    {
+      self.shared.lock().unwrap_or_else( PoisonError ::into_inner ).current.clone()
+    }
+
+    /// error of the last reload, cleared by the next successful one
+    #[ must_use ]
+    #[ inline ]
+    pub fn last_error( &self ) -> Option< WorkspaceError >
+    {
+      self.shared.lock().unwrap_or_else( PoisonError ::into_inner ).last_error.clone()
+    }
+
+    /// stop polling and wait for the thread to finish
+    #[ inline ]
+    pub fn stop( mut self )
+    {
+      self.shutdown();
+    }
+
+    fn shutdown( &mut self )
+    {
+      drop( self.stop.take() );
+      if let Some( thread ) = self.thread.take()
+      {
+        let _ = thread.join();
+      }
+    }
+  }
+
+  impl< T > Drop for WatchHandle< T >
+  {
+    fn drop( &mut self )
+    {
+      self.shutdown();
+    }
+  }
+
+  impl Workspace
+  {
+    /// watch the config `name` for changes
+    ///
+    /// the watcher looks at the same files as `find_config`. call `load` or `spawn`
+    /// on it to read the config for the first time.
+    #[ must_use ]
+    #[ inline ]
+    pub fn watch_config< T >( &self, name: &str ) -> ConfigWatcher< T >
+    where
+      T: serde ::de ::DeserializeOwned + Send + Sync + 'static,
+    {
+      ConfigWatcher ::new( self.clone(), name )
+    }
+  }
+}
+
+/// encrypted secret files
+///
+/// a `Vault` is a secret file encrypted at rest with [age](https://age-encryption.org).
+/// decrypted, it holds the same `KEY="value"` lines as a plaintext `-secrets.sh`
+/// file, so `age -d` recovers a file the other secret loaders understand. the key
+/// is either a passphrase or an age key file, as written by `age-keygen` or
+/// `VaultKey::generate_key_file`.
+///
+/// every write decrypts the whole file, changes it and encrypts it again under a
+/// fresh file key, then replaces the old file in one rename. the plaintext only
+/// lives in zeroized buffers and `SecretString` values.
+#[ cfg( feature = "vault" ) ]
+pub mod vault
+{
+  use super :: { Workspace, WorkspaceError, Result, AsSecure };
+  use secrecy :: { SecretString, ExposeSecret };
+  use zeroize ::Zeroizing;
+  use std ::
+  {
+    collections :: { BTreeMap, HashMap },
+    fs,
+    io :: { Read, Write },
+    path :: { Path, PathBuf },
+  };
+
+  /// key a vault is encrypted with
+  #[ derive( Clone ) ]
+  pub struct VaultKey( KeyKind );
+
+  #[ derive( Clone ) ]
+  enum KeyKind
+  {
+    Passphrase { passphrase: SecretString, work_factor: Option< u8 > },
+    Identities( Vec< age ::x25519 ::Identity > ),
+  }
+
+  impl core ::fmt ::Debug for VaultKey
+  {
+    fn fmt( &self, f: &mut core ::fmt ::Formatter< '_ > ) -> core ::fmt ::Result
+    {
+      match &self.0
+      {
+        KeyKind ::Passphrase { .. } => f.write_str( "VaultKey::Passphrase([REDACTED])" ),
+        KeyKind ::Identities( identities ) => write!( f, "VaultKey::KeyFile({} identities)", identities.len() ),
+      }
+    }
+  }
+
+  impl VaultKey
+  {
+    /// encrypt with a passphrase
+    ///
+    /// the passphrase is stretched with scrypt, tuned to take about a second on
+    /// this machine. prefer a key file for unattended services.
+    #[ must_use ]
+    #[ inline ]
+    pub fn passphrase( passphrase: impl Into< SecretString > ) -> Self
+    {
+      Self( KeyKind ::Passphrase { passphrase: passphrase.into(), work_factor: None } )
+    }
+
+    /// set the scrypt work factor of a passphrase key to `2^log_n`
+    ///
+    /// only affects encryption; files are decrypted with the work factor they were
+    /// written with. has no effect on a key file.
+    ///
+    /// # Errors
+    ///
+    /// returns error if `log_n` is not in `1..64`
+    pub fn work_factor( mut self, log_n: u8 ) -> Result< Self >
+    {
+      if !( 1..64 ).contains( &log_n )
+      {
+        return Err( WorkspaceError::VaultError( format!( "scrypt work factor must be in 1..64, got {log_n}" ) ) );
+      }
+      if let KeyKind ::Passphrase { work_factor, .. } = &mut self.0
+      {
+        *work_factor = Some( log_n );
+      }
+      Ok( self )
+    }
+
+    /// read an age key file
+    ///
+    /// the file holds one `AGE-SECRET-KEY-1...` identity per line; empty lines and
+    /// `#` comments are skipped. a vault is encrypted to every identity in the file
+    /// and opens with any of them.
+    ///
+    /// # Errors
+    ///
+    /// returns error if the file cannot be read or holds no valid identity
+    pub fn key_file< P: AsRef< Path > >( path: P ) -> Result< Self >
+    {
+      let path = path.as_ref();
+      let content = Zeroizing ::new( fs ::read_to_string( path )
+        .map_err( | e | WorkspaceError::IoError( format!( "failed to read key file {}: {}", path.display(), e ) ) )? );
+      let mut identities = Vec ::new();
+      for ( number, line ) in content.lines().enumerate()
+      {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with( '#' )
+        {
+          continue;
+        }
+        // the line is not echoed, it is a secret key
+        let identity = line.parse().map_err( | e | WorkspaceError::VaultError(
+          format!( "key file {} has an invalid identity on line {}: {}", path.display(), number + 1, e )
+        ) )?;
+        identities.push( identity );
+      }
+      if identities.is_empty()
+      {
+        return Err( WorkspaceError::VaultError( format!( "key file {} holds no identity", path.display() ) ) );
+      }
+      Ok( Self( KeyKind ::Identities( identities ) ) )
+    }
+
+    /// create a new age key file at `path` and return its key
+    ///
+    /// the file is readable by its owner only and is never overwritten
+    ///
+    /// # Errors
+    ///
+    /// returns error if `path` already exists or cannot be written
+    pub fn generate_key_file< P: AsRef< Path > >( path: P ) -> Result< Self >
+    {
+      let path = path.as_ref();
+      let identity = age ::x25519 ::Identity ::generate();
+      let content = Zeroizing ::new( format!(
+        "# public key: {}\n{}\n",
+        identity.to_public(),
+        identity.to_string().expose_secret()
+      ) );
+      if let Some( parent ) = path.parent()
+      {
+        fs ::create_dir_all( parent )
+          .map_err( | e | WorkspaceError::IoError( format!( "failed to create {}: {}", parent.display(), e ) ) )?;
+      }
+      let mut file = private_file( fs ::OpenOptions ::new().write( true ).create_new( true ) )
+        .open( path )
+        .map_err( | e | WorkspaceError::IoError( format!( "failed to create key file {}: {}", path.display(), e ) ) )?;
+      file.write_all( content.as_bytes() )
+        .map_err( | e | WorkspaceError::IoError( format!( "failed to write key file {}: {}", path.display(), e ) ) )?;
+      Ok( Self( KeyKind ::Identities( vec![ identity ] ) ) )
+    }
+
+    /// public keys of a key file, to share with whoever should encrypt for it
+    ///
+    /// empty for a passphrase
+    #[ must_use ]
+    pub fn recipients( &self ) -> Vec< String >
+    {
+      match &self.0
+      {
+        KeyKind ::Passphrase { .. } => Vec ::new(),
+        KeyKind ::Identities( identities ) => identities.iter().map( | identity | identity.to_public().to_string() ).collect(),
+      }
+    }
+
+    fn encrypt( &self, plaintext: &[ u8 ] ) -> Result< Vec< u8 > >
+    {
+      let encryptor = match &self.0
+      {
+        KeyKind ::Passphrase { passphrase, work_factor } =>
+        {
+          let mut recipient = age ::scrypt ::Recipient ::new( passphrase.clone() );
+          if let Some( log_n ) = work_factor
+          {
+            recipient.set_work_factor( *log_n );
+          }
+          age ::Encryptor ::with_recipients( core ::iter ::once( &recipient as &dyn age ::Recipient ) )
+        }
+        KeyKind ::Identities( identities ) =>
+        {
+          let recipients: Vec< _ > = identities.iter().map( age ::x25519 ::Identity ::to_public ).collect();
+          age ::Encryptor ::with_recipients( recipients.iter().map( | recipient | recipient as &dyn age ::Recipient ) )
+        }
+      }.map_err( | e | WorkspaceError::VaultError( format!( "failed to encrypt: {e}" ) ) )?;
+
+      let mut encrypted = Vec ::new();
+      let mut writer = encryptor.wrap_output( &mut encrypted )
+        .map_err( | e | WorkspaceError::VaultError( format!( "failed to encrypt: {e}" ) ) )?;
+      writer.write_all( plaintext )
+        .and_then( | () | writer.finish() )
+        .map_err( | e | WorkspaceError::VaultError( format!( "failed to encrypt: {e}" ) ) )?;
+      Ok( encrypted )
+    }
+
+    fn decrypt( &self, encrypted: &[ u8 ] ) -> Result< Zeroizing< Vec< u8 > > >
+    {
+      let failed = | e: &dyn core ::fmt ::Display | WorkspaceError::VaultError( format!( "failed to decrypt: {e}" ) );
+      let decryptor = age ::Decryptor ::new_buffered( encrypted ).map_err( | e | failed( &e ) )?;
+      let mut reader = match &self.0
+      {
+        KeyKind ::Passphrase { passphrase, .. } =>
+        {
+          let identity = age ::scrypt ::Identity ::new( passphrase.clone() );
+          decryptor.decrypt( core ::iter ::once( &identity as &dyn age ::Identity ) )
+        }
+        KeyKind ::Identities( identities ) =>
+        decryptor.decrypt( identities.iter().map( | identity | identity as &dyn age ::Identity ) ),
+      }.map_err( | e | failed( &e ) )?;
+
+      let mut plaintext = Zeroizing ::new( Vec ::new() );
+      reader.read_to_end( &mut plaintext ).map_err( | e | failed( &e ) )?;
+      Ok( plaintext )
+    }
+  }
+
+  /// owner-only permissions for files holding keys or secrets
+  fn private_file( options: &mut fs ::OpenOptions ) -> &mut fs ::OpenOptions
+  {
+    #[ cfg( unix ) ]
+    {
+      use std ::os ::unix ::fs ::OpenOptionsExt;
+      options.mode( 0o600 );
+    }
+    options
+  }
+
+  /// an encrypted secret file
+  #[ derive( Debug, Clone ) ]
+  pub struct Vault
+  {
+    path: PathBuf,
+    key: VaultKey,
+  }
+
+  impl Vault
+  {
+    /// vault at `path`, encrypted with `key`
+    ///
+    /// nothing is read until the vault is used; `set` creates the file
+    #[ must_use ]
+    #[ inline ]
+    pub fn new< P: Into< PathBuf > >( path: P, key: VaultKey ) -> Self
+    {
+      Self { path: path.into(), key }
+    }
+
+    /// path of the encrypted file
+    #[ must_use ]
+    #[ inline ]
+    pub fn path( &self ) -> &Path
+    {
+      &self.path
+    }
+
+    /// whether the encrypted file exists
+    #[ must_use ]
+    #[ inline ]
+    pub fn exists( &self ) -> bool
+    {
+      self.path.is_file()
+    }
+
+    /// decrypt all secrets
+    ///
+    /// # Errors
+    ///
+    /// returns `PathNotFound` if the file does not exist and `VaultError` if the
+    /// key does not open it or it is not an age file
+    pub fn load( &self ) -> Result< HashMap< String, SecretString > >
+    {
+      Ok( self.read()?.into_iter().collect() )
+    }
+
+    /// decrypt one secret, `None` if the vault does not hold `key`
+    ///
+    /// # Errors
+    ///
+    /// returns error if the vault cannot be decrypted
+    pub fn get( &self, key: &str ) -> Result< Option< SecretString > >
+    {
+      Ok( self.read()?.remove( key ) )
+    }
+
+    /// sorted names of the secrets, without their values
+    ///
+    /// # Errors
+    ///
+    /// returns error if the vault cannot be decrypted
+    pub fn list( &self ) -> Result< Vec< String > >
+    {
+      Ok( self.read()?.into_keys().collect() )
+    }
+
+    /// add or replace the secret `key`, creating the vault if it does not exist
+    ///
+    /// # Errors
+    ///
+    /// returns `SecretValidationError` if `key` or `value` cannot be stored as a
+    /// `KEY="value"` line, or error if the vault cannot be decrypted or written
+    pub fn set( &self, key: &str, value: impl Into< SecretString > ) -> Result< () >
+    {
+      let value = value.into();
+      validate_entry( key, &value )?;
+      let mut secrets = if self.path.exists() { self.read()? } else { BTreeMap ::new() };
+      secrets.insert( key.to_string(), value );
+      self.write( &secrets )
+    }
+
+    /// remove the secret `key`, returning whether it was present
+    ///
+    /// # Errors
+    ///
+    /// returns error if the vault cannot be decrypted or written
+    pub fn remove( &self, key: &str ) -> Result< bool >
+    {
+      let mut secrets = self.read()?;
+      if secrets.remove( key ).is_none()
+      {
+        return Ok( false );
+      }
+      self.write( &secrets )?;
+      Ok( true )
+    }
+
+    /// re-encrypt the vault with `new_key`, which is used from then on
+    ///
+    /// rotating to the same key still replaces the file key
+    ///
+    /// # Errors
+    ///
+    /// returns error if the vault cannot be decrypted with the current key or written
+    pub fn rotate( &mut self, new_key: VaultKey ) -> Result< () >
+    {
+      let secrets = self.read()?;
+      self.key = new_key;
+      self.write( &secrets )
+    }
+
+    fn read( &self ) -> Result< BTreeMap< String, SecretString > >
+    {
+      let encrypted = match fs ::read( &self.path )
+      {
+        Ok( encrypted ) => encrypted,
+        Err( e ) if e.kind() == std ::io ::ErrorKind ::NotFound => return Err( WorkspaceError::PathNotFound( self.path.clone() ) ),
+        Err( e ) => return Err( WorkspaceError::IoError( format!( "failed to read vault {}: {}", self.path.display(), e ) ) ),
+      };
+      let plaintext = self.key.decrypt( &encrypted )?;
+      let content = core ::str ::from_utf8( &plaintext )
+        .map_err( | _ | WorkspaceError::VaultError( format!( "vault {} does not hold text", self.path.display() ) ) )?;
+      Ok( Workspace ::parse_key_value_file( content ).into_secure().into_iter().collect() )
+    }
+
+    fn write( &self, secrets: &BTreeMap< String, SecretString > ) -> Result< () >
+    {
+      let mut plaintext = Zeroizing ::new( String ::new() );
+      for ( key, value ) in secrets
+      {
+        plaintext.push_str( key );
+        plaintext.push_str( "=\"" );
+        plaintext.push_str( value.expose_secret() );
+        plaintext.push_str( "\"\n" );
+      }
+      let encrypted = self.key.encrypt( plaintext.as_bytes() )?;
+
+      let io_error = | e: std ::io ::Error | WorkspaceError::IoError( format!( "failed to write vault {}: {}", self.path.display(), e ) );
+      let name = self.path.file_name()
+        .ok_or_else( || WorkspaceError::VaultError( format!( "vault path {} has no file name", self.path.display() ) ) )?;
+      let mut temp_name = std ::ffi ::OsString ::from( "." );
+      temp_name.push( name );
+      temp_name.push( ".tmp" );
+      let temp = self.path.with_file_name( temp_name );
+      if let Some( parent ) = self.path.parent()
+      {
+        fs ::create_dir_all( parent ).map_err( io_error )?;
+      }
+      let written = private_file( fs ::OpenOptions ::new().write( true ).create( true ).truncate( true ) )
+        .open( &temp )
+        .and_then( | mut file | { file.write_all( &encrypted )?; file.sync_all() } )
+        .and_then( | () | fs ::rename( &temp, &self.path ) );
+      if let Err( e ) = written
+      {
+        let _ = fs ::remove_file( &temp );
+        return Err( io_error( e ) );
+      }
+      Ok( () )
+    }
+  }
+
+  /// check that the pair survives a round trip through a `KEY="value"` line
+  fn validate_entry( key: &str, value: &SecretString ) -> Result< () >
+  {
+    if key.is_empty() || key.starts_with( '#' ) || key.contains( '=' ) || key.chars().any( | c | c.is_whitespace() || c.is_control() )
+    {
+      return Err( WorkspaceError::SecretValidationError(
+        format!( "'{key}' is not a valid secret name: it must be non-empty, not start with '#' and hold no '=', whitespace or control characters" )
+      ) );
+    }
+    if value.expose_secret().contains( [ '\n', '\r' ] )
+    {
+      return Err( WorkspaceError::SecretValidationError( format!( "value of '{key}' spans several lines" ) ) );
+    }
+    Ok( () )
+  }
+
+  impl Workspace
+  {
+    /// vault `name` in the secrets directory, encrypted with `key`
+    ///
+    /// returns a vault for `workspace_root/secret/{name}`; by convention the name
+    /// ends in `.age`
+    #[ must_use ]
+    #[ inline ]
+    pub fn secret_vault( &self, name: &str, key: VaultKey ) -> Vault
+    {
+      Vault ::new( self.secret_file( name ), key )
+    }
+
+    /// encrypt the plaintext secret file `filename` into the new vault `vault_name`
+    ///
+    /// only `workspace_root/secret/{filename}` is read, not the `$PRO` and `$HOME`
+    /// fallbacks. the vault is decrypted again and compared before returning. the
+    /// plaintext file is left in place: delete it once the vault is in use.
+    ///
+    /// # Errors
+    ///
+    /// returns error if the plaintext file cannot be read, a secret cannot be stored,
+    /// the vault already exists or cannot be written
+    pub fn migrate_secrets_to_vault( &self, filename: &str, vault_name: &str, key: VaultKey ) -> Result< Vault >
+    {
+      let vault = self.secret_vault( vault_name, key );
+      if vault.path.exists()
+      {
+        return Err( WorkspaceError::VaultError( format!( "vault {} already exists", vault.path.display() ) ) );
+      }
+      let content = Zeroizing ::new( Self ::read_secret_file_validated( &self.secret_file( filename ) )? );
+      let secrets: BTreeMap< String, SecretString > = Self ::parse_key_value_file( &content ).into_secure().into_iter().collect();
+      for ( key, value ) in &secrets
+      {
+        validate_entry( key, value )?;
+      }
+      vault.write( &secrets )?;
+
+      let stored = vault.read()?;
+      let same = stored.len() == secrets.len()
+        && stored.iter().zip( &secrets ).all( | ( ( a, x ), ( b, y ) ) | a == b && x.expose_secret() == y.expose_secret() );
+      if !same
+      {
+        return Err( WorkspaceError::VaultError( format!( "vault {} does not match {}", vault.path.display(), filename ) ) );
+      }
+      Ok( vault )
+    }
+  }
+}
+
 /// convenience function to get workspace instance with extended fallbacks
 ///
 /// uses `Workspace ::resolve_with_extended_fallbacks()` which tries multiple
@@ -0,0 +1,186 @@
+//! Config Watch Tests
+//!
+//! These tests verify hot-reload of workspace configs: change detection, debounce,
+//! keeping the last good config on errors, validation, secret injection and the
+//! background thread.
+
+#![ cfg( feature = "watch" ) ]
+
+use workspace_tools :: { Workspace, WorkspaceError, watch :: { ConfigEvent, MemoryBackend } };
+use serde ::Deserialize;
+use core ::time ::Duration;
+
+#[ derive( Debug, Clone, Deserialize, PartialEq ) ]
+#[ cfg_attr( feature = "validation", derive( schemars ::JsonSchema ) ) ]
+struct AppConfig
+{
+  name: String,
+  port: u16,
+  #[ serde( default ) ]
+  token: Option< String >,
+}
+
+/// virtual workspace with `config/app.toml` in memory
+fn setup( content: &str ) -> ( Workspace, MemoryBackend )
+{
+  let workspace = Workspace ::new( "/virtual/workspace" );
+  let files = MemoryBackend ::new();
+  files.write( workspace.config_dir().join( "app.toml" ), content );
+  ( workspace, files )
+}
+
+fn reloaded( event: Option< ConfigEvent< AppConfig > > ) -> std ::sync ::Arc< AppConfig >
+{
+  match event
+  {
+    Some( ConfigEvent ::Reloaded( config ) ) => config,
+    other => panic!( "expected a reload, got {other:?}" ),
+  }
+}
+
+/// Test that a changed file is reloaded and an unchanged one is not
+#[ test ]
+fn test_change_is_reloaded()
+{
+  let ( workspace, files ) = setup( "name = \"app\"\nport = 80" );
+  let mut watcher = workspace.watch_config ::< AppConfig >( "app" ).backend( files.clone() ).debounce( Duration ::ZERO );
+
+  assert_eq!( watcher.load().unwrap().port, 80 );
+  assert!( watcher.poll().is_none() );
+
+  files.write( workspace.config_dir().join( "app.toml" ), "name = \"app\"\nport = 8080" );
+  assert_eq!( reloaded( watcher.poll() ).port, 8080 );
+  assert_eq!( watcher.current().unwrap().port, 8080 );
+  assert!( watcher.poll().is_none() );
+}
+
+/// Test that reloading waits until the files stop changing
+#[ test ]
+fn test_debounce_waits_for_quiet_period()
+{
+  let ( workspace, files ) = setup( "name = \"app\"\nport = 1" );
+  let path = workspace.config_dir().join( "app.toml" );
+  let mut watcher = workspace.watch_config ::< AppConfig >( "app" ).backend( files.clone() ).debounce( Duration ::from_millis( 300 ) );
+  watcher.load().unwrap();
+
+  files.write( &path, "name = \"app\"\nport = 2" );
+  assert!( watcher.poll().is_none() );
+  std ::thread ::sleep( Duration ::from_millis( 150 ) );
+  files.write( &path, "name = \"app\"\nport = 3" );
+  assert!( watcher.poll().is_none() );
+  std ::thread ::sleep( Duration ::from_millis( 150 ) );
+  assert!( watcher.poll().is_none(), "the second write restarts the debounce period" );
+  std ::thread ::sleep( Duration ::from_millis( 200 ) );
+  assert_eq!( reloaded( watcher.poll() ).port, 3 );
+}
+
+/// Test that a broken or removed file is reported and the last good config kept
+#[ test ]
+fn test_failed_reload_keeps_last_good_config()
+{
+  let ( workspace, files ) = setup( "name = \"app\"\nport = 80" );
+  let path = workspace.config_dir().join( "app.toml" );
+  let mut watcher = workspace.watch_config ::< AppConfig >( "app" ).backend( files.clone() ).debounce( Duration ::ZERO );
+  watcher.load().unwrap();
+
+  files.write( &path, "name = \"app\"\nport = \"eighty\"" );
+  assert!( matches!( watcher.poll(), Some( ConfigEvent ::Failed( WorkspaceError ::SerdeError( _ ) ) ) ) );
+  assert_eq!( watcher.current().unwrap().port, 80 );
+  assert!( watcher.last_error().is_some() );
+
+  files.remove( &path );
+  assert!( matches!( watcher.poll(), Some( ConfigEvent ::Failed( WorkspaceError ::PathNotFound( _ ) ) ) ) );
+  assert_eq!( watcher.current().unwrap().port, 80 );
+
+  files.write( workspace.config_dir().join( "app.json" ), r#"{ "name": "app", "port": 81 }"# );
+  assert_eq!( reloaded( watcher.poll() ).port, 81, "another find_config candidate takes over" );
+  assert!( watcher.last_error().is_none() );
+}
+
+/// Test that the initial load reports a missing config
+#[ test ]
+fn test_initial_load_requires_config()
+{
+  let workspace = Workspace ::new( "/virtual/workspace" );
+  let mut watcher = workspace.watch_config ::< AppConfig >( "app" ).backend( MemoryBackend ::new() );
+
+  assert!( matches!( watcher.load(), Err( WorkspaceError ::PathNotFound( _ ) ) ) );
+  assert!( watcher.current().is_none() );
+}
+
+/// Test that reloads are validated against the schema of the config type
+#[ test ]
+#[ cfg( feature = "validation" ) ]
+fn test_reload_is_validated()
+{
+  let ( workspace, files ) = setup( "name = \"app\"\nport = 80" );
+  let mut watcher = workspace.watch_config ::< AppConfig >( "app" )
+    .backend( files.clone() )
+    .debounce( Duration ::ZERO )
+    .with_validation()
+    .unwrap();
+  watcher.load().unwrap();
+
+  files.write( workspace.config_dir().join( "app.toml" ), "name = \"app\"\nport = 70000" );
+  match watcher.poll()
+  {
+    Some( ConfigEvent ::Failed( WorkspaceError ::ValidationError( message ) ) ) => assert!( message.contains( "/port" ), "{message}" ),
+    other => panic!( "expected a validation error, got {other:?}" ),
+  }
+  assert_eq!( watcher.current().unwrap().port, 80 );
+}
+
+/// Test that a changed secret file reloads the config with the new secrets
+#[ test ]
+#[ cfg( feature = "secrets" ) ]
+fn test_secret_change_is_injected()
+{
+  let ( workspace, files ) = setup( "name = \"app\"\nport = 80" );
+  let secret = workspace.secret_file( "-secrets.sh" );
+  files.write( &secret, "API_TOKEN=first" );
+  let mut watcher = workspace.watch_config ::< AppConfig >( "app" )
+    .backend( files.clone() )
+    .debounce( Duration ::ZERO )
+    .with_secrets( "-secrets.sh", | config: &mut AppConfig, secrets |
+    {
+      config.token = Some( secrets.get( "API_TOKEN" ).cloned().ok_or_else( || WorkspaceError::ConfigurationError( "API_TOKEN missing".into() ) )? );
+      Ok( () )
+    } );
+  assert_eq!( watcher.load().unwrap().token.as_deref(), Some( "first" ) );
+
+  files.write( &secret, "export API_TOKEN=\"second\"" );
+  assert_eq!( reloaded( watcher.poll() ).token.as_deref(), Some( "second" ) );
+
+  files.write( &secret, "OTHER=1" );
+  assert!( matches!( watcher.poll(), Some( ConfigEvent ::Failed( WorkspaceError ::ConfigurationError( _ ) ) ) ) );
+  assert_eq!( watcher.current().unwrap().token.as_deref(), Some( "second" ) );
+}
+
+/// Test the background thread against real files
+#[ test ]
+#[ cfg( feature = "testing" ) ]
+fn test_spawned_watcher_reloads_files()
+{
+  let ( _temp_dir, workspace ) = workspace_tools ::testing ::create_test_workspace_with_structure();
+  let path = workspace.config_dir().join( "app.yaml" );
+  std ::fs ::write( &path, "name: app\nport: 80\n" ).unwrap();
+
+  let ( sender, events ) = std ::sync ::mpsc ::channel();
+  let handle = workspace.watch_config ::< AppConfig >( "app" )
+    .interval( Duration ::from_millis( 10 ) )
+    .debounce( Duration ::from_millis( 20 ) )
+    .spawn( move | event | { let _ = sender.send( matches!( event, ConfigEvent ::Reloaded( _ ) ) ); } )
+    .unwrap();
+  assert_eq!( handle.current().port, 80 );
+
+  std ::fs ::write( &path, "name: app\nport: [\n" ).unwrap();
+  assert!( !events.recv_timeout( Duration ::from_secs( 5 ) ).unwrap() );
+  assert!( handle.last_error().is_some() );
+  assert_eq!( handle.current().port, 80 );
+
+  std ::fs ::write( &path, "name: app\nport: 81\n" ).unwrap();
+  assert!( events.recv_timeout( Duration ::from_secs( 5 ) ).unwrap() );
+  assert_eq!( handle.current().port, 81 );
+  assert!( handle.last_error().is_none() );
+  handle.stop();
+}
@@ -0,0 +1,135 @@
+//! Vault Tests
+//!
+//! These tests verify the age-encrypted secret store: set, list, remove, key
+//! rotation, passphrase and key-file keys, and migration from plaintext files.
+
+#![ cfg( all( feature = "vault", feature = "testing" ) ) ]
+
+use std ::fs;
+use workspace_tools :: { testing, WorkspaceError, vault :: { Vault, VaultKey } };
+use secrecy ::ExposeSecret;
+
+/// cheap passphrase key, so tests do not spend a second per scrypt call
+fn passphrase( text: &str ) -> VaultKey
+{
+  VaultKey ::passphrase( text ).work_factor( 10 ).unwrap()
+}
+
+fn exposed( vault: &Vault, key: &str ) -> Option< String >
+{
+  vault.get( key ).unwrap().map( | value | value.expose_secret().to_string() )
+}
+
+/// Test that secrets are stored encrypted and read back
+#[ test ]
+fn test_set_and_load_round_trip()
+{
+  let ( _temp_dir, workspace ) = testing ::create_test_workspace_with_structure();
+  let vault = workspace.secret_vault( "app.age", passphrase( "correct horse battery staple" ) );
+  assert!( !vault.exists() );
+
+  vault.set( "API_KEY", "sk-123" ).unwrap();
+  vault.set( "QUOTED", r#""keep" 'quotes' = and spaces "# ).unwrap();
+  vault.set( "EMPTY", "" ).unwrap();
+  vault.set( "API_KEY", "sk-456" ).unwrap();
+
+  assert!( vault.exists() );
+  assert_eq!( vault.path(), workspace.secret_file( "app.age" ) );
+  let raw = fs ::read( vault.path() ).unwrap();
+  assert!( raw.starts_with( b"age-encryption.org/v1\n" ) );
+  assert!( !raw.windows( 6 ).any( | window | window == b"sk-456" ), "values are not stored in plaintext" );
+
+  assert_eq!( vault.list().unwrap(), [ "API_KEY", "EMPTY", "QUOTED" ] );
+  assert_eq!( exposed( &vault, "API_KEY" ).as_deref(), Some( "sk-456" ) );
+  assert_eq!( exposed( &vault, "QUOTED" ).as_deref(), Some( r#""keep" 'quotes' = and spaces "# ) );
+  assert_eq!( exposed( &vault, "EMPTY" ).as_deref(), Some( "" ) );
+  assert_eq!( exposed( &vault, "MISSING" ), None );
+  assert_eq!( vault.load().unwrap().len(), 3 );
+
+  assert!( vault.remove( "EMPTY" ).unwrap() );
+  assert!( !vault.remove( "EMPTY" ).unwrap() );
+  assert_eq!( vault.list().unwrap(), [ "API_KEY", "QUOTED" ] );
+}
+
+/// Test that a wrong key, a missing vault and a plaintext file are reported
+#[ test ]
+fn test_errors()
+{
+  let ( _temp_dir, workspace ) = testing ::create_test_workspace_with_structure();
+  let vault = workspace.secret_vault( "app.age", passphrase( "right" ) );
+  assert!( matches!( vault.load(), Err( WorkspaceError ::PathNotFound( _ ) ) ) );
+  vault.set( "KEY", "value" ).unwrap();
+
+  let wrong = workspace.secret_vault( "app.age", passphrase( "wrong" ) );
+  assert!( matches!( wrong.list(), Err( WorkspaceError ::VaultError( _ ) ) ) );
+  assert!( matches!( wrong.set( "OTHER", "x" ), Err( WorkspaceError ::VaultError( _ ) ) ) );
+  assert_eq!( exposed( &vault, "KEY" ).as_deref(), Some( "value" ), "a failed write leaves the vault intact" );
+
+  fs ::write( workspace.secret_file( "-secrets.sh" ), "KEY=value" ).unwrap();
+  let plaintext = workspace.secret_vault( "-secrets.sh", passphrase( "right" ) );
+  assert!( matches!( plaintext.load(), Err( WorkspaceError ::VaultError( _ ) ) ) );
+
+  for ( key, value ) in [ ( "", "x" ), ( "A B", "x" ), ( "A=B", "x" ), ( "#A", "x" ), ( "A", "two\nlines" ) ]
+  {
+    assert!( matches!( vault.set( key, value ), Err( WorkspaceError ::SecretValidationError( _ ) ) ), "{key:?}" );
+  }
+  assert!( VaultKey ::passphrase( "x" ).work_factor( 0 ).is_err() );
+}
+
+/// Test key files and rotation between keys
+#[ test ]
+fn test_key_file_and_rotation()
+{
+  let ( _temp_dir, workspace ) = testing ::create_test_workspace_with_structure();
+  let key_path = workspace.secret_file( "vault.key" );
+  let key = VaultKey ::generate_key_file( &key_path ).unwrap();
+  assert_eq!( key.recipients().len(), 1 );
+  assert!( key.recipients()[ 0 ].starts_with( "age1" ) );
+  assert!( VaultKey ::generate_key_file( &key_path ).is_err(), "key files are never overwritten" );
+  #[ cfg( unix ) ]
+  {
+    use std ::os ::unix ::fs ::PermissionsExt;
+    assert_eq!( fs ::metadata( &key_path ).unwrap().permissions().mode() & 0o777, 0o600 );
+  }
+
+  let mut vault = workspace.secret_vault( "app.age", key );
+  vault.set( "TOKEN", "t-1" ).unwrap();
+  let reopened = workspace.secret_vault( "app.age", VaultKey ::key_file( &key_path ).unwrap() );
+  assert_eq!( exposed( &reopened, "TOKEN" ).as_deref(), Some( "t-1" ) );
+
+  vault.rotate( passphrase( "new passphrase" ) ).unwrap();
+  assert!( reopened.list().is_err(), "the old key no longer opens the vault" );
+  let rotated = workspace.secret_vault( "app.age", passphrase( "new passphrase" ) );
+  assert_eq!( exposed( &rotated, "TOKEN" ).as_deref(), Some( "t-1" ) );
+
+  let other = workspace.secret_file( "other.key" );
+  fs ::write( &other, "# comment\nnot a key\n" ).unwrap();
+  assert!( matches!( VaultKey ::key_file( &other ), Err( WorkspaceError ::VaultError( _ ) ) ) );
+  fs ::write( &other, "# only comments\n" ).unwrap();
+  assert!( matches!( VaultKey ::key_file( &other ), Err( WorkspaceError ::VaultError( _ ) ) ) );
+}
+
+/// Test migration of a plaintext secret file into a vault
+#[ test ]
+fn test_migrate_plaintext_file()
+{
+  let ( _temp_dir, workspace ) = testing ::create_test_workspace_with_structure();
+  fs ::write(
+    workspace.secret_file( "-secrets.sh" ),
+    "# api\nexport API_KEY=\"sk-123\"\nDATABASE_URL='postgres://localhost/db'\n\nEMPTY=\n",
+  ).unwrap();
+  let plaintext = workspace.load_secrets_from_file( "-secrets.sh" ).unwrap();
+
+  let vault = workspace.migrate_secrets_to_vault( "-secrets.sh", "secrets.age", passphrase( "migrate" ) ).unwrap();
+  let migrated = vault.load().unwrap();
+  assert_eq!( migrated.len(), plaintext.len() );
+  for ( key, value ) in &plaintext
+  {
+    assert_eq!( migrated[ key ].expose_secret(), value, "{key}" );
+  }
+  assert!( workspace.secrets_file_exists( "-secrets.sh" ), "the plaintext file is left for the caller" );
+
+  let again = workspace.migrate_secrets_to_vault( "-secrets.sh", "secrets.age", passphrase( "migrate" ) );
+  assert!( matches!( again, Err( WorkspaceError ::VaultError( _ ) ) ), "an existing vault is not overwritten" );
+  assert!( workspace.migrate_secrets_to_vault( "-missing.sh", "other.age", passphrase( "migrate" ) ).is_err() );
+}
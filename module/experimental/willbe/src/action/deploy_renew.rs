@@ -101,6 +101,7 @@ mod private
    is_mandatory: true,
    default_value: None,
    description: None,
+   kind: None,
  }
  );
    archive.add_parameter
@@ -111,6 +112,7 @@ mod private
    is_mandatory: false,
    default_value: None,
    description: None,
+   kind: None,
  }
  );
    archive.add_parameter
@@ -121,6 +123,7 @@ mod private
    is_mandatory: false,
    default_value: None,
    description: None,
+   kind: None,
  }
  );
    archive.add_parameter
@@ -131,6 +134,7 @@ mod private
    is_mandatory: false,
    default_value: None,
    description: None,
+   kind: None,
  }
  );
 
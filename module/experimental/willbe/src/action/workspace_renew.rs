@@ -23,6 +23,7 @@
 //!   is_mandatory: true,
 //!   default_value: None,
 //!   description: None,
+//!   kind: None,
 //! });
 //! ```
 //!
@@ -108,6 +109,7 @@ mod private
    is_mandatory: true,
    default_value: None,
    description: None,
+   kind: None,
  }
  );
    archive.add_parameter
@@ -118,6 +120,7 @@ mod private
    is_mandatory: true,
    default_value: None,
    description: None,
+   kind: None,
  }
  );
    archive.add_parameter
@@ -128,6 +131,7 @@ mod private
    is_mandatory: true,
    default_value: None,
    description: None,
+   kind: None,
  }
  );
 
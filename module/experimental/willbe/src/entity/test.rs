@@ -403,6 +403,7 @@ mod private
    err: e.to_string(),
    current_path: path.as_ref().to_path_buf(),
    error: Err( format_err!( "{e}" ) ),
+   ..Default ::default()
   })?;
   let ( program, args ) = ( "rustup", options.as_rustup_args( &toolchain ) );
 
@@ -417,6 +418,7 @@ mod private
    err: String ::new(),
    current_path: path.as_ref().to_path_buf(),
    error: Ok( () ),
+   ..Default ::default()
  }
  )
  }
@@ -125,6 +125,7 @@ mod private
    err: String ::new(),
    current_path: args.path.clone(),
    error: Ok( () ),
+   ..Default ::default()
  }
  )
  }
@@ -196,6 +197,7 @@ mod private
   err: String ::new(),
   current_path: args.path.clone(),
   error: Ok( () ),
+  ..Default ::default()
  }
  )
  }
@@ -53,6 +53,7 @@ mod private
    err: String ::new(),
    current_path: path.as_ref().to_path_buf(),
    error: Ok( () ),
+   ..Default ::default()
  }
  )
  }
@@ -108,6 +109,7 @@ mod private
    err: String ::new(),
    current_path: path.as_ref().to_path_buf(),
    error: Ok( () ),
+   ..Default ::default()
  }
  )
  }
@@ -153,6 +155,7 @@ mod private
    err: String ::new(),
    current_path: path.as_ref().to_path_buf(),
    error: Ok( () ),
+   ..Default ::default()
  }
  )
  }
@@ -208,6 +211,7 @@ mod private
    err: String ::new(),
    current_path: path.as_ref().to_path_buf(),
    error: Ok( () ),
+   ..Default ::default()
  }
  )
  }